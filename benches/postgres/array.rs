@@ -0,0 +1,53 @@
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::{criterion_group, criterion_main};
+
+use sqlx::postgres::{PgRow, Postgres};
+use sqlx::types::Uuid;
+use sqlx::Row;
+use sqlx_test::new;
+
+const LEN: i32 = 10_000;
+
+async fn fetch_arrays() -> PgRow {
+    let mut conn = new::<Postgres>().await.unwrap();
+
+    sqlx::query(
+        r#"
+        SELECT
+            array_agg(md5(i::text)::uuid) AS uuids,
+            array_agg(i) AS ints,
+            array_agg(i::int8) AS bigints,
+            array_agg(i::float8) AS floats
+        FROM generate_series(1, $1) AS i
+        "#,
+    )
+    .bind(LEN)
+    .fetch_one(&mut conn)
+    .await
+    .unwrap()
+}
+
+fn decode_arrays(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let row = runtime.block_on(fetch_arrays());
+
+    c.bench_with_input(BenchmarkId::new("decode", "uuid[]"), &row, |b, row| {
+        b.iter(|| row.get::<Vec<Uuid>, _>("uuids"));
+    });
+
+    c.bench_with_input(BenchmarkId::new("decode", "int4[]"), &row, |b, row| {
+        b.iter(|| row.get::<Vec<i32>, _>("ints"));
+    });
+
+    c.bench_with_input(BenchmarkId::new("decode", "int8[]"), &row, |b, row| {
+        b.iter(|| row.get::<Vec<i64>, _>("bigints"));
+    });
+
+    c.bench_with_input(BenchmarkId::new("decode", "float8[]"), &row, |b, row| {
+        b.iter(|| row.get::<Vec<f64>, _>("floats"));
+    });
+}
+
+criterion_group!(benches, decode_arrays);
+criterion_main!(benches);
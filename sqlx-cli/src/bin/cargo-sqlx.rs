@@ -13,7 +13,11 @@ enum Cli {
 
 #[tokio::main]
 async fn main() {
-    dotenvy::dotenv().ok();
+    if let Err(error) = sqlx_cli::load_dotenv() {
+        println!("{} {}", style("error:").bold().red(), error);
+        process::exit(1);
+    }
+
     let Cli::Sqlx(opt) = Cli::parse();
 
     if let Err(error) = sqlx_cli::run(opt).await {
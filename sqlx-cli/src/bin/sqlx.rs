@@ -4,7 +4,11 @@ use sqlx_cli::Opt;
 
 #[tokio::main]
 async fn main() {
-    dotenvy::dotenv().ok();
+    if let Err(error) = sqlx_cli::load_dotenv() {
+        println!("{} {}", style("error:").bold().red(), error);
+        std::process::exit(1);
+    }
+
     // no special handling here
     if let Err(error) = sqlx_cli::run(Opt::parse()).await {
         println!("{} {}", style("error:").bold().red(), error);
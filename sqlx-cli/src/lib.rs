@@ -2,8 +2,10 @@ use std::io;
 use std::time::Duration;
 
 use anyhow::Result;
+use console::style;
 use futures::{Future, TryFutureExt};
 
+use sqlx::config::Config;
 use sqlx::{AnyConnection, Connection};
 
 use crate::opt::{Command, ConnectOpts, DatabaseCommand, MigrateCommand};
@@ -103,6 +105,44 @@ pub async fn run(opt: Opt) -> Result<()> {
     Ok(())
 }
 
+/// Load environment variables from a `.env` file, if there is one.
+///
+/// Variables that are already set in the environment take precedence over the `.env` file,
+/// except for `DATABASE_URL`, which follows `common.database-url-precedence` in `sqlx.toml`
+/// (read from the current directory). Since it is easy to miss when the environment and `.env`
+/// point `DATABASE_URL` at different databases, this prints a warning saying which one is used,
+/// or fails if the config says so.
+pub fn load_dotenv() -> Result<()> {
+    let current_dir = std::env::current_dir()?;
+    let config = Config::try_from_crate_or(current_dir)?;
+
+    let from_env = std::env::var("DATABASE_URL").ok();
+
+    let from_file = dotenvy::dotenv_iter().ok().and_then(|iter| {
+        iter.filter_map(Result::ok)
+            .find(|(key, _)| key == "DATABASE_URL")
+            .map(|(_, value)| value)
+    });
+
+    let (database_url, warning) = config
+        .common
+        .database_url_precedence
+        .choose("DATABASE_URL", from_env, from_file)
+        .map_err(anyhow::Error::msg)?;
+
+    if let Some(warning) = warning {
+        eprintln!("{} {warning}", style("warning:").yellow());
+    }
+
+    dotenvy::dotenv().ok();
+
+    if let Some(database_url) = database_url {
+        std::env::set_var("DATABASE_URL", database_url);
+    }
+
+    Ok(())
+}
+
 /// Attempt to connect to the database server, retrying up to `ops.connect_timeout`.
 async fn connect(opts: &ConnectOpts) -> anyhow::Result<AnyConnection> {
     retry_connect_errors(opts, AnyConnection::connect).await
@@ -242,6 +242,9 @@ impl Deref for Source {
 #[derive(Args, Debug)]
 pub struct ConnectOpts {
     /// Location of the DB, by default will be read from the DATABASE_URL env var or `.env` files.
+    ///
+    /// If `DATABASE_URL` is set in both, the environment variable takes precedence, unless
+    /// `common.database-url-precedence` in `sqlx.toml` says otherwise.
     #[clap(long, short = 'D', env)]
     pub database_url: Option<String>,
 
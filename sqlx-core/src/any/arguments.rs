@@ -5,6 +5,7 @@ use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::types::Type;
 
+#[derive(Clone)]
 pub struct AnyArguments<'q> {
     #[doc(hidden)]
     pub values: AnyArgumentBuffer<'q>,
@@ -28,8 +29,13 @@ impl<'q> Arguments<'q> for AnyArguments<'q> {
     fn len(&self) -> usize {
         self.values.0.len()
     }
+
+    fn try_clone(&self) -> Option<Self> {
+        Some(self.clone())
+    }
 }
 
+#[derive(Clone)]
 pub struct AnyArgumentBuffer<'q>(#[doc(hidden)] pub Vec<AnyValueKind<'q>>);
 
 impl<'q> Default for AnyArguments<'q> {
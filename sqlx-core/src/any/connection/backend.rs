@@ -9,6 +9,11 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// The backend name.
     fn name(&self) -> &str;
 
+    /// Forward to [`Connection::id()`].
+    ///
+    /// [`Connection::id()`]: method@crate::connection::Connection::id
+    fn id(&self) -> u64;
+
     /// Explicitly close this database connection.
     ///
     /// This method is **not required** for safe and consistent operation. However, it is
@@ -25,6 +30,56 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
+    /// Forward to [`Connection::reset()`].
+    ///
+    /// [`Connection::reset()`]: method@crate::connection::Connection::reset
+    fn reset(&mut self) -> BoxFuture<'_, crate::Result<()>>;
+
+    /// Forward to [`Connection::rollback_open_transaction()`].
+    ///
+    /// [`Connection::rollback_open_transaction()`]: method@crate::connection::Connection::rollback_open_transaction
+    fn rollback_open_transaction(&mut self) -> BoxFuture<'_, crate::Result<()>>;
+
+    /// Forward to [`Connection::server_max_connections()`].
+    ///
+    /// [`Connection::server_max_connections()`]: method@crate::connection::Connection::server_max_connections
+    fn server_max_connections(&mut self) -> BoxFuture<'_, crate::Result<Option<u32>>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    /// Forward to [`Connection::incremental_vacuum()`].
+    ///
+    /// [`Connection::incremental_vacuum()`]: method@crate::connection::Connection::incremental_vacuum
+    fn incremental_vacuum(&mut self, pages: u32) -> BoxFuture<'_, crate::Result<()>> {
+        let _ = pages;
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Forward to [`Connection::queries_executed()`].
+    ///
+    /// [`Connection::queries_executed()`]: method@crate::connection::Connection::queries_executed
+    fn queries_executed(&self) -> u64;
+
+    /// Forward to `Connection::query_stats_counters()`.
+    #[doc(hidden)]
+    fn query_stats_counters<'a>(
+        &'a mut self,
+        sql: &'a str,
+    ) -> BoxFuture<'a, crate::Result<crate::query::QueryStatsCounters>> {
+        let _ = sql;
+        Box::pin(async { Ok(Default::default()) })
+    }
+
+    /// Forward to `Connection::set_tag()`.
+    #[doc(hidden)]
+    fn set_tag<'a>(&'a mut self, tag: Option<&'a str>) -> BoxFuture<'a, crate::Result<()>> {
+        let _ = tag;
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Forward to `Connection::server_now_micros()`.
+    fn server_now_micros(&mut self) -> BoxFuture<'_, crate::Result<i64>>;
+
     /// Begin a new transaction or establish a savepoint within the active transaction.
     fn begin(&mut self) -> BoxFuture<'_, crate::Result<()>>;
 
@@ -56,6 +111,22 @@ pub trait AnyConnectionBackend: std::any::Any + Debug + Send + 'static {
     #[doc(hidden)]
     fn should_flush(&self) -> bool;
 
+    /// Forward to [`Connection::cancel_handle()`].
+    ///
+    /// [`Connection::cancel_handle()`]: method@crate::connection::Connection::cancel_handle
+    #[doc(hidden)]
+    fn cancel_handle(&self) -> Option<std::sync::Arc<dyn crate::connection::CancelHandle>> {
+        None
+    }
+
+    /// Forward to [`Connection::set_require_transaction_for_writes()`].
+    ///
+    /// [`Connection::set_require_transaction_for_writes()`]: method@crate::connection::Connection::set_require_transaction_for_writes
+    #[doc(hidden)]
+    fn set_require_transaction_for_writes(&mut self, require: bool) {
+        let _ = require;
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(&mut self) -> crate::Result<&mut (dyn crate::migrate::Migrate + Send + 'static)> {
         Err(crate::Error::Configuration(
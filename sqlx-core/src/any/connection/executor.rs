@@ -1,4 +1,5 @@
 use crate::any::{Any, AnyConnection, AnyQueryResult, AnyRow, AnyStatement, AnyTypeInfo};
+use crate::connection::CancelHandle;
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
@@ -7,6 +8,7 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{stream, FutureExt, StreamExt};
 use std::future;
+use std::sync::Arc;
 
 impl<'c> Executor<'c> for &'c mut AnyConnection {
     type Database = Any;
@@ -63,4 +65,8 @@ impl<'c> Executor<'c> for &'c mut AnyConnection {
     {
         self.backend.describe(sql)
     }
+
+    fn cancel_handle(&self) -> Option<Arc<dyn CancelHandle>> {
+        self.backend.cancel_handle()
+    }
 }
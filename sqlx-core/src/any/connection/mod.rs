@@ -3,6 +3,7 @@ use futures_core::future::BoxFuture;
 use crate::any::{Any, AnyConnectOptions};
 use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
+use crate::query::QueryStatsCounters;
 
 use crate::database::Database;
 pub use backend::AnyConnectionBackend;
@@ -71,6 +72,10 @@ impl Connection for AnyConnection {
 
     type Options = AnyConnectOptions;
 
+    fn id(&self) -> u64 {
+        self.backend.id()
+    }
+
     fn close(self) -> BoxFuture<'static, Result<(), Error>> {
         self.backend.close()
     }
@@ -83,6 +88,44 @@ impl Connection for AnyConnection {
         self.backend.ping()
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        self.backend.reset()
+    }
+
+    fn rollback_open_transaction(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        self.backend.rollback_open_transaction()
+    }
+
+    fn server_max_connections(&mut self) -> BoxFuture<'_, Result<Option<u32>, Error>> {
+        self.backend.server_max_connections()
+    }
+
+    fn incremental_vacuum(&mut self, pages: u32) -> BoxFuture<'_, Result<(), Error>> {
+        self.backend.incremental_vacuum(pages)
+    }
+
+    fn queries_executed(&self) -> u64 {
+        self.backend.queries_executed()
+    }
+
+    #[doc(hidden)]
+    fn query_stats_counters<'a>(
+        &'a mut self,
+        sql: &'a str,
+    ) -> BoxFuture<'a, Result<QueryStatsCounters, Error>> {
+        self.backend.query_stats_counters(sql)
+    }
+
+    #[doc(hidden)]
+    fn server_now_micros(&mut self) -> BoxFuture<'_, Result<i64, Error>> {
+        self.backend.server_now_micros()
+    }
+
+    #[doc(hidden)]
+    fn set_tag<'a>(&'a mut self, tag: Option<&'a str>) -> BoxFuture<'a, Result<(), Error>> {
+        self.backend.set_tag(tag)
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -111,4 +154,12 @@ impl Connection for AnyConnection {
     fn should_flush(&self) -> bool {
         self.backend.should_flush()
     }
+
+    fn cancel_handle(&self) -> Option<std::sync::Arc<dyn crate::connection::CancelHandle>> {
+        self.backend.cancel_handle()
+    }
+
+    fn set_require_transaction_for_writes(&mut self, require: bool) {
+        self.backend.set_require_transaction_for_writes(require);
+    }
 }
@@ -2,7 +2,7 @@ use crate::any::{
     AnyArgumentBuffer, AnyArguments, AnyColumn, AnyConnection, AnyQueryResult, AnyRow,
     AnyStatement, AnyTransactionManager, AnyTypeInfo, AnyValue, AnyValueRef,
 };
-use crate::database::{Database, HasStatementCache};
+use crate::database::{Database, HasCovariantLifetimes, HasStatementCache};
 
 /// Opaque database driver. Capable of being used in place of any SQLx database driver. The actual
 /// driver used will be selected at runtime, from the connection url.
@@ -37,3 +37,13 @@ impl Database for Any {
 
 // This _may_ be true, depending on the selected database
 impl HasStatementCache for Any {}
+
+impl HasCovariantLifetimes for Any {
+    fn shorten_arguments<'a, 'b: 'a>(arguments: Self::Arguments<'b>) -> Self::Arguments<'a> {
+        arguments
+    }
+
+    fn shorten_statement<'a, 'b: 'a>(statement: Self::Statement<'b>) -> Self::Statement<'a> {
+        statement
+    }
+}
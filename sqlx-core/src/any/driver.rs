@@ -134,6 +134,15 @@ pub(crate) fn from_url_str(url: &str) -> crate::Result<&'static AnyDriver> {
     from_url(&url.parse().map_err(Error::config)?)
 }
 
+// The name of the driver for `url`, or `None` if no installed driver handles its scheme.
+pub(crate) fn name_for_url(url: &Url) -> Option<&'static str> {
+    DRIVERS
+        .get()?
+        .iter()
+        .find(|driver| driver.url_schemes.contains(&url.scheme()))
+        .map(|driver| driver.name)
+}
+
 pub(crate) fn from_url(url: &Url) -> crate::Result<&'static AnyDriver> {
     let scheme = url.scheme();
 
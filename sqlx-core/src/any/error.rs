@@ -2,15 +2,14 @@ use std::any::type_name;
 
 use crate::any::type_info::AnyTypeInfo;
 use crate::any::Any;
-use crate::error::BoxDynError;
+use crate::error::{BoxDynError, MismatchedTypes};
 use crate::type_info::TypeInfo;
 use crate::types::Type;
 
 pub(super) fn mismatched_types<T: Type<Any>>(ty: &AnyTypeInfo) -> BoxDynError {
-    format!(
-        "mismatched types; Rust type `{}` is not compatible with SQL type `{}`",
-        type_name::<T>(),
-        ty.name()
-    )
-    .into()
+    Box::new(MismatchedTypes {
+        rust_type: type_name::<T>(),
+        rust_sql_type: None,
+        sql_type: ty.name().to_owned(),
+    })
 }
@@ -73,6 +73,38 @@ impl Migrate for AnyConnection {
         Box::pin(async { self.get_migrate()?.apply(migration).await })
     }
 
+    fn skip<'e: 'm, 'm>(
+        &'e mut self,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async { self.get_migrate()?.skip(migration).await })
+    }
+
+    fn analyze<'e: 'm, 'm>(
+        &'e mut self,
+        tables: &'m [String],
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async { self.get_migrate()?.analyze(tables).await })
+    }
+
+    fn set_statement_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<(), MigrateError>> {
+        Box::pin(async move { self.get_migrate()?.set_statement_timeout(timeout).await })
+    }
+
+    fn statement_timeout(&mut self) -> BoxFuture<'_, Result<Option<String>, MigrateError>> {
+        Box::pin(async { self.get_migrate()?.statement_timeout().await })
+    }
+
+    fn restore_statement_timeout<'e: 'm, 'm>(
+        &'e mut self,
+        previous: &'m str,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async { self.get_migrate()?.restore_statement_timeout(previous).await })
+    }
+
     fn revert<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
@@ -1,5 +1,5 @@
-use crate::any::AnyConnection;
-use crate::connection::{ConnectOptions, LogSettings};
+use crate::any::{driver, AnyConnection};
+use crate::connection::{ConnectOptions, LogSettings, SlowQueryHook};
 use crate::error::Error;
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
@@ -19,6 +19,8 @@ use url::Url;
 pub struct AnyConnectOptions {
     pub database_url: Url,
     pub log_settings: LogSettings,
+    /// Set by [`ConnectOptions::pool_name()`] and applied by drivers that support it.
+    pub pool_name: Option<String>,
 }
 impl FromStr for AnyConnectOptions {
     type Err = Error;
@@ -29,6 +31,7 @@ impl FromStr for AnyConnectOptions {
                 .parse::<Url>()
                 .map_err(|e| Error::Configuration(e.into()))?,
             log_settings: LogSettings::default(),
+            pool_name: None,
         })
     }
 }
@@ -40,6 +43,7 @@ impl ConnectOptions for AnyConnectOptions {
         Ok(AnyConnectOptions {
             database_url: url.clone(),
             log_settings: LogSettings::default(),
+            pool_name: None,
         })
     }
 
@@ -62,4 +66,66 @@ impl ConnectOptions for AnyConnectOptions {
         self.log_settings.slow_statements_duration = duration;
         self
     }
+
+    fn redact_parameters(
+        mut self,
+        f: impl Fn(usize, &dyn std::fmt::Debug) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings.redact_parameters(f);
+        self
+    }
+
+    fn redact_all_parameters(mut self) -> Self {
+        self.log_settings.redact_all_parameters();
+        self
+    }
+
+    fn on_slow_query(
+        mut self,
+        threshold: Duration,
+        f: impl Fn(&str, Duration) + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings
+            .on_slow_query(SlowQueryHook::new(threshold, f));
+        self
+    }
+
+    fn pool_name(mut self, pool_name: &str) -> Self {
+        // applied by the Postgres driver when it converts these options
+        if let Some(name) = driver::name_for_url(&self.database_url) {
+            if name != "PostgreSQL" {
+                tracing::warn!(pool_name, "{name} connections can't be labeled with a pool name");
+            }
+        }
+
+        self.pool_name = Some(pool_name.to_owned());
+        self
+    }
+
+    fn connection_id(mut self, f: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        self.log_settings.connection_id(f);
+        self
+    }
+
+    fn sql_rewriter(
+        mut self,
+        f: impl for<'a> Fn(&'a str) -> std::borrow::Cow<'a, str> + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings.sql_rewriter(f);
+        self
+    }
+
+    fn dev_auto_limit(mut self, limit: Option<usize>) -> Self {
+        self.log_settings.dev_auto_limit(limit);
+        self
+    }
+
+    #[cfg(feature = "wire-trace")]
+    fn wire_trace(
+        mut self,
+        f: impl Fn(crate::wire_trace::Direction, &[u8]) + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings.wire_trace = Some(crate::wire_trace::WireTrace::new(f));
+        self
+    }
 }
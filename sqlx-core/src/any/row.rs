@@ -1,6 +1,6 @@
 use crate::any::error::mismatched_types;
 use crate::any::{Any, AnyColumn, AnyTypeInfo, AnyTypeInfoKind, AnyValue, AnyValueKind};
-use crate::column::{Column, ColumnIndex};
+use crate::column::{column_index_by_name, Column, ColumnIndex};
 use crate::database::Database;
 use crate::decode::Decode;
 use crate::error::Error;
@@ -65,10 +65,7 @@ impl Row for AnyRow {
 
 impl<'i> ColumnIndex<AnyRow> for &'i str {
     fn index(&self, row: &AnyRow) -> Result<usize, Error> {
-        row.column_names
-            .get(*self)
-            .copied()
-            .ok_or_else(|| Error::ColumnNotFound(self.to_string()))
+        column_index_by_name(&row.column_names, self)
     }
 }
 
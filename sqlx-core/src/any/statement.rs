@@ -1,5 +1,5 @@
 use crate::any::{Any, AnyArguments, AnyColumn, AnyTypeInfo};
-use crate::column::ColumnIndex;
+use crate::column::{column_index_by_name, ColumnIndex};
 use crate::database::Database;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
@@ -53,11 +53,7 @@ impl<'q> Statement<'q> for AnyStatement<'q> {
 
 impl<'i> ColumnIndex<AnyStatement<'_>> for &'i str {
     fn index(&self, statement: &AnyStatement<'_>) -> Result<usize, Error> {
-        statement
-            .column_names
-            .get(*self)
-            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
-            .copied()
+        column_index_by_name(&statement.column_names, self)
     }
 }
 
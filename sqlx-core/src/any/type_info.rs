@@ -61,4 +61,8 @@ impl AnyTypeInfoKind {
     pub fn is_integer(&self) -> bool {
         matches!(self, SmallInt | Integer | BigInt)
     }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Real | Double)
+    }
 }
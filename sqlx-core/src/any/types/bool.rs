@@ -11,6 +11,11 @@ impl Type<Any> for bool {
             kind: AnyTypeInfoKind::Bool,
         }
     }
+
+    fn compatible(ty: &AnyTypeInfo) -> bool {
+        // SQLite and MySQL store booleans as integers
+        ty.kind() == AnyTypeInfoKind::Bool || ty.kind().is_integer()
+    }
 }
 
 impl<'q> Encode<'q, Any> for bool {
@@ -27,6 +32,9 @@ impl<'r> Decode<'r, Any> for bool {
     fn decode(value: <Any as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
         match value.kind {
             AnyValueKind::Bool(b) => Ok(b),
+            AnyValueKind::SmallInt(i) => Ok(i != 0),
+            AnyValueKind::Integer(i) => Ok(i != 0),
+            AnyValueKind::BigInt(i) => Ok(i != 0),
             other => other.unexpected(),
         }
     }
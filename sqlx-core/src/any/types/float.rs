@@ -11,6 +11,10 @@ impl Type<Any> for f32 {
             kind: AnyTypeInfoKind::Real,
         }
     }
+
+    fn compatible(ty: &AnyTypeInfo) -> bool {
+        ty.kind().is_float()
+    }
 }
 
 impl<'q> Encode<'q, Any> for f32 {
@@ -24,6 +28,8 @@ impl<'r> Decode<'r, Any> for f32 {
     fn decode(value: AnyValueRef<'r>) -> Result<Self, BoxDynError> {
         match value.kind {
             AnyValueKind::Real(r) => Ok(r),
+            // SQLite only has `DOUBLE`, so this narrowing is what its driver does too
+            AnyValueKind::Double(d) => Ok(d as f32),
             other => other.unexpected(),
         }
     }
@@ -35,6 +41,10 @@ impl Type<Any> for f64 {
             kind: AnyTypeInfoKind::Double,
         }
     }
+
+    fn compatible(ty: &AnyTypeInfo) -> bool {
+        ty.kind().is_float()
+    }
 }
 
 impl<'q> Encode<'q, Any> for f64 {
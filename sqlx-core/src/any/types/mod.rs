@@ -2,15 +2,31 @@
 //!
 //! # Types
 //!
+//! These are the types that are portable through [`Any`](crate::any::Any): each of them can be
+//! bound as a parameter and decoded from a column on every supported database.
+//!
 //! | Rust type                             | SQL type(s)                                          |
 //! |---------------------------------------|------------------------------------------------------|
-//! | `bool`                                | BOOLEAN                                              |
-//! | `i16`                                 | SMALLINT                                             |
-//! | `i32`                                 | INT                                                  |
-//! | `i64`                                 | BIGINT                                               |
-//! | `f32`                                 | FLOAT                                                |
-//! | `f64`                                 | DOUBLE                                               |
+//! | `bool`                                | BOOLEAN<sup>1</sup>                                  |
+//! | `i16`                                 | SMALLINT<sup>2</sup>                                 |
+//! | `i32`                                 | INT<sup>2</sup>                                      |
+//! | `i64`                                 | BIGINT<sup>2</sup>                                   |
+//! | `f32`                                 | FLOAT<sup>3</sup>                                    |
+//! | `f64`                                 | DOUBLE<sup>3</sup>                                   |
 //! | `&str`, [`String`]                    | VARCHAR, CHAR, TEXT                                  |
+//! | `&[u8]`, `Vec<u8>`                    | BLOB, BYTEA                                          |
+//!
+//! <sup>1</sup> SQLite and MySQL store booleans as integers, so `bool` also decodes from any
+//! integer column, with nonzero being `true`.
+//!
+//! <sup>2</sup> The integer types decode from an integer column of any width, and fail if the
+//! value does not fit. SQLite integers are always `BIGINT`.
+//!
+//! <sup>3</sup> The float types decode from either `FLOAT` or `DOUBLE`. SQLite only has
+//! `DOUBLE`, so decoding `f32` may lose precision, as it does with the SQLite driver.
+//!
+//! Other types, such as dates and times, decimals or UUIDs, have no common representation and
+//! are not supported; cast them to `TEXT` in the query to move them through `Any`.
 //!
 //! # Nullable
 //!
@@ -123,3 +123,20 @@ impl<'a> ValueRef<'a> for AnyValueRef<'a> {
         matches!(self.kind, AnyValueKind::Null(_))
     }
 }
+
+#[cfg(feature = "json")]
+impl crate::row::ValueToJson for Any {
+    fn value_to_json(value: AnyValueRef<'_>) -> Result<serde_json::Value, BoxDynError> {
+        Ok(match value.kind {
+            AnyValueKind::Null(_) => serde_json::Value::Null,
+            AnyValueKind::Bool(b) => b.into(),
+            AnyValueKind::SmallInt(i) => i.into(),
+            AnyValueKind::Integer(i) => i.into(),
+            AnyValueKind::BigInt(i) => i.into(),
+            AnyValueKind::Real(r) => crate::row::float_to_json(r.into()),
+            AnyValueKind::Double(d) => crate::row::float_to_json(d),
+            AnyValueKind::Text(t) => t.into(),
+            AnyValueKind::Blob(b) => b.into_owned().into(),
+        })
+    }
+}
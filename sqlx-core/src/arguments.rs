@@ -27,6 +27,23 @@ pub trait Arguments<'q>: Send + Sized + Default {
     fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
         writer.write_str("?")
     }
+
+    /// Returns a copy of these arguments, if the driver supports it.
+    ///
+    /// Used by the pool to re-send a query on a fresh connection.
+    #[doc(hidden)]
+    fn try_clone(&self) -> Option<Self> {
+        None
+    }
+
+    /// Returns bytes that are equal only for arguments with equal values of the same types,
+    /// if the driver supports it.
+    ///
+    /// Used by [`QueryCache`][crate::query_cache::QueryCache] to look up the results of a query.
+    #[doc(hidden)]
+    fn to_cache_key(&self) -> Option<Vec<u8>> {
+        None
+    }
 }
 
 pub trait IntoArguments<'q, DB: Database>: Sized + Send {
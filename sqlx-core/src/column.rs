@@ -1,6 +1,9 @@
 use crate::database::Database;
 use crate::error::Error;
+use crate::ext::ustr::UStr;
+use crate::HashMap;
 
+use std::collections::HashSet;
 use std::fmt::Debug;
 
 pub trait Column: 'static + Send + Sync + Debug {
@@ -82,3 +85,94 @@ macro_rules! impl_column_index_for_statement {
         }
     };
 }
+
+/// How result columns that share a name are handled when looking columns up by name.
+///
+/// Joining two tables that both have an `id` column, for example, produces two columns named
+/// `id`, and `row.try_get("id")` can only return one of them.
+///
+/// This is set in the connect options of each driver, e.g. `PgConnectOptions::duplicate_column_names()`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DuplicateColumnNames {
+    /// Looking up a duplicated name returns the last column with that name.
+    ///
+    /// This is the default.
+    #[default]
+    Last,
+
+    /// Looking up a duplicated name returns [`Error::ColumnNameAmbiguous`], so the columns must
+    /// be accessed by index instead (or given distinct aliases in the query).
+    ///
+    /// This also applies to `#[derive(FromRow)]`, which looks up fields by name.
+    Error,
+
+    /// Rename all but the first column with a given name by appending `_1`, `_2`, etc.,
+    /// so a join of two tables with an `id` column produces `id` and `id_1`.
+    ///
+    /// The new name is returned by [`Column::name()`] as well. Suffixes that would clash with
+    /// another column in the result are skipped.
+    Suffix,
+}
+
+/// Stored in a column name map in place of an ordinal when the name is ambiguous.
+const AMBIGUOUS_COLUMN: usize = usize::MAX;
+
+impl DuplicateColumnNames {
+    /// Build the map of column names to ordinals, renaming columns first if necessary.
+    #[doc(hidden)]
+    pub fn column_names<'a>(
+        self,
+        names: impl IntoIterator<Item = &'a mut UStr>,
+    ) -> HashMap<UStr, usize> {
+        let mut names: Vec<&mut UStr> = names.into_iter().collect();
+        let mut column_names = HashMap::with_capacity(names.len());
+
+        if self == DuplicateColumnNames::Suffix {
+            let mut taken = HashSet::with_capacity(names.len());
+            let mut seen = HashSet::with_capacity(names.len());
+
+            for name in &names {
+                taken.insert(UStr::clone(name));
+            }
+
+            for name in &mut names {
+                if seen.insert(UStr::clone(name)) {
+                    continue;
+                }
+
+                let suffixed = (1..)
+                    .map(|n| UStr::new(&format!("{name}_{n}")))
+                    .find(|suffixed| !taken.contains(suffixed))
+                    .expect("BUG: ran out of suffixes");
+
+                taken.insert(suffixed.clone());
+                **name = suffixed;
+            }
+        }
+
+        for (ordinal, name) in names.into_iter().enumerate() {
+            let previous = column_names.insert(name.clone(), ordinal);
+
+            if previous.is_some() && self == DuplicateColumnNames::Error {
+                column_names.insert(name.clone(), AMBIGUOUS_COLUMN);
+            }
+        }
+
+        column_names
+    }
+}
+
+/// Look up the ordinal of the column `name` in a map built by
+/// [`DuplicateColumnNames::column_names()`].
+#[doc(hidden)]
+pub fn column_index_by_name(
+    column_names: &HashMap<UStr, usize>,
+    name: &str,
+) -> Result<usize, Error> {
+    match column_names.get(name) {
+        Some(&AMBIGUOUS_COLUMN) => Err(Error::ColumnNameAmbiguous(name.into())),
+        Some(&ordinal) => Ok(ordinal),
+        None => Err(Error::ColumnNotFound(name.into())),
+    }
+}
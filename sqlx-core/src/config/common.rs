@@ -0,0 +1,121 @@
+//! The `[common]` section of `sqlx.toml`.
+
+/// Settings shared by the query macros, `sqlx-cli` and the runtime.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(default, rename_all = "kebab-case", deny_unknown_fields)
+)]
+#[non_exhaustive]
+pub struct Config {
+    /// The environment variable the query macros read the database URL from.
+    ///
+    /// Useful when one workspace talks to several databases. Defaults to `DATABASE_URL`.
+    pub database_url_var: Option<String>,
+
+    /// Which value of the database URL variable is used when the environment and `.env` set it
+    /// to different values.
+    ///
+    /// Applies to the query macros and `sqlx-cli`.
+    pub database_url_precedence: DatabaseUrlPrecedence,
+}
+
+/// The value of `common.database-url-precedence`.
+///
+/// Whichever value is used, the query macros and `sqlx-cli` warn when the two differ, unless
+/// this is `"error"`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "sqlx-toml", derive(serde::Deserialize), serde(rename_all = "kebab-case"))]
+#[non_exhaustive]
+pub enum DatabaseUrlPrecedence {
+    /// `"env"`: the environment variable is used. This is the default, and how `dotenvy`
+    /// behaves.
+    #[default]
+    Env,
+
+    /// `"dotenv"`: the value in `.env` is used.
+    Dotenv,
+
+    /// `"error"`: differing values are an error.
+    Error,
+}
+
+impl DatabaseUrlPrecedence {
+    /// Choose between the value of the variable `var` in the environment and in `.env`.
+    ///
+    /// Returns the value to use, and a warning to show if the two differ.
+    ///
+    /// ### Errors
+    /// The message to show if the two differ and this is [`DatabaseUrlPrecedence::Error`].
+    pub fn choose(
+        self,
+        var: &str,
+        from_env: Option<String>,
+        from_dotenv: Option<String>,
+    ) -> Result<(Option<String>, Option<String>), String> {
+        let (from_env, from_dotenv) = match (from_env, from_dotenv) {
+            (Some(from_env), Some(from_dotenv)) if from_env != from_dotenv => {
+                (from_env, from_dotenv)
+            }
+            (from_env, from_dotenv) => return Ok((from_env.or(from_dotenv), None)),
+        };
+
+        let conflict =
+            format!("`{var}` is set in both the environment and `.env` with different values");
+
+        match self {
+            Self::Env => Ok((
+                Some(from_env),
+                Some(format!("{conflict}; using the one from the environment")),
+            )),
+            Self::Dotenv => Ok((
+                Some(from_dotenv),
+                Some(format!("{conflict}; using the one from `.env`")),
+            )),
+            Self::Error => Err(format!(
+                "{conflict}, and `common.database-url-precedence` is \"error\""
+            )),
+        }
+    }
+}
+
+impl Config {
+    /// The name of the environment variable holding the database URL.
+    pub fn database_url_var(&self) -> &str {
+        self.database_url_var.as_deref().unwrap_or("DATABASE_URL")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DatabaseUrlPrecedence;
+
+    #[test]
+    fn it_chooses_the_database_url() {
+        let choose = |precedence: DatabaseUrlPrecedence, from_env: &str, from_dotenv: &str| {
+            let value = |s: &str| Some(s.to_owned()).filter(|s| !s.is_empty());
+            precedence.choose("DATABASE_URL", value(from_env), value(from_dotenv))
+        };
+
+        for precedence in [
+            DatabaseUrlPrecedence::Env,
+            DatabaseUrlPrecedence::Dotenv,
+            DatabaseUrlPrecedence::Error,
+        ] {
+            assert_eq!(choose(precedence, "a", ""), Ok((Some("a".into()), None)));
+            assert_eq!(choose(precedence, "", "b"), Ok((Some("b".into()), None)));
+            assert_eq!(choose(precedence, "a", "a"), Ok((Some("a".into()), None)));
+        }
+
+        let (url, warning) = choose(DatabaseUrlPrecedence::Env, "a", "b").unwrap();
+        assert_eq!(url.as_deref(), Some("a"));
+        assert!(warning.unwrap().ends_with("using the one from the environment"));
+
+        let (url, warning) = choose(DatabaseUrlPrecedence::Dotenv, "a", "b").unwrap();
+        assert_eq!(url.as_deref(), Some("b"));
+        assert!(warning.unwrap().ends_with("using the one from `.env`"));
+
+        assert!(choose(DatabaseUrlPrecedence::Error, "a", "b").is_err());
+    }
+}
@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use serde::de::{Deserializer, Error, Unexpected};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Seconds(f64),
+    Enabled(bool),
+}
+
+fn seconds<E: Error>(secs: f64) -> Result<Duration, E> {
+    Duration::try_from_secs_f64(secs)
+        .map_err(|_| E::invalid_value(Unexpected::Float(secs), &"a number of seconds"))
+}
+
+pub(super) fn duration<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    seconds(f64::deserialize(deserializer)?).map(Some)
+}
+
+pub(super) fn optional_duration<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Option<Duration>>, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Seconds(secs) => seconds(secs).map(|duration| Some(Some(duration))),
+        Value::Enabled(false) => Ok(Some(None)),
+        Value::Enabled(true) => Err(D::Error::invalid_value(
+            Unexpected::Bool(true),
+            &"a number of seconds or `false`",
+        )),
+    }
+}
@@ -0,0 +1,118 @@
+//! The `[drivers]` section of `sqlx.toml`.
+
+use crate::pool::ResetOnReturn;
+
+/// Settings that only apply to one database driver.
+///
+/// [`PoolOptions::from_config()`][crate::pool::PoolOptions::from_config] reads the table for the
+/// pool's driver and ignores the others, so one file can serve binaries using different
+/// databases:
+///
+/// ```toml
+/// [drivers.postgres]
+/// # "rollback" when running behind PgBouncer
+/// reset-on-return = "discard-all"
+///
+/// [drivers.sqlite]
+/// reset-on-return = "rollback"
+/// ```
+///
+/// Pools of the `Any` driver only learn which database they use when they connect, so they
+/// don't read this section.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(default, rename_all = "kebab-case", deny_unknown_fields)
+)]
+#[non_exhaustive]
+pub struct Config {
+    /// The `[drivers.postgres]` table.
+    pub postgres: Driver,
+
+    /// The `[drivers.mysql]` table.
+    pub mysql: Driver,
+
+    /// The `[drivers.sqlite]` table.
+    pub sqlite: Driver,
+}
+
+/// The settings of one driver in the `[drivers]` section.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(default, rename_all = "kebab-case", deny_unknown_fields)
+)]
+#[non_exhaustive]
+pub struct Driver {
+    /// See [`PoolOptions::reset_on_return()`][crate::pool::PoolOptions::reset_on_return].
+    ///
+    /// One of `"none"`, `"rollback"` or `"discard-all"`.
+    #[cfg_attr(feature = "sqlx-toml", serde(deserialize_with = "de::reset_on_return"))]
+    pub reset_on_return: Option<ResetOnReturn>,
+}
+
+impl Config {
+    /// The table for the driver with the given [`Database::NAME`][crate::database::Database::NAME],
+    /// if there is one.
+    pub fn for_database(&self, name: &str) -> Option<&Driver> {
+        match name {
+            "PostgreSQL" => Some(&self.postgres),
+            "MySQL" => Some(&self.mysql),
+            "SQLite" => Some(&self.sqlite),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "sqlx-toml")]
+mod de {
+    use serde::de::{Deserializer, Error};
+    use serde::Deserialize;
+
+    use crate::pool::ResetOnReturn;
+
+    pub(super) fn reset_on_return<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<ResetOnReturn>, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map(Some)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(all(test, feature = "sqlx-toml"))]
+mod tests {
+    use crate::config::Config;
+    use crate::pool::ResetOnReturn;
+
+    #[test]
+    fn it_parses_the_drivers_section() {
+        let config: Config = basic_toml::from_str(
+            "[drivers.postgres]\n\
+             reset-on-return = \"discard-all\"\n\
+             [drivers.sqlite]\n\
+             reset-on-return = \"rollback\"\n",
+        )
+        .unwrap();
+
+        let reset = |name| config.drivers.for_database(name).unwrap().reset_on_return;
+
+        assert_eq!(reset("PostgreSQL"), Some(ResetOnReturn::DiscardAll));
+        assert_eq!(reset("MySQL"), None);
+        assert_eq!(reset("SQLite"), Some(ResetOnReturn::Rollback));
+        assert!(config.drivers.for_database("Any").is_none());
+    }
+
+    #[test]
+    fn it_rejects_unknown_reset_policies() {
+        let err = basic_toml::from_str::<Config>("drivers.postgres.reset-on-return = \"discard\"")
+            .unwrap_err();
+        assert!(err.to_string().contains("reset-on-return"), "{err}");
+
+        let unknown_driver = "drivers.oracle.reset-on-return = \"none\"";
+        assert!(basic_toml::from_str::<Config>(unknown_driver).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+//! The `[migrate]` section of `sqlx.toml`.
+
+use std::time::Duration;
+
+/// Settings for the migrators embedded with `migrate!()`.
+///
+/// A key that is not set keeps the default of the [`Migrator`][crate::migrate::Migrator],
+/// and the setters of the `Migrator` can still override them at runtime:
+///
+/// ```toml
+/// [migrate]
+/// schema = "app"
+/// # in seconds; `0` runs migrations without a timeout, which is the default, and `false` keeps
+/// # the connection's own timeout
+/// statement-timeout = 600
+/// ```
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(default, rename_all = "kebab-case", deny_unknown_fields)
+)]
+#[non_exhaustive]
+pub struct Config {
+    /// See [`Migrator::set_schema()`][crate::migrate::Migrator::set_schema].
+    ///
+    /// An invalid name is a compile error in `migrate!()`.
+    pub schema: Option<String>,
+
+    /// See [`Migrator::set_statement_timeout()`][crate::migrate::Migrator::set_statement_timeout].
+    ///
+    /// `Some(None)` keeps the connection's statement timeout.
+    #[cfg_attr(feature = "sqlx-toml", serde(deserialize_with = "super::de::optional_duration"))]
+    pub statement_timeout: Option<Option<Duration>>,
+}
+
+#[cfg(all(test, feature = "sqlx-toml"))]
+mod tests {
+    use std::time::Duration;
+
+    use crate::config::Config;
+
+    #[test]
+    fn it_parses_the_migrate_section() {
+        let config: Config = basic_toml::from_str("migrate.schema = \"app\"").unwrap();
+
+        assert_eq!(config.migrate.schema.as_deref(), Some("app"));
+        assert_eq!(config.migrate.statement_timeout, None);
+        assert!(basic_toml::from_str::<Config>("migrate.search-path = \"app\"").is_err());
+    }
+
+    #[test]
+    fn it_parses_the_statement_timeout() {
+        let timeout = |toml| {
+            basic_toml::from_str::<Config>(toml).map(|config| config.migrate.statement_timeout)
+        };
+
+        assert_eq!(
+            timeout("migrate.statement-timeout = 600").unwrap(),
+            Some(Some(Duration::from_secs(600)))
+        );
+        assert_eq!(
+            timeout("migrate.statement-timeout = 0").unwrap(),
+            Some(Some(Duration::ZERO))
+        );
+        assert_eq!(timeout("migrate.statement-timeout = false").unwrap(), Some(None));
+        assert!(timeout("migrate.statement-timeout = true").is_err());
+    }
+}
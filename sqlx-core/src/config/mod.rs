@@ -0,0 +1,253 @@
+//! Project configuration read from `sqlx.toml`.
+//!
+//! The file lives next to the crate's `Cargo.toml` and every key in it is optional:
+//!
+//! ```toml
+//! [common]
+//! # Read the database URL from `APP_DATABASE_URL` instead of `DATABASE_URL`.
+//! database-url-var = "APP_DATABASE_URL"
+//! # Fail instead of warning when the environment and `.env` set it to different values.
+//! database-url-precedence = "error"
+//!
+//! [pool]
+//! max-connections = 50
+//!
+//! [migrate]
+//! schema = "app"
+//! statement-timeout = 600
+//!
+//! [drivers.postgres]
+//! reset-on-return = "discard-all"
+//! ```
+//!
+//! The query macros always read it. Reading it at runtime requires the `sqlx-toml` feature;
+//! without it, finding a `sqlx.toml` is an error ([`ConfigError::ParseDisabled`]) rather than
+//! silently ignoring the file.
+//!
+//! ### Locating `sqlx.toml`
+//! [`Config::try_from_crate()`] looks in `CARGO_MANIFEST_DIR`, which Cargo sets while building
+//! and running the crate, and fails if it isn't set. Build systems other than Cargo may not set
+//! it; [`Config::try_from_crate_or()`] takes a directory to look in instead:
+//!
+//! 1. `CARGO_MANIFEST_DIR`, if it is set;
+//! 2. otherwise the given fallback directory.
+//!
+//! Either way, a directory without `sqlx.toml` gives [`Config::default()`]. The file is never
+//! searched for in parent directories.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::error::BoxDynError;
+
+pub mod common;
+#[cfg(feature = "sqlx-toml")]
+mod de;
+pub mod drivers;
+pub mod migrate;
+pub mod pool;
+
+/// The contents of `sqlx.toml`.
+///
+/// See the [module documentation](self) for how the file is located.
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(default, rename_all = "kebab-case", deny_unknown_fields)
+)]
+#[non_exhaustive]
+pub struct Config {
+    /// The `[common]` section.
+    pub common: common::Config,
+
+    /// The `[pool]` section.
+    pub pool: pool::Config,
+
+    /// The `[migrate]` section.
+    pub migrate: migrate::Config,
+
+    /// The `[drivers]` section.
+    pub drivers: drivers::Config,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// The environment variable naming the directory to read `sqlx.toml` from is not set.
+    #[error("environment variable `{var}` is not set; see `Config::try_from_crate_or()`")]
+    Env { var: &'static str },
+
+    #[error("error reading {path:?}: {error}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        error: io::Error,
+    },
+
+    #[error("error parsing {path:?}: {error}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        error: BoxDynError,
+    },
+
+    /// A `sqlx.toml` exists but the `sqlx-toml` feature, which is needed to parse it, is not
+    /// enabled.
+    #[error("found {path:?}, but the `sqlx-toml` feature is not enabled to read it")]
+    ParseDisabled { path: PathBuf },
+}
+
+impl Config {
+    /// The name of the file the configuration is read from.
+    pub const FILE_NAME: &'static str = "sqlx.toml";
+
+    /// Read `sqlx.toml` from the directory in `CARGO_MANIFEST_DIR`.
+    ///
+    /// Returns [`Config::default()`] if the file doesn't exist.
+    ///
+    /// ### Errors
+    /// * [`ConfigError::Env`] if `CARGO_MANIFEST_DIR` is not set, e.g. when not built by Cargo;
+    ///   see [`Config::try_from_crate_or()`].
+    /// * [`ConfigError::Io`] or [`ConfigError::Parse`] if the file can't be read or parsed.
+    pub fn try_from_crate() -> Result<Self, ConfigError> {
+        Self::try_from_manifest_dir(manifest_dir(), None)
+    }
+
+    /// Read `sqlx.toml` from the directory in `CARGO_MANIFEST_DIR`, or from `fallback_dir` if it
+    /// is not set.
+    ///
+    /// `CARGO_MANIFEST_DIR` takes precedence even when `fallback_dir` has a `sqlx.toml` and the
+    /// crate's directory doesn't. Returns [`Config::default()`] if the chosen directory has no
+    /// `sqlx.toml`.
+    ///
+    /// ### Errors
+    /// [`ConfigError::Io`] or [`ConfigError::Parse`] if the file can't be read or parsed.
+    pub fn try_from_crate_or(fallback_dir: PathBuf) -> Result<Self, ConfigError> {
+        Self::try_from_manifest_dir(manifest_dir(), Some(fallback_dir))
+    }
+
+    /// Read the configuration from the file at `path`.
+    ///
+    /// Unlike the other constructors, a missing file is an error.
+    pub fn try_from_path(path: PathBuf) -> Result<Self, ConfigError> {
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) => return Err(ConfigError::Io { path, error }),
+        };
+
+        Self::parse(&contents, path)
+    }
+
+    fn try_from_manifest_dir(
+        manifest_dir: Option<PathBuf>,
+        fallback_dir: Option<PathBuf>,
+    ) -> Result<Self, ConfigError> {
+        let dir = manifest_dir.or(fallback_dir).ok_or(ConfigError::Env {
+            var: "CARGO_MANIFEST_DIR",
+        })?;
+
+        Self::try_from_dir(&dir)
+    }
+
+    fn try_from_dir(dir: &Path) -> Result<Self, ConfigError> {
+        match Self::try_from_path(dir.join(Self::FILE_NAME)) {
+            Err(ConfigError::Io { error, .. }) if error.kind() == io::ErrorKind::NotFound => {
+                Ok(Self::default())
+            }
+            res => res,
+        }
+    }
+
+    #[cfg(feature = "sqlx-toml")]
+    fn parse(contents: &str, path: PathBuf) -> Result<Self, ConfigError> {
+        basic_toml::from_str(contents).map_err(|error| ConfigError::Parse {
+            path,
+            error: error.into(),
+        })
+    }
+
+    #[cfg(not(feature = "sqlx-toml"))]
+    fn parse(_contents: &str, path: PathBuf) -> Result<Self, ConfigError> {
+        Err(ConfigError::ParseDisabled { path })
+    }
+}
+
+fn manifest_dir() -> Option<PathBuf> {
+    std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from)
+}
+
+#[cfg(all(test, feature = "sqlx-toml"))]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::{Config, ConfigError};
+
+    fn dir_with(contents: Option<&str>) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+
+        if let Some(contents) = contents {
+            fs::write(dir.path().join(Config::FILE_NAME), contents).unwrap();
+        }
+
+        dir
+    }
+
+    fn var(config: &Config) -> &str {
+        config.common.database_url_var()
+    }
+
+    #[test]
+    fn it_prefers_the_manifest_dir_over_the_fallback() {
+        let manifest = dir_with(Some("common.database-url-var = \"MANIFEST_URL\""));
+        let fallback = dir_with(Some("common.database-url-var = \"FALLBACK_URL\""));
+
+        let config = Config::try_from_manifest_dir(
+            Some(manifest.path().into()),
+            Some(fallback.path().into()),
+        )
+        .unwrap();
+        assert_eq!(var(&config), "MANIFEST_URL");
+
+        let config = Config::try_from_manifest_dir(None, Some(fallback.path().into())).unwrap();
+        assert_eq!(var(&config), "FALLBACK_URL");
+
+        // the fallback is not consulted when the crate's directory has no `sqlx.toml`
+        let empty = dir_with(None);
+        let config =
+            Config::try_from_manifest_dir(Some(empty.path().into()), Some(fallback.path().into()))
+                .unwrap();
+        assert_eq!(var(&config), "DATABASE_URL");
+    }
+
+    #[test]
+    fn it_requires_a_directory() {
+        assert!(matches!(
+            Config::try_from_manifest_dir(None, None),
+            Err(ConfigError::Env {
+                var: "CARGO_MANIFEST_DIR"
+            })
+        ));
+    }
+
+    #[test]
+    fn it_rejects_unknown_keys() {
+        let dir = dir_with(Some("[common]\ndatabase-url = \"postgres://\""));
+
+        assert!(matches!(
+            Config::try_from_manifest_dir(Some(dir.path().into()), None),
+            Err(ConfigError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn it_requires_an_explicit_path_to_exist() {
+        let dir = dir_with(None);
+
+        assert!(matches!(
+            Config::try_from_path(PathBuf::from(dir.path()).join(Config::FILE_NAME)),
+            Err(ConfigError::Io { .. })
+        ));
+    }
+}
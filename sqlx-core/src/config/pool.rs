@@ -0,0 +1,91 @@
+//! The `[pool]` section of `sqlx.toml`.
+
+use std::time::Duration;
+
+/// Defaults for [`PoolOptions::from_config()`][crate::pool::PoolOptions::from_config].
+///
+/// A key that is not set keeps the default of
+/// [`PoolOptions::new()`][crate::pool::PoolOptions::new]. Timeouts are given in seconds:
+///
+/// ```toml
+/// [pool]
+/// max-connections = 50
+/// min-connections = 5
+/// acquire-timeout = 2.5
+/// # `false` disables the idle timeout or the lifetime limit.
+/// idle-timeout = 300
+/// max-lifetime = false
+/// test-before-acquire = false
+/// ```
+#[derive(Debug, Default, Clone)]
+#[cfg_attr(
+    feature = "sqlx-toml",
+    derive(serde::Deserialize),
+    serde(default, rename_all = "kebab-case", deny_unknown_fields)
+)]
+#[non_exhaustive]
+pub struct Config {
+    /// See [`PoolOptions::max_connections()`][crate::pool::PoolOptions::max_connections].
+    pub max_connections: Option<u32>,
+
+    /// See [`PoolOptions::min_connections()`][crate::pool::PoolOptions::min_connections].
+    pub min_connections: Option<u32>,
+
+    /// See [`PoolOptions::acquire_timeout()`][crate::pool::PoolOptions::acquire_timeout].
+    #[cfg_attr(feature = "sqlx-toml", serde(deserialize_with = "super::de::duration"))]
+    pub acquire_timeout: Option<Duration>,
+
+    /// See [`PoolOptions::idle_timeout()`][crate::pool::PoolOptions::idle_timeout].
+    ///
+    /// `Some(None)` disables the idle timeout.
+    #[cfg_attr(feature = "sqlx-toml", serde(deserialize_with = "super::de::optional_duration"))]
+    pub idle_timeout: Option<Option<Duration>>,
+
+    /// See [`PoolOptions::max_lifetime()`][crate::pool::PoolOptions::max_lifetime].
+    ///
+    /// `Some(None)` disables the lifetime limit.
+    #[cfg_attr(feature = "sqlx-toml", serde(deserialize_with = "super::de::optional_duration"))]
+    pub max_lifetime: Option<Option<Duration>>,
+
+    /// See [`PoolOptions::test_before_acquire()`][crate::pool::PoolOptions::test_before_acquire].
+    pub test_before_acquire: Option<bool>,
+}
+
+#[cfg(all(test, feature = "sqlx-toml"))]
+mod tests {
+    use std::time::Duration;
+
+    use crate::config::Config;
+
+    fn parse(toml: &str) -> Result<Config, basic_toml::Error> {
+        basic_toml::from_str(toml)
+    }
+
+    #[test]
+    fn it_parses_the_pool_section() {
+        let config = parse(
+            "[pool]\n\
+             max-connections = 50\n\
+             acquire-timeout = 2.5\n\
+             idle-timeout = 300\n\
+             max-lifetime = false\n\
+             test-before-acquire = false\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.pool.max_connections, Some(50));
+        assert_eq!(config.pool.min_connections, None);
+        assert_eq!(config.pool.acquire_timeout, Some(Duration::from_millis(2500)));
+        assert_eq!(config.pool.idle_timeout, Some(Some(Duration::from_secs(300))));
+        assert_eq!(config.pool.max_lifetime, Some(None));
+        assert_eq!(config.pool.test_before_acquire, Some(false));
+    }
+
+    #[test]
+    fn it_rejects_invalid_timeouts() {
+        assert!(parse("pool.acquire-timeout = -1").is_err());
+        assert!(parse("pool.acquire-timeout = false").is_err());
+        assert!(parse("pool.idle-timeout = true").is_err());
+        assert!(parse("pool.max-lifetime = \"30m\"").is_err());
+    }
+}
@@ -1,11 +1,15 @@
 use crate::database::{Database, HasStatementCache};
 use crate::error::Error;
+use crate::query::QueryStatsCounters;
 
-use crate::transaction::Transaction;
+use crate::transaction::{SqlToken, SqlTokens, Transaction};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
+use std::borrow::Cow;
 use std::fmt::Debug;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 use url::Url;
 
@@ -15,6 +19,14 @@ pub trait Connection: Send {
 
     type Options: ConnectOptions<Connection = Self>;
 
+    /// The id this connection was tagged with when it was established.
+    ///
+    /// This is the value returned by the function set with
+    /// [`ConnectOptions::connection_id()`], or otherwise a number that is unique among the
+    /// connections opened by this process. It is also recorded as `connection_id` in the
+    /// statement logs of this connection and in the events logged by the pool about it.
+    fn id(&self) -> u64;
+
     /// Explicitly close this database connection.
     ///
     /// This notifies the database server that the connection is closing so that it can
@@ -42,6 +54,139 @@ pub trait Connection: Send {
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>>;
 
+    /// Reset the session state of this connection, so it can be reused for unrelated work.
+    ///
+    /// This rolls back any open transaction, even one that was not started through
+    /// [`begin()`][Self::begin], and then clears state left over from previous work:
+    ///
+    /// * Postgres runs `DISCARD ALL`, which closes cursors, resets session parameters,
+    ///   releases advisory locks, drops temporary tables and deallocates prepared statements.
+    /// * MySQL sends `COM_RESET_CONNECTION`, which also resets user variables and releases
+    ///   table and named locks, then re-applies the session settings made on connect.
+    /// * SQLite finalizes all cached statements, which resets any statements still in progress.
+    ///   SQLite has no further session state to discard; `PRAGMA` settings and
+    ///   `TEMP` tables are kept.
+    ///
+    /// In all cases the statement cache is emptied.
+    ///
+    /// If this returns an error, the state of the connection is unknown and it should be closed.
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>>;
+
+    /// Roll back any open transaction, even one that was not started through
+    /// [`begin()`][Self::begin], and keep all other session state.
+    ///
+    /// * Postgres sends `ROLLBACK` if the server reports a transaction is open.
+    /// * MySQL sends `ROLLBACK`, which does nothing outside of a transaction.
+    /// * SQLite does the same as [`reset()`][Self::reset], as it has no other session state.
+    ///
+    /// Used by [`PoolOptions::reset_on_return()`][crate::pool::PoolOptions::reset_on_return].
+    ///
+    /// If this returns an error, the state of the connection is unknown and it should be closed.
+    fn rollback_open_transaction(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        self.reset()
+    }
+
+    /// Ask the server for the maximum number of connections it accepts, if it has a limit.
+    ///
+    /// * Postgres returns the `max_connections` setting. Note that the
+    ///   `superuser_reserved_connections` of those are not available to regular users.
+    /// * MySQL returns the `max_connections` system variable.
+    /// * SQLite has no limit and returns `None`.
+    ///
+    /// Used by [`PoolOptions::max_connections_from_server()`][crate::pool::PoolOptions::max_connections_from_server].
+    fn server_max_connections(&mut self) -> BoxFuture<'_, Result<Option<u32>, Error>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    /// Return up to `pages` free pages of the database file to the filesystem,
+    /// or all of them if `pages` is `0`.
+    ///
+    /// * SQLite runs `PRAGMA incremental_vacuum(pages)`, which only has an effect if the database
+    ///   uses [`SqliteAutoVacuum::Incremental`](https://docs.rs/sqlx/latest/sqlx/sqlite/enum.SqliteAutoVacuum.html).
+    /// * Postgres and MySQL do nothing, as the server manages its own files.
+    ///
+    /// Used by [`PoolOptions::incremental_vacuum_on_release()`][crate::pool::PoolOptions::incremental_vacuum_on_release].
+    fn incremental_vacuum(&mut self, pages: u32) -> BoxFuture<'_, Result<(), Error>> {
+        let _ = pages;
+        Box::pin(async { Ok(()) })
+    }
+
+    /// Read the server's cumulative counters for `sql`, before and after
+    /// [`Query::with_stats()`][crate::query::Query::with_stats] runs it.
+    #[doc(hidden)]
+    fn query_stats_counters<'a>(
+        &'a mut self,
+        sql: &'a str,
+    ) -> BoxFuture<'a, Result<QueryStatsCounters, Error>> {
+        let _ = sql;
+        Box::pin(async { Ok(QueryStatsCounters::default()) })
+    }
+
+    /// Ask the server for its current time, in UTC.
+    ///
+    /// This runs a query every time it is called; nothing is cached.
+    ///
+    /// * Postgres returns `now()`, which is the start time of the current transaction.
+    /// * MySQL returns `NOW(6)`, which is the start time of the current statement.
+    /// * SQLite returns `'now'`, which has millisecond precision.
+    #[cfg(feature = "time")]
+    fn server_now(&mut self) -> BoxFuture<'_, Result<crate::types::time::OffsetDateTime, Error>> {
+        Box::pin(async move {
+            let micros = self.server_now_micros().await?;
+
+            crate::types::time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(micros) * 1000)
+                .map_err(|e| Error::Decode(e.into()))
+        })
+    }
+
+    /// The current time on the server, in microseconds since the Unix epoch.
+    ///
+    /// Used by `server_now()`, so that it does not depend on the `time` feature of the driver.
+    #[doc(hidden)]
+    fn server_now_micros(&mut self) -> BoxFuture<'_, Result<i64, Error>>;
+
+    /// Execute `query`, blocking the current thread until it is done, and return the number
+    /// of rows affected.
+    ///
+    /// This is an escape hatch for code that cannot `.await`, chiefly `Drop` impls that have to
+    /// run a cleanup query. Prefer [`Executor::execute()`](crate::executor::Executor::execute)
+    /// everywhere else. The query runs on a dedicated thread; see
+    /// [`Pool::acquire_blocking()`][crate::pool::Pool::acquire_blocking] for how this
+    /// **deadlocks** when called from a thread that drives the runtime.
+    ///
+    /// ```rust,no_run
+    /// # use sqlx::{Connection, PgPool};
+    /// struct TempTable {
+    ///     pool: PgPool,
+    ///     name: String,
+    /// }
+    ///
+    /// impl Drop for TempTable {
+    ///     fn drop(&mut self) {
+    ///         // only sound if this is dropped outside of the async runtime's threads
+    ///         let result = self.pool.acquire_blocking().and_then(|mut conn| {
+    ///             conn.execute_blocking(&*format!("DROP TABLE IF EXISTS {}", self.name))
+    ///         });
+    ///
+    ///         if let Err(e) = result {
+    ///             eprintln!("failed to drop {}: {e}", self.name);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "blocking")]
+    fn execute_blocking<'q, E>(
+        &mut self,
+        query: E,
+    ) -> Result<<Self::Database as Database>::QueryResult, Error>
+    where
+        Self: Sized,
+        for<'c> &'c mut Self: crate::executor::Executor<'c, Database = Self::Database>,
+        E: 'q + crate::executor::Execute<'q, Self::Database>,
+    {
+        crate::rt::block_on_dedicated_thread(crate::executor::Executor::execute(self, query))
+    }
+
     /// Begin a new transaction or establish a savepoint within the active transaction.
     ///
     /// Returns a [`Transaction`] for controlling and tracking the new transaction.
@@ -95,6 +240,68 @@ pub trait Connection: Send {
         })
     }
 
+    /// Execute the function with the connection tagged with `tag`, such as the id of the request
+    /// being served, so that a query that is stuck on the server can be traced back to it.
+    ///
+    /// * Postgres sets `application_name`, which is shown in `pg_stat_activity` and can be added
+    ///   to the server log with `%a` in `log_line_prefix`. Postgres truncates it to 63 bytes
+    ///   and replaces non-ASCII characters with `?`. Afterwards, whether or not the function
+    ///   returned an error, it is reset to the value the connection was opened with.
+    /// * MySQL and SQLite have no equivalent, and only execute the function.
+    ///
+    /// Tagging is not free, so it is best kept to the requests where it is worth it: with
+    /// Postgres, setting the tag costs a round trip before the function is executed.
+    /// Resetting it is sent without waiting for the reply, which is read before the next
+    /// command on the connection. If the returned future is dropped before it completes,
+    /// the tag stays until the connection is next tagged or [reset][Self::reset].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use sqlx::postgres::{PgConnection, PgRow};
+    /// use sqlx::Connection;
+    ///
+    /// # pub async fn _f(conn: &mut PgConnection, request_id: &str) -> sqlx::Result<Vec<PgRow>> {
+    /// conn.with_tag(request_id, |conn| Box::pin(async move {
+    ///     sqlx::query("select * from ..").fetch_all(conn).await
+    /// })).await
+    /// # }
+    /// ```
+    fn with_tag<'a, F, R, E>(
+        &'a mut self,
+        tag: &'a str,
+        callback: F,
+    ) -> BoxFuture<'a, Result<R, E>>
+    where
+        for<'c> F: FnOnce(&'c mut Self) -> BoxFuture<'c, Result<R, E>> + 'a + Send + Sync,
+        Self: Sized,
+        R: Send,
+        E: From<Error> + Send,
+    {
+        Box::pin(async move {
+            self.set_tag(Some(tag)).await?;
+            let ret = callback(self).await;
+            self.set_tag(None).await?;
+
+            ret
+        })
+    }
+
+    /// Tag the connection with `tag`, or remove the tag if `None`.
+    ///
+    /// Used by [`with_tag()`][Self::with_tag].
+    #[doc(hidden)]
+    fn set_tag<'a>(&'a mut self, tag: Option<&'a str>) -> BoxFuture<'a, Result<(), Error>> {
+        let _ = tag;
+        Box::pin(async { Ok(()) })
+    }
+
+    /// The number of statements this connection has executed since it was opened, including
+    /// those run internally by the driver.
+    ///
+    /// Used by [`PoolOptions::max_queries_per_connection()`][crate::pool::PoolOptions::max_queries_per_connection].
+    fn queries_executed(&self) -> u64;
+
     /// The number of statements currently cached in the connection.
     fn cached_statements_size(&self) -> usize
     where
@@ -103,6 +310,27 @@ pub trait Connection: Send {
         0
     }
 
+    /// The number of statements this connection has prepared since it was opened.
+    ///
+    /// Together with [`statement_cache_hits()`][Self::statement_cache_hits], this can be used
+    /// to verify that a query is being served from the statement cache instead of being
+    /// prepared again on every execution.
+    fn statements_prepared(&self) -> u64
+    where
+        Self::Database: HasStatementCache,
+    {
+        0
+    }
+
+    /// The number of executions on this connection that reused a cached prepared statement
+    /// since it was opened.
+    fn statement_cache_hits(&self) -> u64
+    where
+        Self::Database: HasStatementCache,
+    {
+        0
+    }
+
     /// Removes all statements from the cache, closing them on the server if
     /// needed.
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>>
@@ -132,6 +360,24 @@ pub trait Connection: Send {
     #[doc(hidden)]
     fn should_flush(&self) -> bool;
 
+    /// Returns a handle that can cancel the query currently executing on this connection
+    /// from outside of it, if the database supports that.
+    ///
+    /// Used by [`Pool::cancel_all()`][crate::pool::Pool::cancel_all].
+    #[doc(hidden)]
+    fn cancel_handle(&self) -> Option<Arc<dyn CancelHandle>> {
+        None
+    }
+
+    /// If `require` is `true`, return [`Error::TransactionRequired`] instead of executing a
+    /// statement that modifies the database outside of a transaction.
+    ///
+    /// Used by [`PoolOptions::require_transaction_for_writes()`][crate::pool::PoolOptions::require_transaction_for_writes].
+    #[doc(hidden)]
+    fn set_require_transaction_for_writes(&mut self, require: bool) {
+        let _ = require;
+    }
+
     /// Establish a new database connection.
     ///
     /// A value of [`Options`][Self::Options] is parsed from the provided connection string. This parsing
@@ -155,12 +401,126 @@ pub trait Connection: Send {
     }
 }
 
+/// Out-of-band cancellation of the query running on a connection.
+///
+/// See [`Connection::cancel_handle()`].
+#[doc(hidden)]
+pub trait CancelHandle: Send + Sync + 'static {
+    /// Ask the server to cancel whatever the connection is currently executing.
+    ///
+    /// This is best-effort: if the connection is idle or the query completes first,
+    /// the request has no effect.
+    fn cancel(&self) -> BoxFuture<'_, Result<(), Error>>;
+}
+
 #[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct LogSettings {
     pub statements_level: LevelFilter,
     pub slow_statements_level: LevelFilter,
     pub slow_statements_duration: Duration,
+    pub redact_parameters: RedactParameters,
+    pub slow_query_hook: Option<SlowQueryHook>,
+    pub connection_id_hook: Option<ConnectionIdHook>,
+    /// The id of the connection these settings belong to.
+    ///
+    /// Zero in the settings of connect options; set by [`LogSettings::assign_connection_id()`]
+    /// when a connection is established.
+    pub connection_id: u64,
+    pub sql_rewriter: Option<SqlRewriter>,
+    pub dev_auto_limit: Option<usize>,
+    /// Whether the database reads a backslash in a quoted string as an escape, as MySQL does.
+    ///
+    /// Set by the driver when a connection is established, so that
+    /// [`ConnectOptions::dev_auto_limit()`] can tell where strings end.
+    pub backslash_escapes: bool,
+    #[cfg(feature = "wire-trace")]
+    pub wire_trace: Option<crate::wire_trace::WireTrace>,
+}
+
+/// How bound parameter values are shown in statement logs.
+///
+/// See [`ConnectOptions::redact_parameters()`].
+#[derive(Clone, Default)]
+pub enum RedactParameters {
+    /// Parameter values are not logged.
+    #[default]
+    All,
+    /// Each parameter is logged as the text returned by this function.
+    With(Arc<dyn Fn(usize, &dyn Debug) -> String + Send + Sync>),
+}
+
+impl Debug for RedactParameters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RedactParameters::All => f.write_str("All"),
+            RedactParameters::With(_) => f.write_str("With(..)"),
+        }
+    }
+}
+
+/// A function called for every statement that takes longer than a threshold.
+///
+/// See [`ConnectOptions::on_slow_query()`].
+#[derive(Clone)]
+pub struct SlowQueryHook {
+    pub(crate) threshold: Duration,
+    pub(crate) f: Arc<dyn Fn(&str, Duration) + Send + Sync>,
+}
+
+impl SlowQueryHook {
+    /// Call `f` for every statement that takes at least `threshold` to execute.
+    pub fn new(threshold: Duration, f: impl Fn(&str, Duration) + Send + Sync + 'static) -> Self {
+        SlowQueryHook {
+            threshold,
+            f: Arc::new(f),
+        }
+    }
+}
+
+impl Debug for SlowQueryHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SlowQueryHook")
+            .field("threshold", &self.threshold)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A function that returns the id to tag each new connection with.
+///
+/// See [`ConnectOptions::connection_id()`].
+#[derive(Clone)]
+pub struct ConnectionIdHook(pub(crate) Arc<dyn Fn() -> u64 + Send + Sync>);
+
+impl ConnectionIdHook {
+    pub fn new(f: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        ConnectionIdHook(Arc::new(f))
+    }
+}
+
+impl Debug for ConnectionIdHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ConnectionIdHook(..)")
+    }
+}
+
+/// A function that rewrites the SQL of every statement before it is sent.
+///
+/// See [`ConnectOptions::sql_rewriter()`].
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct SqlRewriter(pub(crate) Arc<dyn for<'a> Fn(&'a str) -> Cow<'a, str> + Send + Sync>);
+
+impl SqlRewriter {
+    pub fn new(f: impl for<'a> Fn(&'a str) -> Cow<'a, str> + Send + Sync + 'static) -> Self {
+        SqlRewriter(Arc::new(f))
+    }
+}
+
+impl Debug for SqlRewriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SqlRewriter(..)")
+    }
 }
 
 impl Default for LogSettings {
@@ -169,6 +529,15 @@ impl Default for LogSettings {
             statements_level: LevelFilter::Debug,
             slow_statements_level: LevelFilter::Warn,
             slow_statements_duration: Duration::from_secs(1),
+            redact_parameters: RedactParameters::All,
+            slow_query_hook: None,
+            connection_id_hook: None,
+            connection_id: 0,
+            sql_rewriter: None,
+            dev_auto_limit: None,
+            backslash_escapes: false,
+            #[cfg(feature = "wire-trace")]
+            wire_trace: None,
         }
     }
 }
@@ -181,6 +550,63 @@ impl LogSettings {
         self.slow_statements_level = level;
         self.slow_statements_duration = duration;
     }
+    pub fn redact_parameters(
+        &mut self,
+        f: impl Fn(usize, &dyn Debug) -> String + Send + Sync + 'static,
+    ) {
+        self.redact_parameters = RedactParameters::With(Arc::new(f));
+    }
+    pub fn redact_all_parameters(&mut self) {
+        self.redact_parameters = RedactParameters::All;
+    }
+    pub fn on_slow_query(&mut self, hook: SlowQueryHook) {
+        self.slow_query_hook = Some(hook);
+    }
+    pub fn connection_id(&mut self, f: impl Fn() -> u64 + Send + Sync + 'static) {
+        self.connection_id_hook = Some(ConnectionIdHook::new(f));
+    }
+
+    pub fn sql_rewriter(
+        &mut self,
+        f: impl for<'a> Fn(&'a str) -> Cow<'a, str> + Send + Sync + 'static,
+    ) {
+        self.sql_rewriter = Some(SqlRewriter::new(f));
+    }
+
+    pub fn dev_auto_limit(&mut self, limit: Option<usize>) {
+        self.dev_auto_limit = limit;
+    }
+
+    /// The SQL to send for `sql`, with the `LIMIT` of [`ConnectOptions::dev_auto_limit()`]
+    /// appended and as rewritten by the function set with [`ConnectOptions::sql_rewriter()`],
+    /// if any.
+    pub fn rewrite_sql<'a>(&self, sql: &'a str) -> Cow<'a, str> {
+        let sql = match self.dev_auto_limit.filter(|_| cfg!(debug_assertions)) {
+            Some(limit) => append_auto_limit(sql, limit, self.backslash_escapes),
+            None => Cow::Borrowed(sql),
+        };
+
+        match (&self.sql_rewriter, sql) {
+            (Some(rewriter), Cow::Borrowed(sql)) => (rewriter.0)(sql),
+            (Some(rewriter), Cow::Owned(sql)) => Cow::Owned((rewriter.0)(&sql).into_owned()),
+            (None, sql) => sql,
+        }
+    }
+
+    /// Pick the id of a connection that is being established with these settings.
+    ///
+    /// Calls the function set with [`ConnectOptions::connection_id()`] if there is one,
+    /// otherwise takes the next value of a process-wide counter.
+    pub fn assign_connection_id(&mut self) -> u64 {
+        static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+
+        self.connection_id = match &self.connection_id_hook {
+            Some(hook) => (hook.0)(),
+            None => NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed),
+        };
+
+        self.connection_id
+    }
 }
 
 pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug + Clone {
@@ -230,9 +656,316 @@ pub trait ConnectOptions: 'static + Send + Sync + FromStr<Err = Error> + Debug +
     /// at the specified `level`.
     fn log_slow_statements(self, level: LevelFilter, duration: Duration) -> Self;
 
+    /// Call `f` with the raw bytes of every protocol message sent or received on connections
+    /// opened with these options.
+    ///
+    /// This is intended for diagnosing protocol-level issues with the driver or with a proxy
+    /// in between; the bytes are passed as they appear on the wire (after TLS decryption),
+    /// so they may include passwords and other sensitive data.
+    ///
+    /// `f` is called synchronously from the connection's I/O path, so it should return quickly.
+    ///
+    /// Has no effect for SQLite, which does not use a wire protocol.
+    #[cfg(feature = "wire-trace")]
+    fn wire_trace(
+        self,
+        f: impl Fn(crate::wire_trace::Direction, &[u8]) + Send + Sync + 'static,
+    ) -> Self {
+        let _ = f;
+        self
+    }
+
+    /// Format bound parameter values with `f` and include them in statement logs.
+    ///
+    /// `f` is called with the position of each parameter (starting at 1, as in `$1` or `?1`)
+    /// and its value, and returns the text to log in its place. This makes it possible to show
+    /// parameters that are known to be safe while masking those that may contain personal data.
+    ///
+    /// By default, parameter values are never included in logs
+    /// (see [`.redact_all_parameters()`][Self::redact_all_parameters]).
+    ///
+    /// The value is the parameter as it will be sent to the database, which for most drivers
+    /// is its encoded form rather than the original Rust value.
+    fn redact_parameters(
+        self,
+        f: impl Fn(usize, &dyn Debug) -> String + Send + Sync + 'static,
+    ) -> Self;
+
+    /// Never include bound parameter values in statement logs. This is the default.
+    fn redact_all_parameters(self) -> Self;
+
+    /// Label connections opened with these options as belonging to the pool `pool_name`,
+    /// in a way that is visible on the server.
+    ///
+    /// Postgres sets `application_name` to `service[pool_name]`, where `service` is the
+    /// application name already set, or just to `pool_name` if there is none. `Any` passes
+    /// it on to Postgres. MySQL and SQLite have no such label; they log a warning and
+    /// otherwise ignore this.
+    ///
+    /// See [`PoolOptions::pool_name()`][crate::pool::PoolOptions::pool_name].
+    fn pool_name(self, pool_name: &str) -> Self;
+
+    /// Let integer columns be decoded into wider integer types, e.g. an `INT4` column into
+    /// an `i64`, which is otherwise a type mismatch. Defaults to `false`.
+    ///
+    /// Only conversions that cannot lose information are allowed, so this eases changing the
+    /// width of a column before changing the code that reads it. Narrower types, and signed
+    /// columns for unsigned types, are still a mismatch. This applies to
+    /// [`Row::try_get()`][crate::row::Row::try_get] and everything built on it, such as
+    /// `query_as()` and `#[derive(FromRow)]`, but not to arrays.
+    ///
+    /// * Postgres allows `INT2` for `i32` and `i64`, and `INT4` for `i64`.
+    /// * MySQL already allows any integer column of the same signedness, failing to decode
+    ///   a value that is out of range. This also allows unsigned columns for wider signed
+    ///   types, e.g. `INT UNSIGNED` for `i64`.
+    /// * SQLite ignores this, as all of its integers are 64-bit and decode into any integer type.
+    fn lenient_integer_decode(self, enabled: bool) -> Self {
+        let _ = enabled;
+        self
+    }
+
+    /// Receive values of the types named in `type_names` in the text format, and let them be
+    /// decoded as strings, e.g. into a `String`.
+    ///
+    /// This is a way to read types that SQLx can't decode from the binary format, such as
+    /// those added by extensions, instead of failing with a type mismatch. Other Rust types
+    /// can still decode these values if they support the text format. Names are matched
+    /// case-insensitively against the type's name, so an array type is named e.g. `"ltree[]"`.
+    /// Replaces any names set previously.
+    ///
+    /// Only Postgres supports this; other drivers ignore it, as MySQL and SQLite already
+    /// decode any value as a string that is sent as one.
+    fn prefer_text_for(self, type_names: &[&str]) -> Self {
+        let _ = type_names;
+        self
+    }
+
+    /// Call `f` with the SQL and the elapsed time of every statement that takes at least
+    /// `threshold` to execute, e.g. to record a metric or raise an alert.
+    ///
+    /// The time is measured from when the statement is sent until its last row has been
+    /// received. `f` is passed the SQL text only, never the bound parameter values.
+    /// It is called whether or not statement logging is enabled, synchronously from the
+    /// executing task, so it should return quickly.
+    ///
+    /// Replaces any function set previously. See also
+    /// [`PoolOptions::on_slow_query()`][crate::pool::PoolOptions::on_slow_query].
+    fn on_slow_query(
+        self,
+        threshold: Duration,
+        f: impl Fn(&str, Duration) + Send + Sync + 'static,
+    ) -> Self;
+
+    /// Call `f` to get the id of every connection opened with these options.
+    ///
+    /// The id is returned by [`Connection::id()`] and recorded as `connection_id` in statement
+    /// logs and pool events, so all activity on a connection can be correlated, e.g. with ids
+    /// that the application also uses in its own telemetry. `f` is called once for every new
+    /// connection; sqlx does not require the ids to be unique.
+    ///
+    /// By default, connections are numbered with a counter that is shared by all connect options
+    /// in the process and starts at 1.
+    fn connection_id(self, f: impl Fn() -> u64 + Send + Sync + 'static) -> Self;
+
+    /// Pass the SQL of every statement through `f` just before it is sent to the database.
+    ///
+    /// This is meant for annotating queries, e.g. appending a [sqlcommenter] comment such as
+    /// `/* app='checkout',tenant='42' */` that makes them attributable on the server, in
+    /// `pg_stat_statements` or the MySQL slow query log. `f` sees the SQL as it will be sent,
+    /// after processing by the driver such as inlining arguments, and must leave its
+    /// placeholders and meaning intact. It is applied to every statement that is prepared or
+    /// sent unprepared, including those sqlx sends itself, such as `BEGIN`. Statement logs and
+    /// [`on_slow_query()`][Self::on_slow_query] still show the original SQL.
+    ///
+    /// Prepared statements are cached under their original SQL, so the rewrite doesn't affect
+    /// which statements are reused. A cached statement is only rewritten when it is prepared,
+    /// however, so if `f` adds context that changes between executions (e.g. a trace id),
+    /// execute those queries with `.persistent(false)` to have it applied every time.
+    ///
+    /// Replaces any function set previously.
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core::connection::ConnectOptions;
+    /// # fn example<O: ConnectOptions>(options: O) -> O {
+    /// use std::borrow::Cow;
+    ///
+    /// options.sql_rewriter(|sql| Cow::Owned(format!("{sql} /* app='checkout' */")))
+    /// # }
+    /// ```
+    ///
+    /// [sqlcommenter]: https://google.github.io/sqlcommenter/
+    fn sql_rewriter(
+        self,
+        f: impl for<'a> Fn(&'a str) -> Cow<'a, str> + Send + Sync + 'static,
+    ) -> Self;
+
+    /// Append `LIMIT {limit}` to every `SELECT` that doesn't limit its rows already.
+    ///
+    /// A guardrail for local development, so that a query accidentally fetching a huge table
+    /// while debugging returns a bounded number of rows instead. It applies to single
+    /// statements that start with `SELECT`, or with `WITH` when the main statement is a
+    /// `SELECT`, and that don't already have a `LIMIT`, `OFFSET` or `FETCH` clause, a locking
+    /// clause such as `FOR UPDATE`, or an `INTO`. Subqueries and common table expressions are
+    /// left alone. The limit is added to the SQL as it is sent, the same way as
+    /// [`sql_rewriter()`][Self::sql_rewriter], so statements are still cached and logged
+    /// under their original SQL.
+    ///
+    /// This only takes effect when sqlx is compiled with debug assertions, i.e. it is ignored
+    /// in release builds, so it can't silently truncate results in production.
+    ///
+    /// Off (`None`) by default.
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core::connection::ConnectOptions;
+    /// # fn example<O: ConnectOptions>(options: O) -> O {
+    /// // `SELECT * FROM events` is sent as `SELECT * FROM events LIMIT 1000`
+    /// options.dev_auto_limit(Some(1000))
+    /// # }
+    /// ```
+    fn dev_auto_limit(self, limit: Option<usize>) -> Self;
+
     /// Entirely disables statement logging (both slow and regular).
     fn disable_statement_logging(self) -> Self {
         self.log_statements(LevelFilter::Off)
             .log_slow_statements(LevelFilter::Off, Duration::default())
     }
 }
+
+/// `sql` with `LIMIT {limit}` appended if it is a single `SELECT` that doesn't limit its rows.
+///
+/// See [`ConnectOptions::dev_auto_limit()`].
+fn append_auto_limit(sql: &str, limit: usize, backslash_escapes: bool) -> Cow<'_, str> {
+    // clauses that already bound the rows, or after which a `LIMIT` is out of place
+    const SKIP_KEYWORDS: &[&str] = &["FETCH", "FOR", "INTO", "LIMIT", "OFFSET"];
+
+    const CTE_MAIN_KEYWORDS: &[&str] = &["DELETE", "INSERT", "MERGE", "SELECT", "UPDATE"];
+
+    let is_keyword = |keywords: &[&str], word: &str| {
+        keywords
+            .iter()
+            .any(|keyword| keyword.eq_ignore_ascii_case(word))
+    };
+
+    let mut tokens = SqlTokens::new(sql).backslash_escapes(backslash_escapes);
+    let mut is_select = false;
+    let mut terminated = false;
+    let mut depth = 0usize;
+    // the end of the statement, before any trailing comments or `;`
+    let mut end = 0;
+
+    while let Some(token) = tokens.next() {
+        match token {
+            // only a single statement gets a limit
+            _ if terminated => return Cow::Borrowed(sql),
+            SqlToken::Semicolon if depth == 0 => {
+                terminated = true;
+                continue;
+            }
+            SqlToken::Word(word) if depth == 0 => {
+                if end == 0 {
+                    // the first keyword
+                    is_select = word.eq_ignore_ascii_case("SELECT");
+                    if !is_select && !word.eq_ignore_ascii_case("WITH") {
+                        return Cow::Borrowed(sql);
+                    }
+                } else if is_select {
+                    if is_keyword(SKIP_KEYWORDS, word) {
+                        return Cow::Borrowed(sql);
+                    }
+                } else if is_keyword(CTE_MAIN_KEYWORDS, word) {
+                    // the statement following the common table expressions
+                    if !word.eq_ignore_ascii_case("SELECT") {
+                        return Cow::Borrowed(sql);
+                    }
+
+                    is_select = true;
+                }
+            }
+            // doesn't start with a keyword, e.g. `(SELECT ...) UNION (SELECT ...)`
+            _ if end == 0 => return Cow::Borrowed(sql),
+            SqlToken::Open => depth += 1,
+            SqlToken::Close => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+
+        end = tokens.offset();
+    }
+
+    if !is_select {
+        return Cow::Borrowed(sql);
+    }
+
+    Cow::Owned(format!("{} LIMIT {limit}{}", &sql[..end], &sql[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::append_auto_limit;
+
+    #[test]
+    fn it_appends_auto_limit() {
+        for (sql, expected) in [
+            ("SELECT * FROM users", "SELECT * FROM users LIMIT 10"),
+            ("  select id from users;", "  select id from users LIMIT 10;"),
+            (
+                "SELECT * FROM users -- everyone\n",
+                "SELECT * FROM users LIMIT 10 -- everyone\n",
+            ),
+            (
+                "SELECT * FROM (SELECT * FROM users LIMIT 5) u",
+                "SELECT * FROM (SELECT * FROM users LIMIT 5) u LIMIT 10",
+            ),
+            (
+                "WITH ids AS (SELECT id FROM users LIMIT 5) SELECT * FROM ids",
+                "WITH ids AS (SELECT id FROM users LIMIT 5) SELECT * FROM ids LIMIT 10",
+            ),
+            (
+                "SELECT 'limit' FROM t WHERE id = $1",
+                "SELECT 'limit' FROM t WHERE id = $1 LIMIT 10",
+            ),
+            (
+                "SELECT $$ for update $$ UNION SELECT \"offset\" FROM t",
+                "SELECT $$ for update $$ UNION SELECT \"offset\" FROM t LIMIT 10",
+            ),
+        ] {
+            assert_eq!(append_auto_limit(sql, 10, false), expected);
+        }
+
+        for sql in [
+            "SELECT * FROM users LIMIT 5",
+            "SELECT * FROM users OFFSET 5",
+            "SELECT * FROM users FETCH FIRST 5 ROWS ONLY",
+            "SELECT * FROM users FOR UPDATE",
+            "SELECT * INTO archive FROM users",
+            "SELECT 1; SELECT 2",
+            "WITH deleted AS (DELETE FROM users RETURNING id) DELETE FROM t",
+            "WITH ids AS (SELECT id FROM users) UPDATE t SET x = 1",
+            "INSERT INTO users SELECT * FROM staged",
+            "(SELECT 1) UNION (SELECT 2)",
+            "EXPLAIN SELECT * FROM users",
+            "SELECT E'it\\'s; limit' FROM t LIMIT 5",
+            "SELECT 'it''s; limit' FROM t LIMIT 5",
+            "",
+        ] {
+            assert_eq!(append_auto_limit(sql, 10, false), sql);
+        }
+    }
+
+    #[test]
+    fn it_reads_backslash_escapes() {
+        // MySQL
+        let sql = "SELECT * FROM t WHERE name = 'it\\'s; limit'";
+        assert_eq!(
+            append_auto_limit(sql, 10, true),
+            "SELECT * FROM t WHERE name = 'it\\'s; limit' LIMIT 10"
+        );
+
+        // a backslash doesn't escape in a MySQL identifier
+        let sql = "SELECT '\\\\', `a\\` FROM t LIMIT 5";
+        assert_eq!(append_auto_limit(sql, 10, true), sql);
+
+        // Postgres and SQLite, where a backslash is an ordinary character
+        let sql = "SELECT 'C:\\' FROM t LIMIT 5";
+        assert_eq!(append_auto_limit(sql, 10, false), sql);
+    }
+}
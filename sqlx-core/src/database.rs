@@ -112,3 +112,16 @@ pub trait Database: 'static + Sized + Send + Debug {
 
 /// A [`Database`] that maintains a client-side cache of prepared statements.
 pub trait HasStatementCache {}
+
+/// A [`Database`] whose [`Arguments`][Database::Arguments] and
+/// [`Statement`][Database::Statement] types may be used for a shorter lifetime than the one
+/// they were created with.
+///
+/// This is true of every driver, but the compiler can't see it through the associated types
+/// in generic code. It is used by [`PrefixedExecutor`][crate::table_prefix::PrefixedExecutor]
+/// to execute a query with rewritten SQL.
+pub trait HasCovariantLifetimes: Database {
+    fn shorten_arguments<'a, 'b: 'a>(arguments: Self::Arguments<'b>) -> Self::Arguments<'a>;
+
+    fn shorten_statement<'a, 'b: 'a>(statement: Self::Statement<'b>) -> Self::Statement<'a>;
+}
@@ -1,13 +1,18 @@
 use crate::database::Database;
+use crate::error::Error;
+use crate::executor::Executor;
 use either::Either;
 use std::convert::identity;
 
 /// Provides extended information on a statement.
 ///
-/// Returned from [`Executor::describe`].
+/// Returned from [`describe()`].
 ///
 /// The query macros (e.g., `query!`, `query_as!`, etc.) use the information here to validate
 /// output and parameter types; and, generate an anonymous record.
+///
+/// Use the methods to read it: the fields are public for the drivers that construct it, and
+/// may change between releases.
 #[derive(Debug)]
 #[cfg_attr(feature = "offline", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(
@@ -17,13 +22,48 @@ use std::convert::identity;
         deserialize = "DB::TypeInfo: serde::de::DeserializeOwned, DB::Column: serde::de::DeserializeOwned",
     ))
 )]
-#[doc(hidden)]
 pub struct Describe<DB: Database> {
+    #[doc(hidden)]
     pub columns: Vec<DB::Column>,
+    #[doc(hidden)]
     pub parameters: Option<Either<Vec<DB::TypeInfo>, usize>>,
+    #[doc(hidden)]
     pub nullable: Vec<Option<bool>>,
 }
 
+/// Prepare `sql` on the database and describe its parameters and result columns, without
+/// executing it.
+///
+/// This is the same introspection the query macros (`query!()` et al.) perform at compile time,
+/// for tools that want to check queries or generate code from them on their own terms,
+/// e.g. in a build script.
+///
+/// ```rust,no_run
+/// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+/// use sqlx::{Column, TypeInfo};
+///
+/// let sql = "SELECT id, name FROM users WHERE id = $1";
+/// let describe = sqlx::describe::describe(&pool, sql).await?;
+///
+/// for (i, column) in describe.columns().iter().enumerate() {
+///     // e.g. `id: INT8, nullable: Some(false)`
+///     println!(
+///         "{}: {}, nullable: {:?}",
+///         column.name(),
+///         column.type_info().name(),
+///         describe.nullable(i)
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn describe<'c, E>(executor: E, sql: &str) -> Result<Describe<E::Database>, Error>
+where
+    E: Executor<'c>,
+{
+    executor.describe(sql).await
+}
+
 impl<DB: Database> Describe<DB> {
     /// Gets all columns in this statement.
     pub fn columns(&self) -> &[DB::Column] {
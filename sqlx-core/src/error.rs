@@ -5,6 +5,7 @@ use std::borrow::Cow;
 use std::error::Error as StdError;
 use std::fmt::Display;
 use std::io;
+use std::time::Duration;
 
 use crate::database::Database;
 
@@ -57,6 +58,46 @@ pub enum Error {
     #[error("no rows returned by a query that expected to return at least one row")]
     RowNotFound,
 
+    /// A query returned more rows than allowed by [`Query::max_rows`].
+    ///
+    /// [`Query::max_rows`]: crate::query::Query::max_rows
+    #[error("query returned more than the maximum of {max_rows} rows")]
+    RowLimitExceeded { max_rows: usize },
+
+    /// A query did not complete, including streaming all of its rows, within the time
+    /// allowed by [`Query::total_timeout`].
+    ///
+    /// [`Query::total_timeout`]: crate::query::Query::total_timeout
+    #[error("query did not complete within its total timeout of {timeout:?}")]
+    QueryTimedOut { timeout: Duration },
+
+    /// The number of arguments bound to a query doesn't match the highest parameter
+    /// placeholder referenced in its SQL, e.g. `$3` or `?3`.
+    ///
+    /// This is checked before the query is executed.
+    #[error("query references {expected} bind parameters, but {provided} were provided")]
+    BindCountMismatch { expected: usize, provided: usize },
+
+    /// A decimal bound to a query has more fractional digits than the scale of the column it
+    /// is assigned to, so the database would round it.
+    ///
+    /// This is checked before the query is executed, if enabled; see
+    /// `PgConnectOptions::decimal_scale_mode()`.
+    #[error("parameter ${index} has {scale} decimal places, but column {column} allows {max_scale}")]
+    DecimalScaleOverflow {
+        /// The number of the parameter, starting at 1.
+        index: usize,
+
+        /// The name of the column the parameter is assigned to.
+        column: String,
+
+        /// The number of decimal places of the value.
+        scale: i32,
+
+        /// The scale of the column.
+        max_scale: i32,
+    },
+
     /// Type in query doesn't exist. Likely due to typo or missing user type.
     #[error("type named {type_name} not found")]
     TypeNotFound { type_name: String },
@@ -69,6 +110,16 @@ pub enum Error {
     #[error("no column found for name: {0}")]
     ColumnNotFound(String),
 
+    /// A row has a column that doesn't map to any field of a `FromRow` struct with
+    /// `#[sqlx(deny_unknown_columns)]`.
+    #[error("no field is mapped to column {0:?}")]
+    ColumnNotMapped(String),
+
+    /// More than one column has the given name, and
+    /// [`DuplicateColumnNames::Error`][crate::column::DuplicateColumnNames::Error] is enabled.
+    #[error("column name {0:?} is ambiguous; access the column by index or alias it instead")]
+    ColumnNameAmbiguous(String),
+
     /// Error occurred while decoding a value from a specific column.
     #[error("error occurred while decoding column {index}: {source}")]
     ColumnDecode {
@@ -78,6 +129,27 @@ pub enum Error {
         source: BoxDynError,
     },
 
+    /// Error occurred while decoding a column into a field of a `#[derive(FromRow)]` struct.
+    ///
+    /// This takes the place of the [`Error::ColumnDecode`] returned for the column, naming the
+    /// field it was decoded for.
+    #[error(
+        "error occurred while decoding column {column} into field {field} ({type_name}): {source}"
+    )]
+    FieldDecode {
+        /// The column, as in [`Error::ColumnDecode`].
+        column: String,
+
+        /// The struct and the field, as `User.created_at`.
+        field: &'static str,
+
+        /// The type of the field, as written in the struct.
+        type_name: &'static str,
+
+        #[source]
+        source: BoxDynError,
+    },
+
     /// Error occured while encoding a value.
     #[error("error occured while encoding a value: {0}")]
     Encode(#[source] BoxDynError),
@@ -108,6 +180,14 @@ pub enum Error {
     #[error("attempted to communicate with a crashed background worker")]
     WorkerCrashed,
 
+    /// A statement that must run inside of a transaction was executed outside of one.
+    ///
+    /// This is returned for statements that modify the database on a connection from a pool with
+    /// [`require_transaction_for_writes`][crate::pool::PoolOptions::require_transaction_for_writes]
+    /// enabled, and by driver helpers that only make sense inside a transaction.
+    #[error("attempted to execute a statement that requires a transaction outside of one")]
+    TransactionRequired,
+
     #[cfg(feature = "migrate")]
     #[error("{0}")]
     Migrate(#[source] Box<crate::migrate::MigrateError>),
@@ -142,6 +222,20 @@ impl Error {
         Error::Configuration(err.into())
     }
 
+    // used in derive(FromRow)
+    #[doc(hidden)]
+    pub fn __into_field_decode(self, field: &'static str, type_name: &'static str) -> Self {
+        match self {
+            Error::ColumnDecode { index, source } => Error::FieldDecode {
+                column: index,
+                field,
+                type_name,
+                source,
+            },
+            e => e,
+        }
+    }
+
     pub(crate) fn tls(err: impl Into<Box<dyn StdError + Send + Sync + 'static>>) -> Self {
         Error::Tls(err.into())
     }
@@ -155,15 +249,58 @@ impl Error {
 
 pub fn mismatched_types<DB: Database, T: Type<DB>>(ty: &DB::TypeInfo) -> BoxDynError {
     // TODO: `#name` only produces `TINYINT` but perhaps we want to show `TINYINT(1)`
-    format!(
-        "mismatched types; Rust type `{}` (as SQL type `{}`) is not compatible with SQL type `{}`",
-        type_name::<T>(),
-        T::type_info().name(),
-        ty.name()
-    )
-    .into()
+    Box::new(MismatchedTypes {
+        rust_type: type_name::<T>(),
+        rust_sql_type: Some(T::type_info().name().to_owned()),
+        sql_type: ty.name().to_owned(),
+    })
+}
+
+/// The SQL type of a value is not compatible with the Rust type it was decoded into.
+///
+/// This is the `source` of an [`Error::ColumnDecode`] returned by
+/// [`Row::try_get()`][crate::row::Row::try_get] when the type check fails, which allows telling
+/// a type mismatch apart from a value that failed to decode:
+///
+/// ```rust
+/// # use sqlx_core::error::{Error, MismatchedTypes};
+/// fn is_type_mismatch(e: &Error) -> bool {
+///     matches!(e, Error::ColumnDecode { source, .. } if source.is::<MismatchedTypes>())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MismatchedTypes {
+    pub(crate) rust_type: &'static str,
+    pub(crate) rust_sql_type: Option<String>,
+    pub(crate) sql_type: String,
 }
 
+impl MismatchedTypes {
+    /// The name of the Rust type that was requested.
+    pub fn rust_type(&self) -> &str {
+        self.rust_type
+    }
+
+    /// The name of the SQL type of the value.
+    pub fn sql_type(&self) -> &str {
+        &self.sql_type
+    }
+}
+
+impl Display for MismatchedTypes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mismatched types; Rust type `{}`", self.rust_type)?;
+
+        if let Some(rust_sql_type) = &self.rust_sql_type {
+            write!(f, " (as SQL type `{rust_sql_type}`)")?;
+        }
+
+        write!(f, " is not compatible with SQL type `{}`", self.sql_type)
+    }
+}
+
+impl StdError for MismatchedTypes {}
+
 /// The error kind.
 ///
 /// This enum is to be used to identify frequent errors that can be handled by the program.
@@ -179,6 +316,8 @@ pub enum ErrorKind {
     NotNullViolation,
     /// Check constraint violation.
     CheckViolation,
+    /// The database gave up waiting for a lock, e.g. because of Postgres' `lock_timeout`.
+    LockTimeout,
     /// An unmapped error.
     Other,
 }
@@ -244,6 +383,11 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
     fn is_check_violation(&self) -> bool {
         matches!(self.kind(), ErrorKind::CheckViolation)
     }
+
+    /// Returns whether the database gave up waiting for a lock.
+    fn is_lock_timeout(&self) -> bool {
+        matches!(self.kind(), ErrorKind::LockTimeout)
+    }
 }
 
 impl dyn DatabaseError {
@@ -1,3 +1,4 @@
+use crate::connection::CancelHandle;
 use crate::database::Database;
 use crate::describe::Describe;
 use crate::error::{BoxDynError, Error};
@@ -7,6 +8,7 @@ use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::{future, FutureExt, StreamExt, TryFutureExt, TryStreamExt};
 use std::fmt::Debug;
+use std::sync::Arc;
 
 /// A type that contains or can provide a database
 /// connection to use for executing queries against the database.
@@ -181,6 +183,15 @@ pub trait Executor<'c>: Send + Debug + Sized {
     ) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>>
     where
         'c: 'e;
+
+    /// Returns a handle that can cancel the query this executor is about to run, if it runs
+    /// queries on a known connection that supports cancellation.
+    ///
+    /// Used by [`Query::total_timeout()`][crate::query::Query::total_timeout].
+    #[doc(hidden)]
+    fn cancel_handle(&self) -> Option<Arc<dyn CancelHandle>> {
+        None
+    }
 }
 
 /// A type that may be executed against a database connection.
@@ -208,6 +219,15 @@ pub trait Execute<'q, DB: Database>: Send + Sized {
 
     /// Returns `true` if the statement should be cached.
     fn persistent(&self) -> bool;
+
+    /// How long a [`CachingExecutor`][crate::query_cache::CachingExecutor] may serve the rows
+    /// of this query from its cache, if at all.
+    ///
+    /// Set by [`Query::cacheable()`][crate::query::Query::cacheable].
+    #[doc(hidden)]
+    fn cache_ttl(&self) -> Option<std::time::Duration> {
+        None
+    }
 }
 
 // NOTE: `Execute` is explicitly not implemented for String and &String to make it slightly more
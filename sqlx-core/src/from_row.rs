@@ -18,6 +18,10 @@ use crate::{error::Error, row::Row};
 /// }
 /// ```
 ///
+/// If a column fails to decode, the generated implementation returns
+/// [`Error::FieldDecode`][crate::error::Error::FieldDecode] instead of `Error::ColumnDecode`,
+/// naming the field and its type as well as the column.
+///
 /// ### Field attributes
 ///
 /// Several attributes can be specified to customize how each column in a row is read:
@@ -177,6 +181,30 @@ use crate::{error::Error, row::Row};
 /// assert!(user.addresses.is_empty());
 /// ```
 ///
+/// #### `deny_unknown_columns`
+///
+/// By default, columns in the row that no field maps to are ignored. With this attribute on the
+/// struct, such a column makes `from_row()` return [`Error::ColumnNotMapped`][crate::error::Error::ColumnNotMapped]
+/// instead. This catches a struct and a table drifting apart, e.g. when a column was renamed
+/// in the schema but the old field was kept under `#[sqlx(default)]`:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// #[sqlx(deny_unknown_columns)]
+/// struct User {
+///     id: i32,
+///     name: String,
+/// }
+///
+/// // Returns `Err(Error::ColumnNotMapped("email"))`
+/// let user: User = sqlx::query_as("SELECT id, name, email FROM users")
+///    .fetch_one(&mut some_connection)
+///    .await?;
+/// ```
+///
+/// Fields marked `skip` don't map to any column. This is similar to `#[serde(deny_unknown_fields)]`,
+/// and likewise it can't be combined with `flatten`.
+///
 /// ## Manual implementation
 ///
 /// You can also implement the [`FromRow`] trait by hand. This can be useful if you
@@ -61,6 +61,7 @@ pub mod column;
 pub mod statement;
 
 pub mod common;
+pub mod config;
 pub mod database;
 pub mod describe;
 pub mod executor;
@@ -71,12 +72,14 @@ pub mod logger;
 pub mod net;
 pub mod query_as;
 pub mod query_builder;
+pub mod query_cache;
 pub mod query_scalar;
 
 pub mod raw_sql;
 pub mod row;
 pub mod rt;
 pub mod sync;
+pub mod table_prefix;
 pub mod type_checking;
 pub mod type_info;
 pub mod value;
@@ -84,6 +87,9 @@ pub mod value;
 #[cfg(feature = "migrate")]
 pub mod migrate;
 
+#[cfg(feature = "wire-trace")]
+pub mod wire_trace;
+
 #[cfg(feature = "any")]
 pub mod any;
 
@@ -1,4 +1,5 @@
-use crate::connection::LogSettings;
+use crate::connection::{LogSettings, RedactParameters};
+use std::fmt::{self, Debug, Formatter};
 use std::time::Instant;
 
 // Yes these look silly. `tracing` doesn't currently support dynamic levels
@@ -66,6 +67,7 @@ pub struct QueryLogger<'q> {
     rows_affected: u64,
     start: Instant,
     settings: LogSettings,
+    parameters: String,
 }
 
 impl<'q> QueryLogger<'q> {
@@ -76,6 +78,33 @@ impl<'q> QueryLogger<'q> {
             rows_affected: 0,
             start: Instant::now(),
             settings,
+            parameters: String::new(),
+        }
+    }
+
+    /// Record the bound parameters of the query, formatted with the function set through
+    /// [`ConnectOptions::redact_parameters()`][crate::connection::ConnectOptions::redact_parameters].
+    ///
+    /// Does nothing if parameters are redacted (the default) or the statement won't be logged.
+    pub fn log_parameters(&mut self, parameters: impl IntoIterator<Item = impl Debug>) {
+        let RedactParameters::With(redact) = &self.settings.redact_parameters else {
+            return;
+        };
+
+        if !is_enabled(self.settings.statements_level)
+            && !is_enabled(self.settings.slow_statements_level)
+        {
+            return;
+        }
+
+        self.parameters.clear();
+
+        for (i, parameter) in parameters.into_iter().enumerate() {
+            if i > 0 {
+                self.parameters.push_str(", ");
+            }
+
+            self.parameters.push_str(&redact(i + 1, &parameter));
         }
     }
 
@@ -90,6 +119,12 @@ impl<'q> QueryLogger<'q> {
     pub fn finish(&self) {
         let elapsed = self.start.elapsed();
 
+        if let Some(hook) = &self.settings.slow_query_hook {
+            if elapsed >= hook.threshold {
+                (hook.f)(self.sql, elapsed);
+            }
+        }
+
         let was_slow = elapsed >= self.settings.slow_statements_duration;
 
         let lvl = if was_slow {
@@ -98,12 +133,8 @@ impl<'q> QueryLogger<'q> {
             self.settings.statements_level
         };
 
-        if let Some((tracing_level, log_level)) = private_level_filter_to_levels(lvl) {
-            // The enabled level could be set from either tracing world or log world, so check both
-            // to see if logging should be enabled for our level
-            let log_is_enabled = log::log_enabled!(target: "sqlx::query", log_level)
-                || private_tracing_dynamic_enabled!(target: "sqlx::query", tracing_level);
-            if log_is_enabled {
+        if let Some((tracing_level, _)) = private_level_filter_to_levels(lvl) {
+            if is_enabled(lvl) {
                 let mut summary = parse_query_summary(self.sql);
 
                 let sql = if summary != self.sql {
@@ -126,7 +157,9 @@ impl<'q> QueryLogger<'q> {
                         target: "sqlx::query",
                         tracing_level,
                         summary,
+                        connection_id = self.settings.connection_id,
                         db.statement = sql,
+                        db.parameters = self.parameters.as_str(),
                         rows_affected = self.rows_affected,
                         rows_returned = self.rows_returned,
                         // Human-friendly - includes units (usually ms). Also kept for backward compatibility
@@ -144,7 +177,9 @@ impl<'q> QueryLogger<'q> {
                         target: "sqlx::query",
                         tracing_level,
                         summary,
+                        connection_id = self.settings.connection_id,
                         db.statement = sql,
+                        db.parameters = self.parameters.as_str(),
                         rows_affected = self.rows_affected,
                         rows_returned = self.rows_returned,
                         // Human-friendly - includes units (usually ms). Also kept for backward compatibility
@@ -158,6 +193,42 @@ impl<'q> QueryLogger<'q> {
     }
 }
 
+fn is_enabled(filter: log::LevelFilter) -> bool {
+    private_level_filter_to_levels(filter).map_or(false, |(tracing_level, log_level)| {
+        // The enabled level could be set from either tracing world or log world, so check both
+        // to see if logging should be enabled for our level
+        log::log_enabled!(target: "sqlx::query", log_level)
+            || private_tracing_dynamic_enabled!(target: "sqlx::query", tracing_level)
+    })
+}
+
+/// A bind parameter in the encoded form that is sent to the database.
+///
+/// Passed to the function set by
+/// [`ConnectOptions::redact_parameters()`][crate::connection::ConnectOptions::redact_parameters]
+/// by drivers that encode parameters to bytes. Formats as `NULL` or the type name followed by the
+/// bytes in hex, e.g. `INT4 0x0000002a`.
+pub struct EncodedParameter<'a> {
+    pub type_name: &'a str,
+    pub value: Option<&'a [u8]>,
+}
+
+impl Debug for EncodedParameter<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Some(value) = self.value else {
+            return f.write_str("NULL");
+        };
+
+        write!(f, "{} 0x", self.type_name)?;
+
+        for byte in value {
+            write!(f, "{byte:02x}")?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<'q> Drop for QueryLogger<'q> {
     fn drop(&mut self) {
         self.finish();
@@ -0,0 +1,379 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use crate::migrate::Migration;
+
+/// Which tables, if any, [`Migrator`][crate::migrate::Migrator] should `ANALYZE` after applying
+/// migrations.
+///
+/// Migrations that bulk-load or heavily rewrite a table leave the planner statistics stale until
+/// autovacuum (or the equivalent) catches up, which can make the first queries after a deploy
+/// unexpectedly slow.
+///
+/// `ANALYZE` is only run if at least one migration was applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum AnalyzeAfter {
+    /// Don't run `ANALYZE` after migrating. This is the default.
+    #[default]
+    Disabled,
+
+    /// `ANALYZE` every table touched by the applied migrations.
+    ///
+    /// Touched tables are detected on a best-effort basis by scanning the migration SQL for
+    /// `INSERT INTO`, `UPDATE`, `DELETE FROM`, `COPY`, `CREATE TABLE` and `ALTER TABLE`.
+    /// Tables dropped or created as temporary in the same run are skipped.
+    ///
+    /// A migration can instead list its tables explicitly with a directive comment, which
+    /// replaces detection for that migration:
+    ///
+    /// ```sql
+    /// -- analyze: users, orders
+    /// ```
+    ///
+    /// An empty directive (`-- analyze:`) opts the migration out entirely.
+    Touched,
+
+    /// `ANALYZE` exactly these tables.
+    Tables(Cow<'static, [Cow<'static, str>]>),
+}
+
+impl AnalyzeAfter {
+    /// Resolve the list of tables to analyze after applying `migrations`.
+    pub(crate) fn tables<'a>(
+        &self,
+        migrations: impl IntoIterator<Item = &'a Migration>,
+    ) -> Vec<String> {
+        match self {
+            AnalyzeAfter::Disabled => Vec::new(),
+            AnalyzeAfter::Tables(tables) => {
+                let mut seen = HashSet::new();
+
+                tables
+                    .iter()
+                    .filter(|table| seen.insert(normalize(table)))
+                    .map(|table| table.to_string())
+                    .collect()
+            }
+            AnalyzeAfter::Touched => {
+                let mut touched = TouchedTables::default();
+
+                for migration in migrations {
+                    match directive(&migration.sql) {
+                        Some(tables) => tables.for_each(|table| touched.touch(table)),
+                        None => touched.scan(&migration.sql),
+                    }
+                }
+
+                touched.into_tables()
+            }
+        }
+    }
+}
+
+/// Parse the tables listed in an `-- analyze:` directive, if the migration has one.
+fn directive(sql: &str) -> Option<impl Iterator<Item = &str>> {
+    let list = sql
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("-- analyze:"))?;
+
+    Some(
+        list.split(',')
+            .map(str::trim)
+            .filter(|table| !table.is_empty()),
+    )
+}
+
+/// Unquoted identifiers are case-insensitive; quoted ones are compared verbatim.
+fn normalize(table: &str) -> String {
+    if table.contains(['"', '`']) {
+        table.to_owned()
+    } else {
+        table.to_ascii_lowercase()
+    }
+}
+
+#[derive(Default)]
+struct TouchedTables {
+    tables: Vec<String>,
+    seen: HashSet<String>,
+    excluded: HashSet<String>,
+}
+
+impl TouchedTables {
+    fn touch(&mut self, table: &str) {
+        if self.seen.insert(normalize(table)) {
+            self.tables.push(table.to_owned());
+        }
+    }
+
+    fn exclude(&mut self, table: &str) {
+        self.excluded.insert(normalize(table));
+    }
+
+    fn scan(&mut self, sql: &str) {
+        let words = words(sql);
+        let keyword =
+            |i: usize, kw: &str| words.get(i).map_or(false, |w| w.eq_ignore_ascii_case(kw));
+
+        // skips `IF [NOT] EXISTS` and `ONLY` and returns the index of the table name
+        let table_at = |mut i: usize| {
+            if keyword(i, "if") {
+                i += if keyword(i + 1, "not") { 3 } else { 2 };
+            }
+
+            if keyword(i, "only") {
+                i += 1;
+            }
+
+            words.get(i).map(String::as_str)
+        };
+
+        for i in 0..words.len() {
+            let prev = i.checked_sub(1).map_or("", |p| words[p].as_str());
+
+            let table = if keyword(i, "insert") && keyword(i + 1, "into")
+                || keyword(i, "delete") && keyword(i + 1, "from")
+                || keyword(i, "alter") && keyword(i + 1, "table")
+            {
+                table_at(i + 2)
+            } else if keyword(i, "create") && keyword(i + 1, "table") {
+                // a table that is re-created after being dropped should be analyzed again
+                if let Some(table) = table_at(i + 2) {
+                    self.excluded.remove(&normalize(table));
+                }
+
+                table_at(i + 2)
+            } else if keyword(i, "create")
+                && (keyword(i + 1, "temp") || keyword(i + 1, "temporary"))
+                && keyword(i + 2, "table")
+            {
+                if let Some(table) = table_at(i + 3) {
+                    self.exclude(table);
+                }
+                continue;
+            } else if keyword(i, "drop") && keyword(i + 1, "table") {
+                let mut j = i + 2;
+
+                if keyword(j, "if") {
+                    j += 2;
+                }
+
+                // `DROP TABLE a, b` is tokenized as `a` `,` `b`
+                while let Some(table) = words.get(j) {
+                    self.exclude(table);
+
+                    if words.get(j + 1).map_or(true, |w| w != ",") {
+                        break;
+                    }
+
+                    j += 2;
+                }
+                continue;
+            } else if keyword(i, "update")
+                // `ON UPDATE`, `FOR [NO KEY] UPDATE` and `DO UPDATE` don't modify a table by name
+                && !["on", "for", "key", "do"]
+                    .iter()
+                    .any(|kw| prev.eq_ignore_ascii_case(kw))
+            {
+                table_at(i + 1)
+            } else if keyword(i, "copy") {
+                words.get(i + 1).map(String::as_str)
+            } else {
+                None
+            };
+
+            if let Some(table) = table.filter(|table| is_identifier(table)) {
+                self.touch(table);
+            }
+        }
+    }
+
+    fn into_tables(self) -> Vec<String> {
+        let excluded = self.excluded;
+
+        self.tables
+            .into_iter()
+            .filter(|table| !excluded.contains(&normalize(table)))
+            .collect()
+    }
+}
+
+fn is_identifier(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '"' || c == '`')
+}
+
+/// Split SQL into identifiers (including dotted and quoted names) and commas,
+/// skipping comments, string literals and dollar-quoted bodies.
+fn words(sql: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut chars = sql.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let starts_comment = matches!((c, chars.peek()), ('-', Some('-')) | ('/', Some('*')));
+
+        if (starts_comment || c == '\'') && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+
+        match c {
+            '-' if chars.peek() == Some(&'-') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+
+            '\'' => {
+                // a doubled quote (`''`) inside a literal is handled by re-entering this arm
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                }
+            }
+
+            '"' | '`' => {
+                word.push(c);
+
+                for q in chars.by_ref() {
+                    word.push(q);
+
+                    if q == c {
+                        break;
+                    }
+                }
+            }
+
+            '$' if word.is_empty() => {
+                // dollar-quoted string, e.g. a function body: `$tag$ ... $tag$`
+                let mut tag = String::from('$');
+
+                while let Some(&t) = chars.peek() {
+                    if !(t == '$' || t.is_alphanumeric() || t == '_') {
+                        break;
+                    }
+
+                    chars.next();
+                    tag.push(t);
+
+                    if t == '$' {
+                        break;
+                    }
+                }
+
+                // otherwise this was a positional parameter like `$1`
+                if tag.len() >= 2 && tag.ends_with('$') {
+                    let mut body = String::new();
+
+                    for b in chars.by_ref() {
+                        body.push(b);
+
+                        if body.ends_with(&tag) {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            c if c.is_alphanumeric() || c == '_' || c == '$' || c == '.' => word.push(c),
+
+            c => {
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+
+                if c == ',' {
+                    words.push(",".into());
+                }
+            }
+        }
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migrate::MigrationType;
+
+    fn migration(sql: &'static str) -> Migration {
+        Migration::new(1, "test".into(), MigrationType::Simple, sql.into(), false)
+    }
+
+    #[test]
+    fn it_detects_touched_tables() {
+        let m = migration(
+            r#"
+-- update the comments table
+CREATE TABLE IF NOT EXISTS comments (
+    id BIGINT PRIMARY KEY,
+    post_id BIGINT REFERENCES posts (id) ON UPDATE CASCADE
+);
+INSERT INTO comments(id, post_id) SELECT id, id FROM posts;
+UPDATE ONLY public.posts SET title = 'INSERT INTO strings';
+DELETE FROM "Users" WHERE id NOT IN (SELECT id FROM accounts FOR UPDATE);
+ALTER TABLE comments ADD COLUMN body TEXT;
+COPY audit_log FROM STDIN;
+INSERT INTO comments VALUES (1, 1) ON CONFLICT (id) DO UPDATE SET post_id = 2;
+"#,
+        );
+
+        assert_eq!(
+            AnalyzeAfter::Touched.tables([&m]),
+            ["comments", "public.posts", "\"Users\"", "audit_log"]
+        );
+    }
+
+    #[test]
+    fn it_skips_dropped_and_temporary_tables() {
+        let m = migration(
+            r#"
+CREATE TEMPORARY TABLE staging (id BIGINT);
+INSERT INTO staging VALUES (1);
+INSERT INTO users SELECT * FROM staging;
+CREATE TABLE old_users (id BIGINT);
+DROP TABLE IF EXISTS old_users, Staging;
+CREATE FUNCTION f() RETURNS VOID AS $body$ INSERT INTO fn_table VALUES (1) $body$ LANGUAGE SQL;
+"#,
+        );
+
+        assert_eq!(AnalyzeAfter::Touched.tables([&m]), ["users"]);
+    }
+
+    #[test]
+    fn it_prefers_the_directive() {
+        let m = migration("-- analyze: users, orders\nINSERT INTO users VALUES (1);");
+        assert_eq!(AnalyzeAfter::Touched.tables([&m]), ["users", "orders"]);
+
+        let m = migration("-- analyze:\nINSERT INTO users VALUES (1);");
+        assert!(AnalyzeAfter::Touched.tables([&m]).is_empty());
+    }
+
+    #[test]
+    fn it_dedups_explicit_tables() {
+        let analyze =
+            AnalyzeAfter::Tables(vec!["users".into(), "USERS".into(), "orders".into()].into());
+
+        assert_eq!(analyze.tables([]), ["users", "orders"]);
+        assert!(AnalyzeAfter::Disabled.tables([]).is_empty());
+    }
+}
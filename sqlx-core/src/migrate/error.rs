@@ -30,6 +30,15 @@ pub enum MigrateError {
     #[error("database driver does not support force-dropping a database (Only PostgreSQL)")]
     ForceNotSupported,
 
+    #[error("database driver does not support setting the migration schema (Only PostgreSQL)")]
+    SchemaNotSupported,
+
+    #[error("invalid migration schema name {0:?}")]
+    InvalidSchema(String),
+
+    #[error("database driver does not support skipping migrations")]
+    SkipNotSupported,
+
     #[deprecated = "migration types are now inferred"]
     #[error("cannot mix reversible migrations with simple migrations. All migrations should be reversible or simple migrations")]
     InvalidMixReversibleAndSimple,
@@ -39,4 +48,7 @@ pub enum MigrateError {
         "migration {0} is partially applied; fix and remove row from `_sqlx_migrations` table"
     )]
     Dirty(i64),
+
+    #[error("found {} problem(s) with the migrations: {}", .0.len(), .0.join("; "))]
+    Invalid(Vec<String>),
 }
@@ -55,6 +55,69 @@ pub trait Migrate {
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>>;
 
+    // record a migration as applied without running it, with `skipped` set to TRUE
+    // adds the `skipped` column to [_migrations] if it does not exist yet
+    // drivers that can't record a skipped migration return `MigrateError::SkipNotSupported`
+    fn skip<'e: 'm, 'm>(
+        &'e mut self,
+        _migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async { Err(MigrateError::SkipNotSupported) })
+    }
+
+    // update planner statistics for the given tables
+    // [`Migrator`] calls this after applying migrations if `analyze_after` is enabled
+    // drivers without an `ANALYZE` equivalent may leave this as a no-op
+    fn analyze<'e: 'm, 'm>(
+        &'e mut self,
+        _tables: &'m [String],
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    // make `schema` the only schema in the search path, so that unqualified objects created by
+    // migrations and the migrations table itself are placed in it
+    // [`Migrator`] calls this before each migration if a schema is set
+    // drivers without a search path return `MigrateError::SchemaNotSupported`
+    fn set_schema<'e: 'm, 'm>(
+        &'e mut self,
+        _schema: &'m str,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async { Err(MigrateError::SchemaNotSupported) })
+    }
+
+    // restore the search path the connection was opened with
+    // [`Migrator`] calls this after running migrations if a schema is set
+    fn reset_schema(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    // set the statement timeout for the session, where zero means no timeout
+    // [`Migrator`] calls this before each migration if a statement timeout is set
+    // drivers without a statement timeout may leave this as a no-op
+    fn set_statement_timeout(
+        &mut self,
+        _timeout: Duration,
+    ) -> BoxFuture<'_, Result<(), MigrateError>> {
+        Box::pin(async { Ok(()) })
+    }
+
+    // the session's current statement timeout, in a form `restore_statement_timeout()` accepts
+    // [`Migrator`] calls this before running migrations if a statement timeout is set
+    // drivers without a statement timeout return `None`
+    fn statement_timeout(&mut self) -> BoxFuture<'_, Result<Option<String>, MigrateError>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    // restore a statement timeout returned by `statement_timeout()`
+    // [`Migrator`] calls this after running migrations, whether or not they succeeded
+    fn restore_statement_timeout<'e: 'm, 'm>(
+        &'e mut self,
+        _previous: &'m str,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async { Ok(()) })
+    }
+
     // run a revert SQL from migration in a DDL transaction
     // deletes the row in [_migrations] table with specified migration version on completion (success or failure)
     // returns the time taking to run the migration SQL
@@ -1,4 +1,5 @@
 use std::borrow::Cow;
+use std::time::Duration;
 
 use sha2::{Digest, Sha384};
 
@@ -39,4 +40,11 @@ impl Migration {
 pub struct AppliedMigration {
     pub version: i64,
     pub checksum: Cow<'static, [u8]>,
+    /// How long the migration took to apply, recorded with nanosecond precision.
+    ///
+    /// `None` if the time was never recorded, e.g. because the process died between
+    /// committing the migration and recording its time. Migrations that were skipped with
+    /// [`Migrator::skip_versions()`][super::Migrator::skip_versions] are recorded as taking no
+    /// time.
+    pub execution_time: Option<Duration>,
 }
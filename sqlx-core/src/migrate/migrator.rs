@@ -1,9 +1,15 @@
 use crate::acquire::Acquire;
-use crate::migrate::{AppliedMigration, Migrate, MigrateError, Migration, MigrationSource};
+use crate::migrate::{
+    AnalyzeAfter, AppliedMigration, Migrate, MigrateError, Migration, MigrationProgress,
+    MigrationSource, MigrationStage, MigrationType,
+};
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Debug, Formatter};
 use std::ops::Deref;
 use std::slice;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// A resolved set of migrations, ready to be run.
 ///
@@ -23,6 +29,69 @@ pub struct Migrator {
     pub locking: bool,
     #[doc(hidden)]
     pub no_tx: bool,
+    #[doc(hidden)]
+    pub analyze_after: AnalyzeAfter,
+    #[doc(hidden)]
+    pub schema: Option<Cow<'static, str>>,
+    #[doc(hidden)]
+    pub statement_timeout: Option<Duration>,
+    #[doc(hidden)]
+    pub skip_versions: Cow<'static, [i64]>,
+    #[doc(hidden)]
+    pub on_applied: Option<MigrationTimingHook>,
+}
+
+/// A function called with the time each migration took; see [`Migrator::set_on_applied()`].
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct MigrationTimingHook(Arc<dyn Fn(&Migration, Duration) + Send + Sync>);
+
+impl Debug for MigrationTimingHook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MigrationTimingHook").finish_non_exhaustive()
+    }
+}
+
+/// Creates a new instance from migrations built in code, e.g. by a schema-diff tool at runtime,
+/// instead of read from files.
+///
+/// The migrations are sorted by version. Each one is run like a migration from a file with the
+/// same version and contents would be; see [`Migration::new()`], which computes the checksum.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use sqlx::migrate::MigrateError;
+/// # fn main() -> Result<(), MigrateError> {
+/// #     sqlx::__rt::test_block_on(async move {
+/// use sqlx::migrate::{Migration, MigrationType, Migrator};
+/// use sqlx::sqlite::SqlitePoolOptions;
+///
+/// let m = Migrator::from_iter([Migration::new(
+///     1,
+///     "create users".into(),
+///     MigrationType::Simple,
+///     "CREATE TABLE users (id INTEGER PRIMARY KEY)".into(),
+///     false,
+/// )]);
+///
+/// let pool = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+/// m.run(&pool).await
+/// #     })
+/// # }
+/// ```
+impl FromIterator<Migration> for Migrator {
+    fn from_iter<I: IntoIterator<Item = Migration>>(iter: I) -> Self {
+        let mut migrations: Vec<Migration> = iter.into_iter().collect();
+
+        // the same order as migrations read from a directory
+        migrations.sort_by_key(|m| m.version);
+
+        Self {
+            migrations: Cow::Owned(migrations),
+            ..Self::DEFAULT
+        }
+    }
 }
 
 fn validate_applied_migrations(
@@ -44,6 +113,16 @@ fn validate_applied_migrations(
     Ok(())
 }
 
+fn is_valid_schema(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    name.len() <= 63
+        && chars
+            .next()
+            .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
 impl Migrator {
     #[doc(hidden)]
     pub const DEFAULT: Migrator = Migrator {
@@ -51,6 +130,11 @@ impl Migrator {
         ignore_missing: false,
         no_tx: false,
         locking: true,
+        analyze_after: AnalyzeAfter::Disabled,
+        schema: None,
+        statement_timeout: Some(Duration::ZERO),
+        skip_versions: Cow::Borrowed(&[]),
+        on_applied: None,
     };
 
     /// Creates a new instance with the given source.
@@ -100,6 +184,94 @@ impl Migrator {
         self
     }
 
+    /// Specify whether to `ANALYZE` tables after applying migrations. Defaults to
+    /// [`AnalyzeAfter::Disabled`].
+    ///
+    /// Nothing is analyzed if there were no pending migrations.
+    /// See [`AnalyzeAfter`] for how touched tables are detected.
+    pub fn set_analyze_after(&mut self, analyze_after: AnalyzeAfter) -> &Self {
+        self.analyze_after = analyze_after;
+        self
+    }
+
+    /// Specify the schema to run migrations in and to keep the migrations table in.
+    ///
+    /// Before each migration the search path is set to only this schema, so the result does
+    /// not depend on the default search path of the connecting user. Afterwards the search path
+    /// is reset to the one the connection was opened with, also when a migration fails.
+    ///
+    /// The schema must already exist. Its name is quoted, so it is case-sensitive, and it must
+    /// be an ASCII letter or `_` followed by up to 62 ASCII letters, digits, `_` or `$`.
+    ///
+    /// For `migrate!()`, this can also be set with `migrate.schema` in `sqlx.toml`.
+    ///
+    /// Only supported on Postgres; on MySQL, choose the database in the connection URL instead.
+    pub fn set_schema(
+        &mut self,
+        schema: impl Into<Cow<'static, str>>,
+    ) -> Result<&Self, MigrateError> {
+        let schema = schema.into();
+
+        if !is_valid_schema(&schema) {
+            return Err(MigrateError::InvalidSchema(schema.into_owned()));
+        }
+
+        self.schema = Some(schema);
+        Ok(self)
+    }
+
+    /// Specify the statement timeout to run migrations with, where [`Duration::ZERO`] means no
+    /// timeout. Defaults to `Some(Duration::ZERO)`, so that long-running DDL is not aborted by a
+    /// `statement_timeout` meant for the application.
+    ///
+    /// The timeout is set for the session before each migration, rather than with
+    /// `SET LOCAL`, so that it also applies to migrations that opt out of running in a
+    /// transaction. Afterwards, whether or not the migrations succeeded, the connection's
+    /// previous statement timeout is restored.
+    ///
+    /// Pass `None` to leave the connection's statement timeout alone.
+    ///
+    /// This can also be set with `migrate.statement-timeout` in `sqlx.toml`, in seconds, or
+    /// `false` for `None`.
+    ///
+    /// Only has an effect on Postgres.
+    pub fn set_statement_timeout(&mut self, statement_timeout: Option<Duration>) -> &Self {
+        self.statement_timeout = statement_timeout;
+        self
+    }
+
+    /// Specify versions of pending migrations to record as applied without running them.
+    ///
+    /// This is an escape hatch for getting a deploy unstuck when a migration is known to be
+    /// broken. Each skipped migration is logged as a warning and recorded in the migrations
+    /// table with `skipped` set to `TRUE` (the column is added the first time a migration is
+    /// skipped). Afterwards it counts as applied like any other, including for
+    /// [`undo()`][Self::undo]; to run it after all, delete its row from the migrations table.
+    ///
+    /// Running migrations fails with [`MigrateError::VersionNotPresent`], before touching the
+    /// database, if a version is not one of the resolved migrations. Versions that were already
+    /// applied are left alone. Drivers that can't record a skipped migration fail with
+    /// [`MigrateError::SkipNotSupported`].
+    pub fn skip_versions(&mut self, versions: &[i64]) -> &Self {
+        self.skip_versions = Cow::Owned(versions.to_vec());
+        self
+    }
+
+    /// Call `f` with each migration and the time it took, after it is applied by
+    /// [`run()`][Self::run] or reverted by [`undo()`][Self::undo], e.g. to record a metric
+    /// and find the migrations that are slow across deploys.
+    ///
+    /// The time is measured by the database driver with nanosecond precision. For an applied
+    /// migration, it is the time recorded in the migrations table (see
+    /// [`AppliedMigration::execution_time`]). Skipped migrations are not reported.
+    pub fn set_on_applied(
+        &mut self,
+        f: impl Fn(&Migration, Duration) + Send + Sync + 'static,
+    ) -> &Self {
+        self.on_applied = Some(MigrationTimingHook(Arc::new(f)));
+        self
+    }
+
     /// Get an iterator over all known migrations.
     pub fn iter(&self) -> slice::Iter<'_, Migration> {
         self.migrations.iter()
@@ -110,9 +282,111 @@ impl Migrator {
         self.iter().any(|m| m.version == version)
     }
 
+    /// Check the resolved migrations for mistakes, without connecting to a database.
+    ///
+    /// This checks that:
+    ///
+    /// * no version is negative, and no version is used by more than one migration of the
+    ///   same type;
+    /// * every migration has a description;
+    /// * no version is used by both a simple and a reversible migration;
+    /// * every up migration has a down migration with the same description, and vice versa.
+    ///
+    /// All problems found are listed in the returned [`MigrateError::Invalid`], which makes
+    /// this suitable for CI or a pre-commit hook.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use sqlx::migrate::MigrateError;
+    /// # fn main() -> Result<(), MigrateError> {
+    /// #     sqlx::__rt::test_block_on(async move {
+    /// use sqlx::migrate::Migrator;
+    ///
+    /// let m = Migrator::new(std::path::Path::new("./migrations")).await?;
+    /// m.validate_offline()
+    /// #     })
+    /// # }
+    /// ```
+    pub fn validate_offline(&self) -> Result<(), MigrateError> {
+        let mut by_version: BTreeMap<i64, Vec<&Migration>> = BTreeMap::new();
+
+        for migration in self.iter() {
+            by_version
+                .entry(migration.version)
+                .or_default()
+                .push(migration);
+        }
+
+        let mut problems = Vec::new();
+
+        for (version, migrations) in by_version {
+            if version < 0 {
+                problems.push(format!("migration {version} has a negative version"));
+            }
+
+            if migrations.iter().any(|m| m.description.trim().is_empty()) {
+                problems.push(format!("migration {version} has no description"));
+            }
+
+            let of_type = |migration_type| {
+                migrations
+                    .iter()
+                    .filter(move |m| m.migration_type == migration_type)
+            };
+
+            let simple = of_type(MigrationType::Simple).count();
+            let up = of_type(MigrationType::ReversibleUp).count();
+            let down = of_type(MigrationType::ReversibleDown).count();
+
+            for (count, kind) in [(simple, "simple"), (up, "up"), (down, "down")] {
+                if count > 1 {
+                    problems.push(format!(
+                        "version {version} is used by {count} {kind} migrations"
+                    ));
+                }
+            }
+
+            if simple > 0 && up + down > 0 {
+                problems.push(format!(
+                    "version {version} is used by both simple and reversible migrations"
+                ));
+            } else if up > 0 && down == 0 {
+                problems.push(format!(
+                    "reversible migration {version} has no down migration"
+                ));
+            } else if down > 0 && up == 0 {
+                problems.push(format!(
+                    "reversible migration {version} has no up migration"
+                ));
+            } else if let (Some(up), Some(down)) = (
+                of_type(MigrationType::ReversibleUp).next(),
+                of_type(MigrationType::ReversibleDown).next(),
+            ) {
+                if up.description != down.description {
+                    problems.push(format!(
+                        "reversible migration {version} has up migration {:?} but down migration {:?}",
+                        up.description, down.description
+                    ));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(MigrateError::Invalid(problems))
+        }
+    }
+
     /// Run any pending migrations against the database; and, validate previously applied migrations
     /// against the current migration source to detect accidental changes in previously-applied migrations.
     ///
+    /// This fails on a pool with
+    /// [`require_transaction_for_writes`][crate::pool::PoolOptions::require_transaction_for_writes]
+    /// enabled, since the migrations table is created outside of a transaction; use a separate
+    /// connection or pool.
+    ///
     /// # Examples
     ///
     /// ```rust,no_run
@@ -137,17 +411,100 @@ impl Migrator {
         self.run_direct(&mut *conn).await
     }
 
+    /// Like [`run()`][Self::run], but calls `progress` before and after each migration that is
+    /// applied, e.g. to show a progress bar.
+    ///
+    /// `progress` is not called for migrations that were applied already.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use sqlx::migrate::MigrateError;
+    /// # fn main() -> Result<(), MigrateError> {
+    /// #     sqlx::__rt::test_block_on(async move {
+    /// use sqlx::migrate::{MigrationStage, Migrator};
+    /// use sqlx::sqlite::SqlitePoolOptions;
+    ///
+    /// let m = Migrator::new(std::path::Path::new("./migrations")).await?;
+    /// let pool = SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    /// m.run_with_progress(&pool, |progress| {
+    ///     if progress.stage == MigrationStage::Finished {
+    ///         println!(
+    ///             "[{}/{}] {} ({:?})",
+    ///             progress.index + 1,
+    ///             progress.total,
+    ///             progress.migration.description,
+    ///             progress.elapsed
+    ///         );
+    ///     }
+    /// })
+    /// .await
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn run_with_progress<'a, A, F>(
+        &self,
+        migrator: A,
+        progress: F,
+    ) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate,
+        F: Fn(MigrationProgress<'_>),
+    {
+        let mut conn = migrator.acquire().await?;
+        self.run_direct_with_progress(&mut *conn, progress).await
+    }
+
     // Getting around the annoying "implementation of `Acquire` is not general enough" error
     #[doc(hidden)]
     pub async fn run_direct<C>(&self, conn: &mut C) -> Result<(), MigrateError>
     where
         C: Migrate,
     {
+        self.run_direct_with_progress(conn, |_| {}).await
+    }
+
+    #[doc(hidden)]
+    pub async fn run_direct_with_progress<C, F>(
+        &self,
+        conn: &mut C,
+        progress: F,
+    ) -> Result<(), MigrateError>
+    where
+        C: Migrate,
+        F: Fn(MigrationProgress<'_>),
+    {
+        // check this before taking the lock, which a mistake here would otherwise hold
+        self.validate_skip_versions()?;
+
         // lock the database for exclusive access by the migrator
         if self.locking {
             conn.lock().await?;
         }
 
+        let timeout = self.save_statement_timeout(conn).await?;
+        let res = self.run_locked(conn, progress).await;
+        self.reset_session(conn, timeout, res).await?;
+
+        // unlock the migrator to allow other migrators to run
+        // but do nothing as we already migrated
+        if self.locking {
+            conn.unlock().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn run_locked<C, F>(&self, conn: &mut C, progress: F) -> Result<(), MigrateError>
+    where
+        C: Migrate,
+        F: Fn(MigrationProgress<'_>),
+    {
+        if let Some(schema) = &self.schema {
+            conn.set_schema(schema).await?;
+        }
+
         // creates [_migrations] table only if needed
         // eventually this will likely migrate previous versions of the table
         conn.ensure_migrations_table().await?;
@@ -165,6 +522,17 @@ impl Migrator {
             .map(|m| (m.version, m))
             .collect();
 
+        let mut newly_applied = Vec::new();
+
+        let total = self
+            .iter()
+            .filter(|migration| {
+                !migration.migration_type.is_down_migration()
+                    && !applied_migrations.contains_key(&migration.version)
+                    && !self.skip_versions.contains(&migration.version)
+            })
+            .count();
+
         for migration in self.iter() {
             if migration.migration_type.is_down_migration() {
                 continue;
@@ -176,16 +544,64 @@ impl Migrator {
                         return Err(MigrateError::VersionMismatch(migration.version));
                     }
                 }
+                None if self.skip_versions.contains(&migration.version) => {
+                    tracing::warn!(
+                        version = migration.version,
+                        description = &*migration.description,
+                        "SKIPPING migration {} ({}) as requested: it is recorded as applied \
+                         but was NOT run",
+                        migration.version,
+                        migration.description,
+                    );
+
+                    if let Some(schema) = &self.schema {
+                        conn.set_schema(schema).await?;
+                    }
+
+                    conn.skip(migration).await?;
+                }
                 None => {
-                    conn.apply(migration).await?;
+                    // a migration may have changed the search path
+                    if let Some(schema) = &self.schema {
+                        conn.set_schema(schema).await?;
+                    }
+
+                    if let Some(timeout) = self.statement_timeout {
+                        conn.set_statement_timeout(timeout).await?;
+                    }
+
+                    let mut report = MigrationProgress {
+                        migration,
+                        index: newly_applied.len(),
+                        total,
+                        stage: MigrationStage::Starting,
+                        elapsed: Duration::ZERO,
+                    };
+                    progress(report);
+
+                    report.elapsed = conn.apply(migration).await?;
+                    report.stage = MigrationStage::Finished;
+                    progress(report);
+
+                    if let Some(MigrationTimingHook(f)) = &self.on_applied {
+                        f(migration, report.elapsed);
+                    }
+
+                    newly_applied.push(migration);
                 }
             }
         }
 
-        // unlock the migrator to allow other migrators to run
-        // but do nothing as we already migrated
-        if self.locking {
-            conn.unlock().await?;
+        if !newly_applied.is_empty() {
+            let tables = self.analyze_after.tables(newly_applied);
+
+            if !tables.is_empty() {
+                if let Some(schema) = &self.schema {
+                    conn.set_schema(schema).await?;
+                }
+
+                conn.analyze(&tables).await?;
+            }
         }
 
         Ok(())
@@ -220,6 +636,24 @@ impl Migrator {
             conn.lock().await?;
         }
 
+        let timeout = self.save_statement_timeout(&mut *conn).await?;
+        let res = self.undo_locked(&mut *conn, target).await;
+        self.reset_session(&mut *conn, timeout, res).await?;
+
+        // unlock the migrator to allow other migrators to run
+        // but do nothing as we already migrated
+        if self.locking {
+            conn.unlock().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn undo_locked<C: Migrate>(&self, conn: &mut C, target: i64) -> Result<(), MigrateError> {
+        if let Some(schema) = &self.schema {
+            conn.set_schema(schema).await?;
+        }
+
         // creates [_migrations] table only if needed
         // eventually this will likely migrate previous versions of the table
         conn.ensure_migrations_table().await?;
@@ -244,15 +678,179 @@ impl Migrator {
             .filter(|m| applied_migrations.contains_key(&m.version))
             .filter(|m| m.version > target)
         {
-            conn.revert(migration).await?;
+            if let Some(schema) = &self.schema {
+                conn.set_schema(schema).await?;
+            }
+
+            if let Some(timeout) = self.statement_timeout {
+                conn.set_statement_timeout(timeout).await?;
+            }
+
+            let elapsed = conn.revert(migration).await?;
+
+            if let Some(MigrationTimingHook(f)) = &self.on_applied {
+                f(migration, elapsed);
+            }
         }
 
-        // unlock the migrator to allow other migrators to run
-        // but do nothing as we already migrated
-        if self.locking {
-            conn.unlock().await?;
+        Ok(())
+    }
+
+    fn validate_skip_versions(&self) -> Result<(), MigrateError> {
+        for &version in self.skip_versions.iter() {
+            if !self
+                .iter()
+                .any(|m| m.version == version && !m.migration_type.is_down_migration())
+            {
+                return Err(MigrateError::VersionNotPresent(version));
+            }
         }
 
         Ok(())
     }
+
+    // The statement timeout to restore afterwards, if the migrations will change it.
+    async fn save_statement_timeout<C: Migrate>(
+        &self,
+        conn: &mut C,
+    ) -> Result<Option<String>, MigrateError> {
+        match self.statement_timeout {
+            Some(_) => conn.statement_timeout().await,
+            None => Ok(None),
+        }
+    }
+
+    // Restore the search path and statement timeout changed for the migrations, whether or not
+    // running them succeeded. Their error takes precedence over one from restoring the session.
+    async fn reset_session<C: Migrate>(
+        &self,
+        conn: &mut C,
+        timeout: Option<String>,
+        res: Result<(), MigrateError>,
+    ) -> Result<(), MigrateError> {
+        let reset_schema = match self.schema {
+            Some(_) => conn.reset_schema().await,
+            None => Ok(()),
+        };
+
+        let reset_timeout = match &timeout {
+            Some(timeout) => conn.restore_statement_timeout(timeout).await,
+            None => Ok(()),
+        };
+
+        res.and(reset_schema).and(reset_timeout)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migrator(migrations: &[(i64, &'static str, MigrationType)]) -> Migrator {
+        Migrator {
+            migrations: migrations
+                .iter()
+                .map(|&(version, description, migration_type)| {
+                    Migration::new(
+                        version,
+                        description.into(),
+                        migration_type,
+                        "SELECT 1".into(),
+                        false,
+                    )
+                })
+                .collect(),
+            ..Migrator::DEFAULT
+        }
+    }
+
+    #[test]
+    fn it_validates_migrations_offline() {
+        use MigrationType::*;
+
+        migrator(&[
+            (1, "create users", Simple),
+            (2, "add email", ReversibleUp),
+            (2, "add email", ReversibleDown),
+        ])
+        .validate_offline()
+        .unwrap();
+
+        let err = migrator(&[
+            (-1, "negative", Simple),
+            (1, "create users", Simple),
+            (1, "create posts", Simple),
+            (2, "", Simple),
+            (3, "add email", ReversibleUp),
+            (4, "add name", ReversibleDown),
+            (5, "add age", ReversibleUp),
+            (5, "add birthday", ReversibleDown),
+            (6, "mixed", Simple),
+            (6, "mixed", ReversibleDown),
+        ])
+        .validate_offline()
+        .unwrap_err();
+
+        let MigrateError::Invalid(problems) = err else {
+            panic!("unexpected error: {err:?}");
+        };
+
+        assert_eq!(
+            problems,
+            [
+                "migration -1 has a negative version",
+                "version 1 is used by 2 simple migrations",
+                "migration 2 has no description",
+                "reversible migration 3 has no down migration",
+                "reversible migration 4 has no up migration",
+                "reversible migration 5 has up migration \"add age\" but down migration \"add birthday\"",
+                "version 6 is used by both simple and reversible migrations",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_validates_the_schema_name() {
+        let mut m = Migrator::DEFAULT;
+
+        for valid in ["app", "_app", "App_2", "app$"] {
+            m.set_schema(valid).unwrap();
+        }
+
+        for invalid in [
+            "",
+            "2app",
+            "app-data",
+            "app\"; DROP",
+            "\u{e9}t\u{e9}",
+            &"a".repeat(64),
+        ] {
+            assert!(matches!(
+                m.set_schema(invalid.to_owned()),
+                Err(MigrateError::InvalidSchema(_))
+            ));
+        }
+
+        assert_eq!(m.schema.as_deref(), Some("app$"));
+    }
+
+    #[test]
+    fn it_validates_skipped_versions() {
+        use MigrationType::*;
+
+        let mut m = migrator(&[
+            (1, "create users", ReversibleUp),
+            (1, "create users", ReversibleDown),
+            (2, "add email", Simple),
+        ]);
+
+        m.skip_versions(&[1, 2]);
+        m.validate_skip_versions().unwrap();
+
+        m.skip_versions(&[2, 3]);
+        assert!(matches!(
+            m.validate_skip_versions(),
+            Err(MigrateError::VersionNotPresent(3))
+        ));
+    }
 }
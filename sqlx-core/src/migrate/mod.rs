@@ -1,16 +1,20 @@
+mod analyze;
 mod error;
 #[allow(clippy::module_inception)]
 mod migrate;
 mod migration;
 mod migration_type;
 mod migrator;
+mod progress;
 mod source;
 
+pub use analyze::AnalyzeAfter;
 pub use error::MigrateError;
 pub use migrate::{Migrate, MigrateDatabase};
 pub use migration::{AppliedMigration, Migration};
 pub use migration_type::MigrationType;
 pub use migrator::Migrator;
+pub use progress::{MigrationProgress, MigrationStage};
 pub use source::MigrationSource;
 
 #[doc(hidden)]
@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+use crate::migrate::Migration;
+
+/// The progress of [`Migrator::run_with_progress()`][crate::migrate::Migrator::run_with_progress],
+/// reported before and after each migration that is applied.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub struct MigrationProgress<'a> {
+    /// The migration being applied.
+    pub migration: &'a Migration,
+
+    /// The position of [`migration`][Self::migration] among the pending migrations,
+    /// starting at 0.
+    pub index: usize,
+
+    /// The number of pending migrations in this run.
+    pub total: usize,
+
+    /// Whether the migration is about to be applied or has been applied.
+    pub stage: MigrationStage,
+
+    /// How long the migration took to apply, as measured by the database driver.
+    ///
+    /// [`Duration::ZERO`] for [`MigrationStage::Starting`].
+    pub elapsed: Duration,
+}
+
+/// When a [`MigrationProgress`] is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationStage {
+    /// The migration is about to be applied.
+    Starting,
+
+    /// The migration has been applied.
+    Finished,
+}
@@ -1,6 +1,8 @@
 mod socket;
 pub mod tls;
 
+#[cfg(unix)]
+pub use socket::connect_fd;
 pub use socket::{
     connect_tcp, connect_uds, BufferedSocket, Socket, SocketIntoBox, WithSocket, WriteBuffer,
 };
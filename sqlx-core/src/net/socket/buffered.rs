@@ -5,6 +5,8 @@ use std::ops::ControlFlow;
 use std::{cmp, io};
 
 use crate::io::{AsyncRead, AsyncReadExt, ProtocolDecode, ProtocolEncode};
+#[cfg(feature = "wire-trace")]
+use crate::wire_trace::{Direction, WireTrace};
 
 // Tokio, async-std, and std all use this as the default capacity for their buffered I/O.
 const DEFAULT_BUF_SIZE: usize = 8192;
@@ -13,6 +15,8 @@ pub struct BufferedSocket<S> {
     socket: S,
     write_buf: WriteBuffer,
     read_buf: ReadBuffer,
+    #[cfg(feature = "wire-trace")]
+    wire_trace: Option<WireTrace>,
 }
 
 pub struct WriteBuffer {
@@ -42,6 +46,31 @@ impl<S: Socket> BufferedSocket<S> {
                 read: BytesMut::new(),
                 available: BytesMut::with_capacity(DEFAULT_BUF_SIZE),
             },
+            #[cfg(feature = "wire-trace")]
+            wire_trace: None,
+        }
+    }
+
+    /// Call `trace` with every message written to or received from this socket.
+    ///
+    /// Written messages are traced automatically; drivers must trace the messages they read
+    /// using [`.wire_trace()`][Self::wire_trace].
+    #[cfg(feature = "wire-trace")]
+    pub fn set_wire_trace(&mut self, trace: Option<WireTrace>) {
+        self.wire_trace = trace;
+    }
+
+    #[cfg(feature = "wire-trace")]
+    pub fn wire_trace(&self) -> Option<&WireTrace> {
+        self.wire_trace.as_ref()
+    }
+
+    /// Trace bytes that were written to the buffer directly through
+    /// [`.write_buffer_mut()`][Self::write_buffer_mut].
+    #[cfg(feature = "wire-trace")]
+    pub fn trace_sent(&self, bytes: &[u8]) {
+        if let Some(trace) = &self.wire_trace {
+            trace.trace(Direction::Sent, bytes);
         }
     }
 
@@ -115,10 +144,18 @@ impl<S: Socket> BufferedSocket<S> {
     where
         T: ProtocolEncode<'en, C>,
     {
+        #[cfg(feature = "wire-trace")]
+        let start = self.write_buf.bytes_written;
+
         value.encode_with(self.write_buf.buf_mut(), context)?;
         self.write_buf.bytes_written = self.write_buf.buf.len();
         self.write_buf.sanity_check();
 
+        #[cfg(feature = "wire-trace")]
+        if let Some(trace) = &self.wire_trace {
+            trace.trace(Direction::Sent, &self.write_buf.buf[start..]);
+        }
+
         Ok(())
     }
 
@@ -145,6 +182,10 @@ impl<S: Socket> BufferedSocket<S> {
         self.read_buf.shrink();
     }
 
+    pub fn socket(&self) -> &S {
+        &self.socket
+    }
+
     pub fn into_inner(self) -> S {
         self.socket
     }
@@ -154,6 +195,8 @@ impl<S: Socket> BufferedSocket<S> {
             socket: Box::new(self.socket),
             write_buf: self.write_buf,
             read_buf: self.read_buf,
+            #[cfg(feature = "wire-trace")]
+            wire_trace: self.wire_trace,
         }
     }
 }
@@ -29,6 +29,11 @@ pub trait Socket: Send + Sync + Unpin + 'static {
 
     fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
 
+    /// The DER-encoded certificate the server presented, if this is a TLS connection.
+    fn peer_certificate(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     fn read<'a, B: ReadBuf>(&'a mut self, buf: &'a mut B) -> Read<'a, Self, B>
     where
         Self: Sized,
@@ -180,6 +185,10 @@ impl<S: Socket + ?Sized> Socket for Box<S> {
     fn poll_shutdown(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         (**self).poll_shutdown(cx)
     }
+
+    fn peer_certificate(&self) -> Option<Vec<u8>> {
+        (**self).peer_certificate()
+    }
 }
 
 pub async fn connect_tcp<Ws: WithSocket>(
@@ -285,3 +294,37 @@ pub async fn connect_uds<P: AsRef<Path>, Ws: WithSocket>(
         .into())
     }
 }
+
+/// Use a socket that is already connected, such as one inherited from a supervising process.
+///
+/// The file descriptor must refer to a connected stream socket (TCP or Unix domain); it is
+/// switched to non-blocking mode and closed when the returned socket is dropped.
+#[cfg(unix)]
+pub async fn connect_fd<Ws: WithSocket>(
+    fd: std::os::fd::OwnedFd,
+    with_socket: Ws,
+) -> crate::Result<Ws::Output> {
+    // Reads and writes on a stream socket don't depend on its address family, so the `UnixStream`
+    // types serve for TCP sockets as well.
+    let stream = std::os::unix::net::UnixStream::from(fd);
+    stream.set_nonblocking(true)?;
+
+    #[cfg(feature = "_rt-tokio")]
+    if crate::rt::rt_tokio::available() {
+        let stream = tokio::net::UnixStream::from_std(stream)?;
+
+        return Ok(with_socket.with_socket(stream));
+    }
+
+    #[cfg(feature = "_rt-async-std")]
+    {
+        let stream = async_io::Async::new(stream)?;
+
+        Ok(with_socket.with_socket(stream))
+    }
+
+    #[cfg(not(feature = "_rt-async-std"))]
+    {
+        crate::rt::missing_rt((stream, with_socket))
+    }
+}
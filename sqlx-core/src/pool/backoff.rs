@@ -0,0 +1,174 @@
+use std::cmp;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+/// How the pool spaces out attempts to open connections while the database is unreachable;
+/// see [`PoolOptions::reconnect_backoff()`][super::PoolOptions::reconnect_backoff].
+///
+/// The backoff is shared by the whole pool: after a failed attempt, every task that needs a new
+/// connection waits for its turn, and the turns are spaced by a delay that grows with each
+/// failure. A successful connection resets the delay.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectBackoff {
+    initial_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+}
+
+impl ReconnectBackoff {
+    /// Wait 100 milliseconds after the first failure and up to 30 seconds after later ones,
+    /// with jitter.
+    pub fn new() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+
+    /// Set the delay after the first failed attempt.
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Set the maximum delay between attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set whether to randomly shorten each delay by up to half. Defaults to `true`.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// The delay after `failures` consecutive failed attempts.
+    fn delay(&self, failures: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(failures.saturating_sub(1));
+        let delay = cmp::min(self.initial_delay.saturating_mul(factor), self.max_delay);
+
+        if self.jitter {
+            rand::thread_rng().gen_range(delay / 2..=delay)
+        } else {
+            delay
+        }
+    }
+}
+
+impl Default for ReconnectBackoff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The state of a [`ReconnectBackoff`] shared by all connection attempts of a pool.
+pub(super) struct ReconnectCoordinator {
+    backoff: ReconnectBackoff,
+    state: Mutex<BackoffState>,
+}
+
+#[derive(Default)]
+struct BackoffState {
+    // consecutive failed attempts; zero while the database is reachable
+    failures: u32,
+    // the earliest time the next attempt may start
+    next_attempt: Option<Instant>,
+}
+
+/// A connection attempt claimed with [`ReconnectCoordinator::begin()`].
+pub(super) struct ReconnectAttempt {
+    // when the attempt may start, or `None` to start right away
+    pub(super) start_at: Option<Instant>,
+    // the number of failures when the attempt was claimed
+    failures: u32,
+}
+
+impl ReconnectCoordinator {
+    pub(super) fn new(backoff: ReconnectBackoff) -> Self {
+        Self {
+            backoff,
+            state: Mutex::new(BackoffState::default()),
+        }
+    }
+
+    /// Claim the next turn to attempt a connection.
+    ///
+    /// While the database is reachable every attempt starts right away. After a failure, each
+    /// attempt is given its own turn, one delay after the previous one.
+    pub(super) fn begin(&self) -> ReconnectAttempt {
+        let mut state = self.state.lock().unwrap();
+
+        let Some(next_attempt) = state.next_attempt else {
+            return ReconnectAttempt {
+                start_at: None,
+                failures: state.failures,
+            };
+        };
+
+        let start_at = cmp::max(next_attempt, Instant::now());
+        state.next_attempt = Some(start_at + self.backoff.delay(state.failures));
+
+        ReconnectAttempt {
+            start_at: Some(start_at),
+            failures: state.failures,
+        }
+    }
+
+    pub(super) fn succeeded(&self) {
+        *self.state.lock().unwrap() = BackoffState::default();
+    }
+
+    pub(super) fn failed(&self, attempt: &ReconnectAttempt) {
+        let mut state = self.state.lock().unwrap();
+
+        // Attempts that were in flight together only count as one failure, so a pool of
+        // connections failing at once doesn't jump straight to the maximum delay.
+        if attempt.failures != state.failures {
+            return;
+        }
+
+        state.failures = state.failures.saturating_add(1);
+
+        let retry_at = Instant::now() + self.backoff.delay(state.failures);
+        state.next_attempt = Some(state.next_attempt.map_or(retry_at, |at| at.max(retry_at)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_attempts_are_spaced_out() {
+        let backoff = ReconnectBackoff::new()
+            .initial_delay(Duration::from_secs(1))
+            .jitter(false);
+        let coordinator = ReconnectCoordinator::new(backoff);
+
+        // Concurrent attempts while the database is reachable aren't delayed.
+        let first = coordinator.begin();
+        let second = coordinator.begin();
+        assert!(first.start_at.is_none() && second.start_at.is_none());
+
+        // ...and only count as one failure.
+        coordinator.failed(&first);
+        coordinator.failed(&second);
+
+        let third = coordinator.begin();
+        let fourth = coordinator.begin();
+        let third_at = third.start_at.unwrap();
+        assert_eq!(fourth.start_at.unwrap() - third_at, Duration::from_secs(1));
+
+        // A failure after that doubles the delay.
+        coordinator.failed(&third);
+        let fifth = coordinator.begin();
+        assert!(fifth.start_at.unwrap() >= third_at + Duration::from_secs(2));
+
+        coordinator.succeeded();
+        assert!(coordinator.begin().start_at.is_none());
+    }
+}
@@ -5,12 +5,12 @@ use std::time::{Duration, Instant};
 
 use crate::sync::AsyncSemaphoreReleaser;
 
-use crate::connection::Connection;
+use crate::connection::{CancelHandle, Connection};
 use crate::database::Database;
 use crate::error::Error;
 
-use super::inner::{is_beyond_max_lifetime, DecrementSizeGuard, PoolInner};
-use crate::pool::options::PoolConnectionMetadata;
+use super::inner::{is_beyond_max_lifetime, is_beyond_max_queries, DecrementSizeGuard, PoolInner};
+use crate::pool::options::{PoolConnectionMetadata, ResetOnReturn};
 use std::future::Future;
 
 const CLOSE_ON_DROP_TIMEOUT: Duration = Duration::from_secs(5);
@@ -27,6 +27,9 @@ pub struct PoolConnection<DB: Database> {
 pub(super) struct Live<DB: Database> {
     pub(super) raw: DB::Connection,
     pub(super) created_at: Instant,
+    pub(super) cancel_handle: Option<Arc<dyn CancelHandle>>,
+    // how many of `PoolOptions::prepare_statements` have been prepared on this connection
+    pub(super) prepared_statements: usize,
 }
 
 pub(super) struct Idle<DB: Database> {
@@ -88,6 +91,26 @@ impl<DB: Database> PoolConnection<DB> {
         floating.inner.raw.close().await
     }
 
+    /// Close this connection and have the pool open a fresh one in its place.
+    ///
+    /// Use this to discard a connection that may be in a bad state (e.g. after recovering from
+    /// a protocol error), where dropping it would return it to the pool.
+    ///
+    /// The connection permit is handed over to the replacement, so the pool keeps its size
+    /// without exceeding `max_connections`. This returns once the connection is closed;
+    /// the replacement is opened in a background task and put in the idle queue.
+    /// If it can't be opened, the pool opens a connection on demand as usual.
+    pub async fn close_and_replace(mut self) -> Result<(), Error> {
+        let Floating { inner: live, guard } = self.take_live().float(self.pool.clone());
+
+        let res = live.raw.close().await;
+
+        let pool = self.pool.clone();
+        crate::rt::spawn(async move { pool.replace(guard).await });
+
+        res
+    }
+
     /// Close this connection on-drop, instead of returning it to the pool.
     ///
     /// May be used in cases where waiting for the [`.close()`][Self::close] call
@@ -111,7 +134,9 @@ impl<DB: Database> PoolConnection<DB> {
     /// [`max_connections`]: crate::pool::PoolOptions::max_connections
     /// [`min_connections`]: crate::pool::PoolOptions::min_connections
     pub fn detach(mut self) -> DB::Connection {
-        self.take_live().float(self.pool.clone()).detach()
+        let mut conn = self.take_live().float(self.pool.clone()).detach();
+        conn.set_require_transaction_for_writes(false);
+        conn
     }
 
     /// Detach this connection from the pool, treating it as permanently checked-out.
@@ -120,11 +145,19 @@ impl<DB: Database> PoolConnection<DB> {
     ///
     /// If you don't want to impact the pool's capacity, use [`.detach()`][Self::detach] instead.
     pub fn leak(mut self) -> DB::Connection {
-        self.take_live().raw
+        let mut conn = self.take_live().raw;
+        conn.set_require_transaction_for_writes(false);
+        conn
     }
 
     fn take_live(&mut self) -> Live<DB> {
-        self.live.take().expect(EXPECT_MSG)
+        self.try_take_live().expect(EXPECT_MSG)
+    }
+
+    fn try_take_live(&mut self) -> Option<Live<DB>> {
+        let live = self.live.take()?;
+        self.pool.untrack_checked_out(&live);
+        Some(live)
     }
 
     /// Test the connection to make sure it is still live before returning it to the pool.
@@ -136,8 +169,9 @@ impl<DB: Database> PoolConnection<DB> {
         // in case the returned `Future` isn't executed, like if it's spawned into a dying runtime
         // https://github.com/launchbadge/sqlx/issues/1396
         // Type hints seem to be broken by `Option` combinators in IntelliJ Rust right now (6/22).
-        let floating: Option<Floating<DB, Live<DB>>> =
-            self.live.take().map(|live| live.float(self.pool.clone()));
+        let floating: Option<Floating<DB, Live<DB>>> = self
+            .try_take_live()
+            .map(|live| live.float(self.pool.clone()));
 
         let pool = self.pool.clone();
 
@@ -159,7 +193,9 @@ impl<DB: Database> PoolConnection<DB> {
         // in case the returned `Future` isn't executed, like if it's spawned into a dying runtime
         // https://github.com/launchbadge/sqlx/issues/1396
         // Type hints seem to be broken by `Option` combinators in IntelliJ Rust right now (6/22).
-        let floating = self.live.take().map(|live| live.float(self.pool.clone()));
+        let floating = self
+            .try_take_live()
+            .map(|live| live.float(self.pool.clone()));
 
         let pool = self.pool.clone();
 
@@ -245,8 +281,10 @@ impl<DB: Database> Floating<DB, Live<DB>> {
     pub fn new_live(conn: DB::Connection, guard: DecrementSizeGuard<DB>) -> Self {
         Self {
             inner: Live {
+                cancel_handle: conn.cancel_handle(),
                 raw: conn,
                 created_at: Instant::now(),
+                prepared_statements: 0,
             },
             guard,
         }
@@ -258,6 +296,7 @@ impl<DB: Database> Floating<DB, Live<DB>> {
         let pool = Arc::clone(&guard.pool);
 
         guard.cancel();
+        pool.track_checked_out(&inner);
         PoolConnection {
             live: Some(inner),
             close_on_drop: false,
@@ -269,6 +308,30 @@ impl<DB: Database> Floating<DB, Live<DB>> {
         self.guard.pool.clone().release(self);
     }
 
+    /// Returns `true` if some of `PoolOptions::prepare_statements` are not prepared yet.
+    pub fn has_unprepared_statements(&self) -> bool {
+        self.inner.prepared_statements < self.guard.pool.options.get_prepare_statements().len()
+    }
+
+    /// Prepare the statements of `PoolOptions::prepare_statements` that are not prepared yet.
+    pub async fn prepare_statements(&mut self) {
+        let Some(prepare) = self.guard.pool.options.prepare_statements.clone() else {
+            return;
+        };
+
+        while let Some(sql) = prepare.statements.get(self.inner.prepared_statements) {
+            if let Err(error) = (prepare.prepare)(&mut self.inner.raw, sql).await {
+                tracing::warn!(
+                    %error,
+                    sql,
+                    connection_id = self.inner.raw.id(),
+                    "error preparing statement on pool connection");
+            }
+
+            self.inner.prepared_statements += 1;
+        }
+    }
+
     /// Return the connection to the pool.
     ///
     /// Returns `true` if the connection was successfully returned, `false` if it was closed.
@@ -279,13 +342,32 @@ impl<DB: Database> Floating<DB, Live<DB>> {
             return false;
         }
 
-        // If the connection is beyond max lifetime, close the connection and
-        // immediately create a new connection
-        if is_beyond_max_lifetime(&self.inner, &self.guard.pool.options) {
+        // If the connection is beyond max lifetime or has served its maximum number of queries,
+        // close the connection and immediately create a new connection
+        if is_beyond_max_lifetime(&self.inner, &self.guard.pool.options)
+            || is_beyond_max_queries(&self.inner, &self.guard.pool.options)
+        {
             self.close().await;
             return false;
         }
 
+        let reset = match self.guard.pool.options.reset_on_return {
+            ResetOnReturn::None => Ok(()),
+            ResetOnReturn::Rollback => self.raw.rollback_open_transaction().await,
+            ResetOnReturn::DiscardAll => self.raw.reset().await,
+        };
+
+        if let Err(error) = reset {
+            tracing::warn!(
+                %error,
+                connection_id = self.raw.id(),
+                "error occurred while resetting the connection on-release",
+            );
+
+            self.close_hard().await;
+            return false;
+        }
+
         if let Some(test) = &self.guard.pool.options.after_release {
             let meta = self.metadata();
             match (test)(&mut self.inner.raw, meta).await {
@@ -295,7 +377,11 @@ impl<DB: Database> Floating<DB, Live<DB>> {
                     return false;
                 }
                 Err(error) => {
-                    tracing::warn!(%error, "error from `after_release`");
+                    tracing::warn!(
+                        %error,
+                        connection_id = self.raw.id(),
+                        "error from `after_release`"
+                    );
                     // Connection is broken, don't try to gracefully close as
                     // something weird might happen.
                     self.close_hard().await;
@@ -304,6 +390,19 @@ impl<DB: Database> Floating<DB, Live<DB>> {
             }
         }
 
+        if let Some(pages) = self.guard.pool.count_release_for_vacuum() {
+            if let Err(error) = self.raw.incremental_vacuum(pages).await {
+                tracing::warn!(
+                    %error,
+                    connection_id = self.raw.id(),
+                    "error occurred while vacuuming the connection on-release",
+                );
+
+                self.close_hard().await;
+                return false;
+            }
+        }
+
         // test the connection on-release to ensure it is still viable,
         // and flush anything time-sensitive like transaction rollbacks
         // if an Executor future/stream is dropped during an `.await` call, the connection
@@ -314,6 +413,7 @@ impl<DB: Database> Floating<DB, Live<DB>> {
         if let Err(error) = self.raw.ping().await {
             tracing::warn!(
                 %error,
+                connection_id = self.raw.id(),
                 "error occurred while testing the connection on-release",
             );
 
@@ -351,6 +451,7 @@ impl<DB: Database> Floating<DB, Live<DB>> {
 
     pub fn metadata(&self) -> PoolConnectionMetadata {
         PoolConnectionMetadata {
+            id: self.raw.id(),
             age: self.created_at.elapsed(),
             idle_for: Duration::ZERO,
         }
@@ -381,8 +482,10 @@ impl<DB: Database> Floating<DB, Idle<DB>> {
     }
 
     pub async fn close(self) -> DecrementSizeGuard<DB> {
+        let connection_id = self.inner.live.raw.id();
+
         if let Err(error) = self.inner.live.raw.close().await {
-            tracing::debug!(%error, "error occurred while closing the pool connection");
+            tracing::debug!(%error, connection_id, "error occurred while closing the pool connection");
         }
         self.guard
     }
@@ -398,6 +501,7 @@ impl<DB: Database> Floating<DB, Idle<DB>> {
         let now = Instant::now();
 
         PoolConnectionMetadata {
+            id: self.live.raw.id(),
             // NOTE: the receiver is the later `Instant` and the arg is the earlier
             // https://github.com/launchbadge/sqlx/issues/1912
             age: now.saturating_duration_since(self.created_at),
@@ -1,13 +1,17 @@
+use std::io;
+
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_util::TryStreamExt;
 
+use crate::arguments::Arguments;
 use crate::database::Database;
 use crate::describe::Describe;
-use crate::error::Error;
+use crate::error::{BoxDynError, Error};
 use crate::executor::{Execute, Executor};
 use crate::pool::Pool;
+use crate::transaction::is_read_only_statement;
 
 impl<'p, DB: Database> Executor<'p> for &'_ Pool<DB>
 where
@@ -25,8 +29,50 @@ where
         let pool = self.clone();
 
         Box::pin(try_stream! {
+            let mut query = query;
+
+            let Some(retryable) = RetryableQuery::from_query(&pool, &mut query)? else {
+                let mut conn = pool.acquire().await?;
+                let mut s = conn.fetch_many(query);
+
+                while let Some(v) = s.try_next().await? {
+                    r#yield!(v);
+                }
+
+                return Ok(());
+            };
+
+            let retry = retryable.try_clone();
+
+            let mut conn = pool.acquire().await?;
+
+            let res = {
+                let mut s = conn.fetch_many(retryable);
+                let mut received = false;
+
+                loop {
+                    match s.try_next().await {
+                        Ok(Some(v)) => {
+                            received = true;
+                            r#yield!(v);
+                        }
+                        Ok(None) => break Ok(()),
+                        Err(e) => break Err((e, received)),
+                    }
+                }
+            };
+
+            let retry = match (res, retry) {
+                (Ok(()), _) => return Ok(()),
+                (Err((e, false)), Some(retry)) if is_disconnect(&e) => retry,
+                (Err((e, _)), _) => return Err(e),
+            };
+
+            conn.close_on_drop();
+            drop(conn);
+
             let mut conn = pool.acquire().await?;
-            let mut s = conn.fetch_many(query);
+            let mut s = conn.fetch_many(retry);
 
             while let Some(v) = s.try_next().await? {
                 r#yield!(v);
@@ -45,7 +91,27 @@ where
     {
         let pool = self.clone();
 
-        Box::pin(async move { pool.acquire().await?.fetch_optional(query).await })
+        Box::pin(async move {
+            let mut query = query;
+
+            let Some(retryable) = RetryableQuery::from_query(&pool, &mut query)? else {
+                return pool.acquire().await?.fetch_optional(query).await;
+            };
+
+            let retry = retryable.try_clone();
+
+            let mut conn = pool.acquire().await?;
+
+            let retry = match (conn.fetch_optional(retryable).await, retry) {
+                (Err(e), Some(retry)) if is_disconnect(&e) => retry,
+                (res, _) => return res,
+            };
+
+            conn.close_on_drop();
+            drop(conn);
+
+            pool.acquire().await?.fetch_optional(retry).await
+        })
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -69,6 +135,88 @@ where
     }
 }
 
+/// A read-only query lifted out of its original `Execute` impl so it can be sent again after
+/// the connection is lost; see [`PoolOptions::retry_read_on_disconnect`][crate::pool::PoolOptions::retry_read_on_disconnect].
+struct RetryableQuery<'q, DB: Database> {
+    sql: &'q str,
+    arguments: Option<<DB as Database>::Arguments<'q>>,
+    persistent: bool,
+}
+
+impl<'q, DB: Database> RetryableQuery<'q, DB> {
+    /// Returns `None` if the query should not be retried, in which case `query` is untouched.
+    fn from_query<E>(pool: &Pool<DB>, query: &mut E) -> Result<Option<Self>, Error>
+    where
+        E: Execute<'q, DB>,
+    {
+        // A cached statement can't be carried over, so these are executed as-is.
+        if !pool.options().retry_read_on_disconnect
+            || query.statement().is_some()
+            || !is_read_only_statement(query.sql())
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            sql: query.sql(),
+            arguments: query.take_arguments().map_err(Error::Encode)?,
+            persistent: query.persistent(),
+        }))
+    }
+
+    /// Returns `None` if the driver can't copy the arguments.
+    fn try_clone(&self) -> Option<Self> {
+        let arguments = match &self.arguments {
+            Some(arguments) => Some(arguments.try_clone()?),
+            None => None,
+        };
+
+        Some(Self {
+            sql: self.sql,
+            arguments,
+            persistent: self.persistent,
+        })
+    }
+}
+
+impl<'q, DB: Database> Execute<'q, DB> for RetryableQuery<'q, DB> {
+    fn sql(&self) -> &'q str {
+        self.sql
+    }
+
+    fn statement(&self) -> Option<&DB::Statement<'q>> {
+        None
+    }
+
+    fn take_arguments(&mut self) -> Result<Option<<DB as Database>::Arguments<'q>>, BoxDynError> {
+        Ok(self.arguments.take())
+    }
+
+    fn persistent(&self) -> bool {
+        self.persistent
+    }
+}
+
+/// Returns `true` if `error` means the connection was lost, rather than the query failing.
+fn is_disconnect(error: &Error) -> bool {
+    match error {
+        Error::Io(e) => matches!(
+            e.kind(),
+            io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+                | io::ErrorKind::BrokenPipe
+                | io::ErrorKind::NotConnected
+                | io::ErrorKind::UnexpectedEof
+        ),
+        // SQLSTATE class 08 is "connection exception"; `57P01`-`57P03` are Postgres'
+        // `admin_shutdown`, `crash_shutdown` and `cannot_connect_now`.
+        Error::Database(e) => e.code().map_or(false, |code| {
+            code.starts_with("08") || matches!(&*code, "57P01" | "57P02" | "57P03")
+        }),
+        _ => false,
+    }
+}
+
 // Causes an overflow when evaluating `&mut DB::Connection: Executor`.
 //
 //
@@ -0,0 +1,102 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crossbeam_queue::ArrayQueue;
+
+use super::connection::Idle;
+use crate::database::Database;
+
+/// Which idle connection the pool hands out next;
+/// see [`PoolOptions::connection_selection()`][super::PoolOptions::connection_selection].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ConnectionSelection {
+    /// The connection that has been idle the longest.
+    ///
+    /// Every connection is used in turn, so the load is spread evenly over them, and no
+    /// connection sits idle long enough to be closed by
+    /// [`idle_timeout`][super::PoolOptions::idle_timeout] while the pool is in use.
+    #[default]
+    Lru,
+    /// The connection that was returned to the pool last.
+    ///
+    /// Under a steady load, only as many connections are used as are needed at once, and they
+    /// are reused as quickly as possible, which keeps their caches (e.g. of prepared statements)
+    /// warm. The other connections stay idle, and are closed by
+    /// [`idle_timeout`][super::PoolOptions::idle_timeout], so the pool shrinks back after a
+    /// burst of load.
+    Mru,
+}
+
+/// The idle connections of a pool, handed out in the order of its [`ConnectionSelection`].
+pub(super) enum IdleQueue<DB: Database> {
+    // lock-free, for the default
+    Lru(ArrayQueue<Idle<DB>>),
+    // oldest at the front; a stack can't be both bounded and lock-free
+    Mru {
+        queue: Mutex<VecDeque<Idle<DB>>>,
+        capacity: usize,
+    },
+}
+
+impl<DB: Database> IdleQueue<DB> {
+    pub(super) fn new(selection: ConnectionSelection, capacity: usize) -> Self {
+        match selection {
+            ConnectionSelection::Lru => IdleQueue::Lru(ArrayQueue::new(capacity)),
+            ConnectionSelection::Mru => IdleQueue::Mru {
+                queue: Mutex::new(VecDeque::with_capacity(capacity)),
+                capacity,
+            },
+        }
+    }
+
+    /// Add a connection that just became idle, or return it if the queue is full.
+    pub(super) fn push(&self, idle: Idle<DB>) -> Result<(), Idle<DB>> {
+        match self {
+            IdleQueue::Lru(queue) => queue.push(idle),
+            IdleQueue::Mru { queue, capacity } => {
+                let mut queue = queue.lock().unwrap();
+
+                if queue.len() >= *capacity {
+                    return Err(idle);
+                }
+
+                queue.push_back(idle);
+                Ok(())
+            }
+        }
+    }
+
+    /// Return a connection taken with [`pop_oldest()`][Self::pop_oldest] to where it was;
+    /// connections always join the end of a `Lru` queue.
+    pub(super) fn push_oldest(&self, idle: Idle<DB>) -> Result<(), Idle<DB>> {
+        match self {
+            IdleQueue::Lru(queue) => queue.push(idle),
+            IdleQueue::Mru { queue, capacity } => {
+                let mut queue = queue.lock().unwrap();
+
+                if queue.len() >= *capacity {
+                    return Err(idle);
+                }
+
+                queue.push_front(idle);
+                Ok(())
+            }
+        }
+    }
+
+    /// Take the connection to hand out next.
+    pub(super) fn pop(&self) -> Option<Idle<DB>> {
+        match self {
+            IdleQueue::Lru(queue) => queue.pop(),
+            IdleQueue::Mru { queue, .. } => queue.lock().unwrap().pop_back(),
+        }
+    }
+
+    /// Take the connection that has been idle the longest, to check whether it has expired.
+    pub(super) fn pop_oldest(&self) -> Option<Idle<DB>> {
+        match self {
+            IdleQueue::Lru(queue) => queue.pop(),
+            IdleQueue::Mru { queue, .. } => queue.lock().unwrap().pop_front(),
+        }
+    }
+}
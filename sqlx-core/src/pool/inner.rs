@@ -1,36 +1,48 @@
+use super::backoff::ReconnectCoordinator;
 use super::connection::{Floating, Idle, Live};
+use super::idle::IdleQueue;
 use crate::connection::ConnectOptions;
-use crate::connection::Connection;
+use crate::connection::{CancelHandle, Connection};
 use crate::database::Database;
 use crate::error::Error;
-use crate::pool::{deadline_as_timeout, CloseEvent, Pool, PoolOptions};
-use crossbeam_queue::ArrayQueue;
+use crate::pool::priority::WaitQueue;
+use crate::pool::{deadline_as_timeout, CloseEvent, Pool, PoolOptions, Priority};
+use crate::HashMap;
 
 use crate::sync::{AsyncSemaphore, AsyncSemaphoreReleaser};
 
 use std::cmp;
 use std::future::Future;
-use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::task::Poll;
 
 use crate::logger::private_level_filter_to_trace_level;
 use crate::pool::options::PoolConnectionMetadata;
 use crate::private_tracing_dynamic_event;
 use futures_util::future::{self};
+use futures_util::stream::{self, StreamExt};
 use futures_util::FutureExt;
 use std::time::{Duration, Instant};
 use tracing::Level;
 
 pub(crate) struct PoolInner<DB: Database> {
     pub(super) connect_options: RwLock<Arc<<DB::Connection as Connection>::Options>>,
-    pub(super) idle_conns: ArrayQueue<Idle<DB>>,
+    pub(super) idle_conns: IdleQueue<DB>,
     pub(super) semaphore: AsyncSemaphore,
     pub(super) size: AtomicU32,
     pub(super) num_idle: AtomicUsize,
     is_closed: AtomicBool,
     pub(super) on_closed: event_listener::Event,
     pub(super) options: PoolOptions<DB>,
+    // cancel handles of checked-out connections, keyed by the address of the handle
+    checked_out: Mutex<HashMap<usize, Arc<dyn CancelHandle>>>,
+    // tasks waiting in `acquire()`, ordered by priority
+    waiters: WaitQueue,
+    // the number of connections returned to the pool, for `incremental_vacuum_on_release`
+    releases: AtomicU64,
+    // the backoff shared by all connection attempts, if `reconnect_backoff` is set
+    reconnect: Option<ReconnectCoordinator>,
     pub(crate) acquire_time_level: Option<Level>,
     pub(crate) acquire_slow_level: Option<Level>,
 }
@@ -41,6 +53,7 @@ impl<DB: Database> PoolInner<DB> {
         connect_options: <DB::Connection as Connection>::Options,
     ) -> Arc<Self> {
         let capacity = options.max_connections as usize;
+        let connect_options = options.apply_to_connect_options(connect_options);
 
         let semaphore_capacity = if let Some(parent) = &options.parent_pool {
             assert!(options.max_connections <= parent.options().max_connections);
@@ -53,12 +66,16 @@ impl<DB: Database> PoolInner<DB> {
 
         let pool = Self {
             connect_options: RwLock::new(Arc::new(connect_options)),
-            idle_conns: ArrayQueue::new(capacity),
+            idle_conns: IdleQueue::new(options.connection_selection, capacity),
             semaphore: AsyncSemaphore::new(options.fair, semaphore_capacity),
             size: AtomicU32::new(0),
             num_idle: AtomicUsize::new(0),
             is_closed: AtomicBool::new(false),
             on_closed: event_listener::Event::new(),
+            checked_out: Mutex::new(HashMap::new()),
+            waiters: WaitQueue::default(),
+            releases: AtomicU64::new(0),
+            reconnect: options.reconnect_backoff.map(ReconnectCoordinator::new),
             acquire_time_level: private_level_filter_to_trace_level(options.acquire_time_level),
             acquire_slow_level: private_level_filter_to_trace_level(options.acquire_slow_level),
             options,
@@ -99,9 +116,11 @@ impl<DB: Database> PoolInner<DB> {
         async move {
             for permits in 1..=self.options.max_connections {
                 // Close any currently idle connections in the pool.
-                while let Some(idle) = self.idle_conns.pop() {
-                    let _ = idle.live.float((*self).clone()).close().await;
-                }
+                stream::iter(std::iter::from_fn(|| self.idle_conns.pop()))
+                    .map(|idle| idle.live.float((*self).clone()).close())
+                    .buffer_unordered(self.options.close_concurrency)
+                    .for_each(|()| future::ready(()))
+                    .await;
 
                 if self.size() == 0 {
                     break;
@@ -113,6 +132,41 @@ impl<DB: Database> PoolInner<DB> {
         }
     }
 
+    /// Count a connection being returned to the pool, returning the number of pages to vacuum
+    /// on it if an incremental vacuum is due.
+    pub(super) fn count_release_for_vacuum(&self) -> Option<u32> {
+        let vacuum = self.options.incremental_vacuum_on_release?;
+        let releases = self.releases.fetch_add(1, Ordering::Relaxed) + 1;
+
+        (releases % vacuum.every == 0).then_some(vacuum.pages)
+    }
+
+    pub(super) fn track_checked_out(&self, live: &Live<DB>) {
+        if let Some(handle) = &live.cancel_handle {
+            self.checked_out
+                .lock()
+                .unwrap()
+                .insert(handle_key(handle), Arc::clone(handle));
+        }
+    }
+
+    pub(super) fn untrack_checked_out(&self, live: &Live<DB>) {
+        if let Some(handle) = &live.cancel_handle {
+            self.checked_out.lock().unwrap().remove(&handle_key(handle));
+        }
+    }
+
+    pub(super) async fn cancel_all(&self) {
+        // don't hold the lock across the cancel requests
+        let handles: Vec<_> = self.checked_out.lock().unwrap().values().cloned().collect();
+
+        let results = future::join_all(handles.iter().map(|handle| handle.cancel())).await;
+
+        for error in results.into_iter().filter_map(Result::err) {
+            tracing::warn!(%error, "error sending cancel request");
+        }
+    }
+
     pub(crate) fn close_event(&self) -> CloseEvent {
         CloseEvent {
             listener: (!self.is_closed()).then(|| self.on_closed.listen()),
@@ -176,6 +230,38 @@ impl<DB: Database> PoolInner<DB> {
         }
     }
 
+    /// Wait for a permit behind every waiting task of the same or a higher priority.
+    ///
+    /// Only the task at the head of `self.waiters` polls `acquire_permit()`, the rest park
+    /// until the head leaves the queue or they overtake it.
+    async fn acquire_permit_with_priority<'a>(
+        self: &'a Arc<Self>,
+        priority: Priority,
+    ) -> Result<AsyncSemaphoreReleaser<'a>, Error> {
+        if self.waiters.is_empty() {
+            if let Some(permit) = self.semaphore.try_acquire(1) {
+                return Ok(permit);
+            }
+        }
+
+        let ticket = self.waiters.join(priority);
+        let mut acquire_permit = None;
+
+        future::poll_fn(|cx| {
+            if !ticket.poll_head(cx.waker()) {
+                // Hand back any permit we were already assigned to the new head.
+                acquire_permit = None;
+                return Poll::Pending;
+            }
+
+            acquire_permit
+                .get_or_insert_with(|| Box::pin(self.acquire_permit()))
+                .as_mut()
+                .poll(cx)
+        })
+        .await
+    }
+
     fn parent(&self) -> Option<&Pool<DB>> {
         self.options.parent_pool.as_ref()
     }
@@ -191,6 +277,23 @@ impl<DB: Database> PoolInner<DB> {
         self.pop_idle(permit).ok()
     }
 
+    /// Like [`try_acquire()`][Self::try_acquire], but take the connection that has been idle
+    /// the longest regardless of `connection_selection`, for checking it in the reaper.
+    fn try_acquire_oldest(self: &Arc<Self>) -> Option<Floating<DB, Idle<DB>>> {
+        if self.is_closed() {
+            return None;
+        }
+
+        let permit = self.semaphore.try_acquire(1)?;
+
+        let Some(idle) = self.idle_conns.pop_oldest() else {
+            return None;
+        };
+
+        self.num_idle.fetch_sub(1, Ordering::AcqRel);
+        Some(Floating::from_idle(idle, (*self).clone(), permit))
+    }
+
     fn pop_idle<'a>(
         self: &'a Arc<Self>,
         permit: AsyncSemaphoreReleaser<'a>,
@@ -206,6 +309,18 @@ impl<DB: Database> PoolInner<DB> {
     pub(super) fn release(&self, floating: Floating<DB, Live<DB>>) {
         // `options.after_release` and other checks are in `PoolConnection::return_to_pool()`.
 
+        // See `PoolOptions::prepare_statements_in_background()`
+        if floating.has_unprepared_statements() {
+            let mut floating = floating;
+
+            crate::rt::spawn(async move {
+                floating.prepare_statements().await;
+                floating.release();
+            });
+
+            return;
+        }
+
         let Floating { inner: idle, guard } = floating.into_idle();
 
         if self.idle_conns.push(idle).is_err() {
@@ -219,6 +334,25 @@ impl<DB: Database> PoolInner<DB> {
         self.num_idle.fetch_add(1, Ordering::AcqRel);
     }
 
+    /// Return the idle connections that the reaper kept, which it took oldest first.
+    fn release_reaped(&self, kept: Vec<Floating<DB, Idle<DB>>>) {
+        if let IdleQueue::Lru(_) = self.idle_conns {
+            kept.into_iter().for_each(|conn| self.release(conn.into_live()));
+            return;
+        }
+
+        // put them back below the connections released since, still idle since the same time
+        for Floating { inner: idle, guard } in kept.into_iter().rev() {
+            if self.idle_conns.push_oldest(idle).is_err() {
+                panic!("BUG: connection queue overflow in release_reaped()");
+            }
+
+            guard.release_permit();
+
+            self.num_idle.fetch_add(1, Ordering::AcqRel);
+        }
+    }
+
     /// Try to atomically increment the pool size for a new connection.
     ///
     /// Returns `Err` if the pool is at max capacity already or is closed.
@@ -245,7 +379,10 @@ impl<DB: Database> PoolInner<DB> {
         }
     }
 
-    pub(super) async fn acquire(self: &Arc<Self>) -> Result<Floating<DB, Live<DB>>, Error> {
+    pub(super) async fn acquire(
+        self: &Arc<Self>,
+        priority: Priority,
+    ) -> Result<Floating<DB, Live<DB>>, Error> {
         if self.is_closed() {
             return Err(Error::PoolClosed);
         }
@@ -258,7 +395,7 @@ impl<DB: Database> PoolInner<DB> {
             async {
                 loop {
                     // Handles the close-event internally
-                    let permit = self.acquire_permit().await?;
+                    let permit = self.acquire_permit_with_priority(priority).await?;
 
 
                     // First attempt to pop a connection from the idle queue.
@@ -337,6 +474,15 @@ impl<DB: Database> PoolInner<DB> {
         let max_backoff = deadline_as_timeout(deadline)? / 5;
 
         loop {
+            let attempt = self.reconnect.as_ref().map(ReconnectCoordinator::begin);
+
+            // Wait for our turn if the pool is backing off, as long as we can before the deadline.
+            if let Some(start_at) = attempt.as_ref().and_then(|attempt| attempt.start_at) {
+                if let Some(wait) = start_at.checked_duration_since(Instant::now()) {
+                    crate::rt::sleep(cmp::min(wait, deadline_as_timeout(deadline)?)).await;
+                }
+            }
+
             let timeout = deadline_as_timeout(deadline)?;
 
             // clone the connect options arc so it can be used without holding the RwLockReadGuard
@@ -354,6 +500,7 @@ impl<DB: Database> PoolInner<DB> {
                 Ok(Ok(mut raw)) => {
                     // See comment on `PoolOptions::after_connect`
                     let meta = PoolConnectionMetadata {
+                        id: raw.id(),
                         age: Duration::ZERO,
                         idle_for: Duration::ZERO,
                     };
@@ -365,9 +512,31 @@ impl<DB: Database> PoolInner<DB> {
                     };
 
                     match res {
-                        Ok(()) => return Ok(Floating::new_live(raw, guard)),
+                        Ok(()) => {
+                            if self.options.require_transaction_for_writes {
+                                raw.set_require_transaction_for_writes(true);
+                            }
+
+                            if let Some(reconnect) = &self.reconnect {
+                                reconnect.succeeded();
+                            }
+
+                            let mut conn = Floating::new_live(raw, guard);
+
+                            if let Some(prepare) = &self.options.prepare_statements {
+                                if !prepare.in_background {
+                                    conn.prepare_statements().await;
+                                }
+                            }
+
+                            return Ok(conn);
+                        }
                         Err(error) => {
-                            tracing::error!(%error, "error returned from after_connect");
+                            tracing::error!(
+                                %error,
+                                connection_id = raw.id(),
+                                "error returned from after_connect"
+                            );
                             // The connection is broken, don't try to close nicely.
                             let _ = raw.close_hard().await;
 
@@ -390,6 +559,12 @@ impl<DB: Database> PoolInner<DB> {
                 Err(_) => return Err(Error::PoolTimedOut),
             }
 
+            if let (Some(reconnect), Some(attempt)) = (&self.reconnect, &attempt) {
+                // The next attempt waits for its turn instead.
+                reconnect.failed(attempt);
+                continue;
+            }
+
             // If the connection is refused, wait in exponentially
             // increasing steps for the server to come up,
             // capped by a factor of the remaining time until the deadline
@@ -422,6 +597,17 @@ impl<DB: Database> PoolInner<DB> {
         Ok(())
     }
 
+    /// Open a connection in place of one that was closed, reusing its size guard.
+    pub(super) async fn replace(self: &Arc<Self>, guard: DecrementSizeGuard<DB>) {
+        let deadline = Instant::now() + self.options.acquire_timeout;
+
+        match self.connect(deadline, guard).await {
+            Ok(conn) => self.release(conn),
+            Err(Error::PoolClosed) => (),
+            Err(error) => tracing::debug!(%error, "error while replacing a closed connection"),
+        }
+    }
+
     /// Attempt to maintain `min_connections`, logging if unable.
     pub async fn min_connections_maintenance(self: &Arc<Self>, deadline: Option<Instant>) {
         let deadline = deadline.unwrap_or_else(|| {
@@ -451,6 +637,10 @@ impl<DB: Database> Drop for PoolInner<DB> {
     }
 }
 
+fn handle_key(handle: &Arc<dyn CancelHandle>) -> usize {
+    Arc::as_ptr(handle) as *const () as usize
+}
+
 /// Returns `true` if the connection has exceeded `options.max_lifetime` if set, `false` otherwise.
 pub(super) fn is_beyond_max_lifetime<DB: Database>(
     live: &Live<DB>,
@@ -461,6 +651,17 @@ pub(super) fn is_beyond_max_lifetime<DB: Database>(
         .map_or(false, |max| live.created_at.elapsed() > max)
 }
 
+/// Returns `true` if the connection has executed `options.max_queries_per_connection` statements
+/// if set, `false` otherwise.
+pub(super) fn is_beyond_max_queries<DB: Database>(
+    live: &Live<DB>,
+    options: &PoolOptions<DB>,
+) -> bool {
+    options
+        .max_queries_per_connection
+        .map_or(false, |max| live.raw.queries_executed() >= max)
+}
+
 /// Returns `true` if the connection has exceeded `options.idle_timeout` if set, `false` otherwise.
 fn is_beyond_idle_timeout<DB: Database>(idle: &Idle<DB>, options: &PoolOptions<DB>) -> bool {
     options
@@ -478,7 +679,11 @@ async fn check_idle_conn<DB: Database>(
             // an error here means the other end has hung up or we lost connectivity
             // either way we're fine to just discard the connection
             // the error itself here isn't necessarily unexpected so WARN is too strong
-            tracing::info!(%error, "ping on idle connection returned error");
+            tracing::info!(
+                %error,
+                connection_id = conn.live.raw.id(),
+                "ping on idle connection returned error"
+            );
             // connection is broken so don't try to close nicely
             return Err(conn.close_hard().await);
         }
@@ -493,7 +698,11 @@ async fn check_idle_conn<DB: Database>(
             }
 
             Err(error) => {
-                tracing::warn!(%error, "error from `before_acquire`");
+                tracing::warn!(
+                    %error,
+                    connection_id = conn.live.raw.id(),
+                    "error from `before_acquire`"
+                );
                 // connection is broken so don't try to close nicely
                 return Err(conn.close_hard().await);
             }
@@ -547,19 +756,38 @@ fn spawn_maintenance_tasks<DB: Database>(pool: &Arc<PoolInner<DB>>) {
                     // and if we have fewer than min_connections after reaping a connection,
                     // open a new one immediately. Note that other connections may be popped from
                     // the queue in the meantime - that's fine, there is no harm in checking more
+                    //
+                    // The connections that are kept are returned before any are closed, in the
+                    // order they were taken, so `ConnectionSelection::Mru` still hands out the
+                    // most recently used one next.
+                    let mut kept = Vec::new();
+                    let mut expired = Vec::new();
+
                     for _ in 0..pool.num_idle() {
-                        if let Some(conn) = pool.try_acquire() {
-                            if is_beyond_idle_timeout(&conn, &pool.options)
-                                || is_beyond_max_lifetime(&conn, &pool.options)
+                        if let Some(conn) = pool.try_acquire_oldest() {
+                            let remaining = pool.size().saturating_sub(expired.len() as u32);
+
+                            // Only an expired lifetime forces a connection to be replaced;
+                            // closing idle connections below `min_connections` would just
+                            // cause them to be reopened immediately.
+                            if is_beyond_max_lifetime(&conn, &pool.options)
+                                || (is_beyond_idle_timeout(&conn, &pool.options)
+                                    && remaining > pool.options.min_connections)
                             {
-                                let _ = conn.close().await;
-                                pool.min_connections_maintenance(Some(next_run)).await;
+                                expired.push(conn);
                             } else {
-                                pool.release(conn.into_live());
+                                kept.push(conn);
                             }
                         }
                     }
 
+                    pool.release_reaped(kept);
+
+                    for conn in expired {
+                        let _ = conn.close().await;
+                        pool.min_connections_maintenance(Some(next_run)).await;
+                    }
+
                     // Don't hold a reference to the pool while sleeping.
                     drop(pool);
 
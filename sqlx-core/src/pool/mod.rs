@@ -70,11 +70,15 @@ use crate::database::Database;
 use crate::error::Error;
 use crate::transaction::Transaction;
 
+pub use self::backoff::ReconnectBackoff;
 pub use self::connection::PoolConnection;
+pub use self::idle::ConnectionSelection;
 use self::inner::PoolInner;
 #[doc(hidden)]
 pub use self::maybe::MaybePoolConnection;
-pub use self::options::{PoolConnectionMetadata, PoolOptions};
+pub use self::options::{PoolConnectionMetadata, PoolOptions, ResetOnReturn};
+pub use self::priority::Priority;
+pub use self::replicated::ReplicatedPool;
 
 #[macro_use]
 mod executor;
@@ -82,9 +86,13 @@ mod executor;
 #[macro_use]
 pub mod maybe;
 
+mod backoff;
 mod connection;
+mod idle;
 mod inner;
 mod options;
+mod priority;
+mod replicated;
 
 /// An asynchronous pool of SQLx database connections.
 ///
@@ -278,7 +286,8 @@ impl<DB: Database> Pool<DB> {
     /// The default configuration is mainly suited for testing and light-duty applications.
     /// For production applications, you'll likely want to make at least few tweaks.
     ///
-    /// See [`PoolOptions::new()`] for details.
+    /// See [`PoolOptions::new()`] for details. Use [`Pool::connect_with()`] to pass settings
+    /// that can't be written in a URL.
     pub async fn connect(url: &str) -> Result<Self, Error> {
         PoolOptions::<DB>::new().connect(url).await
     }
@@ -311,7 +320,8 @@ impl<DB: Database> Pool<DB> {
     /// The default configuration is mainly suited for testing and light-duty applications.
     /// For production applications, you'll likely want to make at least few tweaks.
     ///
-    /// See [`PoolOptions::new()`] for details.
+    /// See [`PoolOptions::new()`] for details. Use [`Pool::connect_lazy_with()`] to pass settings
+    /// that can't be written in a URL.
     pub fn connect_lazy(url: &str) -> Result<Self, Error> {
         PoolOptions::<DB>::new().connect_lazy(url)
     }
@@ -335,6 +345,9 @@ impl<DB: Database> Pool<DB> {
     /// [`PoolOptions::acquire_timeout`].
     /// If that timeout elapses, this will return [`Error::PoolClosed`].
     ///
+    /// A task that is cancelled while it waits for a connection leaves the queue right away,
+    /// so it does not hold up the tasks behind it.
+    ///
     /// ### Note: Cancellation/Timeout May Drop Connections
     /// If `acquire` is cancelled or times out after it acquires a connection from the idle queue or
     /// opens a new one, it will drop that connection because we don't want to assume it
@@ -353,14 +366,71 @@ impl<DB: Database> Pool<DB> {
     /// This should eliminate any potential `.await` points between acquiring a connection and
     /// returning it.
     pub fn acquire(&self) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        self.acquire_with_priority(Priority::Normal)
+    }
+
+    /// Retrieves a connection from the pool, ahead of tasks waiting with a lower [`Priority`].
+    ///
+    /// While the pool is saturated, waiting tasks are served in order of priority, and tasks
+    /// with the same priority in the order they started waiting. [`acquire()`][Self::acquire]
+    /// waits with [`Priority::Normal`]. Being served first does not preempt anyone: a task
+    /// still has to wait for a connection to be released or for room to open a new one.
+    ///
+    /// [`try_acquire()`][Self::try_acquire] does not wait and so is not affected by priority.
+    ///
+    /// The [`acquire_timeout`][PoolOptions::acquire_timeout] and the notes on cancellation for
+    /// [`acquire()`][Self::acquire] apply here as well.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+    /// use sqlx::pool::Priority;
+    ///
+    /// // Keep health checks responsive even when batch jobs are hogging the pool.
+    /// let mut conn = pool.acquire_with_priority(Priority::High).await?;
+    /// sqlx::query("SELECT 1").execute(&mut *conn).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn acquire_with_priority(
+        &self,
+        priority: Priority,
+    ) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
         let shared = self.0.clone();
-        async move { shared.acquire().await.map(|conn| conn.reattach()) }
+        async move { shared.acquire(priority).await.map(|conn| conn.reattach()) }
+    }
+
+    /// Retrieves a connection from the pool, blocking the current thread until one is available.
+    ///
+    /// This is an escape hatch for code that cannot `.await`, chiefly `Drop` impls that have to
+    /// run a cleanup query. Prefer [`acquire()`][Self::acquire] everywhere else.
+    ///
+    /// The connection is acquired on a dedicated thread which, with Tokio, uses the runtime of
+    /// the current context; the calling thread is blocked until it is done.
+    ///
+    /// ### Warning: Deadlocks
+    /// Calling this from a task on a Tokio `current_thread` runtime (e.g. `#[tokio::test]`
+    /// or `#[tokio::main(flavor = "current_thread")]`), or from a thread that is otherwise
+    /// driving the runtime, **deadlocks**: the runtime cannot drive the connection while its only
+    /// thread is blocked. On a multi-threaded runtime, it blocks a worker thread, which stalls
+    /// the other tasks scheduled on it and can still deadlock if every worker is blocked.
+    ///
+    /// Wrap the call in `spawn_blocking()` or only use it from threads that are not part of
+    /// the runtime, such as when a value is dropped inside `spawn_blocking()`.
+    ///
+    /// [`Connection::execute_blocking()`] has the same caveats.
+    #[cfg(feature = "blocking")]
+    pub fn acquire_blocking(&self) -> Result<PoolConnection<DB>, Error> {
+        crate::rt::block_on_dedicated_thread(self.acquire())
     }
 
     /// Attempts to retrieve a connection from the pool if there is one available.
     ///
     /// Returns `None` immediately if there are no idle connections available in the pool
     /// or there are tasks waiting for a connection which have yet to wake.
+    ///
+    /// This never waits, joins the queue of waiting tasks, or opens a new connection, so it is
+    /// suited to paths that would rather give up than add latency. It is not bounded by
+    /// [`acquire_timeout`][PoolOptions::acquire_timeout].
     pub fn try_acquire(&self) -> Option<PoolConnection<DB>> {
         self.0.try_acquire().map(|conn| conn.into_live().reattach())
     }
@@ -390,7 +460,8 @@ impl<DB: Database> Pool<DB> {
     ///
     /// Returns a `Future` which can be `.await`ed to ensure all connections are
     /// gracefully closed. It will first close any idle connections currently waiting in the pool,
-    /// then wait for all checked-out connections to be returned or closed.
+    /// then wait for all checked-out connections to be returned or closed. Idle connections are
+    /// closed up to [`PoolOptions::close_concurrency`] at a time.
     ///
     /// Waiting for connections to be gracefully closed is optional, but will allow the database
     /// server to clean up the resources sooner rather than later. This is especially important
@@ -406,6 +477,24 @@ impl<DB: Database> Pool<DB> {
         self.0.close()
     }
 
+    /// Ask the database server to cancel the queries running on all checked-out connections.
+    ///
+    /// Combine with [`.close()`][Pool::close] for a faster shutdown under load: cancelled queries
+    /// return an error, so their connections are handed back sooner instead of `.close()` waiting
+    /// for every query to run to completion.
+    ///
+    /// Cancellation is best-effort. The cancel request is sent out-of-band (for Postgres,
+    /// on a separate connection using the backend process ID and secret key) and the server does
+    /// not report whether it had any effect: a query that completes in the meantime is not
+    /// affected, and a connection that is between queries ignores the request. Failures to send
+    /// a request are logged and otherwise ignored.
+    ///
+    /// Currently only Postgres connections support cancellation; for other databases this does
+    /// nothing.
+    pub async fn cancel_all(&self) {
+        self.0.cancel_all().await
+    }
+
     /// Returns `true` if [`.close()`][Pool::close] has been called on the pool, `false` otherwise.
     pub fn is_closed(&self) -> bool {
         self.0.is_closed()
@@ -517,6 +606,8 @@ impl<DB: Database> Pool<DB> {
     /// Updates the connection options this pool will use when opening any future connections.  Any
     /// existing open connection in the pool will be left as-is.
     pub fn set_connect_options(&self, connect_options: <DB::Connection as Connection>::Options) {
+        let connect_options = self.0.options.apply_to_connect_options(connect_options);
+
         // technically write() could also panic if the current thread already holds the lock,
         // but because this method can't be re-entered by the same thread that shouldn't be a problem
         let mut guard = self
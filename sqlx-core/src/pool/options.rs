@@ -1,11 +1,16 @@
-use crate::connection::Connection;
+use crate::config::Config;
+use crate::connection::{ConnectOptions, Connection, SlowQueryHook};
 use crate::database::Database;
 use crate::error::Error;
+use crate::executor::Executor;
+use crate::pool::backoff::ReconnectBackoff;
 use crate::pool::inner::PoolInner;
-use crate::pool::Pool;
+use crate::pool::{ConnectionSelection, Pool, Priority};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
-use std::fmt::{self, Debug, Formatter};
+use std::cmp;
+use std::fmt::{self, Debug, Display, Formatter};
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -75,18 +80,126 @@ pub struct PoolOptions<DB: Database> {
         >,
     >,
     pub(crate) max_connections: u32,
+    pub(crate) max_connections_from_server: Option<u32>,
     pub(crate) acquire_time_level: LevelFilter,
     pub(crate) acquire_slow_level: LevelFilter,
     pub(crate) acquire_slow_threshold: Duration,
     pub(crate) acquire_timeout: Duration,
     pub(crate) min_connections: u32,
     pub(crate) max_lifetime: Option<Duration>,
+    pub(crate) max_queries_per_connection: Option<u64>,
     pub(crate) idle_timeout: Option<Duration>,
     pub(crate) fair: bool,
+    pub(crate) connection_selection: ConnectionSelection,
+    pub(crate) require_transaction_for_writes: bool,
+    pub(crate) retry_read_on_disconnect: bool,
+    pub(crate) close_concurrency: usize,
+    pub(crate) on_slow_query: Option<SlowQueryHook>,
+    pub(crate) pool_name: Option<String>,
+    pub(crate) incremental_vacuum_on_release: Option<IncrementalVacuum>,
+    pub(crate) reset_on_return: ResetOnReturn,
+    pub(crate) prepare_statements: Option<PrepareStatements<DB>>,
+    pub(crate) reconnect_backoff: Option<ReconnectBackoff>,
 
     pub(crate) parent_pool: Option<Pool<DB>>,
 }
 
+/// How a connection is cleaned up when it is returned to the pool;
+/// see [`PoolOptions::reset_on_return()`].
+///
+/// Parsed from `"none"`, `"rollback"` or `"discard-all"` by [`FromStr`], for reading the
+/// setting from configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResetOnReturn {
+    /// Leave the connection as it is.
+    ///
+    /// Transactions started through a [`Transaction`][crate::transaction::Transaction] are
+    /// still rolled back when it is dropped, but one started by running `BEGIN` directly
+    /// stays open.
+    #[default]
+    None,
+
+    /// Roll back any open transaction, with [`Connection::rollback_open_transaction()`].
+    ///
+    /// Suitable behind a transaction-pooling proxy like PgBouncer, where session state
+    /// does not belong to the connection anyway.
+    Rollback,
+
+    /// Discard all session state, with [`Connection::reset()`].
+    ///
+    /// Suitable for direct connections to the server, so no settings, temporary tables
+    /// or locks leak from one user of a connection to the next. This also empties
+    /// the statement cache.
+    DiscardAll,
+}
+
+impl ResetOnReturn {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ResetOnReturn::None => "none",
+            ResetOnReturn::Rollback => "rollback",
+            ResetOnReturn::DiscardAll => "discard-all",
+        }
+    }
+}
+
+impl FromStr for ResetOnReturn {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "none" => ResetOnReturn::None,
+            "rollback" => ResetOnReturn::Rollback,
+            "discard-all" => ResetOnReturn::DiscardAll,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!(
+                        "unknown value {s:?} for `reset-on-return`, \
+                         expected \"none\", \"rollback\" or \"discard-all\""
+                    )
+                    .into(),
+                ));
+            }
+        })
+    }
+}
+
+impl Display for ResetOnReturn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The statements set with [`PoolOptions::prepare_statements()`] and how to prepare them.
+pub(crate) struct PrepareStatements<DB: Database> {
+    pub(crate) statements: Arc<[String]>,
+    pub(crate) prepare: Arc<
+        dyn for<'c> Fn(&'c mut DB::Connection, &'c str) -> BoxFuture<'c, Result<(), Error>>
+            + 'static
+            + Send
+            + Sync,
+    >,
+    pub(crate) in_background: bool,
+}
+
+impl<DB: Database> Clone for PrepareStatements<DB> {
+    fn clone(&self) -> Self {
+        PrepareStatements {
+            statements: self.statements.clone(),
+            prepare: self.prepare.clone(),
+            in_background: self.in_background,
+        }
+    }
+}
+
+/// The schedule set with [`PoolOptions::incremental_vacuum_on_release()`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct IncrementalVacuum {
+    pub(crate) every: u64,
+    pub(crate) pages: u32,
+}
+
 // Manually implement `Clone` to avoid a trait bound issue.
 //
 // See: https://github.com/launchbadge/sqlx/issues/2548
@@ -98,14 +211,26 @@ impl<DB: Database> Clone for PoolOptions<DB> {
             before_acquire: self.before_acquire.clone(),
             after_release: self.after_release.clone(),
             max_connections: self.max_connections,
+            max_connections_from_server: self.max_connections_from_server,
             acquire_time_level: self.acquire_time_level,
             acquire_slow_threshold: self.acquire_slow_threshold,
             acquire_slow_level: self.acquire_slow_level,
             acquire_timeout: self.acquire_timeout,
             min_connections: self.min_connections,
             max_lifetime: self.max_lifetime,
+            max_queries_per_connection: self.max_queries_per_connection,
             idle_timeout: self.idle_timeout,
             fair: self.fair,
+            connection_selection: self.connection_selection,
+            require_transaction_for_writes: self.require_transaction_for_writes,
+            retry_read_on_disconnect: self.retry_read_on_disconnect,
+            close_concurrency: self.close_concurrency,
+            on_slow_query: self.on_slow_query.clone(),
+            pool_name: self.pool_name.clone(),
+            incremental_vacuum_on_release: self.incremental_vacuum_on_release,
+            reset_on_return: self.reset_on_return,
+            prepare_statements: self.prepare_statements.clone(),
+            reconnect_backoff: self.reconnect_backoff,
             parent_pool: self.parent_pool.clone(),
         }
     }
@@ -115,6 +240,9 @@ impl<DB: Database> Clone for PoolOptions<DB> {
 #[derive(Debug)] // Don't want to commit to any other trait impls yet.
 #[non_exhaustive] // So we can safely add fields in the future.
 pub struct PoolConnectionMetadata {
+    /// The id of the connection, as returned by [`Connection::id()`].
+    pub id: u64,
+
     /// The duration since the connection was first opened.
     ///
     /// For [`after_connect`][PoolOptions::after_connect], this is [`Duration::ZERO`].
@@ -149,6 +277,7 @@ impl<DB: Database> PoolOptions<DB> {
             test_before_acquire: true,
             // A production application will want to set a higher limit than this.
             max_connections: 10,
+            max_connections_from_server: None,
             min_connections: 0,
             // Logging all acquires is opt-in
             acquire_time_level: LevelFilter::Off,
@@ -160,11 +289,83 @@ impl<DB: Database> PoolOptions<DB> {
             acquire_timeout: Duration::from_secs(30),
             idle_timeout: Some(Duration::from_secs(10 * 60)),
             max_lifetime: Some(Duration::from_secs(30 * 60)),
+            max_queries_per_connection: None,
             fair: true,
+            connection_selection: ConnectionSelection::Lru,
+            require_transaction_for_writes: false,
+            retry_read_on_disconnect: false,
+            close_concurrency: 1,
+            on_slow_query: None,
+            pool_name: None,
+            incremental_vacuum_on_release: None,
+            reset_on_return: ResetOnReturn::None,
+            prepare_statements: None,
+            reconnect_backoff: None,
             parent_pool: None,
         }
     }
 
+    /// Returns the options of [`PoolOptions::new()`] with the `[pool]` section of `sqlx.toml`,
+    /// and the `[drivers]` table for this pool's database, applied on top.
+    ///
+    /// Keys that aren't set keep their defaults, and methods called afterwards override the
+    /// config, so code can still pin a value that ops shouldn't change:
+    ///
+    /// ```rust,no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use sqlx::config::Config;
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let config = Config::try_from_crate()?;
+    ///
+    /// let pool = PgPoolOptions::from_config(&config)
+    ///     // overrides `pool.test-before-acquire`, if it is set
+    ///     .test_before_acquire(true)
+    ///     .connect("postgres:// …")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// See [`config::pool::Config`][crate::config::pool::Config] and
+    /// [`config::drivers::Driver`][crate::config::drivers::Driver] for the keys.
+    pub fn from_config(config: &Config) -> Self {
+        let pool = &config.pool;
+        let mut options = Self::new();
+
+        if let Some(max) = pool.max_connections {
+            options.max_connections = max;
+        }
+
+        if let Some(min) = pool.min_connections {
+            options.min_connections = min;
+        }
+
+        if let Some(timeout) = pool.acquire_timeout {
+            options.acquire_timeout = timeout;
+        }
+
+        if let Some(timeout) = pool.idle_timeout {
+            options.idle_timeout = timeout;
+        }
+
+        if let Some(lifetime) = pool.max_lifetime {
+            options.max_lifetime = lifetime;
+        }
+
+        if let Some(test) = pool.test_before_acquire {
+            options.test_before_acquire = test;
+        }
+
+        let driver = config.drivers.for_database(DB::NAME);
+
+        if let Some(reset) = driver.and_then(|driver| driver.reset_on_return) {
+            options.reset_on_return = reset;
+        }
+
+        options
+    }
+
     /// Set the maximum number of connections that this pool should maintain.
     ///
     /// Be mindful of the connection limits for your database as well as other applications
@@ -180,11 +381,49 @@ impl<DB: Database> PoolOptions<DB> {
         self.max_connections
     }
 
+    /// Size the pool to the server's connection limit minus `reserve`, instead of using
+    /// [`max_connections`][Self::max_connections].
+    ///
+    /// When the pool is created with [`connect()`][Self::connect] or
+    /// [`connect_with()`][Self::connect_with], a separate connection is opened first to ask the
+    /// server for its limit (see [`Connection::server_max_connections()`]) and closed again.
+    /// This is a one-time probe: the pool is not resized if the limit changes later.
+    ///
+    /// The pool size is at least 1 and at least [`min_connections`][Self::min_connections].
+    /// `max_connections` is used instead if the server has no limit (SQLite), and by
+    /// [`connect_lazy()`][Self::connect_lazy], which can't wait for the probe.
+    ///
+    /// The server's limit is shared by everything that connects to it, so `reserve` should
+    /// leave room for administrative connections and other applications. A single instance
+    /// can't know how many others there are, so if you run several, account for their pools
+    /// in `reserve` too, or divide the limit between them with
+    /// [`max_connections`][Self::max_connections] instead.
+    ///
+    /// ```rust,no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// // Leave 10 connections for `psql`, migrations, etc.
+    /// let pool = PgPoolOptions::new()
+    ///     .max_connections_from_server(10)
+    ///     .connect("postgres:// …")
+    ///     .await?;
+    ///
+    /// println!("pool size: {}", pool.options().get_max_connections());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_connections_from_server(mut self, reserve: u32) -> Self {
+        self.max_connections_from_server = Some(reserve);
+        self
+    }
+
     /// Set the minimum number of connections to maintain at all times.
     ///
     /// When the pool is built, this many connections will be automatically spun up.
     ///
-    /// If any connection is reaped by [`max_lifetime`] or [`idle_timeout`], or explicitly closed,
+    /// Connections are not closed by [`idle_timeout`] if that would bring the connection count
+    /// below this amount. If any connection is reaped by [`max_lifetime`], or explicitly closed,
     /// and it brings the connection count below this amount, a new connection will be opened to
     /// replace it.
     ///
@@ -292,9 +531,47 @@ impl<DB: Database> PoolOptions<DB> {
         self.max_lifetime
     }
 
+    /// Set the maximum number of statements an individual connection may execute.
+    ///
+    /// A connection that has executed at least this many statements is closed when it is
+    /// returned to the pool, and replaced by a new one as needed. This retires connections by
+    /// use rather than by age, which suits workloads where session state on the server (cached
+    /// plans, prepared statements, temporary data) grows with every query rather than with time.
+    /// It applies in addition to [`max_lifetime`][Self::max_lifetime].
+    ///
+    /// Statements are counted by the connection itself, as reported by
+    /// [`Connection::queries_executed()`], including those the driver or the pool run
+    /// internally, so the count is a bound rather than an exact budget. A connection is never
+    /// closed while it is checked out, even if it goes over the limit.
+    ///
+    /// Default: `None` (no limit).
+    ///
+    /// ```rust,no_run
+    /// # async fn example() -> sqlx::Result<()> {
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .max_queries_per_connection(10_000)
+    ///     .connect("postgres://localhost/app")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_queries_per_connection(mut self, max: impl Into<Option<u64>>) -> Self {
+        self.max_queries_per_connection = max.into();
+        self
+    }
+
+    /// Get the maximum number of statements an individual connection may execute.
+    pub fn get_max_queries_per_connection(&self) -> Option<u64> {
+        self.max_queries_per_connection
+    }
+
     /// Set a maximum idle duration for individual connections.
     ///
-    /// Any connection that remains in the idle queue longer than this will be closed.
+    /// Any connection that remains in the idle queue longer than this will be closed,
+    /// unless that would bring the number of connections below
+    /// [`min_connections`][Self::min_connections].
     ///
     /// For usage-based database server billing, this can be a cost saver.
     pub fn idle_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
@@ -307,6 +584,36 @@ impl<DB: Database> PoolOptions<DB> {
         self.idle_timeout
     }
 
+    /// Set which idle connection [`Pool::acquire()`] hands out when there is more than one.
+    ///
+    /// Defaults to [`ConnectionSelection::Lru`], the connection that has been idle the longest.
+    /// This rotates through all connections of the pool, spreading the load (and the memory
+    /// the database server uses per connection) evenly over them.
+    ///
+    /// With [`ConnectionSelection::Mru`], the connection that was returned last is reused
+    /// first. A steady load then runs on as few connections as it needs at once. Their
+    /// statement caches see every query, so fewer statements have to be prepared again (see
+    /// [`prepare_statements()`][Self::prepare_statements] for warming them up), and the
+    /// connections on the database server stay hot. The rest of the pool stays idle and is
+    /// closed by [`idle_timeout`][Self::idle_timeout], so it shrinks after a burst of load.
+    /// The tradeoff is that the connections that are used wear unevenly: a problem with one
+    /// of them, e.g. a server-side session setting, tends to be hit by every query.
+    ///
+    /// ```rust,no_run
+    /// # use sqlx::pool::{ConnectionSelection, PoolOptions};
+    /// # use sqlx::Postgres;
+    /// let options = PoolOptions::<Postgres>::new().connection_selection(ConnectionSelection::Mru);
+    /// ```
+    pub fn connection_selection(mut self, selection: ConnectionSelection) -> Self {
+        self.connection_selection = selection;
+        self
+    }
+
+    /// Get which idle connection is handed out next.
+    pub fn get_connection_selection(&self) -> ConnectionSelection {
+        self.connection_selection
+    }
+
     /// If true, the health of a connection will be verified by a call to [`Connection::ping`]
     /// before returning the connection.
     ///
@@ -321,6 +628,358 @@ impl<DB: Database> PoolOptions<DB> {
         self.test_before_acquire
     }
 
+    /// If true, executing a statement that modifies the database directly on a connection
+    /// from this pool, rather than inside a transaction, returns
+    /// [`Error::TransactionRequired`] without running it.
+    ///
+    /// This can be used to enforce that all writes go through a transaction, e.g. in codebases
+    /// using optimistic concurrency, where an accidental autocommit write is a bug. Reads are
+    /// still allowed outside of transactions.
+    ///
+    /// Writes are detected by the keyword each statement starts with (`INSERT`, `UPDATE`,
+    /// `DELETE`, `MERGE`, `REPLACE`, `TRUNCATE`, `CALL`, `DO`, `EXECUTE`, `LOCK`, `VACUUM` and
+    /// DDL such as `CREATE` or `DROP`), as well as data-modifying common table expressions
+    /// (`WITH ... DELETE`), `SELECT ... INTO`, `COPY ... FROM` and `EXPLAIN ANALYZE` of a write.
+    /// A `SELECT` that calls a function with side effects is not detected.
+    ///
+    /// Whether a transaction is open is taken from the database server, so a transaction
+    /// started with a raw `BEGIN` (or `START TRANSACTION`) counts as well as one from
+    /// [`Pool::begin()`][crate::pool::Pool::begin].
+    ///
+    /// This also applies to statements executed by [`before_acquire`][Self::before_acquire]
+    /// and [`after_release`][Self::after_release], but not [`after_connect`][Self::after_connect].
+    /// It no longer applies once a connection is [detached][crate::pool::PoolConnection::detach] from the pool.
+    ///
+    /// It also applies to `Migrator::run()`, which creates its table and runs migrations marked
+    /// as `-- no-transaction` outside of a transaction, so running migrations on a pool with this
+    /// enabled fails. Run them on a separate connection or pool.
+    ///
+    /// Defaults to `false`.
+    pub fn require_transaction_for_writes(mut self, require: bool) -> Self {
+        self.require_transaction_for_writes = require;
+        self
+    }
+
+    /// Get whether `require_transaction_for_writes` is currently set.
+    pub fn get_require_transaction_for_writes(&self) -> bool {
+        self.require_transaction_for_writes
+    }
+
+    /// If true, a read-only query executed directly on the pool (e.g. `.fetch_all(&pool)`)
+    /// that fails because the connection was lost is retried once on a fresh connection.
+    ///
+    /// A query is only retried if all of the following hold:
+    ///
+    /// * the error indicates a lost connection: an I/O error such as `connection reset by peer`
+    ///   or a broken pipe, or a database error with an SQLSTATE in class `08`
+    ///   (connection exception) or Postgres' `57P01`-`57P03` (server shutting down);
+    /// * no rows or results were received before the error;
+    /// * every statement is a `SELECT`, `VALUES`, `TABLE`, `SHOW` or `EXPLAIN` (without
+    ///   `ANALYZE`), without `INTO` and without locking rows (`FOR UPDATE`, `FOR SHARE`,
+    ///   `LOCK IN SHARE MODE`); anything else, e.g. `CALL` or `DO`, may have written to the
+    ///   database before the connection was lost;
+    /// * the driver is able to copy the bind arguments.
+    ///
+    /// Queries executed on a connection that was acquired from the pool, or inside a
+    /// transaction, are never retried. Note that a `SELECT` that calls a function with
+    /// side effects is treated as a read.
+    ///
+    /// Defaults to `false`.
+    pub fn retry_read_on_disconnect(mut self, retry: bool) -> Self {
+        self.retry_read_on_disconnect = retry;
+        self
+    }
+
+    /// Get whether `retry_read_on_disconnect` is currently set.
+    pub fn get_retry_read_on_disconnect(&self) -> bool {
+        self.retry_read_on_disconnect
+    }
+
+    /// Set the maximum number of connections that [`Pool::close()`] closes at the same time.
+    ///
+    /// Closing a connection gracefully takes a round-trip to the server, so closing a large
+    /// pool one connection at a time can noticeably delay shutdown. This only affects the idle
+    /// connections; connections that are checked out when the pool is closed are each closed
+    /// as they are returned.
+    ///
+    /// A value of `0` is treated as `1`.
+    ///
+    /// Defaults to `1`.
+    pub fn close_concurrency(mut self, concurrency: usize) -> Self {
+        self.close_concurrency = cmp::max(concurrency, 1);
+        self
+    }
+
+    /// Get the maximum number of connections that are closed at the same time.
+    pub fn get_close_concurrency(&self) -> usize {
+        self.close_concurrency
+    }
+
+    /// Name this pool, so its connections can be told apart from those of other pools
+    /// on the server.
+    ///
+    /// For Postgres, this sets `application_name` to `service[pool_name]`, so the connections
+    /// show up as e.g. `billing[reports]` in `pg_stat_activity`. `service` is the
+    /// `application_name` set on the connect options passed to this pool; if there is none,
+    /// `application_name` is set to `pool_name` alone. The resulting value can be read back with
+    /// [`Pool::connect_options()`] (`PgConnectOptions::get_application_name()`).
+    ///
+    /// This also applies to an `Any` pool connecting to Postgres. MySQL and SQLite log a warning
+    /// and otherwise ignore this. See
+    /// [`ConnectOptions::pool_name()`][crate::connection::ConnectOptions::pool_name].
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+    ///
+    /// let options = PgConnectOptions::new().application_name("billing");
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .pool_name("reports")
+    ///     .connect_with(options)
+    ///     .await?;
+    ///
+    /// assert_eq!(
+    ///     pool.connect_options().get_application_name(),
+    ///     Some("billing[reports]")
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn pool_name(mut self, pool_name: impl Into<String>) -> Self {
+        self.pool_name = Some(pool_name.into());
+        self
+    }
+
+    /// Get the name of this pool, if one was set.
+    pub fn get_pool_name(&self) -> Option<&str> {
+        self.pool_name.as_deref()
+    }
+
+    /// Every `every` times a connection is returned to this pool, free up to `pages` unused pages
+    /// of the database file on it, or all of them if `pages` is `0`.
+    ///
+    /// For SQLite, this runs `PRAGMA incremental_vacuum(pages)`, which only has an effect if the
+    /// database uses `SqliteAutoVacuum::Incremental` (see `SqliteConnectOptions::auto_vacuum()`).
+    /// Other drivers ignore this. See [`Connection::incremental_vacuum()`].
+    ///
+    /// The count is shared by all connections of the pool, and the vacuum runs before the
+    /// connection is made available again. If it fails, the connection is closed.
+    ///
+    /// A value of `0` for `every` is treated as `1`.
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use sqlx::sqlite::{SqliteAutoVacuum, SqliteConnectOptions, SqlitePoolOptions};
+    ///
+    /// let options = SqliteConnectOptions::new()
+    ///     .filename("app.db")
+    ///     .create_if_missing(true)
+    ///     .auto_vacuum(SqliteAutoVacuum::Incremental);
+    ///
+    /// let pool = SqlitePoolOptions::new()
+    ///     // free up to 100 pages on every 50th release
+    ///     .incremental_vacuum_on_release(50, 100)
+    ///     .connect_with(options)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn incremental_vacuum_on_release(mut self, every: u64, pages: u32) -> Self {
+        self.incremental_vacuum_on_release = Some(IncrementalVacuum {
+            every: cmp::max(every, 1),
+            pages,
+        });
+        self
+    }
+
+    /// Set how a connection is cleaned up when it is returned to this pool.
+    ///
+    /// This runs before [`after_release`][Self::after_release], so the callback sees the
+    /// connection as the next user will. If it fails, the connection is closed.
+    ///
+    /// Which one is right depends on what sits between the application and the database,
+    /// so it can be set with `drivers.<database>.reset-on-return` in `sqlx.toml` (see
+    /// [`from_config()`][Self::from_config]), and [`ResetOnReturn`] can be parsed from a string
+    /// to read it from elsewhere at deployment:
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use sqlx::pool::ResetOnReturn;
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// // "discard-all" when connecting directly, "rollback" behind PgBouncer
+    /// let reset: ResetOnReturn = std::env::var("DB_RESET_ON_RETURN")?.parse()?;
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .reset_on_return(reset)
+    ///     .connect("postgres://localhost/app")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Default: [`ResetOnReturn::None`].
+    pub fn reset_on_return(mut self, reset: ResetOnReturn) -> Self {
+        self.reset_on_return = reset;
+        self
+    }
+
+    /// Get how a connection is cleaned up when it is returned to this pool.
+    pub fn get_reset_on_return(&self) -> ResetOnReturn {
+        self.reset_on_return
+    }
+
+    /// Call `callback` with the SQL and elapsed time of every statement executed on a connection
+    /// of this pool that takes at least `threshold` to complete.
+    ///
+    /// This is intended for collecting slow-query metrics or traces without parsing log output.
+    /// The callback runs inline on the task executing the statement, after the statement has
+    /// finished, so it should be cheap; hand off any expensive work to another task.
+    ///
+    /// This replaces any callback set with [`ConnectOptions::on_slow_query()`][crate::connection::ConnectOptions::on_slow_query]
+    /// on the connect options passed to this pool. It is independent of the slow statement
+    /// logging configured with [`ConnectOptions::log_slow_statements()`][crate::connection::ConnectOptions::log_slow_statements].
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use std::time::Duration;
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .on_slow_query(Duration::from_millis(500), |sql, elapsed| {
+    ///         eprintln!("slow query ({elapsed:?}): {sql}");
+    ///     })
+    ///     .connect("postgres:// …")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_slow_query<F>(mut self, threshold: Duration, callback: F) -> Self
+    where
+        F: Fn(&str, Duration) + 'static + Send + Sync,
+    {
+        self.on_slow_query = Some(SlowQueryHook::new(threshold, callback));
+        self
+    }
+
+    /// Prepare `statements` on every new connection, right after
+    /// [`after_connect`][Self::after_connect], so that queries using them don't have to wait
+    /// for their preparation.
+    ///
+    /// The prepared statements are stored in the statement cache of the connection, so this has
+    /// no lasting effect if the cache is disabled or too small to hold them; see e.g.
+    /// `PgConnectOptions::statement_cache_capacity()`.
+    ///
+    /// If a statement fails to prepare, the error is logged and the statement is prepared when it
+    /// is first executed instead, like any other.
+    ///
+    /// Opening a connection, and so an `acquire()` that has to open one, takes longer by the time
+    /// needed to prepare the statements. See
+    /// [`prepare_statements_in_background()`][Self::prepare_statements_in_background] to move it
+    /// off of that path.
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use sqlx::postgres::PgPoolOptions;
+    ///
+    /// let pool = PgPoolOptions::new()
+    ///     .prepare_statements([
+    ///         "SELECT * FROM users WHERE id = $1",
+    ///         "UPDATE users SET last_seen = now() WHERE id = $1",
+    ///     ])
+    ///     .connect("postgres:// …")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prepare_statements<I>(self, statements: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        self.set_prepare_statements(statements, false)
+    }
+
+    /// Like [`prepare_statements()`][Self::prepare_statements], but prepare the statements on a
+    /// spawned task instead of while opening a connection.
+    ///
+    /// The statements are prepared whenever a connection that has not prepared them yet is
+    /// released into the pool, before it becomes idle:
+    ///
+    /// * A connection opened by `acquire()` is handed out right away and prepares the statements
+    ///   once it is first returned to the pool.
+    /// * A connection opened by [`connect()`][Self::connect] or to maintain
+    ///   [`min_connections`][Self::min_connections] prepares the statements before it is first
+    ///   handed out.
+    ///
+    /// Each statement is cached as soon as it is prepared. Until that happens, queries on
+    /// the connection still prepare the statement themselves. The connection is not available to
+    /// `acquire()` while it is preparing the statements.
+    pub fn prepare_statements_in_background<I>(self, statements: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        self.set_prepare_statements(statements, true)
+    }
+
+    /// Get the statements prepared on every new connection.
+    pub fn get_prepare_statements(&self) -> &[String] {
+        self.prepare_statements
+            .as_ref()
+            .map_or(&[], |prepare| &prepare.statements)
+    }
+
+    fn set_prepare_statements<I>(mut self, statements: I, in_background: bool) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        self.prepare_statements = Some(PrepareStatements {
+            statements: statements.into_iter().map(Into::into).collect(),
+            prepare: Arc::new(|conn, sql| {
+                Box::pin(async move { conn.prepare(sql).await.map(drop) })
+            }),
+            in_background,
+        });
+        self
+    }
+
+    /// Share one backoff between all attempts of the pool to open a connection while the
+    /// database is unreachable.
+    ///
+    /// By default, each task that needs a new connection retries on its own, with its own
+    /// exponential backoff. When the database goes down, every connection of the pool then
+    /// retries at once, and keeps doing so as the database comes back up. With this set, after
+    /// an attempt fails, attempts are made one at a time, spaced by a (jittered) delay that grows
+    /// with each failure, until one succeeds.
+    ///
+    /// Attempts are still bounded by [`acquire_timeout`][Self::acquire_timeout].
+    ///
+    /// ```rust,no_run
+    /// # use std::time::Duration;
+    /// # use sqlx::pool::{PoolOptions, ReconnectBackoff};
+    /// # use sqlx::Postgres;
+    /// let options = PoolOptions::<Postgres>::new().reconnect_backoff(
+    ///     ReconnectBackoff::new()
+    ///         .initial_delay(Duration::from_millis(250))
+    ///         .max_delay(Duration::from_secs(10)),
+    /// );
+    /// ```
+    pub fn reconnect_backoff(mut self, backoff: ReconnectBackoff) -> Self {
+        self.reconnect_backoff = Some(backoff);
+        self
+    }
+
+    /// Get the backoff set with [`reconnect_backoff()`][Self::reconnect_backoff], if any.
+    pub fn get_reconnect_backoff(&self) -> Option<&ReconnectBackoff> {
+        self.reconnect_backoff.as_ref()
+    }
+
     /// If set to `true`, calls to `acquire()` are fair and connections  are issued
     /// in first-come-first-serve order. If `false`, "drive-by" tasks may steal idle connections
     /// ahead of tasks that have been waiting.
@@ -525,6 +1184,10 @@ impl<DB: Database> PoolOptions<DB> {
     /// * MySQL: [`MySqlConnectOptions`][crate::mysql::MySqlConnectOptions]
     /// * SQLite: [`SqliteConnectOptions`][crate::sqlite::SqliteConnectOptions]
     /// * MSSQL: [`MssqlConnectOptions`][crate::mssql::MssqlConnectOptions]
+    ///
+    /// This is a shorthand for parsing `url` and calling [`connect_with()`][Self::connect_with].
+    /// Settings that have no representation in a URL, such as callbacks, can only be set
+    /// through the latter.
     pub async fn connect(self, url: &str) -> Result<Pool<DB>, Error> {
         self.connect_with(url.parse()?).await
     }
@@ -534,13 +1197,22 @@ impl<DB: Database> PoolOptions<DB> {
     /// This ensures the configuration is correct.
     ///
     /// The total number of connections opened is <code>max(1, [min_connections][Self::min_connections])</code>.
+    ///
+    /// Every connection of the pool is opened with `options` as given, including any settings
+    /// that can't be written in a URL, such as callbacks or a custom TLS configuration.
+    /// A few pool settings are applied on top; see [`pool_name()`][Self::pool_name] and
+    /// [`on_slow_query()`][Self::on_slow_query].
     pub async fn connect_with(
-        self,
+        mut self,
         options: <DB::Connection as Connection>::Options,
     ) -> Result<Pool<DB>, Error> {
         // Don't take longer than `acquire_timeout` starting from when this is called.
         let deadline = Instant::now() + self.acquire_timeout;
 
+        if let Some(reserve) = self.max_connections_from_server {
+            self.max_connections = self.size_from_server(&options, reserve, deadline).await?;
+        }
+
         let inner = PoolInner::new_arc(self, options);
 
         if inner.options.min_connections > 0 {
@@ -551,7 +1223,7 @@ impl<DB: Database> PoolOptions<DB> {
 
         // If `min_connections` is nonzero then we'll likely just pull a connection
         // from the idle queue here, but it should at least get tested first.
-        let conn = inner.acquire().await?;
+        let conn = inner.acquire(Priority::Normal).await?;
         inner.release(conn);
 
         Ok(Pool(inner))
@@ -568,6 +1240,9 @@ impl<DB: Database> PoolOptions<DB> {
     /// * MySQL: [`MySqlConnectOptions`][crate::mysql::MySqlConnectOptions]
     /// * SQLite: [`SqliteConnectOptions`][crate::sqlite::SqliteConnectOptions]
     /// * MSSQL: [`MssqlConnectOptions`][crate::mssql::MssqlConnectOptions]
+    ///
+    /// This is a shorthand for parsing `url` and calling
+    /// [`connect_lazy_with()`][Self::connect_lazy_with].
     pub fn connect_lazy(self, url: &str) -> Result<Pool<DB>, Error> {
         Ok(self.connect_lazy_with(url.parse()?))
     }
@@ -576,21 +1251,112 @@ impl<DB: Database> PoolOptions<DB> {
     ///
     /// If [`min_connections`][Self::min_connections] is set, a background task will be spawned to
     /// optimistically establish that many connections for the pool.
+    ///
+    /// As with [`connect_with()`][Self::connect_with], `options` is used as given.
     pub fn connect_lazy_with(self, options: <DB::Connection as Connection>::Options) -> Pool<DB> {
         // `min_connections` is guaranteed by the idle reaper now.
         Pool(PoolInner::new_arc(self, options))
     }
 }
 
+impl<DB: Database> PoolOptions<DB> {
+    /// Probe the server's connection limit for [`max_connections_from_server`][Self::max_connections_from_server].
+    async fn size_from_server(
+        &self,
+        connect_options: &<DB::Connection as Connection>::Options,
+        reserve: u32,
+        deadline: Instant,
+    ) -> Result<u32, Error> {
+        let probe = async {
+            let mut conn = DB::Connection::connect_with(connect_options).await?;
+            let res = conn.server_max_connections().await;
+            conn.close().await?;
+            res
+        };
+
+        let server_max =
+            crate::rt::timeout(deadline.saturating_duration_since(Instant::now()), probe)
+                .await
+                .map_err(|_| Error::PoolTimedOut)??;
+
+        let Some(server_max) = server_max else {
+            return Ok(self.max_connections);
+        };
+
+        let mut size = cmp::max(
+            server_max.saturating_sub(reserve),
+            cmp::max(self.min_connections, 1),
+        );
+
+        if let Some(parent) = &self.parent_pool {
+            size = cmp::min(size, parent.options().max_connections);
+        }
+
+        tracing::debug!(
+            server_max,
+            reserve,
+            size,
+            "sized pool from the server's max_connections"
+        );
+
+        Ok(size)
+    }
+
+    /// Install the [`on_slow_query`][Self::on_slow_query] callback and the
+    /// [`pool_name`][Self::pool_name], if any, on `connect_options`.
+    pub(crate) fn apply_to_connect_options(
+        &self,
+        connect_options: <DB::Connection as Connection>::Options,
+    ) -> <DB::Connection as Connection>::Options {
+        let connect_options = match &self.pool_name {
+            Some(pool_name) => connect_options.pool_name(pool_name),
+            None => connect_options,
+        };
+
+        match &self.on_slow_query {
+            Some(hook) => {
+                let hook = hook.clone();
+                connect_options
+                    .on_slow_query(hook.threshold, move |sql, elapsed| (hook.f)(sql, elapsed))
+            }
+            None => connect_options,
+        }
+    }
+}
+
 impl<DB: Database> Debug for PoolOptions<DB> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_struct("PoolOptions")
             .field("max_connections", &self.max_connections)
+            .field(
+                "max_connections_from_server",
+                &self.max_connections_from_server,
+            )
             .field("min_connections", &self.min_connections)
             .field("connect_timeout", &self.acquire_timeout)
             .field("max_lifetime", &self.max_lifetime)
+            .field(
+                "max_queries_per_connection",
+                &self.max_queries_per_connection,
+            )
             .field("idle_timeout", &self.idle_timeout)
+            .field("connection_selection", &self.connection_selection)
             .field("test_before_acquire", &self.test_before_acquire)
+            .field(
+                "require_transaction_for_writes",
+                &self.require_transaction_for_writes,
+            )
+            .field("retry_read_on_disconnect", &self.retry_read_on_disconnect)
+            .field("close_concurrency", &self.close_concurrency)
+            .field("on_slow_query", &self.on_slow_query)
+            .field("pool_name", &self.pool_name)
+            .field(
+                "incremental_vacuum_on_release",
+                &self.incremental_vacuum_on_release,
+            )
+            .field("reset_on_return", &self.reset_on_return)
+            .field("prepare_statements", &self.get_prepare_statements())
+            .field("reconnect_backoff", &self.reconnect_backoff)
             .finish()
     }
 }
@@ -0,0 +1,110 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::task::Waker;
+
+/// The priority of a call to [`Pool::acquire_with_priority()`][super::Pool::acquire_with_priority].
+///
+/// When the pool is saturated, waiting tasks with a higher priority are given a connection
+/// before those with a lower priority. Tasks with the same priority are served in the order
+/// they started waiting.
+///
+/// [`Pool::acquire()`][super::Pool::acquire] and the [`Executor`][crate::executor::Executor]
+/// impl for `&Pool` use [`Priority::Normal`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// For background work that may wait behind everything else, e.g. batch jobs.
+    Low,
+    /// The priority of [`Pool::acquire()`][super::Pool::acquire].
+    #[default]
+    Normal,
+    /// For work that should stay responsive when the pool is saturated, e.g. health checks.
+    High,
+}
+
+type WaiterKey = (Reverse<Priority>, u64);
+
+/// The tasks waiting for a permit, ordered by priority and then by arrival.
+///
+/// Only the task at the head of the queue waits on the pool's semaphore. This way the
+/// semaphore's own (FIFO) queue never decides which task is served next.
+#[derive(Default)]
+pub(super) struct WaitQueue {
+    inner: Mutex<WaitQueueInner>,
+}
+
+#[derive(Default)]
+struct WaitQueueInner {
+    next_id: u64,
+    waiters: BTreeMap<WaiterKey, Option<Waker>>,
+}
+
+impl WaitQueue {
+    pub(super) fn is_empty(&self) -> bool {
+        self.lock().waiters.is_empty()
+    }
+
+    /// Join the queue; the returned ticket leaves it again when dropped.
+    pub(super) fn join(&self, priority: Priority) -> WaitTicket<'_> {
+        let mut inner = self.lock();
+
+        let key = (Reverse(priority), inner.next_id);
+        inner.next_id += 1;
+
+        inner.waiters.insert(key, None);
+
+        // If we jumped ahead of the current head, it has to stop waiting on the semaphore.
+        let mut waiters = inner.waiters.iter();
+        if waiters.next().map(|(head, _)| head) == Some(&key) {
+            if let Some((_, Some(waker))) = waiters.next() {
+                waker.wake_by_ref();
+            }
+        }
+
+        WaitTicket { queue: self, key }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, WaitQueueInner> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+pub(super) struct WaitTicket<'a> {
+    queue: &'a WaitQueue,
+    key: WaiterKey,
+}
+
+impl WaitTicket<'_> {
+    /// Returns `true` if this ticket is at the head of the queue.
+    ///
+    /// Either way, `waker` is woken when this ticket becomes the head or stops being it.
+    pub(super) fn poll_head(&self, waker: &Waker) -> bool {
+        let mut inner = self.queue.lock();
+
+        let is_head = inner.waiters.keys().next() == Some(&self.key);
+
+        if let Some(slot) = inner.waiters.get_mut(&self.key) {
+            match slot {
+                Some(registered) if registered.will_wake(waker) => (),
+                _ => *slot = Some(waker.clone()),
+            }
+        }
+
+        is_head
+    }
+}
+
+impl Drop for WaitTicket<'_> {
+    fn drop(&mut self) {
+        let mut inner = self.queue.lock();
+
+        inner.waiters.remove(&self.key);
+
+        // Let the next task in line wait on the semaphore.
+        if let Some((_, Some(waker))) = inner.waiters.iter().next() {
+            waker.wake_by_ref();
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+use std::time::{Duration, Instant};
+
+use crate::connection::{ConnectOptions, Connection};
+use crate::database::Database;
+use crate::error::Error;
+
+use super::{Pool, PoolConnection};
+
+/// A primary [`Pool`] for writes, with read replicas that reads are spread over in proportion
+/// to their weights.
+///
+/// [`acquire_read()`][Self::acquire_read] picks a replica by smooth weighted round-robin: a
+/// replica with weight 3 serves three times as many reads as one with weight 1, interleaved
+/// rather than in bursts. Setting a replica's weight to zero with
+/// [`set_weight()`][Self::set_weight] drains it: it gets no new reads, but stays part of the
+/// pool and can be weighted back in later.
+///
+/// A replica is considered unhealthy and weighted out for
+/// [`unhealthy_timeout()`][Self::unhealthy_timeout] (30 seconds by default) when connecting to
+/// it fails, e.g. with an I/O or TLS error, either while acquiring a connection for a read or
+/// in a [health check][Self::check_health]. A replica that doesn't give a connection within
+/// [`replica_acquire_timeout()`][Self::replica_acquire_timeout] (5 seconds by default) is
+/// skipped for that read, but not weighted out, since it is usually just busy. Either way the
+/// read is retried on the other replicas, and when no replica is available it goes to the
+/// primary.
+///
+/// The pool of a replica that is down keeps retrying refused connections until its
+/// `acquire_timeout`, so without health checks every read that picks such a replica is
+/// delayed by `replica_acquire_timeout` before it fails over. Run
+/// [`spawn_health_checks()`][Self::spawn_health_checks] to weight out such replicas in the
+/// background: a read is then only delayed until the next check, and only if it picks the
+/// replica in between.
+///
+/// Cloning a `ReplicatedPool` is cheap; the clones share the weights and health of the replicas.
+///
+/// ```rust,no_run
+/// # async fn example() -> sqlx::Result<()> {
+/// use sqlx::pool::ReplicatedPool;
+/// use sqlx::postgres::PgPool;
+///
+/// let pool = ReplicatedPool::new(
+///     PgPool::connect_lazy("postgres://primary/app")?,
+///     [
+///         (PgPool::connect_lazy("postgres://replica-large/app")?, 3),
+///         (PgPool::connect_lazy("postgres://replica-small/app")?, 1),
+///     ],
+/// );
+///
+/// let mut conn = pool.acquire_read().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ReplicatedPool<DB: Database>(Arc<ReplicatedPoolInner<DB>>);
+
+struct ReplicatedPoolInner<DB: Database> {
+    primary: Pool<DB>,
+    replicas: Vec<Pool<DB>>,
+    state: Mutex<SelectionState>,
+}
+
+/// The weights and health of the replicas, indexed like `ReplicatedPoolInner::replicas`.
+struct SelectionState {
+    replicas: Vec<ReplicaState>,
+    unhealthy_timeout: Duration,
+    replica_acquire_timeout: Duration,
+}
+
+struct ReplicaState {
+    weight: u32,
+    /// The running total of smooth weighted round-robin.
+    current: i64,
+    unhealthy_until: Option<Instant>,
+}
+
+impl<DB: Database> ReplicatedPool<DB> {
+    /// Create a pool that writes to `primary` and reads from `replicas`, given with their
+    /// weights.
+    pub fn new(primary: Pool<DB>, replicas: impl IntoIterator<Item = (Pool<DB>, u32)>) -> Self {
+        let (replicas, weights): (Vec<_>, Vec<_>) = replicas.into_iter().unzip();
+
+        let state = SelectionState {
+            replicas: weights
+                .into_iter()
+                .map(|weight| ReplicaState {
+                    weight,
+                    current: 0,
+                    unhealthy_until: None,
+                })
+                .collect(),
+            unhealthy_timeout: Duration::from_secs(30),
+            replica_acquire_timeout: Duration::from_secs(5),
+        };
+
+        ReplicatedPool(Arc::new(ReplicatedPoolInner {
+            primary,
+            replicas,
+            state: Mutex::new(state),
+        }))
+    }
+
+    /// The pool of the primary.
+    pub fn primary(&self) -> &Pool<DB> {
+        &self.0.primary
+    }
+
+    /// The pools of the replicas, in the order they were given to [`new()`][Self::new].
+    pub fn replicas(&self) -> &[Pool<DB>] {
+        &self.0.replicas
+    }
+
+    /// Retrieves a connection to the primary, for writes and reads that must see them.
+    pub fn acquire_write(
+        &self,
+    ) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        self.0.primary.acquire()
+    }
+
+    /// Retrieves a connection to a replica, picked in proportion to the weights of the healthy
+    /// replicas, or to the primary if there are none.
+    ///
+    /// A replica that fails to connect is weighted out for
+    /// [`unhealthy_timeout()`][Self::unhealthy_timeout] and the next one is tried, as is one
+    /// that doesn't give a connection within
+    /// [`replica_acquire_timeout()`][Self::replica_acquire_timeout].
+    pub async fn acquire_read(&self) -> Result<PoolConnection<DB>, Error> {
+        let mut tried = vec![false; self.0.replicas.len()];
+
+        loop {
+            let (index, timeout) = {
+                let mut state = self.lock_state();
+                let index = state.select(&self.0.replicas, &tried);
+                (index, state.replica_acquire_timeout)
+            };
+
+            let Some(index) = index else {
+                return self.0.primary.acquire().await;
+            };
+
+            let acquired = crate::rt::timeout(timeout, self.0.replicas[index].acquire())
+                .await
+                .unwrap_or(Err(Error::PoolTimedOut));
+
+            match acquired {
+                Ok(conn) => return Ok(conn),
+                // the replica is busy rather than broken, or was closed in the meantime
+                Err(e @ (Error::PoolTimedOut | Error::PoolClosed)) => {
+                    tracing::debug!(replica = index, error = %e, "skipping replica");
+                }
+                Err(e) => self.mark_unhealthy(index, &e),
+            }
+
+            tried[index] = true;
+        }
+    }
+
+    /// Check the health of each replica by opening a new connection to it and pinging it,
+    /// within [`replica_acquire_timeout()`][Self::replica_acquire_timeout].
+    ///
+    /// Replicas that fail are weighted out for [`unhealthy_timeout()`][Self::unhealthy_timeout],
+    /// and replicas that pass are weighted back in straight away. The connections bypass the
+    /// pools of the replicas, so a saturated replica still passes.
+    pub async fn check_health(&self) {
+        let timeout = self.replica_acquire_timeout();
+
+        let checks = self.0.replicas.iter().map(|pool| async move {
+            if pool.is_closed() {
+                return Ok(());
+            }
+
+            let options = pool.connect_options();
+
+            crate::rt::timeout(timeout, async {
+                let mut conn = options.connect().await?;
+                conn.ping().await?;
+                conn.close().await
+            })
+            .await
+            .unwrap_or(Err(Error::PoolTimedOut))
+        });
+
+        let results = futures_util::future::join_all(checks).await;
+
+        for (index, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(()) => self.lock_state().replicas[index].unhealthy_until = None,
+                Err(e) => self.mark_unhealthy(index, &e),
+            }
+        }
+    }
+
+    /// Run [`check_health()`][Self::check_health] every `interval` in a background task, until
+    /// the primary is closed or every clone of this `ReplicatedPool` is dropped.
+    ///
+    /// An `interval` shorter than [`unhealthy_timeout()`][Self::unhealthy_timeout] keeps a
+    /// replica that is down weighted out, instead of letting reads find out again each time
+    /// it is weighted back in.
+    pub fn spawn_health_checks(&self, interval: Duration) {
+        let inner = Arc::downgrade(&self.0);
+
+        crate::rt::spawn(async move {
+            loop {
+                crate::rt::sleep(interval).await;
+
+                let Some(pool) = Weak::upgrade(&inner).map(ReplicatedPool) else {
+                    break;
+                };
+
+                if pool.0.primary.is_closed() {
+                    break;
+                }
+
+                pool.check_health().await;
+            }
+        });
+    }
+
+    /// The weight of the replica at `index`.
+    ///
+    /// # Panics
+    /// If there is no replica at `index`.
+    pub fn weight(&self, index: usize) -> u32 {
+        self.lock_state().replicas[index].weight
+    }
+
+    /// Change the weight of the replica at `index`.
+    ///
+    /// A weight of zero drains the replica: it gets no new reads, while connections already
+    /// acquired from it are unaffected.
+    ///
+    /// # Panics
+    /// If there is no replica at `index`.
+    pub fn set_weight(&self, index: usize, weight: u32) {
+        let mut state = self.lock_state();
+        let replica = &mut state.replicas[index];
+
+        replica.weight = weight;
+        replica.current = 0;
+    }
+
+    /// Returns `false` if the replica at `index` is currently weighted out because connecting
+    /// to it failed.
+    ///
+    /// # Panics
+    /// If there is no replica at `index`.
+    pub fn is_healthy(&self, index: usize) -> bool {
+        self.lock_state().replicas[index]
+            .unhealthy_until
+            .map_or(true, |until| until <= Instant::now())
+    }
+
+    /// How long a replica is weighted out after connecting to it failed.
+    pub fn unhealthy_timeout(&self) -> Duration {
+        self.lock_state().unhealthy_timeout
+    }
+
+    /// Set how long a replica is weighted out after connecting to it failed.
+    ///
+    /// Applies to replicas that become unhealthy from now on.
+    pub fn set_unhealthy_timeout(&self, timeout: Duration) {
+        self.lock_state().unhealthy_timeout = timeout;
+    }
+
+    /// How long [`acquire_read()`][Self::acquire_read] waits for a connection from a replica
+    /// before trying the next one, and how long a [health check][Self::check_health] waits for
+    /// a replica.
+    pub fn replica_acquire_timeout(&self) -> Duration {
+        self.lock_state().replica_acquire_timeout
+    }
+
+    /// Set how long [`acquire_read()`][Self::acquire_read] waits for a connection from a
+    /// replica before trying the next one, and how long a [health check][Self::check_health]
+    /// waits for a replica.
+    ///
+    /// This bounds how long a read is delayed by each replica that is busy or down. Keep it
+    /// above the time it takes to open a connection to a replica.
+    pub fn set_replica_acquire_timeout(&self, timeout: Duration) {
+        self.lock_state().replica_acquire_timeout = timeout;
+    }
+
+    /// Close the primary and all replicas.
+    pub async fn close(&self) {
+        let replicas = self.0.replicas.iter().map(Pool::close);
+
+        futures_util::future::join(
+            self.0.primary.close(),
+            futures_util::future::join_all(replicas),
+        )
+        .await;
+    }
+
+    fn mark_unhealthy(&self, index: usize, error: &Error) {
+        tracing::warn!(replica = index, %error, "replica is unhealthy");
+
+        let mut state = self.lock_state();
+        let until = Instant::now() + state.unhealthy_timeout;
+        state.replicas[index].unhealthy_until = Some(until);
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, SelectionState> {
+        self.0
+            .state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<DB: Database> Clone for ReplicatedPool<DB> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<DB: Database> fmt::Debug for ReplicatedPool<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplicatedPool")
+            .field("primary", &self.0.primary)
+            .field("replicas", &self.0.replicas)
+            .finish()
+    }
+}
+
+impl SelectionState {
+    /// Pick the next replica, skipping those that are `tried`, drained, unhealthy or closed.
+    fn select<DB: Database>(&mut self, pools: &[Pool<DB>], tried: &[bool]) -> Option<usize> {
+        self.select_by(Instant::now(), |index| !tried[index] && !pools[index].is_closed())
+    }
+
+    fn select_by(&mut self, now: Instant, available: impl Fn(usize) -> bool) -> Option<usize> {
+        let mut total = 0;
+        let mut selected: Option<(usize, i64)> = None;
+
+        for (index, replica) in self.replicas.iter_mut().enumerate() {
+            if replica.unhealthy_until.is_some_and(|until| until <= now) {
+                replica.unhealthy_until = None;
+            }
+
+            if replica.weight == 0 || replica.unhealthy_until.is_some() || !available(index) {
+                continue;
+            }
+
+            replica.current += i64::from(replica.weight);
+            total += i64::from(replica.weight);
+
+            if selected.map_or(true, |(_, current)| replica.current > current) {
+                selected = Some((index, replica.current));
+            }
+        }
+
+        let (selected, _) = selected?;
+        self.replicas[selected].current -= total;
+
+        Some(selected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ReplicaState, SelectionState};
+    use std::time::{Duration, Instant};
+
+    fn state(weights: &[u32]) -> SelectionState {
+        SelectionState {
+            replicas: weights
+                .iter()
+                .map(|&weight| ReplicaState {
+                    weight,
+                    current: 0,
+                    unhealthy_until: None,
+                })
+                .collect(),
+            unhealthy_timeout: Duration::from_secs(30),
+            replica_acquire_timeout: Duration::from_secs(5),
+        }
+    }
+
+    fn picks(state: &mut SelectionState, now: Instant, n: usize) -> Vec<Option<usize>> {
+        (0..n).map(|_| state.select_by(now, |_| true)).collect()
+    }
+
+    #[test]
+    fn it_selects_in_proportion_to_weight() {
+        let now = Instant::now();
+        let mut state = state(&[3, 1]);
+
+        // the reads of the heavier replica are interleaved with those of the lighter one
+        assert_eq!(picks(&mut state, now, 8), [0, 0, 1, 0, 0, 0, 1, 0].map(Some));
+    }
+
+    #[test]
+    fn it_skips_drained_and_unhealthy_replicas() {
+        let now = Instant::now();
+        let mut state = state(&[1, 0, 1]);
+
+        assert_eq!(picks(&mut state, now, 4), [0, 2, 0, 2].map(Some));
+
+        state.replicas[2].unhealthy_until = Some(now + Duration::from_secs(1));
+        assert_eq!(picks(&mut state, now, 2), [0, 0].map(Some));
+
+        // healthy again once the timeout has passed
+        let later = now + Duration::from_secs(1);
+        assert_eq!(picks(&mut state, later, 2), [0, 2].map(Some));
+        assert_eq!(state.replicas[2].unhealthy_until, None);
+
+        state.replicas[0].weight = 0;
+        state.replicas[2].weight = 0;
+        assert_eq!(picks(&mut state, later, 1), [None]);
+    }
+}
@@ -1,10 +1,25 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
 use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
 use either::Either;
-use futures_core::stream::BoxStream;
+use futures_core::stream::{BoxStream, Stream};
 use futures_util::{future, StreamExt, TryFutureExt, TryStreamExt};
+use once_cell::sync::Lazy;
+use tracing::callsite::{Callsite, Identifier};
+use tracing::field::{FieldSet, Value};
+use tracing::metadata::Kind;
+use tracing::subscriber::Interest;
+use tracing::{Instrument, Level, Metadata, Span};
 
 use crate::arguments::{Arguments, IntoArguments};
+use crate::connection::{CancelHandle, Connection};
 use crate::database::{Database, HasStatementCache};
 use crate::encode::Encode;
 use crate::error::{BoxDynError, Error};
@@ -19,8 +34,14 @@ pub struct Query<'q, DB: Database, A> {
     pub(crate) arguments: Option<Result<A, BoxDynError>>,
     pub(crate) database: PhantomData<DB>,
     pub(crate) persistent: bool,
+    pub(crate) max_rows: Option<usize>,
+    pub(crate) total_timeout: Option<Duration>,
+    pub(crate) cache_ttl: Option<Duration>,
+    pub(crate) span_attributes: Vec<SpanAttribute>,
 }
 
+pub(crate) type SpanAttribute = (&'static str, Cow<'static, str>);
+
 /// A single SQL query that will map its results to an owned Rust type.
 ///
 /// Executes as a prepared statement.
@@ -70,6 +91,11 @@ where
     fn persistent(&self) -> bool {
         self.persistent
     }
+
+    #[inline]
+    fn cache_ttl(&self) -> Option<Duration> {
+        self.cache_ttl
+    }
 }
 
 impl<'q, DB: Database> Query<'q, DB, <DB as Database>::Arguments<'q>> {
@@ -109,6 +135,73 @@ impl<'q, DB: Database> Query<'q, DB, <DB as Database>::Arguments<'q>> {
         arguments.add(value)
     }
 
+    /// Filter the rows of this query by `<column> = ANY(<values>)`, running it once for every
+    /// `chunk_size` values if there are more than that, and merging the results.
+    ///
+    /// A single array parameter avoids the bind parameter limit, but a huge one can still make
+    /// for a slow query, or run into other limits of the database. Below the threshold of
+    /// `chunk_size`, the query runs once with all of `values`, as if it was written that way.
+    ///
+    /// The SQL of the query must end where the condition goes, usually with `WHERE` or `AND`:
+    /// the condition is appended to it, binding the chunk of `values` after the arguments that
+    /// were bound so far. `column` is appended as-is, so it must not come from untrusted input.
+    ///
+    /// ### Merging Results
+    /// [`AnyChunked::fetch_all()`] returns the rows of each chunk, in the order of the chunks,
+    /// and [`AnyChunked::execute()`] the combined result of all of them. Each execution is a
+    /// query of its own, so:
+    ///
+    /// * the rows are only ordered within each chunk; sort them after fetching if it matters,
+    /// * `LIMIT`, `DISTINCT`, `GROUP BY` and aggregates apply to each chunk separately,
+    /// * a value that appears in more than one chunk returns its rows once for each of them;
+    ///   deduplicate `values` first to avoid this,
+    /// * the executions are not run in a transaction of their own; pass a connection
+    ///   in a [`Transaction`][crate::transaction::Transaction] for a consistent view of the data.
+    ///
+    /// [`.max_rows()`][Self::max_rows] limits the total number of rows, and
+    /// [`.total_timeout()`][Self::total_timeout] each execution. Nothing is executed if
+    /// `values` is empty.
+    ///
+    /// The arguments that were bound before are copied for each execution, which not every
+    /// driver supports; currently, this is only for Postgres, which has arrays.
+    ///
+    /// ### Panics
+    /// If `chunk_size` is zero.
+    ///
+    /// ### Example (Postgres)
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection, ids: Vec<i64>) -> sqlx::Result<()> {
+    /// let rows = sqlx::query("SELECT * FROM users WHERE tenant_id = $1 AND")
+    ///     .bind(42_i64)
+    ///     // runs `... AND id = ANY($2)` once for every 10,000 ids
+    ///     .any_chunked("id", ids, 10_000)
+    ///     .fetch_all(conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn any_chunked<T>(
+        self,
+        column: &str,
+        values: Vec<T>,
+        chunk_size: usize,
+    ) -> AnyChunked<'q, DB, T> {
+        assert!(chunk_size > 0, "`chunk_size` must be greater than zero");
+
+        let sql = match self.statement {
+            Either::Right(statement) => statement.sql(),
+            Either::Left(sql) => sql,
+        };
+
+        AnyChunked {
+            sql: format!("{sql} {column} = ANY("),
+            query: self,
+            values,
+            chunk_size,
+        }
+    }
+
     fn get_arguments(&mut self) -> Result<&mut DB::Arguments<'q>, BoxDynError> {
         let Some(Ok(arguments)) = self.arguments.as_mut().map(Result::as_mut) else {
             return Err("A previous call to Query::bind produced an error"
@@ -145,6 +238,195 @@ where
     DB: Database,
     A: 'q + IntoArguments<'q, DB>,
 {
+    /// Return [`Error::RowLimitExceeded`] if the query returns more than `max_rows` rows.
+    ///
+    /// This is a safety valve for queries whose result set size is not under your control, e.g.
+    /// user-supplied filters, to prevent a runaway query from exhausting available memory.
+    /// Rows are counted as they are streamed, and the query is aborted as soon as row
+    /// `max_rows + 1` is received, so no more than `max_rows` rows are ever collected.
+    ///
+    /// This only applies when the query is executed using its own methods
+    /// (e.g. [`.fetch_all()`][Self::fetch_all]), not when it is passed to an [`Executor`] directly.
+    /// The limit is checked on the client, so also adding a `LIMIT` clause to the query
+    /// saves the server the work of producing rows that will be discarded.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut conn: sqlx::PgConnection) -> sqlx::Result<()> {
+    /// match sqlx::query("SELECT * FROM articles WHERE title ILIKE $1")
+    ///     .bind("%rust%")
+    ///     .max_rows(1000)
+    ///     .fetch_all(&mut conn)
+    ///     .await
+    /// {
+    ///     Ok(rows) => println!("found {} articles", rows.len()),
+    ///     Err(sqlx::Error::RowLimitExceeded { max_rows }) => {
+    ///         println!("more than {max_rows} articles found; please narrow your search")
+    ///     }
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Return [`Error::QueryTimedOut`] if the query does not complete within `timeout`,
+    /// including the time taken to stream all of its rows.
+    ///
+    /// The time starts when the query is first polled and covers everything up to the end of
+    /// the result set, so it bounds a slow server, a server that trickles rows, and a slow
+    /// consumer of [`.fetch()`][Self::fetch] alike. Time spent between polls of the stream
+    /// counts too.
+    ///
+    /// On expiry the query is abandoned and, if the executor is a connection that supports it
+    /// (currently Postgres), a cancel request is sent to the server so it stops working on the
+    /// query. Queries run on a [`Pool`][crate::pool::Pool] directly are abandoned without
+    /// being cancelled; acquire a connection first to have them cancelled too.
+    ///
+    /// This is enforced on the client. It is independent of server-side limits such as
+    /// Postgres' `statement_timeout`, which only bound the time the server spends executing
+    /// the statement, and not how long it takes for the rows to be received.
+    ///
+    /// As with [`.max_rows()`][Self::max_rows], this only applies when the query is executed
+    /// using its own methods, not when it is passed to an [`Executor`] directly.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut conn: sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use std::time::Duration;
+    /// use futures_util::TryStreamExt;
+    ///
+    /// let mut rows = sqlx::query("SELECT * FROM events")
+    ///     .total_timeout(Duration::from_secs(30))
+    ///     .fetch(&mut conn);
+    ///
+    /// while let Some(row) = rows.try_next().await? {
+    ///     // if this takes too long in total, the next call to `.try_next()`
+    ///     // returns `Err(sqlx::Error::QueryTimedOut { .. })`
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn total_timeout(mut self, timeout: Duration) -> Self {
+        self.total_timeout = Some(timeout);
+        self
+    }
+
+    /// Let a [`CachingExecutor`] return the rows of this query from its cache if they were
+    /// fetched with the same arguments less than `ttl` ago, instead of running it.
+    ///
+    /// **Only use this for queries that don't modify anything** and whose results may be
+    /// `ttl` out of date: a cached result is returned even if the data changed since, e.g.
+    /// in the same transaction. Executors other than [`CachingExecutor`] ignore this.
+    ///
+    /// See the [`query_cache`][crate::query_cache] module for details.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+    /// use std::time::Duration;
+    /// use sqlx::{CachingExecutor, QueryCache};
+    ///
+    /// let cache = QueryCache::new(100);
+    ///
+    /// let currencies = sqlx::query("SELECT code, name FROM currencies")
+    ///     .cacheable(Duration::from_secs(300))
+    ///     .fetch_all(CachingExecutor::new(&pool, &cache))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`CachingExecutor`]: crate::query_cache::CachingExecutor
+    pub fn cacheable(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Run the query in a `db.query` span with these key-value pairs attached, e.g. a tenant id,
+    /// for slicing traces and query metrics by them.
+    ///
+    /// Each pair is recorded as a field of the span, so an OpenTelemetry layer exports it as an
+    /// attribute. The span also has the `summary` field of the statement logs, which a pair
+    /// named `summary` replaces. It is created at the `INFO` level with the `sqlx::query`
+    /// target, and only if that is enabled. Calling this again adds more pairs.
+    ///
+    /// `tracing` needs the field names of a span up front, so SQLx registers a span definition
+    /// for each distinct set of names it sees, and keeps it for the rest of the program. The
+    /// names should therefore be a fixed set, like `tenant_id`, rather than built at runtime;
+    /// the values are free to vary.
+    ///
+    /// As with [`.max_rows()`][Self::max_rows], this only applies when the query is executed
+    /// using its own methods, not when it is passed to an [`Executor`] directly.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(mut conn: sqlx::PgConnection, tenant_id: i64) -> sqlx::Result<()> {
+    /// let rows = sqlx::query("SELECT * FROM invoices WHERE tenant_id = $1")
+    ///     .bind(tenant_id)
+    ///     .with_span_attributes([("tenant_id", tenant_id.to_string()), ("flag", "beta".into())])
+    ///     .fetch_all(&mut conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_span_attributes<I, V>(mut self, attributes: I) -> Self
+    where
+        I: IntoIterator<Item = (&'static str, V)>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.span_attributes.extend(
+            attributes
+                .into_iter()
+                .map(|(key, value)| (key, value.into())),
+        );
+        self
+    }
+
+    /// Run the query on a connection and also return [`QueryStats`] about its execution.
+    ///
+    /// The server's counters are read before and after the query, which takes extra round-trips:
+    /// two on MySQL, and four on Postgres, which checks that `pg_stat_statements` is available
+    /// each time. It is meant for query tuning and dashboards rather than every query. What each
+    /// backend reports:
+    ///
+    /// | Field            | Postgres             | MySQL                 | SQLite |
+    /// |------------------|----------------------|-----------------------|--------|
+    /// | `rows_examined`  | -                    | `Handler_read_*`      | -      |
+    /// | `rows_returned`  | yes                  | yes                   | yes    |
+    /// | `server_time`    | `pg_stat_statements` | -                     | -      |
+    /// | `bytes_received` | -                    | `Bytes_sent`          | -      |
+    ///
+    /// `rows_returned` is counted by the client. The others are the differences of the
+    /// server's counters named in the table, and `None` where the backend has no such counter.
+    ///
+    /// * Postgres needs the [`pg_stat_statements`](https://www.postgresql.org/docs/current/pgstatstatements.html)
+    ///   extension, version 13 or later, loaded with `shared_preload_libraries` and created in
+    ///   the database; otherwise `server_time` is `None`. The query is looked up by its text,
+    ///   which only matches if it has no literals for `pg_stat_statements` to replace with
+    ///   parameters, and the time of concurrent executions of the same query by other sessions
+    ///   of the same user is included.
+    /// * MySQL's session status counters include the cost of reading the counters themselves,
+    ///   so they are a slight overestimate.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::MySqlConnection) -> sqlx::Result<()> {
+    /// let (rows, stats) = sqlx::query("SELECT * FROM orders WHERE customer_id = ?")
+    ///     .bind(42)
+    ///     .with_stats()
+    ///     .fetch_all(conn)
+    ///     .await?;
+    ///
+    /// println!(
+    ///     "{} rows returned, {:?} rows examined",
+    ///     stats.rows_returned, stats.rows_examined
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_stats(self) -> WithStats<'q, DB, A> {
+        WithStats { query: self }
+    }
+
     /// Map each row in the result to another type.
     ///
     /// See [`try_map`](Query::try_map) for a fallible version of this method.
@@ -187,7 +469,12 @@ where
         A: 'e,
         E: Executor<'c, Database = DB>,
     {
-        executor.execute(self).await
+        let span = self.span();
+        let (timeout, cancel) = self.timeout_for(&executor);
+
+        timeout_query(executor.execute(self), timeout, cancel)
+            .instrument(span)
+            .await
     }
 
     /// Execute multiple queries and return the rows affected from each query, in a stream.
@@ -202,7 +489,10 @@ where
         A: 'e,
         E: Executor<'c, Database = DB>,
     {
-        executor.execute_many(self)
+        let span = self.span();
+        let (timeout, cancel) = self.timeout_for(&executor);
+
+        instrument_rows(timeout_rows(executor.execute_many(self), timeout, cancel), span)
     }
 
     /// Execute the query and return the generated results as a stream.
@@ -213,7 +503,68 @@ where
         A: 'e,
         E: Executor<'c, Database = DB>,
     {
-        executor.fetch(self)
+        let max_rows = self.max_rows;
+        let span = self.span();
+        let (timeout, cancel) = self.timeout_for(&executor);
+
+        instrument_rows(
+            timeout_rows(
+                limit_rows(executor.fetch(self), max_rows, |_| true),
+                timeout,
+                cancel,
+            ),
+            span,
+        )
+    }
+
+    /// Execute the query and return the generated results as a stream,
+    /// with each row converted by the fallible closure `f`.
+    ///
+    /// This is shorthand for `.try_map(f).fetch(executor)`.
+    #[inline]
+    pub fn fetch_map<'e, 'c: 'e, E, F, O>(
+        self,
+        executor: E,
+        f: F,
+    ) -> BoxStream<'e, Result<O, Error>>
+    where
+        'q: 'e,
+        A: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        F: 'e + FnMut(DB::Row) -> Result<O, Error> + Send,
+        O: 'e + Send + Unpin,
+    {
+        self.try_map(f).fetch(executor)
+    }
+
+    /// Execute the query and collect the results into a [`HashMap`], with each row converted
+    /// into a key-value pair by the fallible closure `f`.
+    ///
+    /// If more than one row produces the same key, the value from the last such row is kept.
+    /// Add an `ORDER BY` clause if it matters which one that is.
+    ///
+    /// ### Note: beware result set size.
+    /// This will attempt to collect the full result set of the query into memory.
+    ///
+    /// To avoid exhausting available memory, ensure the result set has a known upper bound,
+    /// e.g. using `LIMIT`.
+    #[inline]
+    pub async fn fetch_map_by<'e, 'c: 'e, E, F, K, V>(
+        self,
+        executor: E,
+        f: F,
+    ) -> Result<HashMap<K, V>, Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        DB: 'e,
+        F: 'e + FnMut(DB::Row) -> Result<(K, V), Error> + Send,
+        K: 'e + Eq + Hash + Send + Unpin,
+        V: 'e + Send + Unpin,
+    {
+        self.try_map(f).fetch(executor).try_collect().await
     }
 
     /// Execute multiple queries and return the generated results as a stream.
@@ -232,7 +583,18 @@ where
         A: 'e,
         E: Executor<'c, Database = DB>,
     {
-        executor.fetch_many(self)
+        let max_rows = self.max_rows;
+        let span = self.span();
+        let (timeout, cancel) = self.timeout_for(&executor);
+
+        instrument_rows(
+            timeout_rows(
+                limit_rows(executor.fetch_many(self), max_rows, Either::is_right),
+                timeout,
+                cancel,
+            ),
+            span,
+        )
     }
 
     /// Execute the query and return all the resulting rows collected into a [`Vec`].
@@ -249,7 +611,17 @@ where
         A: 'e,
         E: Executor<'c, Database = DB>,
     {
-        executor.fetch_all(self).await
+        if self.max_rows.is_some() {
+            // Don't let the driver collect the whole result set first.
+            return self.fetch(executor).try_collect().await;
+        }
+
+        let span = self.span();
+        let (timeout, cancel) = self.timeout_for(&executor);
+
+        timeout_query(executor.fetch_all(self), timeout, cancel)
+            .instrument(span)
+            .await
     }
 
     /// Execute the query, returning the first row or [`Error::RowNotFound`] otherwise.
@@ -271,7 +643,15 @@ where
         A: 'e,
         E: Executor<'c, Database = DB>,
     {
-        executor.fetch_one(self).await
+        let max_rows = self.max_rows;
+        let span = self.span();
+        let (timeout, cancel) = self.timeout_for(&executor);
+        let row = timeout_query(executor.fetch_one(self), timeout, cancel)
+            .instrument(span)
+            .await?;
+
+        check_single_row(max_rows)?;
+        Ok(row)
     }
 
     /// Execute the query, returning the first row or `None` otherwise.
@@ -293,7 +673,18 @@ where
         A: 'e,
         E: Executor<'c, Database = DB>,
     {
-        executor.fetch_optional(self).await
+        let max_rows = self.max_rows;
+        let span = self.span();
+        let (timeout, cancel) = self.timeout_for(&executor);
+        let row = timeout_query(executor.fetch_optional(self), timeout, cancel)
+            .instrument(span)
+            .await?;
+
+        if row.is_some() {
+            check_single_row(max_rows)?;
+        }
+
+        Ok(row)
     }
 }
 
@@ -321,6 +712,31 @@ where
     fn persistent(&self) -> bool {
         self.inner.arguments.is_some()
     }
+
+    #[inline]
+    fn cache_ttl(&self) -> Option<Duration> {
+        self.inner.cache_ttl
+    }
+}
+
+impl<'q, DB, F, A> Map<'q, DB, F, A>
+where
+    DB: Database + HasStatementCache,
+{
+    /// If `true`, the statement will get prepared once and cached to the
+    /// connection's statement cache.
+    ///
+    /// If queried once with the flag set to `true`, all subsequent queries
+    /// matching the one with the flag will use the cached statement until the
+    /// cache is cleared.
+    ///
+    /// If `false`, the prepared statement will be closed after execution.
+    ///
+    /// Default: `true`.
+    pub fn persistent(mut self, value: bool) -> Self {
+        self.inner = self.inner.persistent(value);
+        self
+    }
 }
 
 impl<'q, DB, F, O, A> Map<'q, DB, F, A>
@@ -330,6 +746,34 @@ where
     O: Send + Unpin,
     A: 'q + Send + IntoArguments<'q, DB>,
 {
+    /// Return [`Error::RowLimitExceeded`] if the query returns more than `max_rows` rows.
+    ///
+    /// See [`Query::max_rows`] for details.
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.inner = self.inner.max_rows(max_rows);
+        self
+    }
+
+    /// Return [`Error::QueryTimedOut`] if the query does not complete within `timeout`.
+    ///
+    /// See [`Query::total_timeout`] for details.
+    pub fn total_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.total_timeout(timeout);
+        self
+    }
+
+    /// Run the query in a `db.query` span with these key-value pairs attached.
+    ///
+    /// See [`Query::with_span_attributes`] for details.
+    pub fn with_span_attributes<I, V>(mut self, attributes: I) -> Self
+    where
+        I: IntoIterator<Item = (&'static str, V)>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.inner = self.inner.with_span_attributes(attributes);
+        self
+    }
+
     /// Map each row in the result to another type.
     ///
     /// See [`try_map`](Map::try_map) for a fallible version of this method.
@@ -404,20 +848,31 @@ where
         F: 'e,
         O: 'e,
     {
-        Box::pin(try_stream! {
-            let mut s = executor.fetch_many(self.inner);
-
-            while let Some(v) = s.try_next().await? {
-                r#yield!(match v {
-                    Either::Left(v) => Either::Left(v),
-                    Either::Right(row) => {
-                        Either::Right((self.mapper)(row)?)
-                    }
-                });
-            }
+        let span = self.inner.span();
+        let (timeout, cancel) = self.inner.timeout_for(&executor);
+
+        let rows = timeout_rows(
+            Box::pin(try_stream! {
+                let max_rows = self.inner.max_rows;
+                let mut s =
+                    limit_rows(executor.fetch_many(self.inner), max_rows, Either::is_right);
+
+                while let Some(v) = s.try_next().await? {
+                    r#yield!(match v {
+                        Either::Left(v) => Either::Left(v),
+                        Either::Right(row) => {
+                            Either::Right((self.mapper)(row)?)
+                        }
+                    });
+                }
 
-            Ok(())
-        })
+                Ok(())
+            }),
+            timeout,
+            cancel,
+        );
+
+        instrument_rows(rows, span)
     }
 
     /// Execute the query and return all the resulting rows collected into a [`Vec`].
@@ -486,9 +941,15 @@ where
         F: 'e,
         O: 'e,
     {
-        let row = executor.fetch_optional(self.inner).await?;
+        let max_rows = self.inner.max_rows;
+        let span = self.inner.span();
+        let (timeout, cancel) = self.inner.timeout_for(&executor);
+        let row = timeout_query(executor.fetch_optional(self.inner), timeout, cancel)
+            .instrument(span)
+            .await?;
 
         if let Some(row) = row {
+            check_single_row(max_rows)?;
             (self.mapper)(row).map(Some)
         } else {
             Ok(None)
@@ -496,6 +957,496 @@ where
     }
 }
 
+/// A query that is run once for every chunk of an `= ANY` parameter.
+///
+/// Returned by [`Query::any_chunked()`].
+#[must_use = "query must be executed to affect database"]
+pub struct AnyChunked<'q, DB: Database, T> {
+    // the SQL of `query`, followed by `<column> = ANY(`
+    sql: String,
+    // holds the arguments bound so far, and the settings to run each chunk with
+    query: Query<'q, DB, <DB as Database>::Arguments<'q>>,
+    values: Vec<T>,
+    chunk_size: usize,
+}
+
+impl<'q, DB, T> AnyChunked<'q, DB, T>
+where
+    DB: Database,
+    Vec<T>: 'q + Encode<'q, DB> + Type<DB>,
+    for<'s> DB::Arguments<'q>: IntoArguments<'s, DB>,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    /// Execute the query for each chunk and return the combined number of rows affected.
+    pub async fn execute(mut self, conn: &mut DB::Connection) -> Result<DB::QueryResult, Error> {
+        let arguments = self.take_arguments()?;
+        let mut values = std::mem::take(&mut self.values).into_iter().peekable();
+        let mut result = DB::QueryResult::default();
+
+        while values.peek().is_some() {
+            let chunk = values.by_ref().take(self.chunk_size).collect();
+            let (sql, arguments) = self.chunk(&arguments, chunk)?;
+
+            result.extend([self
+                .query_for(&sql, arguments, self.query.max_rows)
+                .execute(&mut *conn)
+                .await?]);
+        }
+
+        Ok(result)
+    }
+
+    /// Execute the query for each chunk and return the rows of all of them, in the order of
+    /// the chunks.
+    ///
+    /// ### Note: beware result set size.
+    /// This will attempt to collect the full result set of the query into memory.
+    /// Set [`Query::max_rows()`] to bound it.
+    pub async fn fetch_all(mut self, conn: &mut DB::Connection) -> Result<Vec<DB::Row>, Error> {
+        let arguments = self.take_arguments()?;
+        let mut values = std::mem::take(&mut self.values).into_iter().peekable();
+        let max_rows = self.query.max_rows;
+        let mut rows = Vec::new();
+
+        while values.peek().is_some() {
+            let chunk = values.by_ref().take(self.chunk_size).collect();
+            let (sql, arguments) = self.chunk(&arguments, chunk)?;
+            let remaining = max_rows.map(|max_rows| max_rows - rows.len());
+
+            match self
+                .query_for(&sql, arguments, remaining)
+                .fetch_all(&mut *conn)
+                .await
+            {
+                Ok(chunk) => rows.extend(chunk),
+                // report the limit of the whole query
+                Err(Error::RowLimitExceeded { .. }) => {
+                    return Err(Error::RowLimitExceeded {
+                        max_rows: max_rows.unwrap_or_default(),
+                    })
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(rows)
+    }
+}
+
+impl<'q, DB, T> AnyChunked<'q, DB, T>
+where
+    DB: Database,
+    Vec<T>: 'q + Encode<'q, DB> + Type<DB>,
+{
+    // the arguments bound before `.any_chunked()`, or the error binding them
+    fn take_arguments(&mut self) -> Result<DB::Arguments<'q>, Error> {
+        Ok(self
+            .query
+            .arguments
+            .take()
+            .transpose()
+            .map_err(Error::Encode)?
+            .unwrap_or_default())
+    }
+
+    // the SQL and the arguments to run the query with for one chunk of the values
+    fn chunk(
+        &self,
+        arguments: &DB::Arguments<'q>,
+        chunk: Vec<T>,
+    ) -> Result<(String, DB::Arguments<'q>), Error> {
+        let mut arguments = arguments.try_clone().ok_or_else(|| {
+            Error::Configuration("this driver does not support `Query::any_chunked()`".into())
+        })?;
+
+        arguments.add(chunk).map_err(Error::Encode)?;
+
+        let mut sql = self.sql.clone();
+        arguments
+            .format_placeholder(&mut sql)
+            .map_err(|e| Error::Encode(e.into()))?;
+        sql.push(')');
+
+        Ok((sql, arguments))
+    }
+
+    // a query for one chunk, with the settings of the query `.any_chunked()` was called on
+    fn query_for<'s>(
+        &self,
+        sql: &'s str,
+        arguments: DB::Arguments<'q>,
+        max_rows: Option<usize>,
+    ) -> Query<'s, DB, DB::Arguments<'q>> {
+        Query {
+            statement: Either::Left(sql),
+            arguments: Some(Ok(arguments)),
+            database: PhantomData,
+            persistent: self.query.persistent,
+            max_rows,
+            total_timeout: self.query.total_timeout,
+            cache_ttl: self.query.cache_ttl,
+            span_attributes: self.query.span_attributes.clone(),
+        }
+    }
+}
+
+/// A query that also returns statistics about its execution.
+///
+/// Returned by [`Query::with_stats()`].
+#[must_use = "query must be executed to affect database"]
+pub struct WithStats<'q, DB: Database, A> {
+    query: Query<'q, DB, A>,
+}
+
+/// Statistics about the execution of a query, returned by [`Query::with_stats()`].
+///
+/// Which fields are populated depends on the backend; see [`Query::with_stats()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct QueryStats {
+    /// The number of rows the server read to run the query.
+    pub rows_examined: Option<u64>,
+
+    /// The number of rows returned to the client; always `0` for
+    /// [`WithStats::execute()`].
+    pub rows_returned: u64,
+
+    /// The time the server spent executing the query.
+    pub server_time: Option<Duration>,
+
+    /// The number of bytes the client received from the server for the query.
+    pub bytes_received: Option<u64>,
+}
+
+/// Cumulative counters of the server, from which [`QueryStats`] are computed.
+#[doc(hidden)]
+#[derive(Debug, Clone, Default)]
+pub struct QueryStatsCounters {
+    pub rows_examined: Option<u64>,
+    pub server_time: Option<Duration>,
+    pub bytes_sent: Option<u64>,
+}
+
+impl<'q, DB, A> WithStats<'q, DB, A>
+where
+    DB: Database,
+    A: 'q + IntoArguments<'q, DB> + Send,
+{
+    /// Execute the query and return the total number of rows affected, with the statistics.
+    pub async fn execute<C>(self, conn: &mut C) -> Result<(DB::QueryResult, QueryStats), Error>
+    where
+        C: Connection<Database = DB>,
+        for<'c> &'c mut C: Executor<'c, Database = DB>,
+    {
+        let sql = self.sql();
+        let before = conn.query_stats_counters(sql).await?;
+        let result = self.query.execute(&mut *conn).await?;
+        let after = conn.query_stats_counters(sql).await?;
+
+        Ok((result, QueryStats::between(before, after, 0)))
+    }
+
+    /// Execute the query and return all the generated results, with the statistics.
+    ///
+    /// ### Note: beware result set size.
+    /// This will attempt to collect the full result set of the query into memory.
+    /// Set [`Query::max_rows()`] to bound it.
+    pub async fn fetch_all<C>(self, conn: &mut C) -> Result<(Vec<DB::Row>, QueryStats), Error>
+    where
+        C: Connection<Database = DB>,
+        for<'c> &'c mut C: Executor<'c, Database = DB>,
+    {
+        let sql = self.sql();
+        let before = conn.query_stats_counters(sql).await?;
+        let rows = self.query.fetch_all(&mut *conn).await?;
+        let after = conn.query_stats_counters(sql).await?;
+
+        let stats = QueryStats::between(before, after, rows.len() as u64);
+        Ok((rows, stats))
+    }
+
+    fn sql(&self) -> &'q str {
+        match self.query.statement {
+            Either::Right(statement) => statement.sql(),
+            Either::Left(sql) => sql,
+        }
+    }
+}
+
+impl QueryStats {
+    fn between(before: QueryStatsCounters, after: QueryStatsCounters, rows_returned: u64) -> Self {
+        QueryStats {
+            rows_examined: before
+                .rows_examined
+                .zip(after.rows_examined)
+                .map(|(before, after)| after.saturating_sub(before)),
+            rows_returned,
+            server_time: before
+                .server_time
+                .zip(after.server_time)
+                .map(|(before, after)| after.saturating_sub(before)),
+            bytes_received: before
+                .bytes_sent
+                .zip(after.bytes_sent)
+                .map(|(before, after)| after.saturating_sub(before)),
+        }
+    }
+}
+
+/// Pass through the items of `stream`, failing with [`Error::RowLimitExceeded`] once more than
+/// `max_rows` items are rows according to `is_row`.
+pub(crate) fn limit_rows<'e, T>(
+    stream: BoxStream<'e, Result<T, Error>>,
+    max_rows: Option<usize>,
+    is_row: fn(&T) -> bool,
+) -> BoxStream<'e, Result<T, Error>>
+where
+    T: 'e + Send,
+{
+    let Some(max_rows) = max_rows else {
+        return stream;
+    };
+
+    Box::pin(try_stream! {
+        let mut stream = stream;
+        let mut rows = 0;
+
+        while let Some(v) = stream.try_next().await? {
+            if is_row(&v) {
+                rows += 1;
+
+                if rows > max_rows {
+                    return Err(Error::RowLimitExceeded { max_rows });
+                }
+            }
+
+            r#yield!(v);
+        }
+
+        Ok(())
+    })
+}
+
+impl<DB: Database, A> Query<'_, DB, A> {
+    // the timeout set by `.total_timeout()`, and how to cancel the query on `executor` when it
+    // expires; the executor is consumed by running the query, so this is taken beforehand
+    pub(crate) fn timeout_for<'c, E>(
+        &self,
+        executor: &E,
+    ) -> (Option<Duration>, Option<Arc<dyn CancelHandle>>)
+    where
+        E: Executor<'c, Database = DB>,
+    {
+        let cancel = self.total_timeout.and_then(|_| executor.cancel_handle());
+
+        (self.total_timeout, cancel)
+    }
+
+    // the span set up by `.with_span_attributes()`, created only if it is enabled
+    pub(crate) fn span(&self) -> Span {
+        if self.span_attributes.is_empty() {
+            return Span::none();
+        }
+
+        let sql = match self.statement {
+            Either::Right(statement) => statement.sql(),
+            Either::Left(sql) => sql,
+        };
+
+        if !tracing::level_enabled!(Level::INFO) {
+            return Span::none();
+        }
+
+        let mut names = vec!["summary"];
+
+        for (key, _) in &self.span_attributes {
+            if !names.contains(key) {
+                names.push(key);
+            }
+        }
+
+        let metadata = attributes_callsite(names);
+
+        if !tracing::dispatcher::get_default(|dispatch| dispatch.enabled(metadata)) {
+            return Span::none();
+        }
+
+        let summary = crate::logger::parse_query_summary(sql);
+        let summary = summary.as_str();
+        let fields = metadata.fields();
+        let summary_field = fields.field("summary").expect("BUG: missing `summary` field");
+
+        let span = Span::new(
+            metadata,
+            &fields.value_set(&[(&summary_field, Some(&summary as &dyn Value))]),
+        );
+
+        for (key, value) in &self.span_attributes {
+            span.record(*key, &**value);
+        }
+
+        span
+    }
+}
+
+// The definition of a `db.query` span with the given field names.
+//
+// `tracing` only records the fields a span was defined with, so one is leaked for each set of
+// names and reused for every span with those fields.
+fn attributes_callsite(names: Vec<&'static str>) -> &'static Metadata<'static> {
+    static CALLSITES: Lazy<Mutex<HashMap<Vec<&'static str>, &'static AttributesCallsite>>> =
+        Lazy::new(Default::default);
+
+    let mut callsites = CALLSITES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    if let Some(callsite) = callsites.get(&names) {
+        return callsite.metadata();
+    }
+
+    let callsite: &'static AttributesCallsite = Box::leak(Box::new(AttributesCallsite {
+        metadata: OnceLock::new(),
+    }));
+
+    let fields = FieldSet::new(Box::leak(names.clone().into_boxed_slice()), Identifier(callsite));
+
+    callsite.metadata.get_or_init(|| {
+        Metadata::new(
+            "db.query",
+            "sqlx::query",
+            Level::INFO,
+            Some(file!()),
+            Some(line!()),
+            Some(module_path!()),
+            fields,
+            Kind::SPAN,
+        )
+    });
+
+    tracing::callsite::register(callsite);
+    callsites.insert(names, callsite);
+
+    callsite.metadata()
+}
+
+struct AttributesCallsite {
+    metadata: OnceLock<Metadata<'static>>,
+}
+
+impl Callsite for AttributesCallsite {
+    fn set_interest(&self, _interest: Interest) {}
+
+    fn metadata(&self) -> &Metadata<'_> {
+        self.metadata.get().expect("BUG: callsite used before it was set up")
+    }
+}
+
+/// Poll `stream` in `span`; see [`Query::with_span_attributes`].
+pub(crate) fn instrument_rows<'e, T>(stream: BoxStream<'e, T>, span: Span) -> BoxStream<'e, T>
+where
+    T: 'e,
+{
+    if span.is_none() {
+        return stream;
+    }
+
+    Box::pin(InstrumentRows { stream, span })
+}
+
+struct InstrumentRows<'e, T> {
+    stream: BoxStream<'e, T>,
+    span: Span,
+}
+
+impl<T> Stream for InstrumentRows<'_, T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let _entered = this.span.enter();
+
+        this.stream.poll_next_unpin(cx)
+    }
+}
+
+/// Pass through the items of `stream`, failing with [`Error::QueryTimedOut`] if it does not end
+/// within `timeout` of being first polled; see [`Query::total_timeout`].
+pub(crate) fn timeout_rows<'e, T>(
+    stream: BoxStream<'e, Result<T, Error>>,
+    timeout: Option<Duration>,
+    cancel: Option<Arc<dyn CancelHandle>>,
+) -> BoxStream<'e, Result<T, Error>>
+where
+    T: 'e + Send,
+{
+    let Some(timeout) = timeout else {
+        return stream;
+    };
+
+    Box::pin(try_stream! {
+        let mut stream = stream;
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            // don't return items that are already buffered once the deadline has passed
+            let next = match deadline.checked_duration_since(Instant::now()) {
+                Some(remaining) if !remaining.is_zero() => {
+                    crate::rt::timeout(remaining, stream.try_next()).await.ok()
+                }
+                _ => None,
+            };
+
+            let Some(next) = next else {
+                drop(stream);
+                cancel_query(cancel).await;
+
+                return Err(Error::QueryTimedOut { timeout });
+            };
+
+            match next? {
+                Some(v) => r#yield!(v),
+                None => return Ok(()),
+            }
+        }
+    })
+}
+
+/// Await `query`, failing with [`Error::QueryTimedOut`] if it does not complete within `timeout`;
+/// see [`Query::total_timeout`].
+pub(crate) async fn timeout_query<T>(
+    query: impl Future<Output = Result<T, Error>>,
+    timeout: Option<Duration>,
+    cancel: Option<Arc<dyn CancelHandle>>,
+) -> Result<T, Error> {
+    let Some(timeout) = timeout else {
+        return query.await;
+    };
+
+    match crate::rt::timeout(timeout, query).await {
+        Ok(res) => res,
+        Err(_) => {
+            cancel_query(cancel).await;
+
+            Err(Error::QueryTimedOut { timeout })
+        }
+    }
+}
+
+async fn cancel_query(cancel: Option<Arc<dyn CancelHandle>>) {
+    if let Some(cancel) = cancel {
+        if let Err(error) = cancel.cancel().await {
+            tracing::warn!(%error, "error sending cancel request for timed out query");
+        }
+    }
+}
+
+/// Check a limit set by [`Query::max_rows`] for a query that returned a row.
+pub(crate) fn check_single_row(max_rows: Option<usize>) -> Result<(), Error> {
+    match max_rows {
+        Some(0) => Err(Error::RowLimitExceeded { max_rows: 0 }),
+        _ => Ok(()),
+    }
+}
+
 /// Execute a single SQL query as a prepared statement (explicitly created).
 pub fn query_statement<'q, DB>(
     statement: &'q DB::Statement<'q>,
@@ -508,6 +1459,10 @@ where
         arguments: Some(Ok(Default::default())),
         statement: Either::Right(statement),
         persistent: true,
+        max_rows: None,
+        total_timeout: None,
+        cache_ttl: None,
+        span_attributes: Vec::new(),
     }
 }
 
@@ -525,6 +1480,10 @@ where
         arguments: Some(Ok(arguments)),
         statement: Either::Right(statement),
         persistent: true,
+        max_rows: None,
+        total_timeout: None,
+        cache_ttl: None,
+        span_attributes: Vec::new(),
     }
 }
 
@@ -661,6 +1620,10 @@ where
         arguments: Some(Ok(Default::default())),
         statement: Either::Left(sql),
         persistent: true,
+        max_rows: None,
+        total_timeout: None,
+        cache_ttl: None,
+        span_attributes: Vec::new(),
     }
 }
 
@@ -689,5 +1652,9 @@ where
         arguments: Some(arguments),
         statement: Either::Left(sql),
         persistent: true,
+        max_rows: None,
+        total_timeout: None,
+        cache_ttl: None,
+        span_attributes: Vec::new(),
     }
 }
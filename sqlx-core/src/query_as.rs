@@ -1,8 +1,11 @@
+use std::borrow::Cow;
 use std::marker::PhantomData;
+use std::time::Duration;
 
 use either::Either;
 use futures_core::stream::BoxStream;
 use futures_util::{StreamExt, TryStreamExt};
+use tracing::Instrument;
 
 use crate::arguments::IntoArguments;
 use crate::database::{Database, HasStatementCache};
@@ -10,7 +13,10 @@ use crate::encode::Encode;
 use crate::error::{BoxDynError, Error};
 use crate::executor::{Execute, Executor};
 use crate::from_row::FromRow;
-use crate::query::{query, query_statement, query_statement_with, query_with_result, Query};
+use crate::query::{
+    check_single_row, instrument_rows, limit_rows, query, query_statement, query_statement_with,
+    query_with_result, timeout_query, timeout_rows, Query,
+};
 use crate::types::Type;
 
 /// A single SQL query as a prepared statement, mapping results using [`FromRow`].
@@ -45,6 +51,11 @@ where
     fn persistent(&self) -> bool {
         self.inner.persistent()
     }
+
+    #[inline]
+    fn cache_ttl(&self) -> Option<Duration> {
+        self.inner.cache_ttl()
+    }
 }
 
 impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as Database>::Arguments<'q>> {
@@ -85,6 +96,43 @@ where
     A: 'q + IntoArguments<'q, DB>,
     O: Send + Unpin + for<'r> FromRow<'r, DB::Row>,
 {
+    /// Return [`Error::RowLimitExceeded`] if the query returns more than `max_rows` rows.
+    ///
+    /// See [`Query::max_rows`] for details.
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.inner = self.inner.max_rows(max_rows);
+        self
+    }
+
+    /// Return [`Error::QueryTimedOut`] if the query does not complete within `timeout`.
+    ///
+    /// See [`Query::total_timeout`] for details.
+    pub fn total_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.total_timeout(timeout);
+        self
+    }
+
+    /// Let a [`CachingExecutor`][crate::query_cache::CachingExecutor] return the rows of this
+    /// query from its cache for up to `ttl`.
+    ///
+    /// See [`Query::cacheable`] for details.
+    pub fn cacheable(mut self, ttl: Duration) -> Self {
+        self.inner = self.inner.cacheable(ttl);
+        self
+    }
+
+    /// Run the query in a `db.query` span with these key-value pairs attached.
+    ///
+    /// See [`Query::with_span_attributes`] for details.
+    pub fn with_span_attributes<I, V>(mut self, attributes: I) -> Self
+    where
+        I: IntoIterator<Item = (&'static str, V)>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.inner = self.inner.with_span_attributes(attributes);
+        self
+    }
+
     /// Execute the query and return the generated results as a stream.
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
     where
@@ -116,14 +164,23 @@ where
         O: 'e,
         A: 'e,
     {
-        executor
-            .fetch_many(self.inner)
-            .map(|v| match v {
-                Ok(Either::Right(row)) => O::from_row(&row).map(Either::Right),
-                Ok(Either::Left(v)) => Ok(Either::Left(v)),
-                Err(e) => Err(e),
-            })
-            .boxed()
+        let max_rows = self.inner.max_rows;
+        let span = self.inner.span();
+        let (timeout, cancel) = self.inner.timeout_for(&executor);
+
+        let rows = timeout_rows(
+            limit_rows(executor.fetch_many(self.inner), max_rows, Either::is_right),
+            timeout,
+            cancel,
+        )
+        .map(|v| match v {
+            Ok(Either::Right(row)) => O::from_row(&row).map(Either::Right),
+            Ok(Either::Left(v)) => Ok(Either::Left(v)),
+            Err(e) => Err(e),
+        })
+        .boxed();
+
+        instrument_rows(rows, span)
     }
 
     /// Execute the query and return all the resulting rows collected into a [`Vec`].
@@ -190,8 +247,14 @@ where
         O: 'e,
         A: 'e,
     {
-        let row = executor.fetch_optional(self.inner).await?;
+        let max_rows = self.inner.max_rows;
+        let span = self.inner.span();
+        let (timeout, cancel) = self.inner.timeout_for(&executor);
+        let row = timeout_query(executor.fetch_optional(self.inner), timeout, cancel)
+            .instrument(span)
+            .await?;
         if let Some(row) = row {
+            check_single_row(max_rows)?;
             O::from_row(&row).map(Some)
         } else {
             Ok(None)
@@ -7,10 +7,13 @@ use std::marker::PhantomData;
 use crate::arguments::{Arguments, IntoArguments};
 use crate::database::Database;
 use crate::encode::Encode;
+use crate::error::Error;
+use crate::executor::Executor;
 use crate::from_row::FromRow;
 use crate::query::Query;
 use crate::query_as::QueryAs;
 use crate::query_scalar::QueryScalar;
+use crate::type_info::TypeInfo;
 use crate::types::Type;
 use crate::Either;
 
@@ -18,7 +21,7 @@ use crate::Either;
 ///
 /// See [`.push_values()`][Self::push_values] for an example of building a bulk `INSERT` statement.
 /// Note, however, that with Postgres you can get much better performance by using arrays
-/// and `UNNEST()`. [See our FAQ] for details.
+/// and `UNNEST()`, which [`.push_unnest()`][Self::push_unnest] does for you. [See our FAQ] for details.
 ///
 /// [See our FAQ]: https://github.com/launchbadge/sqlx/blob/master/FAQ.md#how-can-i-bind-an-array-to-a-values-clause-how-can-i-do-bulk-inserts
 pub struct QueryBuilder<'args, DB>
@@ -164,6 +167,68 @@ where
         self
     }
 
+    /// Push a bind argument placeholder cast to its type, e.g. `$1::"jsonb"`, and bind a value
+    /// to it. **Postgres only.**
+    ///
+    /// Postgres infers the type of a placeholder from the context it appears in, which can be
+    /// ambiguous or wrong when it is an operand of an operator that is defined for several
+    /// types, such as the `jsonb` operators `@>`, `<@`, `?` and `?|`. The cast pins the
+    /// parameter to the type that `T` is encoded as, so binding a [`Json<T>`][crate::types::Json]
+    /// or `serde_json::Value` compares against `jsonb`, and binding a `Vec<String>` to `?|`
+    /// gives it the `text[]` it expects.
+    ///
+    /// Built-in types are cast to their name in the `pg_type` catalog, in quotes (`"_text"` is
+    /// `text[]`); types declared by name, e.g. with `#[sqlx(type_name = "..")]`, to that name
+    /// as it is written.
+    ///
+    /// ### Errors
+    /// Nothing is pushed or bound if:
+    /// * the database isn't Postgres: [`BindCastError::Unsupported`];
+    /// * the type of `T` is only known by its OID, e.g. from `PgTypeInfo::with_oid()`:
+    ///   [`BindCastError::UnnamedType`].
+    ///
+    /// ### Example (Postgres)
+    ///
+    /// ```rust
+    /// # #[cfg(all(feature = "postgres", feature = "json"))]
+    /// # fn main() -> Result<(), sqlx::query_builder::BindCastError> {
+    /// use serde_json::json;
+    /// use sqlx::types::Json;
+    /// use sqlx::{Execute, Postgres, QueryBuilder};
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> =
+    ///     QueryBuilder::new("SELECT id FROM events WHERE data @> ");
+    ///
+    /// query_builder
+    ///     .push_bind_cast(Json(json!({ "kind": "login" })))?
+    ///     .push(" AND data ?| ")
+    ///     .push_bind_cast(vec!["user".to_string(), "session".to_string()])?;
+    ///
+    /// assert_eq!(
+    ///     query_builder.build().sql(),
+    ///     r#"SELECT id FROM events WHERE data @> $1::"jsonb" AND data ?| $2::"_text""#
+    /// );
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(all(feature = "postgres", feature = "json")))]
+    /// # fn main() {}
+    /// ```
+    pub fn push_bind_cast<T>(&mut self, value: T) -> Result<&mut Self, BindCastError>
+    where
+        T: 'args + Encode<'args, DB> + Type<DB>,
+    {
+        if DB::NAME != "PostgreSQL" {
+            return Err(BindCastError::Unsupported(DB::NAME.to_owned()));
+        }
+
+        let type_info = T::type_info();
+        let Some(name) = type_info.cast_name() else {
+            return Err(BindCastError::UnnamedType(format!("{type_info:?}")));
+        };
+
+        Ok(self.push_bind(value).push("::").push(name))
+    }
+
     /// Start a list separated by `separator`.
     ///
     /// The returned type exposes identical [`.push()`][Separated::push] and
@@ -433,6 +498,219 @@ where
         separated.query_builder
     }
 
+    /// Push `unnest($1::<type>[], $2::<type>[], ..)`, binding each field of `rows`
+    /// as a separate array. **Postgres only.**
+    ///
+    /// This turns a list of rows into a set of rows with a single bind argument per column,
+    /// which makes it the most efficient way to insert many rows at once: the statement has a
+    /// fixed number of parameters no matter how many rows there are, so it is prepared only once
+    /// and isn't subject to the bind parameter limit (see [`.push_bind()`][Self::push_bind]).
+    ///
+    /// Each element of `rows` is a tuple of up to 16 fields; every field type needs to be
+    /// bindable as an array, i.e. `Vec<T>` must implement [`Encode`] and [`Type`]. Each array
+    /// is cast to the name of its type, so that Postgres infers the row type even where
+    /// a placeholder alone would be ambiguous.
+    ///
+    /// `rows` may be empty, in which case `unnest()` produces no rows.
+    ///
+    /// ### Example (Postgres)
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")]
+    /// # {
+    /// use sqlx::{Execute, Postgres, QueryBuilder};
+    ///
+    /// let users = vec![(1_i32, "alice".to_string()), (2, "bob".to_string())];
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> =
+    ///     QueryBuilder::new("INSERT INTO users(id, username) SELECT * FROM ");
+    ///
+    /// query_builder.push_unnest(users);
+    ///
+    /// let query = query_builder.build();
+    ///
+    /// assert_eq!(
+    ///     query.sql(),
+    ///     "INSERT INTO users(id, username) SELECT * FROM unnest($1::INT4[], $2::TEXT[])"
+    /// );
+    /// # }
+    /// ```
+    pub fn push_unnest<I, R>(&mut self, rows: I) -> &mut Self
+    where
+        I: IntoIterator<Item = R>,
+        R: UnnestRow<'args, DB>,
+    {
+        self.sanity_check();
+
+        self.push("unnest(");
+        R::push_unnest(&mut self.separated(", "), rows);
+        self.push(")")
+    }
+
+    /// Delete the rows of `table` where `column` is one of `ids`, binding at most `chunk_size`
+    /// ids per statement.
+    ///
+    /// This executes `DELETE FROM <table> WHERE <column> IN (...)` once for every `chunk_size`
+    /// ids, so that no statement exceeds the bind parameter limit of the database
+    /// (see [`.push_bind()`][Self::push_bind]), and returns the combined result of all of them.
+    /// Nothing is executed if `ids` is empty.
+    ///
+    /// `table` and `column` are pushed as-is, like [`.push()`][Self::push], so they must not
+    /// come from untrusted input. The statements are not run in a transaction of their own;
+    /// pass a [`Transaction`][crate::transaction::Transaction] to delete all rows or none.
+    ///
+    /// ### Panics
+    /// If `chunk_size` is zero.
+    ///
+    /// ### Example (Postgres)
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use sqlx::{Postgres, QueryBuilder};
+    ///
+    /// let ids: Vec<i64> = (1..=100_000).collect();
+    ///
+    /// // 65535 is the bind parameter limit of Postgres
+    /// let result = QueryBuilder::<Postgres>::push_delete_in(conn, "users", "id", ids, 65535).await?;
+    ///
+    /// println!("deleted {} users", result.rows_affected());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn push_delete_in<I, T>(
+        conn: &mut DB::Connection,
+        table: &str,
+        column: &str,
+        ids: I,
+        chunk_size: usize,
+    ) -> Result<DB::QueryResult, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: for<'q> Encode<'q, DB> + Type<DB>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+        for<'q> DB::Arguments<'q>: IntoArguments<'q, DB>,
+    {
+        assert!(chunk_size > 0, "`chunk_size` must be greater than zero");
+
+        let mut result = DB::QueryResult::default();
+        let mut ids = ids.into_iter().peekable();
+
+        while ids.peek().is_some() {
+            let chunk = ids.by_ref().take(chunk_size);
+            result.extend([delete_in_chunk(conn, table, column, chunk).await?]);
+        }
+
+        Ok(result)
+    }
+
+    /// Push the `ON CONFLICT` clause described by `clause`, in the syntax of the database.
+    ///
+    /// For Postgres and SQLite this is `ON CONFLICT <target> DO NOTHING` or
+    /// `ON CONFLICT <target> DO UPDATE SET ... [WHERE ...]`. For MySQL it is
+    /// `ON DUPLICATE KEY UPDATE ...`, with `EXCLUDED.<column>` written as `VALUES(<column>)`;
+    /// MySQL has no conflict target, so it is ignored, and `DO NOTHING` is emulated by setting
+    /// the first target column to itself.
+    ///
+    /// Column names, the constraint name and expressions are pushed as-is, like
+    /// [`.push()`][Self::push], so they must not come from untrusted input.
+    ///
+    /// ### Errors
+    /// Nothing is pushed if the database can't express `clause`:
+    /// * [`ConflictClauseError::NoColumns`]: the target is an empty list of columns.
+    /// * [`ConflictClauseError::UpdateWithoutTarget`]: `DO UPDATE` on [any][ConflictClause::any]
+    ///   conflict, in Postgres and SQLite.
+    /// * [`ConflictClauseError::Unsupported`]: a [named constraint][ConflictClause::constraint]
+    ///   in SQLite or MySQL, or [any][ConflictClause::any] conflict or a `WHERE` condition in
+    ///   MySQL.
+    /// * [`ConflictClauseError::UnknownBackend`]: the database is `Any`, which may be
+    ///   connected to any of them; use
+    ///   [`.push_on_conflict_for()`][Self::push_on_conflict_for] instead.
+    ///
+    /// ### Example (Postgres)
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")]
+    /// # fn main() -> Result<(), sqlx::query_builder::ConflictClauseError> {
+    /// use sqlx::query_builder::ConflictClause;
+    /// use sqlx::{Execute, Postgres, QueryBuilder};
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> =
+    ///     QueryBuilder::new("INSERT INTO users(id, username, email) ");
+    ///
+    /// query_builder.push_values([(1, "alice", "alice@example.com")], |mut b, user| {
+    ///     b.push_bind(user.0).push_bind(user.1).push_bind(user.2);
+    /// });
+    ///
+    /// query_builder.push_on_conflict(
+    ///     &ConflictClause::columns(["id"])
+    ///         .do_update(["username", "email"])
+    ///         .set("updated_at", "now()")
+    ///         .where_("users.email IS DISTINCT FROM EXCLUDED.email"),
+    /// )?;
+    ///
+    /// assert_eq!(
+    ///     query_builder.build().sql(),
+    ///     "INSERT INTO users(id, username, email) VALUES ($1, $2, $3) \
+    ///      ON CONFLICT (id) DO UPDATE SET username = EXCLUDED.username, \
+    ///      email = EXCLUDED.email, updated_at = now() \
+    ///      WHERE users.email IS DISTINCT FROM EXCLUDED.email"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "postgres"))]
+    /// # fn main() {}
+    /// ```
+    ///
+    /// ### Example (MySQL)
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "mysql")]
+    /// # fn main() -> Result<(), sqlx::query_builder::ConflictClauseError> {
+    /// use sqlx::query_builder::ConflictClause;
+    /// use sqlx::{Execute, MySql, QueryBuilder};
+    ///
+    /// let mut query_builder: QueryBuilder<MySql> =
+    ///     QueryBuilder::new("INSERT INTO counters(name, count) VALUES ('hits', 1)");
+    ///
+    /// let clause = ConflictClause::columns(["name"]).set("count", "count + VALUES(count)");
+    /// query_builder.push_on_conflict(&clause)?;
+    ///
+    /// assert_eq!(
+    ///     query_builder.build().sql(),
+    ///     "INSERT INTO counters(name, count) VALUES ('hits', 1) \
+    ///      ON DUPLICATE KEY UPDATE count = count + VALUES(count)"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "mysql"))]
+    /// # fn main() {}
+    /// ```
+    pub fn push_on_conflict(
+        &mut self,
+        clause: &ConflictClause,
+    ) -> Result<&mut Self, ConflictClauseError> {
+        self.push_on_conflict_for(clause, DB::NAME)
+    }
+
+    /// Push the `ON CONFLICT` clause described by `clause`, in the syntax of the database named
+    /// `backend_name`, one of `"PostgreSQL"`, `"MySQL"` or `"SQLite"`.
+    ///
+    /// This is for building queries for `Any`, where the database is only known once connected,
+    /// e.g. from `AnyConnection::backend_name()`.
+    /// See [`.push_on_conflict()`][Self::push_on_conflict] for details.
+    pub fn push_on_conflict_for(
+        &mut self,
+        clause: &ConflictClause,
+        backend_name: &str,
+    ) -> Result<&mut Self, ConflictClauseError> {
+        self.sanity_check();
+
+        let syntax = clause.validate(backend_name)?;
+        clause.push(syntax, &mut self.query);
+
+        Ok(self)
+    }
+
     /// Produce an executable query from this builder.
     ///
     /// ### Note: Query is not Checked
@@ -453,6 +731,10 @@ where
             arguments: self.arguments.take().map(Ok),
             database: PhantomData,
             persistent: true,
+            max_rows: None,
+            total_timeout: None,
+            cache_ttl: None,
+            span_attributes: Vec::new(),
         }
     }
 
@@ -468,6 +750,37 @@ where
     /// to the state it was in immediately after [`new()`][Self::new].
     ///
     /// Calling any other method but `.reset()` after `.build()` will panic for sanity reasons.
+    ///
+    /// ### Note: Columns are Resolved at Runtime
+    /// The builder doesn't need to know which columns the query returns; [`FromRow`]
+    /// looks each field up by name in the row it receives. This makes it suitable for mapping
+    /// the output of a dynamically built `INSERT ... RETURNING *`:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "postgres")] {
+    /// use sqlx::{FromRow, Postgres, QueryBuilder};
+    ///
+    /// #[derive(FromRow)]
+    /// struct User {
+    ///     id: i64,
+    ///     username: String,
+    ///     // `NULL` values in the row decode as `None`
+    ///     email: Option<String>,
+    /// }
+    ///
+    /// let mut query_builder: QueryBuilder<Postgres> =
+    ///     QueryBuilder::new("INSERT INTO users(username, email) ");
+    ///
+    /// query_builder.push_values(["alice", "bob"], |mut b, username| {
+    ///     b.push_bind(username).push_bind(None::<String>);
+    /// });
+    ///
+    /// query_builder.push(" RETURNING *");
+    ///
+    /// let query = query_builder.build_query_as::<User>();
+    /// // let users: Vec<User> = query.fetch_all(&pool).await?;
+    /// # }
+    /// ```
     pub fn build_query_as<'q, T: FromRow<'q, DB::Row>>(
         &'q mut self,
     ) -> QueryAs<'q, DB, T, <DB as Database>::Arguments<'args>> {
@@ -523,6 +836,293 @@ where
     }
 }
 
+// A separate function, so that the arguments of each chunk get their own lifetime.
+async fn delete_in_chunk<DB, T>(
+    conn: &mut DB::Connection,
+    table: &str,
+    column: &str,
+    ids: impl Iterator<Item = T>,
+) -> Result<DB::QueryResult, Error>
+where
+    DB: Database,
+    T: for<'q> Encode<'q, DB> + Type<DB>,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    for<'q> DB::Arguments<'q>: IntoArguments<'q, DB>,
+{
+    let mut sql = format!("DELETE FROM {table} WHERE {column} IN (");
+    let mut arguments = DB::Arguments::default();
+
+    for (i, id) in ids.enumerate() {
+        if i > 0 {
+            sql.push_str(", ");
+        }
+
+        arguments.add(id).map_err(Error::Encode)?;
+        arguments
+            .format_placeholder(&mut sql)
+            .map_err(|e| Error::Encode(e.into()))?;
+    }
+
+    sql.push(')');
+
+    crate::query::query_with(&sql, arguments)
+        .execute(conn)
+        .await
+}
+
+/// The `ON CONFLICT` clause of an upsert, for [`QueryBuilder::push_on_conflict()`].
+///
+/// Start with the conflict target, i.e. the columns of a unique index with
+/// [`columns()`][Self::columns], a named constraint with [`constraint()`][Self::constraint], or
+/// any conflict with [`any()`][Self::any]. The action is `DO NOTHING` until columns to update
+/// are added with [`do_update()`][Self::do_update] or [`set()`][Self::set].
+///
+/// Not every clause can be expressed by every database; this is checked when it is pushed.
+#[derive(Debug, Clone)]
+pub struct ConflictClause {
+    target: ConflictTarget,
+    set: Vec<(String, Option<String>)>,
+    condition: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+enum ConflictTarget {
+    Any,
+    Columns(Vec<String>),
+    Constraint(String),
+}
+
+/// A [`ConflictClause`] that the database can't express, returned by
+/// [`QueryBuilder::push_on_conflict()`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum ConflictClauseError {
+    /// [`ConflictClause::columns()`] was given no columns.
+    #[error("the conflict target has no columns")]
+    NoColumns,
+
+    /// `DO UPDATE` requires a conflict target in Postgres and SQLite.
+    #[error("`ON CONFLICT DO UPDATE` requires a conflict target")]
+    UpdateWithoutTarget,
+
+    /// The database doesn't support this part of the clause.
+    #[error("{database} does not support {feature}")]
+    Unsupported {
+        /// The name of the database.
+        database: String,
+        /// The unsupported part of the clause.
+        feature: &'static str,
+    },
+
+    /// The syntax of the database isn't known, e.g. because it is `Any`.
+    #[error("no `ON CONFLICT` syntax is known for {0}")]
+    UnknownBackend(String),
+}
+
+/// A bind parameter that [`QueryBuilder::push_bind_cast()`] can't cast.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum BindCastError {
+    /// The database, named here, doesn't cast with `::`.
+    #[error("{0} does not support casting bind parameters with `::`")]
+    Unsupported(String),
+
+    /// The type, named here, is only known by its OID, so there is no name to cast to.
+    #[error("type {0} has no name to cast a bind parameter to")]
+    UnnamedType(String),
+}
+
+/// How a [`ConflictClause`] is written.
+#[derive(Debug, Clone, Copy)]
+enum ConflictSyntax {
+    /// `ON CONFLICT ...`, for Postgres and SQLite.
+    Standard,
+    /// `ON DUPLICATE KEY UPDATE ...`, for MySQL.
+    MySql,
+}
+
+impl ConflictClause {
+    /// A conflict on the unique index or constraint over `columns`, e.g. `ON CONFLICT (a, b)`.
+    ///
+    /// For MySQL, which has no conflict target, the first column is used to emulate
+    /// `DO NOTHING`.
+    pub fn columns<I>(columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        Self::new(ConflictTarget::Columns(columns.into_iter().map(Into::into).collect()))
+    }
+
+    /// A conflict on the constraint named `name`, i.e. `ON CONFLICT ON CONSTRAINT name`.
+    /// **Postgres only.**
+    pub fn constraint(name: impl Into<String>) -> Self {
+        Self::new(ConflictTarget::Constraint(name.into()))
+    }
+
+    /// A conflict on any unique index or constraint. Only valid with `DO NOTHING`.
+    /// **Postgres and SQLite only.**
+    pub fn any() -> Self {
+        Self::new(ConflictTarget::Any)
+    }
+
+    fn new(target: ConflictTarget) -> Self {
+        ConflictClause {
+            target,
+            set: Vec::new(),
+            condition: None,
+        }
+    }
+
+    /// Do nothing on a conflict, i.e. skip the row. This is the default.
+    ///
+    /// Clears any columns to update and the `WHERE` condition.
+    pub fn do_nothing(mut self) -> Self {
+        self.set.clear();
+        self.condition = None;
+        self
+    }
+
+    /// On a conflict, update each of `columns` to the value that was proposed for insertion,
+    /// i.e. `column = EXCLUDED.column`.
+    pub fn do_update<I>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.set.extend(columns.into_iter().map(|column| (column.into(), None)));
+        self
+    }
+
+    /// On a conflict, update `column` to the SQL expression `value`, e.g.
+    /// `.set("count", "counters.count + EXCLUDED.count")`.
+    ///
+    /// `EXCLUDED.<column>` in `value` refers to the value proposed for insertion; for MySQL,
+    /// write `VALUES(<column>)` instead.
+    pub fn set(mut self, column: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set.push((column.into(), Some(value.into())));
+        self
+    }
+
+    /// Only update the conflicting row if `condition` holds, i.e. `DO UPDATE ... WHERE
+    /// condition`. **Postgres and SQLite only.**
+    pub fn where_(mut self, condition: impl Into<String>) -> Self {
+        self.condition = Some(condition.into());
+        self
+    }
+
+    /// Check that the database named `backend_name` can express this clause.
+    fn validate(&self, backend_name: &str) -> Result<ConflictSyntax, ConflictClauseError> {
+        let syntax = match backend_name {
+            "PostgreSQL" | "SQLite" => ConflictSyntax::Standard,
+            "MySQL" => ConflictSyntax::MySql,
+            _ => return Err(ConflictClauseError::UnknownBackend(backend_name.into())),
+        };
+
+        let unsupported = |feature| ConflictClauseError::Unsupported {
+            database: backend_name.into(),
+            feature,
+        };
+
+        match &self.target {
+            ConflictTarget::Columns(columns) if columns.is_empty() => {
+                return Err(ConflictClauseError::NoColumns);
+            }
+            ConflictTarget::Constraint(_) if backend_name != "PostgreSQL" => {
+                return Err(unsupported("a named constraint as the conflict target"));
+            }
+            ConflictTarget::Any => match syntax {
+                ConflictSyntax::MySql => {
+                    return Err(unsupported("`DO NOTHING` without a target column"));
+                }
+                ConflictSyntax::Standard if !self.set.is_empty() => {
+                    return Err(ConflictClauseError::UpdateWithoutTarget);
+                }
+                ConflictSyntax::Standard => (),
+            },
+            ConflictTarget::Columns(_) | ConflictTarget::Constraint(_) => (),
+        }
+
+        if let (ConflictSyntax::MySql, Some(_)) = (syntax, &self.condition) {
+            return Err(unsupported("a `WHERE` condition on conflict"));
+        }
+
+        Ok(syntax)
+    }
+
+    /// Push the clause in `syntax`, once it is validated.
+    fn push(&self, syntax: ConflictSyntax, sql: &mut String) {
+        match syntax {
+            ConflictSyntax::Standard => self.push_standard(sql),
+            ConflictSyntax::MySql => self.push_mysql(sql),
+        }
+    }
+
+    /// ` ON CONFLICT ...`, for Postgres and SQLite.
+    fn push_standard(&self, sql: &mut String) {
+        sql.push_str(" ON CONFLICT");
+
+        match &self.target {
+            ConflictTarget::Any => (),
+            ConflictTarget::Columns(columns) => {
+                write!(sql, " ({})", columns.join(", ")).unwrap();
+            }
+            ConflictTarget::Constraint(name) => {
+                write!(sql, " ON CONSTRAINT {name}").unwrap();
+            }
+        }
+
+        if self.set.is_empty() {
+            sql.push_str(" DO NOTHING");
+            return;
+        }
+
+        sql.push_str(" DO UPDATE SET ");
+
+        for (i, (column, value)) in self.set.iter().enumerate() {
+            if i > 0 {
+                sql.push_str(", ");
+            }
+
+            match value {
+                Some(value) => write!(sql, "{column} = {value}"),
+                None => write!(sql, "{column} = EXCLUDED.{column}"),
+            }
+            .unwrap();
+        }
+
+        if let Some(condition) = &self.condition {
+            write!(sql, " WHERE {condition}").unwrap();
+        }
+    }
+
+    /// ` ON DUPLICATE KEY UPDATE ...`, for MySQL.
+    fn push_mysql(&self, sql: &mut String) {
+        sql.push_str(" ON DUPLICATE KEY UPDATE ");
+
+        if self.set.is_empty() {
+            // `validate()` only lets through a non-empty list of columns as the target
+            if let ConflictTarget::Columns(columns) = &self.target {
+                write!(sql, "{0} = {0}", columns[0]).unwrap();
+            }
+
+            return;
+        }
+
+        for (i, (column, value)) in self.set.iter().enumerate() {
+            if i > 0 {
+                sql.push_str(", ");
+            }
+
+            match value {
+                Some(value) => write!(sql, "{column} = {value}"),
+                None => write!(sql, "{column} = VALUES({column})"),
+            }
+            .unwrap();
+        }
+    }
+}
+
 /// A wrapper around `QueryBuilder` for creating comma(or other token)-separated lists.
 ///
 /// See [`QueryBuilder::separated()`] for details.
@@ -593,3 +1193,229 @@ where
         self
     }
 }
+
+/// A tuple of values that [`QueryBuilder::push_unnest()`] binds column by column as arrays.
+///
+/// This is implemented for tuples of up to 16 fields whose types can be bound as arrays.
+pub trait UnnestRow<'args, DB: Database>: Sized {
+    /// Bind the fields of `rows` as one array per field, pushing each with its cast.
+    #[doc(hidden)]
+    fn push_unnest<I>(separated: &mut Separated<'_, 'args, DB, &'static str>, rows: I)
+    where
+        I: IntoIterator<Item = Self>;
+}
+
+macro_rules! impl_unnest_row_for_tuple {
+    ($( ($idx:tt) -> $T:ident );+;) => {
+        impl<'args, DB, $($T,)+> UnnestRow<'args, DB> for ($($T,)+)
+        where
+            DB: Database,
+            $(Vec<$T>: 'args + Encode<'args, DB> + Type<DB>,)+
+        {
+            fn push_unnest<I>(separated: &mut Separated<'_, 'args, DB, &'static str>, rows: I)
+            where
+                I: IntoIterator<Item = Self>,
+            {
+                let rows = rows.into_iter();
+                let len = rows.size_hint().0;
+
+                let mut columns = ($(Vec::<$T>::with_capacity(len),)+);
+
+                for row in rows {
+                    $(columns.$idx.push(row.$idx);)+
+                }
+
+                $(
+                    separated
+                        .push_bind(columns.$idx)
+                        .push_unseparated("::")
+                        .push_unseparated(<Vec<$T> as Type<DB>>::type_info().name());
+                )+
+            }
+        }
+    };
+}
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+    (14) -> T15;
+);
+
+impl_unnest_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+    (14) -> T15;
+    (15) -> T16;
+);
@@ -0,0 +1,333 @@
+//! Serve the results of repeated read-only queries from memory.
+//!
+//! A [`QueryCache`] holds the rows of recent queries, keyed by their SQL and the values of
+//! their arguments. Wrapping an executor in a [`CachingExecutor`] fetches the rows of queries
+//! marked with [`Query::cacheable()`] from the cache while they are fresh, and stores them
+//! there otherwise:
+//!
+//! ```rust,ignore
+//! use std::time::Duration;
+//! use sqlx::{CachingExecutor, QueryCache};
+//!
+//! // keep the results of up to 100 queries
+//! let cache = QueryCache::new(100);
+//!
+//! // only the first call in a minute runs the query
+//! let countries = sqlx::query("SELECT * FROM countries WHERE region = $1")
+//!     .bind("Europe")
+//!     .cacheable(Duration::from_secs(60))
+//!     .fetch_all(CachingExecutor::new(&pool, &cache))
+//!     .await?;
+//! ```
+//!
+//! ### Caching is only safe for queries that only read
+//! A query served from the cache is not run at all, and its rows may be as old as its
+//! time-to-live: changes made since, even by the same connection or in the same transaction,
+//! are not seen. Only mark queries as cacheable that don't modify anything and whose results
+//! are allowed to be that stale, such as lookups of rarely-changing data.
+//!
+//! ### What is cached
+//! The rows of [`fetch()`][Executor::fetch] and [`fetch_all()`][Executor::fetch_all] (and the
+//! methods of [`Query`] and its relatives that use them) are stored once the result set has
+//! been read to the end without error. [`fetch_one()`][Executor::fetch_one] and
+//! [`fetch_optional()`][Executor::fetch_optional] return the first cached row if there is one,
+//! but don't store their results, and [`execute()`][Executor::execute] always runs the query.
+//!
+//! Queries without [`Query::cacheable()`], and queries whose arguments the driver can't compare
+//! (currently those of the `Any` driver), always run too. Expired results are dropped when they
+//! are next looked up, or when they are the least recently used ones once the cache is full.
+//!
+//! [`Query`]: crate::query::Query
+//! [`Query::cacheable()`]: crate::query::Query::cacheable
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+use hashlink::LruCache;
+
+use crate::arguments::Arguments;
+use crate::connection::CancelHandle;
+use crate::database::Database;
+use crate::describe::Describe;
+use crate::error::{BoxDynError, Error};
+use crate::executor::{Execute, Executor};
+
+/// The rows of recently run queries, shared by the [`CachingExecutor`]s created from it.
+///
+/// Cheap to clone. See the [module documentation][self] for details.
+pub struct QueryCache<DB: Database> {
+    entries: Arc<Mutex<LruCache<CacheKey, CachedRows<DB>>>>,
+}
+
+// the SQL and the arguments, see `Arguments::to_cache_key()`
+type CacheKey = (String, Vec<u8>);
+
+struct CachedRows<DB: Database> {
+    rows: Arc<[DB::Row]>,
+    expires_at: Instant,
+}
+
+impl<DB: Database> QueryCache<DB> {
+    /// Create a cache holding the results of up to `capacity` queries; when it is full, the
+    /// least recently used results are dropped.
+    pub fn new(capacity: usize) -> Self {
+        QueryCache {
+            entries: Arc::new(Mutex::new(LruCache::new(capacity))),
+        }
+    }
+
+    /// The number of cached results, including ones that have expired but have not been
+    /// dropped yet.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns `true` if no results are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all cached results, e.g. after the data they were read from was changed.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<Arc<[DB::Row]>> {
+        let mut entries = self.entries.lock().unwrap();
+        let cached = entries.get(key)?;
+
+        if cached.expires_at <= Instant::now() {
+            entries.remove(key);
+            return None;
+        }
+
+        Some(cached.rows.clone())
+    }
+
+    fn insert(&self, key: CacheKey, rows: Vec<DB::Row>, ttl: Duration) {
+        let cached = CachedRows {
+            rows: rows.into(),
+            expires_at: Instant::now() + ttl,
+        };
+
+        self.entries.lock().unwrap().insert(key, cached);
+    }
+}
+
+impl<DB: Database> Clone for QueryCache<DB> {
+    fn clone(&self) -> Self {
+        QueryCache {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<DB: Database> Debug for QueryCache<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryCache")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// An [`Executor`] that serves the rows of queries marked with
+/// [`Query::cacheable()`][crate::query::Query::cacheable] from a [`QueryCache`], and runs
+/// everything else on the wrapped executor.
+///
+/// See the [module documentation][self] for details.
+#[derive(Debug)]
+pub struct CachingExecutor<E, DB: Database> {
+    inner: E,
+    cache: QueryCache<DB>,
+}
+
+impl<E, DB: Database> CachingExecutor<E, DB> {
+    /// Wrap `executor`, e.g. `&pool` or `&mut *conn`, caching results in `cache`.
+    pub fn new(executor: E, cache: &QueryCache<DB>) -> Self {
+        CachingExecutor {
+            inner: executor,
+            cache: cache.clone(),
+        }
+    }
+
+    /// Return the wrapped executor.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E: Clone, DB: Database> Clone for CachingExecutor<E, DB> {
+    fn clone(&self) -> Self {
+        CachingExecutor {
+            inner: self.inner.clone(),
+            cache: self.cache.clone(),
+        }
+    }
+}
+
+// The key of `sql` with `arguments`, if the driver can compare them.
+fn cache_key<'q, DB: Database>(
+    sql: &str,
+    arguments: Option<&DB::Arguments<'q>>,
+) -> Option<CacheKey> {
+    let arguments = match arguments {
+        Some(arguments) => arguments.to_cache_key()?,
+        None => Vec::new(),
+    };
+
+    Some((sql.to_owned(), arguments))
+}
+
+// A query whose arguments were taken to compute its cache key.
+struct CachedQuery<'q, DB: Database, Q> {
+    query: Q,
+    arguments: Option<DB::Arguments<'q>>,
+}
+
+impl<'q, DB: Database, Q: Execute<'q, DB>> Execute<'q, DB> for CachedQuery<'q, DB, Q> {
+    fn sql(&self) -> &'q str {
+        self.query.sql()
+    }
+
+    fn statement(&self) -> Option<&DB::Statement<'q>> {
+        self.query.statement()
+    }
+
+    fn take_arguments(&mut self) -> Result<Option<DB::Arguments<'q>>, BoxDynError> {
+        Ok(self.arguments.take())
+    }
+
+    fn persistent(&self) -> bool {
+        self.query.persistent()
+    }
+}
+
+impl<'c, E, DB> Executor<'c> for CachingExecutor<E, DB>
+where
+    E: 'c + Executor<'c, Database = DB>,
+    DB: Database,
+    DB::Row: Clone,
+{
+    type Database = DB;
+
+    fn execute<'e, 'q: 'e, Q>(self, query: Q) -> BoxFuture<'e, Result<DB::QueryResult, Error>>
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, DB>,
+    {
+        self.inner.execute(query)
+    }
+
+    fn execute_many<'e, 'q: 'e, Q>(
+        self,
+        query: Q,
+    ) -> BoxStream<'e, Result<DB::QueryResult, Error>>
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, DB>,
+    {
+        self.inner.execute_many(query)
+    }
+
+    fn fetch_many<'e, 'q: 'e, Q>(
+        self,
+        query: Q,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, DB>,
+    {
+        let CachingExecutor { inner, cache } = self;
+
+        Box::pin(try_stream! {
+            let mut query = query;
+
+            let arguments = query.take_arguments().map_err(Error::Encode)?;
+            let cacheable = query.cache_ttl().and_then(|ttl| {
+                Some((cache_key::<DB>(query.sql(), arguments.as_ref())?, ttl))
+            });
+
+            if let Some(rows) = cacheable.as_ref().and_then(|(key, _)| cache.get(key)) {
+                for row in rows.iter() {
+                    r#yield!(Either::Right(row.clone()));
+                }
+
+                return Ok(());
+            }
+
+            let mut rows = Vec::new();
+            let mut s = inner.fetch_many(CachedQuery { query, arguments });
+
+            while let Some(v) = s.try_next().await? {
+                if let (Some(_), Either::Right(row)) = (&cacheable, &v) {
+                    rows.push(row.clone());
+                }
+
+                r#yield!(v);
+            }
+
+            // only complete result sets are stored
+            if let Some((key, ttl)) = cacheable {
+                cache.insert(key, rows, ttl);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn fetch_optional<'e, 'q: 'e, Q>(
+        self,
+        query: Q,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, DB>,
+    {
+        let CachingExecutor { inner, cache } = self;
+
+        Box::pin(async move {
+            let mut query = query;
+
+            let arguments = query.take_arguments().map_err(Error::Encode)?;
+            let key = query
+                .cache_ttl()
+                .and_then(|_| cache_key::<DB>(query.sql(), arguments.as_ref()));
+
+            if let Some(rows) = key.as_ref().and_then(|key| cache.get(key)) {
+                return Ok(rows.first().cloned());
+            }
+
+            inner
+                .fetch_optional(CachedQuery { query, arguments })
+                .await
+        })
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [DB::TypeInfo],
+    ) -> BoxFuture<'e, Result<DB::Statement<'q>, Error>>
+    where
+        'c: 'e,
+    {
+        self.inner.prepare_with(sql, parameters)
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<DB>, Error>>
+    where
+        'c: 'e,
+    {
+        self.inner.describe(sql)
+    }
+
+    fn cancel_handle(&self) -> Option<Arc<dyn CancelHandle>> {
+        self.inner.cancel_handle()
+    }
+}
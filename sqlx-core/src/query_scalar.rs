@@ -1,3 +1,6 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
 use either::Either;
 use futures_core::stream::BoxStream;
 use futures_util::{StreamExt, TryFutureExt, TryStreamExt};
@@ -42,6 +45,11 @@ where
     fn persistent(&self) -> bool {
         Execute::persistent(&self.inner)
     }
+
+    #[inline]
+    fn cache_ttl(&self) -> Option<Duration> {
+        self.inner.cache_ttl()
+    }
 }
 
 impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as Database>::Arguments<'q>> {
@@ -83,6 +91,44 @@ where
     A: 'q + IntoArguments<'q, DB>,
     (O,): Send + Unpin + for<'r> FromRow<'r, DB::Row>,
 {
+    /// Return [`Error::RowLimitExceeded`] if the query returns more than `max_rows` rows.
+    ///
+    /// See [`Query::max_rows`][crate::query::Query::max_rows] for details.
+    pub fn max_rows(mut self, max_rows: usize) -> Self {
+        self.inner = self.inner.max_rows(max_rows);
+        self
+    }
+
+    /// Return [`Error::QueryTimedOut`] if the query does not complete within `timeout`.
+    ///
+    /// See [`Query::total_timeout`][crate::query::Query::total_timeout] for details.
+    pub fn total_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.total_timeout(timeout);
+        self
+    }
+
+    /// Let a [`CachingExecutor`][crate::query_cache::CachingExecutor] return the rows of this
+    /// query from its cache for up to `ttl`.
+    ///
+    /// See [`Query::cacheable`][crate::query::Query::cacheable] for details.
+    pub fn cacheable(mut self, ttl: Duration) -> Self {
+        self.inner = self.inner.cacheable(ttl);
+        self
+    }
+
+    /// Run the query in a `db.query` span with these key-value pairs attached.
+    ///
+    /// See [`Query::with_span_attributes`][crate::query::Query::with_span_attributes] for
+    /// details.
+    pub fn with_span_attributes<I, V>(mut self, attributes: I) -> Self
+    where
+        I: IntoIterator<Item = (&'static str, V)>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.inner = self.inner.with_span_attributes(attributes);
+        self
+    }
+
     /// Execute the query and return the generated results as a stream.
     #[inline]
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
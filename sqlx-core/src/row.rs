@@ -1,6 +1,10 @@
+#[cfg(feature = "json")]
+use crate::column::Column;
 use crate::column::ColumnIndex;
 use crate::database::Database;
 use crate::decode::Decode;
+#[cfg(feature = "json")]
+use crate::error::BoxDynError;
 use crate::error::{mismatched_types, Error};
 
 use crate::type_info::TypeInfo;
@@ -102,10 +106,13 @@ pub trait Row: Unpin + Send + Sync + 'static {
     ///  * [`ColumnNotFound`] if the column by the given name was not found.
     ///  * [`ColumnIndexOutOfBounds`] if the `usize` index was greater than the number of columns in the row.
     ///  * [`ColumnDecode`] if the value could not be decoded into the requested type.
+    ///    If the SQL type of the column is not compatible with the requested type, its `source`
+    ///    is a [`MismatchedTypes`].
     ///
     /// [`ColumnDecode`]: Error::ColumnDecode
     /// [`ColumnNotFound`]: Error::ColumnNotFound
     /// [`ColumnIndexOutOfBounds`]: Error::ColumnIndexOutOfBounds
+    /// [`MismatchedTypes`]: crate::error::MismatchedTypes
     ///
     fn try_get<'r, T, I>(&'r self, index: I) -> Result<T, Error>
     where
@@ -116,8 +123,14 @@ pub trait Row: Unpin + Send + Sync + 'static {
 
         if !value.is_null() {
             let ty = value.type_info();
+            let widens =
+                self.lenient_integer_decode() && ty.is_integer_widening_to(&T::type_info());
 
-            if !ty.is_null() && !T::compatible(&ty) {
+            if !ty.is_null()
+                && !T::compatible(&ty)
+                && !widens
+                && !self.decodes_as_text(&ty, &T::type_info())
+            {
                 return Err(Error::ColumnDecode {
                     index: format!("{index:?}"),
                     source: mismatched_types::<Self::Database, T>(&ty),
@@ -161,7 +174,17 @@ pub trait Row: Unpin + Send + Sync + 'static {
         })
     }
 
-    /// Index into the database row and decode a single value.
+    /// Index into the database row and return the value as it was received, without decoding it.
+    ///
+    /// This is the extension point for custom type handling: the value reference carries the
+    /// [type information][crate::value::ValueRef::type_info] of the column and, depending on
+    /// the driver, the wire format and the still-encoded bytes of the value (e.g.
+    /// `PgValueRef::format()` and `PgValueRef::as_bytes()`), which can be passed to a codec
+    /// that SQLx knows nothing about. [`try_get()`](Self::try_get) and
+    /// [`try_get_unchecked()`](Self::try_get_unchecked) are built on top of this.
+    ///
+    /// The value borrows from the row, so it cannot outlive it;
+    /// use [`ValueRef::to_owned()`][crate::value::ValueRef::to_owned] to keep it longer.
     ///
     /// # Errors
     ///
@@ -174,4 +197,116 @@ pub trait Row: Unpin + Send + Sync + 'static {
     fn try_get_raw<I>(&self, index: I) -> Result<<Self::Database as Database>::ValueRef<'_>, Error>
     where
         I: ColumnIndex<Self>;
+
+    /// Whether [`try_get()`](Self::try_get) accepts integer columns for wider integer types.
+    ///
+    /// See [`ConnectOptions::lenient_integer_decode()`][crate::connection::ConnectOptions::lenient_integer_decode].
+    #[doc(hidden)]
+    fn lenient_integer_decode(&self) -> bool {
+        false
+    }
+
+    /// Whether [`try_get()`](Self::try_get) accepts values of type `ty`, which were received
+    /// as text, for the string type `expected`.
+    ///
+    /// See [`ConnectOptions::prefer_text_for()`][crate::connection::ConnectOptions::prefer_text_for].
+    #[doc(hidden)]
+    fn decodes_as_text(
+        &self,
+        ty: &<Self::Database as Database>::TypeInfo,
+        expected: &<Self::Database as Database>::TypeInfo,
+    ) -> bool {
+        let _ = (ty, expected);
+        false
+    }
+}
+
+/// A database whose values can be converted to JSON without knowing their type statically.
+///
+/// See [`row_to_json()`] for how the drivers map their types.
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub trait ValueToJson: Database {
+    /// Convert a value to JSON based on its runtime type information.
+    ///
+    /// Returns an error if the type has no JSON mapping.
+    fn value_to_json(value: Self::ValueRef<'_>) -> Result<serde_json::Value, BoxDynError>;
+}
+
+/// Convert a row to a JSON object, mapping each column to a JSON value based on its
+/// runtime type information.
+///
+/// This allows serializing the results of arbitrary queries without knowing their schema.
+/// The object is keyed by column name; if several columns have the same name, the last one wins.
+///
+/// All drivers map `NULL` to `null`, booleans to `true`/`false`, integers and floats to numbers,
+/// strings to strings and binary data to arrays of bytes (like `serde` serializes `Vec<u8>`).
+/// Non-finite floats, which JSON cannot represent, become the strings `"NaN"`, `"Infinity"` and
+/// `"-Infinity"`. Decimal types such as `NUMERIC` become strings to preserve their precision.
+///
+/// ### Postgres
+/// `JSON` and `JSONB` are nested as-is and arrays (including multidimensional ones) become JSON
+/// arrays. Enums become their label. Other types are only supported if they were sent in the
+/// text format; cast them to `TEXT` in the query to convert them to strings.
+///
+/// ### MySQL
+/// `JSON` is nested as-is and `ENUM` and `SET` become strings. Date and time types are only
+/// supported if they were sent in the text format, e.g. by a query without arguments.
+///
+/// ### SQLite
+/// Values are mapped by their storage class, so `BOOLEAN` columns become `0` and `1` and JSON
+/// stored as `TEXT` becomes a string.
+///
+/// ### Any
+/// Values are mapped by the type the driver converted them to, e.g. `NUMERIC` is already a string.
+///
+/// # Errors
+///
+///  * [`ColumnDecode`] if the value of a column could not be converted.
+///
+/// [`ColumnDecode`]: Error::ColumnDecode
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub fn row_to_json<R>(row: &R) -> Result<serde_json::Value, Error>
+where
+    R: Row,
+    R::Database: ValueToJson,
+    usize: ColumnIndex<R>,
+{
+    let mut object = serde_json::Map::with_capacity(row.len());
+
+    for (index, column) in row.columns().iter().enumerate() {
+        let value = row.try_get_raw(index)?;
+
+        let value = <R::Database as ValueToJson>::value_to_json(value).map_err(|source| {
+            Error::ColumnDecode {
+                index: format!("{:?}", column.name()),
+                source,
+            }
+        })?;
+
+        object.insert(column.name().to_owned(), value);
+    }
+
+    Ok(serde_json::Value::Object(object))
+}
+
+/// Convert a float to a JSON number, or a string if it is not finite.
+#[cfg(feature = "json")]
+#[doc(hidden)]
+pub fn float_to_json(value: f64) -> serde_json::Value {
+    serde_json::Number::from_f64(value)
+        .map(serde_json::Value::Number)
+        .unwrap_or_else(|| {
+            serde_json::Value::String(
+                if value.is_nan() {
+                    "NaN"
+                } else if value.is_sign_positive() {
+                    "Infinity"
+                } else {
+                    "-Infinity"
+                }
+                .into(),
+            )
+        })
 }
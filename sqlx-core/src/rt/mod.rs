@@ -134,6 +134,51 @@ pub fn test_block_on<F: Future>(f: F) -> F::Output {
     }
 }
 
+/// Run `f` to completion on a new thread, blocking the current one until it finishes.
+///
+/// With Tokio, `f` runs on the runtime of the current context if there is one, so that it can
+/// use the I/O resources of that runtime; this deadlocks if that runtime is a `current_thread`
+/// runtime and the current thread is the one driving it.
+#[cfg(feature = "blocking")]
+#[track_caller]
+pub fn block_on_dedicated_thread<F>(f: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    #[cfg(feature = "_rt-tokio")]
+    {
+        let handle = tokio::runtime::Handle::try_current().ok();
+
+        return std::thread::scope(|s| {
+            s.spawn(move || match handle {
+                Some(handle) => handle.block_on(f),
+                None => tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .expect("failed to start Tokio runtime")
+                    .block_on(f),
+            })
+            .join()
+            .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+        });
+    }
+
+    #[cfg(all(feature = "_rt-async-std", not(feature = "_rt-tokio")))]
+    {
+        std::thread::scope(|s| {
+            s.spawn(move || async_std::task::block_on(f))
+                .join()
+                .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+        })
+    }
+
+    #[cfg(not(any(feature = "_rt-async-std", feature = "_rt-tokio")))]
+    {
+        missing_rt(f)
+    }
+}
+
 #[track_caller]
 pub fn missing_rt<T>(_unused: T) -> ! {
     if cfg!(feature = "_rt-tokio") {
@@ -0,0 +1,527 @@
+//! Run queries against schema-qualified copies of tables without editing the SQL.
+//!
+//! A [`TablePrefix`] names a schema and the tables that live in it. Wrapping an executor in a
+//! [`PrefixedExecutor`] rewrites every bare reference to one of those tables in the SQL it
+//! executes to `schema.table`:
+//!
+//! ```rust,ignore
+//! use sqlx::{PrefixedExecutor, TablePrefix};
+//!
+//! let prefix = TablePrefix::new("test_1234", ["users", "posts"]);
+//!
+//! // executes `SELECT * FROM test_1234.users WHERE name = 'users'`
+//! let rows = sqlx::query("SELECT * FROM users WHERE name = 'users'")
+//!     .fetch_all(PrefixedExecutor::new(&pool, &prefix))
+//!     .await?;
+//! ```
+//!
+//! This is intended for tests, e.g. to give each test its own schema while sharing the queries
+//! of the code under test.
+//!
+//! ### Rewriting rules
+//! The SQL is scanned for unquoted identifiers; string literals, quoted identifiers,
+//! dollar-quoted strings and comments are left as they are. An identifier is prefixed if it
+//! matches one of the tables (ignoring ASCII case, like unquoted identifiers in SQL) and is not
+//! already qualified, i.e. not preceded by `.`. Backslashes escape quotes in all strings for
+//! MySQL and only in `E'...'` strings otherwise; with `Any`, the backend has to be named with
+//! [`PrefixedExecutor::for_backend()`].
+//!
+//! The rewrite doesn't know where an identifier refers to a table, so a column or alias with
+//! the same name as one of the tables is prefixed as well. Keep the list of tables to the ones
+//! that need it.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+
+use crate::connection::CancelHandle;
+use crate::database::{Database, HasCovariantLifetimes};
+use crate::describe::Describe;
+use crate::error::{BoxDynError, Error};
+use crate::executor::{Execute, Executor};
+use crate::statement::Statement;
+
+/// A schema and the tables in it that [`PrefixedExecutor`] qualifies with the schema name.
+///
+/// Cheap to clone. See the [module documentation][self] for details.
+#[derive(Debug, Clone)]
+pub struct TablePrefix(Arc<TablePrefixInner>);
+
+#[derive(Debug)]
+struct TablePrefixInner {
+    schema: String,
+    // lowercased
+    tables: HashSet<String>,
+}
+
+impl TablePrefix {
+    /// Qualify the given `tables` with `schema`.
+    ///
+    /// `schema` is inserted into the SQL as-is, so it must be quoted by the caller if needed.
+    pub fn new<I>(schema: impl Into<String>, tables: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        TablePrefix(Arc::new(TablePrefixInner {
+            schema: schema.into(),
+            tables: tables
+                .into_iter()
+                .map(|table| table.as_ref().to_ascii_lowercase())
+                .collect(),
+        }))
+    }
+
+    /// The schema that tables are qualified with.
+    pub fn schema(&self) -> &str {
+        &self.0.schema
+    }
+
+    /// Rewrite `sql`, qualifying each bare reference to one of the tables with the schema.
+    ///
+    /// Backslash escapes are recognized only in `E'...'` strings, as in Postgres.
+    pub fn rewrite(&self, sql: &str) -> String {
+        self.rewrite_with(sql, false)
+    }
+
+    /// Rewrite `sql` by the rules of the backend named `backend_name`.
+    fn rewrite_for(&self, sql: &str, backend_name: &str) -> Result<String, Error> {
+        let backslash_escapes = match backend_name {
+            // MySQL treats backslashes in all strings as escapes, by default
+            "MySQL" => true,
+            "Any" => {
+                return Err(Error::Configuration(
+                    "the backend of `Any` is only known once connected; \
+                     name it with `PrefixedExecutor::for_backend()`"
+                        .into(),
+                ))
+            }
+            _ => false,
+        };
+
+        Ok(self.rewrite_with(sql, backslash_escapes))
+    }
+
+    fn rewrite_with(&self, sql: &str, backslash_escapes: bool) -> String {
+        let bytes = sql.as_bytes();
+
+        let mut out = String::with_capacity(sql.len());
+        let mut copied = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            i = match bytes[i] {
+                b'\'' => {
+                    let escapes = backslash_escapes
+                        || (i > 0
+                            && bytes[i - 1].eq_ignore_ascii_case(&b'e')
+                            && (i == 1 || !is_ident_char(bytes[i - 2])));
+
+                    skip_quoted(bytes, i, b'\'', escapes)
+                }
+                quote @ (b'"' | b'`') => skip_quoted(bytes, i, quote, false),
+                b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                    match bytes[i..].iter().position(|&byte| byte == b'\n') {
+                        Some(end) => i + end + 1,
+                        None => bytes.len(),
+                    }
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => skip_block_comment(bytes, i),
+                b'$' => skip_dollar_quoted(bytes, i),
+                // numbers, so the exponent of e.g. `1e10` isn't taken for an identifier
+                b'0'..=b'9' => skip_ident(bytes, i),
+                byte if is_ident_start(byte) => {
+                    let end = skip_ident(bytes, i);
+                    let word = &sql[i..end];
+
+                    let qualified = i > 0 && bytes[i - 1] == b'.';
+
+                    if !qualified && self.0.tables.contains(&word.to_ascii_lowercase()) {
+                        out.push_str(&sql[copied..i]);
+                        out.push_str(&self.0.schema);
+                        out.push('.');
+                        copied = i;
+                    }
+
+                    end
+                }
+                _ => i + 1,
+            };
+        }
+
+        out.push_str(&sql[copied..]);
+        out
+    }
+}
+
+fn is_ident_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || !byte.is_ascii()
+}
+
+fn is_ident_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' || !byte.is_ascii()
+}
+
+fn skip_ident(bytes: &[u8], start: usize) -> usize {
+    let mut i = start;
+
+    while i < bytes.len() && is_ident_char(bytes[i]) {
+        i += 1;
+    }
+
+    i
+}
+
+/// Returns the index just past the closing quote; a doubled quote is an escaped quote.
+fn skip_quoted(bytes: &[u8], start: usize, quote: u8, backslash_escapes: bool) -> usize {
+    let mut i = start + 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' if backslash_escapes => i += 2,
+            byte if byte == quote => {
+                if bytes.get(i + 1) == Some(&quote) {
+                    i += 2;
+                } else {
+                    return i + 1;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+/// Block comments nest in Postgres and the SQL standard.
+fn skip_block_comment(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+
+    while i < bytes.len() {
+        match (bytes[i], bytes.get(i + 1)) {
+            (b'/', Some(b'*')) => {
+                depth += 1;
+                i += 2;
+            }
+            (b'*', Some(b'/')) => {
+                depth -= 1;
+                i += 2;
+
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+/// Skips a Postgres dollar-quoted string like `$$...$$` or `$tag$...$tag$`.
+///
+/// Anything else starting with `$`, e.g. a `$1` placeholder, is skipped one byte at a time.
+fn skip_dollar_quoted(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+
+    if i < bytes.len() && bytes[i] != b'$' {
+        if !is_ident_start(bytes[i]) {
+            return start + 1;
+        }
+
+        while i < bytes.len() && is_ident_char(bytes[i]) && bytes[i] != b'$' {
+            i += 1;
+        }
+    }
+
+    if bytes.get(i) != Some(&b'$') {
+        return start + 1;
+    }
+
+    let tag = &bytes[start..=i];
+    let body = i + 1;
+
+    match bytes[body..]
+        .windows(tag.len())
+        .position(|window| window == tag)
+    {
+        Some(end) => body + end + tag.len(),
+        None => bytes.len(),
+    }
+}
+
+/// An [`Executor`] that qualifies the tables of a [`TablePrefix`] in the SQL of every query
+/// before passing it on to the wrapped executor.
+///
+/// The query is prepared from the rewritten SQL, so a cached statement of the original
+/// query is not used. See the [module documentation][self] for the rewriting rules.
+#[derive(Debug)]
+pub struct PrefixedExecutor<E> {
+    inner: E,
+    prefix: TablePrefix,
+    // overrides `Database::NAME`
+    backend_name: Option<Arc<str>>,
+}
+
+impl<E> PrefixedExecutor<E> {
+    /// Wrap `executor`, e.g. `&pool` or `&mut *conn`, qualifying the tables of `prefix`.
+    ///
+    /// With `Any`, also call [`for_backend()`][Self::for_backend]; queries fail with
+    /// [`Error::Configuration`] otherwise.
+    pub fn new(executor: E, prefix: &TablePrefix) -> Self {
+        PrefixedExecutor {
+            inner: executor,
+            prefix: prefix.clone(),
+            backend_name: None,
+        }
+    }
+
+    /// Rewrite the SQL for the backend named `backend_name`, e.g. as returned by
+    /// `AnyConnection::backend_name()`, instead of the database of the executor.
+    ///
+    /// The backend decides how string literals are skipped: MySQL treats backslashes as escapes
+    /// in all strings, other databases only in `E'...'` strings.
+    pub fn for_backend(mut self, backend_name: &str) -> Self {
+        self.backend_name = Some(backend_name.into());
+        self
+    }
+
+    fn rewrite<DB: Database>(
+        prefix: &TablePrefix,
+        backend_name: Option<&str>,
+        sql: &str,
+    ) -> Result<String, Error> {
+        prefix.rewrite_for(sql, backend_name.unwrap_or(DB::NAME))
+    }
+
+    /// Return the wrapped executor.
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E: Clone> Clone for PrefixedExecutor<E> {
+    fn clone(&self) -> Self {
+        PrefixedExecutor {
+            inner: self.inner.clone(),
+            prefix: self.prefix.clone(),
+            backend_name: self.backend_name.clone(),
+        }
+    }
+}
+
+struct RewrittenQuery<'q, DB: Database> {
+    sql: &'q str,
+    arguments: Option<DB::Arguments<'q>>,
+    persistent: bool,
+}
+
+impl<'q, DB: Database> Execute<'q, DB> for RewrittenQuery<'q, DB> {
+    fn sql(&self) -> &'q str {
+        self.sql
+    }
+
+    fn statement(&self) -> Option<&DB::Statement<'q>> {
+        None
+    }
+
+    fn take_arguments(&mut self) -> Result<Option<DB::Arguments<'q>>, BoxDynError> {
+        Ok(self.arguments.take())
+    }
+
+    fn persistent(&self) -> bool {
+        self.persistent
+    }
+}
+
+impl<'c, E, DB> Executor<'c> for PrefixedExecutor<E>
+where
+    E: 'c + Executor<'c, Database = DB>,
+    DB: HasCovariantLifetimes,
+{
+    type Database = DB;
+
+    fn fetch_many<'e, 'q: 'e, Q>(
+        self,
+        query: Q,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, DB>,
+    {
+        let PrefixedExecutor {
+            inner,
+            prefix,
+            backend_name,
+        } = self;
+
+        Box::pin(try_stream! {
+            let mut query = query;
+
+            let sql = Self::rewrite::<DB>(&prefix, backend_name.as_deref(), query.sql())?;
+            let arguments = query.take_arguments().map_err(Error::Encode)?;
+
+            let mut s = inner.fetch_many(RewrittenQuery::<DB> {
+                sql: &sql,
+                arguments: arguments.map(DB::shorten_arguments),
+                persistent: query.persistent(),
+            });
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(v);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn fetch_optional<'e, 'q: 'e, Q>(
+        self,
+        query: Q,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        'c: 'e,
+        Q: 'q + Execute<'q, DB>,
+    {
+        let PrefixedExecutor {
+            inner,
+            prefix,
+            backend_name,
+        } = self;
+
+        Box::pin(async move {
+            let mut query = query;
+
+            let sql = Self::rewrite::<DB>(&prefix, backend_name.as_deref(), query.sql())?;
+            let arguments = query.take_arguments().map_err(Error::Encode)?;
+
+            inner
+                .fetch_optional(RewrittenQuery::<DB> {
+                    sql: &sql,
+                    arguments: arguments.map(DB::shorten_arguments),
+                    persistent: query.persistent(),
+                })
+                .await
+        })
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [DB::TypeInfo],
+    ) -> BoxFuture<'e, Result<DB::Statement<'q>, Error>>
+    where
+        'c: 'e,
+    {
+        let PrefixedExecutor {
+            inner,
+            prefix,
+            backend_name,
+        } = self;
+
+        Box::pin(async move {
+            let sql = Self::rewrite::<DB>(&prefix, backend_name.as_deref(), sql)?;
+            let statement = inner.prepare_with(&sql, parameters).await?;
+
+            Ok(DB::shorten_statement(Statement::to_owned(&statement)))
+        })
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(self, sql: &'q str) -> BoxFuture<'e, Result<Describe<DB>, Error>>
+    where
+        'c: 'e,
+    {
+        let PrefixedExecutor {
+            inner,
+            prefix,
+            backend_name,
+        } = self;
+
+        Box::pin(async move {
+            let sql = Self::rewrite::<DB>(&prefix, backend_name.as_deref(), sql)?;
+            inner.describe(&sql).await
+        })
+    }
+
+    fn cancel_handle(&self) -> Option<Arc<dyn CancelHandle>> {
+        self.inner.cancel_handle()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TablePrefix;
+    use crate::error::Error;
+
+    #[test]
+    fn it_prefixes_bare_table_names() {
+        let prefix = TablePrefix::new("test", ["users", "Posts"]);
+
+        assert_eq!(
+            prefix.rewrite("SELECT * FROM users JOIN posts ON posts.user_id = users.id"),
+            "SELECT * FROM test.users JOIN test.posts ON test.posts.user_id = test.users.id"
+        );
+        assert_eq!(
+            prefix.rewrite("insert into USERS(name) values ($1)"),
+            "insert into test.USERS(name) values ($1)"
+        );
+        assert_eq!(
+            prefix.rewrite("SELECT * FROM public.users, users_archive"),
+            "SELECT * FROM public.users, users_archive"
+        );
+    }
+
+    #[test]
+    fn it_skips_literals_and_comments() {
+        let prefix = TablePrefix::new("test", ["users"]);
+
+        for sql in [
+            "SELECT 'users', 'it''s users'",
+            r#"SELECT "users" FROM "users""#,
+            "SELECT `users`",
+            "SELECT 1 -- from users\n",
+            "SELECT 1 /* from /* nested */ users */",
+            "SELECT $$users$$, $tag$ users $tag$",
+            r"SELECT E'\' users'",
+        ] {
+            assert_eq!(prefix.rewrite(sql), sql);
+        }
+
+        assert_eq!(
+            prefix.rewrite("SELECT $1::text FROM users -- users\nWHERE 'users' = users.name"),
+            "SELECT $1::text FROM test.users -- users\nWHERE 'users' = test.users.name"
+        );
+
+        // a backslash is not an escape in a standard string
+        assert_eq!(
+            prefix.rewrite(r"SELECT 'C:\' FROM users"),
+            r"SELECT 'C:\' FROM test.users"
+        );
+        assert_eq!(
+            prefix
+                .rewrite_for(r"SELECT 'it\'s users' FROM users", "MySQL")
+                .unwrap(),
+            r"SELECT 'it\'s users' FROM test.users"
+        );
+    }
+
+    #[test]
+    fn it_requires_the_backend_of_any() {
+        let prefix = TablePrefix::new("test", ["users"]);
+
+        assert!(matches!(
+            prefix.rewrite_for("SELECT * FROM users", "Any"),
+            Err(Error::Configuration(_))
+        ));
+        assert_eq!(
+            prefix.rewrite_for("SELECT * FROM users", "SQLite").unwrap(),
+            "SELECT * FROM test.users"
+        );
+    }
+}
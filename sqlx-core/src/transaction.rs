@@ -6,7 +6,7 @@ use futures_core::future::BoxFuture;
 
 use crate::database::Database;
 use crate::error::Error;
-use crate::pool::MaybePoolConnection;
+use crate::pool::{MaybePoolConnection, PoolConnection};
 
 /// Generic management of database transactions.
 ///
@@ -72,7 +72,9 @@ pub struct Transaction<'c, DB>
 where
     DB: Database,
 {
-    connection: MaybePoolConnection<'c, DB>,
+    // only `None` after the connection has been handed back by `commit_returning_connection()`
+    // or `rollback_returning_connection()`
+    connection: Option<MaybePoolConnection<'c, DB>>,
     open: bool,
 }
 
@@ -90,7 +92,7 @@ where
             DB::TransactionManager::begin(&mut conn).await?;
 
             Ok(Self {
-                connection: conn,
+                connection: Some(conn),
                 open: true,
             })
         })
@@ -98,7 +100,7 @@ where
 
     /// Commits this transaction or savepoint.
     pub async fn commit(mut self) -> Result<(), Error> {
-        DB::TransactionManager::commit(&mut self.connection).await?;
+        DB::TransactionManager::commit(self.connection_mut()).await?;
         self.open = false;
 
         Ok(())
@@ -106,11 +108,77 @@ where
 
     /// Aborts this transaction or savepoint.
     pub async fn rollback(mut self) -> Result<(), Error> {
-        DB::TransactionManager::rollback(&mut self.connection).await?;
+        DB::TransactionManager::rollback(self.connection_mut()).await?;
         self.open = false;
 
         Ok(())
     }
+
+    /// Commits this transaction and returns the connection it was started on, so it can be used
+    /// again without acquiring another one from the pool.
+    ///
+    /// ### Errors
+    /// Besides errors from committing, [`Error::Configuration`] if this transaction was not
+    /// started with [`Pool::begin()`][crate::pool::Pool::begin] or on a [`PoolConnection`] it
+    /// owns, e.g. if it is a savepoint in another transaction. The transaction is rolled back
+    /// instead of committed in that case.
+    pub async fn commit_returning_connection(mut self) -> Result<PoolConnection<DB>, Error> {
+        self.check_owns_pool_connection("commit_returning_connection")?;
+
+        DB::TransactionManager::commit(self.connection_mut()).await?;
+        self.open = false;
+
+        Ok(self.into_pool_connection())
+    }
+
+    /// Aborts this transaction and returns the connection it was started on, so it can be used
+    /// again without acquiring another one from the pool.
+    ///
+    /// ### Errors
+    /// Besides errors from rolling back, [`Error::Configuration`] if this transaction was not
+    /// started with [`Pool::begin()`][crate::pool::Pool::begin] or on a [`PoolConnection`] it
+    /// owns, e.g. if it is a savepoint in another transaction. The transaction is still rolled
+    /// back in that case.
+    pub async fn rollback_returning_connection(mut self) -> Result<PoolConnection<DB>, Error> {
+        self.check_owns_pool_connection("rollback_returning_connection")?;
+
+        DB::TransactionManager::rollback(self.connection_mut()).await?;
+        self.open = false;
+
+        Ok(self.into_pool_connection())
+    }
+
+    fn connection_ref(&self) -> &MaybePoolConnection<'c, DB> {
+        self.connection
+            .as_ref()
+            .expect("BUG: transaction connection already taken")
+    }
+
+    fn connection_mut(&mut self) -> &mut MaybePoolConnection<'c, DB> {
+        self.connection
+            .as_mut()
+            .expect("BUG: transaction connection already taken")
+    }
+
+    // dropping `self` after this fails rolls the transaction back
+    fn check_owns_pool_connection(&self, method: &str) -> Result<(), Error> {
+        match self.connection_ref() {
+            MaybePoolConnection::PoolConnection(_) => Ok(()),
+            MaybePoolConnection::Connection(_) => Err(Error::Configuration(
+                format!(
+                    "Transaction::{method}() called on a transaction that borrows its connection"
+                )
+                .into(),
+            )),
+        }
+    }
+
+    fn into_pool_connection(mut self) -> PoolConnection<DB> {
+        match self.connection.take() {
+            Some(MaybePoolConnection::PoolConnection(conn)) => conn,
+            _ => unreachable!("BUG: checked by `check_owns_pool_connection()`"),
+        }
+    }
 }
 
 // NOTE: fails to compile due to lack of lazy normalization
@@ -201,7 +269,7 @@ where
 
     #[inline]
     fn deref(&self) -> &Self::Target {
-        &self.connection
+        self.connection_ref()
     }
 }
 
@@ -211,7 +279,7 @@ where
 {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.connection
+        self.connection_mut()
     }
 }
 
@@ -221,7 +289,7 @@ where
 // See: https://github.com/launchbadge/sqlx/issues/2520
 impl<'c, DB: Database> AsMut<DB::Connection> for Transaction<'c, DB> {
     fn as_mut(&mut self) -> &mut DB::Connection {
-        &mut self.connection
+        self.connection_mut()
     }
 }
 
@@ -253,7 +321,7 @@ where
             // operation that will happen on the next asynchronous invocation of the underlying
             // connection (including if the connection is returned to a pool)
 
-            DB::TransactionManager::start_rollback(&mut self.connection);
+            DB::TransactionManager::start_rollback(self.connection_mut());
         }
     }
 }
@@ -284,3 +352,425 @@ pub fn rollback_ansi_transaction_sql(depth: usize) -> Cow<'static, str> {
         ))
     }
 }
+
+/// Returns `true` if `sql` contains a statement that may modify the database.
+///
+/// This looks at the keyword each statement starts with, data-modifying common table
+/// expressions, `SELECT ... INTO`, `COPY ... FROM` and `EXPLAIN ANALYZE` of a write. Calling
+/// a function with side effects from a `SELECT` is not detected.
+///
+/// Used to enforce [`PoolOptions::require_transaction_for_writes`][crate::pool::PoolOptions::require_transaction_for_writes].
+#[doc(hidden)]
+pub fn is_write_statement(sql: &str) -> bool {
+    const WRITE_KEYWORDS: &[&str] = &[
+        "ALTER", "ANALYSE", "ANALYZE", "CALL", "CLUSTER", "COMMENT", "CREATE", "DELETE", "DO",
+        "DROP", "EXECUTE", "GRANT", "IMPORT", "INSERT", "LOAD", "LOCK", "MERGE", "OPTIMIZE",
+        "REFRESH", "REINDEX", "RENAME", "REPLACE", "REVOKE", "TRUNCATE", "UPDATE", "UPSERT",
+        "VACUUM",
+    ];
+
+    const CTE_WRITE_KEYWORDS: &[&str] = &["DELETE", "INSERT", "MERGE", "UPDATE"];
+
+    enum State {
+        Start,
+        Copy,
+        With,
+        Select,
+        Explain { analyze: bool },
+        Other,
+    }
+
+    let mut state = State::Start;
+    let mut depth = 0usize;
+    let mut prev = None;
+
+    for token in SqlTokens::new(sql) {
+        match token {
+            SqlToken::Open => depth += 1,
+            SqlToken::Close => depth = depth.saturating_sub(1),
+            SqlToken::Semicolon => {
+                state = State::Start;
+                depth = 0;
+            }
+            SqlToken::Word(word) => {
+                let is = |keyword: &str| keyword.eq_ignore_ascii_case(word);
+                let is_any = |keywords: &[&str]| keywords.iter().any(|keyword| is(keyword));
+
+                // the statement that `EXPLAIN ANALYZE` executes starts like any other
+                let starts_statement = match state {
+                    State::Start => true,
+                    State::Explain { analyze } => analyze && depth == 0,
+                    _ => false,
+                };
+
+                match state {
+                    _ if starts_statement && is_any(WRITE_KEYWORDS) => return true,
+                    _ if starts_statement && is("WITH") => state = State::With,
+                    _ if starts_statement && is("SELECT") => state = State::Select,
+                    State::Start if is("COPY") => state = State::Copy,
+                    State::Start if is("EXPLAIN") => state = State::Explain { analyze: false },
+                    State::Start => state = State::Other,
+                    State::Explain { .. } if is("ANALYZE") || is("ANALYSE") => {
+                        state = State::Explain { analyze: true }
+                    }
+                    State::Copy if depth == 0 && is("FROM") => return true,
+                    // the body of a CTE, or the statement after them; not e.g. `FOR UPDATE`
+                    State::With
+                        if matches!(prev, Some(SqlToken::Open | SqlToken::Close))
+                            && is_any(CTE_WRITE_KEYWORDS) =>
+                    {
+                        return true
+                    }
+                    State::With | State::Select if depth == 0 && is("INTO") => return true,
+                    State::Copy
+                    | State::With
+                    | State::Select
+                    | State::Explain { .. }
+                    | State::Other => (),
+                }
+            }
+            _ => continue,
+        }
+
+        prev = Some(token);
+    }
+
+    false
+}
+
+/// Returns `true` if every statement in `sql` only reads from the database.
+///
+/// Unlike [`is_write_statement()`], this is an allow-list: only `SELECT`, `VALUES`, `TABLE`,
+/// `SHOW` and `EXPLAIN` (without `ANALYZE`) are accepted, and not if they contain `INTO` or
+/// lock rows with `FOR UPDATE`, `FOR SHARE` or `LOCK IN SHARE MODE`. Calling a function with
+/// side effects from a `SELECT` is not detected.
+///
+/// Used to decide whether a query may be retried, see
+/// [`PoolOptions::retry_read_on_disconnect`][crate::pool::PoolOptions::retry_read_on_disconnect].
+#[doc(hidden)]
+pub fn is_read_only_statement(sql: &str) -> bool {
+    const READ_KEYWORDS: &[&str] = &["SELECT", "VALUES", "TABLE", "SHOW", "EXPLAIN"];
+
+    let mut statements = 0;
+    let mut at_start = true;
+    let mut prev: Option<&str> = None;
+
+    for token in SqlTokens::new(sql) {
+        let word = match token {
+            SqlToken::Semicolon => {
+                at_start = true;
+                prev = None;
+                continue;
+            }
+            SqlToken::Word(word) => word,
+            _ => continue,
+        };
+
+        let is = |word: &str, keyword: &str| keyword.eq_ignore_ascii_case(word);
+
+        if at_start {
+            if !READ_KEYWORDS.iter().any(|keyword| is(word, keyword)) {
+                return false;
+            }
+
+            statements += 1;
+            at_start = false;
+        }
+
+        let locks = (is(word, "UPDATE") || is(word, "SHARE"))
+            && prev.is_some_and(|prev| is(prev, "FOR") || is(prev, "KEY") || is(prev, "IN"));
+
+        if is(word, "INTO") || is(word, "ANALYZE") || is(word, "ANALYSE") || locks {
+            return false;
+        }
+
+        prev = Some(word);
+    }
+
+    statements > 0
+}
+
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlToken<'a> {
+    /// A keyword or unquoted identifier.
+    Word(&'a str),
+    /// A string or identifier with its quotes, e.g. `'it''s'`, `E'it\'s'`, `"Name"` or
+    /// `$$...$$`.
+    Quoted(&'a str),
+    /// A Postgres bind parameter such as `$1`.
+    Param(&'a str),
+    /// A numeric literal.
+    Number(&'a str),
+    Open,
+    Close,
+    Semicolon,
+    /// Any other punctuation, e.g. `,` or an operator.
+    Punct(u8),
+}
+
+/// Splits SQL into tokens, skipping whitespace and comments.
+#[doc(hidden)]
+pub struct SqlTokens<'a> {
+    sql: &'a str,
+    i: usize,
+    backslash_escapes: bool,
+}
+
+impl<'a> SqlTokens<'a> {
+    pub fn new(sql: &'a str) -> Self {
+        Self {
+            sql,
+            i: 0,
+            backslash_escapes: false,
+        }
+    }
+
+    /// Read a backslash in a quoted string as escaping the next character, as MySQL does.
+    ///
+    /// Postgres `E'...'` strings are always read this way.
+    pub fn backslash_escapes(mut self, backslash_escapes: bool) -> Self {
+        self.backslash_escapes = backslash_escapes;
+        self
+    }
+
+    /// The byte offset just past the last token returned.
+    pub fn offset(&self) -> usize {
+        self.i
+    }
+}
+
+impl<'a> Iterator for SqlTokens<'a> {
+    type Item = SqlToken<'a>;
+
+    fn next(&mut self) -> Option<SqlToken<'a>> {
+        let find = |haystack: &[u8], needle: &[u8]| {
+            haystack
+                .windows(needle.len())
+                .position(|window| window == needle)
+        };
+
+        let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80;
+
+        let bytes = self.sql.as_bytes();
+
+        while self.i < bytes.len() {
+            let rest = &bytes[self.i..];
+
+            let (len, token): (usize, fn(&'a str) -> SqlToken<'a>) = match rest[0] {
+                b'-' if rest.starts_with(b"--") => {
+                    self.i += find(rest, b"\n").unwrap_or(rest.len());
+                    continue;
+                }
+                b'/' if rest.starts_with(b"/*") => {
+                    self.i += find(&rest[2..], b"*/").map_or(rest.len(), |end| end + 4);
+                    continue;
+                }
+                b if b.is_ascii_whitespace() => {
+                    self.i += 1;
+                    continue;
+                }
+                quote @ (b'\'' | b'"' | b'`') => {
+                    let backslash_escapes = self.backslash_escapes && quote != b'`';
+                    (quoted_len(rest, backslash_escapes), SqlToken::Quoted)
+                }
+                // Postgres string with C-style escapes, e.g. `E'it\'s'`
+                b'E' | b'e' if rest.get(1) == Some(&b'\'') => {
+                    (1 + quoted_len(&rest[1..], true), SqlToken::Quoted)
+                }
+                // Postgres dollar-quoted string, e.g. `$$...$$` or `$tag$...$tag$`, or a bind
+                // parameter such as `$1`
+                b'$' => {
+                    let tag_len = rest[1..].iter().position(|&b| !is_ident(b) || b == b'$');
+
+                    match tag_len.filter(|&len| rest[len + 1] == b'$') {
+                        Some(tag_len) if !rest[1].is_ascii_digit() => {
+                            let tag = &rest[..tag_len + 2];
+                            let len = find(&rest[tag.len()..], tag)
+                                .map_or(rest.len(), |end| end + tag.len() * 2);
+
+                            (len, SqlToken::Quoted)
+                        }
+                        _ => match rest[1..].iter().take_while(|b| b.is_ascii_digit()).count() {
+                            0 => (1, |_| SqlToken::Punct(b'$')),
+                            digits => (1 + digits, SqlToken::Param),
+                        },
+                    }
+                }
+                b'(' => (1, |_| SqlToken::Open),
+                b')' => (1, |_| SqlToken::Close),
+                b';' => (1, |_| SqlToken::Semicolon),
+                b if b.is_ascii_alphabetic() || b == b'_' || b >= 0x80 => {
+                    let len = rest
+                        .iter()
+                        .position(|&b| !is_ident(b))
+                        .unwrap_or(rest.len());
+
+                    (len, SqlToken::Word)
+                }
+                b if b.is_ascii_digit() => {
+                    let len = rest
+                        .iter()
+                        .position(|&b| !is_ident(b) && b != b'.')
+                        .unwrap_or(rest.len());
+
+                    (len, SqlToken::Number)
+                }
+                _ => (1, |text| SqlToken::Punct(text.as_bytes()[0])),
+            };
+
+            let text = &self.sql[self.i..self.i + len];
+            self.i += len;
+            return Some(token(text));
+        }
+
+        None
+    }
+}
+
+/// The length of the quoted string or identifier at the start of `rest`, including the quotes.
+fn quoted_len(rest: &[u8], backslash_escapes: bool) -> usize {
+    let quote = rest[0];
+    let mut i = 1;
+
+    while i < rest.len() {
+        match rest[i] {
+            b'\\' if backslash_escapes => i += 2,
+            // a doubled quote stands for the quote itself
+            b if b == quote && rest.get(i + 1) == Some(&quote) => i += 2,
+            b if b == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+
+    rest.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_read_only_statement, is_write_statement, SqlToken, SqlTokens};
+
+    #[test]
+    fn it_splits_sql_into_tokens() {
+        use SqlToken::*;
+
+        let sql = "SELECT \"A\"\"b\", E'it\\'s' -- comment\n\
+                   FROM t /* comment */ WHERE x = $1 AND y > 1.5 AND z = $tag$;$tag$;";
+        let tokens: Vec<_> = SqlTokens::new(sql).collect();
+
+        assert_eq!(
+            tokens,
+            [
+                Word("SELECT"),
+                Quoted("\"A\"\"b\""),
+                Punct(b','),
+                Quoted("E'it\\'s'"),
+                Word("FROM"),
+                Word("t"),
+                Word("WHERE"),
+                Word("x"),
+                Punct(b'='),
+                Param("$1"),
+                Word("AND"),
+                Word("y"),
+                Punct(b'>'),
+                Number("1.5"),
+                Word("AND"),
+                Word("z"),
+                Punct(b'='),
+                Quoted("$tag$;$tag$"),
+                Semicolon,
+            ]
+        );
+
+        let sql = "SELECT 'it\\'s', `a\\`";
+        let tokens: Vec<_> = SqlTokens::new(sql).backslash_escapes(true).collect();
+        assert_eq!(
+            tokens,
+            [Word("SELECT"), Quoted("'it\\'s'"), Punct(b','), Quoted("`a\\`")]
+        );
+    }
+
+    #[test]
+    fn it_detects_write_statements() {
+        for sql in [
+            "INSERT INTO users (name) VALUES ($1)",
+            "  update users set name = 'x'",
+            "-- remove them\nDELETE FROM users",
+            "/* tidy up */ truncate users",
+            "SELECT 1; DROP TABLE users",
+            "WITH deleted AS (DELETE FROM users RETURNING id) SELECT count(*) FROM deleted",
+            "COPY users FROM STDIN",
+            "CREATE INDEX ON users (name)",
+            "CALL archive_users()",
+            "DO $$BEGIN DELETE FROM users; END$$",
+            "EXECUTE delete_users",
+            "SELECT * INTO users_copy FROM users",
+            "WITH ids AS (SELECT id FROM users) SELECT * INTO copy FROM ids",
+            "WITH ids AS (SELECT id FROM users) DELETE FROM users WHERE id IN (SELECT id FROM ids)",
+            "EXPLAIN ANALYZE DELETE FROM users",
+            "EXPLAIN (ANALYZE, BUFFERS) WITH d AS (DELETE FROM users) SELECT 1",
+            "LOCK TABLE users",
+            "VACUUM users",
+        ] {
+            assert!(is_write_statement(sql), "{sql:?} is a write");
+        }
+
+        for sql in [
+            "SELECT * FROM users",
+            "select 'delete from users'",
+            "SELECT \"update\" FROM t",
+            "-- DELETE FROM users\nSELECT 1",
+            "/* insert */ SELECT 1",
+            "SELECT $$; DELETE FROM users$$",
+            "SELECT $1, $tag$; drop table x$tag$",
+            "WITH ids AS (SELECT id FROM users) SELECT * FROM ids",
+            "COPY (SELECT id FROM users) TO STDOUT",
+            "WITH ids AS (SELECT id FROM users) SELECT * FROM ids FOR UPDATE",
+            "WITH ids AS (SELECT id FROM users) SELECT * FROM ids FOR NO KEY UPDATE",
+            "SELECT * FROM users WHERE id IN (SELECT id FROM admins)",
+            "EXPLAIN DELETE FROM users",
+            "BEGIN",
+            "SET search_path = public",
+            "",
+        ] {
+            assert!(!is_write_statement(sql), "{sql:?} is not a write");
+        }
+    }
+
+    #[test]
+    fn it_detects_read_only_statements() {
+        for sql in [
+            "SELECT * FROM users",
+            "select 'delete from users'",
+            "VALUES (1), (2)",
+            "TABLE users",
+            "SHOW search_path",
+            "EXPLAIN SELECT * FROM users",
+            "SELECT 1; SELECT 2",
+            "-- DELETE FROM users\nSELECT 1",
+            "SELECT * FROM users WHERE id IN (SELECT id FROM admins)",
+        ] {
+            assert!(is_read_only_statement(sql), "{sql:?} is read-only");
+        }
+
+        for sql in [
+            "CALL archive_users()",
+            "DO $$BEGIN PERFORM 1; END$$",
+            "EXECUTE stmt",
+            "SELECT * INTO users_copy FROM users",
+            "SELECT * FROM users FOR UPDATE",
+            "SELECT * FROM users FOR NO KEY UPDATE",
+            "SELECT * FROM users FOR SHARE",
+            "SELECT * FROM users LOCK IN SHARE MODE",
+            "EXPLAIN ANALYZE SELECT 1",
+            "WITH ids AS (SELECT id FROM users) SELECT * FROM ids",
+            "SELECT 1; DELETE FROM users",
+            "LOCK TABLE users",
+            "VACUUM",
+            "",
+        ] {
+            assert!(!is_read_only_statement(sql), "{sql:?} is not read-only");
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::fmt::{Debug, Display};
 
 /// Provides information about a SQL type for the database driver.
@@ -23,4 +24,24 @@ pub trait TypeInfo: Debug + Display + Clone + PartialEq<Self> + Send + Sync {
     fn is_void(&self) -> bool {
         false
     }
+
+    /// The type as it is written after `::` to cast a bind parameter to it, quoted as needed.
+    ///
+    /// `None` if the database doesn't cast with `::` or the type has no name to cast to.
+    ///
+    /// Used by [`QueryBuilder::push_bind_cast()`][crate::query_builder::QueryBuilder::push_bind_cast].
+    #[doc(hidden)]
+    fn cast_name(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// Return `true` if `self` is an integer type whose values can all be represented by
+    /// the integer type `wider`, but which is not otherwise compatible with it.
+    ///
+    /// Used by [`ConnectOptions::lenient_integer_decode()`][crate::connection::ConnectOptions::lenient_integer_decode].
+    #[doc(hidden)]
+    fn is_integer_widening_to(&self, wider: &Self) -> bool {
+        let _ = wider;
+        false
+    }
 }
@@ -4,6 +4,28 @@ use serde::{Deserialize, Serialize};
 pub use serde_json::value::RawValue as JsonRawValue;
 pub use serde_json::Value as JsonValue;
 
+/// A JSON object, for `json` and `jsonb` fields that always hold one.
+///
+/// Decoding fails with an error naming the kind of value found if the field holds anything
+/// else, e.g. an array. [`Json<JsonMap>`][Json] works too, but its error is the one
+/// from `serde_json`.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::types::JsonMap;
+///
+/// let settings: JsonMap = sqlx::query_scalar("SELECT settings FROM users WHERE id = 1")
+///     .fetch_one(conn)
+///     .await?;
+///
+/// if let Some(theme) = settings.get("theme") {
+///     println!("theme: {theme}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub type JsonMap = serde_json::Map<String, JsonValue>;
+
 use crate::database::Database;
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
@@ -52,6 +74,37 @@ use crate::types::Type;
 /// }
 /// ```
 ///
+/// # Borrowing from the row
+///
+/// `T` is deserialized straight from the bytes of the value, so it can borrow from them with
+/// `#[serde(borrow)]` instead of copying every string. This works for both `JSON` and `JSONB`
+/// in Postgres, which sends `JSONB` as JSON text, and for `JSON` in MySQL and SQLite.
+///
+/// The borrowed value can't outlive the row it was decoded from, so it suits code that processes
+/// rows one at a time, e.g. from `fetch()`, rather than a `query_as()` into an owned struct.
+/// Prefer `Cow<'r, str>` over `&'r str` for borrowed strings: `serde_json` can only borrow a
+/// string that contains no escape sequences, and with `Cow` it falls back to allocating instead
+/// of failing.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use std::borrow::Cow;
+/// use sqlx::types::Json;
+/// use sqlx::Row;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Event<'r> {
+///     #[serde(borrow)]
+///     kind: Cow<'r, str>,
+/// }
+///
+/// let row = sqlx::query("SELECT payload FROM events").fetch_one(&mut *conn).await?;
+///
+/// let event: Json<Event<'_>> = row.try_get("payload")?;
+/// # Ok(())
+/// # }
+/// ```
+///
 /// If the query macros are used, it is necessary to tell the macro to use
 /// the `Json` adapter by using the type override syntax
 /// ```rust,ignore
@@ -182,6 +235,52 @@ where
     }
 }
 
+impl<DB> Type<DB> for JsonMap
+where
+    Json<Self>: Type<DB>,
+    DB: Database,
+{
+    fn type_info() -> DB::TypeInfo {
+        <Json<Self> as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <Json<Self> as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB> Encode<'q, DB> for JsonMap
+where
+    for<'a> Json<&'a Self>: Encode<'q, DB>,
+    DB: Database,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        <Json<&Self> as Encode<'q, DB>>::encode(Json(self), buf)
+    }
+}
+
+impl<'r, DB> Decode<'r, DB> for JsonMap
+where
+    Json<JsonValue>: Decode<'r, DB>,
+    DB: Database,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let kind = match <Json<JsonValue> as Decode<DB>>::decode(value)?.0 {
+            JsonValue::Object(map) => return Ok(map),
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "a boolean",
+            JsonValue::Number(_) => "a number",
+            JsonValue::String(_) => "a string",
+            JsonValue::Array(_) => "an array",
+        };
+
+        Err(format!("expected a JSON object, got {kind}").into())
+    }
+}
+
 impl<DB> Type<DB> for JsonRawValue
 where
     for<'a> Json<&'a Self>: Type<DB>,
@@ -207,3 +306,132 @@ where
         <Json<Self> as Decode<DB>>::decode(value).map(|item| item.0)
     }
 }
+
+/// A JSON document that is deserialized according to the value of its version field.
+///
+/// Documents whose schema evolves over time often carry a version number, e.g.
+/// `{"version": 2, ...}`. Decoding this type reads the version field first and then hands both
+/// the version and the document to [`JsonVersions::from_version`], which picks the Rust type
+/// to deserialize the document into. Serde's internally tagged enums can do this for
+/// string tags, but not for numbers or missing fields.
+///
+/// Encoding serializes the inner value as it is, so the serialized document must include its
+/// version field. Like [`Json<T>`], this maps to `JSONB` in Postgres and `JSON` or `TEXT`
+/// elsewhere.
+///
+/// # Example
+///
+/// ```
+/// # use serde::Deserialize;
+/// use sqlx::types::{JsonValue, JsonVersions, VersionedJson};
+///
+/// #[derive(Deserialize)]
+/// struct SettingsV1 {
+///     dark_mode: bool,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct SettingsV2 {
+///     theme: String,
+/// }
+///
+/// enum Settings {
+///     V1(SettingsV1),
+///     V2(SettingsV2),
+/// }
+///
+/// impl JsonVersions for Settings {
+///     fn from_version(
+///         version: JsonValue,
+///         document: JsonValue,
+///     ) -> Result<Self, sqlx::error::BoxDynError> {
+///         match version.as_u64() {
+///             // Documents written before versioning was introduced have no version field.
+///             None | Some(1) => Ok(Settings::V1(serde_json::from_value(document)?)),
+///             Some(2) => Ok(Settings::V2(serde_json::from_value(document)?)),
+///             Some(other) => Err(format!("unknown settings version {other}").into()),
+///         }
+///     }
+/// }
+///
+/// #[derive(sqlx::FromRow)]
+/// struct User {
+///     name: String,
+///     settings: VersionedJson<Settings>,
+/// }
+/// ```
+///
+/// With the query macros, use a type override: `SELECT settings AS "settings: VersionedJson<Settings>"`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct VersionedJson<T>(pub T);
+
+/// The mapping from the version of a JSON document to the Rust type it is deserialized into.
+///
+/// See [`VersionedJson`].
+pub trait JsonVersions: Sized {
+    /// The name of the top-level field that holds the version of a document.
+    const VERSION_FIELD: &'static str = "version";
+
+    /// Deserialize `document`, where `version` is the value of its
+    /// [`VERSION_FIELD`][Self::VERSION_FIELD], or [`JsonValue::Null`] if it has no such field.
+    fn from_version(version: JsonValue, document: JsonValue) -> Result<Self, BoxDynError>;
+}
+
+impl<T> Deref for VersionedJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for VersionedJson<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<DB, T> Type<DB> for VersionedJson<T>
+where
+    Json<JsonValue>: Type<DB>,
+    DB: Database,
+{
+    fn type_info() -> DB::TypeInfo {
+        <Json<JsonValue> as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <Json<JsonValue> as Type<DB>>::compatible(ty)
+    }
+}
+
+impl<'q, DB, T> Encode<'q, DB> for VersionedJson<T>
+where
+    for<'a> Json<&'a T>: Encode<'q, DB>,
+    DB: Database,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        <Json<&T> as Encode<'q, DB>>::encode(Json(&self.0), buf)
+    }
+}
+
+impl<'r, DB, T> Decode<'r, DB> for VersionedJson<T>
+where
+    Json<JsonValue>: Decode<'r, DB>,
+    DB: Database,
+    T: JsonVersions,
+{
+    fn decode(value: <DB as Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let Json(document) = <Json<JsonValue> as Decode<DB>>::decode(value)?;
+
+        let version = document
+            .get(T::VERSION_FIELD)
+            .cloned()
+            .unwrap_or(JsonValue::Null);
+
+        T::from_version(version, document).map(VersionedJson)
+    }
+}
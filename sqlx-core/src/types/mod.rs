@@ -51,6 +51,11 @@ pub mod chrono {
 #[doc(no_inline)]
 pub use bit_vec::BitVec;
 
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+#[doc(no_inline)]
+pub use bytes::{Bytes, BytesMut};
+
 #[cfg(feature = "time")]
 #[cfg_attr(docsrs, doc(cfg(feature = "time")))]
 pub mod time {
@@ -82,7 +87,7 @@ pub mod mac_address {
 }
 
 #[cfg(feature = "json")]
-pub use json::{Json, JsonRawValue, JsonValue};
+pub use json::{Json, JsonMap, JsonRawValue, JsonValue, JsonVersions, VersionedJson};
 pub use text::Text;
 
 /// Indicates that a SQL type is supported for a database.
@@ -117,6 +122,22 @@ pub use text::Text;
 /// struct UserId(i64);
 /// ```
 ///
+/// The wrapped field may also be named, and the struct may carry any number of `PhantomData`
+/// fields besides it. This allows a single generic id type to be tagged with the table it
+/// belongs to, so ids for different tables can't be mixed up:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::Type)]
+/// #[sqlx(transparent)]
+/// struct Id<T>(i64, PhantomData<T>);
+///
+/// struct User;
+///
+/// let user_id: Option<Id<User>> = sqlx::query_scalar("select id from users limit 1")
+///     .fetch_optional(&mut conn)
+///     .await?;
+/// ```
+///
 /// ##### Note: `PgHasArrayType`
 /// If you have the `postgres` feature enabled, this derive also generates a `PgHasArrayType` impl
 /// so that you may use it with `Vec` and other types that decode from an array in Postgres:
@@ -128,13 +149,13 @@ pub use text::Text;
 /// ```
 ///
 /// However, if you are wrapping a type that does not implement `PgHasArrayType`
-/// (e.g. `Vec` itself, because we don't currently support multidimensional arrays),
+/// (e.g. a `Vec` of a type that doesn't support multidimensional arrays),
 /// you may receive an error:
 ///
 /// ```rust,ignore
-/// #[derive(sqlx::Type)] // ERROR: `Vec<i64>` does not implement `PgHasArrayType`
+/// #[derive(sqlx::Type)] // ERROR: `Vec<PgInterval>` does not implement `PgHasArrayType`
 /// #[sqlx(transparent)]
-/// struct UserIds(Vec<i64>);
+/// struct Intervals(Vec<PgInterval>);
 /// ```
 ///
 /// To remedy this, add `#[sqlx(no_pg_array)]`, which disables the generation
@@ -143,7 +164,7 @@ pub use text::Text;
 /// ```rust,ignore
 /// #[derive(sqlx::Type)]
 /// #[sqlx(transparent, no_pg_array)]
-/// struct UserIds(Vec<i64>);
+/// struct Intervals(Vec<PgInterval>);
 /// ```
 ///
 /// ##### Attributes
@@ -178,6 +199,23 @@ pub use text::Text;
 /// enum Color { Red, Green, Blue }
 /// ```
 ///
+/// By default, decoding a value that doesn't match any variant is an error. To stay
+/// compatible with values added to the SQL type later, mark one variant with
+/// `#[sqlx(other)]`; it must have a single `String` field, which receives any unknown value.
+/// That value is encoded back unchanged.
+///
+/// ```rust,ignore
+/// #[derive(sqlx::Type)]
+/// #[sqlx(type_name = "color", rename_all = "lowercase")]
+/// enum Color {
+///     Red,
+///     Green,
+///     Blue,
+///     #[sqlx(other)]
+///     Unknown(String),
+/// }
+/// ```
+///
 /// ### Records
 ///
 /// User-defined composite types are supported through deriving a `struct`.
@@ -0,0 +1,36 @@
+//! Hook for observing the raw bytes of the wire protocol.
+//!
+//! See [`ConnectOptions::wire_trace()`][crate::connection::ConnectOptions::wire_trace].
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+/// The direction a traced protocol message travelled in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    /// Sent from the client to the server.
+    Sent,
+    /// Received by the client from the server.
+    Received,
+}
+
+/// A callback invoked with the bytes of every protocol message on a connection.
+#[derive(Clone)]
+pub struct WireTrace(Arc<dyn Fn(Direction, &[u8]) + Send + Sync>);
+
+impl WireTrace {
+    pub fn new(f: impl Fn(Direction, &[u8]) + Send + Sync + 'static) -> Self {
+        WireTrace(Arc::new(f))
+    }
+
+    #[inline]
+    pub fn trace(&self, direction: Direction, bytes: &[u8]) {
+        (self.0)(direction, bytes)
+    }
+}
+
+impl Debug for WireTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WireTrace").finish_non_exhaustive()
+    }
+}
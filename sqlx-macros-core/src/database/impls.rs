@@ -14,6 +14,7 @@ macro_rules! impl_database_ext {
 
 macro_rules! impl_describe_blocking {
     ($database:path $(,)?) => {
+        #[cfg(feature = "macros")]
         fn describe_blocking(
             query: &str,
             database_url: &str,
@@ -27,6 +28,7 @@ macro_rules! impl_describe_blocking {
         }
     };
     ($database:path, $describe:path) => {
+        #[cfg(feature = "macros")]
         fn describe_blocking(
             query: &str,
             database_url: &str,
@@ -17,14 +17,29 @@ pub trait DatabaseExt: Database + TypeChecking {
     const DATABASE_PATH: &'static str;
     const ROW_PATH: &'static str;
 
+    /// The path of the database the generated code executes queries on.
+    ///
+    /// This is `Any` if `SQLX_EXECUTE_ON_ANY` is set, in which case `Self` is only used to
+    /// check the queries.
+    #[cfg(feature = "macros")]
     fn db_path() -> syn::Path {
+        if crate::query::execute_on_any() {
+            return syn::parse_str("sqlx::any::Any").unwrap();
+        }
+
         syn::parse_str(Self::DATABASE_PATH).unwrap()
     }
 
+    #[cfg(feature = "macros")]
     fn row_path() -> syn::Path {
+        if crate::query::execute_on_any() {
+            return syn::parse_str("sqlx::any::AnyRow").unwrap();
+        }
+
         syn::parse_str(Self::ROW_PATH).unwrap()
     }
 
+    #[cfg(feature = "macros")]
     fn describe_blocking(query: &str, database_url: &str) -> sqlx_core::Result<Describe<Self>>;
 }
 
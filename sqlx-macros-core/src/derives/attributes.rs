@@ -1,8 +1,8 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote_spanned;
 use syn::{
-    punctuated::Punctuated, token::Comma, Attribute, DeriveInput, Field, LitStr, Meta, Token, Type,
-    Variant,
+    punctuated::Punctuated, token::Comma, Attribute, DeriveInput, Field, Fields, LitStr, Meta,
+    Token, Type, Variant,
 };
 
 macro_rules! assert_attribute {
@@ -59,6 +59,7 @@ pub struct SqlxContainerAttributes {
     pub repr: Option<Ident>,
     pub no_pg_array: bool,
     pub default: bool,
+    pub deny_unknown_columns: bool,
 }
 
 pub struct SqlxChildAttributes {
@@ -68,6 +69,7 @@ pub struct SqlxChildAttributes {
     pub try_from: Option<Type>,
     pub skip: bool,
     pub json: bool,
+    pub other: bool,
 }
 
 pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContainerAttributes> {
@@ -77,6 +79,7 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
     let mut rename_all = None;
     let mut no_pg_array = None;
     let mut default = None;
+    let mut deny_unknown_columns = None;
 
     for attr in input {
         if attr.path().is_ident("sqlx") {
@@ -87,6 +90,8 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                     try_set!(no_pg_array, true, attr);
                 } else if meta.path.is_ident("default") {
                     try_set!(default, true, attr);
+                } else if meta.path.is_ident("deny_unknown_columns") {
+                    try_set!(deny_unknown_columns, true, attr);
                 } else if meta.path.is_ident("rename_all") {
                     meta.input.parse::<Token![=]>()?;
                     let lit: LitStr = meta.input.parse()?;
@@ -135,6 +140,7 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
         rename_all,
         no_pg_array: no_pg_array.unwrap_or(false),
         default: default.unwrap_or(false),
+        deny_unknown_columns: deny_unknown_columns.unwrap_or(false),
     })
 }
 
@@ -145,6 +151,7 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
     let mut flatten = false;
     let mut skip: bool = false;
     let mut json = false;
+    let mut other = false;
 
     for attr in input.iter().filter(|a| a.path().is_ident("sqlx")) {
         attr.parse_nested_meta(|meta| {
@@ -164,6 +171,8 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
                 skip = true;
             } else if meta.path.is_ident("json") {
                 json = true;
+            } else if meta.path.is_ident("other") {
+                other = true;
             }
 
             Ok(())
@@ -184,6 +193,7 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
         try_from,
         skip,
         json,
+        other,
     })
 }
 
@@ -244,6 +254,8 @@ pub fn check_weak_enum_attributes(
             "unexpected #[sqlx(rename = ..)]",
             variant
         );
+
+        assert_attribute!(!attributes.other, "unexpected #[sqlx(other)]", variant);
     }
 
     Ok(attributes)
@@ -251,15 +263,53 @@ pub fn check_weak_enum_attributes(
 
 pub fn check_strong_enum_attributes(
     input: &DeriveInput,
-    _variants: &Punctuated<Variant, Comma>,
+    variants: &Punctuated<Variant, Comma>,
 ) -> syn::Result<SqlxContainerAttributes> {
     let attributes = check_enum_attributes(input)?;
 
     assert_attribute!(attributes.repr.is_none(), "unexpected #[repr(..)]", input);
 
+    let mut has_other = false;
+
+    for variant in variants {
+        let ch_attributes = parse_child_attributes(&variant.attrs)?;
+
+        if !ch_attributes.other {
+            continue;
+        }
+
+        assert_attribute!(
+            !has_other,
+            "only one variant may be marked #[sqlx(other)]",
+            variant
+        );
+        has_other = true;
+
+        assert_attribute!(
+            ch_attributes.rename.is_none(),
+            "unexpected #[sqlx(rename = ..)] on #[sqlx(other)] variant",
+            variant
+        );
+
+        assert_attribute!(
+            matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1),
+            "#[sqlx(other)] variant must have a single unnamed `String` field",
+            variant
+        );
+    }
+
     Ok(attributes)
 }
 
+/// Returns the variant of a strong enum marked `#[sqlx(other)]`, if any.
+pub fn other_variant(variants: &Punctuated<Variant, Comma>) -> Option<&Variant> {
+    variants.iter().find(|variant| {
+        parse_child_attributes(&variant.attrs)
+            .map(|attributes| attributes.other)
+            .unwrap_or(false)
+    })
+}
+
 pub fn check_struct_attributes(
     input: &DeriveInput,
     fields: &Punctuated<Field, Comma>,
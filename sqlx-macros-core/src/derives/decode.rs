@@ -1,26 +1,25 @@
 use super::attributes::{
     check_strong_enum_attributes, check_struct_attributes, check_transparent_attributes,
-    check_weak_enum_attributes, parse_child_attributes, parse_container_attributes,
+    check_weak_enum_attributes, other_variant, parse_child_attributes, parse_container_attributes,
 };
-use super::rename_all;
+use super::{rename_all, Newtype};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{
-    parse_quote, Arm, Data, DataEnum, DataStruct, DeriveInput, Field, Fields, FieldsNamed,
-    FieldsUnnamed, Stmt, TypeParamBound, Variant,
+    parse_quote, Arm, Data, DataEnum, DataStruct, DeriveInput, Field, Fields, FieldsNamed, Stmt,
+    TypeParamBound, Variant,
 };
 
 pub fn expand_derive_decode(input: &DeriveInput) -> syn::Result<TokenStream> {
     let attrs = parse_container_attributes(&input.attrs)?;
+
+    if let Some(newtype) = Newtype::from_input(input, attrs.transparent) {
+        return expand_derive_decode_transparent(input, &newtype);
+    }
+
     match &input.data {
-        Data::Struct(DataStruct {
-            fields: Fields::Unnamed(FieldsUnnamed { unnamed, .. }),
-            ..
-        }) if unnamed.len() == 1 => {
-            expand_derive_decode_transparent(input, unnamed.first().unwrap())
-        }
         Data::Enum(DataEnum { variants, .. }) => match attrs.repr {
             Some(_) => expand_derive_decode_weak_enum(input, variants),
             None => expand_derive_decode_strong_enum(input, variants),
@@ -35,7 +34,7 @@ pub fn expand_derive_decode(input: &DeriveInput) -> syn::Result<TokenStream> {
             ..
         }) => Err(syn::Error::new_spanned(
             input,
-            "structs with zero or more than one unnamed field are not supported",
+            "structs with zero or more than one unnamed field (besides `PhantomData`) are not supported",
         )),
         Data::Struct(DataStruct {
             fields: Fields::Unit,
@@ -49,12 +48,13 @@ pub fn expand_derive_decode(input: &DeriveInput) -> syn::Result<TokenStream> {
 
 fn expand_derive_decode_transparent(
     input: &DeriveInput,
-    field: &Field,
+    newtype: &Newtype,
 ) -> syn::Result<TokenStream> {
-    check_transparent_attributes(input, field)?;
+    check_transparent_attributes(input, newtype.field)?;
 
     let ident = &input.ident;
-    let ty = &field.ty;
+    let ty = &newtype.field.ty;
+    let construct = newtype.construct(quote!(value));
 
     // extract type generics
     let generics = &input.generics;
@@ -83,7 +83,7 @@ fn expand_derive_decode_transparent(
                     dyn ::std::error::Error + 'static + ::std::marker::Send + ::std::marker::Sync,
                 >,
             > {
-                <#ty as ::sqlx::decode::Decode<'r, DB>>::decode(value).map(Self)
+                <#ty as ::sqlx::decode::Decode<'r, DB>>::decode(value).map(|value| #construct)
             }
         }
     );
@@ -111,6 +111,12 @@ fn expand_derive_decode_weak_enum(
         })
         .collect::<Vec<Arm>>();
 
+    let expected = variants.iter().map(|v| {
+        let id = &v.ident;
+        let id_s = id.to_string();
+        quote!(::std::format!("{} = {:?}", #id_s, #ident::#id as #repr))
+    });
+
     Ok(quote!(
         #[automatically_derived]
         impl<'r, DB: ::sqlx::Database> ::sqlx::decode::Decode<'r, DB> for #ident
@@ -129,9 +135,19 @@ fn expand_derive_decode_weak_enum(
 
                 match value {
                     #(#arms)*
-                    _ => ::std::result::Result::Err(::std::boxed::Box::new(::sqlx::Error::Decode(
-                        ::std::format!("invalid value {:?} for enum {}", value, #ident_s).into(),
-                    )))
+                    _ => {
+                        let expected: &[::std::string::String] = &[#(#expected),*];
+
+                        ::std::result::Result::Err(::std::boxed::Box::new(::sqlx::Error::Decode(
+                            ::std::format!(
+                                "invalid value {:?} for enum {}, expected one of: {}",
+                                value,
+                                #ident_s,
+                                expected.join(", "),
+                            )
+                            .into(),
+                        )))
+                    }
                 }
             }
         }
@@ -147,27 +163,54 @@ fn expand_derive_decode_strong_enum(
     let ident = &input.ident;
     let ident_s = ident.to_string();
 
-    let value_arms = variants.iter().map(|v| -> Arm {
+    let other = other_variant(variants);
+    let variants = variants
+        .iter()
+        .filter(|v| !other.is_some_and(|other| std::ptr::eq(other, *v)))
+        .collect::<Vec<_>>();
+
+    let names = variants
+        .iter()
+        .map(|v| {
+            let attributes = parse_child_attributes(&v.attrs).unwrap();
+
+            if let Some(rename) = attributes.rename {
+                rename
+            } else if let Some(pattern) = cattr.rename_all {
+                rename_all(&v.ident.to_string(), pattern)
+            } else {
+                v.ident.to_string()
+            }
+        })
+        .collect::<Vec<String>>();
+
+    let value_arms = variants.iter().zip(&names).map(|(v, name)| -> Arm {
         let id = &v.ident;
-        let attributes = parse_child_attributes(&v.attrs).unwrap();
+        parse_quote!(#name => ::std::result::Result::Ok(#ident :: #id),)
+    });
 
-        if let Some(rename) = attributes.rename {
-            parse_quote!(#rename => ::std::result::Result::Ok(#ident :: #id),)
-        } else if let Some(pattern) = cattr.rename_all {
-            let name = rename_all(&id.to_string(), pattern);
+    let expected = names
+        .iter()
+        .map(|name| format!("{name:?}"))
+        .collect::<Vec<_>>()
+        .join(", ");
 
-            parse_quote!(#name => ::std::result::Result::Ok(#ident :: #id),)
-        } else {
-            let name = id.to_string();
-            parse_quote!(#name => ::std::result::Result::Ok(#ident :: #id),)
+    let fallback_arm = match other {
+        Some(v) => {
+            let id = &v.ident;
+            quote!(_ => ::std::result::Result::Ok(#ident :: #id(value.into())))
         }
-    });
+        None => quote!(_ => Err(format!(
+            "invalid value {:?} for enum {}, expected one of: {}",
+            value, #ident_s, #expected,
+        ).into())),
+    };
 
     let values = quote! {
         match value {
             #(#value_arms)*
 
-            _ => Err(format!("invalid value {:?} for enum {}", value, #ident_s).into())
+            #fallback_arm
         }
     };
 
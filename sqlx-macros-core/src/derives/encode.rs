@@ -1,27 +1,25 @@
 use super::attributes::{
     check_strong_enum_attributes, check_struct_attributes, check_transparent_attributes,
-    check_weak_enum_attributes, parse_child_attributes, parse_container_attributes,
+    check_weak_enum_attributes, other_variant, parse_child_attributes, parse_container_attributes,
 };
-use super::rename_all;
+use super::{rename_all, Newtype};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{
     parse_quote, Data, DataEnum, DataStruct, DeriveInput, Expr, Field, Fields, FieldsNamed,
-    FieldsUnnamed, Lifetime, LifetimeParam, Stmt, TypeParamBound, Variant,
+    Lifetime, LifetimeParam, Stmt, TypeParamBound, Variant,
 };
 
 pub fn expand_derive_encode(input: &DeriveInput) -> syn::Result<TokenStream> {
     let args = parse_container_attributes(&input.attrs)?;
 
+    if let Some(newtype) = Newtype::from_input(input, args.transparent) {
+        return expand_derive_encode_transparent(input, &newtype);
+    }
+
     match &input.data {
-        Data::Struct(DataStruct {
-            fields: Fields::Unnamed(FieldsUnnamed { unnamed, .. }),
-            ..
-        }) if unnamed.len() == 1 => {
-            expand_derive_encode_transparent(input, unnamed.first().unwrap())
-        }
         Data::Enum(DataEnum { variants, .. }) => match args.repr {
             Some(_) => expand_derive_encode_weak_enum(input, variants),
             None => expand_derive_encode_strong_enum(input, variants),
@@ -36,7 +34,7 @@ pub fn expand_derive_encode(input: &DeriveInput) -> syn::Result<TokenStream> {
             ..
         }) => Err(syn::Error::new_spanned(
             input,
-            "structs with zero or more than one unnamed field are not supported",
+            "structs with zero or more than one unnamed field (besides `PhantomData`) are not supported",
         )),
         Data::Struct(DataStruct {
             fields: Fields::Unit,
@@ -50,12 +48,13 @@ pub fn expand_derive_encode(input: &DeriveInput) -> syn::Result<TokenStream> {
 
 fn expand_derive_encode_transparent(
     input: &DeriveInput,
-    field: &Field,
+    newtype: &Newtype,
 ) -> syn::Result<TokenStream> {
-    check_transparent_attributes(input, field)?;
+    check_transparent_attributes(input, newtype.field)?;
 
     let ident = &input.ident;
-    let ty = &field.ty;
+    let ty = &newtype.field.ty;
+    let member = &newtype.member;
 
     // extract type generics
     let generics = &input.generics;
@@ -86,15 +85,15 @@ fn expand_derive_encode_transparent(
                 &self,
                 buf: &mut <DB as ::sqlx::database::Database>::ArgumentBuffer<#lifetime>,
             ) -> ::std::result::Result<::sqlx::encode::IsNull, ::sqlx::error::BoxDynError> {
-                <#ty as ::sqlx::encode::Encode<#lifetime, DB>>::encode_by_ref(&self.0, buf)
+                <#ty as ::sqlx::encode::Encode<#lifetime, DB>>::encode_by_ref(&self.#member, buf)
             }
 
             fn produces(&self) -> Option<DB::TypeInfo> {
-                <#ty as ::sqlx::encode::Encode<#lifetime, DB>>::produces(&self.0)
+                <#ty as ::sqlx::encode::Encode<#lifetime, DB>>::produces(&self.#member)
             }
 
             fn size_hint(&self) -> usize {
-                <#ty as ::sqlx::encode::Encode<#lifetime, DB>>::size_hint(&self.0)
+                <#ty as ::sqlx::encode::Encode<#lifetime, DB>>::size_hint(&self.#member)
             }
         }
     ))
@@ -147,22 +146,46 @@ fn expand_derive_encode_strong_enum(
 
     let ident = &input.ident;
 
+    let other = other_variant(variants);
+
     let mut value_arms = Vec::new();
+    let mut size_hint_arms = Vec::new();
+    let mut other_where = None;
 
     for v in variants {
         let id = &v.ident;
+
+        // Unknown values are stored in an owned `String`, which can't be borrowed for `'q`
+        if other.is_some_and(|other| std::ptr::eq(other, v)) {
+            value_arms.push(quote!(
+                #ident :: #id(val) => {
+                    return <::std::string::String as ::sqlx::encode::Encode<'q, DB>>::encode_by_ref(val, buf);
+                }
+            ));
+            size_hint_arms.push(quote!(
+                #ident :: #id(val) => {
+                    return <::std::string::String as ::sqlx::encode::Encode<'q, DB>>::size_hint(val);
+                }
+            ));
+            other_where = Some(quote!(::std::string::String: ::sqlx::encode::Encode<'q, DB>,));
+            continue;
+        }
+
         let attributes = parse_child_attributes(&v.attrs)?;
 
-        if let Some(rename) = attributes.rename {
-            value_arms.push(quote!(#ident :: #id => #rename,));
+        let arm = if let Some(rename) = attributes.rename {
+            quote!(#ident :: #id => #rename,)
         } else if let Some(pattern) = cattr.rename_all {
             let name = rename_all(&id.to_string(), pattern);
 
-            value_arms.push(quote!(#ident :: #id => #name,));
+            quote!(#ident :: #id => #name,)
         } else {
             let name = id.to_string();
-            value_arms.push(quote!(#ident :: #id => #name,));
-        }
+            quote!(#ident :: #id => #name,)
+        };
+
+        value_arms.push(arm.clone());
+        size_hint_arms.push(arm);
     }
 
     Ok(quote!(
@@ -170,6 +193,7 @@ fn expand_derive_encode_strong_enum(
         impl<'q, DB: ::sqlx::Database> ::sqlx::encode::Encode<'q, DB> for #ident
         where
             &'q ::std::primitive::str: ::sqlx::encode::Encode<'q, DB>,
+            #other_where
         {
             fn encode_by_ref(
                 &self,
@@ -184,7 +208,7 @@ fn expand_derive_encode_strong_enum(
 
             fn size_hint(&self) -> ::std::primitive::usize {
                 let val = match self {
-                    #(#value_arms)*
+                    #(#size_hint_arms)*
                 };
 
                 <&::std::primitive::str as ::sqlx::encode::Encode<'q, DB>>::size_hint(&val)
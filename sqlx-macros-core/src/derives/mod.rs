@@ -12,7 +12,8 @@ pub use row::expand_derive_from_row;
 use self::attributes::RenameAll;
 use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::TokenStream;
-use syn::DeriveInput;
+use quote::quote;
+use syn::{Data, DataStruct, DeriveInput, Field, Fields, Member, Type};
 
 pub fn expand_derive_type_encode_decode(input: &DeriveInput) -> syn::Result<TokenStream> {
     let encode_tts = expand_derive_encode(input)?;
@@ -35,3 +36,85 @@ pub(crate) fn rename_all(s: &str, pattern: RenameAll) -> String {
         RenameAll::PascalCase => s.to_upper_camel_case(),
     }
 }
+
+/// The wrapped field of a newtype struct.
+///
+/// Besides the wrapped field, a newtype may contain any number of `PhantomData` fields, e.g. to
+/// tag an id with the table it belongs to:
+///
+/// ```rust,ignore
+/// #[derive(sqlx::Type)]
+/// #[sqlx(transparent)]
+/// struct Id<T>(i64, PhantomData<T>);
+/// ```
+pub(crate) struct Newtype<'a> {
+    pub field: &'a Field,
+    pub member: Member,
+    fields: &'a Fields,
+}
+
+impl<'a> Newtype<'a> {
+    /// Tuple structs with a single field (besides markers) are always newtypes, structs with
+    /// named fields only if they are `#[sqlx(transparent)]`.
+    pub fn from_input(input: &'a DeriveInput, transparent: bool) -> Option<Self> {
+        let Data::Struct(DataStruct { fields, .. }) = &input.data else {
+            return None;
+        };
+
+        if matches!(fields, Fields::Named(_)) && !transparent {
+            return None;
+        }
+
+        let mut wrapped = fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| !is_phantom_data(&field.ty));
+
+        let (index, field) = wrapped.next()?;
+
+        if wrapped.next().is_some() {
+            return None;
+        }
+
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(index.into()),
+        };
+
+        Some(Newtype {
+            field,
+            member,
+            fields,
+        })
+    }
+
+    /// An expression constructing the newtype from `value`.
+    pub fn construct(&self, value: TokenStream) -> TokenStream {
+        let values = self.fields.iter().map(|field| {
+            if std::ptr::eq(field, self.field) {
+                value.clone()
+            } else {
+                quote!(::std::marker::PhantomData)
+            }
+        });
+
+        match self.fields {
+            Fields::Named(_) => {
+                let names = self.fields.iter().map(|field| &field.ident);
+                quote!(Self { #(#names: #values),* })
+            }
+            _ => quote!(Self(#(#values),*)),
+        }
+    }
+}
+
+fn is_phantom_data(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
@@ -1,8 +1,8 @@
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{
     parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Expr, Field,
-    Fields, FieldsNamed, FieldsUnnamed, Lifetime, Stmt,
+    Fields, FieldsNamed, FieldsUnnamed, Lifetime, Stmt, Type,
 };
 
 use super::{
@@ -74,6 +74,9 @@ fn expand_derive_from_row_struct(
         None
     };
 
+    // The column names read by each field, for `deny_unknown_columns`.
+    let mut columns = Vec::new();
+
     let reads: Vec<Stmt> = fields
         .iter()
         .filter_map(|field| -> Option<Stmt> {
@@ -97,6 +100,8 @@ fn expand_derive_from_row_struct(
                 }
             };
 
+            columns.push(id_s.clone());
+
             let expr: Expr = match (attributes.flatten, attributes.try_from, attributes.json) {
                 // <No attributes>
                 (false, None, false) => {
@@ -185,6 +190,16 @@ fn expand_derive_from_row_struct(
                 },
             };
 
+            // a flattened struct names its own fields
+            let expr: Expr = if attributes.flatten {
+                expr
+            } else {
+                let field = format!("{ident}.{}", id.to_string().trim_start_matches("r#"));
+                let type_name = type_name(ty);
+
+                parse_quote!(#expr.map_err(|e| e.__into_field_decode(#field, #type_name)))
+            };
+
             if attributes.default {
                 Some(parse_quote!(
                     let #id: #ty = #expr.or_else(|e| match e {
@@ -211,6 +226,33 @@ fn expand_derive_from_row_struct(
         })
         .collect();
 
+    let deny_unknown_columns: Option<Stmt> = if container_attributes.deny_unknown_columns {
+        if let Some(field) = fields
+            .iter()
+            .find(|field| parse_child_attributes(&field.attrs).map_or(false, |a| a.flatten))
+        {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`flatten` cannot be used with `deny_unknown_columns`",
+            ));
+        }
+
+        Some(parse_quote!(
+            for __column in ::sqlx::Row::columns(__row) {
+                let __name = ::sqlx::Column::name(__column);
+                let __mapped: &[&::std::primitive::str] = &[#(#columns),*];
+
+                if !__mapped.contains(&__name) {
+                    return ::std::result::Result::Err(::sqlx::Error::ColumnNotMapped(
+                        ::std::string::ToString::to_string(__name),
+                    ));
+                }
+            }
+        ))
+    } else {
+        None
+    };
+
     let (impl_generics, _, where_clause) = generics.split_for_impl();
 
     let names = fields.iter().map(|field| &field.ident);
@@ -219,6 +261,8 @@ fn expand_derive_from_row_struct(
         #[automatically_derived]
         impl #impl_generics ::sqlx::FromRow<#lifetime, R> for #ident #ty_generics #where_clause {
             fn from_row(__row: &#lifetime R) -> ::sqlx::Result<Self> {
+                #deny_unknown_columns
+
                 #default_instance
 
                 #(#reads)*
@@ -269,10 +313,12 @@ fn expand_derive_from_row_struct_unnamed(
 
     let (impl_generics, _, where_clause) = generics.split_for_impl();
 
-    let gets = fields
-        .iter()
-        .enumerate()
-        .map(|(idx, _)| quote!(row.try_get(#idx)?));
+    let gets = fields.iter().enumerate().map(|(idx, field)| {
+        let field_s = format!("{ident}.{idx}");
+        let type_name = type_name(&field.ty);
+
+        quote!(row.try_get(#idx).map_err(|e| e.__into_field_decode(#field_s, #type_name))?)
+    });
 
     Ok(quote!(
         #[automatically_derived]
@@ -285,3 +331,27 @@ fn expand_derive_from_row_struct_unnamed(
         }
     ))
 }
+
+/// The type as it would be written, rather than with a space between every token.
+fn type_name(ty: &Type) -> String {
+    let mut name = ty.to_token_stream().to_string();
+
+    for (spaced, joined) in [
+        (" < ", "<"),
+        (" <", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (" :: ", "::"),
+        (":: ", "::"),
+        ("& ", "&"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ;", ";"),
+        ("( ", "("),
+        (" )", ")"),
+    ] {
+        name = name.replace(spaced, joined);
+    }
+
+    name
+}
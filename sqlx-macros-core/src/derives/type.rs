@@ -2,33 +2,33 @@ use super::attributes::{
     check_strong_enum_attributes, check_struct_attributes, check_transparent_attributes,
     check_weak_enum_attributes, parse_container_attributes, TypeName,
 };
+use super::Newtype;
 use proc_macro2::{Ident, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
 use syn::{
-    parse_quote, Data, DataEnum, DataStruct, DeriveInput, Field, Fields, FieldsNamed,
-    FieldsUnnamed, Variant,
+    parse_quote, Data, DataEnum, DataStruct, DeriveInput, Field, Fields, FieldsNamed, Variant,
 };
 
 pub fn expand_derive_type(input: &DeriveInput) -> syn::Result<TokenStream> {
     let attrs = parse_container_attributes(&input.attrs)?;
+
+    // Newtype structs:
+    // struct Foo(i32);
+    // struct Foo<T>(i32, PhantomData<T>);
+    if let Some(newtype) = Newtype::from_input(input, attrs.transparent) {
+        return expand_derive_has_sql_type_transparent(input, newtype.field);
+    }
+
     match &input.data {
-        // Newtype structs:
-        // struct Foo(i32);
         Data::Struct(DataStruct {
-            fields: Fields::Unnamed(FieldsUnnamed { unnamed, .. }),
+            fields: Fields::Unnamed(..),
             ..
-        }) => {
-            if unnamed.len() == 1 {
-                expand_derive_has_sql_type_transparent(input, unnamed.first().unwrap())
-            } else {
-                Err(syn::Error::new_spanned(
-                    input,
-                    "structs with zero or more than one unnamed field are not supported",
-                ))
-            }
-        }
+        }) => Err(syn::Error::new_spanned(
+            input,
+            "structs with zero or more than one unnamed field (besides `PhantomData`) are not supported",
+        )),
         // Record types
         // struct Foo { foo: i32, bar: String }
         Data::Struct(DataStruct {
@@ -105,6 +105,10 @@ fn expand_derive_has_sql_type_transparent(
                     fn array_type_info() -> ::sqlx::postgres::PgTypeInfo {
                         <#ty as ::sqlx::postgres::PgHasArrayType>::array_type_info()
                     }
+
+                    fn array_compatible(ty: &::sqlx::postgres::PgTypeInfo) -> ::std::primitive::bool {
+                        <#ty as ::sqlx::postgres::PgHasArrayType>::array_compatible(ty)
+                    }
                 }
             ));
         }
@@ -116,10 +120,13 @@ fn expand_derive_has_sql_type_transparent(
 
     if cfg!(feature = "postgres") {
         let ty_name = type_name(ident, attr.type_name.as_ref());
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
 
         tts.extend(quote!(
             #[automatically_derived]
-            impl ::sqlx::Type<::sqlx::postgres::Postgres> for #ident #ty_generics {
+            impl #impl_generics ::sqlx::Type<::sqlx::postgres::Postgres> for #ident #ty_generics
+            #where_clause
+            {
                 fn type_info() -> ::sqlx::postgres::PgTypeInfo {
                     ::sqlx::postgres::PgTypeInfo::with_name(#ty_name)
                 }
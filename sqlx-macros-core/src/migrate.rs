@@ -7,7 +7,8 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens, TokenStreamExt};
 use syn::LitStr;
 
-use sqlx_core::migrate::{Migration, MigrationType};
+use sqlx_core::config::Config;
+use sqlx_core::migrate::{Migration, MigrationType, Migrator};
 
 pub struct QuoteMigrationType(MigrationType);
 
@@ -119,11 +120,46 @@ pub(crate) fn expand_migrator(path: &Path) -> crate::Result<TokenStream> {
         proc_macro::tracked_path::path(path);
     }
 
+    let config = Config::try_from_crate().map_err(|e| e.to_string())?;
+
+    let schema = match config.migrate.schema {
+        Some(schema) => {
+            // check the name the same way as `Migrator::set_schema()` at runtime
+            let mut migrator = Migrator::DEFAULT;
+            migrator
+                .set_schema(schema.clone())
+                .map_err(|e| format!("`migrate.schema` in sqlx.toml: {e}"))?;
+
+            Some(quote! {
+                schema: ::std::option::Option::Some(::std::borrow::Cow::Borrowed(#schema)),
+            })
+        }
+        None => None,
+    };
+
+    let statement_timeout = match config.migrate.statement_timeout {
+        Some(Some(timeout)) => {
+            let (secs, nanos) = (timeout.as_secs(), timeout.subsec_nanos());
+
+            Some(quote! {
+                statement_timeout: ::std::option::Option::Some(
+                    ::std::time::Duration::new(#secs, #nanos)
+                ),
+            })
+        }
+        Some(None) => Some(quote! {
+            statement_timeout: ::std::option::Option::None,
+        }),
+        None => None,
+    };
+
     Ok(quote! {
         ::sqlx::migrate::Migrator {
             migrations: ::std::borrow::Cow::Borrowed(&[
                     #(#migrations),*
             ]),
+            #schema
+            #statement_timeout
             ..::sqlx::migrate::Migrator::DEFAULT
         }
     })
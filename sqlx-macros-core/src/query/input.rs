@@ -30,6 +30,8 @@ pub enum RecordType {
     Given(Type),
     Scalar,
     Generated,
+    /// A public struct with this name is generated in place of the query, for `query_as_named!()`
+    Named(Ident),
 }
 
 impl Parse for QueryMacroInput {
@@ -69,6 +71,16 @@ impl Parse for QueryMacroInput {
                 }
 
                 record_type = RecordType::Given(input.parse()?);
+            } else if key == "record_name" {
+                if !matches!(record_type, RecordType::Generated) {
+                    return Err(input.error("colliding `scalar` or `record` key"));
+                }
+
+                let lit_str = input.parse::<LitStr>()?;
+                let ident = syn::parse_str::<Ident>(&lit_str.value()).map_err(|_| {
+                    syn::Error::new_spanned(&lit_str, "expected the name of a struct")
+                })?;
+                record_type = RecordType::Named(Ident::new(&ident.to_string(), lit_str.span()));
             } else if key == "scalar" {
                 if !matches!(record_type, RecordType::Generated) {
                     return Err(input.error("colliding `scalar` or `record` key"));
@@ -8,6 +8,7 @@ use syn::Type;
 
 pub use input::QueryMacroInput;
 use quote::{format_ident, quote};
+use sqlx_core::config::Config;
 use sqlx_core::database::Database;
 use sqlx_core::{column::Column, describe::Describe, type_info::TypeInfo};
 
@@ -72,7 +73,12 @@ struct Metadata {
     #[allow(unused)]
     manifest_dir: PathBuf,
     offline: bool,
+    // `common.database-url-var` in `sqlx.toml`, `DATABASE_URL` by default
+    database_url_var: String,
     database_url: Option<String>,
+    // the variable is set in both the environment and `.env`, to different values
+    database_url_conflict: Option<String>,
+    execute_on_any: bool,
     workspace_root: Arc<Mutex<Option<PathBuf>>>,
 }
 
@@ -108,20 +114,32 @@ impl Metadata {
 
 // If we are in a workspace, lookup `workspace_root` since `CARGO_MANIFEST_DIR` won't
 // reflect the workspace dir: https://github.com/rust-lang/cargo/issues/3946
-static METADATA: Lazy<Metadata> = Lazy::new(|| {
+static METADATA: Lazy<Result<Metadata, String>> = Lazy::new(load_metadata);
+
+fn metadata() -> crate::Result<&'static Metadata> {
+    METADATA.as_ref().map_err(|e| e.clone().into())
+}
+
+fn load_metadata() -> Result<Metadata, String> {
     let manifest_dir: PathBuf = env("CARGO_MANIFEST_DIR")
-        .expect("`CARGO_MANIFEST_DIR` must be set")
+        .map_err(|_| "`CARGO_MANIFEST_DIR` is not set; please use Cargo to build")?
         .into();
 
+    let config = Config::try_from_crate().map_err(|e| e.to_string())?;
+    let database_url_var = config.common.database_url_var().to_owned();
+
+    // Read before `.env` is loaded, which doesn't override variables that are already set;
+    // which of the two is used is up to `common.database-url-precedence`, as in `sqlx-cli`.
+    let env_database_url = env(&database_url_var).ok();
+
     // If a .env file exists at CARGO_MANIFEST_DIR, load environment variables from this,
     // otherwise fallback to default dotenv behaviour.
     let env_path = manifest_dir.join(".env");
 
-    #[cfg_attr(not(procmacro2_semver_exempt), allow(unused_variables))]
     let env_path = if env_path.exists() {
         let res = dotenvy::from_path(&env_path);
         if let Err(e) = res {
-            panic!("failed to load environment from {env_path:?}, {e}");
+            return Err(format!("failed to load environment from {env_path:?}, {e}"));
         }
 
         Some(env_path)
@@ -139,21 +157,57 @@ static METADATA: Lazy<Metadata> = Lazy::new(|| {
         .map(|s| s.eq_ignore_ascii_case("true") || s == "1")
         .unwrap_or(false);
 
-    let database_url = env("DATABASE_URL").ok();
+    let dotenv_database_url = env_path.as_ref().and_then(|path| {
+        dotenvy::from_path_iter(path)
+            .ok()?
+            .filter_map(Result::ok)
+            .find(|(key, _)| *key == database_url_var)
+            .map(|(_, value)| value)
+    });
+
+    let (database_url, database_url_conflict) = config.common.database_url_precedence.choose(
+        &database_url_var,
+        env_database_url,
+        dotenv_database_url,
+    )?;
+
+    // an unrecognized value is an error rather than `false`, as it changes the generated code
+    let execute_on_any = match env("SQLX_EXECUTE_ON_ANY").ok().as_deref() {
+        None | Some("" | "0") => false,
+        Some("1") => true,
+        Some(s) if s.eq_ignore_ascii_case("false") => false,
+        Some(s) if s.eq_ignore_ascii_case("true") => true,
+        Some(s) => {
+            return Err(format!("`SQLX_EXECUTE_ON_ANY` must be `true` or `false`, got {s:?}"));
+        }
+    };
 
-    Metadata {
+    Ok(Metadata {
         manifest_dir,
         offline,
+        database_url_var,
         database_url,
+        database_url_conflict,
+        execute_on_any,
         workspace_root: Arc::new(Mutex::new(None)),
-    }
-});
+    })
+}
+
+/// Whether the queries are generated for the `Any` driver, instead of the driver they are
+/// checked against (`SQLX_EXECUTE_ON_ANY`).
+pub(crate) fn execute_on_any() -> bool {
+    METADATA
+        .as_ref()
+        .map_or(false, |metadata| metadata.execute_on_any)
+}
 
 pub fn expand_input<'a>(
     input: QueryMacroInput,
     drivers: impl IntoIterator<Item = &'a QueryDriver>,
 ) -> crate::Result<TokenStream> {
-    let data_source = match &*METADATA {
+    let metadata = metadata()?;
+
+    let data_source = match metadata {
         Metadata {
             offline: false,
             database_url: Some(db_url),
@@ -165,10 +219,10 @@ pub fn expand_input<'a>(
             let filename = format!("query-{}.json", hash_string(&input.sql));
 
             // Check SQLX_OFFLINE_DIR, then local .sqlx, then workspace .sqlx.
-            let dirs = [
-                || env("SQLX_OFFLINE_DIR").ok().map(PathBuf::from),
-                || Some(METADATA.manifest_dir.join(".sqlx")),
-                || Some(METADATA.workspace_root().join(".sqlx")),
+            let dirs: [&dyn Fn() -> Option<PathBuf>; 3] = [
+                &|| env("SQLX_OFFLINE_DIR").ok().map(PathBuf::from),
+                &|| Some(metadata.manifest_dir.join(".sqlx")),
+                &|| Some(metadata.workspace_root().join(".sqlx")),
             ];
             let Some(data_file_path) = dirs
                 .iter()
@@ -178,9 +232,9 @@ pub fn expand_input<'a>(
             else {
                 return Err(
                     if *offline {
-                        "`SQLX_OFFLINE=true` but there is no cached data for this query, run `cargo sqlx prepare` to update the query cache or unset `SQLX_OFFLINE`"
+                        "`SQLX_OFFLINE=true` but there is no cached data for this query, run `cargo sqlx prepare` to update the query cache or unset `SQLX_OFFLINE`".into()
                     } else {
-                        "set `DATABASE_URL` to use query macros online, or run `cargo sqlx prepare` to update the query cache"
+                        format!("set `{}` to use query macros online, or run `cargo sqlx prepare` to update the query cache", metadata.database_url_var)
                     }.into()
                 );
             };
@@ -246,6 +300,26 @@ fn expand_with_data<DB: DatabaseExt>(
 where
     Describe<DB>: DescribeExt,
 {
+    let metadata = metadata()?;
+
+    let warnings = match &metadata.database_url_conflict {
+        Some(conflict) if !offline => quote_warning(conflict),
+        _ => TokenStream::new(),
+    };
+
+    // the struct doesn't include the query, so it needs no arguments
+    if let RecordType::Named(ref name) = input.record_type {
+        let columns = output::columns_to_rust::<DB>(&data.describe)?;
+        let mut ret_tokens = output::quote_named_record::<DB>(&input, name, &columns)?;
+        ret_tokens.extend(warnings);
+
+        if !offline {
+            store_query_data(&data)?;
+        }
+
+        return Ok(ret_tokens);
+    }
+
     // validate at the minimum that our args match the query's input parameters
     let num_parameters = match data.describe.parameters() {
         Some(Either::Left(params)) => Some(params.len()),
@@ -324,11 +398,14 @@ where
             RecordType::Scalar => {
                 output::quote_query_scalar::<DB>(&input, &query_args, &data.describe)?
             }
+            RecordType::Named(_) => unreachable!("handled above"),
         }
     };
 
     let ret_tokens = quote! {
         {
+            #warnings
+
             #[allow(clippy::all)]
             {
                 use ::sqlx::Arguments as _;
@@ -343,36 +420,59 @@ where
     // Store query metadata only if offline support is enabled but the current build is online.
     // If the build is offline, the cache is our input so it's pointless to also write data for it.
     if !offline {
-        // Only save query metadata if SQLX_OFFLINE_DIR is set manually or by `cargo sqlx prepare`.
-        // Note: in a cargo workspace this path is relative to the root.
-        if let Ok(dir) = env("SQLX_OFFLINE_DIR") {
-            let path = PathBuf::from(&dir);
-
-            match fs::metadata(&path) {
-                Err(e) => {
-                    if e.kind() != io::ErrorKind::NotFound {
-                        // Can't obtain information about .sqlx
-                        return Err(format!("{e}: {dir}").into());
-                    }
-                    // .sqlx doesn't exist.
-                    return Err(format!("sqlx offline path does not exist: {dir}").into());
-                }
-                Ok(meta) => {
-                    if !meta.is_dir() {
-                        return Err(format!(
-                            "sqlx offline path exists, but is not a directory: {dir}"
-                        )
-                        .into());
-                    }
+        store_query_data(&data)?;
+    }
 
-                    // .sqlx exists and is a directory, store data.
-                    data.save_in(path)?;
+    Ok(ret_tokens)
+}
+
+/// An item that makes the compiler emit `message` as a warning, as proc macros can't do so
+/// directly on stable.
+fn quote_warning(message: &str) -> TokenStream {
+    quote! {
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(dead_code)]
+            struct Warning;
+
+            let _ = Warning;
+        };
+    }
+}
+
+fn store_query_data<DB: DatabaseExt>(data: &QueryData<DB>) -> crate::Result<()>
+where
+    Describe<DB>: DescribeExt,
+{
+    // Only save query metadata if SQLX_OFFLINE_DIR is set manually or by `cargo sqlx prepare`.
+    // Note: in a cargo workspace this path is relative to the root.
+    if let Ok(dir) = env("SQLX_OFFLINE_DIR") {
+        let path = PathBuf::from(&dir);
+
+        match fs::metadata(&path) {
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    // Can't obtain information about .sqlx
+                    return Err(format!("{e}: {dir}").into());
                 }
+                // .sqlx doesn't exist.
+                return Err(format!("sqlx offline path does not exist: {dir}").into());
+            }
+            Ok(meta) => {
+                if !meta.is_dir() {
+                    return Err(format!(
+                        "sqlx offline path exists, but is not a directory: {dir}"
+                    )
+                    .into());
+                }
+
+                // .sqlx exists and is a directory, store data.
+                data.save_in(path)?;
             }
         }
     }
 
-    Ok(ret_tokens)
+    Ok(())
 }
 
 /// Get the value of an environment variable, telling the compiler about it if applicable.
@@ -191,6 +191,54 @@ pub fn quote_query_as<DB: DatabaseExt>(
     }
 }
 
+/// A public struct named `name` with the columns of the query as fields, and its `FromRow` impl.
+pub fn quote_named_record<DB: DatabaseExt>(
+    input: &QueryMacroInput,
+    name: &Ident,
+    columns: &[RustColumn],
+) -> crate::Result<TokenStream> {
+    if columns.is_empty() {
+        return Err(syn::Error::new(input.src_span, "query does not return any columns").into());
+    }
+
+    if columns.iter().any(|col| col.type_.is_wildcard()) {
+        return Err(syn::Error::new(
+            input.src_span,
+            "wildcard overrides are not allowed in `query_as_named!()`",
+        )
+        .into());
+    }
+
+    let ident = columns.iter().map(|col| &col.ident);
+    let type_ = columns.iter().map(|col| &col.type_);
+    let index = 0..columns.len();
+
+    let record_fields = columns
+        .iter()
+        .map(|RustColumn { ident, type_, .. }| quote!(pub #ident: #type_,));
+
+    let row_path = DB::row_path();
+
+    Ok(quote! {
+        #[derive(Debug)]
+        #[allow(non_snake_case)]
+        pub struct #name {
+            #(#record_fields)*
+        }
+
+        // Columns are decoded by position, as their names may contain overrides.
+        impl<'r> ::sqlx::FromRow<'r, #row_path> for #name {
+            fn from_row(row: &'r #row_path) -> ::sqlx::Result<Self> {
+                use ::sqlx::Row as _;
+
+                ::std::result::Result::Ok(#name {
+                    #(#ident: row.try_get::<#type_, _>(#index)?,)*
+                })
+            }
+        }
+    })
+}
+
 pub fn quote_query_scalar<DB: DatabaseExt>(
     input: &QueryMacroInput,
     bind_args: &Ident,
@@ -144,7 +144,12 @@ fn expand_advanced(args: AttributeArgs, input: syn::ItemFn) -> crate::Result<Tok
     let migrations = match args.migrations {
         MigrationsOpt::ExplicitPath(path) => {
             let migrator = crate::migrate::expand_migrator_from_lit_dir(path)?;
-            quote! { args.migrator(&#migrator); }
+            quote! {
+                args.migrator({
+                    static MIGRATOR: ::sqlx::migrate::Migrator = #migrator;
+                    &MIGRATOR
+                });
+            }
         }
         MigrationsOpt::InferredPath if !inputs.is_empty() => {
             let migrations_path =
@@ -152,7 +157,12 @@ fn expand_advanced(args: AttributeArgs, input: syn::ItemFn) -> crate::Result<Tok
 
             if migrations_path.is_dir() {
                 let migrator = crate::migrate::expand_migrator(&migrations_path)?;
-                quote! { args.migrator(&#migrator); }
+                quote! {
+                    args.migrator({
+                        static MIGRATOR: ::sqlx::migrate::Migrator = #migrator;
+                        &MIGRATOR
+                    });
+                }
             } else {
                 quote! {}
             }
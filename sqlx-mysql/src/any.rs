@@ -14,6 +14,7 @@ use sqlx_core::any::{
 use sqlx_core::connection::Connection;
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
+use sqlx_core::query::QueryStatsCounters;
 use sqlx_core::executor::Executor;
 use sqlx_core::transaction::TransactionManager;
 use std::future;
@@ -25,6 +26,10 @@ impl AnyConnectionBackend for MySqlConnection {
         <MySql as Database>::NAME
     }
 
+    fn id(&self) -> u64 {
+        Connection::id(self)
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -37,6 +42,33 @@ impl AnyConnectionBackend for MySqlConnection {
         Connection::ping(self)
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::reset(self)
+    }
+
+    fn rollback_open_transaction(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::rollback_open_transaction(self)
+    }
+
+    fn server_max_connections(&mut self) -> BoxFuture<'_, sqlx_core::Result<Option<u32>>> {
+        Connection::server_max_connections(self)
+    }
+
+    fn queries_executed(&self) -> u64 {
+        Connection::queries_executed(self)
+    }
+
+    fn query_stats_counters<'a>(
+        &'a mut self,
+        sql: &'a str,
+    ) -> BoxFuture<'a, sqlx_core::Result<QueryStatsCounters>> {
+        Connection::query_stats_counters(self, sql)
+    }
+
+    fn server_now_micros(&mut self) -> BoxFuture<'_, sqlx_core::Result<i64>> {
+        Connection::server_now_micros(self)
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         MySqlTransactionManager::begin(self)
     }
@@ -65,6 +97,10 @@ impl AnyConnectionBackend for MySqlConnection {
         Connection::should_flush(self)
     }
 
+    fn set_require_transaction_for_writes(&mut self, require: bool) {
+        Connection::set_require_transaction_for_writes(self, require);
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
@@ -156,8 +192,9 @@ impl<'a> TryFrom<&'a MySqlTypeInfo> for AnyTypeInfo {
         Ok(AnyTypeInfo {
             kind: match &type_info.r#type {
                 ColumnType::Null => AnyTypeInfoKind::Null,
-                ColumnType::Short => AnyTypeInfoKind::SmallInt,
-                ColumnType::Long => AnyTypeInfoKind::Integer,
+                // `BOOLEAN` is an alias of `TINYINT(1)`
+                ColumnType::Tiny | ColumnType::Short => AnyTypeInfoKind::SmallInt,
+                ColumnType::Int24 | ColumnType::Long => AnyTypeInfoKind::Integer,
                 ColumnType::LongLong => AnyTypeInfoKind::BigInt,
                 ColumnType::Float => AnyTypeInfoKind::Real,
                 ColumnType::Double => AnyTypeInfoKind::Double,
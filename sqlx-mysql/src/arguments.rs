@@ -11,6 +11,8 @@ pub struct MySqlArguments {
     pub(crate) values: Vec<u8>,
     pub(crate) types: Vec<MySqlTypeInfo>,
     pub(crate) null_bitmap: NullBitMap,
+    // offset of each value in `values`
+    pub(crate) offsets: Vec<usize>,
 }
 
 impl MySqlArguments {
@@ -32,9 +34,30 @@ impl MySqlArguments {
 
         self.types.push(ty);
         self.null_bitmap.push(is_null);
+        self.offsets.push(value_length_before_encoding);
 
         Ok(())
     }
+
+    /// The encoded value of each argument, or `None` if it is `NULL`.
+    pub(crate) fn encoded_values(&self) -> impl Iterator<Item = Option<&[u8]>> {
+        let ends = self
+            .offsets
+            .iter()
+            .copied()
+            .skip(1)
+            .chain([self.values.len()]);
+
+        self.offsets
+            .iter()
+            .copied()
+            .zip(ends)
+            .enumerate()
+            .map(|(i, (start, end))| {
+                let is_null = self.null_bitmap[i / 8] & (1 << (i % 8)) != 0;
+                (!is_null).then(|| &self.values[start..end])
+            })
+    }
 }
 
 impl<'q> Arguments<'q> for MySqlArguments {
@@ -42,6 +65,7 @@ impl<'q> Arguments<'q> for MySqlArguments {
 
     fn reserve(&mut self, len: usize, size: usize) {
         self.types.reserve(len);
+        self.offsets.reserve(len);
         self.values.reserve(size);
     }
 
@@ -55,6 +79,30 @@ impl<'q> Arguments<'q> for MySqlArguments {
     fn len(&self) -> usize {
         self.types.len()
     }
+
+    fn try_clone(&self) -> Option<Self> {
+        Some(self.clone())
+    }
+
+    fn to_cache_key(&self) -> Option<Vec<u8>> {
+        let mut key = Vec::with_capacity(self.values.len() + 12 * self.types.len());
+
+        for (ty, value) in self.types.iter().zip(self.encoded_values()) {
+            key.push(ty.r#type as u8);
+            key.extend_from_slice(&ty.flags.bits().to_le_bytes());
+
+            match value {
+                Some(value) => {
+                    key.push(1);
+                    key.extend_from_slice(&(value.len() as u64).to_le_bytes());
+                    key.extend_from_slice(value);
+                }
+                None => key.push(0),
+            }
+        }
+
+        Some(key)
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -105,4 +153,20 @@ mod test {
 
         assert_eq!([0b01010101, 0b1].as_slice(), bit_map.deref());
     }
+
+    #[test]
+    fn encoded_values_should_split_arguments() {
+        let mut arguments = MySqlArguments::default();
+
+        arguments.add(1_i32).unwrap();
+        arguments.add(None::<i64>).unwrap();
+        arguments.add(0x0102_i16).unwrap();
+
+        let values: Vec<_> = arguments.encoded_values().collect();
+
+        assert_eq!(
+            values,
+            [Some([1, 0, 0, 0].as_slice()), None, Some([2, 1].as_slice())]
+        );
+    }
 }
@@ -24,12 +24,25 @@ impl MySqlConnection {
 
         let stream = handshake.await?;
 
+        let mut log_settings = options.log_settings.clone();
+        log_settings.assign_connection_id();
+        log_settings.backslash_escapes = true;
+
         Ok(Self {
             inner: Box::new(MySqlConnectionInner {
                 stream,
                 transaction_depth: 0,
+                require_transaction_for_writes: false,
                 cache_statement: StatementCache::new(options.statement_cache_capacity),
-                log_settings: options.log_settings.clone(),
+                statements_prepared: 0,
+                statement_cache_hits: 0,
+                queries_executed: 0,
+                open_statements: 0,
+                duplicate_column_names: options.duplicate_column_names,
+                zero_date_handling: options.zero_date_handling,
+                lenient_integer_decode: options.lenient_integer_decode,
+                session_init_sql: None,
+                log_settings,
             }),
         })
     }
@@ -1,11 +1,12 @@
 use super::MySqlStream;
+use crate::column::DuplicateColumnNames;
 use crate::connection::stream::Waiting;
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::ext::ustr::UStr;
 use crate::io::MySqlBufExt;
-use crate::logger::QueryLogger;
+use crate::logger::{EncodedParameter, QueryLogger};
 use crate::protocol::response::Status;
 use crate::protocol::statement::{
     BinaryRow, Execute as StatementExecute, Prepare, PrepareOk, StmtClose,
@@ -21,7 +22,10 @@ use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
-use futures_util::{pin_mut, TryStreamExt};
+use futures_util::{future, pin_mut, TryStreamExt};
+use sqlx_core::arguments::Arguments;
+use sqlx_core::transaction::is_write_statement;
+use sqlx_core::type_info::TypeInfo;
 use std::{borrow::Cow, sync::Arc};
 
 impl MySqlConnection {
@@ -32,9 +36,12 @@ impl MySqlConnection {
         // https://dev.mysql.com/doc/internals/en/com-stmt-prepare.html
         // https://dev.mysql.com/doc/internals/en/com-stmt-prepare-response.html#packet-COM_STMT_PREPARE_OK
 
+        // see `ConnectOptions::sql_rewriter()`
+        let sql = self.inner.log_settings.rewrite_sql(sql);
+
         self.inner
             .stream
-            .send_packet(Prepare { query: sql })
+            .send_packet(Prepare { query: &sql })
             .await?;
 
         let ok: PrepareOk = self.inner.stream.recv().await?;
@@ -57,7 +64,13 @@ impl MySqlConnection {
         let mut columns = Vec::new();
 
         let column_names = if ok.columns > 0 {
-            recv_result_metadata(&mut self.inner.stream, ok.columns as usize, &mut columns).await?
+            recv_result_metadata(
+                &mut self.inner.stream,
+                ok.columns as usize,
+                &mut columns,
+                self.inner.duplicate_column_names,
+            )
+            .await?
         } else {
             Default::default()
         };
@@ -69,14 +82,31 @@ impl MySqlConnection {
             column_names: Arc::new(column_names),
         };
 
+        self.inner.statements_prepared += 1;
+        self.inner.open_statements += 1;
+
         Ok((id, metadata))
     }
 
+    pub(super) async fn close_statement(&mut self, id: u32) -> Result<(), Error> {
+        // https://dev.mysql.com/doc/internals/en/com-stmt-close.html
+        self.inner
+            .stream
+            .send_packet(StmtClose { statement: id })
+            .await?;
+
+        self.inner.open_statements -= 1;
+
+        Ok(())
+    }
+
     async fn get_or_prepare_statement<'c>(
         &mut self,
         sql: &str,
     ) -> Result<(u32, MySqlStatementMetadata), Error> {
         if let Some(statement) = self.inner.cache_statement.get_mut(sql) {
+            self.inner.statement_cache_hits += 1;
+
             // <MySqlStatementMetadata> is internally reference-counted
             return Ok((*statement).clone());
         }
@@ -89,15 +119,31 @@ impl MySqlConnection {
             .cache_statement
             .insert(sql, (id, metadata.clone()))
         {
-            self.inner
-                .stream
-                .send_packet(StmtClose { statement: id })
-                .await?;
+            self.close_statement(id).await?;
         }
 
         Ok((id, metadata))
     }
 
+    /// Check that `arguments` has a value for every parameter of the statement before executing it.
+    fn check_bind_count(
+        &mut self,
+        metadata: &MySqlStatementMetadata,
+        arguments: &MySqlArguments,
+    ) -> Result<(), Error> {
+        if metadata.parameters == arguments.len() {
+            return Ok(());
+        }
+
+        // The statement won't be executed, so there's no result to wait for.
+        self.inner.stream.waiting.pop_front();
+
+        Err(Error::BindCountMismatch {
+            expected: metadata.parameters,
+            provided: arguments.len(),
+        })
+    }
+
     #[allow(clippy::needless_lifetimes)]
     pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
@@ -106,9 +152,47 @@ impl MySqlConnection {
         persistent: bool,
     ) -> Result<impl Stream<Item = Result<Either<MySqlQueryResult, MySqlRow>, Error>> + 'e, Error>
     {
-        let mut logger = QueryLogger::new(sql, self.inner.log_settings.clone());
+        let steps = self.run_steps(sql, arguments, persistent).await?;
+
+        Ok(steps.try_filter_map(|step| {
+            future::ready(Ok(match step {
+                Step::ResultSet(_) => None,
+                Step::Row(row) => Some(Either::Right(row)),
+                Step::Done { result, .. } => Some(Either::Left(result)),
+            }))
+        }))
+    }
 
+    #[allow(clippy::needless_lifetimes)]
+    pub(crate) async fn run_steps<'e, 'c: 'e, 'q: 'e>(
+        &'c mut self,
+        sql: &'q str,
+        arguments: Option<MySqlArguments>,
+        persistent: bool,
+    ) -> Result<impl Stream<Item = Result<Step, Error>> + 'e, Error> {
         self.inner.stream.wait_until_ready().await?;
+
+        // see `PoolOptions::require_transaction_for_writes`
+        if self.inner.require_transaction_for_writes
+            && !self.inner.stream.status.contains(Status::SERVER_STATUS_IN_TRANS)
+            && is_write_statement(sql)
+        {
+            return Err(Error::TransactionRequired);
+        }
+
+        self.inner.queries_executed += 1;
+
+        let mut logger = QueryLogger::new(sql, self.inner.log_settings.clone());
+
+        if let Some(arguments) = &arguments {
+            logger.log_parameters(arguments.types.iter().zip(arguments.encoded_values()).map(
+                |(ty, value)| EncodedParameter {
+                    type_name: ty.name(),
+                    value,
+                },
+            ));
+        }
+
         self.inner.stream.waiting.push_back(Waiting::Result);
 
         Ok(Box::pin(try_stream! {
@@ -123,6 +207,8 @@ impl MySqlConnection {
                         .get_or_prepare_statement(sql)
                         .await?;
 
+                    self.check_bind_count(&metadata, &arguments)?;
+
                     // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
                     self.inner.stream
                         .send_packet(StatementExecute {
@@ -137,6 +223,11 @@ impl MySqlConnection {
                         .prepare_statement(sql)
                         .await?;
 
+                    if let Err(error) = self.check_bind_count(&metadata, &arguments) {
+                        self.close_statement(id).await?;
+                        return Err(error);
+                    }
+
                     // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
                     self.inner.stream
                         .send_packet(StatementExecute {
@@ -145,13 +236,14 @@ impl MySqlConnection {
                         })
                         .await?;
 
-                    self.inner.stream.send_packet(StmtClose { statement: id }).await?;
+                    self.close_statement(id).await?;
 
                     (metadata.column_names, MySqlValueFormat::Binary, false)
                 }
             } else {
                 // https://dev.mysql.com/doc/internals/en/com-query.html
-                self.inner.stream.send_packet(Query(sql)).await?;
+                let sql = self.inner.log_settings.rewrite_sql(sql);
+                self.inner.stream.send_packet(Query(&sql)).await?;
 
                 (Arc::default(), MySqlValueFormat::Text, true)
             };
@@ -165,6 +257,7 @@ impl MySqlConnection {
                     // first packet in a query response is OK or ERR
                     // this indicates either a successful query with no rows at all or a failed query
                     let ok = packet.ok()?;
+                    self.inner.stream.status = ok.status;
 
                     let rows_affected = ok.affected_rows;
                     logger.increase_rows_affected(rows_affected);
@@ -173,7 +266,7 @@ impl MySqlConnection {
                         last_insert_id: ok.last_insert_id,
                     };
 
-                    r#yield!(Either::Left(done));
+                    r#yield!(Step::Done { result: done, out_params: false });
 
                     if ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         // more result sets exist, continue to the next one
@@ -192,7 +285,7 @@ impl MySqlConnection {
                     .map_err(|_| err_protocol!("column count overflows usize: {num_columns}"))?;
 
                 if needs_metadata {
-                    column_names = Arc::new(recv_result_metadata(&mut self.inner.stream, num_columns, Arc::make_mut(&mut columns)).await?);
+                    column_names = Arc::new(recv_result_metadata(&mut self.inner.stream, num_columns, Arc::make_mut(&mut columns), self.inner.duplicate_column_names).await?);
                 } else {
                     // next time we hit here, it'll be a new result set and we'll need the
                     // full metadata
@@ -201,17 +294,23 @@ impl MySqlConnection {
                     recv_result_columns(&mut self.inner.stream, num_columns, Arc::make_mut(&mut columns)).await?;
                 }
 
+                r#yield!(Step::ResultSet(Arc::clone(&columns)));
+
                 // finally, there will be none or many result-rows
                 loop {
                     let packet = self.inner.stream.recv_packet().await?;
 
                     if packet[0] == 0xfe && packet.len() < 9 {
                         let eof = packet.eof(self.inner.stream.capabilities)?;
+                        self.inner.stream.status = eof.status;
 
-                        r#yield!(Either::Left(MySqlQueryResult {
-                            rows_affected: 0,
-                            last_insert_id: 0,
-                        }));
+                        r#yield!(Step::Done {
+                            result: MySqlQueryResult {
+                                rows_affected: 0,
+                                last_insert_id: 0,
+                            },
+                            out_params: eof.status.contains(Status::SERVER_PS_OUT_PARAMS),
+                        });
 
                         if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                             // more result sets exist, continue to the next one
@@ -228,9 +327,11 @@ impl MySqlConnection {
                         MySqlValueFormat::Text => packet.decode_with::<TextRow, _>(&columns)?.0,
                     };
 
-                    let v = Either::Right(MySqlRow {
+                    let v = Step::Row(MySqlRow {
                         row,
                         format,
+                        zero_dates: self.inner.zero_date_handling,
+                        lenient_integers: self.inner.lenient_integer_decode,
                         columns: Arc::clone(&columns),
                         column_names: Arc::clone(&column_names),
                     });
@@ -244,6 +345,19 @@ impl MySqlConnection {
     }
 }
 
+// A part of the response to a query, as read by `MySqlConnection::run_steps()`.
+pub(crate) enum Step {
+    // The start of a result set, with its columns.
+    ResultSet(Arc<Vec<MySqlColumn>>),
+    Row(MySqlRow),
+    // The end of a result set, or the result of a statement that returns no rows.
+    Done {
+        result: MySqlQueryResult,
+        // the result set holds the `OUT` parameters of a procedure called by a prepared statement
+        out_params: bool,
+    },
+}
+
 impl<'c> Executor<'c> for &'c mut MySqlConnection {
     type Database = MySql;
 
@@ -310,10 +424,7 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
             } else {
                 let (id, metadata) = self.prepare_statement(sql).await?;
 
-                self.inner
-                    .stream
-                    .send_packet(StmtClose { statement: id })
-                    .await?;
+                self.close_statement(id).await?;
 
                 metadata
             };
@@ -336,10 +447,7 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
 
             let (id, metadata) = self.prepare_statement(sql).await?;
 
-            self.inner
-                .stream
-                .send_packet(StmtClose { statement: id })
-                .await?;
+            self.close_statement(id).await?;
 
             let columns = (*metadata.columns).clone();
 
@@ -401,12 +509,11 @@ async fn recv_result_metadata(
     stream: &mut MySqlStream,
     num_columns: usize,
     columns: &mut Vec<MySqlColumn>,
+    duplicate_column_names: DuplicateColumnNames,
 ) -> Result<HashMap<UStr, usize>, Error> {
     // the result-set metadata is primarily a listing of each output
     // column in the result-set
 
-    let mut column_names = HashMap::with_capacity(num_columns);
-
     columns.clear();
     columns.reserve(num_columns);
 
@@ -415,11 +522,10 @@ async fn recv_result_metadata(
 
         let column = recv_next_result_column(&def, ordinal)?;
 
-        column_names.insert(column.name.clone(), ordinal);
         columns.push(column);
     }
 
     stream.maybe_recv_eof().await?;
 
-    Ok(column_names)
+    Ok(duplicate_column_names.column_names(columns.iter_mut().map(|column| &mut column.name)))
 }
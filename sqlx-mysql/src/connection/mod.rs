@@ -2,13 +2,18 @@ use std::fmt::{self, Debug, Formatter};
 
 use futures_core::future::BoxFuture;
 use futures_util::FutureExt;
+pub use procedure::MySqlResultSet;
 pub(crate) use sqlx_core::connection::*;
 pub(crate) use stream::{MySqlStream, Waiting};
 
+use crate::column::DuplicateColumnNames;
 use crate::common::StatementCache;
 use crate::error::Error;
-use crate::protocol::statement::StmtClose;
-use crate::protocol::text::{Ping, Quit};
+use crate::executor::Executor;
+use crate::options::ZeroDateHandling;
+use crate::protocol::text::{Ping, Quit, ResetConnection};
+use crate::query::QueryStatsCounters;
+use crate::row::Row;
 use crate::statement::MySqlStatementMetadata;
 use crate::transaction::Transaction;
 use crate::{MySql, MySqlConnectOptions};
@@ -16,6 +21,7 @@ use crate::{MySql, MySqlConnectOptions};
 mod auth;
 mod establish;
 mod executor;
+mod procedure;
 mod stream;
 mod tls;
 
@@ -35,10 +41,45 @@ pub(crate) struct MySqlConnectionInner {
     // transaction status
     pub(crate) transaction_depth: usize,
 
+    // see `PoolOptions::require_transaction_for_writes`
+    pub(crate) require_transaction_for_writes: bool,
+
     // cache by query string to the statement id and metadata
     cache_statement: StatementCache<(u32, MySqlStatementMetadata)>,
 
-    log_settings: LogSettings,
+    // number of statements prepared and statement cache hits over the life of the connection
+    statements_prepared: u64,
+    statement_cache_hits: u64,
+
+    // number of statements executed over the life of the connection
+    queries_executed: u64,
+
+    // number of prepared statements currently open on the server
+    open_statements: usize,
+
+    duplicate_column_names: DuplicateColumnNames,
+
+    zero_date_handling: ZeroDateHandling,
+
+    // see `ConnectOptions::lenient_integer_decode()`
+    lenient_integer_decode: bool,
+
+    // the `SET` statement run after connecting, run again by `Connection::reset()`
+    pub(crate) session_init_sql: Option<String>,
+
+    pub(crate) log_settings: LogSettings,
+}
+
+impl MySqlConnection {
+    /// The number of prepared statements this connection currently holds open on the server.
+    ///
+    /// This includes the statements in the cache (see
+    /// [`cached_statements_size()`][Connection::cached_statements_size]) and any that were
+    /// prepared outside of it and are still open. A count that keeps growing past the cache
+    /// capacity points to statements that are never closed.
+    pub fn prepared_statement_count(&self) -> usize {
+        self.inner.open_statements
+    }
 }
 
 impl Debug for MySqlConnection {
@@ -52,6 +93,10 @@ impl Connection for MySqlConnection {
 
     type Options = MySqlConnectOptions;
 
+    fn id(&self) -> u64 {
+        self.inner.log_settings.connection_id
+    }
+
     fn close(mut self) -> BoxFuture<'static, Result<(), Error>> {
         Box::pin(async move {
             self.inner.stream.send_packet(Quit).await?;
@@ -78,24 +123,123 @@ impl Connection for MySqlConnection {
         })
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.inner.stream.wait_until_ready().await?;
+            self.inner.stream.send_packet(ResetConnection).await?;
+            self.inner.stream.recv_ok().await?;
+
+            // The server rolled back any open transaction and deallocated all prepared statements.
+            self.inner.transaction_depth = 0;
+            self.inner.cache_statement.clear();
+            self.inner.open_statements = 0;
+
+            // Session variables were reset to their global values.
+            if let Some(sql) = self.inner.session_init_sql.clone() {
+                self.execute(&*sql).await?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn rollback_open_transaction(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            // outside of a transaction, this does nothing
+            self.execute("ROLLBACK").await?;
+            self.inner.transaction_depth = 0;
+
+            Ok(())
+        })
+    }
+
+    fn server_max_connections(&mut self) -> BoxFuture<'_, Result<Option<u32>, Error>> {
+        Box::pin(async move {
+            let row = self.fetch_one("SELECT @@max_connections").await?;
+
+            // `max_connections` is an unsigned integer, but its type is reported inconsistently
+            let max: u64 = row.try_get_unchecked(0)?;
+
+            Ok(Some(u32::try_from(max).unwrap_or(u32::MAX)))
+        })
+    }
+
+    #[doc(hidden)]
+    fn query_stats_counters<'a>(
+        &'a mut self,
+        sql: &'a str,
+    ) -> BoxFuture<'a, Result<QueryStatsCounters, Error>> {
+        let _ = sql;
+
+        Box::pin(async move {
+            let rows = self
+                .fetch_all(
+                    "SHOW SESSION STATUS WHERE Variable_name LIKE 'Handler\\_read\\_%' \
+                     OR Variable_name = 'Bytes_sent'",
+                )
+                .await?;
+
+            let mut counters = QueryStatsCounters::default();
+
+            for row in rows {
+                let name: String = row.try_get(0)?;
+                let value: String = row.try_get(1)?;
+
+                let value: u64 = value.parse().map_err(|_| {
+                    err_protocol!("expected {name} to be an integer, got {value:?}")
+                })?;
+
+                // the `Handler_read_*` counters together count the rows read by storage engines
+                let counter = if name == "Bytes_sent" {
+                    &mut counters.bytes_sent
+                } else {
+                    &mut counters.rows_examined
+                };
+
+                *counter = Some(counter.unwrap_or(0) + value);
+            }
+
+            Ok(counters)
+        })
+    }
+
+    #[doc(hidden)]
+    fn server_now_micros(&mut self) -> BoxFuture<'_, Result<i64, Error>> {
+        Box::pin(async move {
+            // `UNIX_TIMESTAMP()` converts from the session time zone, as `NOW()` returns it
+            let row = self
+                .fetch_one("SELECT CAST(UNIX_TIMESTAMP(NOW(6)) * 1000000 AS SIGNED)")
+                .await?;
+
+            row.try_get(0)
+        })
+    }
+
     #[doc(hidden)]
     fn flush(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         self.inner.stream.wait_until_ready().boxed()
     }
 
+    fn queries_executed(&self) -> u64 {
+        self.inner.queries_executed
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.inner.cache_statement.len()
     }
 
+    fn statements_prepared(&self) -> u64 {
+        self.inner.statements_prepared
+    }
+
+    fn statement_cache_hits(&self) -> u64 {
+        self.inner.statement_cache_hits
+    }
+
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             while let Some((statement_id, _)) = self.inner.cache_statement.remove_lru() {
-                self.inner
-                    .stream
-                    .send_packet(StmtClose {
-                        statement: statement_id,
-                    })
-                    .await?;
+                self.close_statement(statement_id).await?;
             }
 
             Ok(())
@@ -107,6 +251,10 @@ impl Connection for MySqlConnection {
         !self.inner.stream.write_buffer().is_empty()
     }
 
+    fn set_require_transaction_for_writes(&mut self, require: bool) {
+        self.inner.require_transaction_for_writes = require;
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -0,0 +1,150 @@
+use std::sync::Arc;
+
+use futures_core::stream::BoxStream;
+use futures_util::{pin_mut, TryStreamExt};
+
+use super::executor::Step;
+use crate::error::Error;
+use crate::executor::Execute;
+use crate::{MySql, MySqlColumn, MySqlConnection, MySqlQueryResult, MySqlRow};
+
+/// One of the results returned by [`MySqlConnection::call_procedure()`].
+///
+/// This is either a result set, with its columns and all of its rows, or the result of a
+/// statement that returns no rows, which has no columns.
+#[derive(Debug)]
+pub struct MySqlResultSet {
+    columns: Arc<Vec<MySqlColumn>>,
+    rows: Vec<MySqlRow>,
+    result: MySqlQueryResult,
+    out_params: bool,
+}
+
+impl MySqlResultSet {
+    /// The columns of the result set, which are known even if it has no rows.
+    ///
+    /// Empty for the result of a statement that returns no rows.
+    pub fn columns(&self) -> &[MySqlColumn] {
+        &self.columns
+    }
+
+    /// The rows of the result set.
+    pub fn rows(&self) -> &[MySqlRow] {
+        &self.rows
+    }
+
+    /// Take the rows of the result set.
+    pub fn into_rows(self) -> Vec<MySqlRow> {
+        self.rows
+    }
+
+    /// The number of rows affected, for the result of a statement that returns no rows.
+    ///
+    /// Always `0` for a result set.
+    pub fn rows_affected(&self) -> u64 {
+        self.result.rows_affected()
+    }
+
+    /// The last `AUTO_INCREMENT` id generated, for the result of a statement that returns no
+    /// rows.
+    ///
+    /// Always `0` for a result set.
+    pub fn last_insert_id(&self) -> u64 {
+        self.result.last_insert_id()
+    }
+
+    /// Returns `true` if this is the single row holding the values of the `OUT` and `INOUT`
+    /// parameters of the procedure.
+    ///
+    /// MySQL only returns these for a `CALL` that is run as a prepared statement, i.e. that
+    /// has arguments bound to it, with `?` placeholders for the `OUT` parameters.
+    pub fn is_out_params(&self) -> bool {
+        self.out_params
+    }
+}
+
+impl MySqlConnection {
+    /// Call a stored procedure, returning each of the result sets it produces, in order.
+    ///
+    /// The executor methods flatten these into a single stream of rows, and the `fetch_*`
+    /// methods other than [`fetch_many()`](crate::executor::Executor::fetch_many) only return
+    /// the rows of the first one. Here, each result set is returned with its own columns.
+    ///
+    /// A `CALL` always ends with the result of the `CALL` statement itself, which has no
+    /// columns and is therefore the only item for a procedure that returns no result sets.
+    /// If the `CALL` is run as a prepared statement, the values of `OUT` parameters are
+    /// returned in a result set of their own just before that; see
+    /// [`MySqlResultSet::is_out_params()`].
+    ///
+    /// Each result set is buffered in full before it is returned.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::MySqlConnection) -> sqlx::Result<()> {
+    /// use futures::TryStreamExt;
+    /// use sqlx::Column;
+    ///
+    /// let mut results = conn.call_procedure(sqlx::query("CALL order_summary(?, @total)").bind(42));
+    ///
+    /// while let Some(result) = results.try_next().await? {
+    ///     if let Some(column) = result.columns().first() {
+    ///         println!("{} rows of {}", result.rows().len(), column.name());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn call_procedure<'e, 'q: 'e, E>(
+        &'e mut self,
+        mut query: E,
+    ) -> BoxStream<'e, Result<MySqlResultSet, Error>>
+    where
+        E: 'q + Execute<'q, MySql>,
+    {
+        let sql = query.sql();
+        let arguments = query.take_arguments().map_err(Error::Encode);
+        let persistent = query.persistent();
+
+        Box::pin(try_stream! {
+            let arguments = arguments?;
+            let steps = self.run_steps(sql, arguments, persistent).await?;
+            pin_mut!(steps);
+
+            let mut current = None;
+
+            while let Some(step) = steps.try_next().await? {
+                match step {
+                    Step::ResultSet(columns) => {
+                        current = Some(MySqlResultSet {
+                            columns,
+                            rows: Vec::new(),
+                            result: MySqlQueryResult::default(),
+                            out_params: false,
+                        });
+                    }
+
+                    Step::Row(row) => {
+                        if let Some(result_set) = &mut current {
+                            result_set.rows.push(row);
+                        }
+                    }
+
+                    Step::Done { result, out_params } => {
+                        let mut result_set = current.take().unwrap_or_else(|| MySqlResultSet {
+                            columns: Arc::default(),
+                            rows: Vec::new(),
+                            result: MySqlQueryResult::default(),
+                            out_params: false,
+                        });
+
+                        result_set.result = result;
+                        result_set.out_params = out_params;
+
+                        r#yield!(result_set);
+                    }
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
@@ -22,6 +22,8 @@ pub struct MySqlStream<S = Box<dyn Socket>> {
     pub(crate) charset: CharSet,
     pub(crate) collation: Collation,
     pub(crate) is_tls: bool,
+    // server status of the last completed statement, e.g. whether a transaction is open
+    pub(crate) status: Status,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -57,6 +59,12 @@ impl<S: Socket> MySqlStream<S> {
             capabilities |= Capabilities::CONNECT_WITH_DB;
         }
 
+        #[allow(unused_mut)]
+        let mut socket = BufferedSocket::new(socket);
+
+        #[cfg(feature = "wire-trace")]
+        socket.set_wire_trace(options.log_settings.wire_trace.clone());
+
         Self {
             waiting: VecDeque::new(),
             capabilities,
@@ -64,8 +72,9 @@ impl<S: Socket> MySqlStream<S> {
             sequence_id: 0,
             collation,
             charset,
-            socket: BufferedSocket::new(socket),
+            socket,
             is_tls: false,
+            status: Status::empty(),
         }
     }
 
@@ -80,6 +89,7 @@ impl<S: Socket> MySqlStream<S> {
 
                 if !packet.is_empty() && packet[0] == 0xfe && packet.len() < 9 {
                     let eof = packet.eof(self.capabilities)?;
+                    self.status = eof.status;
 
                     if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         *self.waiting.front_mut().unwrap() = Waiting::Result;
@@ -94,6 +104,7 @@ impl<S: Socket> MySqlStream<S> {
 
                 if !packet.is_empty() && (packet[0] == 0x00 || packet[0] == 0xff) {
                     let ok = packet.ok()?;
+                    self.status = ok.status;
 
                     if !ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         self.waiting.pop_front();
@@ -132,6 +143,9 @@ impl<S: Socket> MySqlStream<S> {
 
         let mut header: Bytes = self.socket.read(4).await?;
 
+        #[cfg(feature = "wire-trace")]
+        let raw_header = header.clone();
+
         // cannot overflow
         #[allow(clippy::cast_possible_truncation)]
         let packet_size = header.get_uint_le(3) as usize;
@@ -141,6 +155,14 @@ impl<S: Socket> MySqlStream<S> {
 
         let payload: Bytes = self.socket.read(packet_size).await?;
 
+        #[cfg(feature = "wire-trace")]
+        if let Some(trace) = self.socket.wire_trace() {
+            trace.trace(
+                sqlx_core::wire_trace::Direction::Received,
+                &[&raw_header[..], &payload[..]].concat(),
+            );
+        }
+
         // TODO: packet compression
 
         Ok(payload)
@@ -192,7 +214,10 @@ impl<S: Socket> MySqlStream<S> {
     }
 
     pub(crate) async fn recv_ok(&mut self) -> Result<OkPacket, Error> {
-        self.recv_packet().await?.ok()
+        let ok = self.recv_packet().await?.ok()?;
+        self.status = ok.status;
+
+        Ok(ok)
     }
 
     pub(crate) async fn maybe_recv_eof(&mut self) -> Result<Option<EofPacket>, Error> {
@@ -225,6 +250,7 @@ impl<S: Socket> MySqlStream<S> {
             charset: self.charset,
             collation: self.collation,
             is_tls: self.is_tls,
+            status: self.status,
         }
     }
 }
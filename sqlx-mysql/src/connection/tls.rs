@@ -4,6 +4,7 @@ use crate::error::Error;
 use crate::net::tls::TlsConfig;
 use crate::net::{tls, BufferedSocket, Socket, WithSocket};
 use crate::protocol::connect::SslRequest;
+use crate::protocol::response::Status;
 use crate::protocol::Capabilities;
 use crate::{MySqlConnectOptions, MySqlSslMode};
 use std::collections::VecDeque;
@@ -15,6 +16,8 @@ struct MapStream {
     waiting: VecDeque<Waiting>,
     charset: CharSet,
     collation: Collation,
+    #[cfg(feature = "wire-trace")]
+    wire_trace: Option<sqlx_core::wire_trace::WireTrace>,
 }
 
 pub(super) async fn maybe_upgrade<S: Socket>(
@@ -76,10 +79,15 @@ pub(super) async fn maybe_upgrade<S: Socket>(
 
     stream.flush().await?;
 
+    #[cfg(feature = "wire-trace")]
+    let wire_trace = stream.socket.wire_trace().cloned();
+
     tls::handshake(
         stream.socket.into_inner(),
         tls_config,
         MapStream {
+            #[cfg(feature = "wire-trace")]
+            wire_trace,
             server_version: stream.server_version,
             capabilities: stream.capabilities,
             sequence_id: stream.sequence_id,
@@ -95,8 +103,14 @@ impl WithSocket for MapStream {
     type Output = MySqlStream;
 
     fn with_socket<S: Socket>(self, socket: S) -> Self::Output {
+        #[allow(unused_mut)]
+        let mut socket = BufferedSocket::new(Box::new(socket) as Box<dyn Socket>);
+
+        #[cfg(feature = "wire-trace")]
+        socket.set_wire_trace(self.wire_trace);
+
         MySqlStream {
-            socket: BufferedSocket::new(Box::new(socket)),
+            socket,
             server_version: self.server_version,
             capabilities: self.capabilities,
             sequence_id: self.sequence_id,
@@ -104,6 +118,7 @@ impl WithSocket for MapStream {
             charset: self.charset,
             collation: self.collation,
             is_tls: true,
+            status: Status::empty(),
         }
     }
 }
@@ -3,7 +3,7 @@ use crate::{
     MySqlArguments, MySqlColumn, MySqlConnection, MySqlQueryResult, MySqlRow, MySqlStatement,
     MySqlTransactionManager, MySqlTypeInfo,
 };
-pub(crate) use sqlx_core::database::{Database, HasStatementCache};
+pub(crate) use sqlx_core::database::{Database, HasCovariantLifetimes, HasStatementCache};
 
 /// MySQL database driver.
 #[derive(Debug)]
@@ -36,3 +36,13 @@ impl Database for MySql {
 }
 
 impl HasStatementCache for MySql {}
+
+impl HasCovariantLifetimes for MySql {
+    fn shorten_arguments<'a, 'b: 'a>(arguments: Self::Arguments<'b>) -> Self::Arguments<'a> {
+        arguments
+    }
+
+    fn shorten_statement<'a, 'b: 'a>(statement: Self::Statement<'b>) -> Self::Statement<'a> {
+        statement
+    }
+}
@@ -114,16 +114,21 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let rows: Vec<(i64, Vec<u8>)> =
-                query_as("SELECT version, checksum FROM _sqlx_migrations ORDER BY version")
-                    .fetch_all(self)
-                    .await?;
+            let rows: Vec<(i64, Vec<u8>, i64)> = query_as(
+                "SELECT version, checksum, execution_time FROM _sqlx_migrations ORDER BY version",
+            )
+            .fetch_all(self)
+            .await?;
 
             let migrations = rows
                 .into_iter()
-                .map(|(version, checksum)| AppliedMigration {
+                .map(|(version, checksum, execution_time)| AppliedMigration {
                     version,
                     checksum: checksum.into(),
+                    // -1 until the time is recorded
+                    execution_time: u64::try_from(execution_time)
+                        .ok()
+                        .map(Duration::from_nanos),
                 })
                 .collect();
 
@@ -241,6 +246,65 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
         })
     }
 
+    fn skip<'e: 'm, 'm>(
+        &'e mut self,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // MySQL has no `ADD COLUMN IF NOT EXISTS`, and `ALTER TABLE` commits implicitly
+            // language=MySQL
+            let has_column: bool = query_scalar(
+                "SELECT EXISTS( \
+                     SELECT 1 FROM information_schema.columns \
+                     WHERE table_schema = DATABASE() \
+                     AND table_name = '_sqlx_migrations' \
+                     AND column_name = 'skipped' \
+                 )",
+            )
+            .fetch_one(&mut *self)
+            .await?;
+
+            if !has_column {
+                // language=MySQL
+                let _ = self
+                    .execute(
+                        "ALTER TABLE _sqlx_migrations \
+                         ADD COLUMN skipped BOOLEAN NOT NULL DEFAULT FALSE",
+                    )
+                    .await?;
+            }
+
+            // language=MySQL
+            let _ = query(
+                r#"
+    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time, skipped )
+    VALUES ( ?, ?, TRUE, ?, 0, TRUE )
+                "#,
+            )
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .execute(self)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    fn analyze<'e: 'm, 'm>(
+        &'e mut self,
+        tables: &'m [String],
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async move {
+            for table in tables {
+                // language=MySQL
+                let _ = self.execute(&*format!("ANALYZE TABLE {table}")).await?;
+            }
+
+            Ok(())
+        })
+    }
+
     fn revert<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
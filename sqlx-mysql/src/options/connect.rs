@@ -1,4 +1,4 @@
-use crate::connection::ConnectOptions;
+use crate::connection::{ConnectOptions, SlowQueryHook};
 use crate::error::Error;
 use crate::executor::Executor;
 use crate::{MySqlConnectOptions, MySqlConnection};
@@ -77,8 +77,13 @@ impl ConnectOptions for MySqlConnectOptions {
             }
 
             if !options.is_empty() {
-                conn.execute(&*format!(r#"SET {};"#, options.join(",")))
-                    .await?;
+                let sql = format!(r#"SET {};"#, options.join(","));
+                conn.execute(&*sql).await?;
+                conn.inner.session_init_sql = Some(sql);
+            }
+
+            if let Some(sql) = &self.warmup_query {
+                conn.execute(&**sql).await?;
             }
 
             Ok(conn)
@@ -94,4 +99,64 @@ impl ConnectOptions for MySqlConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn redact_parameters(
+        mut self,
+        f: impl Fn(usize, &dyn std::fmt::Debug) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings.redact_parameters(f);
+        self
+    }
+
+    fn redact_all_parameters(mut self) -> Self {
+        self.log_settings.redact_all_parameters();
+        self
+    }
+
+    fn on_slow_query(
+        mut self,
+        threshold: Duration,
+        f: impl Fn(&str, Duration) + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings
+            .on_slow_query(SlowQueryHook::new(threshold, f));
+        self
+    }
+
+    fn lenient_integer_decode(mut self, enabled: bool) -> Self {
+        self.lenient_integer_decode = enabled;
+        self
+    }
+
+    fn pool_name(self, pool_name: &str) -> Self {
+        tracing::warn!(pool_name, "MySQL connections can't be labeled with a pool name");
+        self
+    }
+
+    fn connection_id(mut self, f: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        self.log_settings.connection_id(f);
+        self
+    }
+
+    fn sql_rewriter(
+        mut self,
+        f: impl for<'a> Fn(&'a str) -> std::borrow::Cow<'a, str> + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings.sql_rewriter(f);
+        self
+    }
+
+    fn dev_auto_limit(mut self, limit: Option<usize>) -> Self {
+        self.log_settings.dev_auto_limit(limit);
+        self
+    }
+
+    #[cfg(feature = "wire-trace")]
+    fn wire_trace(
+        mut self,
+        f: impl Fn(sqlx_core::wire_trace::Direction, &[u8]) + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings.wire_trace = Some(sqlx_core::wire_trace::WireTrace::new(f));
+        self
+    }
 }
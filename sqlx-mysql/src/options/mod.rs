@@ -3,9 +3,11 @@ use std::path::{Path, PathBuf};
 mod connect;
 mod parse;
 mod ssl_mode;
+mod zero_date;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::{column::DuplicateColumnNames, connection::LogSettings, net::tls::CertificateInput};
 pub use ssl_mode::MySqlSslMode;
+pub use zero_date::ZeroDateHandling;
 
 /// Options and flags which can be used to configure a MySQL connection.
 ///
@@ -72,6 +74,9 @@ pub struct MySqlConnectOptions {
     pub(crate) ssl_client_cert: Option<CertificateInput>,
     pub(crate) ssl_client_key: Option<CertificateInput>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) duplicate_column_names: DuplicateColumnNames,
+    pub(crate) zero_date_handling: ZeroDateHandling,
+    pub(crate) lenient_integer_decode: bool,
     pub(crate) charset: String,
     pub(crate) collation: Option<String>,
     pub(crate) log_settings: LogSettings,
@@ -80,6 +85,7 @@ pub struct MySqlConnectOptions {
     pub(crate) no_engine_substitution: bool,
     pub(crate) timezone: Option<String>,
     pub(crate) set_names: bool,
+    pub(crate) warmup_query: Option<String>,
 }
 
 impl Default for MySqlConnectOptions {
@@ -105,12 +111,16 @@ impl MySqlConnectOptions {
             ssl_client_cert: None,
             ssl_client_key: None,
             statement_cache_capacity: 100,
+            duplicate_column_names: DuplicateColumnNames::default(),
+            zero_date_handling: ZeroDateHandling::default(),
+            lenient_integer_decode: false,
             log_settings: Default::default(),
             pipes_as_concat: true,
             enable_cleartext_plugin: false,
             no_engine_substitution: true,
             timezone: Some(String::from("+00:00")),
             set_names: true,
+            warmup_query: None,
         }
     }
 
@@ -297,10 +307,34 @@ impl MySqlConnectOptions {
         self
     }
 
+    /// Sets how result columns that share a name are handled when looking them up by name,
+    /// e.g. the `id` columns of two joined tables.
+    ///
+    /// See [`DuplicateColumnNames`] for the available policies.
+    /// Defaults to [`DuplicateColumnNames::Last`].
+    pub fn duplicate_column_names(mut self, policy: DuplicateColumnNames) -> Self {
+        self.duplicate_column_names = policy;
+        self
+    }
+
+    /// Sets how zero dates (`0000-00-00`) and dates with a zero month or day are decoded
+    /// into date and date-time types such as `NaiveDate` or `OffsetDateTime`.
+    ///
+    /// See [`ZeroDateHandling`] for the available policies.
+    /// Defaults to [`ZeroDateHandling::Error`].
+    pub fn zero_date_handling(mut self, handling: ZeroDateHandling) -> Self {
+        self.zero_date_handling = handling;
+        self
+    }
+
     /// Sets the character set for the connection.
     ///
     /// The default character set is `utf8mb4`. This is supported from MySQL 5.5.3.
     /// If you need to connect to an older version, we recommend you to change this to `utf8`.
+    ///
+    /// The character set is sent in the handshake and, unless [`.set_names(false)`][Self::set_names]
+    /// is used, applied with `SET NAMES` after connecting, so it takes effect regardless of the
+    /// server's default. Connecting fails if the name is not a known MySQL character set.
     pub fn charset(mut self, charset: &str) -> Self {
         charset.clone_into(&mut self.charset);
         self
@@ -310,6 +344,8 @@ impl MySqlConnectOptions {
     ///
     /// The default collation is derived from the `charset`. Normally, you should only have to set
     /// the `charset`.
+    ///
+    /// Connecting fails if the name is not a known MySQL collation.
     pub fn collation(mut self, collation: &str) -> Self {
         self.collation = Some(collation.to_owned());
         self
@@ -403,6 +439,25 @@ impl MySqlConnectOptions {
         self.set_names = flag_val;
         self
     }
+
+    /// Sets a query to run once on every new connection, after it has been configured.
+    ///
+    /// This is meant for priming connection-local state, such as caches, that would
+    /// otherwise be populated by the first real query. Unlike the statements the driver issues
+    /// to configure the session, the warmup query may be a `SELECT`; any rows it returns are
+    /// discarded. An error from the query fails the connection attempt.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_mysql::MySqlConnectOptions;
+    /// let options = MySqlConnectOptions::new()
+    ///     .warmup_query("SELECT @@version");
+    /// ```
+    pub fn warmup_query(mut self, sql: &str) -> Self {
+        self.warmup_query = Some(sql.to_owned());
+        self
+    }
 }
 
 impl MySqlConnectOptions {
@@ -112,7 +112,7 @@ impl MySqlConnectOptions {
         url.query_pairs_mut().append_pair("charset", &self.charset);
 
         if let Some(collation) = &self.collation {
-            url.query_pairs_mut().append_pair("charset", collation);
+            url.query_pairs_mut().append_pair("collation", collation);
         }
 
         if let Some(ssl_client_cert) = &self.ssl_client_cert {
@@ -176,3 +176,14 @@ fn it_returns_the_parsed_url() {
 
     assert_eq!(expected_url, opts.build_url());
 }
+
+#[test]
+fn it_returns_the_collation_in_the_parsed_url() {
+    let url = "mysql://hostname/database?charset=latin1&collation=latin1_general_ci";
+    let opts = MySqlConnectOptions::from_str(url).unwrap();
+
+    let opts = MySqlConnectOptions::parse_from_url(&opts.build_url()).unwrap();
+
+    assert_eq!(opts.get_charset(), "latin1");
+    assert_eq!(opts.get_collation(), Some("latin1_general_ci"));
+}
@@ -0,0 +1,28 @@
+/// How dates that MySQL stores but that cannot be represented by date types are decoded.
+///
+/// Unless the `NO_ZERO_DATE` and `NO_ZERO_IN_DATE` SQL modes are enabled, MySQL accepts the
+/// "zero date" `0000-00-00` and dates with a zero month or day such as `2020-00-15` in `DATE`,
+/// `DATETIME` and `TIMESTAMP` columns. These have no equivalent in `chrono` or `time`.
+///
+/// It is used by the [`zero_date_handling`](super::MySqlConnectOptions::zero_date_handling) method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ZeroDateHandling {
+    /// Decoding such a date as a date or date-time type returns an error.
+    ///
+    /// For compatibility, a zero date received in the binary protocol (used for queries
+    /// with bind parameters) is still reported as `NULL`, so it decodes as `None` into an
+    /// `Option`.
+    ///
+    /// This is the default.
+    #[default]
+    Error,
+
+    /// Such dates are reported as `NULL`, so they decode as `None` into an `Option`.
+    Null,
+
+    /// Such dates decode as the minimum value of the target type, e.g. [`NaiveDate::MIN`].
+    ///
+    /// [`NaiveDate::MIN`]: https://docs.rs/chrono/latest/chrono/naive/struct.NaiveDate.html#associatedconstant.MIN
+    MinDate,
+}
@@ -2,7 +2,7 @@ use std::ops::Range;
 
 use bytes::Bytes;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Row {
     pub(crate) storage: Bytes,
     pub(crate) values: Vec<Option<Range<usize>>>,
@@ -2,17 +2,21 @@ use std::sync::Arc;
 
 pub(crate) use sqlx_core::row::*;
 
-use crate::column::ColumnIndex;
+use crate::column::{column_index_by_name, ColumnIndex};
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::HashMap;
+use crate::ZeroDateHandling;
 use crate::{protocol, MySql, MySqlColumn, MySqlValueFormat, MySqlValueRef};
 
 /// Implementation of [`Row`] for MySQL.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MySqlRow {
     pub(crate) row: protocol::Row,
     pub(crate) format: MySqlValueFormat,
+    pub(crate) zero_dates: ZeroDateHandling,
+    // see `ConnectOptions::lenient_integer_decode()`
+    pub(crate) lenient_integers: bool,
     pub(crate) columns: Arc<Vec<MySqlColumn>>,
     pub(crate) column_names: Arc<HashMap<UStr, usize>>,
 }
@@ -34,18 +38,20 @@ impl Row for MySqlRow {
 
         Ok(MySqlValueRef {
             format: self.format,
+            zero_dates: self.zero_dates,
             row: Some(&self.row.storage),
             type_info: column.type_info.clone(),
             value,
         })
     }
+
+    fn lenient_integer_decode(&self) -> bool {
+        self.lenient_integers
+    }
 }
 
 impl ColumnIndex<MySqlRow> for &'_ str {
     fn index(&self, row: &MySqlRow) -> Result<usize, Error> {
-        row.column_names
-            .get(*self)
-            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
-            .copied()
+        column_index_by_name(&row.column_names, self)
     }
 }
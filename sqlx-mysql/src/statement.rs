@@ -1,5 +1,5 @@
 use super::MySqlColumn;
-use crate::column::ColumnIndex;
+use crate::column::{column_index_by_name, ColumnIndex};
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::HashMap;
@@ -50,11 +50,6 @@ impl<'q> Statement<'q> for MySqlStatement<'q> {
 
 impl ColumnIndex<MySqlStatement<'_>> for &'_ str {
     fn index(&self, statement: &MySqlStatement<'_>) -> Result<usize, Error> {
-        statement
-            .metadata
-            .column_names
-            .get(*self)
-            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
-            .copied()
+        column_index_by_name(&statement.metadata.column_names, self)
     }
 }
@@ -57,9 +57,12 @@ impl TransactionManager for MySqlTransactionManager {
         if depth > 0 {
             conn.inner.stream.waiting.push_back(Waiting::Result);
             conn.inner.stream.sequence_id = 0;
+            let sql = rollback_ansi_transaction_sql(depth);
+            let sql = conn.inner.log_settings.rewrite_sql(&sql);
+
             conn.inner
                 .stream
-                .write_packet(Query(&rollback_ansi_transaction_sql(depth)))
+                .write_packet(Query(&sql))
                 .expect("BUG: unexpected error queueing ROLLBACK");
 
             conn.inner.transaction_depth = depth - 1;
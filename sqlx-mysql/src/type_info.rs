@@ -79,6 +79,22 @@ impl TypeInfo for MySqlTypeInfo {
     fn name(&self) -> &str {
         self.r#type.name(self.flags, self.max_size)
     }
+
+    fn is_integer_widening_to(&self, wider: &Self) -> bool {
+        let width = |ty: &MySqlTypeInfo| match ty.r#type {
+            ColumnType::Tiny => Some(1),
+            ColumnType::Short => Some(2),
+            ColumnType::Int24 => Some(3),
+            ColumnType::Long => Some(4),
+            ColumnType::LongLong => Some(8),
+            _ => None,
+        };
+
+        // integers of the same signedness are already compatible, whatever their width
+        self.flags.contains(ColumnFlags::UNSIGNED)
+            && !wider.flags.contains(ColumnFlags::UNSIGNED)
+            && matches!((width(self), width(wider)), (Some(a), Some(b)) if a < b)
+    }
 }
 
 impl PartialEq<MySqlTypeInfo> for MySqlTypeInfo {
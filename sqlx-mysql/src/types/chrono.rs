@@ -183,6 +183,10 @@ impl Encode<'_, MySql> for NaiveDate {
 
 impl<'r> Decode<'r, MySql> for NaiveDate {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        if value.is_min_date()? {
+            return Ok(NaiveDate::MIN);
+        }
+
         match value.format() {
             MySqlValueFormat::Binary => {
                 let buf = value.as_bytes()?;
@@ -230,6 +234,10 @@ impl Encode<'_, MySql> for NaiveDateTime {
 
 impl<'r> Decode<'r, MySql> for NaiveDateTime {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        if value.is_min_date()? {
+            return Ok(NaiveDateTime::MIN);
+        }
+
         match value.format() {
             MySqlValueFormat::Binary => {
                 let buf = value.as_bytes()?;
@@ -4,6 +4,7 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::types::uint::{year_compatible, year_decode};
 use crate::types::Type;
 use crate::{MySql, MySqlTypeInfo, MySqlValueFormat, MySqlValueRef};
 
@@ -44,7 +45,7 @@ impl Type<MySql> for i32 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        int_compatible(ty)
+        int_compatible(ty) || year_compatible(ty)
     }
 }
 
@@ -54,7 +55,7 @@ impl Type<MySql> for i64 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        int_compatible(ty)
+        int_compatible(ty) || year_compatible(ty)
     }
 }
 
@@ -91,6 +92,10 @@ impl Encode<'_, MySql> for i64 {
 }
 
 fn int_decode(value: MySqlValueRef<'_>) -> Result<i64, BoxDynError> {
+    if value.type_info.r#type == ColumnType::Year {
+        return year_decode(value).map(i64::from);
+    }
+
     Ok(match value.format() {
         MySqlValueFormat::Text => value.as_str()?.parse()?,
         MySqlValueFormat::Binary => {
@@ -109,7 +114,12 @@ fn int_decode(value: MySqlValueRef<'_>) -> Result<i64, BoxDynError> {
                 .into());
             }
 
-            LittleEndian::read_int(buf, buf.len())
+            // an unsigned column read with `ConnectOptions::lenient_integer_decode()`
+            if value.type_info.flags.contains(ColumnFlags::UNSIGNED) {
+                LittleEndian::read_uint(buf, buf.len()).try_into()?
+            } else {
+                LittleEndian::read_int(buf, buf.len())
+            }
         }
     })
 }
@@ -7,12 +7,12 @@
 //! | `bool`                                | TINYINT(1), BOOLEAN, BOOL (see below)                |
 //! | `i8`                                  | TINYINT                                              |
 //! | `i16`                                 | SMALLINT                                             |
-//! | `i32`                                 | INT                                                  |
-//! | `i64`                                 | BIGINT                                               |
+//! | `i32`                                 | INT, YEAR                                            |
+//! | `i64`                                 | BIGINT, YEAR                                         |
 //! | `u8`                                  | TINYINT UNSIGNED                                     |
-//! | `u16`                                 | SMALLINT UNSIGNED                                    |
-//! | `u32`                                 | INT UNSIGNED                                         |
-//! | `u64`                                 | BIGINT UNSIGNED                                      |
+//! | `u16`                                 | SMALLINT UNSIGNED, YEAR (see below)                  |
+//! | `u32`                                 | INT UNSIGNED, YEAR                                   |
+//! | `u64`                                 | BIGINT UNSIGNED, YEAR                                |
 //! | `f32`                                 | FLOAT                                                |
 //! | `f64`                                 | DOUBLE                                               |
 //! | `&str`, [`String`]                    | VARCHAR, CHAR, TEXT                                  |
@@ -40,6 +40,12 @@
 //! Thus, you must use the type override syntax in the query to tell the macros you are expecting
 //! a `bool` column. See the docs for `query!()` and `query_as!()` for details on this syntax.
 //!
+//! ##### Note: `YEAR` Type
+//! The query macros map a `YEAR` column to `u16`. The zero year, `0000`, decodes as `0`.
+//! The legacy `YEAR(2)` type only stores the last two digits, which decode as 1970 to 2069
+//! (so its zero value decodes as 2000). A `YEAR` value is bound like any other integer, e.g.
+//! as a `u16`.
+//!
 //! ### NOTE: MySQL's `TIME` type is signed
 //! MySQL's `TIME` type can be used as either a time-of-day value, or a signed interval.
 //! Thus, it may take on negative values.
@@ -165,3 +171,6 @@ mod time;
 
 #[cfg(feature = "uuid")]
 mod uuid;
+
+#[cfg(feature = "json")]
+mod value_to_json;
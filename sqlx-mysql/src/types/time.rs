@@ -161,6 +161,10 @@ impl Encode<'_, MySql> for Date {
 
 impl<'r> Decode<'r, MySql> for Date {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        if value.is_min_date()? {
+            return Ok(Date::MIN);
+        }
+
         match value.format() {
             MySqlValueFormat::Binary => {
                 let buf = value.as_bytes()?;
@@ -207,6 +211,10 @@ impl Encode<'_, MySql> for PrimitiveDateTime {
 
 impl<'r> Decode<'r, MySql> for PrimitiveDateTime {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        if value.is_min_date()? {
+            return Ok(PrimitiveDateTime::MIN);
+        }
+
         match value.format() {
             MySqlValueFormat::Binary => {
                 let mut buf = value.as_bytes()?;
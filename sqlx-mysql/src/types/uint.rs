@@ -22,11 +22,15 @@ fn uint_compatible(ty: &MySqlTypeInfo) -> bool {
             | ColumnType::Long
             | ColumnType::Int24
             | ColumnType::LongLong
-            | ColumnType::Year
             | ColumnType::Bit
     ) && ty.flags.contains(ColumnFlags::UNSIGNED)
 }
 
+// `YEAR` needs at least 16 bits, so `u8` and `i8` are not compatible with it.
+pub(super) fn year_compatible(ty: &MySqlTypeInfo) -> bool {
+    ty.r#type == ColumnType::Year
+}
+
 impl Type<MySql> for u8 {
     fn type_info() -> MySqlTypeInfo {
         uint_type_info(ColumnType::Tiny)
@@ -43,7 +47,7 @@ impl Type<MySql> for u16 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        uint_compatible(ty)
+        uint_compatible(ty) || year_compatible(ty)
     }
 }
 
@@ -53,7 +57,7 @@ impl Type<MySql> for u32 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        uint_compatible(ty)
+        uint_compatible(ty) || year_compatible(ty)
     }
 }
 
@@ -63,7 +67,7 @@ impl Type<MySql> for u64 {
     }
 
     fn compatible(ty: &MySqlTypeInfo) -> bool {
-        uint_compatible(ty)
+        uint_compatible(ty) || year_compatible(ty)
     }
 }
 
@@ -99,7 +103,36 @@ impl Encode<'_, MySql> for u64 {
     }
 }
 
+/// Decode a `YEAR` value; `0` is the zero year, `0000`.
+///
+/// The legacy `YEAR(2)` type (removed in MySQL 5.7.5, still supported by MariaDB) only sends the
+/// last two digits, which stand for 1970 to 2069 as they do when inserting a two-digit year.
+pub(super) fn year_decode(value: MySqlValueRef<'_>) -> Result<u16, BoxDynError> {
+    let year: u16 = match value.format() {
+        MySqlValueFormat::Text => value.as_str()?.parse()?,
+        MySqlValueFormat::Binary => {
+            let buf = value.as_bytes()?;
+
+            if buf.len() != 2 {
+                return Err(format!("expected 2 bytes for YEAR value, got {}", buf.len()).into());
+            }
+
+            LittleEndian::read_u16(buf)
+        }
+    };
+
+    Ok(match (value.type_info.max_size, year) {
+        (Some(2), 0..=69) => 2000 + year,
+        (Some(2), 70..=99) => 1900 + year,
+        _ => year,
+    })
+}
+
 fn uint_decode(value: MySqlValueRef<'_>) -> Result<u64, BoxDynError> {
+    if value.type_info.r#type == ColumnType::Year {
+        return year_decode(value).map(u64::from);
+    }
+
     if value.type_info.r#type == ColumnType::Bit {
         // NOTE: Regardless of the value format, there is raw binary data here
 
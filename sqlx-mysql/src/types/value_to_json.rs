@@ -0,0 +1,80 @@
+use sqlx_core::row::{float_to_json, ValueToJson};
+
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::protocol::text::{ColumnFlags, ColumnType};
+use crate::types::JsonValue;
+use crate::value::{MySqlValueFormat, ValueRef};
+use crate::{MySql, MySqlValueRef};
+
+impl ValueToJson for MySql {
+    fn value_to_json(value: MySqlValueRef<'_>) -> Result<JsonValue, BoxDynError> {
+        if value.is_null() {
+            return Ok(JsonValue::Null);
+        }
+
+        let flags = value.type_info.flags;
+
+        let json = match value.type_info.r#type {
+            ColumnType::Null => JsonValue::Null,
+
+            ColumnType::Tiny
+            | ColumnType::Short
+            | ColumnType::Int24
+            | ColumnType::Long
+            | ColumnType::LongLong
+            | ColumnType::Year => {
+                if flags.contains(ColumnFlags::UNSIGNED) {
+                    decode::<u64>(value)?.into()
+                } else {
+                    decode::<i64>(value)?.into()
+                }
+            }
+
+            ColumnType::Bit => decode::<u64>(value)?.into(),
+
+            ColumnType::Float | ColumnType::Double => float_to_json(decode::<f64>(value)?),
+
+            // Sent as a string in both formats, which also preserves precision.
+            ColumnType::Decimal | ColumnType::NewDecimal => value.as_str()?.into(),
+
+            ColumnType::Json => decode::<JsonValue>(value)?,
+
+            ColumnType::Enum | ColumnType::Set => value.as_str()?.into(),
+
+            ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::String
+            | ColumnType::TinyBlob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+            | ColumnType::Blob
+            | ColumnType::Geometry => {
+                if flags.contains(ColumnFlags::BINARY) {
+                    value.as_bytes()?.into()
+                } else {
+                    value.as_str()?.into()
+                }
+            }
+
+            ColumnType::Date | ColumnType::Time | ColumnType::Datetime | ColumnType::Timestamp => {
+                match value.format() {
+                    MySqlValueFormat::Text => value.as_str()?.into(),
+                    MySqlValueFormat::Binary => {
+                        return Err(format!(
+                            "no JSON mapping for values of type {} in the binary format",
+                            value.type_info.r#type.name(flags, value.type_info.max_size)
+                        )
+                        .into())
+                    }
+                }
+            }
+        };
+
+        Ok(json)
+    }
+}
+
+fn decode<'r, T: Decode<'r, MySql>>(value: MySqlValueRef<'r>) -> Result<T, BoxDynError> {
+    T::decode(value)
+}
@@ -6,7 +6,7 @@ pub(crate) use sqlx_core::value::*;
 
 use crate::error::{BoxDynError, UnexpectedNullError};
 use crate::protocol::text::ColumnType;
-use crate::{MySql, MySqlTypeInfo};
+use crate::{MySql, MySqlTypeInfo, ZeroDateHandling};
 
 #[derive(Debug, Clone, Copy)]
 #[repr(u8)]
@@ -21,6 +21,7 @@ pub struct MySqlValue {
     value: Option<Bytes>,
     type_info: MySqlTypeInfo,
     format: MySqlValueFormat,
+    zero_dates: ZeroDateHandling,
 }
 
 /// Implementation of [`ValueRef`] for MySQL.
@@ -30,23 +31,59 @@ pub struct MySqlValueRef<'r> {
     pub(crate) row: Option<&'r Bytes>,
     pub(crate) type_info: MySqlTypeInfo,
     pub(crate) format: MySqlValueFormat,
+    pub(crate) zero_dates: ZeroDateHandling,
 }
 
 impl<'r> MySqlValueRef<'r> {
-    pub(crate) fn format(&self) -> MySqlValueFormat {
+    /// The format the value was sent in.
+    ///
+    /// Values are sent in the binary format in response to prepared statements, i.e. queries
+    /// with arguments, and in the text format otherwise.
+    pub fn format(&self) -> MySqlValueFormat {
         self.format
     }
 
-    pub(crate) fn as_bytes(&self) -> Result<&'r [u8], BoxDynError> {
+    /// The encoded value, in the [format](Self::format) it was sent in.
+    ///
+    /// Returns an error if the value is `NULL`.
+    pub fn as_bytes(&self) -> Result<&'r [u8], BoxDynError> {
         match &self.value {
             Some(v) => Ok(v),
             None => Err(UnexpectedNullError.into()),
         }
     }
 
-    pub(crate) fn as_str(&self) -> Result<&'r str, BoxDynError> {
+    /// The encoded value as a string, for a value in the text format.
+    ///
+    /// Returns an error if the value is `NULL` or not valid UTF-8.
+    pub fn as_str(&self) -> Result<&'r str, BoxDynError> {
         Ok(from_utf8(self.as_bytes()?)?)
     }
+
+    /// Check for a zero date or a date with a zero month or day before decoding a date type.
+    ///
+    /// Returns `true` if the value should decode as the minimum value of the type
+    /// (see [`ZeroDateHandling::MinDate`]), and an error if it cannot be decoded at all.
+    #[cfg_attr(not(any(feature = "time", feature = "chrono")), allow(dead_code))]
+    pub(crate) fn is_min_date(&self) -> Result<bool, BoxDynError> {
+        let Some(value) = self.value else {
+            return Ok(false);
+        };
+
+        let Some((year, month, day)) = invalid_date(value, &self.type_info, self.format) else {
+            return Ok(false);
+        };
+
+        match self.zero_dates {
+            ZeroDateHandling::MinDate => Ok(true),
+            _ if ValueRef::is_null(self) => Err(UnexpectedNullError.into()),
+            _ => Err(format!(
+                "cannot decode invalid date {year:04}-{month:02}-{day:02}; \
+                 see `MySqlConnectOptions::zero_date_handling()`"
+            )
+            .into()),
+        }
+    }
 }
 
 impl Value for MySqlValue {
@@ -58,6 +95,7 @@ impl Value for MySqlValue {
             row: None,
             type_info: self.type_info.clone(),
             format: self.format,
+            zero_dates: self.zero_dates,
         }
     }
 
@@ -66,7 +104,12 @@ impl Value for MySqlValue {
     }
 
     fn is_null(&self) -> bool {
-        is_null(self.value.as_deref(), &self.type_info)
+        is_null(
+            self.value.as_deref(),
+            &self.type_info,
+            self.format,
+            self.zero_dates,
+        )
     }
 }
 
@@ -86,6 +129,7 @@ impl<'r> ValueRef<'r> for MySqlValueRef<'r> {
             value,
             format: self.format,
             type_info: self.type_info.clone(),
+            zero_dates: self.zero_dates,
         }
     }
 
@@ -95,21 +139,120 @@ impl<'r> ValueRef<'r> for MySqlValueRef<'r> {
 
     #[inline]
     fn is_null(&self) -> bool {
-        is_null(self.value, &self.type_info)
+        is_null(self.value, &self.type_info, self.format, self.zero_dates)
     }
 }
 
-fn is_null(value: Option<&[u8]>, ty: &MySqlTypeInfo) -> bool {
+fn is_null(
+    value: Option<&[u8]>,
+    ty: &MySqlTypeInfo,
+    format: MySqlValueFormat,
+    zero_dates: ZeroDateHandling,
+) -> bool {
     if let Some(value) = value {
-        // zero dates and date times should be treated the same as NULL
-        if matches!(
-            ty.r#type,
-            ColumnType::Date | ColumnType::Timestamp | ColumnType::Datetime
-        ) && value.starts_with(b"\0")
-        {
-            return true;
+        match zero_dates {
+            // zero dates and date times should be treated the same as NULL
+            ZeroDateHandling::Error if value.starts_with(b"\0") => {
+                return invalid_date(value, ty, format).is_some()
+            }
+            ZeroDateHandling::Null => return invalid_date(value, ty, format).is_some(),
+            _ => {}
         }
     }
 
     value.is_none()
 }
+
+/// Returns the year, month and day of a date or date time value that is a zero date
+/// or has a zero month or day.
+fn invalid_date(
+    value: &[u8],
+    ty: &MySqlTypeInfo,
+    format: MySqlValueFormat,
+) -> Option<(u16, u8, u8)> {
+    if !matches!(
+        ty.r#type,
+        ColumnType::Date | ColumnType::Timestamp | ColumnType::Datetime
+    ) {
+        return None;
+    }
+
+    let (year, month, day) = match format {
+        // length prefix, then `year: u16, month: u8, day: u8`; no bytes means all zeros
+        MySqlValueFormat::Binary => match value {
+            [0, ..] => (0, 0, 0),
+            [_, y0, y1, month, day, ..] => (u16::from_le_bytes([*y0, *y1]), *month, *day),
+            _ => return None,
+        },
+
+        // `YYYY-MM-DD`, optionally followed by the time
+        MySqlValueFormat::Text => {
+            let s = from_utf8(value.get(..10)?).ok()?;
+
+            (
+                s.get(..4)?.parse().ok()?,
+                s.get(5..7)?.parse().ok()?,
+                s.get(8..10)?.parse().ok()?,
+            )
+        }
+    };
+
+    (month == 0 || day == 0).then_some((year, month, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(
+        bytes: &[u8],
+        format: MySqlValueFormat,
+        zero_dates: ZeroDateHandling,
+    ) -> MySqlValueRef<'_> {
+        MySqlValueRef {
+            value: Some(bytes),
+            row: None,
+            type_info: MySqlTypeInfo::binary(ColumnType::Date),
+            format,
+            zero_dates,
+        }
+    }
+
+    #[test]
+    fn test_zero_date_handling() {
+        use MySqlValueFormat::{Binary, Text};
+
+        let zero = [0];
+        let zero_month = [4, 0xe4, 0x07, 0, 15];
+        let valid = [4, 0xe4, 0x07, 1, 15];
+
+        for handling in [
+            ZeroDateHandling::Error,
+            ZeroDateHandling::Null,
+            ZeroDateHandling::MinDate,
+        ] {
+            assert!(!value(&valid, Binary, handling).is_null());
+            assert!(!value(&valid, Binary, handling).is_min_date().unwrap());
+            assert!(!value(b"2020-01-15", Text, handling).is_min_date().unwrap());
+        }
+
+        let error = ZeroDateHandling::Error;
+        assert!(value(&zero, Binary, error).is_null());
+        assert!(!value(&zero_month, Binary, error).is_null());
+        assert!(!value(b"0000-00-00", Text, error).is_null());
+        assert!(value(&zero_month, Binary, error).is_min_date().is_err());
+        assert!(value(b"0000-00-00", Text, error).is_min_date().is_err());
+
+        let null = ZeroDateHandling::Null;
+        assert!(value(&zero, Binary, null).is_null());
+        assert!(value(&zero_month, Binary, null).is_null());
+        assert!(value(b"0000-00-00", Text, null).is_null());
+        assert!(value(b"2020-01-00 10:00:00", Text, null).is_null());
+
+        let min_date = ZeroDateHandling::MinDate;
+        assert!(!value(&zero, Binary, min_date).is_null());
+        assert!(value(&zero, Binary, min_date).is_min_date().unwrap());
+        assert!(value(&zero_month, Binary, min_date).is_min_date().unwrap());
+        assert!(value(b"0000-00-00", Text, min_date).is_min_date().unwrap());
+    }
+}
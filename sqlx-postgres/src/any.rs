@@ -10,11 +10,12 @@ use std::future;
 pub use sqlx_core::any::*;
 
 use crate::type_info::PgType;
-use sqlx_core::connection::Connection;
+use sqlx_core::connection::{ConnectOptions, Connection};
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
 use sqlx_core::executor::Executor;
 use sqlx_core::ext::ustr::UStr;
+use sqlx_core::query::QueryStatsCounters;
 use sqlx_core::transaction::TransactionManager;
 
 sqlx_core::declare_driver_with_optional_migrate!(DRIVER = Postgres);
@@ -24,6 +25,10 @@ impl AnyConnectionBackend for PgConnection {
         <Postgres as Database>::NAME
     }
 
+    fn id(&self) -> u64 {
+        Connection::id(self)
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -36,6 +41,37 @@ impl AnyConnectionBackend for PgConnection {
         Connection::ping(self)
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::reset(self)
+    }
+
+    fn rollback_open_transaction(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::rollback_open_transaction(self)
+    }
+
+    fn server_max_connections(&mut self) -> BoxFuture<'_, sqlx_core::Result<Option<u32>>> {
+        Connection::server_max_connections(self)
+    }
+
+    fn queries_executed(&self) -> u64 {
+        Connection::queries_executed(self)
+    }
+
+    fn query_stats_counters<'a>(
+        &'a mut self,
+        sql: &'a str,
+    ) -> BoxFuture<'a, sqlx_core::Result<QueryStatsCounters>> {
+        Connection::query_stats_counters(self, sql)
+    }
+
+    fn set_tag<'a>(&'a mut self, tag: Option<&'a str>) -> BoxFuture<'a, sqlx_core::Result<()>> {
+        Connection::set_tag(self, tag)
+    }
+
+    fn server_now_micros(&mut self) -> BoxFuture<'_, sqlx_core::Result<i64>> {
+        Connection::server_now_micros(self)
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         PgTransactionManager::begin(self)
     }
@@ -64,6 +100,14 @@ impl AnyConnectionBackend for PgConnection {
         Connection::should_flush(self)
     }
 
+    fn cancel_handle(&self) -> Option<std::sync::Arc<dyn sqlx_core::connection::CancelHandle>> {
+        Connection::cancel_handle(self)
+    }
+
+    fn set_require_transaction_for_writes(&mut self, require: bool) {
+        Connection::set_require_transaction_for_writes(self, require);
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
@@ -192,7 +236,9 @@ impl<'a> TryFrom<&'a PgTypeInfo> for AnyTypeInfo {
                 PgType::Float4 => AnyTypeInfoKind::Real,
                 PgType::Float8 => AnyTypeInfoKind::Double,
                 PgType::Bytea => AnyTypeInfoKind::Blob,
-                PgType::Text | PgType::Varchar => AnyTypeInfoKind::Text,
+                PgType::Text | PgType::Varchar | PgType::Bpchar | PgType::Name => {
+                    AnyTypeInfoKind::Text
+                }
                 PgType::DeclareWithName(UStr::Static("citext")) => AnyTypeInfoKind::Text,
                 _ => {
                     return Err(sqlx_core::Error::AnyDriverError(
@@ -236,6 +282,11 @@ impl<'a> TryFrom<&'a AnyConnectOptions> for PgConnectOptions {
     fn try_from(value: &'a AnyConnectOptions) -> Result<Self, Self::Error> {
         let mut opts = PgConnectOptions::parse_from_url(&value.database_url)?;
         opts.log_settings = value.log_settings.clone();
+
+        if let Some(pool_name) = &value.pool_name {
+            opts = opts.pool_name(pool_name);
+        }
+
         Ok(opts)
     }
 }
@@ -1,5 +1,5 @@
 use std::fmt::{self, Write};
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, Range};
 use std::sync::Arc;
 
 use crate::encode::{Encode, IsNull};
@@ -22,7 +22,7 @@ use sqlx_core::error::BoxDynError;
 //            that has a patch, we then apply the patch which should write to &mut Vec<u8>,
 //            backtrack and update the prefixed-len, then write until the next patch offset
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct PgArgumentBuffer {
     buffer: Vec<u8>,
 
@@ -46,20 +46,22 @@ pub struct PgArgumentBuffer {
     type_holes: Vec<(usize, HoleKind)>, // Vec<{ offset, type_name }>
 }
 
+#[derive(Clone)]
 enum HoleKind {
     Type { name: UStr },
     Array(Arc<PgArrayOf>),
 }
 
+#[derive(Clone)]
 struct Patch {
     buf_offset: usize,
     arg_index: usize,
     #[allow(clippy::type_complexity)]
-    callback: Box<dyn Fn(&mut [u8], &PgTypeInfo) + 'static + Send + Sync>,
+    callback: Arc<dyn Fn(&mut [u8], &PgTypeInfo) + 'static + Send + Sync>,
 }
 
 /// Implementation of [`Arguments`] for PostgreSQL.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct PgArguments {
     // Types of each bind parameter
     pub(crate) types: Vec<PgTypeInfo>,
@@ -93,6 +95,24 @@ impl PgArguments {
         Ok(())
     }
 
+    /// The encoded value of each parameter, or `None` if it is `NULL`.
+    pub(crate) fn values(&self) -> impl Iterator<Item = Option<&[u8]>> {
+        let mut buf = &self.buffer.buffer[..];
+
+        (0..self.buffer.count).map(move |_| {
+            let (len, rest) = buf.split_at(4);
+            buf = rest;
+
+            // a negative length indicates NULL
+            let len = usize::try_from(i32::from_be_bytes([len[0], len[1], len[2], len[3]])).ok()?;
+
+            let (value, rest) = buf.split_at(len);
+            buf = rest;
+
+            Some(value)
+        })
+    }
+
     // Apply patches
     // This should only go out and ask postgres if we have not seen the type name yet
     pub(crate) async fn apply_patches(
@@ -149,6 +169,24 @@ impl<'q> Arguments<'q> for PgArguments {
     fn len(&self) -> usize {
         self.buffer.count
     }
+
+    fn try_clone(&self) -> Option<Self> {
+        Some(self.clone())
+    }
+
+    fn to_cache_key(&self) -> Option<Vec<u8>> {
+        // the values are length-prefixed, and the type names can't contain NUL
+        let mut key = Vec::with_capacity(self.buffer.len() + 8 * self.types.len());
+
+        for ty in &self.types {
+            key.extend_from_slice(ty.name().as_bytes());
+            key.push(0);
+        }
+
+        key.extend_from_slice(&self.buffer);
+
+        Some(key)
+    }
 }
 
 impl PgArgumentBuffer {
@@ -194,7 +232,7 @@ impl PgArgumentBuffer {
         self.patches.push(Patch {
             buf_offset: offset,
             arg_index,
-            callback: Box::new(callback),
+            callback: Arc::new(callback),
         });
     }
 
@@ -219,6 +257,38 @@ impl PgArgumentBuffer {
         self.type_holes.push((offset, HoleKind::Array(array)));
     }
 
+    // Replaces `range` of the buffer with `replace_with`, shifting any patches or type holes
+    // that come after it. Patches and holes inside `range` are discarded.
+    pub(crate) fn splice(&mut self, range: Range<usize>, replace_with: &[u8]) {
+        let Range { start, end } = range;
+
+        self.buffer.splice(start..end, replace_with.iter().copied());
+
+        let shift = |offset: usize| offset - (end - start) + replace_with.len();
+
+        self.patches.retain_mut(|patch| {
+            if patch.buf_offset < start {
+                true
+            } else if patch.buf_offset >= end {
+                patch.buf_offset = shift(patch.buf_offset);
+                true
+            } else {
+                false
+            }
+        });
+
+        self.type_holes.retain_mut(|(offset, _)| {
+            if *offset < start {
+                true
+            } else if *offset >= end {
+                *offset = shift(*offset);
+                true
+            } else {
+                false
+            }
+        });
+    }
+
     fn snapshot(&self) -> PgArgumentBufferSnapshot {
         let Self {
             buffer,
@@ -0,0 +1,157 @@
+use std::io::Write;
+
+use sqlx_core::error::mismatched_types;
+use sqlx_core::executor::Execute;
+use sqlx_core::query::Query;
+use sqlx_core::Either;
+
+use crate::connection::PgConnection;
+use crate::error::Error;
+use crate::io::PortalId;
+use crate::logger::{EncodedParameter, QueryLogger};
+use crate::message::{BackendMessageFormat, Bind, Close, Execute as ExecuteMessage};
+use crate::{PgArguments, PgTypeInfo, PgValueFormat, Postgres};
+
+impl PgConnection {
+    /// Write the `BYTEA` value returned by `query` to `writer` in chunks of at most `chunk_size`
+    /// bytes, as they arrive from the server, without holding the whole value in memory.
+    ///
+    /// This is intended for hashing or copying large values, e.g. by passing a hasher that
+    /// implements [`Write`]. `query` is run as it is, as a prepared statement, and must return
+    /// the value in its first column; only the first row is read, and its other columns are
+    /// discarded.
+    ///
+    /// Returns the length of the value, or `None` if it is `NULL`.
+    /// Returns [`Error::RowNotFound`] if `query` returns no rows, and [`Error::ColumnDecode`] if
+    /// its first column is not a `BYTEA`.
+    ///
+    /// If writing fails, or the returned future is dropped, the rest of the value is discarded
+    /// the next time the connection is used.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection, id: i64) -> sqlx::Result<()> {
+    /// let mut file = std::fs::File::create("blob.bin")?;
+    ///
+    /// let len = conn
+    ///     .read_bytea_into(
+    ///         sqlx::query("SELECT data FROM blobs WHERE id = $1").bind(id),
+    ///         1024 * 1024,
+    ///         &mut file,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// ### Panics
+    /// If `chunk_size` is `0`.
+    pub async fn read_bytea_into<W>(
+        &mut self,
+        mut query: Query<'_, Postgres, PgArguments>,
+        chunk_size: usize,
+        writer: &mut W,
+    ) -> Result<Option<u64>, Error>
+    where
+        W: Write + ?Sized,
+    {
+        assert_ne!(chunk_size, 0, "chunk_size must be greater than 0");
+
+        let persistent = Execute::persistent(&query);
+        let mut arguments = query
+            .take_arguments()
+            .map_err(Error::Encode)?
+            .unwrap_or_default();
+        let sql = query.sql();
+
+        self.wait_until_ready().await?;
+        self.check_write_allowed(sql)?;
+
+        self.inner.queries_executed += 1;
+
+        let mut logger = QueryLogger::new(sql, self.inner.log_settings.clone());
+
+        let num_params = u16::try_from(arguments.types.len()).map_err(|_| {
+            err_protocol!(
+                "PgConnection::read_bytea_into(): too many arguments for query: {}",
+                arguments.types.len()
+            )
+        })?;
+
+        let (statement, metadata) = self
+            .get_or_prepare(sql, &arguments.types, persistent, None)
+            .await?;
+
+        match metadata.columns.first() {
+            Some(column) if column.type_info == PgTypeInfo::BYTEA => {}
+            Some(column) => {
+                return Err(Error::ColumnDecode {
+                    index: "0".into(),
+                    source: mismatched_types::<Postgres, Vec<u8>>(&column.type_info),
+                })
+            }
+            None => return Err(Error::ColumnIndexOutOfBounds { index: 0, len: 0 }),
+        }
+
+        arguments.apply_patches(self, &metadata.parameters).await?;
+
+        logger.log_parameters(arguments.types.iter().zip(arguments.values()).map(
+            |(ty, value)| EncodedParameter {
+                type_name: ty.name(),
+                value,
+            },
+        ));
+
+        self.wait_until_ready().await?;
+
+        self.inner.stream.write_msg(Bind {
+            portal: PortalId::UNNAMED,
+            statement,
+            formats: &[PgValueFormat::Binary],
+            num_params,
+            params: &arguments.buffer,
+            result_formats: &[PgValueFormat::Binary],
+        })?;
+        self.inner.stream.write_msg(ExecuteMessage {
+            portal: PortalId::UNNAMED,
+            limit: 1,
+        })?;
+        self.inner
+            .stream
+            .write_msg(Close::Portal(PortalId::UNNAMED))?;
+        self.write_sync();
+
+        self.inner.stream.flush().await?;
+        self.inner.query_in_progress = true;
+
+        let mut length = None;
+
+        loop {
+            let received = self.inner.stream.recv_value_into(chunk_size, writer).await;
+
+            let message = match received {
+                Ok(Either::Left(message)) => message,
+                Ok(Either::Right(value_length)) => {
+                    logger.increment_rows_returned();
+                    length = Some(value_length);
+                    continue;
+                }
+                // an error from the server is returned for this query, not left to be drained
+                Err(error @ Error::Database(_)) => {
+                    self.inner.query_in_progress = false;
+                    return Err(error);
+                }
+                Err(error) => return Err(error),
+            };
+
+            if message.format == BackendMessageFormat::ReadyForQuery {
+                self.inner.query_in_progress = false;
+                self.handle_ready_for_query(message)?;
+                break;
+            }
+        }
+
+        writer.flush()?;
+
+        length.ok_or(Error::RowNotFound)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::ext::ustr::UStr;
 use crate::{PgTypeInfo, Postgres};
 
-pub(crate) use sqlx_core::column::{Column, ColumnIndex};
+pub(crate) use sqlx_core::column::{column_index_by_name, Column, ColumnIndex};
 
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "offline", derive(serde::Serialize, serde::Deserialize))]
@@ -0,0 +1,66 @@
+use std::io;
+use std::sync::Arc;
+
+use futures_core::future::BoxFuture;
+
+use crate::connection::stream::PgStream;
+use crate::connection::CancelHandle;
+use crate::error::Error;
+use crate::message::CancelRequest;
+use crate::PgConnectOptions;
+
+/// A token for cancelling the query currently executing on a [`PgConnection`][crate::PgConnection].
+///
+/// Obtained from [`PgConnection::cancel_token()`][crate::PgConnection::cancel_token]. The token
+/// does not borrow the connection, so it can be sent to another task while the connection is busy.
+///
+/// Cancellation is best-effort: the server gives no indication of whether the request did
+/// anything. If the query already completed (or the connection is idle) the request is ignored,
+/// and a query that is cancelled fails with an error whose code is `57014` (`query_canceled`).
+#[derive(Debug, Clone)]
+pub struct PgCancelToken {
+    pub(super) options: Arc<PgConnectOptions>,
+    pub(super) process_id: u32,
+    pub(super) secret_key: u32,
+}
+
+impl PgCancelToken {
+    /// Ask the server to cancel the query currently executing on the connection.
+    ///
+    /// This opens a new connection to the server (using the same host, port and TLS settings)
+    /// to deliver the cancel request, and waits for the server to close it, which it does once
+    /// it has passed the request on to the backend.
+    pub async fn cancel(&self) -> Result<(), Error> {
+        let mut stream = PgStream::connect(&self.options).await?;
+
+        // https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-CANCELING-REQUESTS
+        stream.write(CancelRequest {
+            process_id: self.process_id,
+            secret_key: self.secret_key,
+        })?;
+
+        stream.flush().await?;
+
+        // The server closes the connection without replying. Like libpq, wait for that before
+        // returning, so that a query sent on the connection right after a cancel request for
+        // a query that already completed is not cancelled instead.
+        match stream.read_buffered(1).await {
+            Err(Error::Io(e))
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::UnexpectedEof | io::ErrorKind::ConnectionReset
+                ) =>
+            {
+                Ok(())
+            }
+            Err(e) => Err(e),
+            Ok(_) => Err(err_protocol!("unexpected reply to a cancel request")),
+        }
+    }
+}
+
+impl CancelHandle for PgCancelToken {
+    fn cancel(&self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(PgCancelToken::cancel(self))
+    }
+}
@@ -0,0 +1,437 @@
+use std::iter::Peekable;
+use std::slice;
+use std::sync::Arc;
+
+use byteorder::{BigEndian, ByteOrder};
+use sqlx_core::transaction::{SqlToken, SqlTokens};
+
+use crate::error::Error;
+use crate::query_as::query_as;
+use crate::{PgArguments, PgConnection, PgTypeInfo};
+
+/// A `NUMERIC` column with a scale, that a parameter is assigned to.
+#[derive(Debug, Clone)]
+pub(crate) struct ScaledColumn {
+    name: String,
+    scale: i32,
+}
+
+impl PgConnection {
+    /// Fail if a `NUMERIC` argument has more decimal places than the column it is assigned to.
+    ///
+    /// See [`PgConnectOptions::decimal_scale_mode()`][crate::PgConnectOptions::decimal_scale_mode].
+    pub(super) async fn check_decimal_scales(
+        &mut self,
+        sql: &str,
+        parameters: &[PgTypeInfo],
+        arguments: &PgArguments,
+    ) -> Result<(), Error> {
+        let columns = match self.inner.cache_scaled_columns.get_mut(sql) {
+            Some(columns) => Arc::clone(columns),
+            None => {
+                let columns = self.fetch_scaled_columns(sql).await?;
+                self.inner
+                    .cache_scaled_columns
+                    .insert(sql, Arc::clone(&columns));
+                columns
+            }
+        };
+
+        let values = parameters.iter().zip(arguments.values()).enumerate();
+
+        for (i, (ty, value)) in values {
+            let (Some(column), Some(value)) = (columns.get(i).and_then(Option::as_ref), value)
+            else {
+                continue;
+            };
+
+            if *ty != PgTypeInfo::NUMERIC {
+                continue;
+            }
+
+            match numeric_scale(value) {
+                Some(scale) if scale > column.scale => {
+                    return Err(Error::DecimalScaleOverflow {
+                        index: i + 1,
+                        column: column.name.clone(),
+                        scale,
+                        max_scale: column.scale,
+                    });
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The `NUMERIC` column with a scale that each parameter of `sql` is assigned to, if any.
+    async fn fetch_scaled_columns(
+        &mut self,
+        sql: &str,
+    ) -> Result<Arc<[Option<ScaledColumn>]>, Error> {
+        let Some(assignments) = parse_assignments(sql) else {
+            return Ok(Arc::new([]));
+        };
+
+        let scales: Vec<(String, i32)> = query_as(
+            "SELECT attname::text, atttypmod FROM pg_catalog.pg_attribute \
+             WHERE attrelid = $1::regclass AND atttypid = 'numeric'::regtype \
+             AND atttypmod >= 4 AND attnum > 0 AND NOT attisdropped",
+        )
+        .bind(&assignments.table)
+        .fetch_all(&mut *self)
+        .await?;
+
+        let len = assignments.columns.iter().map(|(n, _)| *n).max().unwrap_or(0);
+        let mut columns = vec![None; len];
+
+        for (n, name) in assignments.columns {
+            let Some((_, typmod)) = scales.iter().find(|(column, _)| *column == name) else {
+                continue;
+            };
+
+            // the scale is the low 11 bits of `typmod - 4`, signed since Postgres 15
+            let scale = (((typmod - 4) & 0x7ff) ^ 1024) - 1024;
+
+            columns[n - 1] = Some(ScaledColumn { name, scale });
+        }
+
+        Ok(columns.into())
+    }
+}
+
+/// The number of decimal places of a `NUMERIC` in the binary format, not counting trailing
+/// zeros; negative if the value is a multiple of 10.
+///
+/// Returns `None` for zero, `NaN` and the infinities, which fit any scale.
+fn numeric_scale(value: &[u8]) -> Option<i32> {
+    const SIGN_SPECIAL: u16 = 0xC000;
+
+    if value.len() < 8 {
+        return None;
+    }
+
+    let num_digits = usize::from(BigEndian::read_u16(&value[0..]));
+    let weight = i32::from(BigEndian::read_i16(&value[2..]));
+    let sign = BigEndian::read_u16(&value[4..]);
+
+    if sign & SIGN_SPECIAL == SIGN_SPECIAL || value.len() < 8 + num_digits * 2 {
+        return None;
+    }
+
+    // each digit is a base-10000 digit, the first one of weight `weight`
+    let (i, digit) = (0..num_digits)
+        .map(|i| (i, BigEndian::read_u16(&value[8 + i * 2..])))
+        .rfind(|&(_, digit)| digit != 0)?;
+
+    let trailing_zeros = match digit {
+        d if d % 1000 == 0 => 3,
+        d if d % 100 == 0 => 2,
+        d if d % 10 == 0 => 1,
+        _ => 0,
+    };
+
+    Some((i as i32 - weight) * 4 - trailing_zeros)
+}
+
+/// The table of an `INSERT` or `UPDATE` and the column each bare `$n` parameter is assigned to.
+#[derive(Debug, PartialEq)]
+struct Assignments {
+    /// The name of the table, quoted for a cast to `regclass`.
+    table: String,
+    columns: Vec<(usize, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// An identifier or keyword; unquoted ones are folded to lower case.
+    Ident { name: String, quoted: bool },
+    Param(usize),
+    Punct(u8),
+    /// Anything else, e.g. a literal.
+    Other,
+}
+
+type Tokens<'a> = Peekable<slice::Iter<'a, Token>>;
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Ident { name, quoted: false } if name == keyword)
+    }
+}
+
+/// Parse `INSERT INTO table (col, ..) VALUES ($1, ..), ..` or `UPDATE table SET col = $1, ..`.
+fn parse_assignments(sql: &str) -> Option<Assignments> {
+    let tokens = tokenize(sql)?;
+    let mut tokens = tokens.iter().peekable();
+
+    let first = tokens.next()?;
+
+    if first.is_keyword("insert") {
+        if !tokens.next()?.is_keyword("into") {
+            return None;
+        }
+
+        let table = parse_table_name(&mut tokens)?;
+
+        if tokens.peek()?.is_keyword("as") {
+            tokens.next();
+            tokens.next();
+        }
+
+        if tokens.next()? != &Token::Punct(b'(') {
+            return None;
+        }
+
+        let mut names = Vec::new();
+
+        loop {
+            match tokens.next()? {
+                Token::Ident { name, .. } => names.push(name.clone()),
+                _ => return None,
+            }
+
+            match tokens.next()? {
+                Token::Punct(b',') => continue,
+                Token::Punct(b')') => break,
+                _ => return None,
+            }
+        }
+
+        if !tokens.next()?.is_keyword("values") {
+            return None;
+        }
+
+        let mut columns = Vec::new();
+
+        // every row of `VALUES`
+        while tokens.next_if_eq(&&Token::Punct(b'(')).is_some() {
+            for name in &names {
+                let value = parse_expression(&mut tokens);
+
+                if let [Token::Param(n)] = value[..] {
+                    columns.push((n, name.clone()));
+                }
+
+                tokens.next_if_eq(&&Token::Punct(b','));
+            }
+
+            if tokens.next()? != &Token::Punct(b')') {
+                return None;
+            }
+
+            if tokens.next_if_eq(&&Token::Punct(b',')).is_none() {
+                break;
+            }
+        }
+
+        Some(Assignments { table, columns })
+    } else if first.is_keyword("update") {
+        if tokens.peek()?.is_keyword("only") {
+            tokens.next();
+        }
+
+        let table = parse_table_name(&mut tokens)?;
+
+        // an alias, with or without `AS`
+        while !tokens.peek()?.is_keyword("set") {
+            tokens.next();
+        }
+
+        tokens.next();
+
+        let mut columns = Vec::new();
+
+        loop {
+            let Some(Token::Ident { name, .. }) = tokens.next() else {
+                break;
+            };
+
+            if tokens.next() != Some(&Token::Punct(b'=')) {
+                break;
+            }
+
+            let value = parse_expression(&mut tokens);
+
+            if let [Token::Param(n)] = value[..] {
+                columns.push((n, name.clone()));
+            }
+
+            if tokens.next() != Some(&Token::Punct(b',')) {
+                break;
+            }
+        }
+
+        Some(Assignments { table, columns })
+    } else {
+        None
+    }
+}
+
+/// Parse a possibly schema-qualified name, quoting its parts.
+fn parse_table_name(tokens: &mut Tokens<'_>) -> Option<String> {
+    let mut table = String::new();
+
+    loop {
+        let Token::Ident { name, .. } = tokens.next()? else {
+            return None;
+        };
+
+        table.push('"');
+        table.push_str(&name.replace('"', "\"\""));
+        table.push('"');
+
+        if tokens.next_if_eq(&&Token::Punct(b'.')).is_none() {
+            return Some(table);
+        }
+
+        table.push('.');
+    }
+}
+
+/// Take the tokens of an expression, up to a `,` or `)` that isn't nested in it.
+fn parse_expression(tokens: &mut Tokens<'_>) -> Vec<Token> {
+    let mut expression = Vec::new();
+    let mut depth = 0usize;
+
+    while let Some(&token) = tokens.peek() {
+        match token {
+            Token::Punct(b',' | b')') if depth == 0 => break,
+            Token::Ident { .. } if depth == 0 && is_clause_keyword(token) => break,
+            Token::Punct(b'(') => depth += 1,
+            Token::Punct(b')') => depth -= 1,
+            _ => (),
+        }
+
+        expression.push(token.clone());
+        tokens.next();
+    }
+
+    expression
+}
+
+/// Keywords that end the `SET` list of an `UPDATE`.
+fn is_clause_keyword(token: &Token) -> bool {
+    ["from", "where", "returning"]
+        .iter()
+        .any(|keyword| token.is_keyword(keyword))
+}
+
+/// Split `sql` into tokens.
+///
+/// Returns `None` if there is more than one statement.
+fn tokenize(sql: &str) -> Option<Vec<Token>> {
+    let mut sql_tokens = SqlTokens::new(sql);
+    let mut tokens = Vec::new();
+
+    while let Some(token) = sql_tokens.next() {
+        tokens.push(match token {
+            SqlToken::Word(word) => Token::Ident {
+                name: word.to_lowercase(),
+                quoted: false,
+            },
+            SqlToken::Quoted(text) if text.starts_with('"') => Token::Ident {
+                name: text.strip_prefix('"')?.strip_suffix('"')?.replace("\"\"", "\""),
+                quoted: true,
+            },
+            SqlToken::Quoted(_) | SqlToken::Number(_) => Token::Other,
+            SqlToken::Param(param) => Token::Param(param[1..].parse().ok()?),
+            SqlToken::Open => Token::Punct(b'('),
+            SqlToken::Close => Token::Punct(b')'),
+            SqlToken::Punct(b) => Token::Punct(b),
+            // only a trailing `;`
+            SqlToken::Semicolon => return sql_tokens.next().is_none().then_some(tokens),
+        });
+    }
+
+    Some(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{numeric_scale, parse_assignments, Assignments};
+
+    fn assignments(table: &str, columns: &[(usize, &str)]) -> Option<Assignments> {
+        Some(Assignments {
+            table: table.to_owned(),
+            columns: columns
+                .iter()
+                .map(|&(n, name)| (n, name.to_owned()))
+                .collect(),
+        })
+    }
+
+    #[test]
+    fn it_parses_insert_assignments() {
+        assert_eq!(
+            parse_assignments("INSERT INTO orders (id, Total, \"Fee\") VALUES ($1, $2, $3)"),
+            assignments("\"orders\"", &[(1, "id"), (2, "total"), (3, "Fee")])
+        );
+
+        assert_eq!(
+            parse_assignments(
+                "insert into shop.orders as o (id, total) \
+                 values ($1, round($2, 2)), ($3, $4) returning id;"
+            ),
+            assignments("\"shop\".\"orders\"", &[(1, "id"), (3, "id"), (4, "total")])
+        );
+
+        assert_eq!(parse_assignments("INSERT INTO orders SELECT * FROM staged"), None);
+        assert_eq!(parse_assignments("SELECT $1; INSERT INTO t (a) VALUES ($2)"), None);
+    }
+
+    #[test]
+    fn it_parses_update_assignments() {
+        assert_eq!(
+            parse_assignments(
+                "UPDATE orders o SET total = $1, fee = fee + $2, \"Tax\" = $3 WHERE id = $4"
+            ),
+            assignments("\"orders\"", &[(1, "total"), (3, "Tax")])
+        );
+
+        assert_eq!(
+            parse_assignments("UPDATE ONLY orders SET total = $1"),
+            assignments("\"orders\"", &[(1, "total")])
+        );
+
+        // literals that contain quotes or separators
+        assert_eq!(
+            parse_assignments("UPDATE orders SET note = $$a, b = $1$$, total = $1"),
+            assignments("\"orders\"", &[(1, "total")])
+        );
+        assert_eq!(
+            parse_assignments("UPDATE orders SET note = E'it\\'s', total = $2 WHERE id = $1"),
+            assignments("\"orders\"", &[(2, "total")])
+        );
+    }
+
+    #[test]
+    fn it_computes_numeric_scale() {
+        // ndigits, weight, sign, dscale, digits...
+        let numeric = |weight: i16, digits: &[u16]| {
+            let mut buf = Vec::new();
+            buf.extend((digits.len() as u16).to_be_bytes());
+            buf.extend(weight.to_be_bytes());
+            buf.extend(0u16.to_be_bytes());
+            buf.extend(0u16.to_be_bytes());
+            for digit in digits {
+                buf.extend(digit.to_be_bytes());
+            }
+            buf
+        };
+
+        // 1.5
+        assert_eq!(numeric_scale(&numeric(0, &[1, 5000])), Some(1));
+        // 1.005
+        assert_eq!(numeric_scale(&numeric(0, &[1, 50])), Some(3));
+        // 0.00001
+        assert_eq!(numeric_scale(&numeric(-2, &[1000])), Some(5));
+        // 150
+        assert_eq!(numeric_scale(&numeric(0, &[150])), Some(-1));
+        // 1.50000 is sent without the trailing zero digits
+        assert_eq!(numeric_scale(&numeric(0, &[1, 5000, 0])), Some(1));
+        // 0
+        assert_eq!(numeric_scale(&numeric(0, &[])), None);
+    }
+}
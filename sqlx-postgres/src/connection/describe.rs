@@ -104,17 +104,15 @@ impl PgConnection {
         should_fetch: bool,
     ) -> Result<(Vec<PgColumn>, HashMap<UStr, usize>), Error> {
         let mut columns = Vec::new();
-        let mut column_names = HashMap::new();
 
         let desc = if let Some(desc) = desc {
             desc
         } else {
             // no rows
-            return Ok((columns, column_names));
+            return Ok((columns, HashMap::new()));
         };
 
         columns.reserve(desc.fields.len());
-        column_names.reserve(desc.fields.len());
 
         for (index, field) in desc.fields.into_iter().enumerate() {
             let name = UStr::from(field.name);
@@ -125,16 +123,21 @@ impl PgConnection {
 
             let column = PgColumn {
                 ordinal: index,
-                name: name.clone(),
+                name,
                 type_info,
                 relation_id: field.relation_id,
                 relation_attribute_no: field.relation_attribute_no,
             };
 
             columns.push(column);
-            column_names.insert(name, index);
         }
 
+        let column_names = self
+            .inner
+            .options
+            .duplicate_column_names
+            .column_names(columns.iter_mut().map(|column| &mut column.name));
+
         Ok((columns, column_names))
     }
 
@@ -151,7 +154,7 @@ impl PgConnection {
         Ok(params)
     }
 
-    async fn maybe_fetch_type_info_by_oid(
+    pub(super) async fn maybe_fetch_type_info_by_oid(
         &mut self,
         oid: Oid,
         should_fetch: bool,
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::HashMap;
 
 use crate::common::StatementCache;
@@ -7,7 +9,7 @@ use crate::io::StatementId;
 use crate::message::{
     Authentication, BackendKeyData, BackendMessageFormat, Password, ReadyForQuery, Startup,
 };
-use crate::{PgConnectOptions, PgConnection};
+use crate::{PgChannelBinding, PgConnectOptions, PgConnection};
 
 use super::PgConnectionInner;
 
@@ -58,8 +60,9 @@ impl PgConnection {
         // provisionally acceptable, and what additional
         // authentication is required (if any).
 
-        let mut process_id = 0;
+        let mut process_id = None;
         let mut secret_key = 0;
+        let mut channel_bound = false;
         let transaction_status;
 
         loop {
@@ -68,7 +71,27 @@ impl PgConnection {
                 BackendMessageFormat::Authentication => match message.decode()? {
                     Authentication::Ok => {
                         // the authentication exchange is successfully completed
-                        // do nothing; no more information is required to continue
+                        // no more information is required to continue, unless the server
+                        // skipped the channel binding we require
+                        if options.channel_binding == PgChannelBinding::Require && !channel_bound
+                        {
+                            return Err(Error::Tls(
+                                "channel binding required, but the server authenticated the \
+                                 client without it"
+                                    .into(),
+                            ));
+                        }
+                    }
+
+                    // don't hand the password to a server that may be a man in the middle
+                    Authentication::CleartextPassword | Authentication::Md5Password(_)
+                        if options.channel_binding == PgChannelBinding::Require =>
+                    {
+                        return Err(Error::Tls(
+                            "channel binding required, but the server requested password \
+                             authentication without it"
+                                .into(),
+                        ));
                     }
 
                     Authentication::CleartextPassword => {
@@ -98,7 +121,7 @@ impl PgConnection {
                     }
 
                     Authentication::Sasl(body) => {
-                        sasl::authenticate(&mut stream, options, body).await?;
+                        channel_bound = sasl::authenticate(&mut stream, options, body).await?;
                     }
 
                     method => {
@@ -115,7 +138,7 @@ impl PgConnection {
 
                     let data: BackendKeyData = message.decode()?;
 
-                    process_id = data.process_id;
+                    process_id = Some(data.process_id);
                     secret_key = data.secret_key;
                 }
 
@@ -135,20 +158,32 @@ impl PgConnection {
             }
         }
 
+        let mut log_settings = options.log_settings.clone();
+        log_settings.assign_connection_id();
+
         Ok(PgConnection {
             inner: Box::new(PgConnectionInner {
                 stream,
                 process_id,
                 secret_key,
+                options: Arc::new(options.clone()),
                 transaction_status,
                 transaction_depth: 0,
+                require_transaction_for_writes: false,
                 pending_ready_for_query_count: 0,
+                query_in_progress: false,
                 next_statement_id: StatementId::NAMED_START,
                 cache_statement: StatementCache::new(options.statement_cache_capacity),
+                statements_prepared: 0,
+                statement_cache_hits: 0,
+                queries_executed: 0,
+                open_statements: 0,
+                cache_cast_sql: StatementCache::new(options.statement_cache_capacity),
+                cache_scaled_columns: StatementCache::new(options.statement_cache_capacity),
                 cache_type_oid: HashMap::new(),
                 cache_type_info: HashMap::new(),
                 cache_elem_type_to_array: HashMap::new(),
-                log_settings: options.log_settings.clone(),
+                log_settings,
             }),
         })
     }
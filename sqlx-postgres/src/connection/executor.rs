@@ -2,22 +2,30 @@ use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::io::{PortalId, StatementId};
-use crate::logger::QueryLogger;
+use crate::logger::{EncodedParameter, QueryLogger};
 use crate::message::{
     self, BackendMessageFormat, Bind, Close, CommandComplete, DataRow, ParameterDescription, Parse,
-    ParseComplete, Query, RowDescription,
+    ParseComplete, Query, RowDescription, TransactionStatus,
 };
 use crate::statement::PgStatementMetadata;
+use crate::types::write_literal;
 use crate::{
-    statement::PgStatement, PgArguments, PgConnection, PgQueryResult, PgRow, PgTypeInfo,
-    PgValueFormat, Postgres,
+    statement::PgStatement, PgArguments, PgConnection, PgDecimalScaleMode, PgQueryResult, PgRow,
+    PgTypeInfo, PgValueFormat, Postgres,
 };
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
 use sqlx_core::arguments::Arguments;
+use sqlx_core::connection::{CancelHandle, Connection};
+use sqlx_core::error::BoxDynError;
+use sqlx_core::transaction::is_write_statement;
+use sqlx_core::type_info::TypeInfo;
 use sqlx_core::Either;
+use std::cmp::max;
+use std::ops::Range;
+use std::str::from_utf8;
 use std::{borrow::Cow, sync::Arc};
 
 async fn prepare(
@@ -42,10 +50,13 @@ async fn prepare(
     // flush and wait until we are re-ready
     conn.wait_until_ready().await?;
 
+    // see `ConnectOptions::sql_rewriter()`
+    let sql = conn.inner.log_settings.rewrite_sql(sql);
+
     // next we send the PARSE command to the server
     conn.inner.stream.write_msg(Parse {
         param_types: &param_types,
-        query: sql,
+        query: &sql,
         statement: id,
     })?;
 
@@ -63,6 +74,26 @@ async fn prepare(
     // indicates that the SQL query string is now successfully parsed and has semantic validity
     conn.inner.stream.recv_expect::<ParseComplete>().await?;
 
+    match recv_statement_metadata(conn, metadata).await {
+        Ok(metadata) => Ok((id, metadata)),
+        Err(error) => {
+            // the statement exists on the server, but is never going to be used;
+            // close it along with the next message that is sent
+            conn.inner
+                .stream
+                .write_msg(Close::Statement(id))
+                .expect("BUG: Close should not be too big for protocol");
+            conn.write_sync();
+
+            Err(error)
+        }
+    }
+}
+
+async fn recv_statement_metadata(
+    conn: &mut PgConnection,
+    metadata: Option<Arc<PgStatementMetadata>>,
+) -> Result<Arc<PgStatementMetadata>, Error> {
     let metadata = if let Some(metadata) = metadata {
         // each SYNC produces one READY FOR QUERY
         conn.recv_ready_for_query().await?;
@@ -92,7 +123,186 @@ async fn prepare(
         })
     };
 
-    Ok((id, metadata))
+    Ok(metadata)
+}
+
+/// Whether `error` shows that a cached statement can't be executed anymore, e.g. after
+/// `DEALLOCATE ALL` or a change to the types of the columns it returns.
+fn is_stale_statement_error(error: &Error) -> bool {
+    let Some(error) = error.as_database_error() else {
+        return false;
+    };
+
+    match error.code().as_deref() {
+        // invalid_sql_statement_name
+        Some("26000") => true,
+        // feature_not_supported
+        Some("0A000") => error
+            .message()
+            .starts_with("cached plan must not change result type"),
+        _ => false,
+    }
+}
+
+/// Whether `error` shows that a statement doesn't fit the types of its arguments, e.g.
+/// `operator does not exist: text + integer`.
+///
+/// See [`PgConnectOptions::cast_parameters()`][crate::PgConnectOptions::cast_parameters].
+fn is_parameter_type_error(error: &Error) -> bool {
+    let Some(error) = error.as_database_error() else {
+        return false;
+    };
+
+    // `datatype_mismatch`, `undefined_function` and `ambiguous_function`
+    matches!(error.code().as_deref(), Some("42804" | "42883" | "42725"))
+}
+
+/// The highest `$n` parameter referenced in `sql`, or 0 if there are none.
+fn highest_parameter(sql: &str) -> usize {
+    let mut highest = 0;
+
+    for_each_parameter(sql, |_, n| highest = max(highest, n));
+
+    highest
+}
+
+/// Reject arguments that are timestamps without a time zone.
+///
+/// See [`PgConnectOptions::strict_timezone()`][crate::PgConnectOptions::strict_timezone].
+fn check_timezone_arguments(types: &[PgTypeInfo]) -> Result<(), Error> {
+    let naive = [
+        PgTypeInfo::TIMESTAMP,
+        PgTypeInfo::TIMESTAMP_ARRAY,
+        PgTypeInfo::TS_RANGE,
+        PgTypeInfo::TS_RANGE_ARRAY,
+    ];
+
+    match types.iter().position(|ty| naive.contains(ty)) {
+        Some(i) => Err(Error::Encode(
+            format!(
+                "argument ${} is a {} without a time zone, which `strict_timezone` rejects; \
+                 bind a `DateTime<Utc>` or `OffsetDateTime` instead",
+                i + 1,
+                types[i]
+            )
+            .into(),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Replace each `$n` parameter in `sql` with the literal value of the `n`th argument.
+///
+/// See [`PgConnectOptions::prepared_statements()`][crate::PgConnectOptions::prepared_statements].
+fn inline_arguments<'a>(
+    sql: &str,
+    parameters: &[PgTypeInfo],
+    values: impl Iterator<Item = Option<&'a [u8]>>,
+) -> Result<String, BoxDynError> {
+    let literals = parameters
+        .iter()
+        .zip(values)
+        .map(|(ty, value)| {
+            let mut literal = String::new();
+            write_literal(&mut literal, ty, value)?;
+            Ok(literal)
+        })
+        .collect::<Result<Vec<_>, BoxDynError>>()?;
+
+    let mut inlined =
+        String::with_capacity(sql.len() + literals.iter().map(String::len).sum::<usize>());
+    let mut copied = 0;
+
+    for_each_parameter(sql, |range, n| {
+        // the number of arguments was checked against the highest parameter already
+        if let Some(literal) = n.checked_sub(1).and_then(|i| literals.get(i)) {
+            inlined.push_str(&sql[copied..range.start]);
+
+            // e.g. `$1[1]` and `$1.field` need the parentheses
+            inlined.push('(');
+            inlined.push_str(literal);
+            inlined.push(')');
+
+            copied = range.end;
+        }
+    });
+
+    inlined.push_str(&sql[copied..]);
+
+    Ok(inlined)
+}
+
+/// Call `f` with the byte range and the number of each `$n` parameter in `sql`.
+///
+/// String literals, quoted identifiers, dollar-quoted strings and comments are skipped.
+fn for_each_parameter(sql: &str, mut f: impl FnMut(Range<usize>, usize)) {
+    let find = |haystack: &[u8], needle: &[u8]| {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    };
+
+    let is_ident = |b: u8| b.is_ascii_alphanumeric() || b == b'_' || b == b'$';
+
+    let sql = sql.as_bytes();
+    let mut i = 0;
+
+    while i < sql.len() {
+        let rest = &sql[i..];
+
+        match rest[0] {
+            b'-' if rest.starts_with(b"--") => {
+                i += find(rest, b"\n").unwrap_or(rest.len());
+            }
+            b'/' if rest.starts_with(b"/*") => {
+                i += find(&rest[2..], b"*/").map_or(rest.len(), |end| end + 4);
+            }
+            // `E'...'` strings may contain backslash-escaped quotes
+            b'\'' if i > 0 && sql[i - 1].eq_ignore_ascii_case(&b'e') => {
+                let mut end = 1;
+
+                while end < rest.len() && rest[end] != b'\'' {
+                    end += if rest[end] == b'\\' { 2 } else { 1 };
+                }
+
+                i += end + 1;
+            }
+            quote @ (b'\'' | b'"') => {
+                i += find(&rest[1..], &[quote]).map_or(rest.len(), |end| end + 2);
+            }
+            b'$' => {
+                let digits = rest[1..].iter().take_while(|b| b.is_ascii_digit()).count();
+
+                if digits > 0 {
+                    // all ASCII digits, so this can only fail on overflow
+                    if let Ok(n) = from_utf8(&rest[1..=digits]).unwrap_or_default().parse() {
+                        f(i..i + digits + 1, n);
+                    }
+
+                    i += digits + 1;
+                    continue;
+                }
+
+                // dollar-quoted string, e.g. `$$...$$` or `$tag$...$tag$`
+                let tag_len = rest[1..].iter().position(|&b| !is_ident(b) || b == b'$');
+
+                match tag_len.filter(|&len| rest[len + 1] == b'$') {
+                    Some(tag_len) => {
+                        let tag = &rest[..tag_len + 2];
+
+                        i += find(&rest[tag.len()..], tag)
+                            .map_or(rest.len(), |end| end + tag.len() * 2);
+                    }
+                    None => i += 1,
+                }
+            }
+            // identifiers may contain `$`, e.g. `foo$1`, which is not a parameter
+            b if is_ident(b) => {
+                i += rest.iter().take_while(|&&b| is_ident(b)).count();
+            }
+            _ => i += 1,
+        }
+    }
 }
 
 async fn recv_desc_params(conn: &mut PgConnection) -> Result<ParameterDescription, Error> {
@@ -148,6 +358,19 @@ impl PgConnection {
         Ok(())
     }
 
+    // see `PoolOptions::require_transaction_for_writes`; must be called once the connection is
+    // ready, as the transaction status is that of the last `ReadyForQuery`
+    pub(crate) fn check_write_allowed(&self, query: &str) -> Result<(), Error> {
+        if self.inner.require_transaction_for_writes
+            && matches!(self.inner.transaction_status, TransactionStatus::Idle)
+            && is_write_statement(query)
+        {
+            return Err(Error::TransactionRequired);
+        }
+
+        Ok(())
+    }
+
     #[inline(always)]
     pub(crate) fn write_sync(&mut self) {
         self.inner
@@ -159,7 +382,28 @@ impl PgConnection {
         self.inner.pending_ready_for_query_count += 1;
     }
 
-    async fn get_or_prepare<'a>(
+    /// Remove the statement cached for `sql` if it is `id`, and close it on the server along with
+    /// the next message that is sent, so the statement is prepared again the next time.
+    fn invalidate_cached_statement(&mut self, sql: &str, id: StatementId) {
+        let is_cached = matches!(
+            self.inner.cache_statement.get_mut(sql),
+            Some((cached, _)) if *cached == id
+        );
+
+        if !is_cached {
+            return;
+        }
+
+        self.inner.cache_statement.remove(sql);
+        self.inner
+            .stream
+            .write_msg(Close::Statement(id))
+            .expect("BUG: Close should not be too big for protocol");
+        self.inner.open_statements -= 1;
+        self.write_sync();
+    }
+
+    pub(crate) async fn get_or_prepare<'a>(
         &mut self,
         sql: &str,
         parameters: &[PgTypeInfo],
@@ -170,14 +414,18 @@ impl PgConnection {
         metadata: Option<Arc<PgStatementMetadata>>,
     ) -> Result<(StatementId, Arc<PgStatementMetadata>), Error> {
         if let Some(statement) = self.inner.cache_statement.get_mut(sql) {
+            self.inner.statement_cache_hits += 1;
             return Ok((*statement).clone());
         }
 
         let statement = prepare(self, sql, parameters, metadata).await?;
+        self.inner.statements_prepared += 1;
+        self.inner.open_statements += 1;
 
         if store_to_cache && self.inner.cache_statement.is_enabled() {
             if let Some((id, _)) = self.inner.cache_statement.insert(sql, statement.clone()) {
                 self.inner.stream.write_msg(Close::Statement(id))?;
+                self.inner.open_statements -= 1;
                 self.write_sync();
 
                 self.inner.stream.flush().await?;
@@ -190,6 +438,135 @@ impl PgConnection {
         Ok(statement)
     }
 
+    /// Cast each `$n` parameter in `sql` to the built-in type the server infers for it, if that
+    /// is not the type of the argument bound to it.
+    ///
+    /// See [`PgConnectOptions::cast_parameters()`][crate::PgConnectOptions::cast_parameters].
+    async fn cast_parameters<'q>(
+        &mut self,
+        sql: &'q str,
+        arguments: &[PgTypeInfo],
+    ) -> Result<Cow<'q, str>, Error> {
+        // prepare the statement without argument types, so the server infers them
+        let inferred = match prepare(self, sql, &[], None).await {
+            Ok((id, metadata)) => {
+                // the statement was only prepared to describe it
+                self.inner.stream.write_msg(Close::Statement(id))?;
+                self.write_sync();
+
+                metadata
+            }
+            // the server can't infer the types either
+            Err(error) if error.as_database_error().is_some() => return Ok(Cow::Borrowed(sql)),
+            Err(error) => return Err(error),
+        };
+
+        let mut casts = Vec::with_capacity(arguments.len());
+
+        for (argument, inferred) in arguments.iter().zip(&inferred.parameters) {
+            let oid = self.resolve_type_id(&argument.0).await?;
+            let cast = inferred
+                .oid()
+                .filter(|&inferred| inferred != oid && inferred != PgTypeInfo::UNKNOWN.0.oid())
+                .and_then(PgTypeInfo::try_from_oid)
+                .map(|ty| ty.name().to_owned());
+
+            casts.push(cast);
+        }
+
+        if casts.iter().all(Option::is_none) {
+            return Ok(Cow::Borrowed(sql));
+        }
+
+        let mut cast_sql = String::with_capacity(sql.len() + 16 * casts.len());
+        let mut copied = 0;
+
+        for_each_parameter(sql, |range, n| {
+            if let Some(Some(ty)) = n.checked_sub(1).and_then(|i| casts.get(i)) {
+                cast_sql.push_str(&sql[copied..range.start]);
+
+                // e.g. `$1[1]` and `$1.field` need the parentheses
+                cast_sql.push('(');
+                cast_sql.push_str(&sql[range.clone()]);
+                cast_sql.push_str("::");
+                cast_sql.push_str(ty);
+                cast_sql.push(')');
+
+                copied = range.end;
+            }
+        });
+
+        cast_sql.push_str(&sql[copied..]);
+
+        Ok(Cow::Owned(cast_sql))
+    }
+
+    /// [`run()`][Self::run], clearing the statement cache and running the query again if its
+    /// cached statement turns out to be stale.
+    ///
+    /// See [`PgConnectOptions::recover_stale_statements()`][options].
+    ///
+    /// [options]: crate::PgConnectOptions::recover_stale_statements
+    fn run_recovering<'e, 'c: 'e, 'q: 'e>(
+        &'c mut self,
+        query: &'q str,
+        arguments: Option<PgArguments>,
+        limit: u8,
+        persistent: bool,
+        metadata_opt: Option<Arc<PgStatementMetadata>>,
+    ) -> impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e {
+        try_stream! {
+            // explicitly prepared statements are not retried, their metadata may be stale too
+            let mut retry = match &arguments {
+                Some(arguments)
+                    if self.inner.options.recover_stale_statements && metadata_opt.is_none() =>
+                {
+                    arguments.try_clone()
+                }
+                _ => None,
+            };
+            let mut arguments = arguments;
+
+            loop {
+                let mut yielded = false;
+
+                let error = {
+                    let s = self
+                        .run(query, arguments, limit, persistent, metadata_opt.clone())
+                        .await?;
+                    pin_mut!(s);
+
+                    loop {
+                        match s.try_next().await {
+                            Ok(Some(v)) => {
+                                yielded = true;
+                                r#yield!(v);
+                            }
+                            Ok(None) => return Ok(()),
+                            Err(error) => break error,
+                        }
+                    }
+                };
+
+                let stale = !yielded && is_stale_statement_error(&error);
+
+                let Some(copy) = retry.take().filter(|_| stale) else {
+                    return Err(error);
+                };
+
+                // the error aborts the transaction, if there is one
+                self.wait_until_ready().await?;
+
+                if !matches!(self.inner.transaction_status, TransactionStatus::Idle) {
+                    return Err(error);
+                }
+
+                Connection::clear_cached_statements(&mut *self).await?;
+                arguments = Some(copy);
+            }
+        }
+    }
+
     pub(crate) async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
         query: &'q str,
@@ -198,96 +575,262 @@ impl PgConnection {
         persistent: bool,
         metadata_opt: Option<Arc<PgStatementMetadata>>,
     ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
-        let mut logger = QueryLogger::new(query, self.inner.log_settings.clone());
-
         // before we continue, wait until we are "ready" to accept more queries
         self.wait_until_ready().await?;
 
+        self.check_write_allowed(query)?;
+
+        self.inner.queries_executed += 1;
+
+        let mut logger = QueryLogger::new(query, self.inner.log_settings.clone());
+
         let mut metadata: Arc<PgStatementMetadata>;
+        // the prepared statement that is executed, if any
+        let mut statement_id = None;
+
+        if let Some(arguments) = &arguments {
+            // Without any arguments, `$n` may belong to a statement in the query rather than to
+            // the query itself, e.g. `PREPARE p(int4) AS SELECT $1`; if it doesn't, the server
+            // reports the missing parameter.
+            let expected = match arguments.len() {
+                0 => 0,
+                _ => highest_parameter(query),
+            };
+
+            if expected != arguments.len() {
+                return Err(Error::BindCountMismatch {
+                    expected,
+                    provided: arguments.len(),
+                });
+            }
 
-        let format = if let Some(mut arguments) = arguments {
-            // Check this before we write anything to the stream.
-            //
-            // Note: Postgres actually interprets this value as unsigned,
-            // making the max number of parameters 65535, not 32767
-            // https://github.com/launchbadge/sqlx/issues/3464
-            // https://www.postgresql.org/docs/current/limits.html
-            let num_params = u16::try_from(arguments.len()).map_err(|_| {
-                err_protocol!(
-                    "PgConnection::run(): too many arguments for query: {}",
-                    arguments.len()
-                )
-            })?;
-
-            // prepare the statement if this our first time executing it
-            // always return the statement ID here
-            let (statement, metadata_) = self
-                .get_or_prepare(query, &arguments.types, persistent, metadata_opt)
-                .await?;
-
-            metadata = metadata_;
-
-            // patch holes created during encoding
-            arguments.apply_patches(self, &metadata.parameters).await?;
-
-            // consume messages till `ReadyForQuery` before bind and execute
-            self.wait_until_ready().await?;
+            if self.inner.options.strict_timezone {
+                check_timezone_arguments(&arguments.types)?;
+            }
+        }
 
-            // bind to attach the arguments to the statement and create a portal
-            self.inner.stream.write_msg(Bind {
-                portal: PortalId::UNNAMED,
-                statement,
-                formats: &[PgValueFormat::Binary],
-                num_params,
-                params: &arguments.buffer,
-                result_formats: &[PgValueFormat::Binary],
-            })?;
-
-            // executes the portal up to the passed limit
-            // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
-            self.inner.stream.write_msg(message::Execute {
-                portal: PortalId::UNNAMED,
-                limit: limit.into(),
-            })?;
-            // From https://www.postgresql.org/docs/current/protocol-flow.html:
-            //
-            // "An unnamed portal is destroyed at the end of the transaction, or as
-            // soon as the next Bind statement specifying the unnamed portal as
-            // destination is issued. (Note that a simple Query message also
-            // destroys the unnamed portal."
-
-            // we ask the database server to close the unnamed portal and free the associated resources
-            // earlier - after the execution of the current query.
-            self.inner
-                .stream
-                .write_msg(Close::Portal(PortalId::UNNAMED))?;
-
-            // finally, [Sync] asks postgres to process the messages that we sent and respond with
-            // a [ReadyForQuery] message when it's completely done. Theoretically, we could send
-            // dozens of queries before a [Sync] and postgres can handle that. Execution on the server
-            // is still serial but it would reduce round-trips. Some kind of builder pattern that is
-            // termed batching might suit this.
-            self.write_sync();
-
-            // prepared statements are binary
-            PgValueFormat::Binary
-        } else {
-            // Query will trigger a ReadyForQuery
-            self.inner.stream.write_msg(Query(query))?;
-            self.inner.pending_ready_for_query_count += 1;
-
-            // metadata starts out as "nothing"
-            metadata = Arc::new(PgStatementMetadata::default());
-
-            // and unprepared statements are text
-            PgValueFormat::Text
+        // the SQL of the prepared statement, see `PgConnectOptions::cast_parameters()`
+        let mut sql = Cow::Borrowed(query);
+
+        let format = match arguments {
+            Some(mut arguments) if !self.inner.options.prepared_statements => {
+                // see `PgConnectOptions::prepared_statements()`
+                let mut parameters = Vec::with_capacity(arguments.types.len());
+
+                for ty in &arguments.types {
+                    let oid = self.resolve_type_id(&ty.0).await?;
+                    parameters.push(self.maybe_fetch_type_info_by_oid(oid, true).await?);
+                }
+
+                // patch holes created during encoding
+                arguments.apply_patches(self, &parameters).await?;
+
+                logger.log_parameters(arguments.types.iter().zip(arguments.values()).map(
+                    |(ty, value)| EncodedParameter {
+                        type_name: ty.name(),
+                        value,
+                    },
+                ));
+
+                let sql = inline_arguments(query, &parameters, arguments.values())
+                    .map_err(Error::Encode)?;
+
+                // fetching the parameter types may have run queries
+                self.wait_until_ready().await?;
+
+                let sql = self.inner.log_settings.rewrite_sql(&sql);
+
+                // Query will trigger a ReadyForQuery
+                self.inner.stream.write_msg(Query(&sql))?;
+                self.inner.pending_ready_for_query_count += 1;
+
+                metadata = Arc::new(PgStatementMetadata::default());
+
+                // the simple query protocol only returns text
+                PgValueFormat::Text
+            }
+            Some(mut arguments) => {
+                // Check this before we write anything to the stream.
+                //
+                // Note: Postgres actually interprets this value as unsigned,
+                // making the max number of parameters 65535, not 32767
+                // https://github.com/launchbadge/sqlx/issues/3464
+                // https://www.postgresql.org/docs/current/limits.html
+                let num_params = u16::try_from(arguments.len()).map_err(|_| {
+                    err_protocol!(
+                        "PgConnection::run(): too many arguments for query: {}",
+                        arguments.len()
+                    )
+                })?;
+
+                // see `PgConnectOptions::offline_describe_cache()`
+                #[cfg(feature = "offline")]
+                let metadata_opt = metadata_opt.or_else(|| {
+                    let cache = self.inner.options.offline_describe_cache.clone()?;
+
+                    if self.inner.cache_statement.contains_key(query) {
+                        return None;
+                    }
+
+                    cache.get(query, self.inner.options.duplicate_column_names)
+                });
+
+                // see `PgConnectOptions::cast_parameters()`
+                let may_cast = self.inner.options.cast_parameters && metadata_opt.is_none();
+
+                // a failed statement aborts the transaction it is in, so there is no way to
+                // describe it again and retry
+                let may_retry_cast =
+                    may_cast && matches!(self.inner.transaction_status, TransactionStatus::Idle);
+
+                if let Some(cast) = self.inner.cache_cast_sql.get_mut(query).filter(|_| may_cast) {
+                    sql = Cow::Owned(cast.clone());
+                }
+
+                // prepare the statement if this our first time executing it
+                // always return the statement ID here
+                let (statement, metadata_) = match self
+                    .get_or_prepare(&sql, &arguments.types, persistent, metadata_opt)
+                    .await
+                {
+                    Err(error) if may_retry_cast && is_parameter_type_error(&error) => {
+                        let cast = self.cast_parameters(query, &arguments.types).await?;
+
+                        if cast == sql {
+                            return Err(error);
+                        }
+
+                        let prepared = self
+                            .get_or_prepare(&cast, &arguments.types, persistent, None)
+                            .await?;
+
+                        match &cast {
+                            Cow::Owned(cast) => {
+                                self.inner.cache_cast_sql.insert(query, cast.clone());
+                            }
+                            Cow::Borrowed(_) => {
+                                self.inner.cache_cast_sql.remove(query);
+                            }
+                        }
+
+                        sql = cast;
+                        prepared
+                    }
+                    prepared => prepared?,
+                };
+
+                metadata = metadata_;
+                statement_id = Some(statement);
+
+                // patch holes created during encoding
+                arguments.apply_patches(self, &metadata.parameters).await?;
+
+                logger.log_parameters(arguments.types.iter().zip(arguments.values()).map(
+                    |(ty, value)| EncodedParameter {
+                        type_name: ty.name(),
+                        value,
+                    },
+                ));
+
+                // see `PgConnectOptions::decimal_scale_mode()`
+                if self.inner.options.decimal_scale_mode == PgDecimalScaleMode::Error
+                    && metadata.parameters.contains(&PgTypeInfo::NUMERIC)
+                {
+                    self.check_decimal_scales(query, &metadata.parameters, &arguments)
+                        .await?;
+                }
+
+                // consume messages till `ReadyForQuery` before bind and execute
+                self.wait_until_ready().await?;
+
+                // a format per column is only needed if one of them can't be sent as binary
+                let text_types = &self.inner.options.text_types;
+                let result_formats: Vec<PgValueFormat> = if metadata
+                    .columns
+                    .iter()
+                    .any(|column| column.type_info.result_format(text_types) == PgValueFormat::Text)
+                {
+                    metadata
+                        .columns
+                        .iter()
+                        .map(|column| column.type_info.result_format(text_types))
+                        .collect()
+                } else {
+                    vec![PgValueFormat::Binary]
+                };
+
+                // bind to attach the arguments to the statement and create a portal
+                self.inner.stream.write_msg(Bind {
+                    portal: PortalId::UNNAMED,
+                    statement,
+                    formats: &[PgValueFormat::Binary],
+                    num_params,
+                    params: &arguments.buffer,
+                    result_formats: &result_formats,
+                })?;
+
+                // executes the portal up to the passed limit
+                // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
+                self.inner.stream.write_msg(message::Execute {
+                    portal: PortalId::UNNAMED,
+                    limit: limit.into(),
+                })?;
+                // From https://www.postgresql.org/docs/current/protocol-flow.html:
+                //
+                // "An unnamed portal is destroyed at the end of the transaction, or as
+                // soon as the next Bind statement specifying the unnamed portal as
+                // destination is issued. (Note that a simple Query message also
+                // destroys the unnamed portal."
+
+                // we ask the database server to close the unnamed portal and free the associated resources
+                // earlier - after the execution of the current query.
+                self.inner
+                    .stream
+                    .write_msg(Close::Portal(PortalId::UNNAMED))?;
+
+                // finally, [Sync] asks postgres to process the messages that we sent and respond with
+                // a [ReadyForQuery] message when it's completely done. Theoretically, we could send
+                // dozens of queries before a [Sync] and postgres can handle that. Execution on the server
+                // is still serial but it would reduce round-trips. Some kind of builder pattern that is
+                // termed batching might suit this.
+                self.write_sync();
+
+                // prepared statements are binary
+                PgValueFormat::Binary
+            }
+            None => {
+                let query = self.inner.log_settings.rewrite_sql(query);
+
+                // Query will trigger a ReadyForQuery
+                self.inner.stream.write_msg(Query(&query))?;
+                self.inner.pending_ready_for_query_count += 1;
+
+                // metadata starts out as "nothing"
+                metadata = Arc::new(PgStatementMetadata::default());
+
+                // and unprepared statements are text
+                PgValueFormat::Text
+            }
         };
 
         self.inner.stream.flush().await?;
+        self.inner.query_in_progress = true;
 
         Ok(try_stream! {
             loop {
-                let message = self.inner.stream.recv().await?;
+                let message = match self.inner.stream.recv().await {
+                    Ok(message) => message,
+                    Err(error) => {
+                        // the error is returned for this query, not left to be drained
+                        self.inner.query_in_progress = false;
+
+                        if let Some(id) = statement_id.filter(|_| is_stale_statement_error(&error)) {
+                            self.invalidate_cached_statement(&sql, id);
+                        }
+
+                        return Err(error);
+                    }
+                };
 
                 match message.format {
                     BackendMessageFormat::BindComplete
@@ -346,6 +889,9 @@ impl PgConnection {
                             data,
                             format,
                             metadata: Arc::clone(&metadata),
+                            lenient_integers: self.inner.options.lenient_integer_decode,
+                            text_types: Arc::clone(&self.inner.options.text_types),
+                            strict_timezone: self.inner.options.strict_timezone,
                         };
 
                         r#yield!(Either::Right(row));
@@ -353,6 +899,7 @@ impl PgConnection {
 
                     BackendMessageFormat::ReadyForQuery => {
                         // processing of the query string is complete
+                        self.inner.query_in_progress = false;
                         self.handle_ready_for_query(message)?;
                         break;
                     }
@@ -393,7 +940,7 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
 
         Box::pin(try_stream! {
             let arguments = arguments?;
-            let s = self.run(sql, arguments, 0, persistent, metadata).await?;
+            let s = self.run_recovering(sql, arguments, 0, persistent, metadata);
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
@@ -420,7 +967,7 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
 
         Box::pin(async move {
             let arguments = arguments?;
-            let s = self.run(sql, arguments, 1, persistent, metadata).await?;
+            let s = self.run_recovering(sql, arguments, 1, persistent, metadata);
             pin_mut!(s);
 
             // With deferred constraints we need to check all responses as we
@@ -479,4 +1026,25 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
             })
         })
     }
+
+    fn cancel_handle(&self) -> Option<Arc<dyn CancelHandle>> {
+        Connection::cancel_handle(&**self)
+    }
+}
+
+#[test]
+fn test_highest_parameter() {
+    assert_eq!(highest_parameter("SELECT 1"), 0);
+    assert_eq!(highest_parameter("SELECT $1, $2"), 2);
+    assert_eq!(highest_parameter("SELECT $2"), 2);
+    assert_eq!(highest_parameter("SELECT $10 + $9"), 10);
+    assert_eq!(highest_parameter("SELECT $1::int4+$3"), 3);
+    assert_eq!(highest_parameter("SELECT '$5', \"$5\", $1"), 1);
+    assert_eq!(highest_parameter("SELECT 'it''s $5', $1"), 1);
+    assert_eq!(highest_parameter(r"SELECT E'it\'s $5', $1"), 1);
+    assert_eq!(
+        highest_parameter("SELECT $$ $5 $$, $tag$ $$ $5 $tag$, $1"),
+        1
+    );
+    assert_eq!(highest_parameter("SELECT foo$5 -- $5\n, $1 /* $5 */"), 1);
 }
@@ -1,5 +1,6 @@
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::HashMap;
 use futures_core::future::BoxFuture;
@@ -7,21 +8,30 @@ use futures_util::FutureExt;
 
 use crate::common::StatementCache;
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::ext::ustr::UStr;
 use crate::io::StatementId;
 use crate::message::{
     BackendMessageFormat, Close, Query, ReadyForQuery, ReceivedMessage, Terminate,
     TransactionStatus,
 };
+use crate::row::Row;
+use crate::query::{query, QueryStatsCounters};
+use crate::query_as::query_as;
+use crate::query_scalar::query_scalar;
 use crate::statement::PgStatementMetadata;
 use crate::transaction::Transaction;
+use crate::type_info::{PgCustomType, PgType, PgTypeKind};
 use crate::types::Oid;
 use crate::{PgConnectOptions, PgTypeInfo, Postgres};
 
 pub(crate) use sqlx_core::connection::*;
 
+pub use self::cancel::PgCancelToken;
 pub use self::stream::PgStream;
 
+mod cancel;
+mod decimal_scale;
 pub(crate) mod describe;
 mod establish;
 mod executor;
@@ -42,14 +52,16 @@ pub struct PgConnectionInner {
 
     // process id of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
-    process_id: u32,
+    // `None` if the server never sent `BackendKeyData`
+    process_id: Option<u32>,
 
     // secret key of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     secret_key: u32,
 
+    // options used to open a separate connection for cancel requests
+    options: Arc<PgConnectOptions>,
+
     // sequence of statement IDs for use in preparing statements
     // in PostgreSQL, the statement is prepared to a user-supplied identifier
     next_statement_id: StatementId,
@@ -57,6 +69,23 @@ pub struct PgConnectionInner {
     // cache statement by query string to the id and columns
     cache_statement: StatementCache<(StatementId, Arc<PgStatementMetadata>)>,
 
+    // number of statements prepared and statement cache hits over the life of the connection
+    statements_prepared: u64,
+    statement_cache_hits: u64,
+
+    // number of statements executed over the life of the connection
+    pub(crate) queries_executed: u64,
+
+    // number of prepared statements currently open on the server
+    open_statements: usize,
+
+    // query string to the same query with casts, see `PgConnectOptions::cast_parameters()`
+    cache_cast_sql: StatementCache<String>,
+
+    // query string to the column each parameter is assigned to, see
+    // `PgConnectOptions::decimal_scale_mode()`
+    cache_scaled_columns: StatementCache<Arc<[Option<decimal_scale::ScaledColumn>]>>,
+
     // cache user-defined types by id <-> info
     cache_type_info: HashMap<Oid, PgTypeInfo>,
     cache_type_oid: HashMap<UStr, Oid>,
@@ -65,11 +94,18 @@ pub struct PgConnectionInner {
     // number of ReadyForQuery messages that we are currently expecting
     pub(crate) pending_ready_for_query_count: usize,
 
+    // whether the results of a query are being received; if this is still set when
+    // `wait_until_ready()` is called, the query was abandoned, e.g. because it timed out
+    pub(crate) query_in_progress: bool,
+
     // current transaction status
     transaction_status: TransactionStatus,
     pub(crate) transaction_depth: usize,
 
-    log_settings: LogSettings,
+    // see `PoolOptions::require_transaction_for_writes`
+    pub(crate) require_transaction_for_writes: bool,
+
+    pub(crate) log_settings: LogSettings,
 }
 
 impl PgConnection {
@@ -78,6 +114,138 @@ impl PgConnection {
         self.inner.stream.server_version_num
     }
 
+    /// The process ID of the server backend handling this connection.
+    ///
+    /// This is the value reported in the `BackendKeyData` message at startup and matches
+    /// the `pid` column of `pg_stat_activity` (and the result of `pg_backend_pid()`).
+    ///
+    /// Returns `None` if the server did not send `BackendKeyData`, which some proxies and
+    /// Postgres-compatible databases omit.
+    ///
+    /// Also available on a `PoolConnection<Postgres>` through `Deref`.
+    pub fn server_process_id(&self) -> Option<u32> {
+        self.inner.process_id
+    }
+
+    /// The number of prepared statements this connection currently holds open on the server.
+    ///
+    /// This includes the statements in the cache (see
+    /// [`cached_statements_size()`][Connection::cached_statements_size]) and any that were
+    /// prepared outside of it, e.g. for non-persistent queries, and are still open. A count that
+    /// keeps growing past the cache capacity points to statements that are never closed.
+    pub fn prepared_statement_count(&self) -> usize {
+        self.inner.open_statements
+    }
+
+    /// Returns a token that can be used to cancel the query currently executing on this
+    /// connection from another task.
+    ///
+    /// Returns `None` if the server did not send `BackendKeyData`
+    /// (see [`server_process_id()`][Self::server_process_id]), or if the connection was made
+    /// over a socket passed to
+    /// [`PgConnectOptions::connect_fd()`][crate::PgConnectOptions::connect_fd], since a cancel
+    /// request needs a connection of its own.
+    pub fn cancel_token(&self) -> Option<PgCancelToken> {
+        #[cfg(unix)]
+        if self.inner.options.inherited_fd.is_some() {
+            return None;
+        }
+
+        Some(PgCancelToken {
+            options: Arc::clone(&self.inner.options),
+            process_id: self.inner.process_id?,
+            secret_key: self.inner.secret_key,
+        })
+    }
+
+    /// Register the enum type `name`, so that its values and arrays decode as
+    /// [`PgEnumValue`][crate::types::PgEnumValue] with only the given `variants`.
+    ///
+    /// This looks up the enum and its array type now and caches them for the life of the
+    /// connection, so they also decode from the text format, for which the driver can't look
+    /// up unknown types in the middle of a query. Decoding a label that is not in `variants`
+    /// fails, as does registering a variant the enum does not have. Register the enum before
+    /// preparing queries that return it; statements already in the cache keep the type as
+    /// it was.
+    ///
+    /// ```rust,no_run
+    /// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+    /// use sqlx::postgres::types::PgEnumValue;
+    /// use sqlx::Row;
+    ///
+    /// // CREATE TYPE status AS ENUM ('new', 'open', 'closed');
+    /// conn.register_enum("status", &["new", "open", "closed"]).await?;
+    ///
+    /// let row = sqlx::raw_sql("SELECT '{new,closed}'::status[]")
+    ///     .fetch_one(&mut *conn)
+    ///     .await?;
+    ///
+    /// let statuses: Vec<PgEnumValue> = row.try_get(0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn register_enum(&mut self, name: &str, variants: &[&str]) -> Result<(), Error> {
+        let oid = self.fetch_type_id_by_name(name).await?;
+        let info = self.maybe_fetch_type_info_by_oid(oid, true).await?;
+
+        let PgTypeKind::Enum(labels) = info.kind() else {
+            return Err(Error::Configuration(
+                format!("type {name:?} is not an enum").into(),
+            ));
+        };
+
+        if let Some(missing) = variants
+            .iter()
+            .find(|variant| !labels.iter().any(|label| label == *variant))
+        {
+            return Err(Error::Configuration(
+                format!("enum {name:?} has no variant {missing:?}").into(),
+            ));
+        }
+
+        let (array_oid,): (Oid,) =
+            query_as("SELECT typarray FROM pg_catalog.pg_type WHERE oid = $1")
+                .bind(oid)
+                .fetch_one(&mut *self)
+                .await?;
+
+        let array_info = self.maybe_fetch_type_info_by_oid(array_oid, true).await?;
+
+        let info = PgTypeInfo(PgType::Custom(Arc::new(PgCustomType {
+            oid,
+            name: info.0.name().to_string().into(),
+            kind: PgTypeKind::Enum(variants.iter().copied().map(String::from).collect()),
+        })));
+
+        let array_info = PgTypeInfo(PgType::Custom(Arc::new(PgCustomType {
+            oid: array_oid,
+            name: array_info.0.name().to_string().into(),
+            kind: PgTypeKind::Array(info.clone()),
+        })));
+
+        self.inner.cache_type_info.insert(oid, info);
+        self.inner.cache_type_info.insert(array_oid, array_info);
+        self.inner.cache_elem_type_to_array.insert(oid, array_oid);
+
+        Ok(())
+    }
+
+    // applies `PgConnectOptions::plan_cache_mode()` and `lock_timeout()`, after connecting and
+    // after `DISCARD ALL`
+    pub(crate) async fn apply_session_settings(&mut self) -> Result<(), Error> {
+        if let Some(mode) = self.inner.options.plan_cache_mode {
+            self.execute(&*format!("SET plan_cache_mode = {}", mode.as_str()))
+                .await?;
+        }
+
+        if let Some(timeout) = self.inner.options.lock_timeout {
+            self.execute(&*format!("SET lock_timeout = {}", timeout.as_millis()))
+                .await?;
+        }
+
+        Ok(())
+    }
+
     // will return when the connection is ready for another query
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
         if !self.inner.stream.write_buffer_mut().is_empty() {
@@ -85,13 +253,21 @@ impl PgConnection {
         }
 
         while self.inner.pending_ready_for_query_count > 0 {
-            let message = self.inner.stream.recv().await?;
+            let message = match self.inner.stream.recv().await {
+                Ok(message) => message,
+                // the results being drained belong to a query that was abandoned; its error is
+                // not for the query we are about to run
+                Err(Error::Database(_)) if self.inner.query_in_progress => continue,
+                Err(e) => return Err(e),
+            };
 
             if let BackendMessageFormat::ReadyForQuery = message.format {
                 self.handle_ready_for_query(message)?;
             }
         }
 
+        self.inner.query_in_progress = false;
+
         Ok(())
     }
 
@@ -105,7 +281,7 @@ impl PgConnection {
     }
 
     #[inline(always)]
-    fn handle_ready_for_query(&mut self, message: ReceivedMessage) -> Result<(), Error> {
+    pub(crate) fn handle_ready_for_query(&mut self, message: ReceivedMessage) -> Result<(), Error> {
         self.inner.pending_ready_for_query_count = self
             .inner
             .pending_ready_for_query_count
@@ -122,7 +298,8 @@ impl PgConnection {
     /// Used for rolling back transactions and releasing advisory locks.
     #[inline(always)]
     pub(crate) fn queue_simple_query(&mut self, query: &str) -> Result<(), Error> {
-        self.inner.stream.write_msg(Query(query))?;
+        let query = self.inner.log_settings.rewrite_sql(query);
+        self.inner.stream.write_msg(Query(&query))?;
         self.inner.pending_ready_for_query_count += 1;
 
         Ok(())
@@ -140,6 +317,10 @@ impl Connection for PgConnection {
 
     type Options = PgConnectOptions;
 
+    fn id(&self) -> u64 {
+        self.inner.log_settings.connection_id
+    }
+
     fn close(mut self) -> BoxFuture<'static, Result<(), Error>> {
         // The normal, graceful termination procedure is that the frontend sends a Terminate
         // message and immediately closes the connection.
@@ -163,18 +344,164 @@ impl Connection for PgConnection {
         })
     }
 
+    fn cancel_handle(&self) -> Option<Arc<dyn CancelHandle>> {
+        self.cancel_token()
+            .map(|token| Arc::new(token) as Arc<dyn CancelHandle>)
+    }
+
+    fn set_require_transaction_for_writes(&mut self, require: bool) {
+        self.inner.require_transaction_for_writes = require;
+    }
+
     fn ping(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         // Users were complaining about this showing up in query statistics on the server.
         // By sending a comment we avoid an error if the connection was in the middle of a rowset
         // self.execute("/* SQLx ping */").map_ok(|_| ()).boxed()
 
         Box::pin(async move {
+            if let Some(expected) = self.inner.options.verify_search_path.clone() {
+                let actual: String = self.fetch_one("SHOW search_path").await?.try_get(0)?;
+
+                if actual != expected {
+                    return Err(Error::Configuration(
+                        format!("search_path is {actual:?}, expected {expected:?}").into(),
+                    ));
+                }
+
+                return Ok(());
+            }
+
             // The simplest call-and-response that's possible.
             self.write_sync();
             self.wait_until_ready().await
         })
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.wait_until_ready().await?;
+
+            // `DISCARD ALL` cannot run inside a transaction block.
+            if !matches!(self.inner.transaction_status, TransactionStatus::Idle) {
+                self.execute("ROLLBACK").await?;
+            }
+
+            self.inner.transaction_depth = 0;
+
+            self.execute("DISCARD ALL").await?;
+
+            // The server deallocated all prepared statements, so there is nothing to close.
+            self.inner.cache_statement.clear();
+            self.inner.open_statements = 0;
+            self.inner.cache_type_oid.clear();
+
+            self.apply_session_settings().await?;
+
+            Ok(())
+        })
+    }
+
+    fn rollback_open_transaction(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            self.wait_until_ready().await?;
+
+            if !matches!(self.inner.transaction_status, TransactionStatus::Idle) {
+                self.execute("ROLLBACK").await?;
+            }
+
+            self.inner.transaction_depth = 0;
+
+            Ok(())
+        })
+    }
+
+    fn server_max_connections(&mut self) -> BoxFuture<'_, Result<Option<u32>, Error>> {
+        Box::pin(async move {
+            let row = self.fetch_one("SHOW max_connections").await?;
+            let max: String = row.try_get(0)?;
+
+            max.parse().map(Some).map_err(|_| {
+                err_protocol!("expected max_connections to be an integer, got {max:?}")
+            })
+        })
+    }
+
+    #[doc(hidden)]
+    fn query_stats_counters<'a>(
+        &'a mut self,
+        sql: &'a str,
+    ) -> BoxFuture<'a, Result<QueryStatsCounters, Error>> {
+        Box::pin(async move {
+            // `total_exec_time` is only available since Postgres 13
+            if self.server_version_num().map_or(true, |version| version < 130000) {
+                return Ok(QueryStatsCounters::default());
+            }
+
+            // querying `pg_stat_statements` fails if the library isn't loaded, which would
+            // abort the current transaction
+            let available: bool = query_scalar(
+                "SELECT to_regclass('pg_stat_statements') IS NOT NULL \
+                 AND current_setting('shared_preload_libraries') ~ 'pg_stat_statements'",
+            )
+            .fetch_one(&mut *self)
+            .await?;
+
+            if !available {
+                return Ok(QueryStatsCounters::default());
+            }
+
+            let sql = self.inner.log_settings.rewrite_sql(sql).into_owned();
+
+            let exec_time_ms: f64 = query_scalar(
+                "SELECT coalesce(sum(total_exec_time), 0)::float8 FROM pg_stat_statements \
+                 WHERE query = $1 \
+                 AND userid = (SELECT oid FROM pg_roles WHERE rolname = current_user) \
+                 AND dbid = (SELECT oid FROM pg_database WHERE datname = current_database())",
+            )
+            .bind(sql)
+            .fetch_one(&mut *self)
+            .await?;
+
+            Ok(QueryStatsCounters {
+                server_time: Some(Duration::from_secs_f64(exec_time_ms / 1000.0)),
+                ..QueryStatsCounters::default()
+            })
+        })
+    }
+
+    #[doc(hidden)]
+    fn server_now_micros(&mut self) -> BoxFuture<'_, Result<i64, Error>> {
+        Box::pin(async move {
+            let row = self
+                .fetch_one("SELECT (extract(epoch FROM now()) * 1000000)::int8")
+                .await?;
+
+            row.try_get(0)
+        })
+    }
+
+    #[doc(hidden)]
+    fn set_tag<'a>(&'a mut self, tag: Option<&'a str>) -> BoxFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            match tag {
+                Some(tag) => {
+                    query("SELECT set_config('application_name', $1, false)")
+                        .bind(tag)
+                        .execute(&mut *self)
+                        .await?;
+                }
+                None => {
+                    // back to the value from the startup packet; the reply is read by
+                    // `wait_until_ready()` before the next command
+                    self.queue_simple_query("RESET application_name")?;
+                    self.inner.stream.flush().await?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -182,10 +509,22 @@ impl Connection for PgConnection {
         Transaction::begin(self)
     }
 
+    fn queries_executed(&self) -> u64 {
+        self.inner.queries_executed
+    }
+
     fn cached_statements_size(&self) -> usize {
         self.inner.cache_statement.len()
     }
 
+    fn statements_prepared(&self) -> u64 {
+        self.inner.statements_prepared
+    }
+
+    fn statement_cache_hits(&self) -> u64 {
+        self.inner.statement_cache_hits
+    }
+
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             self.inner.cache_type_oid.clear();
@@ -199,6 +538,8 @@ impl Connection for PgConnection {
                 cleared += 1;
             }
 
+            self.inner.open_statements -= cleared;
+
             if cleared > 0 {
                 self.write_sync();
                 self.inner.stream.flush().await?;
@@ -1,25 +1,30 @@
 use crate::connection::stream::PgStream;
 use crate::error::Error;
 use crate::message::{Authentication, AuthenticationSasl, SaslInitialResponse, SaslResponse};
-use crate::PgConnectOptions;
+use crate::{PgChannelBinding, PgConnectOptions};
 use hmac::{Hmac, Mac};
 use rand::Rng;
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use sqlx_core::net::Socket;
 use stringprep::saslprep;
 
 use base64::prelude::{Engine as _, BASE64_STANDARD};
 
-const GS2_HEADER: &str = "n,,";
+// gs2-header = gs2-cbind-flag "," [ authzid ] ","
+const GS2_HEADER_NO_BINDING: &str = "n,,";
+const GS2_HEADER_BINDING_UNSUPPORTED_BY_SERVER: &str = "y,,";
+const GS2_HEADER_BINDING: &str = "p=tls-server-end-point,,";
 const CHANNEL_ATTR: &str = "c";
 const USERNAME_ATTR: &str = "n";
 const CLIENT_PROOF_ATTR: &str = "p";
 const NONCE_ATTR: &str = "r";
 
+/// Authenticate with SCRAM, returning whether channel binding was used.
 pub(crate) async fn authenticate(
     stream: &mut PgStream,
     options: &PgConnectOptions,
     data: AuthenticationSasl,
-) -> Result<(), Error> {
+) -> Result<bool, Error> {
     let mut has_sasl = false;
     let mut has_sasl_plus = false;
     let mut unknown = Vec::new();
@@ -47,9 +52,47 @@ pub(crate) async fn authenticate(
         ));
     }
 
-    // channel-binding = "c=" base64
+    // see `PgConnectOptions::channel_binding()`
+    let cbind_data = match options.channel_binding {
+        PgChannelBinding::Disable => None,
+        _ => stream.socket().peer_certificate(),
+    };
+
+    let (gs2_header, cbind_data) = match cbind_data {
+        Some(certificate) if has_sasl_plus => {
+            (GS2_HEADER_BINDING, tls_server_end_point(&certificate))
+        }
+
+        _ if options.channel_binding == PgChannelBinding::Require => {
+            let reason = if has_sasl_plus {
+                "the connection is not encrypted"
+            } else {
+                "the server does not support SCRAM-SHA-256-PLUS"
+            };
+
+            return Err(Error::Tls(
+                format!("channel binding required, but {reason}").into(),
+            ));
+        }
+
+        _ if !has_sasl => {
+            return Err(err_protocol!(
+                "server requires SCRAM-SHA-256-PLUS, but the connection is not encrypted"
+            ));
+        }
+
+        // we could have bound the channel, but the server didn't offer it
+        Some(_) => (GS2_HEADER_BINDING_UNSUPPORTED_BY_SERVER, Vec::new()),
+
+        None => (GS2_HEADER_NO_BINDING, Vec::new()),
+    };
+
+    let plus = gs2_header == GS2_HEADER_BINDING;
+
+    // channel-binding = "c=" base64(gs2-header [cbind-data])
     let mut channel_binding = format!("{CHANNEL_ATTR}=");
-    BASE64_STANDARD.encode_string(GS2_HEADER, &mut channel_binding);
+    let cbind_input = [gs2_header.as_bytes(), &cbind_data].concat();
+    BASE64_STANDARD.encode_string(cbind_input, &mut channel_binding);
 
     // "n=" saslname ;; Usernames are prepared using SASLprep.
     let username = format!("{}={}", USERNAME_ATTR, options.username);
@@ -65,12 +108,12 @@ pub(crate) async fn authenticate(
     // client-first-message-bare = [reserved-mext ","] username "," nonce ["," extensions]
     let client_first_message_bare = format!("{username},{nonce}");
 
-    let client_first_message = format!("{GS2_HEADER}{client_first_message_bare}");
+    let client_first_message = format!("{gs2_header}{client_first_message_bare}");
 
     stream
         .send(SaslInitialResponse {
             response: &client_first_message,
-            plus: false,
+            plus,
         })
         .await?;
 
@@ -156,7 +199,69 @@ pub(crate) async fn authenticate(
     // authentication is only considered valid if this verification passes
     mac.verify_slice(&data.verifier).map_err(Error::protocol)?;
 
-    Ok(())
+    Ok(plus)
+}
+
+// The `tls-server-end-point` channel binding (RFC 5929): the hash of the server's certificate,
+// with the hash function of its signature algorithm, where MD5 and SHA-1 are replaced by SHA-256.
+fn tls_server_end_point(certificate: &[u8]) -> Vec<u8> {
+    // sha384WithRSAEncryption, sha512WithRSAEncryption, ecdsa-with-SHA384, ecdsa-with-SHA512
+    const SHA384: [&[u8]; 2] = [
+        &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0C],
+        &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x03],
+    ];
+    const SHA512: [&[u8]; 2] = [
+        &[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0D],
+        &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x04],
+    ];
+
+    match signature_algorithm(certificate) {
+        Some(oid) if SHA384.contains(&oid) => Sha384::digest(certificate).to_vec(),
+        Some(oid) if SHA512.contains(&oid) => Sha512::digest(certificate).to_vec(),
+        _ => Sha256::digest(certificate).to_vec(),
+    }
+}
+
+// The object identifier of `signatureAlgorithm` in a DER-encoded X.509 certificate:
+//
+// Certificate ::= SEQUENCE { tbsCertificate, signatureAlgorithm AlgorithmIdentifier, .. }
+// AlgorithmIdentifier ::= SEQUENCE { algorithm OBJECT IDENTIFIER, .. }
+fn signature_algorithm(certificate: &[u8]) -> Option<&[u8]> {
+    const SEQUENCE: u8 = 0x30;
+    const OBJECT_IDENTIFIER: u8 = 0x06;
+
+    // returns the contents of the element with `tag` at the start of `der`, and the rest
+    fn element(der: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+        let (&actual, der) = der.split_first()?;
+        let (&len, mut der) = der.split_first()?;
+
+        if actual != tag {
+            return None;
+        }
+
+        let len = if len < 0x80 {
+            usize::from(len)
+        } else {
+            let num_bytes = usize::from(len & 0x7F);
+
+            if num_bytes > 4 || der.len() < num_bytes {
+                return None;
+            }
+
+            let (len, rest) = der.split_at(num_bytes);
+            der = rest;
+            len.iter().fold(0, |acc, &b| (acc << 8) | usize::from(b))
+        };
+
+        (der.len() >= len).then(|| der.split_at(len))
+    }
+
+    let (certificate, _) = element(certificate, SEQUENCE)?;
+    let (_, rest) = element(certificate, SEQUENCE)?;
+    let (algorithm, _) = element(rest, SEQUENCE)?;
+    let (oid, _) = element(algorithm, OBJECT_IDENTIFIER)?;
+
+    Some(oid)
 }
 
 // nonce is a sequence of random printable bytes
@@ -223,3 +328,35 @@ fn bench_sasl_hi(b: &mut test::Bencher) {
         );
     });
 }
+
+#[test]
+fn test_tls_server_end_point_hash() {
+    // Certificate { tbsCertificate: SEQUENCE {}, signatureAlgorithm: { OID, NULL }, BIT STRING }
+    let certificate = |oid: &[u8]| {
+        let mut algorithm = vec![0x06, oid.len() as u8];
+        algorithm.extend_from_slice(oid);
+        algorithm.extend_from_slice(&[0x05, 0x00]);
+
+        let mut body = vec![0x30, 0x00, 0x30, algorithm.len() as u8];
+        body.extend_from_slice(&algorithm);
+        body.extend_from_slice(&[0x03, 0x01, 0x00]);
+
+        let mut der = vec![0x30, 0x81, body.len() as u8];
+        der.extend_from_slice(&body);
+        der
+    };
+
+    // sha384WithRSAEncryption
+    let sha384 = certificate(&[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x0C]);
+    assert_eq!(tls_server_end_point(&sha384), Sha384::digest(&sha384).to_vec());
+
+    // ecdsa-with-SHA512
+    let sha512 = certificate(&[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x04, 0x03, 0x04]);
+    assert_eq!(tls_server_end_point(&sha512), Sha512::digest(&sha512).to_vec());
+
+    // sha1WithRSAEncryption is upgraded to SHA-256
+    let sha1 = certificate(&[0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x05]);
+    assert_eq!(tls_server_end_point(&sha1), Sha256::digest(&sha1).to_vec());
+
+    assert_eq!(signature_algorithm(&[0x30, 0x05, 0x30]), None);
+}
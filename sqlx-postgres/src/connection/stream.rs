@@ -1,11 +1,13 @@
 use std::collections::BTreeMap;
+use std::io::Write;
 use std::ops::{ControlFlow, Deref, DerefMut};
 use std::str::FromStr;
 
 use futures_channel::mpsc::UnboundedSender;
 use futures_util::SinkExt;
 use log::Level;
-use sqlx_core::bytes::Buf;
+use sqlx_core::bytes::{Buf, BytesMut};
+use sqlx_core::Either;
 
 use crate::connection::tls::MaybeUpgradeTls;
 use crate::error::Error;
@@ -14,7 +16,10 @@ use crate::message::{
     ParameterStatus, ReceivedMessage,
 };
 use crate::net::{self, BufferedSocket, Socket};
+use crate::options::NoticeFilter;
 use crate::{PgConnectOptions, PgDatabaseError, PgSeverity};
+#[cfg(feature = "wire-trace")]
+use sqlx_core::wire_trace::Direction;
 
 // the stream is a separate type from the connection to uphold the invariant where an instantiated
 // [PgConnection] is a **valid** connection to postgres
@@ -38,22 +43,40 @@ pub struct PgStream {
     pub(crate) parameter_statuses: BTreeMap<String, String>,
 
     pub(crate) server_version_num: Option<u32>,
+
+    // see `PgConnectOptions::notices_as_errors()`
+    notices_as_errors: Option<NoticeFilter>,
+
+    // the length of the rest of a message that was being streamed by `recv_value_into()`
+    unread_len: usize,
 }
 
 impl PgStream {
     pub(super) async fn connect(options: &PgConnectOptions) -> Result<Self, Error> {
         let socket_future = match options.fetch_socket() {
+            #[cfg(unix)]
+            _ if options.inherited_fd.is_some() => {
+                net::connect_fd(options.take_inherited_fd()?, MaybeUpgradeTls(options)).await?
+            }
             Some(ref path) => net::connect_uds(path, MaybeUpgradeTls(options)).await?,
             None => net::connect_tcp(&options.host, options.port, MaybeUpgradeTls(options)).await?,
         };
 
         let socket = socket_future.await?;
 
+        #[allow(unused_mut)]
+        let mut inner = BufferedSocket::new(socket);
+
+        #[cfg(feature = "wire-trace")]
+        inner.set_wire_trace(options.log_settings.wire_trace.clone());
+
         Ok(Self {
-            inner: BufferedSocket::new(socket),
+            inner,
             notifications: None,
             parameter_statuses: BTreeMap::default(),
             server_version_num: None,
+            notices_as_errors: options.notices_as_errors.clone(),
+            unread_len: 0,
         })
     }
 
@@ -77,6 +100,11 @@ impl PgStream {
     }
 
     pub(crate) async fn recv_unchecked(&mut self) -> Result<ReceivedMessage, Error> {
+        self.skip_unread().await?;
+
+        #[cfg(feature = "wire-trace")]
+        let wire_trace = self.inner.wire_trace().cloned();
+
         // NOTE: to not break everything, this should be cancel-safe;
         // DO NOT modify `buf` unless a full message has been read
         self.inner
@@ -104,6 +132,11 @@ impl PgStream {
 
                 // `buf` SHOULD NOT be modified ABOVE this line
 
+                #[cfg(feature = "wire-trace")]
+                if let Some(trace) = &wire_trace {
+                    trace.trace(Direction::Received, &buf[..expected_len]);
+                }
+
                 // pop off the format code since it's not counted in `message_len`
                 buf.advance(1);
 
@@ -118,6 +151,112 @@ impl PgStream {
             .await
     }
 
+    // Receive the next message like `recv()`, unless it is a `DataRow`: then write the value in
+    // its first column to `writer` in pieces of at most `chunk_size` bytes as they arrive, instead
+    // of reading the whole row into memory, and return the length of the value (`None` if NULL).
+    //
+    // The rest of the row is skipped by the next receive, as is the rest of the value if writing
+    // it fails or this is cancelled part way through, so the connection stays usable.
+    pub(crate) async fn recv_value_into<W>(
+        &mut self,
+        chunk_size: usize,
+        writer: &mut W,
+    ) -> Result<Either<ReceivedMessage, Option<u64>>, Error>
+    where
+        W: Write + ?Sized,
+    {
+        self.skip_unread().await?;
+
+        #[cfg(feature = "wire-trace")]
+        let wire_trace = self.inner.wire_trace().cloned();
+
+        // like `recv_unchecked()`, but only the header of a `DataRow` is consumed
+        let data_row_len = self
+            .inner
+            .try_read(|buf| {
+                let Some(mut header) = buf.get(..5) else {
+                    return Ok(ControlFlow::Continue(5));
+                };
+
+                let format = BackendMessageFormat::try_from_u8(header.get_u8())?;
+                if format != BackendMessageFormat::DataRow {
+                    return Ok(ControlFlow::Break(None));
+                }
+
+                let message_len = header.get_u32() as usize;
+
+                #[cfg(feature = "wire-trace")]
+                if let Some(trace) = &wire_trace {
+                    trace.trace(Direction::Received, &buf[..5]);
+                }
+
+                buf.advance(5);
+
+                Ok(ControlFlow::Break(Some(message_len)))
+            })
+            .await?;
+
+        let Some(message_len) = data_row_len else {
+            return self.recv().await.map(Either::Left);
+        };
+
+        // the length counts itself, and the row holds at least a column count and one value
+        self.unread_len = message_len.saturating_sub(4);
+        if self.unread_len < 6 {
+            return Err(err_protocol!("DataRow too short: {message_len} bytes"));
+        }
+
+        let mut header = self.read_part(6).await?;
+
+        let columns = header.get_i16();
+        if columns < 1 {
+            return Err(err_protocol!("expected a DataRow with a column, got {columns}"));
+        }
+
+        // `-1` for `NULL`
+        let Ok(value_len) = usize::try_from(header.get_i32()) else {
+            return Ok(Either::Right(None));
+        };
+
+        if value_len > self.unread_len {
+            return Err(err_protocol!(
+                "DataRow value of {value_len} bytes overruns the message"
+            ));
+        }
+
+        let mut remaining = value_len;
+
+        while remaining > 0 {
+            let part = self.read_part(remaining.min(chunk_size)).await?;
+            remaining -= part.len();
+
+            writer.write_all(&part)?;
+        }
+
+        Ok(Either::Right(Some(value_len as u64)))
+    }
+
+    // read `len` bytes of the rest of the message being streamed
+    async fn read_part(&mut self, len: usize) -> Result<BytesMut, Error> {
+        let part = self.inner.read_buffered(len).await?;
+        self.unread_len -= len;
+
+        #[cfg(feature = "wire-trace")]
+        if let Some(trace) = self.inner.wire_trace() {
+            trace.trace(Direction::Received, &part);
+        }
+
+        Ok(part)
+    }
+
+    async fn skip_unread(&mut self) -> Result<(), Error> {
+        while self.unread_len > 0 {
+            self.read_part(self.unread_len.min(8192)).await?;
+        }
+
+        Ok(())
+    }
+
     // Get the next message from the server
     // May wait for more data from the server
     pub(crate) async fn recv(&mut self) -> Result<ReceivedMessage, Error> {
@@ -189,6 +328,14 @@ impl PgStream {
                         );
                     }
 
+                    if let Some(NoticeFilter(filter)) = &self.notices_as_errors {
+                        let notice = PgDatabaseError(notice);
+
+                        if filter(&notice) {
+                            return Err(notice.into());
+                        }
+                    }
+
                     continue;
                 }
 
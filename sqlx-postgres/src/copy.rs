@@ -12,10 +12,33 @@ use crate::ext::async_stream::TryAsyncStream;
 use crate::io::AsyncRead;
 use crate::message::{
     BackendMessageFormat, CommandComplete, CopyData, CopyDone, CopyFail, CopyInResponse,
-    CopyOutResponse, CopyResponseData, Query, ReadyForQuery,
+    CopyOutResponse, CopyResponseData, Notice, Query, ReadyForQuery,
 };
 use crate::pool::{Pool, PoolConnection};
-use crate::Postgres;
+use crate::{PgDatabaseError, Postgres};
+
+/// What the server should do with rows that fail to parse during `COPY FROM`,
+/// given to [`PgConnection::copy_in_raw_on_error()`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum PgCopyOnError {
+    /// Abort the `COPY` on the first malformed row. This is the default behavior of Postgres.
+    #[default]
+    Stop,
+    /// Skip malformed rows and continue with the next one (`ON_ERROR ignore`).
+    ///
+    /// Requires PostgreSQL 17 or newer. The number of rows skipped is returned by
+    /// [`PgCopyIn::finish_with_skipped()`].
+    Ignore,
+}
+
+/// The outcome of a `COPY FROM STDIN`, returned by [`PgCopyIn::finish_with_skipped()`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PgCopyInResult {
+    /// The number of rows that were copied into the table.
+    pub rows_copied: u64,
+    /// The number of rows that were skipped because of [`PgCopyOnError::Ignore`].
+    pub rows_skipped: u64,
+}
 
 impl PgConnection {
     /// Issue a `COPY FROM STDIN` statement and transition the connection to streaming data
@@ -32,7 +55,47 @@ impl PgConnection {
     /// [PgCopyIn::finish] or [PgCopyIn::abort] *must* be called when finished or the connection
     /// will return an error the next time it is used.
     pub async fn copy_in_raw(&mut self, statement: &str) -> Result<PgCopyIn<&mut Self>> {
-        PgCopyIn::begin(self, statement).await
+        PgCopyIn::begin(self, statement, PgCopyOnError::Stop).await
+    }
+
+    /// Like [`copy_in_raw()`][Self::copy_in_raw], but sets the `ON_ERROR` option of the
+    /// `COPY` statement to control what happens to rows that fail to parse.
+    ///
+    /// `statement` should not contain an `ON_ERROR` option of its own. With
+    /// [`PgCopyOnError::Ignore`], the option is appended to the statement, which therefore must
+    /// end with either `STDIN` or its parenthesized option list, e.g.:
+    ///
+    /// ```sql
+    /// COPY users (id, name) FROM STDIN (FORMAT csv)
+    /// ```
+    ///
+    /// `ON_ERROR ignore` was added in PostgreSQL 17; on older servers an error is returned
+    /// without issuing the statement. Use [`PgCopyIn::finish_with_skipped()`] to learn how many
+    /// rows were skipped.
+    pub async fn copy_in_raw_on_error(
+        &mut self,
+        statement: &str,
+        on_error: PgCopyOnError,
+    ) -> Result<PgCopyIn<&mut Self>> {
+        let statement = match on_error {
+            PgCopyOnError::Stop => Cow::Borrowed(statement),
+            PgCopyOnError::Ignore => {
+                if !matches!(self.server_version_num(), Some(version) if version >= 170000) {
+                    return Err(Error::Configuration(
+                        format!(
+                            "COPY with ON_ERROR ignore requires PostgreSQL 17 or newer \
+                             (server_version_num is {:?})",
+                            self.server_version_num()
+                        )
+                        .into(),
+                    ));
+                }
+
+                Cow::Owned(with_on_error_ignore(statement)?)
+            }
+        };
+
+        PgCopyIn::begin(self, &statement, on_error).await
     }
 
     /// Issue a `COPY TO STDOUT` statement and transition the connection to streaming data
@@ -118,7 +181,9 @@ impl PgPoolCopyExt for Pool<Postgres> {
         &'a self,
         statement: &'a str,
     ) -> BoxFuture<'a, Result<PgCopyIn<PoolConnection<Postgres>>>> {
-        Box::pin(async { PgCopyIn::begin(self.acquire().await?, statement).await })
+        Box::pin(async {
+            PgCopyIn::begin(self.acquire().await?, statement, PgCopyOnError::Stop).await
+        })
     }
 
     fn copy_out_raw<'a>(
@@ -140,12 +205,16 @@ impl PgPoolCopyExt for Pool<Postgres> {
 pub struct PgCopyIn<C: DerefMut<Target = PgConnection>> {
     conn: Option<C>,
     response: CopyResponseData,
+    on_error: PgCopyOnError,
 }
 
 impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
-    async fn begin(mut conn: C, statement: &str) -> Result<Self> {
+    async fn begin(mut conn: C, statement: &str, on_error: PgCopyOnError) -> Result<Self> {
         conn.wait_until_ready().await?;
-        conn.inner.stream.send(Query(statement)).await?;
+        conn.check_write_allowed(statement)?;
+
+        let statement = conn.inner.log_settings.rewrite_sql(statement);
+        conn.inner.stream.send(Query(&statement)).await?;
 
         let response = match conn.inner.stream.recv_expect::<CopyInResponse>().await {
             Ok(res) => res.0,
@@ -158,6 +227,7 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
         Ok(PgCopyIn {
             conn: Some(conn),
             response,
+            on_error,
         })
     }
 
@@ -235,6 +305,11 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
 
             (&mut buf.get_mut()[1..]).put_u32(read32 + 4);
 
+            #[cfg(feature = "wire-trace")]
+            conn.inner
+                .stream
+                .trace_sent(conn.inner.stream.write_buffer().get());
+
             conn.inner.stream.flush().await?;
         }
 
@@ -276,27 +351,101 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
     /// Signal that the `COPY` process is complete.
     ///
     /// The number of rows affected is returned.
-    pub async fn finish(mut self) -> Result<u64> {
+    pub async fn finish(self) -> Result<u64> {
+        Ok(self.finish_with_skipped().await?.rows_copied)
+    }
+
+    /// Signal that the `COPY` process is complete.
+    ///
+    /// Returns the number of rows copied and, if the `COPY` was started with
+    /// [`PgCopyOnError::Ignore`], the number of rows skipped.
+    ///
+    /// The skipped count is taken from the `NOTICE` the server sends at the end of the `COPY`,
+    /// which is only recognized when `lc_messages` is set to English.
+    pub async fn finish_with_skipped(mut self) -> Result<PgCopyInResult> {
         let mut conn = self
             .conn
             .take()
             .expect("CopyWriter::finish: conn taken illegally");
 
         conn.inner.stream.send(CopyDone).await?;
-        let cc: CommandComplete = match conn.inner.stream.recv_expect().await {
-            Ok(cc) => cc,
-            Err(e) => {
-                conn.inner.stream.recv().await?;
-                return Err(e);
-            }
+
+        let mut rows_skipped = 0;
+
+        let cc: CommandComplete = match self.on_error {
+            PgCopyOnError::Stop => match conn.inner.stream.recv_expect().await {
+                Ok(cc) => cc,
+                Err(e) => {
+                    conn.inner.stream.recv().await?;
+                    return Err(e);
+                }
+            },
+            // the skipped row count is only reported in a notice, which `recv()` would swallow
+            PgCopyOnError::Ignore => loop {
+                let message = conn.inner.stream.recv_unchecked().await?;
+
+                match message.format {
+                    BackendMessageFormat::NoticeResponse => {
+                        let notice: Notice = message.decode()?;
+
+                        if let Some(skipped) = parse_rows_skipped(notice.message()) {
+                            rows_skipped = skipped;
+                        }
+                    }
+                    BackendMessageFormat::ErrorResponse => {
+                        let e = message.decode::<PgDatabaseError>()?;
+                        conn.inner.stream.recv().await?;
+                        return Err(e.into());
+                    }
+                    BackendMessageFormat::CommandComplete => break message.decode()?,
+                    // informs the frontend about a change of a backend parameter
+                    BackendMessageFormat::ParameterStatus => {}
+                    format => {
+                        return Err(err_protocol!(
+                            "finish: expected CommandComplete, got: {:?}",
+                            format
+                        ))
+                    }
+                }
+            },
         };
 
         conn.inner.stream.recv_expect::<ReadyForQuery>().await?;
 
-        Ok(cc.rows_affected())
+        Ok(PgCopyInResult {
+            rows_copied: cc.rows_affected(),
+            rows_skipped,
+        })
+    }
+}
+
+/// Append `ON_ERROR ignore` to the options of a `COPY ... FROM STDIN` statement.
+fn with_on_error_ignore(statement: &str) -> Result<String> {
+    let statement = statement.trim_end().trim_end_matches(';').trim_end();
+
+    if let Some(options) = statement.strip_suffix(')') {
+        Ok(format!("{options}, ON_ERROR ignore)"))
+    } else if statement.to_ascii_uppercase().ends_with("STDIN") {
+        Ok(format!("{statement} (ON_ERROR ignore)"))
+    } else {
+        Err(Error::Configuration(
+            "COPY statement must end with `STDIN` or its option list to set ON_ERROR".into(),
+        ))
     }
 }
 
+/// Parse the notice sent at the end of a `COPY` with `ON_ERROR ignore`, e.g.
+/// `3 rows were skipped due to data type incompatibility`.
+fn parse_rows_skipped(message: &str) -> Option<u64> {
+    let (count, rest) = message.split_once(' ')?;
+
+    if !rest.contains("skipped") {
+        return None;
+    }
+
+    count.parse().ok()
+}
+
 impl<C: DerefMut<Target = PgConnection>> Drop for PgCopyIn<C> {
     fn drop(&mut self) {
         if let Some(mut conn) = self.conn.take() {
@@ -315,7 +464,9 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
     statement: &str,
 ) -> Result<BoxStream<'c, Result<Bytes>>> {
     conn.wait_until_ready().await?;
-    conn.inner.stream.send(Query(statement)).await?;
+
+    let statement = conn.inner.log_settings.rewrite_sql(statement);
+    conn.inner.stream.send(Query(&statement)).await?;
 
     let _: CopyOutResponse = conn.inner.stream.recv_expect().await?;
 
@@ -5,7 +5,7 @@ use crate::{
     PgTypeInfo,
 };
 
-pub(crate) use sqlx_core::database::{Database, HasStatementCache};
+pub(crate) use sqlx_core::database::{Database, HasCovariantLifetimes, HasStatementCache};
 
 /// PostgreSQL database driver.
 #[derive(Debug)]
@@ -38,3 +38,13 @@ impl Database for Postgres {
 }
 
 impl HasStatementCache for Postgres {}
+
+impl HasCovariantLifetimes for Postgres {
+    fn shorten_arguments<'a, 'b: 'a>(arguments: Self::Arguments<'b>) -> Self::Arguments<'a> {
+        arguments
+    }
+
+    fn shorten_statement<'a, 'b: 'a>(statement: Self::Statement<'b>) -> Self::Statement<'a> {
+        statement
+    }
+}
@@ -11,6 +11,12 @@ use crate::message::{BackendMessage, BackendMessageFormat, Notice, PgSeverity};
 /// An error returned from the PostgreSQL database.
 pub struct PgDatabaseError(pub(crate) Notice);
 
+/// A notice sent by the PostgreSQL server, such as a `WARNING` from `RAISE WARNING`.
+///
+/// Notices carry the same fields as errors, so this is the same type as [`PgDatabaseError`].
+/// See [`PgConnectOptions::notices_as_errors()`][crate::PgConnectOptions::notices_as_errors].
+pub type PgNotice = PgDatabaseError;
+
 // Error message fields are documented:
 // https://www.postgresql.org/docs/current/protocol-error-fields.html
 
@@ -214,6 +220,7 @@ impl DatabaseError for PgDatabaseError {
             error_codes::FOREIGN_KEY_VIOLATION => ErrorKind::ForeignKeyViolation,
             error_codes::NOT_NULL_VIOLATION => ErrorKind::NotNullViolation,
             error_codes::CHECK_VIOLATION => ErrorKind::CheckViolation,
+            error_codes::LOCK_NOT_AVAILABLE => ErrorKind::LockTimeout,
             _ => ErrorKind::Other,
         }
     }
@@ -239,4 +246,43 @@ pub(crate) mod error_codes {
     pub const NOT_NULL_VIOLATION: &str = "23502";
     /// Caused when a check constraint is violated.
     pub const CHECK_VIOLATION: &str = "23514";
+    /// Caused when a lock could not be acquired within `lock_timeout`, or at once with `NOWAIT`.
+    pub const LOCK_NOT_AVAILABLE: &str = "55P03";
+}
+
+#[test]
+fn test_error_response_fields() {
+    const DATA: &[u8] = b"SERROR\0VERROR\0C23505\0Mduplicate key value violates unique constraint \"users_email_key\"\0DKey (email)=(a@example.com) already exists.\0Hpick another email\0P12\0Wfunction register()\0spublic\0tusers\0cemail\0dtext\0nusers_email_key\0Fnbtinsert.c\0L664\0R_bt_check_unique\0\0";
+
+    let e = PgDatabaseError::decode_body(Bytes::from_static(DATA)).unwrap();
+
+    assert_eq!(e.code(), "23505");
+    assert_eq!(e.detail(), Some("Key (email)=(a@example.com) already exists."));
+    assert_eq!(e.hint(), Some("pick another email"));
+    assert_eq!(e.position(), Some(PgErrorPosition::Original(12)));
+    assert_eq!(e.r#where(), Some("function register()"));
+    assert_eq!(e.schema(), Some("public"));
+    assert_eq!(e.table(), Some("users"));
+    assert_eq!(e.column(), Some("email"));
+    assert_eq!(e.data_type(), Some("text"));
+    assert_eq!(e.constraint(), Some("users_email_key"));
+    assert_eq!(e.line(), Some(664));
+    assert_eq!(e.kind(), ErrorKind::UniqueViolation);
+}
+
+#[test]
+fn test_error_response_internal_position() {
+    const DATA: &[u8] = b"SERROR\0VERROR\0C42703\0Mcolumn \"x\" does not exist\0p8\0qSELECT x\0\0";
+
+    let e = PgDatabaseError::decode_body(Bytes::from_static(DATA)).unwrap();
+
+    assert_eq!(
+        e.position(),
+        Some(PgErrorPosition::Internal {
+            position: 8,
+            query: "SELECT x"
+        })
+    );
+    assert_eq!(e.detail(), None);
+    assert_eq!(e.constraint(), None);
 }
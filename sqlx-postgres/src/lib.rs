@@ -7,6 +7,7 @@ use crate::executor::Executor;
 
 mod advisory_lock;
 mod arguments;
+mod bytea;
 mod column;
 mod connection;
 mod copy;
@@ -45,13 +46,15 @@ pub(crate) use sqlx_core::driver_prelude::*;
 pub use advisory_lock::{PgAdvisoryLock, PgAdvisoryLockGuard, PgAdvisoryLockKey};
 pub use arguments::{PgArgumentBuffer, PgArguments};
 pub use column::PgColumn;
-pub use connection::PgConnection;
-pub use copy::{PgCopyIn, PgPoolCopyExt};
+pub use connection::{PgCancelToken, PgConnection};
+pub use copy::{PgCopyIn, PgCopyInResult, PgCopyOnError, PgPoolCopyExt};
 pub use database::Postgres;
-pub use error::{PgDatabaseError, PgErrorPosition};
-pub use listener::{PgListener, PgNotification};
+pub use error::{PgDatabaseError, PgErrorPosition, PgNotice};
+pub use listener::{PgListener, PgNotification, PgReconnectEvent, PgReconnectPolicy};
 pub use message::PgSeverity;
-pub use options::{PgConnectOptions, PgSslMode};
+pub use options::{
+    PgChannelBinding, PgConnectOptions, PgDecimalScaleMode, PgPlanCacheMode, PgSslMode,
+};
 pub use query_result::PgQueryResult;
 pub use row::PgRow;
 pub use statement::PgStatement;
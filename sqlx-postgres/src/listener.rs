@@ -1,11 +1,13 @@
 use std::fmt::{self, Debug};
 use std::io;
 use std::str::from_utf8;
+use std::time::Duration;
 
 use futures_channel::mpsc;
 use futures_core::future::BoxFuture;
 use futures_core::stream::{BoxStream, Stream};
 use futures_util::{FutureExt, StreamExt, TryStreamExt};
+use rand::Rng;
 use sqlx_core::Either;
 
 use crate::describe::Describe;
@@ -22,6 +24,9 @@ use crate::{PgConnection, PgQueryResult, PgRow, PgStatement, PgTypeInfo, Postgre
 /// connection being used ever dies, this listener will detect that event, create a
 /// new connection, will re-subscribe to all of the originally specified channels, and will resume
 /// operations as normal.
+///
+/// By default, a single attempt is made to reconnect, and its error is returned if it fails.
+/// See [`reconnect_policy()`][Self::reconnect_policy] to retry with a backoff instead.
 pub struct PgListener {
     pool: Pool<Postgres>,
     connection: Option<PoolConnection<Postgres>>,
@@ -29,11 +34,108 @@ pub struct PgListener {
     buffer_tx: Option<mpsc::UnboundedSender<Notification>>,
     channels: Vec<String>,
     ignore_close_event: bool,
+    reconnect_policy: Option<PgReconnectPolicy>,
+    on_reconnect: Option<ReconnectCallback>,
 }
 
+type ReconnectCallback = Box<dyn Fn(PgReconnectEvent<'_>) + Send + Sync>;
+
 /// An asynchronous notification from Postgres.
 pub struct PgNotification(Notification);
 
+/// How [`PgListener`] retries when it fails to reconnect.
+///
+/// After each failed attempt, the listener waits before trying again. The delay starts at
+/// [`initial_delay`][Self::initial_delay], doubles after every attempt up to
+/// [`max_delay`][Self::max_delay], and, with [`jitter`][Self::jitter] enabled, is randomly
+/// shortened by up to half so that many listeners don't reconnect in lockstep.
+///
+/// Set with [`PgListener::reconnect_policy()`].
+#[derive(Debug, Clone)]
+pub struct PgReconnectPolicy {
+    initial_delay: Duration,
+    max_delay: Duration,
+    max_attempts: Option<u32>,
+    jitter: bool,
+}
+
+/// An event reported to the callback set with [`PgListener::on_reconnect()`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PgReconnectEvent<'a> {
+    /// An attempt to reconnect is being made. Attempts are numbered from 1.
+    Attempting { attempt: u32 },
+    /// The attempt failed with `error`; the next one is made after `delay`.
+    Failed {
+        attempt: u32,
+        error: &'a Error,
+        delay: Duration,
+    },
+    /// The listener reconnected and re-subscribed to its channels.
+    Reconnected { attempts: u32 },
+    /// The listener stopped trying to reconnect; `error` is what the receiving method returns.
+    ///
+    /// The next call to a receiving method starts a new series of attempts.
+    GaveUp { attempts: u32, error: &'a Error },
+}
+
+impl PgReconnectPolicy {
+    /// Retry forever, waiting 100 milliseconds after the first failure and up to 30 seconds
+    /// after later ones, with jitter.
+    pub fn new() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+            max_attempts: None,
+            jitter: true,
+        }
+    }
+
+    /// Set the delay after the first failed attempt.
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Set the maximum delay between attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Give up after this many failed attempts, or never if `None`. Defaults to `None`.
+    ///
+    /// A value of 0 is treated as 1.
+    pub fn max_attempts(mut self, attempts: impl Into<Option<u32>>) -> Self {
+        self.max_attempts = attempts.into();
+        self
+    }
+
+    /// Set whether to randomly shorten each delay by up to half. Defaults to `true`.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// The delay after failed attempt number `attempt`.
+    fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1));
+        let delay = std::cmp::min(self.initial_delay.saturating_mul(factor), self.max_delay);
+
+        if self.jitter {
+            rand::thread_rng().gen_range(delay / 2..=delay)
+        } else {
+            delay
+        }
+    }
+}
+
+impl Default for PgReconnectPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PgListener {
     pub async fn connect(url: &str) -> Result<Self, Error> {
         // Create a pool of 1 without timeouts (as they don't apply here)
@@ -67,6 +169,8 @@ impl PgListener {
             buffer_tx: None,
             channels: Vec::new(),
             ignore_close_event: false,
+            reconnect_policy: None,
+            on_reconnect: None,
         })
     }
 
@@ -93,6 +197,42 @@ impl PgListener {
         self.ignore_close_event = val;
     }
 
+    /// Retry reconnecting according to `policy` when the connection is lost,
+    /// instead of returning the error of the first failed attempt.
+    ///
+    /// The receiving methods and [`listen()`][Self::listen] wait while the listener is
+    /// reconnecting. If the pool is closed, the listener gives up immediately.
+    ///
+    /// Note that each attempt [acquires][Pool::acquire] a connection from the pool, which itself
+    /// retries opening a connection until the pool's
+    /// [`acquire_timeout`][PoolOptions::acquire_timeout] elapses.
+    ///
+    /// ```rust,no_run
+    /// # use std::time::Duration;
+    /// # use sqlx::postgres::{PgListener, PgReconnectPolicy};
+    /// # sqlx::__rt::test_block_on(async move {
+    /// let mut listener = PgListener::connect("postgres:// ...").await?;
+    ///
+    /// listener.reconnect_policy(
+    ///     PgReconnectPolicy::new()
+    ///         .max_delay(Duration::from_secs(10))
+    ///         .max_attempts(20),
+    /// );
+    /// # Result::<(), sqlx::Error>::Ok(())
+    /// # }).unwrap();
+    /// ```
+    pub fn reconnect_policy(&mut self, policy: PgReconnectPolicy) {
+        self.reconnect_policy = Some(policy);
+    }
+
+    /// Call `callback` on every attempt to reconnect and its outcome, e.g. for logging or metrics.
+    pub fn on_reconnect(
+        &mut self,
+        callback: impl Fn(PgReconnectEvent<'_>) + Send + Sync + 'static,
+    ) {
+        self.on_reconnect = Some(Box::new(callback));
+    }
+
     /// Starts listening for notifications on a channel.
     /// The channel name is quoted here to ensure case sensitivity.
     pub async fn listen(&mut self, channel: &str) -> Result<(), Error> {
@@ -153,20 +293,74 @@ impl PgListener {
 
     #[inline]
     async fn connect_if_needed(&mut self) -> Result<(), Error> {
-        if self.connection.is_none() {
-            let mut connection = self.pool.acquire().await?;
-            connection.inner.stream.notifications = self.buffer_tx.take();
+        if self.connection.is_some() {
+            return Ok(());
+        }
 
-            connection
-                .execute(&*build_listen_all_query(&self.channels))
-                .await?;
+        let Some(policy) = self.reconnect_policy.clone() else {
+            return self.reconnect().await;
+        };
 
-            self.connection = Some(connection);
+        let max_attempts = policy.max_attempts.map(|max| std::cmp::max(max, 1));
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            self.emit(PgReconnectEvent::Attempting { attempt });
+
+            let error = match self.reconnect().await {
+                Ok(()) => {
+                    self.emit(PgReconnectEvent::Reconnected { attempts: attempt });
+                    return Ok(());
+                }
+                Err(error) => error,
+            };
+
+            if matches!(error, Error::PoolClosed) || max_attempts.is_some_and(|max| attempt >= max)
+            {
+                self.emit(PgReconnectEvent::GaveUp {
+                    attempts: attempt,
+                    error: &error,
+                });
+                return Err(error);
+            }
+
+            let delay = policy.delay(attempt);
+            self.emit(PgReconnectEvent::Failed {
+                attempt,
+                error: &error,
+                delay,
+            });
+
+            crate::rt::sleep(delay).await;
+        }
+    }
+
+    async fn reconnect(&mut self) -> Result<(), Error> {
+        let mut connection = self.pool.acquire().await?;
+        connection.inner.stream.notifications = self.buffer_tx.take();
+
+        if let Err(error) = connection
+            .execute(&*build_listen_all_query(&self.channels))
+            .await
+        {
+            // Keep the buffer for the next attempt.
+            self.buffer_tx = connection.inner.stream.notifications.take();
+            connection.close_on_drop();
+            return Err(error);
         }
 
+        self.connection = Some(connection);
+
         Ok(())
     }
 
+    fn emit(&self, event: PgReconnectEvent<'_>) {
+        if let Some(callback) = &self.on_reconnect {
+            callback(event);
+        }
+    }
+
     #[inline]
     async fn connection(&mut self) -> Result<&mut PgConnection, Error> {
         // Ensure we have an active connection to work with.
@@ -458,3 +652,25 @@ fn test_build_listen_all_query_with_multiple_channels() {
     let output = build_listen_all_query(&["channel.0", "channel.1"]);
     assert_eq!(output.as_str(), r#"LISTEN "channel.0";LISTEN "channel.1";"#);
 }
+
+#[test]
+fn test_reconnect_policy_delay() {
+    let policy = PgReconnectPolicy::new()
+        .initial_delay(Duration::from_millis(100))
+        .max_delay(Duration::from_secs(1))
+        .jitter(false);
+
+    assert_eq!(policy.delay(1), Duration::from_millis(100));
+    assert_eq!(policy.delay(2), Duration::from_millis(200));
+    assert_eq!(policy.delay(4), Duration::from_millis(800));
+    assert_eq!(policy.delay(5), Duration::from_secs(1));
+    assert_eq!(policy.delay(u32::MAX), Duration::from_secs(1));
+
+    let policy = policy.jitter(true);
+
+    for attempt in 1..10 {
+        let delay = policy.delay(attempt);
+        let max = policy.clone().jitter(false).delay(attempt);
+        assert!(delay >= max / 2 && delay <= max, "{delay:?}");
+    }
+}
@@ -0,0 +1,52 @@
+use crate::io::ProtocolEncode;
+
+pub struct CancelRequest {
+    pub process_id: u32,
+    pub secret_key: u32,
+}
+
+impl CancelRequest {
+    // https://www.postgresql.org/docs/current/protocol-message-formats.html#PROTOCOL-MESSAGE-FORMATS-CANCELREQUEST
+    const HEADER: &'static [u8] = b"\x00\x00\x00\x10\x04\xd2\x16\x2e";
+}
+
+// Cannot impl FrontendMessage because it does not have a format code
+impl ProtocolEncode<'_> for CancelRequest {
+    #[inline(always)]
+    fn encode_with(&self, buf: &mut Vec<u8>, _context: ()) -> Result<(), crate::Error> {
+        buf.extend_from_slice(Self::HEADER);
+        buf.extend_from_slice(&self.process_id.to_be_bytes());
+        buf.extend_from_slice(&self.secret_key.to_be_bytes());
+        Ok(())
+    }
+}
+
+#[test]
+fn test_encode_cancel_request() {
+    let mut buf = Vec::new();
+
+    // Int32(16)
+    // Length of message contents in bytes, including self.
+    buf.extend_from_slice(&16_u32.to_be_bytes());
+
+    // Int32(80877102)
+    // The cancel request code. The value is chosen to contain 1234 in the most significant 16 bits,
+    // and 5678 in the least significant 16 bits.
+    buf.extend_from_slice(&(((1234 << 16) | 5678) as u32).to_be_bytes());
+
+    // Int32: The process ID of the target backend.
+    buf.extend_from_slice(&1234_u32.to_be_bytes());
+
+    // Int32: The secret key for the target backend.
+    buf.extend_from_slice(&5678_u32.to_be_bytes());
+
+    let mut encoded = Vec::new();
+    CancelRequest {
+        process_id: 1234,
+        secret_key: 5678,
+    }
+    .encode(&mut encoded)
+    .unwrap();
+
+    assert_eq!(buf, encoded);
+}
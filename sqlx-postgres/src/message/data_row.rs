@@ -6,7 +6,7 @@ use crate::error::Error;
 use crate::message::{BackendMessage, BackendMessageFormat};
 
 /// A row of data from the database.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DataRow {
     pub(crate) storage: Bytes,
 
@@ -150,16 +150,21 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
             // language=SQL
-            let rows: Vec<(i64, Vec<u8>)> =
-                query_as("SELECT version, checksum FROM _sqlx_migrations ORDER BY version")
-                    .fetch_all(self)
-                    .await?;
+            let rows: Vec<(i64, Vec<u8>, i64)> = query_as(
+                "SELECT version, checksum, execution_time FROM _sqlx_migrations ORDER BY version",
+            )
+            .fetch_all(self)
+            .await?;
 
             let migrations = rows
                 .into_iter()
-                .map(|(version, checksum)| AppliedMigration {
+                .map(|(version, checksum, execution_time)| AppliedMigration {
                     version,
                     checksum: checksum.into(),
+                    // -1 until the time is recorded
+                    execution_time: u64::try_from(execution_time)
+                        .ok()
+                        .map(Duration::from_nanos),
                 })
                 .collect();
 
@@ -247,6 +252,121 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
         })
     }
 
+    fn skip<'e: 'm, 'm>(
+        &'e mut self,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async move {
+            let mut tx = self.begin().await?;
+
+            // language=SQL
+            let _ = tx
+                .execute(
+                    "ALTER TABLE _sqlx_migrations \
+                     ADD COLUMN IF NOT EXISTS skipped BOOLEAN NOT NULL DEFAULT FALSE",
+                )
+                .await?;
+
+            // language=SQL
+            let _ = query(
+                r#"
+    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time, skipped )
+    VALUES ( $1, $2, TRUE, $3, 0, TRUE )
+                "#,
+            )
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .execute(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn analyze<'e: 'm, 'm>(
+        &'e mut self,
+        tables: &'m [String],
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async move {
+            for table in tables {
+                // language=SQL
+                let _ = self.execute(&*format!("ANALYZE {table}")).await?;
+            }
+
+            Ok(())
+        })
+    }
+
+    fn set_schema<'e: 'm, 'm>(
+        &'e mut self,
+        schema: &'m str,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // the name is validated by `Migrator::set_schema()`
+            // language=SQL
+            let _ = self
+                .execute(&*format!(r#"SET search_path TO "{schema}""#))
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn reset_schema(&mut self) -> BoxFuture<'_, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let _ = self.execute("RESET search_path").await?;
+
+            Ok(())
+        })
+    }
+
+    fn set_statement_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> BoxFuture<'_, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // in milliseconds, rounding up so that a short timeout does not become no timeout
+            let millis = timeout.as_nanos().div_ceil(1_000_000).min(i32::MAX as u128);
+
+            // language=SQL
+            let _ = self
+                .execute(&*format!("SET statement_timeout = {millis}"))
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    fn statement_timeout(&mut self) -> BoxFuture<'_, Result<Option<String>, MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let timeout: String = query_scalar("SELECT current_setting('statement_timeout')")
+                .fetch_one(self)
+                .await?;
+
+            Ok(Some(timeout))
+        })
+    }
+
+    fn restore_statement_timeout<'e: 'm, 'm>(
+        &'e mut self,
+        previous: &'m str,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async move {
+            // language=SQL
+            let _ = query("SELECT set_config('statement_timeout', $1, false)")
+                .bind(previous)
+                .execute(self)
+                .await?;
+
+            Ok(())
+        })
+    }
+
     fn revert<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
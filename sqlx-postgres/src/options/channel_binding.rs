@@ -0,0 +1,42 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// Options for controlling the use of SCRAM channel binding, which ties the authentication to
+/// the TLS connection so that it cannot be relayed by a man in the middle.
+///
+/// It is used by the [`channel_binding`](super::PgConnectOptions::channel_binding) method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PgChannelBinding {
+    /// Never use channel binding.
+    Disable,
+
+    /// Use channel binding (`SCRAM-SHA-256-PLUS`) if the connection uses TLS and the server
+    /// supports it; otherwise authenticate with plain `SCRAM-SHA-256` or whatever other
+    /// method the server asks for.
+    ///
+    /// This is the default if no other mode is specified.
+    #[default]
+    Prefer,
+
+    /// Fail to connect unless the server authenticates with channel binding, which requires
+    /// TLS and `SCRAM-SHA-256-PLUS`.
+    Require,
+}
+
+impl FromStr for PgChannelBinding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "disable" => PgChannelBinding::Disable,
+            "prefer" => PgChannelBinding::Prefer,
+            "require" => PgChannelBinding::Require,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {s:?} for `channel_binding`").into(),
+                ));
+            }
+        })
+    }
+}
@@ -1,5 +1,6 @@
-use crate::connection::ConnectOptions;
+use crate::connection::{ConnectOptions, SlowQueryHook};
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::{PgConnectOptions, PgConnection};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
@@ -21,7 +22,22 @@ impl ConnectOptions for PgConnectOptions {
     where
         Self::Connection: Sized,
     {
-        Box::pin(PgConnection::establish(self))
+        Box::pin(async move {
+            #[cfg(feature = "offline")]
+            if let Some(cache) = &self.offline_describe_cache {
+                cache.load().await?;
+            }
+
+            let mut conn = PgConnection::establish(self).await?;
+
+            conn.apply_session_settings().await?;
+
+            if let Some(sql) = &self.warmup_query {
+                conn.execute(&**sql).await?;
+            }
+
+            Ok(conn)
+        })
     }
 
     fn log_statements(mut self, level: LevelFilter) -> Self {
@@ -33,4 +49,80 @@ impl ConnectOptions for PgConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn redact_parameters(
+        mut self,
+        f: impl Fn(usize, &dyn std::fmt::Debug) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings.redact_parameters(f);
+        self
+    }
+
+    fn redact_all_parameters(mut self) -> Self {
+        self.log_settings.redact_all_parameters();
+        self
+    }
+
+    fn on_slow_query(
+        mut self,
+        threshold: Duration,
+        f: impl Fn(&str, Duration) + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings
+            .on_slow_query(SlowQueryHook::new(threshold, f));
+        self
+    }
+
+    fn pool_name(mut self, pool_name: &str) -> Self {
+        let labeled = match self.application_name.as_deref() {
+            // the options may have come from a pool that already applied this name
+            Some(service)
+                if service == pool_name || service.ends_with(&format!("[{pool_name}]")) =>
+            {
+                return self
+            }
+            Some(service) => format!("{service}[{pool_name}]"),
+            None => pool_name.to_owned(),
+        };
+
+        self.application_name = Some(labeled);
+        self
+    }
+
+    fn lenient_integer_decode(mut self, enabled: bool) -> Self {
+        self.lenient_integer_decode = enabled;
+        self
+    }
+
+    fn prefer_text_for(mut self, type_names: &[&str]) -> Self {
+        self.text_types = type_names.iter().map(|&name| name.to_owned()).collect();
+        self
+    }
+
+    fn connection_id(mut self, f: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        self.log_settings.connection_id(f);
+        self
+    }
+
+    fn sql_rewriter(
+        mut self,
+        f: impl for<'a> Fn(&'a str) -> std::borrow::Cow<'a, str> + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings.sql_rewriter(f);
+        self
+    }
+
+    fn dev_auto_limit(mut self, limit: Option<usize>) -> Self {
+        self.log_settings.dev_auto_limit(limit);
+        self
+    }
+
+    #[cfg(feature = "wire-trace")]
+    fn wire_trace(
+        mut self,
+        f: impl Fn(sqlx_core::wire_trace::Direction, &[u8]) + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings.wire_trace = Some(sqlx_core::wire_trace::WireTrace::new(f));
+        self
+    }
 }
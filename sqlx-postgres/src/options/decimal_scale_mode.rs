@@ -0,0 +1,17 @@
+/// What to do with a bound decimal that has more decimal places than the scale of the `NUMERIC`
+/// column it is assigned to.
+///
+/// It is used by the [`decimal_scale_mode`](super::PgConnectOptions::decimal_scale_mode) method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PgDecimalScaleMode {
+    /// Let the server round the value to the scale of the column, e.g. `1.005` to `1.01` for a
+    /// `NUMERIC(10, 2)` column.
+    ///
+    /// This is the server's behavior.
+    #[default]
+    Round,
+
+    /// Fail the query with [`Error::DecimalScaleOverflow`][sqlx_core::Error::DecimalScaleOverflow]
+    /// before it is executed.
+    Error,
+}
@@ -1,15 +1,29 @@
 use std::borrow::Cow;
 use std::env::var;
-use std::fmt::{Display, Write};
+use std::fmt::{self, Debug, Display, Formatter, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 
+#[cfg(unix)]
+use std::{os::fd::OwnedFd, sync::Mutex};
+
+pub use channel_binding::PgChannelBinding;
+pub use decimal_scale_mode::PgDecimalScaleMode;
+pub use plan_cache_mode::PgPlanCacheMode;
 pub use ssl_mode::PgSslMode;
 
-use crate::{connection::LogSettings, net::tls::CertificateInput};
+use crate::{connection::LogSettings, net::tls::CertificateInput, PgNotice};
+use sqlx_core::column::DuplicateColumnNames;
 
+mod channel_binding;
 mod connect;
+mod decimal_scale_mode;
+#[cfg(feature = "offline")]
+mod offline;
 mod parse;
 mod pgpass;
+mod plan_cache_mode;
 mod ssl_mode;
 
 /// Options and flags which can be used to configure a PostgreSQL connection.
@@ -31,8 +45,10 @@ mod ssl_mode;
 /// |Parameter|Default|Description|
 /// |---------|-------|-----------|
 /// | `sslmode` | `prefer` | Determines whether or with what priority a secure SSL TCP/IP connection will be negotiated. See [`PgSslMode`]. |
+/// | `channel_binding` | `prefer` | Whether SCRAM authentication uses channel binding. See [`PgChannelBinding`]. |
 /// | `sslrootcert` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
+/// | `prepared-statements` | `true` | Set to `false` to send arguments without preparing statements, e.g. behind PgBouncer. See [`PgConnectOptions::prepared_statements()`]. |
 /// | `host` | `None` | Path to the directory containing a PostgreSQL unix domain socket, which will be used instead of TCP if set. |
 /// | `hostaddr` | `None` | Same as `host`, but only accepts IP addresses. |
 /// | `application-name` | `None` | The name will be displayed in the pg_stat_activity view and included in CSV log entries. |
@@ -91,18 +107,47 @@ pub struct PgConnectOptions {
     pub(crate) host: String,
     pub(crate) port: u16,
     pub(crate) socket: Option<PathBuf>,
+    // taken by the first connection, see `PgConnectOptions::connect_fd()`
+    #[cfg(unix)]
+    pub(crate) inherited_fd: Option<Arc<Mutex<Option<OwnedFd>>>>,
     pub(crate) username: String,
     pub(crate) password: Option<String>,
     pub(crate) database: Option<String>,
     pub(crate) ssl_mode: PgSslMode,
+    pub(crate) channel_binding: PgChannelBinding,
     pub(crate) ssl_root_cert: Option<CertificateInput>,
     pub(crate) ssl_client_cert: Option<CertificateInput>,
     pub(crate) ssl_client_key: Option<CertificateInput>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) prepared_statements: bool,
+    pub(crate) cast_parameters: bool,
+    pub(crate) recover_stale_statements: bool,
+    #[cfg(feature = "offline")]
+    pub(crate) offline_describe_cache: Option<std::sync::Arc<offline::OfflineDescribeCache>>,
+    pub(crate) duplicate_column_names: DuplicateColumnNames,
+    pub(crate) lenient_integer_decode: bool,
+    pub(crate) text_types: Arc<[String]>,
     pub(crate) application_name: Option<String>,
     pub(crate) log_settings: LogSettings,
     pub(crate) extra_float_digits: Option<Cow<'static, str>>,
     pub(crate) options: Option<String>,
+    pub(crate) verify_search_path: Option<String>,
+    pub(crate) warmup_query: Option<String>,
+    pub(crate) plan_cache_mode: Option<PgPlanCacheMode>,
+    pub(crate) lock_timeout: Option<Duration>,
+    pub(crate) notices_as_errors: Option<NoticeFilter>,
+    pub(crate) strict_timezone: bool,
+    pub(crate) decimal_scale_mode: PgDecimalScaleMode,
+}
+
+/// See [`PgConnectOptions::notices_as_errors()`].
+#[derive(Clone)]
+pub(crate) struct NoticeFilter(pub(crate) Arc<dyn Fn(&PgNotice) -> bool + Send + Sync>);
+
+impl Debug for NoticeFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("NoticeFilter")
+    }
 }
 
 impl Default for PgConnectOptions {
@@ -126,6 +171,7 @@ impl PgConnectOptions {
     ///  * `PGSSLCERT`
     ///  * `PGSSLKEY`
     ///  * `PGSSLMODE`
+    ///  * `PGCHANNELBINDING`
     ///  * `PGAPPNAME`
     ///
     /// # Example
@@ -154,6 +200,8 @@ impl PgConnectOptions {
             port,
             host,
             socket: None,
+            #[cfg(unix)]
+            inherited_fd: None,
             username,
             password: var("PGPASSWORD").ok(),
             database,
@@ -164,11 +212,30 @@ impl PgConnectOptions {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or_default(),
+            channel_binding: var("PGCHANNELBINDING")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or_default(),
             statement_cache_capacity: 100,
+            prepared_statements: true,
+            cast_parameters: false,
+            recover_stale_statements: true,
+            #[cfg(feature = "offline")]
+            offline_describe_cache: None,
+            duplicate_column_names: DuplicateColumnNames::default(),
+            lenient_integer_decode: false,
+            text_types: Arc::new([]),
             application_name: var("PGAPPNAME").ok(),
             extra_float_digits: Some("2".into()),
             log_settings: Default::default(),
             options: var("PGOPTIONS").ok(),
+            verify_search_path: None,
+            warmup_query: None,
+            plan_cache_mode: None,
+            lock_timeout: None,
+            notices_as_errors: None,
+            strict_timezone: false,
+            decimal_scale_mode: PgDecimalScaleMode::Round,
         }
     }
 
@@ -231,6 +298,47 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets a socket that is already connected to the server, such as one inherited from a
+    /// supervising process in a sandbox, to use instead of opening a new one.
+    ///
+    /// The socket may be a TCP or Unix domain socket; a `RawFd` can be converted with
+    /// [`OwnedFd::from_raw_fd()`][std::os::fd::FromRawFd::from_raw_fd]. The protocol starts
+    /// from the beginning on it, so it must not have been used to talk to the server yet.
+    /// TLS is negotiated over it as usual according to [`ssl_mode()`][Self::ssl_mode], and
+    /// the server certificate is checked against [`host()`][Self::host], which is otherwise
+    /// unused, as are [`port()`][Self::port] and [`socket()`][Self::socket].
+    ///
+    /// A socket can carry only one connection: it is taken by the first connection made with
+    /// these options (or any clone of them) and closed when that connection is closed. Later
+    /// attempts fail with [`Error::Configuration`][sqlx_core::Error::Configuration], so
+    /// a pool using these options should be limited to one connection and can't replace it.
+    ///
+    /// Queries on the connection can't be cancelled, since a cancel request is sent over a new
+    /// connection: [`PgConnection::cancel_token()`][crate::PgConnection::cancel_token] returns
+    /// `None`, and [`Query::total_timeout()`][sqlx_core::query::Query::total_timeout] abandons
+    /// the query without cancelling it.
+    ///
+    /// Only available on Unix.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// use std::os::fd::{FromRawFd, OwnedFd};
+    ///
+    /// // e.g. passed by the supervisor as file descriptor 3
+    /// let fd = unsafe { OwnedFd::from_raw_fd(3) };
+    ///
+    /// let options = PgConnectOptions::new()
+    ///     .host("db.internal")
+    ///     .connect_fd(fd);
+    /// ```
+    #[cfg(unix)]
+    pub fn connect_fd(mut self, fd: impl Into<OwnedFd>) -> Self {
+        self.inherited_fd = Some(Arc::new(Mutex::new(Some(fd.into()))));
+        self
+    }
+
     /// Sets the username to connect as.
     ///
     /// Defaults to be the same as the operating system name of
@@ -297,6 +405,31 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets whether SCRAM authentication uses channel binding (`SCRAM-SHA-256-PLUS`).
+    ///
+    /// With channel binding, the client proves that it authenticated over the same TLS
+    /// connection the server sees, using the `tls-server-end-point` binding of the server's
+    /// certificate, so a man in the middle can't relay the authentication. Servers that only
+    /// allow `scram-sha-256` over `hostssl` offer it.
+    ///
+    /// By default ([`PgChannelBinding::Prefer`]) it is used when the connection is encrypted
+    /// and the server offers it, falling back to plain SCRAM or the server's other methods
+    /// otherwise. [`PgChannelBinding::Require`] fails to connect without it, which also needs
+    /// [`ssl_mode()`][Self::ssl_mode] to negotiate TLS.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::{PgChannelBinding, PgConnectOptions, PgSslMode};
+    /// let options = PgConnectOptions::new()
+    ///     .ssl_mode(PgSslMode::VerifyFull)
+    ///     .channel_binding(PgChannelBinding::Require);
+    /// ```
+    pub fn channel_binding(mut self, mode: PgChannelBinding) -> Self {
+        self.channel_binding = mode;
+        self
+    }
+
     /// Sets the name of a file containing SSL certificate authority (CA) certificate(s).
     /// If the file exists, the server's certificate will be verified to be signed by
     /// one of these authorities.
@@ -426,6 +559,169 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets whether queries with arguments are run as prepared statements.
+    ///
+    /// By default, the SQL of each query with arguments is prepared as a statement on the
+    /// server, which the arguments are then bound to. Prepared statements live on the server
+    /// connection they were prepared on, which breaks when connecting through
+    /// [PgBouncer] in transaction pooling mode, or a similar proxy: the next query may be run
+    /// on a different server connection, where the statement doesn't exist (or a different
+    /// statement exists under the same name).
+    ///
+    /// When set to `false`, queries with arguments are sent with the simple query protocol
+    /// instead, like queries without arguments. Each `$N` placeholder is replaced by the value
+    /// of the argument, formatted as a string constant cast to the type of the argument,
+    /// e.g. `'42'::"int4"`. No statement is prepared on the server, and the statement cache is
+    /// not used.
+    ///
+    /// This comes with some costs:
+    ///
+    /// * Arguments are no longer sent in the binary format. They are converted from their
+    ///   binary encoding to text on the client, which is only supported for the built-in scalar
+    ///   types, arrays, ranges, enums, domains and composite types of these, and the `citext`,
+    ///   `ltree`, `lquery` and `hstore` extension types. Other arguments, such as `MONEY`, fail
+    ///   the query with [`Error::Encode`][sqlx_core::Error::Encode].
+    /// * Rows are received in the text format, which not every type can be decoded from,
+    ///   e.g. `&[u8]` and [`PgInterval`][crate::types::PgInterval]. The server also parses and
+    ///   plans the query every time it runs.
+    /// * A query string with arguments may contain several statements, as the simple query
+    ///   protocol allows it.
+    ///
+    /// ### Injection
+    ///
+    /// Formatting arguments into the query string is only as safe as the escaping. Quotes and
+    /// backslashes in values are escaped so that string constants mean the same regardless of
+    /// the `standard_conforming_strings` setting, and values containing NUL bytes are
+    /// rejected. The escaping relies on the `client_encoding` of the connection being `UTF8`,
+    /// which SQLx sets when connecting; do not change it. Placeholders inside string
+    /// constants, quoted identifiers and comments are left alone. Never build the query
+    /// string itself from untrusted input, with or without prepared statements.
+    ///
+    /// Statements prepared explicitly, through [`Executor::prepare()`], are still prepared on
+    /// the server.
+    ///
+    /// The default is `true`.
+    ///
+    /// [PgBouncer]: https://www.pgbouncer.org/features.html
+    /// [`Executor::prepare()`]: sqlx_core::executor::Executor::prepare
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .host("pgbouncer")
+    ///     .prepared_statements(false);
+    /// ```
+    pub fn prepared_statements(mut self, enabled: bool) -> Self {
+        self.prepared_statements = enabled;
+        self
+    }
+
+    /// Cast each parameter of a prepared statement to the type the server infers for it, where
+    /// that is not the type of the bound argument. Defaults to `false`.
+    ///
+    /// Normally the type of each argument, e.g. `TEXT` for a `&str`, is sent along with the
+    /// statement and must fit where the parameter is used. With this enabled, a statement that
+    /// fails to prepare because an argument doesn't fit (e.g. `operator does not exist:
+    /// text + integer`) is described without argument types, and each parameter whose inferred
+    /// type differs is rewritten to e.g. `($1::INTEGER)`, so the server converts the argument
+    /// as if it were written with an explicit cast. The rewritten SQL is remembered for the
+    /// connection, so later executions skip the failed attempt.
+    ///
+    /// This changes the SQL that is sent, and costs two extra round trips the first time a
+    /// statement is rewritten. Only casts to built-in types are added. Statements that prepare
+    /// without error are left alone, as are statements prepared explicitly or with
+    /// [`prepared_statements(false)`][Self::prepared_statements].
+    ///
+    /// Statements are only rewritten outside of a transaction: the failed attempt aborts the
+    /// transaction, so there, the original error is returned and later statements fail with
+    /// `current transaction is aborted` (SQLSTATE `25P02`) until it is rolled back. A statement
+    /// that was already rewritten outside of a transaction uses the rewritten SQL in one, too.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new().cast_parameters(true);
+    /// ```
+    pub fn cast_parameters(mut self, enabled: bool) -> Self {
+        self.cast_parameters = enabled;
+        self
+    }
+
+    /// Clear the statement cache and run a query again when it fails because its cached
+    /// statement became invalid, e.g. with `cached plan must not change result type` after a
+    /// migration altered the type of a column it returns. Defaults to `true`.
+    ///
+    /// A schema change like that breaks every statement of the connection that uses the
+    /// changed table, so rather than dropping just the failed statement, all cached statements
+    /// are closed and prepared again on their next use. The query is retried once, and only
+    /// when it is safe to do so: outside of a transaction (where the error aborts the
+    /// transaction), before any rows were returned, and not for statements prepared
+    /// explicitly with [`Executor::prepare()`], whose metadata may be stale as well.
+    ///
+    /// With this disabled, the error is returned and only the failed statement is dropped from
+    /// the cache.
+    ///
+    /// [`Executor::prepare()`]: sqlx_core::executor::Executor::prepare
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new().recover_stale_statements(false);
+    /// ```
+    pub fn recover_stale_statements(mut self, enabled: bool) -> Self {
+        self.recover_stale_statements = enabled;
+        self
+    }
+
+    /// Seed the metadata of prepared statements from the query data that `cargo sqlx prepare`
+    /// saved in `dir` (usually the `.sqlx` directory of the crate), instead of having the server
+    /// describe each statement the first time a connection prepares it.
+    ///
+    /// This saves the `Describe` part of preparing statements that are known at compile time,
+    /// along with any queries it takes to look up the types of the parameters and columns,
+    /// which reduces the latency of the first execution of each query on a new connection.
+    /// The statement is still parsed on the server.
+    ///
+    /// The files are read once, when the first connection with these options is opened;
+    /// opening the connection fails if they cannot be read. Queries that are not in the cache,
+    /// or that use custom types (whose OIDs are not saved), are described as usual.
+    ///
+    /// ### Note: Trusts the Cache
+    /// The saved metadata is used as-is. If it does not match the live schema, e.g. because a
+    /// column changed type after `cargo sqlx prepare` was last run, rows may fail to decode or
+    /// be decoded incorrectly. Only enable this if the query data is always regenerated
+    /// and deployed together with schema changes.
+    ///
+    /// Requires the `offline-describe-cache` feature of `sqlx`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new().offline_describe_cache(".sqlx");
+    /// ```
+    #[cfg(feature = "offline")]
+    pub fn offline_describe_cache(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.offline_describe_cache = Some(std::sync::Arc::new(
+            offline::OfflineDescribeCache::new(dir.into()),
+        ));
+        self
+    }
+
+    /// Sets how result columns that share a name are handled when looking them up by name,
+    /// e.g. the `id` columns of two joined tables.
+    ///
+    /// See [`DuplicateColumnNames`] for the available policies.
+    /// Defaults to [`DuplicateColumnNames::Last`].
+    pub fn duplicate_column_names(mut self, policy: DuplicateColumnNames) -> Self {
+        self.duplicate_column_names = policy;
+        self
+    }
+
     /// Sets the application name. Defaults to None
     ///
     /// # Example
@@ -520,6 +816,197 @@ impl PgConnectOptions {
         self
     }
 
+    /// Sets the `search_path` that [`ping()`][sqlx_core::connection::Connection::ping] expects
+    /// the session to have, as reported by `SHOW search_path`.
+    ///
+    /// When set, `ping()` fetches the session's `search_path` instead of sending an empty
+    /// message, and returns an error if it differs from `expected`. The check costs the same
+    /// single round-trip as the regular ping.
+    ///
+    /// Because the pool pings connections when they are released and, unless
+    /// [`test_before_acquire(false)`][sqlx_core::pool::PoolOptions::test_before_acquire] is set,
+    /// before handing them out, a connection whose `search_path` was changed (e.g. by a stray
+    /// `SET search_path`) is closed instead of being reused. With a schema per tenant,
+    /// this guards against a query running against another tenant's schema.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .options([("search_path", "tenant_a")])
+    ///     .verify_search_path("tenant_a");
+    /// ```
+    pub fn verify_search_path(mut self, expected: &str) -> Self {
+        self.verify_search_path = Some(expected.to_owned());
+        self
+    }
+
+    /// Sets a query to run once on every new connection, after it has been configured.
+    ///
+    /// This is meant for priming connection-local state, such as caches, that would
+    /// otherwise be populated by the first real query. Unlike the statements the driver issues
+    /// to configure the session, the warmup query may be a `SELECT`; any rows it returns are
+    /// discarded. An error from the query fails the connection attempt.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .warmup_query("SELECT count(*) FROM pg_extension");
+    /// ```
+    pub fn warmup_query(mut self, sql: &str) -> Self {
+        self.warmup_query = Some(sql.to_owned());
+        self
+    }
+
+    /// Sets the `plan_cache_mode` of every new connection, which decides whether prepared
+    /// statements are planned for their actual parameters or reuse a generic plan.
+    ///
+    /// Forcing custom plans helps with queries on skewed data, where a generic plan can be
+    /// much slower for some parameter values. The setting is applied with `SET` after
+    /// connecting, and applied again by [`Connection::reset()`][sqlx_core::connection::Connection::reset],
+    /// whose `DISCARD ALL` would otherwise undo it. Requires PostgreSQL 12 or later.
+    ///
+    /// By default, the server's setting is used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::{PgConnectOptions, PgPlanCacheMode};
+    /// let options = PgConnectOptions::new()
+    ///     .plan_cache_mode(PgPlanCacheMode::ForceCustomPlan);
+    /// ```
+    pub fn plan_cache_mode(mut self, mode: PgPlanCacheMode) -> Self {
+        self.plan_cache_mode = Some(mode);
+        self
+    }
+
+    /// Sets the `lock_timeout` of every new connection, which bounds how long a statement waits
+    /// to acquire a lock on a table, row or other object before it fails.
+    ///
+    /// Unlike `statement_timeout`, this only limits the time spent waiting on other sessions,
+    /// so slow queries under contention fail fast instead of piling up behind a lock. A statement
+    /// that times out fails with a database error for which
+    /// [`is_lock_timeout()`][sqlx_core::error::DatabaseError::is_lock_timeout] returns `true`. The
+    /// setting is applied with `SET` after connecting, and applied again by
+    /// [`Connection::reset()`][sqlx_core::connection::Connection::reset], so with
+    /// [`ResetOnReturn::DiscardAll`][sqlx_core::pool::ResetOnReturn::DiscardAll] a change made
+    /// with `SET lock_timeout` doesn't outlive a connection's return to the pool.
+    ///
+    /// A timeout of zero disables it. By default, the server's setting is used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::time::Duration;
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .lock_timeout(Duration::from_secs(2));
+    /// ```
+    pub fn lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = Some(timeout);
+        self
+    }
+
+    /// Makes notices for which `filter` returns `true` fail the query that is running when they
+    /// are received, with an [`Error::Database`][sqlx_core::Error::Database] holding the notice.
+    ///
+    /// This turns server warnings that should never happen, such as a `RAISE WARNING` from
+    /// a trigger, into hard failures, e.g. in tests. Every notice is still logged to the
+    /// `sqlx::postgres::notice` target first.
+    ///
+    /// The error comes from the client, so the statement still runs to completion on the
+    /// server; its effects are only undone if it ran in a transaction that is then rolled back.
+    ///
+    /// By default, notices never cause an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::{PgConnectOptions, PgSeverity};
+    /// let options = PgConnectOptions::new()
+    ///     .notices_as_errors(|notice| notice.severity() == PgSeverity::Warning);
+    /// ```
+    pub fn notices_as_errors(
+        mut self,
+        filter: impl Fn(&PgNotice) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.notices_as_errors = Some(NoticeFilter(Arc::new(filter)));
+        self
+    }
+
+    /// Rejects timestamps without a time zone where one is easily mistaken for the other.
+    ///
+    /// With this enabled, binding a `TIMESTAMP` value, such as a `chrono::NaiveDateTime` or
+    /// a `time::PrimitiveDateTime` (or an array or range of them), fails the query with an
+    /// [`Error::Encode`][sqlx_core::Error::Encode] naming the parameter, before it is sent.
+    /// Decoding a `TIMESTAMPTZ` column into one of these types fails with an
+    /// [`Error::ColumnDecode`][sqlx_core::Error::ColumnDecode] naming the column, even with
+    /// [`Row::try_get_unchecked()`][sqlx_core::row::Row::try_get_unchecked] or a type override
+    /// in `query!()`. Use `DateTime<Utc>` or `OffsetDateTime` and `TIMESTAMPTZ` columns instead.
+    ///
+    /// Values that are only ever read from `TIMESTAMP` columns still decode, as there is no time
+    /// zone to lose. Elements of arrays and ranges received as text are not checked, since the
+    /// server doesn't send their type.
+    ///
+    /// Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::PgConnectOptions;
+    /// let options = PgConnectOptions::new()
+    ///     .strict_timezone(true);
+    /// ```
+    pub fn strict_timezone(mut self, enabled: bool) -> Self {
+        self.strict_timezone = enabled;
+        self
+    }
+
+    /// Sets what happens when a decimal bound to a query has more decimal places than the
+    /// scale of the `NUMERIC` column it is assigned to.
+    ///
+    /// By default ([`PgDecimalScaleMode::Round`]) the server silently rounds the value, which
+    /// for financial values can go unnoticed. With [`PgDecimalScaleMode::Error`], such a query
+    /// fails with [`Error::DecimalScaleOverflow`][sqlx_core::Error::DecimalScaleOverflow]
+    /// before it is executed instead. This applies to any `NUMERIC` parameter, whether bound as a
+    /// `rust_decimal::Decimal`, a `bigdecimal::BigDecimal` or otherwise, and trailing zeros
+    /// don't count, so `1.50` fits a scale of 1.
+    ///
+    /// The server doesn't report which column a parameter is assigned to, so this is worked
+    /// out from the SQL for the common forms `INSERT INTO table (col, ..) VALUES ($1, ..), ..`
+    /// and `UPDATE table SET col = $1, ..`, where the parameter is the whole value. Other
+    /// parameters are not checked. The scales of the columns are looked up the first time a
+    /// statement is executed and cached with it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_postgres::{PgConnectOptions, PgDecimalScaleMode};
+    /// let options = PgConnectOptions::new()
+    ///     .decimal_scale_mode(PgDecimalScaleMode::Error);
+    /// ```
+    pub fn decimal_scale_mode(mut self, mode: PgDecimalScaleMode) -> Self {
+        self.decimal_scale_mode = mode;
+        self
+    }
+
+    /// Take the socket passed to [`connect_fd()`][Self::connect_fd] for a new connection.
+    #[cfg(unix)]
+    pub(crate) fn take_inherited_fd(&self) -> Result<OwnedFd, sqlx_core::Error> {
+        let fd = self.inherited_fd.as_ref().and_then(|fd| fd.lock().unwrap().take());
+
+        fd.ok_or_else(|| {
+            sqlx_core::Error::Configuration(
+                "the socket passed to `PgConnectOptions::connect_fd()` was already used by \
+                 another connection"
+                    .into(),
+            )
+        })
+    }
+
     /// We try using a socket if hostname starts with `/` or if socket parameter
     /// is specified.
     pub(crate) fn fetch_socket(&self) -> Option<String> {
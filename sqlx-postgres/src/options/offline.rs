@@ -0,0 +1,154 @@
+use std::fmt::{self, Debug, Formatter};
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use sqlx_core::column::DuplicateColumnNames;
+use sqlx_core::database::Database;
+use sqlx_core::describe::Describe;
+use sqlx_core::Either;
+
+use crate::error::Error;
+use crate::statement::PgStatementMetadata;
+use crate::type_info::PgType;
+use crate::{HashMap, PgColumn, PgTypeInfo, Postgres};
+
+/// The statement metadata saved by `cargo sqlx prepare`, keyed by the query string.
+///
+/// See [`PgConnectOptions::offline_describe_cache()`][super::PgConnectOptions::offline_describe_cache].
+pub(crate) struct OfflineDescribeCache {
+    dir: PathBuf,
+    statements: OnceLock<HashMap<String, SavedStatement>>,
+}
+
+struct SavedStatement {
+    parameters: Vec<PgTypeInfo>,
+    columns: Vec<PgColumn>,
+}
+
+/// The parts of a `query-<hash>.json` file that are needed to seed a statement.
+#[derive(Deserialize)]
+struct QueryData<'a> {
+    db_name: String,
+    query: String,
+    #[serde(borrow)]
+    describe: &'a RawValue,
+}
+
+impl OfflineDescribeCache {
+    pub(crate) fn new(dir: PathBuf) -> Self {
+        OfflineDescribeCache {
+            dir,
+            statements: OnceLock::new(),
+        }
+    }
+
+    /// Read the query data files, if that hasn't been done yet.
+    pub(crate) async fn load(&self) -> Result<(), Error> {
+        if self.statements.get().is_some() {
+            return Ok(());
+        }
+
+        let mut statements = HashMap::new();
+        let mut entries = sqlx_core::fs::read_dir(self.dir.clone())
+            .await
+            .map_err(|e| self.error(e))?;
+
+        while let Some(entry) = entries.next().await.map_err(|e| self.error(e))? {
+            let is_query_data = entry.file_name.to_str().map_or(false, |name| {
+                name.starts_with("query-") && name.ends_with(".json")
+            });
+
+            if !is_query_data {
+                continue;
+            }
+
+            let contents = sqlx_core::fs::read_to_string(&entry.path)
+                .await
+                .map_err(|e| self.error(e))?;
+
+            let data: QueryData<'_> = serde_json::from_str(&contents).map_err(|e| self.error(e))?;
+
+            if data.db_name != Postgres::NAME {
+                continue;
+            }
+
+            let describe: Describe<Postgres> =
+                serde_json::from_str(data.describe.get()).map_err(|e| self.error(e))?;
+
+            let Some(Either::Left(parameters)) = describe.parameters else {
+                continue;
+            };
+
+            // The OIDs of custom types are not saved, so statements using them are still
+            // described by the server.
+            if parameters.iter().all(is_builtin)
+                && describe
+                    .columns
+                    .iter()
+                    .all(|column| is_builtin(&column.type_info))
+            {
+                statements.insert(
+                    data.query,
+                    SavedStatement {
+                        parameters,
+                        columns: describe.columns,
+                    },
+                );
+            }
+        }
+
+        // another connection may have finished loading first, which is fine
+        let _ = self.statements.set(statements);
+
+        Ok(())
+    }
+
+    /// Look up the metadata saved for `sql`, naming the columns as the connection would.
+    pub(crate) fn get(
+        &self,
+        sql: &str,
+        duplicate_column_names: DuplicateColumnNames,
+    ) -> Option<Arc<PgStatementMetadata>> {
+        let saved = self.statements.get()?.get(sql)?;
+
+        let mut columns = saved.columns.clone();
+        let column_names =
+            duplicate_column_names.column_names(columns.iter_mut().map(|column| &mut column.name));
+
+        Some(Arc::new(PgStatementMetadata {
+            parameters: saved.parameters.clone(),
+            columns,
+            column_names: Arc::new(column_names),
+        }))
+    }
+
+    fn error(&self, error: impl std::fmt::Display) -> Error {
+        Error::Configuration(
+            format!(
+                "failed to load the offline describe cache from {}: {error}",
+                self.dir.display()
+            )
+            .into(),
+        )
+    }
+}
+
+fn is_builtin(ty: &PgTypeInfo) -> bool {
+    !matches!(
+        ty.0,
+        PgType::Custom(_)
+            | PgType::DeclareWithName(_)
+            | PgType::DeclareWithOid(_)
+            | PgType::DeclareArrayOf(_)
+    )
+}
+
+impl Debug for OfflineDescribeCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OfflineDescribeCache")
+            .field("dir", &self.dir)
+            .finish_non_exhaustive()
+    }
+}
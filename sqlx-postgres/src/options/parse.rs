@@ -49,6 +49,10 @@ impl PgConnectOptions {
                     options = options.ssl_mode(value.parse().map_err(Error::config)?);
                 }
 
+                "channel_binding" | "channel-binding" => {
+                    options = options.channel_binding(value.parse().map_err(Error::config)?);
+                }
+
                 "sslrootcert" | "ssl-root-cert" | "ssl-ca" => {
                     options = options.ssl_root_cert(&*value);
                 }
@@ -62,6 +66,10 @@ impl PgConnectOptions {
                         options.statement_cache_capacity(value.parse().map_err(Error::config)?);
                 }
 
+                "prepared-statements" => {
+                    options = options.prepared_statements(value.parse().map_err(Error::config)?);
+                }
+
                 "host" => {
                     if value.starts_with('/') {
                         options = options.socket(&*value);
@@ -162,6 +170,11 @@ impl PgConnectOptions {
             &self.statement_cache_capacity.to_string(),
         );
 
+        if !self.prepared_statements {
+            url.query_pairs_mut()
+                .append_pair("prepared-statements", "false");
+        }
+
         url
     }
 }
@@ -202,6 +215,15 @@ fn it_parses_hostaddr_correctly_from_parameter() {
     assert_eq!("8.8.8.8", &opts.host);
 }
 
+#[test]
+fn it_parses_prepared_statements_correctly_from_parameter() {
+    let url = "postgres:///?prepared-statements=false";
+    let opts = PgConnectOptions::from_str(url).unwrap();
+
+    assert!(!opts.prepared_statements);
+    assert!(PgConnectOptions::from_str("postgres:///?prepared-statements=0").is_err());
+}
+
 #[test]
 fn it_parses_port_correctly_from_parameter() {
     let url = "postgres:///?port=1234";
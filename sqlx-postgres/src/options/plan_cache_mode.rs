@@ -0,0 +1,50 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// How the server plans prepared statements, set with the `plan_cache_mode` parameter.
+///
+/// It is used by the [`plan_cache_mode`](super::PgConnectOptions::plan_cache_mode) method.
+/// Requires PostgreSQL 12 or later.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PgPlanCacheMode {
+    /// Use a generic plan once it is not estimated to be much worse than the custom plans
+    /// made for the first executions of a statement.
+    ///
+    /// This is the server's default.
+    #[default]
+    Auto,
+
+    /// Plan every execution of a statement for its actual parameter values.
+    ForceCustomPlan,
+
+    /// Always use a generic plan that does not depend on the parameter values.
+    ForceGenericPlan,
+}
+
+impl PgPlanCacheMode {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            PgPlanCacheMode::Auto => "auto",
+            PgPlanCacheMode::ForceCustomPlan => "force_custom_plan",
+            PgPlanCacheMode::ForceGenericPlan => "force_generic_plan",
+        }
+    }
+}
+
+impl FromStr for PgPlanCacheMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "auto" => PgPlanCacheMode::Auto,
+            "force_custom_plan" => PgPlanCacheMode::ForceCustomPlan,
+            "force_generic_plan" => PgPlanCacheMode::ForceGenericPlan,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {s:?} for `plan_cache_mode`").into(),
+                ));
+            }
+        })
+    }
+}
@@ -1,9 +1,10 @@
-use crate::column::ColumnIndex;
+use crate::column::{column_index_by_name, ColumnIndex};
 use crate::error::Error;
 use crate::message::DataRow;
 use crate::statement::PgStatementMetadata;
 use crate::value::PgValueFormat;
-use crate::{PgColumn, PgValueRef, Postgres};
+use crate::types::Type;
+use crate::{PgColumn, PgTypeInfo, PgValueRef, Postgres};
 pub(crate) use sqlx_core::row::Row;
 use sqlx_core::type_checking::TypeChecking;
 use sqlx_core::value::ValueRef;
@@ -11,10 +12,17 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 /// Implementation of [`Row`] for PostgreSQL.
+#[derive(Clone)]
 pub struct PgRow {
     pub(crate) data: DataRow,
     pub(crate) format: PgValueFormat,
     pub(crate) metadata: Arc<PgStatementMetadata>,
+    // see `ConnectOptions::lenient_integer_decode()`
+    pub(crate) lenient_integers: bool,
+    // see `ConnectOptions::prefer_text_for()`
+    pub(crate) text_types: Arc<[String]>,
+    // see `PgConnectOptions::strict_timezone()`
+    pub(crate) strict_timezone: bool,
 }
 
 impl Row for PgRow {
@@ -33,21 +41,31 @@ impl Row for PgRow {
         let value = self.data.get(index);
 
         Ok(PgValueRef {
-            format: self.format,
+            // see `PgTypeInfo::result_format()`
+            format: match self.format {
+                PgValueFormat::Binary => column.type_info.result_format(&self.text_types),
+                PgValueFormat::Text => PgValueFormat::Text,
+            },
             row: Some(&self.data.storage),
             type_info: column.type_info.clone(),
             value,
+            strict_timezone: self.strict_timezone,
         })
     }
+
+    fn lenient_integer_decode(&self) -> bool {
+        self.lenient_integers
+    }
+
+    fn decodes_as_text(&self, ty: &PgTypeInfo, expected: &PgTypeInfo) -> bool {
+        ty.result_format(&self.text_types) == PgValueFormat::Text
+            && <str as Type<Postgres>>::compatible(expected)
+    }
 }
 
 impl ColumnIndex<PgRow> for &'_ str {
     fn index(&self, row: &PgRow) -> Result<usize, Error> {
-        row.metadata
-            .column_names
-            .get(*self)
-            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
-            .copied()
+        column_index_by_name(&row.metadata.column_names, self)
     }
 }
 
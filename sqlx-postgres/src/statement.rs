@@ -1,5 +1,5 @@
 use super::{PgColumn, PgTypeInfo};
-use crate::column::ColumnIndex;
+use crate::column::{column_index_by_name, ColumnIndex};
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::{PgArguments, Postgres};
@@ -51,12 +51,7 @@ impl<'q> Statement<'q> for PgStatement<'q> {
 
 impl ColumnIndex<PgStatement<'_>> for &'_ str {
     fn index(&self, statement: &PgStatement<'_>) -> Result<usize, Error> {
-        statement
-            .metadata
-            .column_names
-            .get(*self)
-            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
-            .copied()
+        column_index_by_name(&statement.metadata.column_names, self)
     }
 }
 
@@ -7,6 +7,55 @@ use crate::{PgConnection, Postgres};
 
 pub(crate) use sqlx_core::transaction::*;
 
+impl PgConnection {
+    /// Defer checking all deferrable constraints until the end of the current transaction,
+    /// by executing `SET CONSTRAINTS ALL DEFERRED`.
+    ///
+    /// This allows writes that temporarily violate a constraint, e.g. inserting rows that
+    /// reference each other in any order, as long as the constraint holds again on commit.
+    /// Only constraints declared as `DEFERRABLE` are affected.
+    ///
+    /// Returns [`Error::TransactionRequired`] if the connection is not in a transaction,
+    /// because the setting would have no effect. Usually called on a [`Transaction`]:
+    ///
+    /// ```rust,no_run
+    /// # async fn example(pool: sqlx::PgPool) -> sqlx::Result<()> {
+    /// let mut tx = pool.begin().await?;
+    /// tx.defer_constraints().await?;
+    /// // ...
+    /// tx.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn defer_constraints(&mut self) -> Result<(), Error> {
+        self.set_constraints("ALL").await
+    }
+
+    /// Defer checking the deferrable constraint `name` until the end of the current
+    /// transaction, by executing `SET CONSTRAINTS "<name>" DEFERRED`.
+    ///
+    /// The name is quoted as a single identifier, so it is case-sensitive and may not be
+    /// schema-qualified.
+    ///
+    /// Returns [`Error::TransactionRequired`] if the connection is not in a transaction.
+    /// See [`defer_constraints()`][Self::defer_constraints] for details.
+    pub async fn defer_constraint(&mut self, name: &str) -> Result<(), Error> {
+        let name = format!("\"{}\"", name.replace('"', "\"\""));
+        self.set_constraints(&name).await
+    }
+
+    async fn set_constraints(&mut self, constraints: &str) -> Result<(), Error> {
+        if self.inner.transaction_depth == 0 {
+            return Err(Error::TransactionRequired);
+        }
+
+        self.execute(&*format!("SET CONSTRAINTS {constraints} DEFERRED"))
+            .await?;
+
+        Ok(())
+    }
+}
+
 /// Implementation of [`TransactionManager`] for PostgreSQL.
 pub struct PgTransactionManager;
 
@@ -26,6 +26,14 @@ impl_type_checking!(
 
         sqlx::postgres::types::PgMoney,
 
+        sqlx::postgres::types::PgLsn,
+
+        sqlx::postgres::types::PgTid,
+
+        sqlx::postgres::types::PgRegConfig,
+
+        sqlx::postgres::types::PgAclItem,
+
         sqlx::postgres::types::PgLTree,
 
         sqlx::postgres::types::PgLQuery,
@@ -97,6 +105,14 @@ impl_type_checking!(
         Vec<f64> | &[f64],
         Vec<sqlx::postgres::types::Oid> | &[sqlx::postgres::types::Oid],
         Vec<sqlx::postgres::types::PgMoney> | &[sqlx::postgres::types::PgMoney],
+        Vec<sqlx::postgres::types::PgLsn> | &[sqlx::postgres::types::PgLsn],
+        Vec<sqlx::postgres::types::PgTid> | &[sqlx::postgres::types::PgTid],
+        Vec<sqlx::postgres::types::PgRegConfig> | &[sqlx::postgres::types::PgRegConfig],
+        Vec<sqlx::postgres::types::PgAclItem> | &[sqlx::postgres::types::PgAclItem],
+        Vec<sqlx::postgres::types::PgInterval> | &[sqlx::postgres::types::PgInterval],
+        Vec<sqlx::postgres::types::PgLTree> | &[sqlx::postgres::types::PgLTree],
+        Vec<sqlx::postgres::types::PgLQuery> | &[sqlx::postgres::types::PgLQuery],
+        Vec<sqlx::postgres::types::PgCube> | &[sqlx::postgres::types::PgCube],
 
         #[cfg(feature = "uuid")]
         Vec<sqlx::types::Uuid> | &[sqlx::types::Uuid],
@@ -140,6 +156,9 @@ impl_type_checking!(
         #[cfg(feature = "json")]
         Vec<sqlx::types::JsonValue> | &[sqlx::types::JsonValue],
 
+        #[cfg(feature = "bit-vec")]
+        Vec<sqlx::types::BitVec> | &[sqlx::types::BitVec],
+
         Vec<sqlx::postgres::types::PgHstore> | &[sqlx::postgres::types::PgHstore],
 
         // Ranges
@@ -154,23 +173,23 @@ impl_type_checking!(
         sqlx::postgres::types::PgRange<sqlx::types::Decimal>,
 
         #[cfg(all(feature = "chrono", not(feature = "time")))]
-        sqlx::postgres::types::PgRange<sqlx::types::chrono::NaiveDate>,
+        sqlx::postgres::types::chrono::PgDateRange,
 
         #[cfg(all(feature = "chrono", not(feature = "time")))]
-        sqlx::postgres::types::PgRange<sqlx::types::chrono::NaiveDateTime>,
+        sqlx::postgres::types::chrono::PgTsRange,
 
         #[cfg(all(feature = "chrono", not(feature = "time")))]
-        sqlx::postgres::types::PgRange<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>> |
+        sqlx::postgres::types::chrono::PgTsTzRange |
             sqlx::postgres::types::PgRange<sqlx::types::chrono::DateTime<_>>,
 
         #[cfg(feature = "time")]
-        sqlx::postgres::types::PgRange<sqlx::types::time::Date>,
+        sqlx::postgres::types::time::PgDateRange,
 
         #[cfg(feature = "time")]
-        sqlx::postgres::types::PgRange<sqlx::types::time::PrimitiveDateTime>,
+        sqlx::postgres::types::time::PgTsRange,
 
         #[cfg(feature = "time")]
-        sqlx::postgres::types::PgRange<sqlx::types::time::OffsetDateTime>,
+        sqlx::postgres::types::time::PgTsTzRange,
 
         // Range arrays
 
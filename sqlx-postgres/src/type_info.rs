@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use crate::ext::ustr::UStr;
 use crate::types::Oid;
+use crate::value::PgValueFormat;
 
 pub(crate) use sqlx_core::type_info::TypeInfo;
 
@@ -146,6 +147,16 @@ pub enum PgType {
     JsonpathArray,
     Money,
     MoneyArray,
+    PgLsn,
+    PgLsnArray,
+    Tid,
+    TidArray,
+    RegConfig,
+    RegConfigArray,
+    Aclitem,
+    AclitemArray,
+    Int2Vector,
+    OidVector,
 
     // https://www.postgresql.org/docs/9.3/datatype-pseudo.html
     Void,
@@ -198,6 +209,23 @@ impl PgTypeInfo {
         PgType::try_from_oid(oid).map(Self)
     }
 
+    /// The format a prepared statement should return values of this type in.
+    ///
+    /// This is binary, except for the few types that have no binary format and those named in
+    /// `text_types` (see `ConnectOptions::prefer_text_for()`).
+    pub(crate) fn result_format(&self, text_types: &[String]) -> PgValueFormat {
+        match self.0 {
+            PgType::Aclitem | PgType::AclitemArray => PgValueFormat::Text,
+            _ if text_types
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(self.name())) =>
+            {
+                PgValueFormat::Text
+            }
+            _ => PgValueFormat::Binary,
+        }
+    }
+
     /// Returns the _kind_ (simple, array, enum, etc.) for this type.
     pub fn kind(&self) -> &PgTypeKind {
         self.0.kind()
@@ -363,7 +391,9 @@ impl PgType {
             21 => PgType::Int2,
             23 => PgType::Int4,
             25 => PgType::Text,
+            22 => PgType::Int2Vector,
             26 => PgType::Oid,
+            30 => PgType::OidVector,
             114 => PgType::Json,
             199 => PgType::JsonArray,
             600 => PgType::Point,
@@ -431,6 +461,14 @@ impl PgType {
             2287 => PgType::RecordArray,
             2950 => PgType::Uuid,
             2951 => PgType::UuidArray,
+            3220 => PgType::PgLsn,
+            3221 => PgType::PgLsnArray,
+            27 => PgType::Tid,
+            1010 => PgType::TidArray,
+            3734 => PgType::RegConfig,
+            3735 => PgType::RegConfigArray,
+            1033 => PgType::Aclitem,
+            1034 => PgType::AclitemArray,
             3802 => PgType::Jsonb,
             3807 => PgType::JsonbArray,
             3904 => PgType::Int4Range,
@@ -539,6 +577,16 @@ impl PgType {
             PgType::RecordArray => Oid(2287),
             PgType::Uuid => Oid(2950),
             PgType::UuidArray => Oid(2951),
+            PgType::PgLsn => Oid(3220),
+            PgType::PgLsnArray => Oid(3221),
+            PgType::Tid => Oid(27),
+            PgType::TidArray => Oid(1010),
+            PgType::RegConfig => Oid(3734),
+            PgType::RegConfigArray => Oid(3735),
+            PgType::Aclitem => Oid(1033),
+            PgType::AclitemArray => Oid(1034),
+            PgType::Int2Vector => Oid(22),
+            PgType::OidVector => Oid(30),
             PgType::Jsonb => Oid(3802),
             PgType::JsonbArray => Oid(3807),
             PgType::Int4Range => Oid(3904),
@@ -661,6 +709,16 @@ impl PgType {
             PgType::JsonpathArray => "JSONPATH[]",
             PgType::Money => "MONEY",
             PgType::MoneyArray => "MONEY[]",
+            PgType::PgLsn => "PG_LSN",
+            PgType::PgLsnArray => "PG_LSN[]",
+            PgType::Tid => "TID",
+            PgType::TidArray => "TID[]",
+            PgType::RegConfig => "REGCONFIG",
+            PgType::RegConfigArray => "REGCONFIG[]",
+            PgType::Aclitem => "ACLITEM",
+            PgType::AclitemArray => "ACLITEM[]",
+            PgType::Int2Vector => "INT2VECTOR",
+            PgType::OidVector => "OIDVECTOR",
             PgType::Void => "VOID",
             PgType::Custom(ty) => &ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -762,6 +820,16 @@ impl PgType {
             PgType::JsonpathArray => "_jsonpath",
             PgType::Money => "money",
             PgType::MoneyArray => "_money",
+            PgType::PgLsn => "pg_lsn",
+            PgType::PgLsnArray => "_pg_lsn",
+            PgType::Tid => "tid",
+            PgType::TidArray => "_tid",
+            PgType::RegConfig => "regconfig",
+            PgType::RegConfigArray => "_regconfig",
+            PgType::Aclitem => "aclitem",
+            PgType::AclitemArray => "_aclitem",
+            PgType::Int2Vector => "int2vector",
+            PgType::OidVector => "oidvector",
             PgType::Void => "void",
             PgType::Custom(ty) => &ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -770,6 +838,31 @@ impl PgType {
         }
     }
 
+    /// Returns the length of a value of this type in the binary format, if it is always the same.
+    pub(crate) fn binary_size(&self) -> Option<usize> {
+        Some(match self {
+            PgType::Bool | PgType::Char => 1,
+            PgType::Int2 => 2,
+            PgType::Int4
+            | PgType::Oid
+            | PgType::Float4
+            | PgType::Date
+            | PgType::RegConfig => 4,
+            PgType::Macaddr | PgType::Tid => 6,
+            PgType::Int8
+            | PgType::Float8
+            | PgType::Money
+            | PgType::Time
+            | PgType::Timestamp
+            | PgType::Timestamptz
+            | PgType::Macaddr8
+            | PgType::PgLsn => 8,
+            PgType::Timetz => 12,
+            PgType::Uuid | PgType::Interval | PgType::Point => 16,
+            _ => return None,
+        })
+    }
+
     pub(crate) fn kind(&self) -> &PgTypeKind {
         match self {
             PgType::Bool => &PgTypeKind::Simple,
@@ -863,6 +956,17 @@ impl PgType {
             PgType::JsonpathArray => &PgTypeKind::Array(PgTypeInfo(PgType::Jsonpath)),
             PgType::Money => &PgTypeKind::Simple,
             PgType::MoneyArray => &PgTypeKind::Array(PgTypeInfo(PgType::Money)),
+            PgType::PgLsn => &PgTypeKind::Simple,
+            PgType::PgLsnArray => &PgTypeKind::Array(PgTypeInfo(PgType::PgLsn)),
+            PgType::Tid => &PgTypeKind::Simple,
+            PgType::TidArray => &PgTypeKind::Array(PgTypeInfo(PgType::Tid)),
+            PgType::RegConfig => &PgTypeKind::Simple,
+            PgType::RegConfigArray => &PgTypeKind::Array(PgTypeInfo(PgType::RegConfig)),
+            PgType::Aclitem => &PgTypeKind::Simple,
+            PgType::AclitemArray => &PgTypeKind::Array(PgTypeInfo(PgType::Aclitem)),
+            // Vectors are one-dimensional arrays, except in the text format.
+            PgType::Int2Vector => &PgTypeKind::Array(PgTypeInfo(PgType::Int2)),
+            PgType::OidVector => &PgTypeKind::Array(PgTypeInfo(PgType::Oid)),
 
             PgType::Void => &PgTypeKind::Pseudo,
 
@@ -931,6 +1035,16 @@ impl PgType {
             PgType::Macaddr8Array => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr8))),
             PgType::Money => None,
             PgType::MoneyArray => Some(Cow::Owned(PgTypeInfo(PgType::Money))),
+            PgType::PgLsn => None,
+            PgType::PgLsnArray => Some(Cow::Owned(PgTypeInfo(PgType::PgLsn))),
+            PgType::Tid => None,
+            PgType::TidArray => Some(Cow::Owned(PgTypeInfo(PgType::Tid))),
+            PgType::RegConfig => None,
+            PgType::RegConfigArray => Some(Cow::Owned(PgTypeInfo(PgType::RegConfig))),
+            PgType::Aclitem => None,
+            PgType::AclitemArray => Some(Cow::Owned(PgTypeInfo(PgType::Aclitem))),
+            PgType::Int2Vector => Some(Cow::Owned(PgTypeInfo(PgType::Int2))),
+            PgType::OidVector => Some(Cow::Owned(PgTypeInfo(PgType::Oid))),
             PgType::Macaddr => None,
             PgType::MacaddrArray => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr))),
             PgType::Inet => None,
@@ -1050,12 +1164,36 @@ impl TypeInfo for PgTypeInfo {
         matches!(self.0, PgType::Void)
     }
 
+    fn cast_name(&self) -> Option<Cow<'_, str>> {
+        match &self.0 {
+            // written the way `regtype` reads and prints it
+            PgType::Custom(ty) => Some(Cow::Borrowed(&ty.name)),
+            PgType::DeclareWithName(name) => Some(Cow::Borrowed(name)),
+            PgType::DeclareArrayOf(array) => Some(Cow::Borrowed(&array.name)),
+            // only known by its OID
+            PgType::DeclareWithOid(_) => None,
+            // the name in `pg_type`, quoted so that e.g. `"char"` isn't read as `bpchar`
+            ty => Some(Cow::Owned(format!("\"{}\"", ty.name()))),
+        }
+    }
+
     fn type_compatible(&self, other: &Self) -> bool
     where
         Self: Sized,
     {
         self == other
     }
+
+    fn is_integer_widening_to(&self, wider: &Self) -> bool {
+        let width = |ty: &PgTypeInfo| match ty.0 {
+            PgType::Int2 => Some(2),
+            PgType::Int4 => Some(4),
+            PgType::Int8 => Some(8),
+            _ => None,
+        };
+
+        matches!((width(self), width(wider)), (Some(a), Some(b)) if a < b)
+    }
 }
 
 impl PartialEq<PgCustomType> for PgCustomType {
@@ -1149,10 +1287,16 @@ impl PgTypeInfo {
     pub(crate) const OID: Self = Self(PgType::Oid);
     pub(crate) const OID_ARRAY: Self = Self(PgType::OidArray);
 
+    // space-separated vector of OIDs, used in the system catalogs
+    pub(crate) const OID_VECTOR: Self = Self(PgType::OidVector);
+
     // small-range integer; -32768 to +32767
     pub(crate) const INT2: Self = Self(PgType::Int2);
     pub(crate) const INT2_ARRAY: Self = Self(PgType::Int2Array);
 
+    // space-separated vector of INT2, used in the system catalogs
+    pub(crate) const INT2_VECTOR: Self = Self(PgType::Int2Vector);
+
     // typical choice for integer; -2147483648 to +2147483647
     pub(crate) const INT4: Self = Self(PgType::Int4);
     pub(crate) const INT4_ARRAY: Self = Self(PgType::Int4Array);
@@ -1177,6 +1321,22 @@ impl PgTypeInfo {
     pub(crate) const MONEY: Self = Self(PgType::Money);
     pub(crate) const MONEY_ARRAY: Self = Self(PgType::MoneyArray);
 
+    // write-ahead log location
+    pub(crate) const PG_LSN: Self = Self(PgType::PgLsn);
+    pub(crate) const PG_LSN_ARRAY: Self = Self(PgType::PgLsnArray);
+
+    // physical location of a row
+    pub(crate) const TID: Self = Self(PgType::Tid);
+    pub(crate) const TID_ARRAY: Self = Self(PgType::TidArray);
+
+    // text search configuration
+    pub(crate) const REGCONFIG: Self = Self(PgType::RegConfig);
+    pub(crate) const REGCONFIG_ARRAY: Self = Self(PgType::RegConfigArray);
+
+    // access privileges
+    pub(crate) const ACLITEM: Self = Self(PgType::Aclitem);
+    pub(crate) const ACLITEM_ARRAY: Self = Self(PgType::AclitemArray);
+
     //
     // date/time types
     // https://www.postgresql.org/docs/current/datatype-datetime.html
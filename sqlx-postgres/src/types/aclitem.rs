@@ -0,0 +1,311 @@
+use std::fmt::{self, Display, Formatter, Write};
+use std::str::FromStr;
+
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+
+/// An entry of a Postgres access control list, as found in `pg_class.relacl` and the other
+/// `ACLITEM[]` columns of the system catalogs.
+///
+/// The Postgres text format is `grantee=privileges/grantor`, e.g. `alice=arw*/postgres`, where
+/// an empty grantee stands for `PUBLIC` and each privilege is a single letter, followed by `*`
+/// if it was granted with the grant option. Role names are double-quoted where needed.
+/// [`Display`] and [`FromStr`] use the same format.
+///
+/// Postgres has no binary format for `ACLITEM`, so the driver requests `ACLITEM` columns in the
+/// text format. This type also decodes from text, e.g. `relacl::text[]`.
+/// Only decoding is supported.
+///
+/// See [Privileges](https://www.postgresql.org/docs/current/ddl-priv.html) in the Postgres manual.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PgAclItem {
+    /// The role the privileges are granted to, or `None` for `PUBLIC`.
+    pub grantee: Option<String>,
+
+    /// The privileges granted, in the order Postgres lists them.
+    pub privileges: Vec<PgAclPrivilege>,
+
+    /// The role that granted the privileges.
+    pub grantor: String,
+}
+
+/// A privilege in a [`PgAclItem`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PgAclPrivilege {
+    pub kind: PgAclPrivilegeKind,
+
+    /// Whether the grantee may grant the privilege to others (`WITH GRANT OPTION`).
+    pub grant_option: bool,
+}
+
+/// The kind of a [`PgAclPrivilege`], with the letter Postgres uses for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PgAclPrivilegeKind {
+    /// `SELECT` (`r`)
+    Select,
+    /// `INSERT` (`a`)
+    Insert,
+    /// `UPDATE` (`w`)
+    Update,
+    /// `DELETE` (`d`)
+    Delete,
+    /// `TRUNCATE` (`D`)
+    Truncate,
+    /// `REFERENCES` (`x`)
+    References,
+    /// `TRIGGER` (`t`)
+    Trigger,
+    /// `EXECUTE` (`X`)
+    Execute,
+    /// `USAGE` (`U`)
+    Usage,
+    /// `CREATE` (`C`)
+    Create,
+    /// `CONNECT` (`c`)
+    Connect,
+    /// `TEMPORARY` (`T`)
+    Temporary,
+    /// `SET` (`s`)
+    Set,
+    /// `ALTER SYSTEM` (`A`)
+    AlterSystem,
+    /// `MAINTAIN` (`m`)
+    Maintain,
+    /// A letter that isn't known to SQLx, e.g. from a newer version of Postgres.
+    Other(char),
+}
+
+impl PgAclPrivilegeKind {
+    /// The letter Postgres uses for this privilege.
+    pub fn code(&self) -> char {
+        match self {
+            PgAclPrivilegeKind::Select => 'r',
+            PgAclPrivilegeKind::Insert => 'a',
+            PgAclPrivilegeKind::Update => 'w',
+            PgAclPrivilegeKind::Delete => 'd',
+            PgAclPrivilegeKind::Truncate => 'D',
+            PgAclPrivilegeKind::References => 'x',
+            PgAclPrivilegeKind::Trigger => 't',
+            PgAclPrivilegeKind::Execute => 'X',
+            PgAclPrivilegeKind::Usage => 'U',
+            PgAclPrivilegeKind::Create => 'C',
+            PgAclPrivilegeKind::Connect => 'c',
+            PgAclPrivilegeKind::Temporary => 'T',
+            PgAclPrivilegeKind::Set => 's',
+            PgAclPrivilegeKind::AlterSystem => 'A',
+            PgAclPrivilegeKind::Maintain => 'm',
+            PgAclPrivilegeKind::Other(code) => *code,
+        }
+    }
+
+    /// The privilege for the letter Postgres uses for it.
+    pub fn from_code(code: char) -> Self {
+        match code {
+            'r' => PgAclPrivilegeKind::Select,
+            'a' => PgAclPrivilegeKind::Insert,
+            'w' => PgAclPrivilegeKind::Update,
+            'd' => PgAclPrivilegeKind::Delete,
+            'D' => PgAclPrivilegeKind::Truncate,
+            'x' => PgAclPrivilegeKind::References,
+            't' => PgAclPrivilegeKind::Trigger,
+            'X' => PgAclPrivilegeKind::Execute,
+            'U' => PgAclPrivilegeKind::Usage,
+            'C' => PgAclPrivilegeKind::Create,
+            'c' => PgAclPrivilegeKind::Connect,
+            'T' => PgAclPrivilegeKind::Temporary,
+            's' => PgAclPrivilegeKind::Set,
+            'A' => PgAclPrivilegeKind::AlterSystem,
+            'm' => PgAclPrivilegeKind::Maintain,
+            other => PgAclPrivilegeKind::Other(other),
+        }
+    }
+}
+
+impl PgAclItem {
+    /// Returns `true` if `kind` is granted, with or without the grant option.
+    pub fn has(&self, kind: PgAclPrivilegeKind) -> bool {
+        self.privileges.iter().any(|p| p.kind == kind)
+    }
+}
+
+impl Display for PgAclItem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(grantee) = &self.grantee {
+            write_role(f, grantee)?;
+        }
+
+        f.write_char('=')?;
+
+        for privilege in &self.privileges {
+            f.write_char(privilege.kind.code())?;
+
+            if privilege.grant_option {
+                f.write_char('*')?;
+            }
+        }
+
+        f.write_char('/')?;
+        write_role(f, &self.grantor)
+    }
+}
+
+impl FromStr for PgAclItem {
+    type Err = BoxDynError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid ACLITEM {s:?}: expected `grantee=privileges/grantor`");
+
+        let (grantee, rest) = parse_role(s, '=').ok_or_else(invalid)?;
+        let rest = rest.strip_prefix('=').ok_or_else(invalid)?;
+        let (privileges, grantor) = rest.split_once('/').ok_or_else(invalid)?;
+        let (grantor, rest) = parse_role(grantor, '/').ok_or_else(invalid)?;
+
+        if !rest.is_empty() || grantor.is_empty() {
+            return Err(invalid().into());
+        }
+
+        let mut parsed = Vec::with_capacity(privileges.len());
+        let mut chars = privileges.chars().peekable();
+
+        while let Some(code) = chars.next() {
+            if !code.is_ascii_alphabetic() {
+                return Err(invalid().into());
+            }
+
+            parsed.push(PgAclPrivilege {
+                kind: PgAclPrivilegeKind::from_code(code),
+                grant_option: chars.next_if_eq(&'*').is_some(),
+            });
+        }
+
+        Ok(PgAclItem {
+            grantee: (!grantee.is_empty()).then_some(grantee),
+            privileges: parsed,
+            grantor,
+        })
+    }
+}
+
+// Reads a role name up to `end` or the end of the input, returning the name and the rest of
+// the input, which starts with `end` if it isn't empty.
+fn parse_role(s: &str, end: char) -> Option<(String, &str)> {
+    let Some(quoted) = s.strip_prefix('"') else {
+        let len = s.find(end).unwrap_or(s.len());
+        return Some((s[..len].to_owned(), &s[len..]));
+    };
+
+    let mut name = String::new();
+    let mut rest = quoted;
+
+    loop {
+        let close = rest.find('"')?;
+        name.push_str(&rest[..close]);
+        rest = &rest[close + 1..];
+
+        // `""` is an escaped quote
+        match rest.strip_prefix('"') {
+            Some(after) => {
+                name.push('"');
+                rest = after;
+            }
+            None => break,
+        }
+    }
+
+    Some((name, rest))
+}
+
+fn write_role(f: &mut Formatter<'_>, name: &str) -> fmt::Result {
+    // Postgres only quotes names with characters other than letters, digits and `_`
+    if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return f.write_str(name);
+    }
+
+    f.write_char('"')?;
+    f.write_str(&name.replace('"', "\"\""))?;
+    f.write_char('"')
+}
+
+impl Type<Postgres> for PgAclItem {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::ACLITEM
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::ACLITEM || <&str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for PgAclItem {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::ACLITEM_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::ACLITEM_ARRAY || <&str as PgHasArrayType>::array_compatible(ty)
+    }
+}
+
+impl Decode<'_, Postgres> for PgAclItem {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        // `ACLITEM` only has a text format, and a text value is the same in both formats
+        value.as_str()?.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aclitem_parse_and_display() {
+        let item: PgAclItem = "alice=arw*/postgres".parse().unwrap();
+
+        assert_eq!(item.grantee.as_deref(), Some("alice"));
+        assert_eq!(item.grantor, "postgres");
+        assert_eq!(
+            item.privileges,
+            [
+                PgAclPrivilege {
+                    kind: PgAclPrivilegeKind::Insert,
+                    grant_option: false
+                },
+                PgAclPrivilege {
+                    kind: PgAclPrivilegeKind::Select,
+                    grant_option: false
+                },
+                PgAclPrivilege {
+                    kind: PgAclPrivilegeKind::Update,
+                    grant_option: true
+                },
+            ]
+        );
+        assert!(item.has(PgAclPrivilegeKind::Update));
+        assert!(!item.has(PgAclPrivilegeKind::Delete));
+        assert_eq!(item.to_string(), "alice=arw*/postgres");
+
+        let public: PgAclItem = "=r/postgres".parse().unwrap();
+        assert_eq!(public.grantee, None);
+        assert_eq!(public.to_string(), "=r/postgres");
+
+        let quoted: PgAclItem = r#""Bob ""B"" Smith"=Q/"Admin""#.parse().unwrap();
+        assert_eq!(quoted.grantee.as_deref(), Some(r#"Bob "B" Smith"#));
+        assert_eq!(quoted.grantor, "Admin");
+        assert_eq!(quoted.privileges[0].kind, PgAclPrivilegeKind::Other('Q'));
+        assert_eq!(quoted.to_string(), r#""Bob ""B"" Smith"=Q/Admin"#);
+
+        for invalid in [
+            "",
+            "alice",
+            "alice=r",
+            "alice=r/",
+            "alice=r1/bob",
+            "\"alice=r/bob",
+        ] {
+            assert!(invalid.parse::<PgAclItem>().is_err(), "{invalid:?}");
+        }
+    }
+}
@@ -30,12 +30,12 @@ use crate::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 /// ```
 ///
 /// However, this may cause an error if the type being wrapped does not implement `PgHasArrayType`,
-/// e.g. `Vec` itself, because we don't currently support multidimensional arrays:
+/// e.g. a `Vec` of a type that doesn't support multidimensional arrays (see below):
 ///
 /// ```rust,ignore
-/// #[derive(sqlx::Type)] // ERROR: `Vec<i64>` does not implement `PgHasArrayType`
+/// #[derive(sqlx::Type)] // ERROR: `Vec<PgInterval>` does not implement `PgHasArrayType`
 /// #[sqlx(transparent)]
-/// struct UserIds(Vec<i64>);
+/// struct Intervals(Vec<PgInterval>);
 /// ```
 ///
 /// To remedy this, add `#[sqlx(no_pg_array)]`, which disables the generation
@@ -44,9 +44,24 @@ use crate::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
 /// ```rust,ignore
 /// #[derive(sqlx::Type)]
 /// #[sqlx(transparent, no_pg_array)]
-/// struct UserIds(Vec<i64>);
+/// struct Intervals(Vec<PgInterval>);
 /// ```
 ///
+/// ### Multidimensional Arrays
+/// Multidimensional arrays (e.g. `FLOAT8[][]`) are supported as nested `Vec`s, e.g.
+/// `Vec<Vec<f64>>`. Postgres requires multidimensional arrays to be rectangular, so encoding
+/// sub-arrays of different lengths is an error.
+///
+/// Postgres does not distinguish array types by their number of dimensions, so `Vec<T>` is given
+/// the same array type as `T`. This is implemented for `bool`, the integer and float types and
+/// `String` (including `Option`s of those), as well as any `Vec<Vec<T>>` where `Vec<T>` is
+/// supported.
+///
+/// ### Note: new scalar types
+/// The array impls are generic over the element type, so a type only has to implement this
+/// trait (alongside `Type`, `Encode` and `Decode`) to get array support. Every scalar type
+/// in this crate does so; a type without an impl can't be used as an array element.
+///
 /// See [the documentation of `Type`][Type] for more details.
 pub trait PgHasArrayType {
     fn array_type_info() -> PgTypeInfo;
@@ -91,6 +106,48 @@ impl<T> PgHasArrayType for Text<T> {
     }
 }
 
+impl<T> PgHasArrayType for Vec<Vec<T>>
+where
+    Vec<T>: PgHasArrayType,
+{
+    fn array_type_info() -> PgTypeInfo {
+        <Vec<T>>::array_type_info()
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        <Vec<T>>::array_compatible(ty)
+    }
+}
+
+// Can't be a blanket impl as it would conflict with `Vec<u8>` (`BYTEA`).
+macro_rules! impl_multidimensional_array {
+    ($($ty:ty),*) => {
+        $(
+            impl PgHasArrayType for Vec<$ty> {
+                fn array_type_info() -> PgTypeInfo {
+                    <$ty>::array_type_info()
+                }
+
+                fn array_compatible(ty: &PgTypeInfo) -> bool {
+                    <$ty>::array_compatible(ty)
+                }
+            }
+
+            impl PgHasArrayType for Vec<Option<$ty>> {
+                fn array_type_info() -> PgTypeInfo {
+                    <$ty>::array_type_info()
+                }
+
+                fn array_compatible(ty: &PgTypeInfo) -> bool {
+                    <$ty>::array_compatible(ty)
+                }
+            }
+        )*
+    };
+}
+
+impl_multidimensional_array!(bool, i8, i16, i32, i64, f32, f64, String);
+
 impl<T> Type<Postgres> for [T]
 where
     T: PgHasArrayType,
@@ -161,11 +218,17 @@ where
             .and_then(Encode::produces)
             .unwrap_or_else(T::type_info);
 
+        // elements which are arrays themselves are flattened into a multidimensional array
+        let sub_array_element = type_info.try_array_element().map(Cow::into_owned);
+
+        let is_nested = sub_array_element.is_some();
+        let ndim_offset = buf.len();
+
         buf.extend(&1_i32.to_be_bytes()); // number of dimensions
         buf.extend(&0_i32.to_be_bytes()); // flags
 
         // element type
-        match type_info.0 {
+        match sub_array_element.map_or(type_info.0, |element| element.0) {
             PgType::DeclareWithName(name) => buf.patch_type_by_name(&name),
             PgType::DeclareArrayOf(array) => buf.patch_array_type(array),
 
@@ -184,14 +247,79 @@ where
         buf.extend(array_len.to_be_bytes()); // len
         buf.extend(&1_i32.to_be_bytes()); // lower bound
 
+        if !is_nested {
+            for element in self.iter() {
+                buf.encode(element)?;
+            }
+
+            return Ok(IsNull::No);
+        }
+
+        let dims_offset = buf.len();
+
+        // the `(len, lower bound)` pairs of the first sub-array, which all others must match
+        let mut sub_dims: Option<Vec<u8>> = None;
+
         for element in self.iter() {
+            let start = buf.len();
+
             buf.encode(element)?;
+
+            let (header_len, dims) = sub_array_header(&buf[start..])?;
+
+            match &sub_dims {
+                Some(expected) if *expected != dims => {
+                    return Err(
+                        "multidimensional arrays must have sub-arrays with matching dimensions"
+                            .into(),
+                    );
+                }
+                Some(_) => (),
+                None => sub_dims = Some(dims.to_vec()),
+            }
+
+            // keep only the elements of the sub-array
+            buf.splice(start..(start + header_len), &[]);
         }
 
+        let sub_dims = sub_dims.unwrap_or_default();
+        let ndim = i32::try_from(1 + sub_dims.len() / 8)
+            .map_err(|_| "encoded array has too many dimensions")?;
+
+        buf[ndim_offset..(ndim_offset + 4)].copy_from_slice(&ndim.to_be_bytes());
+        buf.splice(dims_offset..dims_offset, &sub_dims);
+
         Ok(IsNull::No)
     }
 }
 
+/// Parse the length prefix and header of an encoded sub-array,
+/// returning the total length of both and the `(len, lower bound)` pairs of its dimensions.
+fn sub_array_header(buf: &[u8]) -> Result<(usize, &[u8]), BoxDynError> {
+    let read_i32 = |offset: usize| {
+        buf.get(offset..(offset + 4))
+            .map(|b| i32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or("unexpected end of encoded sub-array")
+    };
+
+    if read_i32(0)? == -1 {
+        return Err("multidimensional arrays cannot contain NULL sub-arrays".into());
+    }
+
+    let ndim = usize::try_from(read_i32(4)?)
+        .map_err(|_| "encoded sub-array has a negative number of dimensions")?;
+
+    // length prefix + ndim + flags + element OID
+    let dims_start = 16;
+    let header_len = dims_start + ndim * 8;
+
+    let dims = buf
+        .get(dims_start..header_len)
+        .ok_or("unexpected end of encoded sub-array")?;
+
+    Ok((header_len, dims))
+}
+
 impl<'r, T, const N: usize> Decode<'r, Postgres> for [T; N]
 where
     T: for<'a> Decode<'a, Postgres> + Type<Postgres>,
@@ -226,10 +354,24 @@ where
                     return Ok(Vec::new());
                 }
 
-                if ndim != 1 {
-                    return Err(format!("encountered an array of {ndim} dimensions; only one-dimensional arrays are supported").into());
+                // whether `T` is itself an array, i.e. we're decoding into a nested `Vec`
+                let is_nested = T::type_info().try_array_element().is_some();
+
+                if ndim != 1 && !is_nested {
+                    return Err(format!("encountered an array of {ndim} dimensions; decode multidimensional arrays into a nested `Vec`, e.g. `Vec<Vec<T>>`").into());
+                }
+
+                if ndim == 1 && is_nested {
+                    return Err(
+                        "expected a multidimensional array but encountered an array of 1 dimension"
+                            .into(),
+                    );
                 }
 
+                let sub_ndim = ndim - 1;
+                let ndim = usize::try_from(ndim)
+                    .map_err(|_| format!("encountered an array with {ndim} dimensions"))?;
+
                 // appears to have been used in the past to communicate potential NULLS
                 // but reading source code back through our supported postgres versions (9.5+)
                 // this is never used for anything
@@ -246,35 +388,158 @@ where
                         ))
                     })?;
 
-                // length of the array axis
-                let len = buf.get_i32();
+                // the `(len, lower bound)` pairs of each dimension
+                let raw_dims = buf
+                    .get(..(ndim * 8))
+                    .ok_or("unexpected end of array data")?;
+
+                let mut dims = Vec::with_capacity(ndim);
+
+                for dim in 1..=ndim {
+                    // length of the array axis
+                    let len = buf.get_i32();
+
+                    let len = usize::try_from(len)
+                        .map_err(|_| format!("overflow converting array len ({len}) to usize"))?;
+
+                    // the lower bound, we only support arrays starting from "1",
+                    // except for `int2vector` and `oidvector` which always start from "0"
+                    let lower = buf.get_i32();
+                    let expected_lower = match value.type_info.0 {
+                        PgType::Int2Vector | PgType::OidVector => 0,
+                        _ => 1,
+                    };
+
+                    if lower != expected_lower {
+                        return Err(format!("encountered an array with a lower bound of {lower} in dimension {dim}; only arrays starting at one are supported").into());
+                    }
+
+                    dims.push(len);
+                }
+
+                let mut elements = Vec::with_capacity(dims[0]);
+
+                if ndim == 1 {
+                    // Fast path for elements of a fixed size: if none of them are `NULL`,
+                    // each one is the same length prefix followed by that many bytes, so
+                    // they can be sliced straight out of the buffer after checking the
+                    // prefixes in a single pass.
+                    if let Some(size) = element_type_info.0.binary_size() {
+                        let stride = size + 4;
+                        let prefix = i32::try_from(size)?.to_be_bytes();
+
+                        let data = dims[0]
+                            .checked_mul(stride)
+                            .and_then(|len| buf.get(..len))
+                            .filter(|data| {
+                                data.chunks_exact(stride).all(|chunk| chunk[..4] == prefix)
+                            });
+
+                        if let Some(data) = data {
+                            for chunk in data.chunks_exact(stride) {
+                                elements.push(T::decode(PgValueRef {
+                                    value: Some(&chunk[4..]),
+                                    row: None,
+                                    type_info: element_type_info.clone(),
+                                    format,
+                                    strict_timezone: value.strict_timezone,
+                                })?);
+                            }
+
+                            return Ok(elements);
+                        }
+                    }
 
-                let len = usize::try_from(len)
-                    .map_err(|_| format!("overflow converting array len ({len}) to usize"))?;
+                    for _ in 0..dims[0] {
+                        let value_ref =
+                            PgValueRef::get(&mut buf, format, element_type_info.clone())?;
 
-                // the lower bound, we only support arrays starting from "1"
-                let lower = buf.get_i32();
+                        elements.push(T::decode(PgValueRef {
+                            strict_timezone: value.strict_timezone,
+                            ..value_ref
+                        })?);
+                    }
 
-                if lower != 1 {
-                    return Err(format!("encountered an array with a lower bound of {lower} in the first dimension; only arrays starting at one are supported").into());
+                    return Ok(elements);
                 }
 
-                let mut elements = Vec::with_capacity(len);
+                // The elements of a multidimensional array are stored flat, in row-major order.
+                // Each sub-array is decoded by `T` from a header for the remaining dimensions
+                // followed by its share of the elements.
+                let sub_len: usize = dims[1..].iter().product();
+
+                for _ in 0..dims[0] {
+                    let mut sub_array = Vec::new();
+
+                    sub_array.extend(&sub_ndim.to_be_bytes());
+                    sub_array.extend(&0_i32.to_be_bytes());
+                    sub_array.extend(&element_type_oid.0.to_be_bytes());
+                    sub_array.extend(&raw_dims[8..]);
+
+                    for _ in 0..sub_len {
+                        let len = buf.get_i32();
+
+                        sub_array.extend(&len.to_be_bytes());
+
+                        if let Ok(len) = usize::try_from(len) {
+                            if buf.len() < len {
+                                return Err("unexpected end of array data".into());
+                            }
 
-                for _ in 0..len {
-                    let value_ref = PgValueRef::get(&mut buf, format, element_type_info.clone())?;
+                            sub_array.extend(&buf[..len]);
+                            buf.advance(len);
+                        }
+                    }
 
-                    elements.push(T::decode(value_ref)?);
+                    elements.push(T::decode(PgValueRef {
+                        value: Some(&sub_array),
+                        row: None,
+                        type_info: value.type_info.clone(),
+                        format,
+                        strict_timezone: value.strict_timezone,
+                    })?);
                 }
 
                 Ok(elements)
             }
 
-            PgValueFormat::Text => {
-                // no type is provided from the database for the element
+            // `int2vector` and `oidvector` are space-separated in the text format
+            PgValueFormat::Text
+                if matches!(value.type_info.0, PgType::Int2Vector | PgType::OidVector) =>
+            {
                 let element_type_info = T::type_info();
 
+                value
+                    .as_str()?
+                    .split_ascii_whitespace()
+                    .map(|element| {
+                        T::decode(PgValueRef {
+                            value: Some(element.as_bytes()),
+                            row: None,
+                            type_info: element_type_info.clone(),
+                            format,
+                            strict_timezone: value.strict_timezone,
+                        })
+                    })
+                    .collect()
+            }
+
+            PgValueFormat::Text => {
+                let is_nested = T::type_info().try_array_element().is_some();
+
+                // no type is provided from the database for the element, but the type of a
+                // user-defined array (e.g. one primed by `PgConnection::register_enum()`)
+                // knows its element type, and `CHAR(N)` elements are decoded without padding
+                let element_type_info = match &value.type_info.0 {
+                    PgType::Custom(_) | PgType::BpcharArray if !is_nested => {
+                        value.type_info.try_array_element().map(Cow::into_owned)
+                    }
+                    _ => None,
+                }
+                .unwrap_or_else(T::type_info);
+
                 let s = value.as_str()?;
+                let strict_timezone = value.strict_timezone;
 
                 // https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L718
 
@@ -296,6 +561,9 @@ where
                 let mut done = false;
                 let mut in_quotes = false;
                 let mut in_escape = false;
+                // sub-arrays of a multidimensional array are passed to `T` verbatim
+                let mut depth = 0_usize;
+                let mut is_sub_array = false;
                 let mut value = String::with_capacity(10);
                 let mut chars = s.chars();
                 let mut elements = Vec::with_capacity(4);
@@ -305,11 +573,19 @@ where
                         match chars.next() {
                             Some(ch) => match ch {
                                 _ if in_escape => {
+                                    if depth > 0 {
+                                        value.push('\\');
+                                    }
+
                                     value.push(ch);
                                     in_escape = false;
                                 }
 
                                 '"' => {
+                                    if depth > 0 {
+                                        value.push(ch);
+                                    }
+
                                     in_quotes = !in_quotes;
                                 }
 
@@ -317,7 +593,18 @@ where
                                     in_escape = true;
                                 }
 
-                                _ if ch == delimiter && !in_quotes => {
+                                '{' if !in_quotes => {
+                                    is_sub_array |= depth == 0 && value.is_empty();
+                                    depth += 1;
+                                    value.push(ch);
+                                }
+
+                                '}' if !in_quotes => {
+                                    depth = depth.saturating_sub(1);
+                                    value.push(ch);
+                                }
+
+                                _ if ch == delimiter && !in_quotes && depth == 0 => {
                                     break;
                                 }
 
@@ -333,6 +620,15 @@ where
                         }
                     }
 
+                    if is_sub_array != is_nested {
+                        return Err(if is_nested {
+                            "expected a multidimensional array but encountered an array of 1 dimension"
+                        } else {
+                            "encountered a multidimensional array; decode multidimensional arrays into a nested `Vec`, e.g. `Vec<Vec<T>>`"
+                        }
+                        .into());
+                    }
+
                     let value_opt = if value == "NULL" {
                         None
                     } else {
@@ -344,9 +640,11 @@ where
                         row: None,
                         type_info: element_type_info.clone(),
                         format,
+                        strict_timezone,
                     })?);
 
                     value.clear();
+                    is_sub_array = false;
                 }
 
                 Ok(elements)
@@ -0,0 +1,85 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::Deref;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::{array_compatible, Type};
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+
+/// A blank-padded `CHAR(N)` (`BPCHAR`) value, including its trailing spaces.
+///
+/// `String`, `&str`, etc. decode `CHAR(N)` without the padding, e.g. `'ab'::char(4)` as `"ab"`,
+/// which matches how Postgres compares these values. This type keeps the value exactly as
+/// stored, `"ab  "`, for when the padding matters.
+///
+/// Binds as `BPCHAR`; Postgres pads or truncates the value when it is stored into a `CHAR(N)`
+/// column.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PgBpChar(pub String);
+
+impl PgBpChar {
+    /// The value without its trailing spaces, as `String` would decode it.
+    pub fn trimmed(&self) -> &str {
+        self.0.trim_end_matches(' ')
+    }
+}
+
+impl Deref for PgBpChar {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_str()
+    }
+}
+
+impl From<String> for PgBpChar {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<PgBpChar> for String {
+    fn from(value: PgBpChar) -> Self {
+        value.0
+    }
+}
+
+impl Display for PgBpChar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Type<Postgres> for PgBpChar {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::BPCHAR
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        <&str as Type<Postgres>>::compatible(ty)
+    }
+}
+
+impl PgHasArrayType for PgBpChar {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::BPCHAR_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        array_compatible::<&str>(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for PgBpChar {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        <&str as Encode<Postgres>>::encode(&**self, buf)
+    }
+}
+
+impl Decode<'_, Postgres> for PgBpChar {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        // unlike `String`, keep the padding
+        Ok(PgBpChar(value.as_str()?.to_owned()))
+    }
+}
@@ -110,3 +110,76 @@ impl<const N: usize> Decode<'_, Postgres> for [u8; N] {
         Ok(bytes)
     }
 }
+
+#[cfg(feature = "bytes")]
+mod bytes_crate {
+    use super::text_hex_decode_input;
+    use crate::decode::Decode;
+    use crate::encode::{Encode, IsNull};
+    use crate::error::BoxDynError;
+    use crate::types::Type;
+    use crate::{
+        PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+    };
+    use sqlx_core::bytes::{Bytes, BytesMut};
+
+    impl Type<Postgres> for Bytes {
+        fn type_info() -> PgTypeInfo {
+            PgTypeInfo::BYTEA
+        }
+    }
+
+    impl Type<Postgres> for BytesMut {
+        fn type_info() -> PgTypeInfo {
+            PgTypeInfo::BYTEA
+        }
+    }
+
+    impl PgHasArrayType for Bytes {
+        fn array_type_info() -> PgTypeInfo {
+            PgTypeInfo::BYTEA_ARRAY
+        }
+    }
+
+    impl PgHasArrayType for BytesMut {
+        fn array_type_info() -> PgTypeInfo {
+            PgTypeInfo::BYTEA_ARRAY
+        }
+    }
+
+    impl Encode<'_, Postgres> for Bytes {
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            <&[u8] as Encode<Postgres>>::encode(self, buf)
+        }
+    }
+
+    impl Encode<'_, Postgres> for BytesMut {
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            <&[u8] as Encode<Postgres>>::encode(self, buf)
+        }
+    }
+
+    impl Decode<'_, Postgres> for Bytes {
+        fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+            Ok(match value.format() {
+                // share the buffer of the row, if the value came from one
+                PgValueFormat::Binary => match value.row {
+                    Some(row) => row.slice_ref(value.as_bytes()?),
+                    None => Bytes::copy_from_slice(value.as_bytes()?),
+                },
+                PgValueFormat::Text => hex::decode(text_hex_decode_input(value)?)?.into(),
+            })
+        }
+    }
+
+    impl Decode<'_, Postgres> for BytesMut {
+        fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+            Ok(match value.format() {
+                PgValueFormat::Binary => BytesMut::from(value.as_bytes()?),
+                PgValueFormat::Text => {
+                    BytesMut::from(&*hex::decode(text_hex_decode_input(value)?)?)
+                }
+            })
+        }
+    }
+}
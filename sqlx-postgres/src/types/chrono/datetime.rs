@@ -49,6 +49,8 @@ impl Encode<'_, Postgres> for NaiveDateTime {
 
 impl<'r> Decode<'r, Postgres> for NaiveDateTime {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        value.check_naive_timestamp()?;
+
         Ok(match value.format() {
             PgValueFormat::Binary => {
                 // TIMESTAMP is encoded as the microseconds since the epoch
@@ -102,7 +104,10 @@ impl<'r> Decode<'r, Postgres> for DateTime<FixedOffset> {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
         Ok(match value.format() {
             PgValueFormat::Binary => {
-                let naive = <NaiveDateTime as Decode<Postgres>>::decode(value)?;
+                let naive = <NaiveDateTime as Decode<Postgres>>::decode(PgValueRef {
+                    strict_timezone: false,
+                    ..value
+                })?;
                 Utc.fix().from_utc_datetime(&naive)
             }
 
@@ -1,3 +1,23 @@
+//! Aliases of [`PgRange`] for the range types with elements from the `chrono` crate.
+//!
+//! Requires the `chrono` Cargo feature flag.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+use crate::types::PgRange;
+
 mod date;
 mod datetime;
 mod time;
+
+/// A `DATERANGE`.
+pub type PgDateRange = PgRange<NaiveDate>;
+
+/// A `TSRANGE`.
+pub type PgTsRange = PgRange<NaiveDateTime>;
+
+/// A `TSTZRANGE`.
+///
+/// Values are decoded in UTC. `PgRange<DateTime<Tz>>` decodes `TSTZRANGE` in any other
+/// timezone as well.
+pub type PgTsTzRange = PgRange<DateTime<Utc>>;
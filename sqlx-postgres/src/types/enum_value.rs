@@ -0,0 +1,86 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::type_info::{PgCustomType, PgType, PgTypeKind};
+use crate::types::Type;
+use crate::{PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+
+/// A value of any Postgres enum type, as its label.
+///
+/// This decodes enums that have no Rust counterpart, such as types created at runtime,
+/// and arrays of them as `Vec<PgEnumValue>`. If the enum was registered with
+/// [`PgConnection::register_enum()`][crate::PgConnection::register_enum], decoding fails for
+/// labels that were not registered, and the enum and its arrays can also be decoded from
+/// the text format (e.g. the results of a query without arguments).
+///
+/// For enums known at compile time, prefer `#[derive(sqlx::Type)]`.
+/// Only decoding is supported.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PgEnumValue(pub String);
+
+impl PgEnumValue {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for PgEnumValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+// The variants of `ty`, if it is a resolved enum type.
+fn enum_variants(ty: &PgTypeInfo) -> Option<&[String]> {
+    match &ty.0 {
+        PgType::Custom(custom) => match &**custom {
+            PgCustomType {
+                kind: PgTypeKind::Enum(variants),
+                ..
+            } => Some(variants),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl Type<Postgres> for PgEnumValue {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("anyenum")
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        enum_variants(ty).is_some()
+    }
+}
+
+impl PgHasArrayType for PgEnumValue {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::array_of("anyenum")
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        ty.try_array_element()
+            .map_or(false, |element| Self::compatible(&element))
+    }
+}
+
+impl Decode<'_, Postgres> for PgEnumValue {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        // Both formats are the label itself.
+        let label = value.as_str()?;
+
+        if let Some(variants) = enum_variants(&value.type_info) {
+            if !variants.iter().any(|variant| variant == label) {
+                return Err(format!(
+                    "unknown variant {label:?} of enum {}",
+                    value.type_info.0.display_name()
+                )
+                .into());
+            }
+        }
+
+        Ok(PgEnumValue(label.to_owned()))
+    }
+}
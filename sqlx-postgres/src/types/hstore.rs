@@ -274,6 +274,7 @@ mod test {
             row: None,
             type_info: PgTypeInfo::with_name("hstore"),
             format: PgValueFormat::Binary,
+            strict_timezone: false,
         };
 
         let name_surname = PgValueRef {
@@ -281,6 +282,7 @@ mod test {
             row: None,
             type_info: PgTypeInfo::with_name("hstore"),
             format: PgValueFormat::Binary,
+            strict_timezone: false,
         };
 
         let res_empty = PgHstore::decode(empty).unwrap();
@@ -300,6 +302,7 @@ mod test {
             row: None,
             type_info: PgTypeInfo::with_name("hstore"),
             format: PgValueFormat::Binary,
+            strict_timezone: false,
         };
 
         PgHstore::decode(buf).unwrap();
@@ -89,6 +89,10 @@ impl PgHasArrayType for i16 {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::INT2_ARRAY
     }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::INT2_ARRAY || *ty == PgTypeInfo::INT2_VECTOR
+    }
 }
 
 impl Encode<'_, Postgres> for i16 {
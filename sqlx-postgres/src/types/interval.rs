@@ -17,6 +17,209 @@ pub struct PgInterval {
     pub microseconds: i64,
 }
 
+const MICROS_PER_SEC: i64 = 1_000_000;
+const MICROS_PER_MINUTE: i64 = 60 * MICROS_PER_SEC;
+const MICROS_PER_HOUR: i64 = 60 * MICROS_PER_MINUTE;
+
+impl PgInterval {
+    /// Format this interval as an ISO 8601 duration, e.g. `P1Y2M3DT4H5M6.5S`.
+    ///
+    /// This is the same format Postgres outputs with `SET intervalstyle = 'iso_8601'`:
+    ///
+    /// * `months` is split into years and months (`P1Y2M`), but is never converted to days
+    ///   because the length of a month varies.
+    /// * `days` are output as days (`P3D`), and are never converted to hours because the length
+    ///   of a day varies across daylight saving time changes.
+    /// * `microseconds` are output as hours, minutes and fractional seconds (`PT4H5M6.5S`).
+    ///   Hours are not carried over into days.
+    ///
+    /// Each component carries its own sign, so `-1 day +02:00:00` is output as `P-1DT2H`.
+    /// The zero interval is output as `PT0S`.
+    pub fn to_iso8601(&self) -> String {
+        use std::fmt::Write;
+
+        if *self == PgInterval::default() {
+            return "PT0S".into();
+        }
+
+        let mut out = String::from("P");
+
+        let years = self.months / 12;
+        let months = self.months % 12;
+
+        // writing to a `String` cannot fail
+        for (value, designator) in [(years, 'Y'), (months, 'M'), (self.days, 'D')] {
+            if value != 0 {
+                let _ = write!(out, "{value}{designator}");
+            }
+        }
+
+        if self.microseconds != 0 {
+            out.push('T');
+
+            let hours = self.microseconds / MICROS_PER_HOUR;
+            let minutes = self.microseconds % MICROS_PER_HOUR / MICROS_PER_MINUTE;
+            let seconds = self.microseconds % MICROS_PER_MINUTE;
+
+            for (value, designator) in [(hours, 'H'), (minutes, 'M')] {
+                if value != 0 {
+                    let _ = write!(out, "{value}{designator}");
+                }
+            }
+
+            if seconds != 0 {
+                if seconds < 0 {
+                    out.push('-');
+                }
+
+                let seconds = seconds.unsigned_abs();
+                let _ = write!(out, "{}", seconds / MICROS_PER_SEC.unsigned_abs());
+
+                let fraction = seconds % MICROS_PER_SEC.unsigned_abs();
+                if fraction != 0 {
+                    let fraction = format!("{fraction:06}");
+                    let _ = write!(out, ".{}", fraction.trim_end_matches('0'));
+                }
+
+                out.push('S');
+            }
+        }
+
+        out
+    }
+
+    /// Parse an ISO 8601 duration in the format `PnYnMnWnDTnHnMnS`, e.g. `P1Y2M3DT4H5M6.5S`.
+    ///
+    /// Components that are zero may be omitted, but at least one must be present and they must
+    /// appear in the order above. Any component may be negative or have a decimal fraction
+    /// (with either `.` or `,`), and a leading `-` negates the whole duration.
+    ///
+    /// Components map onto the fields of `PgInterval` the same way Postgres maps them:
+    ///
+    /// * `Y` is 12 months and `M` (before `T`) is one month: `P1M` is `months = 1`,
+    ///   not 30 days.
+    /// * `W` is 7 days and `D` is one day.
+    /// * `H`, `M` (after `T`) and `S` are converted to microseconds.
+    /// * Fractional months are converted to days assuming 30 days per month, and fractional
+    ///   days to microseconds assuming 24 hours per day (`P1.5M` is 1 month and 15 days).
+    ///
+    /// Precision beyond microseconds is truncated.
+    pub fn from_iso8601(s: &str) -> Result<Self, BoxDynError> {
+        let invalid = || format!("invalid ISO 8601 duration: {s:?}");
+
+        let (negate, duration) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+
+        let duration = duration.strip_prefix('P').ok_or_else(invalid)?;
+
+        let (date, time) = match duration.split_once('T') {
+            Some((date, time)) if !time.is_empty() => (date, Some(time)),
+            Some(_) => return Err(invalid().into()),
+            None => (duration, None),
+        };
+
+        if date.is_empty() && time.is_none() {
+            return Err(invalid().into());
+        }
+
+        // accumulate as fixed-point with 6 decimal places to carry fractions over exactly
+        let mut months: i128 = 0;
+        let mut days: i128 = 0;
+        let mut micros: i128 = 0;
+
+        for (value, designator) in
+            iso8601_components(date, &['Y', 'M', 'W', 'D']).ok_or_else(invalid)?
+        {
+            match designator {
+                'Y' => months += value * 12,
+                'M' => months += value,
+                'W' => days += value * 7,
+                _ => days += value,
+            }
+        }
+
+        if let Some(time) = time {
+            for (value, designator) in
+                iso8601_components(time, &['H', 'M', 'S']).ok_or_else(invalid)?
+            {
+                micros += match designator {
+                    'H' => value * i128::from(MICROS_PER_HOUR / MICROS_PER_SEC),
+                    'M' => value * i128::from(MICROS_PER_MINUTE / MICROS_PER_SEC),
+                    _ => value,
+                };
+            }
+        }
+
+        let scale = i128::from(MICROS_PER_SEC);
+
+        // cascade fractional months into days and fractional days into microseconds
+        days += months % scale * 30;
+        micros += days % scale * 86_400;
+
+        let sign = if negate { -1 } else { 1 };
+        let out_of_range = |_| format!("ISO 8601 duration out of range for INTERVAL: {s:?}");
+
+        Ok(PgInterval {
+            months: i32::try_from(sign * (months / scale)).map_err(out_of_range)?,
+            days: i32::try_from(sign * (days / scale)).map_err(out_of_range)?,
+            microseconds: i64::try_from(sign * micros).map_err(out_of_range)?,
+        })
+    }
+}
+
+/// Split `s` into `(value, designator)` pairs, where the designators must appear in the order
+/// given by `designators` and each at most once.
+///
+/// Values are fixed-point numbers with 6 decimal places.
+fn iso8601_components(mut s: &str, designators: &[char]) -> Option<Vec<(i128, char)>> {
+    let mut components = Vec::new();
+    let mut allowed = designators;
+
+    while !s.is_empty() {
+        let end = s.find(|c: char| c.is_ascii_alphabetic())?;
+        let designator = s[end..].chars().next()?;
+
+        let position = allowed.iter().position(|&d| d == designator)?;
+        allowed = &allowed[position + 1..];
+
+        components.push((iso8601_number(&s[..end])?, designator));
+        s = &s[end + 1..];
+    }
+
+    Some(components)
+}
+
+/// Parse a decimal number like `-12.5` into a fixed-point number with 6 decimal places.
+fn iso8601_number(s: &str) -> Option<i128> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+
+    let (whole, fraction) = match s.split_once(['.', ',']) {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (s, ""),
+    };
+
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+
+    if whole.is_empty() || !is_digits(whole) || !is_digits(fraction) || whole.len() > 20 {
+        return None;
+    }
+
+    let mut value = whole.parse::<i128>().ok()? * 1_000_000;
+
+    // truncate precision beyond microseconds
+    let fraction = fraction.get(..6).unwrap_or(fraction);
+    if !fraction.is_empty() {
+        value += format!("{fraction:0<6}").parse::<i128>().ok()?;
+    }
+
+    Some(if negative { -value } else { value })
+}
+
 impl Type<Postgres> for PgInterval {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INTERVAL
@@ -397,3 +600,71 @@ fn test_pginterval_time() {
     assert!(PgInterval::try_from(time::Duration::seconds(10_000_000_000_000)).is_err());
     assert!(PgInterval::try_from(time::Duration::seconds(-10_000_000_000_000)).is_err());
 }
+
+#[test]
+fn test_pginterval_to_iso8601() {
+    let interval = |months, days, microseconds| PgInterval {
+        months,
+        days,
+        microseconds,
+    };
+
+    assert_eq!(interval(0, 0, 0).to_iso8601(), "PT0S");
+    assert_eq!(interval(1, 0, 0).to_iso8601(), "P1M");
+    assert_eq!(interval(14, 3, 0).to_iso8601(), "P1Y2M3D");
+    assert_eq!(
+        interval(14, 3, 14_706_500_000).to_iso8601(),
+        "P1Y2M3DT4H5M6.5S"
+    );
+    assert_eq!(interval(0, -1, 7_200_000_000).to_iso8601(), "P-1DT2H");
+    assert_eq!(interval(-13, 0, -500_000).to_iso8601(), "P-1Y-1MT-0.5S");
+    assert_eq!(interval(0, 0, 1).to_iso8601(), "PT0.000001S");
+}
+
+#[test]
+fn test_pginterval_from_iso8601() {
+    let interval = |months, days, microseconds| PgInterval {
+        months,
+        days,
+        microseconds,
+    };
+
+    let parse = |s: &str| PgInterval::from_iso8601(s).unwrap();
+
+    assert_eq!(parse("PT0S"), interval(0, 0, 0));
+    assert_eq!(parse("P1M"), interval(1, 0, 0));
+    assert_eq!(parse("PT1M"), interval(0, 0, 60_000_000));
+    assert_eq!(parse("P1Y2M3DT4H5M6.5S"), interval(14, 3, 14_706_500_000));
+    assert_eq!(parse("P2W"), interval(0, 14, 0));
+    assert_eq!(parse("P-1DT2H"), interval(0, -1, 7_200_000_000));
+    assert_eq!(parse("-P1DT2H"), interval(0, -1, -7_200_000_000));
+    assert_eq!(parse("PT0,25S"), interval(0, 0, 250_000));
+    assert_eq!(parse("PT0.0000019S"), interval(0, 0, 1));
+
+    // fractions cascade down into the smaller fields
+    assert_eq!(parse("P1.5M"), interval(1, 15, 0));
+    assert_eq!(parse("P0.5D"), interval(0, 0, 43_200_000_000));
+    assert_eq!(parse("P-1.5M"), interval(-1, -15, 0));
+
+    for invalid in [
+        "", "P", "PT", "1D", "P1", "P1D2", "P1DT", "P1D1Y", "P1M1M", "P1H", "PT1D", "P.5D",
+        "P1.D2", "P1xD",
+    ] {
+        assert!(
+            PgInterval::from_iso8601(invalid).is_err(),
+            "{invalid:?} should not parse"
+        );
+    }
+
+    // out of range for `months`
+    assert!(PgInterval::from_iso8601("P178956971Y").is_err());
+
+    for months in [-25, -1, 0, 1, 12, 25] {
+        for days in [-3, 0, 3] {
+            for microseconds in [-3_723_000_001, -500_000, 0, 1, 3_723_000_001] {
+                let value = interval(months, days, microseconds);
+                assert_eq!(parse(&value.to_iso8601()), value);
+            }
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::ops::Range;
+
+use serde::Deserialize;
+
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// A JSON array read from a `JSON` or `JSONB` column, whose elements are only parsed
+/// when they are accessed.
+///
+/// Decoding only finds where each element starts and ends, which is much cheaper than parsing
+/// them. This pays off for large arrays of which only a few elements are used. If every element
+/// is going to be used anyway, [`Json<Vec<T>>`](crate::types::Json) is faster, as it parses the
+/// array in a single pass, and leaves nothing to fail later.
+///
+/// The JSON text is kept in full. An element that is accessed more than once is parsed again
+/// each time.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::postgres::types::PgJsonbArray;
+///
+/// let events: PgJsonbArray = sqlx::query_scalar("SELECT events FROM logs WHERE id = 1")
+///     .fetch_one(conn)
+///     .await?;
+///
+/// if let Some(last) = events.len().checked_sub(1) {
+///     let event: serde_json::Value = events.get(last).unwrap()?;
+///     println!("{} events, last was {event}", events.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgJsonbArray {
+    json: String,
+    // the byte range of each element in `json`
+    elements: Vec<Range<usize>>,
+}
+
+impl PgJsonbArray {
+    /// The number of elements in the array.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns `true` if the array has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Parse the element at `index`, or return `None` if it is out of bounds.
+    pub fn get<'a, T>(&'a self, index: usize) -> Option<Result<T, serde_json::Error>>
+    where
+        T: Deserialize<'a>,
+    {
+        self.get_raw(index).map(serde_json::from_str)
+    }
+
+    /// The JSON text of the element at `index`, or `None` if it is out of bounds.
+    pub fn get_raw(&self, index: usize) -> Option<&str> {
+        self.elements
+            .get(index)
+            .map(|range| &self.json[range.clone()])
+    }
+
+    /// Iterate over the JSON text of each element.
+    pub fn iter_raw(&self) -> impl ExactSizeIterator<Item = &str> + '_ {
+        self.elements.iter().map(|range| &self.json[range.clone()])
+    }
+
+    /// The JSON text of the whole array.
+    pub fn as_str(&self) -> &str {
+        &self.json
+    }
+
+    fn parse(json: String) -> Result<Self, BoxDynError> {
+        let elements = split_elements(&json)?;
+
+        Ok(PgJsonbArray { json, elements })
+    }
+}
+
+// Find the byte range of each element of the JSON array in `json`.
+//
+// The elements themselves are not validated; that happens when they are parsed.
+fn split_elements(json: &str) -> Result<Vec<Range<usize>>, BoxDynError> {
+    let bytes = json.as_bytes();
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .filter(|&i| bytes[i] == b'[')
+        .ok_or("expected a JSON array")?;
+
+    let mut elements = Vec::new();
+    let mut depth = 0_usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut element_start = start + 1;
+    let mut end = None;
+
+    for (i, &b) in bytes.iter().enumerate().skip(start + 1) {
+        if in_string {
+            match b {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+
+            continue;
+        }
+
+        match b {
+            b'"' => in_string = true,
+            b'[' | b'{' => depth += 1,
+            b']' if depth == 0 => {
+                push_element(json, element_start..i, &mut elements)?;
+                end = Some(i);
+                break;
+            }
+            b']' | b'}' => depth = depth.checked_sub(1).ok_or("unbalanced JSON array")?,
+            b',' if depth == 0 => {
+                push_element(json, element_start..i, &mut elements)?;
+                element_start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    let end = end.ok_or("unterminated JSON array")?;
+
+    if !json[end + 1..].trim().is_empty() {
+        return Err("unexpected data after JSON array".into());
+    }
+
+    Ok(elements)
+}
+
+fn push_element(
+    json: &str,
+    range: Range<usize>,
+    elements: &mut Vec<Range<usize>>,
+) -> Result<(), BoxDynError> {
+    let element = &json[range.clone()];
+    let trimmed = element.trim();
+
+    if trimmed.is_empty() {
+        // `[]` has no elements, but `[,1]`, `[1,,2]` or `[1,]` is invalid
+        return if elements.is_empty() && json[range.end..].trim_start().starts_with(']') {
+            Ok(())
+        } else {
+            Err("empty element in JSON array".into())
+        };
+    }
+
+    let leading = element.len() - element.trim_start().len();
+    let start = range.start + leading;
+    elements.push(start..start + trimmed.len());
+
+    Ok(())
+}
+
+impl Type<Postgres> for PgJsonbArray {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::JSONB
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::JSON || *ty == PgTypeInfo::JSONB
+    }
+}
+
+impl Decode<'_, Postgres> for PgJsonbArray {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        let mut buf = value.as_bytes()?;
+
+        if value.format() == PgValueFormat::Binary && value.type_info == PgTypeInfo::JSONB {
+            match buf.split_first() {
+                Some((1, rest)) => buf = rest,
+                _ => return Err("unsupported JSONB format version".into()),
+            }
+        }
+
+        PgJsonbArray::parse(std::str::from_utf8(buf)?.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value as JsonValue};
+
+    #[test]
+    fn test_split_json_array() {
+        let array =
+            PgJsonbArray::parse(r#" [1, "a, \"b\" ]", {"c": [2, 3]}, [], null ] "#.to_owned())
+                .unwrap();
+
+        assert_eq!(array.len(), 5);
+        assert_eq!(
+            array.iter_raw().collect::<Vec<_>>(),
+            ["1", r#""a, \"b\" ]""#, r#"{"c": [2, 3]}"#, "[]", "null"]
+        );
+        assert_eq!(array.get::<i64>(0).unwrap().unwrap(), 1);
+        assert!(array.get::<&str>(1).unwrap().is_err());
+        assert_eq!(array.get::<String>(1).unwrap().unwrap(), "a, \"b\" ]");
+        assert_eq!(
+            array.get::<JsonValue>(2).unwrap().unwrap(),
+            json!({ "c": [2, 3] })
+        );
+        assert!(array.get::<JsonValue>(5).is_none());
+
+        assert!(PgJsonbArray::parse("[]".to_owned()).unwrap().is_empty());
+        assert!(PgJsonbArray::parse(" [ ] ".to_owned()).unwrap().is_empty());
+
+        for invalid in [
+            "", "{}", "1", "[1", "[1,]", "[,1]", "[1,,2]", "[1]]", "[1] x",
+        ] {
+            assert!(
+                PgJsonbArray::parse(invalid.to_owned()).is_err(),
+                "{invalid:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,371 @@
+use serde_json::{Map, Value};
+
+/// Helpers for tracking changes to `JSONB` documents with
+/// [RFC 6902](https://datatracker.ietf.org/doc/html/rfc6902) JSON patches.
+///
+/// [`diff()`][Self::diff] computes the patch that turns one version of a document into
+/// another, e.g. to store it in an audit table, and [`apply_patch()`][Self::apply_patch]
+/// replays it. Both work on `serde_json::Value`, so they can be used with values decoded as
+/// `serde_json::Value` or [`Json<Value>`](crate::types::Json), which dereferences to it.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::postgres::types::PgJsonb;
+/// use sqlx::types::Json;
+///
+/// let (old, new): (Json<serde_json::Value>, Json<serde_json::Value>) = sqlx::query_as(
+///     "SELECT old.doc, new.doc FROM documents old, documents new \
+///      WHERE old.id = 1 AND new.id = 2",
+/// )
+/// .fetch_one(&mut *conn)
+/// .await?;
+///
+/// let patch = PgJsonb::diff(&old, &new);
+///
+/// sqlx::query("INSERT INTO document_changes (document_id, patch) VALUES ($1, $2)")
+///     .bind(1)
+///     .bind(&patch)
+///     .execute(&mut *conn)
+///     .await?;
+///
+/// let mut replayed = old.0;
+/// PgJsonb::apply_patch(&mut replayed, &patch).unwrap();
+/// assert_eq!(replayed, new.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub enum PgJsonb {}
+
+/// An error applying a JSON patch with [`PgJsonb::apply_patch()`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum PgJsonPatchError {
+    /// The patch is not an array of valid operations.
+    #[error("invalid JSON patch: {0}")]
+    InvalidPatch(String),
+
+    /// A path of an operation does not point into the document.
+    #[error("JSON patch path {0:?} does not exist in the document")]
+    PathNotFound(String),
+
+    /// A `test` operation found a different value.
+    #[error("JSON patch test failed at path {0:?}")]
+    TestFailed(String),
+}
+
+impl PgJsonb {
+    /// Compute an RFC 6902 patch, as a JSON array of operations, that turns `old` into `new`.
+    ///
+    /// Only `add`, `remove` and `replace` operations are produced. Objects are compared key by
+    /// key and arrays index by index, with elements added or removed at the end.
+    pub fn diff(old: &Value, new: &Value) -> Value {
+        let mut ops = Vec::new();
+        diff_into(&mut ops, &mut String::new(), old, new);
+        Value::Array(ops)
+    }
+
+    /// Apply an RFC 6902 `patch` to `doc`.
+    ///
+    /// All six operations (`add`, `remove`, `replace`, `move`, `copy` and `test`) are supported.
+    /// The patch is applied as a whole: if any operation fails, `doc` is left unchanged.
+    pub fn apply_patch(doc: &mut Value, patch: &Value) -> Result<(), PgJsonPatchError> {
+        let ops = patch
+            .as_array()
+            .ok_or_else(|| PgJsonPatchError::InvalidPatch("expected an array".into()))?;
+
+        let mut patched = doc.clone();
+
+        for op in ops {
+            apply_op(&mut patched, op)?;
+        }
+
+        *doc = patched;
+        Ok(())
+    }
+}
+
+fn diff_into(ops: &mut Vec<Value>, path: &mut String, old: &Value, new: &Value) {
+    if old == new {
+        return;
+    }
+
+    let len = path.len();
+
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                push_token(path, key);
+
+                match new.get(key) {
+                    Some(new_value) => diff_into(ops, path, old_value, new_value),
+                    None => ops.push(op("remove", path, None)),
+                }
+
+                path.truncate(len);
+            }
+
+            for (key, new_value) in new.iter().filter(|(key, _)| !old.contains_key(*key)) {
+                push_token(path, key);
+                ops.push(op("add", path, Some(new_value)));
+                path.truncate(len);
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for (i, (old_value, new_value)) in old.iter().zip(new).enumerate() {
+                push_token(path, &i.to_string());
+                diff_into(ops, path, old_value, new_value);
+                path.truncate(len);
+            }
+
+            for (i, new_value) in new.iter().enumerate().skip(old.len()) {
+                push_token(path, &i.to_string());
+                ops.push(op("add", path, Some(new_value)));
+                path.truncate(len);
+            }
+
+            // from the end, so the indexes of the remaining elements don't shift
+            for i in (new.len()..old.len()).rev() {
+                push_token(path, &i.to_string());
+                ops.push(op("remove", path, None));
+                path.truncate(len);
+            }
+        }
+        _ => ops.push(op("replace", path, Some(new))),
+    }
+}
+
+fn op(name: &str, path: &str, value: Option<&Value>) -> Value {
+    let mut op = Map::new();
+    op.insert("op".into(), name.into());
+    op.insert("path".into(), path.into());
+
+    if let Some(value) = value {
+        op.insert("value".into(), value.clone());
+    }
+
+    Value::Object(op)
+}
+
+/// Append `token` to a JSON pointer, escaping `~` and `/`.
+fn push_token(path: &mut String, token: &str) {
+    path.push('/');
+    path.push_str(&token.replace('~', "~0").replace('/', "~1"));
+}
+
+fn apply_op(doc: &mut Value, op: &Value) -> Result<(), PgJsonPatchError> {
+    let field = |name: &str| {
+        op.get(name)
+            .ok_or_else(|| PgJsonPatchError::InvalidPatch(format!("missing {name:?} in {op}")))
+    };
+
+    let str_field = |name: &str| {
+        field(name)?
+            .as_str()
+            .ok_or_else(|| PgJsonPatchError::InvalidPatch(format!("{name:?} is not a string")))
+    };
+
+    let path = str_field("path")?;
+
+    match str_field("op")? {
+        "add" => add(doc, path, field("value")?.clone()),
+        "remove" => remove(doc, path).map(drop),
+        "replace" => {
+            *pointer_mut(doc, path)? = field("value")?.clone();
+            Ok(())
+        }
+        "move" => {
+            let from = str_field("from")?;
+
+            if path.starts_with(from) && path[from.len()..].starts_with('/') {
+                return Err(PgJsonPatchError::InvalidPatch(format!(
+                    "cannot move {from:?} into its own child {path:?}"
+                )));
+            }
+
+            let value = remove(doc, from)?;
+            add(doc, path, value)
+        }
+        "copy" => {
+            let from = str_field("from")?;
+            let value = pointer_mut(doc, from)?.clone();
+            add(doc, path, value)
+        }
+        "test" => {
+            if *pointer_mut(doc, path)? == *field("value")? {
+                Ok(())
+            } else {
+                Err(PgJsonPatchError::TestFailed(path.to_owned()))
+            }
+        }
+        other => Err(PgJsonPatchError::InvalidPatch(format!("unknown operation {other:?}"))),
+    }
+}
+
+/// Split a JSON pointer into the pointer to the parent and the unescaped last token.
+fn split_last(path: &str) -> Result<(&str, String), PgJsonPatchError> {
+    let (parent, token) = path
+        .rsplit_once('/')
+        .ok_or_else(|| PgJsonPatchError::InvalidPatch(format!("invalid path {path:?}")))?;
+
+    Ok((parent, token.replace("~1", "/").replace("~0", "~")))
+}
+
+fn pointer_mut<'a>(doc: &'a mut Value, path: &str) -> Result<&'a mut Value, PgJsonPatchError> {
+    if !path.is_empty() && !path.starts_with('/') {
+        return Err(PgJsonPatchError::InvalidPatch(format!("invalid path {path:?}")));
+    }
+
+    doc.pointer_mut(path)
+        .ok_or_else(|| PgJsonPatchError::PathNotFound(path.to_owned()))
+}
+
+/// The index of `token` in an array of `len` elements, where `len` itself is allowed if
+/// `appending`.
+fn array_index(
+    token: &str,
+    len: usize,
+    appending: bool,
+    path: &str,
+) -> Result<usize, PgJsonPatchError> {
+    if appending && token == "-" {
+        return Ok(len);
+    }
+
+    let index = token
+        .parse::<usize>()
+        .ok()
+        .filter(|_| token == "0" || !token.starts_with('0'))
+        .ok_or_else(|| {
+            PgJsonPatchError::InvalidPatch(format!("invalid array index in {path:?}"))
+        })?;
+
+    if index < len || (appending && index == len) {
+        Ok(index)
+    } else {
+        Err(PgJsonPatchError::PathNotFound(path.to_owned()))
+    }
+}
+
+fn add(doc: &mut Value, path: &str, value: Value) -> Result<(), PgJsonPatchError> {
+    if path.is_empty() {
+        *doc = value;
+        return Ok(());
+    }
+
+    let (parent, token) = split_last(path)?;
+
+    match pointer_mut(doc, parent)? {
+        Value::Object(object) => {
+            object.insert(token, value);
+        }
+        Value::Array(array) => {
+            let index = array_index(&token, array.len(), true, path)?;
+            array.insert(index, value);
+        }
+        _ => return Err(PgJsonPatchError::PathNotFound(path.to_owned())),
+    }
+
+    Ok(())
+}
+
+fn remove(doc: &mut Value, path: &str) -> Result<Value, PgJsonPatchError> {
+    if path.is_empty() {
+        return Ok(doc.take());
+    }
+
+    let (parent, token) = split_last(path)?;
+
+    match pointer_mut(doc, parent)? {
+        Value::Object(object) => object
+            .remove(&token)
+            .ok_or_else(|| PgJsonPatchError::PathNotFound(path.to_owned())),
+        Value::Array(array) => {
+            let index = array_index(&token, array.len(), false, path)?;
+            Ok(array.remove(index))
+        }
+        _ => Err(PgJsonPatchError::PathNotFound(path.to_owned())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PgJsonPatchError, PgJsonb};
+    use serde_json::json;
+
+    fn assert_round_trip(old: serde_json::Value, new: serde_json::Value) -> serde_json::Value {
+        let patch = PgJsonb::diff(&old, &new);
+
+        let mut patched = old;
+        PgJsonb::apply_patch(&mut patched, &patch).unwrap();
+        assert_eq!(patched, new);
+
+        patch
+    }
+
+    #[test]
+    fn it_round_trips_add_remove_and_replace() {
+        let patch = assert_round_trip(
+            json!({ "meta": { "a/b~": true, "v": 1 }, "name": "a", "tags": ["x", "y", "z"] }),
+            json!({ "meta": { "v": 1, "w": null }, "name": "b", "new": [1], "tags": ["x"] }),
+        );
+
+        assert_eq!(
+            patch,
+            json!([
+                { "op": "remove", "path": "/meta/a~1b~0" },
+                { "op": "add", "path": "/meta/w", "value": null },
+                { "op": "replace", "path": "/name", "value": "b" },
+                { "op": "remove", "path": "/tags/2" },
+                { "op": "remove", "path": "/tags/1" },
+                { "op": "add", "path": "/new", "value": [1] },
+            ])
+        );
+
+        assert_round_trip(json!([1, 2]), json!([1, { "a": 2 }, 3, 4]));
+        assert_round_trip(json!({ "a": 1 }), json!("replaced"));
+        assert_eq!(PgJsonb::diff(&json!({ "a": [1] }), &json!({ "a": [1] })), json!([]));
+    }
+
+    #[test]
+    fn it_applies_move_copy_and_test() {
+        let mut doc = json!({ "a": { "b": 1 }, "list": [1, 2] });
+
+        PgJsonb::apply_patch(
+            &mut doc,
+            &json!([
+                { "op": "test", "path": "/a/b", "value": 1 },
+                { "op": "move", "from": "/a/b", "path": "/c" },
+                { "op": "copy", "from": "/c", "path": "/list/-" },
+                { "op": "add", "path": "/list/0", "value": 0 },
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(doc, json!({ "a": {}, "c": 1, "list": [0, 1, 2, 1] }));
+    }
+
+    #[test]
+    fn it_leaves_the_document_unchanged_on_error() {
+        let mut doc = json!({ "a": 1 });
+
+        let err = PgJsonb::apply_patch(
+            &mut doc,
+            &json!([
+                { "op": "replace", "path": "/a", "value": 2 },
+                { "op": "remove", "path": "/missing" },
+            ]),
+        )
+        .unwrap_err();
+
+        assert!(matches!(err, PgJsonPatchError::PathNotFound(path) if path == "/missing"));
+        assert_eq!(doc, json!({ "a": 1 }));
+
+        let test = json!([{ "op": "test", "path": "/a", "value": 2 }]);
+        let err = PgJsonb::apply_patch(&mut doc, &test).unwrap_err();
+        assert!(matches!(err, PgJsonPatchError::TestFailed(_)));
+
+        let err = PgJsonb::apply_patch(&mut doc, &json!({ "op": "add" })).unwrap_err();
+        assert!(matches!(err, PgJsonPatchError::InvalidPatch(_)));
+    }
+}
@@ -0,0 +1,170 @@
+use serde_json::{Number, Value as JsonValue};
+
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// A single JSON value read from a `JSON` or `JSONB` column, such as the result of `data->'field'`.
+///
+/// Strings, numbers, booleans and `null` are read directly, without building a
+/// [`serde_json::Value`] first. Objects and arrays are parsed in full into [`PgJsonScalar::Complex`].
+///
+/// A SQL `NULL` is not a JSON `null`; decode into `Option<PgJsonScalar>` to accept one.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::PgConnection) -> sqlx::Result<()> {
+/// use sqlx::postgres::types::PgJsonScalar;
+///
+/// let name: PgJsonScalar = sqlx::query_scalar("SELECT data->'name' FROM users")
+///     .fetch_one(conn)
+///     .await?;
+///
+/// assert_eq!(name.as_str(), Some("Alice"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum PgJsonScalar {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    /// An object or an array.
+    Complex(JsonValue),
+}
+
+impl PgJsonScalar {
+    /// Returns `true` for a JSON `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, PgJsonScalar::Null)
+    }
+
+    /// The value of a JSON boolean.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            PgJsonScalar::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// The value of a JSON number, if it is an integer that fits in an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            PgJsonScalar::Number(n) => n.as_i64(),
+            _ => None,
+        }
+    }
+
+    /// The value of a JSON number, as an `f64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            PgJsonScalar::Number(n) => n.as_f64(),
+            _ => None,
+        }
+    }
+
+    /// The value of a JSON string.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PgJsonScalar::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn parse(s: &str) -> Result<Self, BoxDynError> {
+        let s = s.trim_matches(|c: char| c.is_ascii_whitespace());
+
+        Ok(match s.as_bytes().first() {
+            Some(b'n') if s == "null" => PgJsonScalar::Null,
+            Some(b't') if s == "true" => PgJsonScalar::Bool(true),
+            Some(b'f') if s == "false" => PgJsonScalar::Bool(false),
+
+            Some(b'"') => match s[1..].strip_suffix('"') {
+                // the server only returns valid JSON, so without escapes the contents are the string
+                Some(inner) if !inner.contains(['\\', '"']) => {
+                    PgJsonScalar::String(inner.to_owned())
+                }
+                _ => PgJsonScalar::String(serde_json::from_str(s)?),
+            },
+
+            Some(b'-' | b'0'..=b'9') => PgJsonScalar::Number(serde_json::from_str(s)?),
+
+            _ => PgJsonScalar::Complex(serde_json::from_str(s)?),
+        })
+    }
+}
+
+impl From<PgJsonScalar> for JsonValue {
+    fn from(scalar: PgJsonScalar) -> Self {
+        match scalar {
+            PgJsonScalar::Null => JsonValue::Null,
+            PgJsonScalar::Bool(b) => JsonValue::Bool(b),
+            PgJsonScalar::Number(n) => JsonValue::Number(n),
+            PgJsonScalar::String(s) => JsonValue::String(s),
+            PgJsonScalar::Complex(value) => value,
+        }
+    }
+}
+
+impl Type<Postgres> for PgJsonScalar {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::JSONB
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::JSON || *ty == PgTypeInfo::JSONB
+    }
+}
+
+impl<'r> Decode<'r, Postgres> for PgJsonScalar {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let mut buf = value.as_bytes()?;
+
+        if value.format() == PgValueFormat::Binary && value.type_info == PgTypeInfo::JSONB {
+            match buf.split_first() {
+                Some((1, rest)) => buf = rest,
+                _ => return Err("unsupported JSONB format version".into()),
+            }
+        }
+
+        PgJsonScalar::parse(std::str::from_utf8(buf)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_json_scalar() {
+        assert_eq!(PgJsonScalar::parse("null").unwrap(), PgJsonScalar::Null);
+        assert_eq!(
+            PgJsonScalar::parse(" true\n").unwrap(),
+            PgJsonScalar::Bool(true)
+        );
+        assert_eq!(PgJsonScalar::parse("false").unwrap().as_bool(), Some(false));
+        assert_eq!(PgJsonScalar::parse("-42").unwrap().as_i64(), Some(-42));
+        assert_eq!(PgJsonScalar::parse("1.5e3").unwrap().as_f64(), Some(1500.0));
+        assert_eq!(
+            PgJsonScalar::parse(r#""plain""#).unwrap().as_str(),
+            Some("plain")
+        );
+        assert_eq!(
+            PgJsonScalar::parse(r#""esc\"aped\né""#).unwrap().as_str(),
+            Some("esc\"aped\n\u{e9}")
+        );
+        assert_eq!(
+            PgJsonScalar::parse(r#"{"a": [1, 2]}"#).unwrap(),
+            PgJsonScalar::Complex(json!({ "a": [1, 2] }))
+        );
+        assert_eq!(
+            JsonValue::from(PgJsonScalar::parse("[]").unwrap()),
+            json!([])
+        );
+
+        assert!(PgJsonScalar::parse("nul").is_err());
+        assert!(PgJsonScalar::parse(r#""unterminated"#).is_err());
+    }
+}
@@ -0,0 +1,566 @@
+use std::fmt::Write;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::from_utf8;
+
+use crate::error::BoxDynError;
+use crate::type_info::{PgType, PgTypeKind};
+use crate::types::range::RangeFlags;
+use crate::PgTypeInfo;
+
+#[cfg(any(feature = "bigdecimal", feature = "rust_decimal", feature = "json"))]
+use crate::types::numeric::PgNumeric;
+
+const USECS_PER_DAY: i64 = 86_400_000_000;
+
+/// Write a parameter value of type `ty`, encoded in the binary format, as a SQL expression of
+/// that type.
+///
+/// This is used instead of binding parameters when prepared statements are disabled,
+/// see [`PgConnectOptions::prepared_statements()`][crate::PgConnectOptions::prepared_statements].
+///
+/// Values are written as string constants cast to their type, e.g. `'42'::"int4"`. Quotes and
+/// backslashes are escaped so the constant means the same regardless of
+/// `standard_conforming_strings`, and values containing a NUL byte are rejected.
+pub(crate) fn write_literal(
+    buf: &mut String,
+    ty: &PgTypeInfo,
+    value: Option<&[u8]>,
+) -> Result<(), BoxDynError> {
+    if matches!(
+        ty.0,
+        PgType::DeclareWithOid(_) | PgType::DeclareWithName(_) | PgType::DeclareArrayOf(_)
+    ) {
+        return Err(format!("unresolved parameter type {}", ty.0.display_name()).into());
+    }
+
+    let Some(value) = value else {
+        buf.push_str("NULL");
+        write_cast(buf, ty);
+        return Ok(());
+    };
+
+    match ty.kind() {
+        PgTypeKind::Array(element) => write_array(buf, ty, element, value)?,
+        PgTypeKind::Range(element) => write_range(buf, ty, element, value)?,
+        PgTypeKind::Composite(fields) => {
+            let mut value = value;
+            let count = read_i32(&mut value)?;
+
+            if usize::try_from(count).ok() != Some(fields.len()) {
+                return Err(format!(
+                    "expected {} fields for a value of type {}, got {count}",
+                    fields.len(),
+                    ty.0.display_name()
+                )
+                .into());
+            }
+
+            buf.push_str("ROW(");
+
+            for (i, (_, field)) in fields.iter().enumerate() {
+                if i > 0 {
+                    buf.push_str(", ");
+                }
+
+                // the OID of the field
+                take(&mut value, 4)?;
+
+                let field_value = read_value(&mut value)?;
+                write_literal(buf, field, field_value)?;
+            }
+
+            buf.push(')');
+            write_cast(buf, ty);
+        }
+        PgTypeKind::Domain(base) => {
+            buf.push('(');
+            write_literal(buf, base, Some(value))?;
+            buf.push(')');
+            write_cast(buf, ty);
+        }
+        PgTypeKind::Enum(_) => {
+            write_string(buf, from_utf8(value)?)?;
+            write_cast(buf, ty);
+        }
+        PgTypeKind::Simple | PgTypeKind::Pseudo => {
+            write_string(buf, &to_text(ty, value)?)?;
+            write_cast(buf, ty);
+        }
+    }
+
+    Ok(())
+}
+
+/// Convert a value of a scalar type from the binary format to the text format.
+fn to_text(ty: &PgTypeInfo, value: &[u8]) -> Result<String, BoxDynError> {
+    let mut text = String::new();
+
+    match &ty.0 {
+        PgType::Bool => text.push(if fixed::<1>(value)?[0] != 0 { 't' } else { 'f' }),
+        PgType::Char => match fixed::<1>(value)?[0] {
+            0 => (),
+            b if b.is_ascii_graphic() || b == b' ' => text.push(char::from(b)),
+            b => write!(text, "\\{b:03o}")?,
+        },
+        PgType::Int2 => write!(text, "{}", i16::from_be_bytes(fixed(value)?))?,
+        PgType::Int4 => write!(text, "{}", i32::from_be_bytes(fixed(value)?))?,
+        PgType::Int8 => write!(text, "{}", i64::from_be_bytes(fixed(value)?))?,
+        PgType::Oid => write!(text, "{}", u32::from_be_bytes(fixed(value)?))?,
+        PgType::Float4 => write_float(&mut text, f64::from(f32::from_be_bytes(fixed(value)?)))?,
+        PgType::Float8 => write_float(&mut text, f64::from_be_bytes(fixed(value)?))?,
+
+        #[cfg(any(feature = "bigdecimal", feature = "rust_decimal", feature = "json"))]
+        PgType::Numeric => write!(text, "{}", PgNumeric::decode(value)?)?,
+
+        PgType::Text
+        | PgType::Varchar
+        | PgType::Bpchar
+        | PgType::Name
+        | PgType::Unknown
+        | PgType::Json => text.push_str(from_utf8(value)?),
+
+        PgType::Jsonb | PgType::Jsonpath => text.push_str(from_utf8(versioned(value)?)?),
+
+        PgType::Bytea => {
+            text.push_str("\\x");
+
+            for b in value {
+                write!(text, "{b:02x}")?;
+            }
+        }
+
+        PgType::Uuid => {
+            for (i, b) in fixed::<16>(value)?.iter().enumerate() {
+                if matches!(i, 4 | 6 | 8 | 10) {
+                    text.push('-');
+                }
+
+                write!(text, "{b:02x}")?;
+            }
+        }
+
+        PgType::Date => match i32::from_be_bytes(fixed(value)?) {
+            i32::MAX => text.push_str("infinity"),
+            i32::MIN => text.push_str("-infinity"),
+            days => {
+                if write_date(&mut text, days.into())? {
+                    text.push_str(" BC");
+                }
+            }
+        },
+
+        PgType::Time => write_time(&mut text, i64::from_be_bytes(fixed(value)?))?,
+
+        PgType::Timetz => {
+            let value = fixed::<12>(value)?;
+            let (time, zone) = value.split_at(8);
+
+            write_time(&mut text, i64::from_be_bytes(fixed(time)?))?;
+
+            // the zone is stored in seconds *west* of UTC
+            let zone = i32::from_be_bytes(fixed(zone)?);
+            let sign = if zone > 0 { '-' } else { '+' };
+            let offset = zone.unsigned_abs();
+
+            write!(
+                text,
+                "{sign}{:02}:{:02}:{:02}",
+                offset / 3600,
+                offset / 60 % 60,
+                offset % 60
+            )?;
+        }
+
+        PgType::Timestamp | PgType::Timestamptz => match i64::from_be_bytes(fixed(value)?) {
+            i64::MAX => text.push_str("infinity"),
+            i64::MIN => text.push_str("-infinity"),
+            usecs => {
+                let bc = write_date(&mut text, usecs.div_euclid(USECS_PER_DAY))?;
+
+                text.push(' ');
+                write_time(&mut text, usecs.rem_euclid(USECS_PER_DAY))?;
+
+                if matches!(ty.0, PgType::Timestamptz) {
+                    text.push_str("+00");
+                }
+
+                if bc {
+                    text.push_str(" BC");
+                }
+            }
+        },
+
+        PgType::Interval => {
+            let value = fixed::<16>(value)?;
+            let usecs = i64::from_be_bytes(fixed(&value[..8])?);
+            let days = i32::from_be_bytes(fixed(&value[8..12])?);
+            let months = i32::from_be_bytes(fixed(&value[12..])?);
+
+            // every field is signed, so `IntervalStyle` does not change the meaning
+            write!(text, "{months:+} mons {days:+} days ")?;
+            text.push(if usecs < 0 { '-' } else { '+' });
+
+            let usecs = usecs.unsigned_abs();
+            write!(
+                text,
+                "{}:{:02}:{:02}.{:06}",
+                usecs / 3_600_000_000,
+                usecs / 60_000_000 % 60,
+                usecs / 1_000_000 % 60,
+                usecs % 1_000_000
+            )?;
+        }
+
+        PgType::Inet | PgType::Cidr => {
+            let (header, addr) = value.split_at(std::cmp::min(value.len(), 4));
+            let [family, bits, _is_cidr, _len] = fixed(header)?;
+
+            match family {
+                2 => write!(text, "{}/{bits}", Ipv4Addr::from(fixed::<4>(addr)?))?,
+                3 => write!(text, "{}/{bits}", Ipv6Addr::from(fixed::<16>(addr)?))?,
+                _ => return Err(format!("unknown address family {family}").into()),
+            }
+        }
+
+        PgType::Macaddr | PgType::Macaddr8 => {
+            for (i, b) in value.iter().enumerate() {
+                if i > 0 {
+                    text.push(':');
+                }
+
+                write!(text, "{b:02x}")?;
+            }
+        }
+
+        PgType::Bit | PgType::Varbit => {
+            let mut value = value;
+            let len = usize::try_from(read_i32(&mut value)?)?;
+
+            for i in 0..len {
+                let byte = value.get(i / 8).ok_or("unexpected end of bit string")?;
+                text.push(if byte & (0x80 >> (i % 8)) != 0 {
+                    '1'
+                } else {
+                    '0'
+                });
+            }
+        }
+
+        PgType::PgLsn => {
+            let lsn = u64::from_be_bytes(fixed(value)?);
+            write!(text, "{:X}/{:X}", lsn >> 32, lsn & 0xFFFF_FFFF)?;
+        }
+
+        PgType::Tid => {
+            let tid: [u8; 6] = fixed(value)?;
+            let block = u32::from_be_bytes([tid[0], tid[1], tid[2], tid[3]]);
+            write!(text, "({},{})", block, u16::from_be_bytes([tid[4], tid[5]]))?;
+        }
+
+        // the name may be qualified with the schema of the extension
+        PgType::Custom(custom) => match custom.name.rsplit('.').next().unwrap_or_default() {
+            "citext" => text.push_str(from_utf8(value)?),
+            "ltree" | "lquery" | "ltxtquery" => text.push_str(from_utf8(versioned(value)?)?),
+            "hstore" => write_hstore(&mut text, value)?,
+            _ => return Err(unsupported(ty)),
+        },
+
+        _ => return Err(unsupported(ty)),
+    }
+
+    Ok(text)
+}
+
+fn unsupported(ty: &PgTypeInfo) -> BoxDynError {
+    format!(
+        "parameters of type {} cannot be sent without prepared statements",
+        ty.0.display_name()
+    )
+    .into()
+}
+
+fn write_array(
+    buf: &mut String,
+    ty: &PgTypeInfo,
+    element: &PgTypeInfo,
+    mut value: &[u8],
+) -> Result<(), BoxDynError> {
+    // https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L1548
+    let ndim = read_i32(&mut value)?;
+
+    if ndim == 0 {
+        buf.push_str("'{}'");
+        write_cast(buf, ty);
+        return Ok(());
+    }
+
+    // the flags and the OID of the element type
+    take(&mut value, 8)?;
+
+    let dims = (0..ndim)
+        .map(|_| {
+            let len = read_i32(&mut value)?;
+            let _lower = read_i32(&mut value)?;
+
+            Ok(usize::try_from(len)?)
+        })
+        .collect::<Result<Vec<_>, BoxDynError>>()?;
+
+    write_elements(buf, &mut value, &dims, element)?;
+    write_cast(buf, ty);
+
+    Ok(())
+}
+
+/// Write the elements of an array with the dimensions `dims`, nesting one `ARRAY[...]` per
+/// dimension.
+fn write_elements(
+    buf: &mut String,
+    value: &mut &[u8],
+    dims: &[usize],
+    element: &PgTypeInfo,
+) -> Result<(), BoxDynError> {
+    let Some((&len, dims)) = dims.split_first() else {
+        let element_value = read_value(value)?;
+        return write_literal(buf, element, element_value);
+    };
+
+    buf.push_str("ARRAY[");
+
+    for i in 0..len {
+        if i > 0 {
+            buf.push_str(", ");
+        }
+
+        write_elements(buf, value, dims, element)?;
+    }
+
+    buf.push(']');
+
+    Ok(())
+}
+
+fn write_range(
+    buf: &mut String,
+    ty: &PgTypeInfo,
+    element: &PgTypeInfo,
+    mut value: &[u8],
+) -> Result<(), BoxDynError> {
+    let flags = RangeFlags::from_bits_truncate(fixed::<1>(take(&mut value, 1)?)?[0]);
+
+    if flags.contains(RangeFlags::EMPTY) {
+        buf.push_str("'empty'");
+        write_cast(buf, ty);
+        return Ok(());
+    }
+
+    // the constructor function of a range type has the same name as the type
+    write_type_name(buf, ty);
+    buf.push('(');
+
+    let lower = if flags.contains(RangeFlags::LB_INF) {
+        None
+    } else {
+        read_value(&mut value)?
+    };
+    write_literal(buf, element, lower)?;
+
+    buf.push_str(", ");
+
+    let upper = if flags.contains(RangeFlags::UB_INF) {
+        None
+    } else {
+        read_value(&mut value)?
+    };
+    write_literal(buf, element, upper)?;
+
+    buf.push_str(", '");
+    buf.push(if flags.contains(RangeFlags::LB_INC) {
+        '['
+    } else {
+        '('
+    });
+    buf.push(if flags.contains(RangeFlags::UB_INC) {
+        ']'
+    } else {
+        ')'
+    });
+    buf.push_str("')");
+
+    Ok(())
+}
+
+fn write_hstore(text: &mut String, mut value: &[u8]) -> Result<(), BoxDynError> {
+    let quote = |text: &mut String, s: &str| {
+        text.push('"');
+
+        for c in s.chars() {
+            if matches!(c, '"' | '\\') {
+                text.push('\\');
+            }
+
+            text.push(c);
+        }
+
+        text.push('"');
+    };
+
+    let count = read_i32(&mut value)?;
+
+    for i in 0..count {
+        if i > 0 {
+            text.push_str(", ");
+        }
+
+        let key = read_value(&mut value)?.ok_or("hstore keys cannot be NULL")?;
+        quote(text, from_utf8(key)?);
+
+        text.push_str("=>");
+
+        match read_value(&mut value)? {
+            Some(value) => quote(text, from_utf8(value)?),
+            None => text.push_str("NULL"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the proleptic Gregorian calendar date `days` after 2000-01-01 as `YYYY-MM-DD`.
+///
+/// Returns `true` if the date is BC, in which case the year has been written as counted BC.
+// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn write_date(text: &mut String, days: i64) -> Result<bool, BoxDynError> {
+    // the days from 0000-03-01 to 2000-01-01
+    let z = days + 730_425;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    // there is no year 0, 1 BC comes right before 1 AD
+    let (year, bc) = if year <= 0 {
+        (1 - year, true)
+    } else {
+        (year, false)
+    };
+
+    write!(text, "{year:04}-{month:02}-{day:02}")?;
+
+    Ok(bc)
+}
+
+/// Write the time of day `usecs` after midnight as `HH:MM:SS.ffffff`.
+fn write_time(text: &mut String, usecs: i64) -> Result<(), BoxDynError> {
+    write!(
+        text,
+        "{:02}:{:02}:{:02}.{:06}",
+        usecs / 3_600_000_000,
+        usecs / 60_000_000 % 60,
+        usecs / 1_000_000 % 60,
+        usecs % 1_000_000
+    )?;
+
+    Ok(())
+}
+
+fn write_float(text: &mut String, value: f64) -> Result<(), BoxDynError> {
+    if value.is_nan() {
+        text.push_str("NaN");
+    } else if value.is_infinite() {
+        text.push_str(if value > 0.0 { "Infinity" } else { "-Infinity" });
+    } else {
+        write!(text, "{value}")?;
+    }
+
+    Ok(())
+}
+
+/// Write `s` as a string constant.
+fn write_string(buf: &mut String, s: &str) -> Result<(), BoxDynError> {
+    // the query string is sent NUL-terminated, and string values cannot contain NUL anyway
+    if s.contains('\0') {
+        return Err("parameter values cannot contain NUL bytes without prepared statements".into());
+    }
+
+    // backslashes are only special in escape string constants, and in regular string
+    // constants if `standard_conforming_strings` is off; use the former to be unambiguous
+    if s.contains('\\') {
+        buf.push('E');
+    }
+
+    buf.push('\'');
+
+    for c in s.chars() {
+        match c {
+            '\'' => buf.push_str("''"),
+            '\\' => buf.push_str("\\\\"),
+            c => buf.push(c),
+        }
+    }
+
+    buf.push('\'');
+
+    Ok(())
+}
+
+fn write_cast(buf: &mut String, ty: &PgTypeInfo) {
+    buf.push_str("::");
+    write_type_name(buf, ty);
+}
+
+fn write_type_name(buf: &mut String, ty: &PgTypeInfo) {
+    match &ty.0 {
+        // the name of a user-defined type is the output of `regtype`, so it is already quoted and
+        // qualified as needed to be found through the `search_path`
+        PgType::Custom(custom) => buf.push_str(&custom.name),
+        ty => {
+            buf.push('"');
+            buf.push_str(ty.name());
+            buf.push('"');
+        }
+    }
+}
+
+/// Strip the version byte that is sent before the text of `JSONB` and some extension types.
+fn versioned(value: &[u8]) -> Result<&[u8], BoxDynError> {
+    match value.split_first() {
+        Some((1, text)) => Ok(text),
+        Some((version, _)) => Err(format!("unsupported binary format version {version}").into()),
+        None => Err("unexpected empty value".into()),
+    }
+}
+
+fn fixed<const N: usize>(value: &[u8]) -> Result<[u8; N], BoxDynError> {
+    value
+        .try_into()
+        .map_err(|_| format!("expected a value of {N} bytes, got {}", value.len()).into())
+}
+
+fn take<'a>(value: &mut &'a [u8], len: usize) -> Result<&'a [u8], BoxDynError> {
+    if value.len() < len {
+        return Err("unexpected end of value".into());
+    }
+
+    let (taken, rest) = value.split_at(len);
+    *value = rest;
+
+    Ok(taken)
+}
+
+fn read_i32(value: &mut &[u8]) -> Result<i32, BoxDynError> {
+    Ok(i32::from_be_bytes(fixed(take(value, 4)?)?))
+}
+
+/// Read a length-prefixed value, as in arrays, ranges and composites.
+fn read_value<'a>(value: &mut &'a [u8]) -> Result<Option<&'a [u8]>, BoxDynError> {
+    // a negative length indicates NULL
+    match usize::try_from(read_i32(value)?) {
+        Ok(len) => take(value, len).map(Some),
+        Err(_) => Ok(None),
+    }
+}
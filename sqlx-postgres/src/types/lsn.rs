@@ -0,0 +1,115 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// The PostgreSQL [`PG_LSN`] type stores a position in the write-ahead log.
+///
+/// The Postgres text format is two hexadecimal numbers of up to 8 digits each, separated
+/// by a slash, e.g. `16/B374D848`; these are the upper and lower 32 bits of the position.
+/// [`Display`] and [`FromStr`] use the same format.
+///
+/// Subtracting two positions gives the number of bytes of WAL between them, which can be used
+/// to measure replication lag.
+///
+/// [`PG_LSN`]: https://www.postgresql.org/docs/current/datatype-pg-lsn.html
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct PgLsn(
+    /// The position as a 64-bit integer, as sent over the wire.
+    pub u64,
+);
+
+impl Display for PgLsn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 & 0xFFFF_FFFF)
+    }
+}
+
+impl FromStr for PgLsn {
+    type Err = BoxDynError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid PG_LSN {s:?}: expected `XXXXXXXX/XXXXXXXX`");
+
+        let (hi, lo) = s.split_once('/').ok_or_else(invalid)?;
+
+        let parse = |word: &str| {
+            // `from_str_radix` also accepts a leading `+`, which Postgres does not.
+            if word.is_empty() || word.len() > 8 || !word.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+
+            u32::from_str_radix(word, 16).map_err(|_| invalid())
+        };
+
+        Ok(PgLsn((u64::from(parse(hi)?) << 32) | u64::from(parse(lo)?)))
+    }
+}
+
+impl Type<Postgres> for PgLsn {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::PG_LSN
+    }
+}
+
+impl PgHasArrayType for PgLsn {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::PG_LSN_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgLsn {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend(&self.0.to_be_bytes());
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        8
+    }
+}
+
+impl Decode<'_, Postgres> for PgLsn {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => Ok(PgLsn(BigEndian::read_u64(value.as_bytes()?))),
+            PgValueFormat::Text => value.as_str()?.parse(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PgLsn;
+
+    #[test]
+    fn test_pglsn_display() {
+        assert_eq!(PgLsn(0).to_string(), "0/0");
+        assert_eq!(PgLsn(0x16_B374_D848).to_string(), "16/B374D848");
+        assert_eq!(PgLsn(u64::MAX).to_string(), "FFFFFFFF/FFFFFFFF");
+    }
+
+    #[test]
+    fn test_pglsn_from_str() {
+        assert_eq!("0/0".parse::<PgLsn>().unwrap(), PgLsn(0));
+        assert_eq!(
+            "16/b374d848".parse::<PgLsn>().unwrap(),
+            PgLsn(0x16_B374_D848)
+        );
+        assert_eq!(
+            "FFFFFFFF/FFFFFFFF".parse::<PgLsn>().unwrap(),
+            PgLsn(u64::MAX)
+        );
+
+        for invalid in ["", "16", "/0", "0/", "100000000/0", "+1/0", "0/-1", "G/0"] {
+            assert!(invalid.parse::<PgLsn>().is_err(), "{invalid:?}");
+        }
+    }
+}
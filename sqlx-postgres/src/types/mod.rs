@@ -11,15 +11,22 @@
 //! | `i64`                                 | BIGINT, BIGSERIAL, INT8                              |
 //! | `f32`                                 | REAL, FLOAT4                                         |
 //! | `f64`                                 | DOUBLE PRECISION, FLOAT8                             |
-//! | `&str`, [`String`]                    | VARCHAR, CHAR(N), TEXT, NAME, CITEXT                 |
+//! | `&str`, [`String`]                    | VARCHAR, CHAR(N)<sup>6</sup>, TEXT, NAME<sup>2</sup>, CITEXT |
 //! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
 //! | `()`                                  | VOID                                                 |
+//! | `u128`                                | UUID<sup>4</sup>                                     |
 //! | [`PgInterval`]                        | INTERVAL                                             |
 //! | [`PgRange<T>`](PgRange)               | INT8RANGE, INT4RANGE, TSRANGE, TSTZRANGE, DATERANGE, NUMRANGE |
 //! | [`PgMoney`]                           | MONEY                                                |
+//! | [`PgNumericInt<i128>`], [`PgNumericInt<u128>`] | NUMERIC<sup>5</sup>                         |
+//! | [`PgLsn`]                             | PG_LSN                                               |
+//! | [`PgTid`]                             | TID                                                  |
+//! | [`PgRegConfig`]                       | REGCONFIG                                            |
+//! | [`PgAclItem`]                         | ACLITEM<sup>3</sup>                                  |
 //! | [`PgLTree`]                           | LTREE                                                |
 //! | [`PgLQuery`]                          | LQUERY                                               |
 //! | [`PgCiText`]                          | CITEXT<sup>1</sup>                                   |
+//! | [`PgBpChar`]                          | CHAR(N)<sup>6</sup>                                  |
 //! | [`PgCube`]                            | CUBE                                                 |
 //! | [`PgHstore`]                          | HSTORE                                               |
 //!
@@ -27,6 +34,28 @@
 //! but this wrapper type is available for edge cases, such as `CITEXT[]` which Postgres
 //! does not consider to be compatible with `TEXT[]`.
 //!
+//! <sup>2</sup> `NAME` is the type of identifiers in the system catalogs, e.g. `pg_class.relname`.
+//! Although it is stored as a fixed-length, NUL-padded field, values are sent without the
+//! padding, so they decode like `TEXT`. Note that Postgres silently truncates a `NAME` to
+//! 63 bytes, so a longer string bound as a `NAME` parameter will not compare equal to the
+//! stored value.
+//!
+//! <sup>3</sup> Decoding only. Postgres has no binary format for `ACLITEM`, so it is always
+//! read in the text format; `PgAclItem` also decodes from `TEXT`.
+//!
+//! <sup>4</sup> The UUID as a big-endian 128-bit integer, for treating UUIDs as opaque numbers
+//! without the `uuid` crate. `uuid::Uuid` remains the type used for `UUID` by the query macros;
+//! use a type override such as `id as "id: u128"` to get a `u128` instead.
+//!
+//! <sup>5</sup> For integers stored as `NUMERIC`, without the `bigdecimal` or `rust_decimal`
+//! crates. Decoding fails if the value has a fractional part or is out of range. Select with a
+//! type override such as `balance as "balance: PgNumericInt<u128>"`.
+//!
+//! <sup>6</sup> `CHAR(N)` (`BPCHAR`) values are padded with spaces to `N` characters. Like
+//! comparisons in SQL, `&str`, `String`, etc. ignore the padding: `'ab'::char(4)` decodes as
+//! `"ab"`, and so does a `CHAR(N)` column in the query macros. Decode as `PgBpChar` to get the
+//! padded value, `"ab  "`.
+//!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
 //! Requires the `bigdecimal` Cargo feature flag.
 //!
@@ -34,6 +63,8 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `bigdecimal::BigDecimal`              | NUMERIC                                              |
 //!
+//! [`PgRange<BigDecimal>`](PgRange) maps to NUMRANGE.
+//!
 #![doc=include_str!("bigdecimal-range.md")]
 //!
 //! ### [`rust_decimal`](https://crates.io/crates/rust_decimal)
@@ -43,6 +74,8 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `rust_decimal::Decimal`               | NUMERIC                                              |
 //!
+//! [`PgRange<Decimal>`](PgRange) maps to NUMRANGE.
+//!
 #![doc=include_str!("rust_decimal-range.md")]
 //!
 //! ### [`chrono`](https://crates.io/crates/chrono)
@@ -58,6 +91,9 @@
 //! | `chrono::NaiveTime`                   | TIME                                                 |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
 //!
+//! [`PgRange<T>`](PgRange) of these types maps to the range types. The [`chrono`] module has
+//! aliases for them: [`chrono::PgDateRange`], [`chrono::PgTsRange`] and [`chrono::PgTsTzRange`].
+//!
 //! ### [`time`](https://crates.io/crates/time)
 //!
 //! Requires the `time` Cargo feature flag.
@@ -70,6 +106,9 @@
 //! | `time::Time`                          | TIME                                                 |
 //! | [`PgTimeTz`]                          | TIMETZ                                               |
 //!
+//! [`PgRange<T>`](PgRange) of these types maps to the range types. The [`time`] module has
+//! aliases for them: [`time::PgDateRange`], [`time::PgTsRange`] and [`time::PgTsTzRange`].
+//!
 //! ### [`uuid`](https://crates.io/crates/uuid)
 //!
 //! Requires the `uuid` Cargo feature flag.
@@ -109,6 +148,19 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `bit_vec::BitVec`                     | BIT, VARBIT                                          |
 //!
+//! ### [`bytes`](https://crates.io/crates/bytes)
+//!
+//! Requires the `bytes` Cargo feature flag.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `bytes::Bytes`                        | BYTEA                                                |
+//! | `bytes::BytesMut`                     | BYTEA                                                |
+//!
+//! Decoding `Bytes` from a row shares the row's buffer instead of copying the value, when
+//! the value was received in the binary format (i.e. from a prepared query). Note that this
+//! keeps the whole row buffer alive for as long as the `Bytes` is.
+//!
 //! ### [`json`](https://crates.io/crates/serde_json)
 //!
 //! Requires the `json` Cargo feature flag.
@@ -118,12 +170,35 @@
 //! | [`Json<T>`]                           | JSON, JSONB                                          |
 //! | `serde_json::Value`                   | JSON, JSONB                                          |
 //! | `&serde_json::value::RawValue`        | JSON, JSONB                                          |
+//! | [`VersionedJson<T>`]                  | JSON, JSONB                                          |
+//! | [`PgJsonScalar`]                      | JSON, JSONB                                          |
+//! | [`PgJsonbArray`]                      | JSON, JSONB                                          |
 //!
 //! `Value` and `RawValue` from `serde_json` can be used for unstructured JSON data with
 //! Postgres.
 //!
 //! [`Json<T>`](crate::types::Json) can be used for structured JSON data with Postgres.
 //!
+//! [`VersionedJson<T>`] can be used for documents that are
+//! deserialized into a different type depending on their version field.
+//!
+//! [`VersionedJson<T>`]: sqlx_core::types::VersionedJson
+//!
+//! [`PgJsonScalar`] reads a single string, number, boolean or `null`, such as the result of
+//! `data->'field'`, without parsing it into a `serde_json::Value`.
+//!
+//! [`PgJsonbArray`] reads a JSON array and only parses the elements that are accessed,
+//! which is cheaper than [`Json<Vec<T>>`](crate::types::Json) if only a few of them are needed.
+//!
+//! [`PgJsonb`] computes and applies RFC 6902 JSON patches between versions of a document,
+//! for tracking changes to `JSONB` columns.
+//!
+//! To bind a document or key to one of the `jsonb` operators, such as `data @> $1`,
+//! `data <@ $1`, `data ? $1` or `data ?| $1`, cast the parameter to the type of its operand
+//! (`$1::jsonb`, `$1::text` or `$1::text[]`) so that Postgres does not have to infer it;
+//! [`QueryBuilder::push_bind_cast()`](sqlx_core::query_builder::QueryBuilder::push_bind_cast)
+//! pushes the placeholder with this cast.
+//!
 //! # [Composite types](https://www.postgresql.org/docs/current/rowtypes.html)
 //!
 //! User-defined composite types are supported through a derive for `Type`.
@@ -153,6 +228,11 @@
 //!
 //! One-dimensional arrays are supported as `Vec<T>` or `&[T]` where `T` implements `Type`.
 //!
+//! The `INT2VECTOR` and `OIDVECTOR` types of the system catalogs, e.g. `pg_index.indkey`,
+//! decode as `Vec<i16>` and `Vec<Oid>` respectively, in both the binary and the text format.
+//! Only decoding is supported: `Vec<i16>` and `Vec<Oid>` are still encoded as `INT2[]` and
+//! `OID[]`, which Postgres doesn't cast to the vector types.
+//!
 //! # [Enumerations](https://www.postgresql.org/docs/current/datatype-enum.html)
 //!
 //! User-defined enumerations are supported through a derive for `Type`.
@@ -167,6 +247,9 @@
 //! enum Mood { Sad, Ok, Happy }
 //! ```
 //!
+//! Enumerations that are only known at runtime decode as [`PgEnumValue`], and their arrays as
+//! `Vec<PgEnumValue>`. See [`PgConnection::register_enum()`][crate::PgConnection::register_enum].
+//!
 //! Rust enumerations may also be defined to be represented as an integer using `repr`.
 //! The following type expects a SQL type of `INTEGER` or `INT4` and will convert to/from the
 //! Rust enumeration.
@@ -183,25 +266,33 @@ use crate::{PgTypeInfo, Postgres};
 
 pub(crate) use sqlx_core::types::{Json, Type};
 
+mod aclitem;
 mod array;
 mod bool;
+mod bpchar;
 mod bytes;
 mod citext;
+mod enum_value;
 mod float;
 mod hstore;
 mod int;
 mod interval;
 mod lquery;
+mod lsn;
 mod ltree;
 // Not behind a Cargo feature because we require JSON in the driver implementation.
 mod json;
+mod literal;
 mod money;
 mod oid;
 mod range;
 mod record;
+mod regconfig;
 mod str;
 mod text;
+mod tid;
 mod tuple;
+mod uuid_int;
 mod void;
 
 #[cfg(any(feature = "chrono", feature = "time"))]
@@ -212,17 +303,29 @@ mod bigdecimal;
 
 mod cube;
 
-#[cfg(any(feature = "bigdecimal", feature = "rust_decimal"))]
 mod numeric;
+mod numeric_int;
+
+#[cfg(feature = "json")]
+mod json_array;
+
+#[cfg(feature = "json")]
+mod json_patch;
+
+#[cfg(feature = "json")]
+mod json_scalar;
+
+#[cfg(feature = "json")]
+mod value_to_json;
 
 #[cfg(feature = "rust_decimal")]
 mod rust_decimal;
 
 #[cfg(feature = "chrono")]
-mod chrono;
+pub mod chrono;
 
 #[cfg(feature = "time")]
-mod time;
+pub mod time;
 
 #[cfg(feature = "uuid")]
 mod uuid;
@@ -239,25 +342,42 @@ mod mac_address;
 #[cfg(feature = "bit-vec")]
 mod bit_vec;
 
+pub use aclitem::{PgAclItem, PgAclPrivilege, PgAclPrivilegeKind};
 pub use array::PgHasArrayType;
+pub use bpchar::PgBpChar;
 pub use citext::PgCiText;
 pub use cube::PgCube;
+pub use enum_value::PgEnumValue;
 pub use hstore::PgHstore;
 pub use interval::PgInterval;
+#[cfg(feature = "json")]
+pub use json_array::PgJsonbArray;
+
+#[cfg(feature = "json")]
+pub use json_patch::{PgJsonPatchError, PgJsonb};
+
+#[cfg(feature = "json")]
+pub use json_scalar::PgJsonScalar;
 pub use lquery::PgLQuery;
 pub use lquery::PgLQueryLevel;
 pub use lquery::PgLQueryVariant;
 pub use lquery::PgLQueryVariantFlag;
+pub use lsn::PgLsn;
 pub use ltree::PgLTree;
 pub use ltree::PgLTreeLabel;
 pub use ltree::PgLTreeParseError;
 pub use money::PgMoney;
+pub use numeric_int::PgNumericInt;
 pub use oid::Oid;
 pub use range::PgRange;
+pub use regconfig::PgRegConfig;
+pub use tid::PgTid;
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 pub use time_tz::PgTimeTz;
 
+pub(crate) use literal::write_literal;
+
 // used in derive(Type) for `struct`
 // but the interface is not considered part of the public API
 #[doc(hidden)]
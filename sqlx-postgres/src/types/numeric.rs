@@ -1,4 +1,11 @@
+// Without a decimal crate, only `PgNumericInt` and `row_to_json()` use this, and not all of it.
+#![cfg_attr(
+    not(any(feature = "bigdecimal", feature = "rust_decimal")),
+    allow(dead_code)
+)]
+
 use sqlx_core::bytes::Buf;
+use std::fmt::{self, Display, Formatter, Write};
 use std::num::Saturating;
 
 use crate::error::BoxDynError;
@@ -170,3 +177,61 @@ impl PgNumeric {
         Ok(())
     }
 }
+
+impl Display for PgNumeric {
+    /// Formats the number in plain decimal notation, with exactly `scale` fractional digits.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let PgNumeric::Number {
+            sign,
+            digits,
+            weight,
+            scale,
+        } = self
+        else {
+            return f.write_str("NaN");
+        };
+
+        // The base-10000 digit at `index`, where `0` is multiplied by `10000 ^ weight`.
+        let digit = |index: i32| {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| digits.get(index))
+                .copied()
+                .unwrap_or(0)
+        };
+
+        if *sign == PgNumericSign::Negative {
+            f.write_char('-')?;
+        }
+
+        let weight = i32::from(*weight);
+
+        if weight < 0 {
+            f.write_char('0')?;
+        } else {
+            write!(f, "{}", digit(0))?;
+
+            for index in 1..=weight {
+                write!(f, "{:04}", digit(index))?;
+            }
+        }
+
+        let scale = usize::try_from(*scale).unwrap_or(0);
+
+        if scale > 0 {
+            let mut fraction = String::with_capacity(scale + 3);
+            let mut index = weight + 1;
+
+            while fraction.len() < scale {
+                write!(fraction, "{:04}", digit(index))?;
+                index += 1;
+            }
+
+            fraction.truncate(scale);
+
+            write!(f, ".{fraction}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,251 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::numeric::{PgNumeric, PgNumericSign};
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// An integer stored in a `NUMERIC` column, such as a token balance that does not fit in a
+/// `BIGINT`.
+///
+/// Implemented for `i128` and `u128`, without needing a decimal crate. Decoding fails if the
+/// value has a nonzero fractional part, does not fit in the integer type, or is `NaN` or
+/// infinite. Trailing fractional zeroes (e.g. `42.00`) are accepted.
+///
+/// `u128` on its own maps to `UUID` (and `i128` is not mapped), so use a type override to select
+/// this type in the query macros:
+///
+/// ```rust,ignore
+/// let row = sqlx::query!(r#"SELECT balance as "balance: PgNumericInt<u128>" FROM accounts"#)
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// let balance: u128 = row.balance.0;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PgNumericInt<T>(pub T);
+
+impl<T> From<T> for PgNumericInt<T> {
+    fn from(value: T) -> Self {
+        PgNumericInt(value)
+    }
+}
+
+// The sign and magnitude of an integral `NUMERIC`, or an error if it is not one, or if the
+// magnitude does not fit in a `u128`.
+fn numeric_to_parts(numeric: PgNumeric) -> Result<(bool, u128), BoxDynError> {
+    let PgNumeric::Number {
+        sign,
+        digits,
+        weight,
+        ..
+    } = numeric
+    else {
+        return Err("NUMERIC value NaN is not an integer".into());
+    };
+
+    let out_of_range = || BoxDynError::from("NUMERIC value out of range for a 128-bit integer");
+
+    // The digits at indexes greater than `weight` are after the decimal point.
+    let integral = usize::try_from(i32::from(weight) + 1).unwrap_or(0);
+
+    if digits.iter().skip(integral).any(|&digit| digit != 0) {
+        return Err("NUMERIC value has a fractional part".into());
+    }
+
+    let mut magnitude = 0_u128;
+
+    for index in 0..integral {
+        let digit = digits.get(index).copied().unwrap_or(0);
+
+        magnitude = magnitude
+            .checked_mul(10_000)
+            .and_then(|magnitude| magnitude.checked_add(digit as u128))
+            .ok_or_else(out_of_range)?;
+    }
+
+    Ok((sign == PgNumericSign::Negative && magnitude != 0, magnitude))
+}
+
+fn parts_to_numeric(negative: bool, mut magnitude: u128) -> PgNumeric {
+    if magnitude == 0 {
+        return PgNumeric::ZERO;
+    }
+
+    let mut digits = Vec::with_capacity(10);
+
+    while magnitude > 0 {
+        digits.push((magnitude % 10_000) as i16);
+        magnitude /= 10_000;
+    }
+
+    // `weight` counts the trailing zero digits, which are left off like Postgres does.
+    let weight = digits.len() as i16 - 1;
+    let zeroes = digits.iter().take_while(|&&digit| digit == 0).count();
+
+    digits.drain(..zeroes);
+    digits.reverse();
+
+    PgNumeric::Number {
+        sign: if negative {
+            PgNumericSign::Negative
+        } else {
+            PgNumericSign::Positive
+        },
+        digits,
+        weight,
+        scale: 0,
+    }
+}
+
+// The text format is plain decimal notation, possibly with a fractional part.
+fn parse_parts(s: &str) -> Result<(bool, u128), BoxDynError> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+
+    let (integral, fraction) = s.split_once('.').unwrap_or((s, ""));
+
+    if integral.is_empty() || !integral.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("NUMERIC value {s:?} is not an integer").into());
+    }
+
+    if !fraction.bytes().all(|b| b == b'0') {
+        return Err("NUMERIC value has a fractional part".into());
+    }
+
+    let magnitude = integral
+        .parse::<u128>()
+        .map_err(|_| "NUMERIC value out of range for a 128-bit integer")?;
+
+    Ok((negative && magnitude != 0, magnitude))
+}
+
+fn decode_parts(value: PgValueRef<'_>) -> Result<(bool, u128), BoxDynError> {
+    match value.format() {
+        PgValueFormat::Binary => numeric_to_parts(PgNumeric::decode(value.as_bytes()?)?),
+        PgValueFormat::Text => parse_parts(value.as_str()?),
+    }
+}
+
+impl<T> Type<Postgres> for PgNumericInt<T> {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::NUMERIC
+    }
+}
+
+impl<T> PgHasArrayType for PgNumericInt<T> {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::NUMERIC_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgNumericInt<i128> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        parts_to_numeric(self.0 < 0, self.0.unsigned_abs()).encode(buf)?;
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        PgNumeric::size_hint(39)
+    }
+}
+
+impl Encode<'_, Postgres> for PgNumericInt<u128> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        parts_to_numeric(false, self.0).encode(buf)?;
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        PgNumeric::size_hint(39)
+    }
+}
+
+impl Decode<'_, Postgres> for PgNumericInt<i128> {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        let (negative, magnitude) = decode_parts(value)?;
+
+        let value = if negative {
+            0_i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        };
+
+        value
+            .map(PgNumericInt)
+            .ok_or_else(|| "NUMERIC value out of range for i128".into())
+    }
+}
+
+impl Decode<'_, Postgres> for PgNumericInt<u128> {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match decode_parts(value)? {
+            (false, magnitude) => Ok(PgNumericInt(magnitude)),
+            (true, _) => Err("NUMERIC value out of range for u128: negative".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_int_round_trip() {
+        for value in [0, 1, 9_999, 10_000, 10_001, 1_0000_0000, u128::MAX] {
+            let (negative, magnitude) = numeric_to_parts(parts_to_numeric(false, value)).unwrap();
+
+            assert!(!negative);
+            assert_eq!(magnitude, value);
+        }
+
+        assert_eq!(
+            parts_to_numeric(true, 1_0000_0000),
+            PgNumeric::Number {
+                sign: PgNumericSign::Negative,
+                digits: vec![1],
+                weight: 2,
+                scale: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_numeric_int_rejects_non_integers() {
+        // 12.5
+        let fractional = PgNumeric::Number {
+            sign: PgNumericSign::Positive,
+            digits: vec![12, 5000],
+            weight: 0,
+            scale: 1,
+        };
+
+        assert!(numeric_to_parts(fractional).is_err());
+        assert!(numeric_to_parts(PgNumeric::NotANumber).is_err());
+
+        // 10000 ^ 10 > u128::MAX
+        let huge = PgNumeric::Number {
+            sign: PgNumericSign::Positive,
+            digits: vec![1],
+            weight: 10,
+            scale: 0,
+        };
+
+        assert!(numeric_to_parts(huge).is_err());
+    }
+
+    #[test]
+    fn test_parse_numeric_int() {
+        assert_eq!(parse_parts("42").unwrap(), (false, 42));
+        assert_eq!(parse_parts("-42.000").unwrap(), (true, 42));
+        assert_eq!(parse_parts("-0").unwrap(), (false, 0));
+
+        for invalid in ["", "-", "1.5", "NaN", "Infinity", "+1", "1e3"] {
+            assert!(parse_parts(invalid).is_err(), "{invalid:?}");
+        }
+    }
+}
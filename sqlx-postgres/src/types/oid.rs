@@ -27,6 +27,10 @@ impl PgHasArrayType for Oid {
     fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::OID_ARRAY
     }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        *ty == PgTypeInfo::OID_ARRAY || *ty == PgTypeInfo::OID_VECTOR
+    }
 }
 
 impl Encode<'_, Postgres> for Oid {
@@ -14,7 +14,7 @@ use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValue
 // https://github.com/postgres/postgres/blob/2f48ede080f42b97b594fb14102c82ca1001b80c/src/include/utils/rangetypes.h#L35-L44
 bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-    struct RangeFlags: u8 {
+    pub(crate) struct RangeFlags: u8 {
         const EMPTY = 0x01;
         const LB_INC = 0x02;
         const UB_INC = 0x04;
@@ -112,6 +112,36 @@ impl<T> RangeBounds<T> for PgRange<T> {
     }
 }
 
+impl<T: PartialOrd> PgRange<T> {
+    /// Returns `true` if `value` lies within the bounds of this range, like the `@>` operator.
+    pub fn contains(&self, value: &T) -> bool {
+        RangeBounds::contains(self, value)
+    }
+
+    /// Returns `true` if the two ranges have at least one value in common, like the `&&`
+    /// operator.
+    ///
+    /// The bounds are compared as for a continuous range. This matches Postgres for ranges of
+    /// discrete types, e.g. `INT4RANGE` or `DATERANGE`, as long as their bounds are in the
+    /// canonical `[start,end)` form that Postgres returns them in.
+    pub fn overlaps(&self, other: &PgRange<T>) -> bool {
+        starts_before_end(self.start_bound(), self.end_bound())
+            && starts_before_end(other.start_bound(), other.end_bound())
+            && starts_before_end(self.start_bound(), other.end_bound())
+            && starts_before_end(other.start_bound(), self.end_bound())
+    }
+}
+
+/// Returns `true` if there is at least one value between the bounds `start` and `end`.
+fn starts_before_end<T: PartialOrd>(start: Bound<&T>, end: Bound<&T>) -> bool {
+    match (start, end) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+        (Bound::Included(start), Bound::Included(end)) => start <= end,
+        (Bound::Included(start) | Bound::Excluded(start), Bound::Excluded(end))
+        | (Bound::Excluded(start), Bound::Included(end)) => start < end,
+    }
+}
+
 impl Type<Postgres> for PgRange<i32> {
     fn type_info() -> PgTypeInfo {
         PgTypeInfo::INT4_RANGE
@@ -349,8 +379,10 @@ where
                 }
 
                 if !flags.contains(RangeFlags::LB_INF) {
-                    let value =
-                        T::decode(PgValueRef::get(&mut buf, value.format, element_ty.clone())?)?;
+                    let value = T::decode(PgValueRef {
+                        strict_timezone: value.strict_timezone,
+                        ..PgValueRef::get(&mut buf, value.format, element_ty.clone())?
+                    })?;
 
                     start = if flags.contains(RangeFlags::LB_INC) {
                         Bound::Included(value)
@@ -360,8 +392,10 @@ where
                 }
 
                 if !flags.contains(RangeFlags::UB_INF) {
-                    let value =
-                        T::decode(PgValueRef::get(&mut buf, value.format, element_ty.clone())?)?;
+                    let value = T::decode(PgValueRef {
+                        strict_timezone: value.strict_timezone,
+                        ..PgValueRef::get(&mut buf, value.format, element_ty.clone())?
+                    })?;
 
                     end = if flags.contains(RangeFlags::UB_INC) {
                         Bound::Included(value)
@@ -393,15 +427,17 @@ where
 
                 let mut element = String::new();
                 let mut done = false;
-                let mut quoted = false;
                 let mut in_quotes = false;
                 let mut in_escape = false;
-                let mut prev_ch = '\0';
                 let mut count = 0;
 
                 while !done {
                     element.clear();
 
+                    // both bounds may be quoted, e.g. `["2019-01-02 05:10:20","2019-01-03 05:10:20")`
+                    let mut quoted = false;
+                    let mut prev_ch = '\0';
+
                     loop {
                         match chars.next() {
                             Some(ch) => {
@@ -451,6 +487,7 @@ where
                             format: PgValueFormat::Text,
                             value: Some(element.as_bytes()),
                             row: None,
+                            strict_timezone: value.strict_timezone,
                         })?);
 
                         if count == 1 {
@@ -520,3 +557,43 @@ fn range_compatible<E: Type<Postgres>>(ty: &PgTypeInfo) -> bool {
 
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use super::PgRange;
+
+    #[test]
+    fn test_contains() {
+        let range = PgRange::from(1..5);
+
+        assert!(range.contains(&1));
+        assert!(range.contains(&4));
+        assert!(!range.contains(&5));
+        assert!(!range.contains(&0));
+
+        assert!(PgRange::from(..=5).contains(&5));
+        assert!(PgRange::<i32>::from((Bound::Unbounded, Bound::Unbounded)).contains(&i32::MIN));
+    }
+
+    #[test]
+    fn test_overlaps() {
+        assert!(PgRange::from(1..5).overlaps(&PgRange::from(4..8)));
+        assert!(PgRange::from(1..=5).overlaps(&PgRange::from(5..8)));
+        assert!(PgRange::from(1..).overlaps(&PgRange::from(..2)));
+
+        // Touching, but no value in common.
+        assert!(!PgRange::from(1..5).overlaps(&PgRange::from(5..8)));
+        assert!(
+            !PgRange::from(1..=5).overlaps(&PgRange::from((Bound::Excluded(5), Bound::Unbounded)))
+        );
+        assert!(!PgRange::from(..1).overlaps(&PgRange::from(1..)));
+
+        // An empty range overlaps nothing, not even a range that contains its bounds.
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = PgRange::from(3..3);
+        assert!(!empty.overlaps(&PgRange::from(0..10)));
+        assert!(!PgRange::from(0..10).overlaps(&empty));
+    }
+}
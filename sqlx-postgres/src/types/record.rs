@@ -63,6 +63,9 @@ pub struct PgRecordDecoder<'r> {
     typ: PgTypeInfo,
     fmt: PgValueFormat,
     ind: usize,
+    // in the text format, whether the last field was terminated by the end of the record
+    // rather than a delimiter; an empty trailing field (e.g. in `(foo,)`) is a NULL
+    ended: bool,
 }
 
 impl<'r> PgRecordDecoder<'r> {
@@ -88,6 +91,7 @@ impl<'r> PgRecordDecoder<'r> {
             fmt,
             typ,
             ind: 0,
+            ended: false,
         })
     }
 
@@ -96,7 +100,12 @@ impl<'r> PgRecordDecoder<'r> {
     where
         T: for<'a> Decode<'a, Postgres> + Type<Postgres>,
     {
-        if self.buf.is_empty() {
+        let exhausted = match self.fmt {
+            PgValueFormat::Binary => self.buf.is_empty(),
+            PgValueFormat::Text => self.ended,
+        };
+
+        if exhausted {
             return Err(format!("no field `{0}` found on record", self.ind).into());
         }
 
@@ -147,6 +156,8 @@ impl<'r> PgRecordDecoder<'r> {
                 let mut in_escape = false;
                 let mut prev_ch = '\0';
 
+                self.ended = true;
+
                 while !self.buf.is_empty() {
                     let ch = self.buf.get_u8() as char;
                     match ch {
@@ -172,7 +183,10 @@ impl<'r> PgRecordDecoder<'r> {
                             in_escape = true;
                         }
 
-                        ',' if !in_quotes => break,
+                        ',' if !in_quotes => {
+                            self.ended = false;
+                            break;
+                        }
 
                         _ => {
                             element.push(ch);
@@ -188,6 +202,8 @@ impl<'r> PgRecordDecoder<'r> {
                     Some(element.as_bytes())
                 };
 
+                self.ind += 1;
+
                 // NOTE: we do not call [`accepts`] or give a chance to from a user as
                 //       TEXT sequences are not strongly typed
 
@@ -198,6 +214,7 @@ impl<'r> PgRecordDecoder<'r> {
                     format: self.fmt,
                     value: buf,
                     row: None,
+                    strict_timezone: false,
                 })
             }
         }
@@ -0,0 +1,86 @@
+use std::fmt::{self, Display, Formatter};
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::{Oid, Type};
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// The PostgreSQL [`REGCONFIG`] type refers to a text search configuration, e.g.
+/// `'english'::regconfig`.
+///
+/// Like the other `reg*` types, a `REGCONFIG` is the OID of a row in a system catalog
+/// (`pg_ts_config`), which is what is sent in the binary format. The text format is the name of
+/// the configuration instead, schema-qualified if it isn't on the `search_path`. A decoded value
+/// is therefore [`PgRegConfig::Oid`] when a query is prepared (the default), and
+/// [`PgRegConfig::Name`] for queries that return text, such as [`raw_sql()`][sqlx_core::raw_sql::raw_sql].
+///
+/// To get the name of a configuration from its OID, cast it to text in the query, e.g.
+/// `SELECT cfg::text`. Only [`PgRegConfig::Oid`] can be bound as a parameter; to bind a
+/// configuration by name, bind the name as text and cast it, e.g.
+/// `to_tsvector($1::regconfig, body)`.
+///
+/// [`REGCONFIG`]: https://www.postgresql.org/docs/current/datatype-oid.html
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub enum PgRegConfig {
+    /// The OID of the configuration, as sent in the binary format.
+    Oid(Oid),
+    /// The name of the configuration, as sent in the text format, e.g. `english` or
+    /// `myschema.custom`.
+    Name(String),
+}
+
+impl Display for PgRegConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PgRegConfig::Oid(oid) => write!(f, "{}", oid.0),
+            PgRegConfig::Name(name) => f.write_str(name),
+        }
+    }
+}
+
+impl Type<Postgres> for PgRegConfig {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::REGCONFIG
+    }
+}
+
+impl PgHasArrayType for PgRegConfig {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::REGCONFIG_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgRegConfig {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        match self {
+            PgRegConfig::Oid(oid) => buf.extend(&oid.0.to_be_bytes()),
+            PgRegConfig::Name(name) => {
+                return Err(format!(
+                    "cannot bind REGCONFIG {name:?} by name: \
+                     bind it as text and cast it with `::regconfig` instead"
+                )
+                .into())
+            }
+        }
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        4
+    }
+}
+
+impl Decode<'_, Postgres> for PgRegConfig {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => Ok(PgRegConfig::Oid(Oid(BigEndian::read_u32(
+                value.as_bytes()?,
+            )))),
+            PgValueFormat::Text => Ok(PgRegConfig::Name(value.as_str()?.to_owned())),
+        }
+    }
+}
@@ -123,26 +123,39 @@ impl Encode<'_, Postgres> for String {
     }
 }
 
+// The value as a string, without the trailing spaces of a `CHAR(N)`, which are insignificant
+// when comparing in SQL. Use `PgBpChar` to keep them.
+fn as_unpadded_str(value: PgValueRef<'_>) -> Result<&'_ str, BoxDynError> {
+    let is_bpchar = value.type_info == PgTypeInfo::BPCHAR;
+    let str = value.as_str()?;
+
+    Ok(if is_bpchar {
+        str.trim_end_matches(' ')
+    } else {
+        str
+    })
+}
+
 impl<'r> Decode<'r, Postgres> for &'r str {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        value.as_str()
+        as_unpadded_str(value)
     }
 }
 
 impl<'r> Decode<'r, Postgres> for Cow<'r, str> {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(Cow::Borrowed(value.as_str()?))
+        Ok(Cow::Borrowed(as_unpadded_str(value)?))
     }
 }
 
 impl<'r> Decode<'r, Postgres> for Box<str> {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(Box::from(value.as_str()?))
+        Ok(Box::from(as_unpadded_str(value)?))
     }
 }
 
 impl Decode<'_, Postgres> for String {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        Ok(value.as_str()?.to_owned())
+        Ok(as_unpadded_str(value)?.to_owned())
     }
 }
@@ -0,0 +1,145 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+/// The PostgreSQL [`TID`] type is the physical location of a row version within its table, as
+/// in the `ctid` system column.
+///
+/// The Postgres text format is the block number and the (1-based) index of the row within the
+/// block, in parentheses, e.g. `(0,1)`. [`Display`] and [`FromStr`] use the same format.
+///
+/// A row gets a new location whenever it is updated, and `VACUUM FULL` moves rows too, so a
+/// `TID` only identifies a row for a short time.
+///
+/// [`TID`]: https://www.postgresql.org/docs/current/datatype-oid.html
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct PgTid {
+    /// The number of the block (page) of the table.
+    pub block: u32,
+    /// The number of the row within the block, starting at 1.
+    pub offset: u16,
+}
+
+impl Display for PgTid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.block, self.offset)
+    }
+}
+
+impl FromStr for PgTid {
+    type Err = BoxDynError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid TID {s:?}: expected `(block,offset)`");
+
+        let (block, offset) = s
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .and_then(|s| s.split_once(','))
+            .ok_or_else(invalid)?;
+
+        // `parse()` also accepts a leading `+`, which Postgres does not.
+        let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+
+        if !is_number(block) || !is_number(offset) {
+            return Err(invalid().into());
+        }
+
+        Ok(PgTid {
+            block: block.parse().map_err(|_| invalid())?,
+            offset: offset.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl Type<Postgres> for PgTid {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::TID
+    }
+}
+
+impl PgHasArrayType for PgTid {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::TID_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for PgTid {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend(&self.block.to_be_bytes());
+        buf.extend(&self.offset.to_be_bytes());
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        6
+    }
+}
+
+impl Decode<'_, Postgres> for PgTid {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let bytes = value.as_bytes()?;
+
+                if bytes.len() != 6 {
+                    return Err(format!("expected 6 bytes for TID, got {}", bytes.len()).into());
+                }
+
+                Ok(PgTid {
+                    block: BigEndian::read_u32(&bytes[..4]),
+                    offset: BigEndian::read_u16(&bytes[4..]),
+                })
+            }
+            PgValueFormat::Text => value.as_str()?.parse(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PgTid;
+
+    #[test]
+    fn test_pgtid_display() {
+        assert_eq!(PgTid::default().to_string(), "(0,0)");
+        assert_eq!(
+            PgTid {
+                block: 42,
+                offset: 7
+            }
+            .to_string(),
+            "(42,7)"
+        );
+    }
+
+    #[test]
+    fn test_pgtid_from_str() {
+        assert_eq!(
+            "(42,7)".parse::<PgTid>().unwrap(),
+            PgTid {
+                block: 42,
+                offset: 7
+            }
+        );
+        assert_eq!(
+            "(4294967295,65535)".parse::<PgTid>().unwrap(),
+            PgTid {
+                block: u32::MAX,
+                offset: u16::MAX
+            }
+        );
+
+        for invalid in ["", "()", "(0)", "0,1", "(0,1", "(+0,1)", "(0, 1)", "(0,65536)"] {
+            assert!(invalid.parse::<PgTid>().is_err(), "{invalid:?}");
+        }
+    }
+}
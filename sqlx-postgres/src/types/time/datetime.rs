@@ -53,6 +53,8 @@ impl Encode<'_, Postgres> for PrimitiveDateTime {
 
 impl<'r> Decode<'r, Postgres> for PrimitiveDateTime {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        value.check_naive_timestamp()?;
+
         Ok(match value.format() {
             PgValueFormat::Binary => {
                 // TIMESTAMP is encoded as the microseconds since the epoch
@@ -63,11 +65,12 @@ impl<'r> Decode<'r, Postgres> for PrimitiveDateTime {
             PgValueFormat::Text => {
                 let s = value.as_str()?;
 
-                // If there is no decimal point we need to add one.
+                // If there is no decimal point we need to add one, before the time-zone specifier.
                 let s = if s.contains('.') {
                     Cow::Borrowed(s)
                 } else {
-                    Cow::Owned(format!("{s}.0"))
+                    let (datetime, offset) = s.split_at(s.find('+').unwrap_or(s.len()));
+                    Cow::Owned(format!("{datetime}.0{offset}"))
                 };
 
                 // Contains a time-zone specifier
@@ -103,6 +106,11 @@ impl Encode<'_, Postgres> for OffsetDateTime {
 
 impl<'r> Decode<'r, Postgres> for OffsetDateTime {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
+        let value = PgValueRef {
+            strict_timezone: false,
+            ..value
+        };
+
         Ok(<PrimitiveDateTime as Decode<Postgres>>::decode(value)?.assume_utc())
     }
 }
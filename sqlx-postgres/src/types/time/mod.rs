@@ -1,3 +1,11 @@
+//! Aliases of [`PgRange`] for the range types with elements from the `time` crate.
+//!
+//! Requires the `time` Cargo feature flag.
+
+use ::time::{Date, OffsetDateTime, PrimitiveDateTime};
+
+use crate::types::PgRange;
+
 mod date;
 mod datetime;
 
@@ -7,3 +15,14 @@ mod time;
 
 #[rustfmt::skip]
 const PG_EPOCH: ::time::Date = ::time::macros::date!(2000-1-1);
+
+/// A `DATERANGE`.
+pub type PgDateRange = PgRange<Date>;
+
+/// A `TSRANGE`.
+pub type PgTsRange = PgRange<PrimitiveDateTime>;
+
+/// A `TSTZRANGE`.
+///
+/// Values are decoded with a UTC offset.
+pub type PgTsTzRange = PgRange<OffsetDateTime>;
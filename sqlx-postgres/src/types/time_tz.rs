@@ -124,6 +124,7 @@ mod chrono {
 #[cfg(feature = "time")]
 mod time {
     use super::*;
+    use ::time::macros::format_description;
     use ::time::{Duration, Time, UtcOffset};
 
     impl Type<Postgres> for PgTimeTz<Time, UtcOffset> {
@@ -166,9 +167,28 @@ mod time {
                 }
 
                 PgValueFormat::Text => {
-                    // the `time` crate has a limited ability to parse and can't parse the
-                    // timezone format
-                    Err("reading a `TIMETZ` value in text format is not supported.".into())
+                    let s = value.as_str()?;
+
+                    // The offset always carries a sign, the time never does.
+                    let split = s
+                        .rfind(['+', '-'])
+                        .ok_or_else(|| format!("missing offset in `TIMETZ` value: {s:?}"))?;
+
+                    let time = Time::parse(
+                        &s[..split],
+                        // Postgres will not include the subsecond part if it's zero.
+                        &format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]"),
+                    )?;
+
+                    // Postgres only includes the minutes and seconds of the offset if nonzero.
+                    let offset = UtcOffset::parse(
+                        &s[split..],
+                        &format_description!(
+                            "[offset_hour sign:mandatory][optional [:[offset_minute]]][optional [:[offset_second]]]"
+                        ),
+                    )?;
+
+                    Ok(PgTimeTz { time, offset })
                 }
             }
         }
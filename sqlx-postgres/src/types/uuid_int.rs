@@ -0,0 +1,99 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::types::Type;
+use crate::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+// `u128` maps to `UUID`, for code that treats UUIDs as opaque 128-bit integers and does not
+// want to depend on the `uuid` crate. The integer is the UUID read as a big-endian number,
+// so `00000000-0000-0000-0000-000000000001` is `1`.
+
+impl Type<Postgres> for u128 {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::UUID
+    }
+}
+
+impl PgHasArrayType for u128 {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::UUID_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for u128 {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+        buf.extend(&self.to_be_bytes());
+
+        Ok(IsNull::No)
+    }
+
+    fn size_hint(&self) -> usize {
+        16
+    }
+}
+
+impl Decode<'_, Postgres> for u128 {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => {
+                let bytes: [u8; 16] = value
+                    .as_bytes()?
+                    .try_into()
+                    .map_err(|_| "invalid UUID: expected 16 bytes")?;
+
+                Ok(u128::from_be_bytes(bytes))
+            }
+            PgValueFormat::Text => parse_uuid(value.as_str()?),
+        }
+    }
+}
+
+// Postgres outputs the standard hyphenated form, but also accepts braces and leaves out or
+// moves the hyphens on input, so this only requires 32 hexadecimal digits.
+fn parse_uuid(s: &str) -> Result<u128, BoxDynError> {
+    let invalid = || format!("invalid UUID {s:?}");
+
+    let digits = s
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .unwrap_or(s)
+        .replace('-', "");
+
+    // `from_str_radix` also accepts a leading `+`
+    if digits.len() != 32 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(invalid().into());
+    }
+
+    u128::from_str_radix(&digits, 16).map_err(|_| invalid().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_uuid;
+
+    #[test]
+    fn test_parse_uuid_as_u128() {
+        let expected = 0xa0ee_bc99_9c0b_4ef8_bb6d_6bb9_bd38_0a11_u128;
+
+        for valid in [
+            "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11",
+            "A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11",
+            "{a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11}",
+            "a0eebc999c0b4ef8bb6d6bb9bd380a11",
+            "a0ee-bc99-9c0b-4ef8-bb6d-6bb9-bd38-0a11",
+        ] {
+            assert_eq!(parse_uuid(valid).unwrap(), expected, "{valid:?}");
+        }
+
+        for invalid in [
+            "",
+            "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a1",
+            "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a111",
+            "+0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11",
+            "g0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11",
+            "{a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11",
+        ] {
+            assert!(parse_uuid(invalid).is_err(), "{invalid:?}");
+        }
+    }
+}
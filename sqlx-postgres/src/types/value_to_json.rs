@@ -0,0 +1,227 @@
+use serde_json::Value as JsonValue;
+use sqlx_core::bytes::Buf;
+use sqlx_core::row::{float_to_json, ValueToJson};
+use sqlx_core::value::ValueRef;
+
+use crate::decode::Decode;
+use crate::error::BoxDynError;
+use crate::type_info::{PgType, PgTypeKind};
+use crate::types::numeric::PgNumeric;
+use crate::types::Oid;
+use crate::{PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+
+impl ValueToJson for Postgres {
+    fn value_to_json(value: PgValueRef<'_>) -> Result<JsonValue, BoxDynError> {
+        if value.is_null() {
+            return Ok(JsonValue::Null);
+        }
+
+        let json = match &value.type_info.0 {
+            PgType::Bool => decode::<bool>(value)?.into(),
+            PgType::Int2 => decode::<i16>(value)?.into(),
+            PgType::Int4 => decode::<i32>(value)?.into(),
+            PgType::Int8 => decode::<i64>(value)?.into(),
+            PgType::Oid => decode::<Oid>(value)?.0.into(),
+            PgType::Float4 => float_to_json(decode::<f32>(value)?.into()),
+            PgType::Float8 => float_to_json(decode::<f64>(value)?),
+
+            // Formatted as a string to preserve precision.
+            PgType::Numeric => match value.format() {
+                PgValueFormat::Text => value.as_str()?.into(),
+                PgValueFormat::Binary => PgNumeric::decode(value.as_bytes()?)?.to_string().into(),
+            },
+
+            PgType::Text | PgType::Varchar | PgType::Bpchar | PgType::Name | PgType::Unknown => {
+                value.as_str()?.into()
+            }
+
+            PgType::Json | PgType::Jsonb => decode::<JsonValue>(value)?,
+            PgType::Bytea => decode::<Vec<u8>>(value)?.into(),
+            PgType::Void => JsonValue::Null,
+
+            _ => {
+                if let Some(element) = value.type_info.try_array_element() {
+                    let element = element.into_owned();
+                    return array_to_json(value, element);
+                }
+
+                match value.type_info.kind() {
+                    // Enums are sent as their label in both formats.
+                    PgTypeKind::Enum(_) => value.as_str()?.into(),
+
+                    PgTypeKind::Domain(base) => {
+                        return Self::value_to_json(PgValueRef {
+                            type_info: base.clone(),
+                            ..value
+                        })
+                    }
+
+                    _ if value.format() == PgValueFormat::Text => value.as_str()?.into(),
+
+                    _ => {
+                        return Err(format!(
+                            "no JSON mapping for values of type {} in the binary format",
+                            value.type_info.0.display_name()
+                        )
+                        .into())
+                    }
+                }
+            }
+        };
+
+        Ok(json)
+    }
+}
+
+fn array_to_json(value: PgValueRef<'_>, element: PgTypeInfo) -> Result<JsonValue, BoxDynError> {
+    // `int2vector` and `oidvector` are space-separated in the text format
+    if value.format() == PgValueFormat::Text
+        && matches!(value.type_info.0, PgType::Int2Vector | PgType::OidVector)
+    {
+        return value
+            .as_str()?
+            .split_ascii_whitespace()
+            .map(|text| {
+                Postgres::value_to_json(PgValueRef {
+                    value: Some(text.as_bytes()),
+                    row: None,
+                    type_info: element.clone(),
+                    format: PgValueFormat::Text,
+                    strict_timezone: false,
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(JsonValue::Array);
+    }
+
+    if value.format() == PgValueFormat::Text {
+        let mut input = value.as_str()?;
+        let json = text_array_to_json(&mut input, &element)?;
+
+        if !input.is_empty() {
+            return Err(format!("unexpected trailing characters in array: {input:?}").into());
+        }
+
+        return Ok(json);
+    }
+
+    // https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L1548
+    let mut buf = value.as_bytes()?;
+
+    let ndim = buf.get_i32();
+
+    if ndim == 0 {
+        return Ok(JsonValue::Array(Vec::new()));
+    }
+
+    let _flags = buf.get_i32();
+
+    let element = PgTypeInfo::try_from_oid(Oid(buf.get_u32())).unwrap_or(element);
+
+    let dims = (0..ndim)
+        .map(|_| {
+            let len = buf.get_i32();
+            let _lower = buf.get_i32();
+
+            usize::try_from(len).map_err(|_| format!("invalid array length {len}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    elements_to_json(&mut buf, &dims, &element)
+}
+
+/// Convert the elements of an array with the dimensions `dims`, nesting one JSON array per
+/// dimension.
+fn elements_to_json(
+    buf: &mut &[u8],
+    dims: &[usize],
+    element: &PgTypeInfo,
+) -> Result<JsonValue, BoxDynError> {
+    let Some((&len, dims)) = dims.split_first() else {
+        let value = PgValueRef::get(buf, PgValueFormat::Binary, element.clone())?;
+        return Postgres::value_to_json(value);
+    };
+
+    (0..len)
+        .map(|_| elements_to_json(buf, dims, element))
+        .collect::<Result<_, _>>()
+        .map(JsonValue::Array)
+}
+
+/// Parse an array in the text format from the start of `input`, nesting one JSON array per
+/// dimension.
+///
+/// The elements are converted as text values of their type.
+// https://github.com/postgres/postgres/blob/a995b371ae29de2d38c4b7881cf414b1560e9746/src/backend/utils/adt/arrayfuncs.c#L718
+fn text_array_to_json(input: &mut &str, element: &PgTypeInfo) -> Result<JsonValue, BoxDynError> {
+    let text_to_json = |text: &str| {
+        Postgres::value_to_json(PgValueRef {
+            value: Some(text.as_bytes()),
+            row: None,
+            type_info: element.clone(),
+            format: PgValueFormat::Text,
+            strict_timezone: false,
+        })
+    };
+
+    *input = input
+        .strip_prefix('{')
+        .ok_or("expected '{' at the start of an array")?;
+
+    let mut elements = Vec::new();
+
+    if let Some(rest) = input.strip_prefix('}') {
+        *input = rest;
+        return Ok(JsonValue::Array(elements));
+    }
+
+    loop {
+        let json = if input.starts_with('{') {
+            text_array_to_json(input, element)?
+        } else if let Some(quoted) = input.strip_prefix('"') {
+            let mut text = String::new();
+            let mut chars = quoted.char_indices();
+
+            loop {
+                match chars.next() {
+                    Some((_, '\\')) => {
+                        text.push(chars.next().ok_or("unterminated array element")?.1)
+                    }
+                    Some((end, '"')) => {
+                        *input = &quoted[end + 1..];
+                        break;
+                    }
+                    Some((_, c)) => text.push(c),
+                    None => return Err("unterminated array element".into()),
+                }
+            }
+
+            text_to_json(&text)?
+        } else {
+            let end = input.find([',', '}']).ok_or("unterminated array")?;
+            let (text, rest) = input.split_at(end);
+            *input = rest;
+
+            if text.eq_ignore_ascii_case("NULL") {
+                JsonValue::Null
+            } else {
+                text_to_json(text)?
+            }
+        };
+
+        elements.push(json);
+
+        if let Some(rest) = input.strip_prefix(',') {
+            *input = rest;
+        } else if let Some(rest) = input.strip_prefix('}') {
+            *input = rest;
+            return Ok(JsonValue::Array(elements));
+        } else {
+            return Err("expected ',' or '}' after an array element".into());
+        }
+    }
+}
+
+fn decode<'r, T: Decode<'r, Postgres>>(value: PgValueRef<'r>) -> Result<T, BoxDynError> {
+    T::decode(value)
+}
@@ -19,6 +19,8 @@ pub struct PgValueRef<'r> {
     pub(crate) row: Option<&'r Bytes>,
     pub(crate) type_info: PgTypeInfo,
     pub(crate) format: PgValueFormat,
+    // see `PgConnectOptions::strict_timezone()`
+    pub(crate) strict_timezone: bool,
 }
 
 /// Implementation of [`Value`] for PostgreSQL.
@@ -54,13 +56,22 @@ impl<'r> PgValueRef<'r> {
             row: None,
             type_info: ty,
             format,
+            strict_timezone: false,
         })
     }
 
+    /// The format the value was sent in.
+    ///
+    /// Values are sent in the binary format, except for the results of the simple query
+    /// protocol (e.g. [`raw_sql()`](sqlx_core::raw_sql::raw_sql)) and types with no binary
+    /// format, which are sent as text.
     pub fn format(&self) -> PgValueFormat {
         self.format
     }
 
+    /// The encoded value, in the [format](Self::format) it was sent in.
+    ///
+    /// Returns an error if the value is `NULL`.
     pub fn as_bytes(&self) -> Result<&'r [u8], BoxDynError> {
         match &self.value {
             Some(v) => Ok(v),
@@ -68,9 +79,27 @@ impl<'r> PgValueRef<'r> {
         }
     }
 
+    /// The encoded value as a string, for a value in the text format.
+    ///
+    /// Returns an error if the value is `NULL` or not valid UTF-8.
     pub fn as_str(&self) -> Result<&'r str, BoxDynError> {
         Ok(from_utf8(self.as_bytes()?)?)
     }
+
+    /// Reject a `TIMESTAMPTZ` value for a type without a time zone.
+    ///
+    /// See [`PgConnectOptions::strict_timezone()`][crate::PgConnectOptions::strict_timezone].
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    pub(crate) fn check_naive_timestamp(&self) -> Result<(), BoxDynError> {
+        if self.strict_timezone && self.type_info == PgTypeInfo::TIMESTAMPTZ {
+            return Err("a TIMESTAMPTZ is decoded into a type without a time zone, which \
+                        `strict_timezone` rejects; decode into `DateTime<Utc>` or \
+                        `OffsetDateTime` instead"
+                .into());
+        }
+
+        Ok(())
+    }
 }
 
 impl Value for PgValue {
@@ -83,6 +112,7 @@ impl Value for PgValue {
             row: None,
             type_info: self.type_info.clone(),
             format: self.format,
+            strict_timezone: false,
         }
     }
 
@@ -25,6 +25,10 @@ impl AnyConnectionBackend for SqliteConnection {
         <Sqlite as Database>::NAME
     }
 
+    fn id(&self) -> u64 {
+        Connection::id(self)
+    }
+
     fn close(self: Box<Self>) -> BoxFuture<'static, sqlx_core::Result<()>> {
         Connection::close(*self)
     }
@@ -37,6 +41,26 @@ impl AnyConnectionBackend for SqliteConnection {
         Connection::ping(self)
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::reset(self)
+    }
+
+    fn rollback_open_transaction(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::rollback_open_transaction(self)
+    }
+
+    fn incremental_vacuum(&mut self, pages: u32) -> BoxFuture<'_, sqlx_core::Result<()>> {
+        Connection::incremental_vacuum(self, pages)
+    }
+
+    fn queries_executed(&self) -> u64 {
+        Connection::queries_executed(self)
+    }
+
+    fn server_now_micros(&mut self) -> BoxFuture<'_, sqlx_core::Result<i64>> {
+        Connection::server_now_micros(self)
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, sqlx_core::Result<()>> {
         SqliteTransactionManager::begin(self)
     }
@@ -65,6 +89,10 @@ impl AnyConnectionBackend for SqliteConnection {
         Connection::should_flush(self)
     }
 
+    fn set_require_transaction_for_writes(&mut self, require: bool) {
+        Connection::set_require_transaction_for_writes(self, require);
+    }
+
     #[cfg(feature = "migrate")]
     fn as_migrate(
         &mut self,
@@ -143,6 +171,7 @@ impl<'a> TryFrom<&'a SqliteTypeInfo> for AnyTypeInfo {
         Ok(AnyTypeInfo {
             kind: match &sqlite_type.0 {
                 DataType::Null => AnyTypeInfoKind::Null,
+                DataType::Bool => AnyTypeInfoKind::Bool,
                 DataType::Int4 => AnyTypeInfoKind::Integer,
                 DataType::Integer => AnyTypeInfoKind::BigInt,
                 DataType::Float => AnyTypeInfoKind::Double,
@@ -72,11 +72,57 @@ impl<'q> Arguments<'q> for SqliteArguments<'q> {
     fn len(&self) -> usize {
         self.values.len()
     }
+
+    fn try_clone(&self) -> Option<Self> {
+        Some(self.clone())
+    }
+
+    fn to_cache_key(&self) -> Option<Vec<u8>> {
+        let mut key = Vec::new();
+
+        for value in &self.values {
+            match value {
+                SqliteArgumentValue::Null => key.push(0),
+                SqliteArgumentValue::Text(text) => {
+                    key.push(1);
+                    key.extend_from_slice(&(text.len() as u64).to_le_bytes());
+                    key.extend_from_slice(text.as_bytes());
+                }
+                SqliteArgumentValue::Blob(blob) => {
+                    key.push(2);
+                    key.extend_from_slice(&(blob.len() as u64).to_le_bytes());
+                    key.extend_from_slice(blob);
+                }
+                SqliteArgumentValue::Double(double) => {
+                    key.push(3);
+                    key.extend_from_slice(&double.to_bits().to_le_bytes());
+                }
+                SqliteArgumentValue::Int(int) => {
+                    key.push(4);
+                    key.extend_from_slice(&int.to_le_bytes());
+                }
+                SqliteArgumentValue::Int64(int) => {
+                    key.push(5);
+                    key.extend_from_slice(&int.to_le_bytes());
+                }
+            }
+        }
+
+        Some(key)
+    }
 }
 
 impl SqliteArguments<'_> {
-    pub(super) fn bind(&self, handle: &mut StatementHandle, offset: usize) -> Result<usize, Error> {
+    /// Bind the parameters of one statement, starting at argument `offset` for unnumbered ones.
+    ///
+    /// Returns the number of unnumbered parameters and the highest argument number referenced.
+    pub(super) fn bind(
+        &self,
+        handle: &mut StatementHandle,
+        offset: usize,
+    ) -> Result<(usize, usize), Error> {
         let mut arg_i = offset;
+        let mut highest = 0;
         // for handle in &statement.handles {
 
         let cnt = handle.bind_parameter_count();
@@ -103,20 +149,23 @@ impl SqliteArguments<'_> {
                 arg_i
             };
 
+            highest = std::cmp::max(highest, n);
+
             if n > self.values.len() {
-                // SQLite treats unbound variables as NULL
-                // we reproduce this here
-                // If you are reading this and think this should be an error, open an issue and we can
-                // discuss configuring this somehow
-                // Note that the query macros have a different way of enforcing
-                // argument arity
-                break;
+                // SQLite treats unbound variables as NULL, and we used to reproduce this here.
+                // That hides a missing `.bind()`, so like the other drivers this is now an error;
+                // bind `Option::<T>::None` for NULL. Note that the query macros have a different
+                // way of enforcing argument arity.
+                return Err(Error::BindCountMismatch {
+                    expected: n,
+                    provided: self.values.len(),
+                });
             }
 
             self.values[n - 1].bind(handle, param_i)?;
         }
 
-        Ok(arg_i - offset)
+        Ok((arg_i - offset, highest))
     }
 }
 
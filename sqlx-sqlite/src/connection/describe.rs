@@ -12,7 +12,8 @@ pub(crate) fn describe(conn: &mut ConnectionState, query: &str) -> Result<Descri
     // describing a statement from SQLite can be involved
     // each SQLx statement is comprised of multiple SQL statements
 
-    let mut statement = VirtualStatement::new(query, false)?;
+    let mut statement =
+        VirtualStatement::new(query, false, conn.statements.duplicate_column_names)?;
 
     let mut columns = Vec::new();
     let mut nullable = Vec::new();
@@ -1,7 +1,9 @@
+use crate::column::DuplicateColumnNames;
 use crate::connection::handle::ConnectionHandle;
 use crate::connection::LogSettings;
 use crate::connection::{ConnectionState, Statements};
 use crate::error::Error;
+use crate::options::BusyRetry;
 use crate::{SqliteConnectOptions, SqliteError};
 use libsqlite3_sys::{
     sqlite3, sqlite3_busy_timeout, sqlite3_db_config, sqlite3_extended_result_codes, sqlite3_free,
@@ -45,7 +47,9 @@ pub struct EstablishParams {
     filename: CString,
     open_flags: i32,
     busy_timeout: Duration,
+    busy_retry: Option<BusyRetry>,
     statement_cache_capacity: usize,
+    duplicate_column_names: DuplicateColumnNames,
     log_settings: LogSettings,
     extensions: IndexMap<CString, Option<CString>>,
     pub(crate) thread_name: String,
@@ -150,12 +154,17 @@ impl EstablishParams {
 
         let thread_id = THREAD_ID.fetch_add(1, Ordering::AcqRel);
 
+        let mut log_settings = options.log_settings.clone();
+        log_settings.assign_connection_id();
+
         Ok(Self {
             filename,
             open_flags: flags,
             busy_timeout: options.busy_timeout,
+            busy_retry: options.busy_retry,
             statement_cache_capacity: options.statement_cache_capacity,
-            log_settings: options.log_settings.clone(),
+            duplicate_column_names: options.duplicate_column_names,
+            log_settings,
             extensions,
             thread_name: (options.thread_name)(thread_id as u64),
             command_channel_size: options.command_channel_size,
@@ -291,9 +300,10 @@ impl EstablishParams {
 
         Ok(ConnectionState {
             handle,
-            statements: Statements::new(self.statement_cache_capacity),
+            statements: Statements::new(self.statement_cache_capacity, self.duplicate_column_names),
             transaction_depth: 0,
             log_settings: self.log_settings.clone(),
+            busy_retry: self.busy_retry,
             progress_handler_callback: None,
             update_hook_callback: None,
             commit_hook_callback: None,
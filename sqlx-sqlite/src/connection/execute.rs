@@ -1,6 +1,7 @@
 use crate::connection::{ConnectionHandle, ConnectionState};
 use crate::error::Error;
 use crate::logger::QueryLogger;
+use crate::options::BusyRetry;
 use crate::statement::{StatementHandle, VirtualStatement};
 use crate::{SqliteArguments, SqliteQueryResult, SqliteRow};
 use sqlx_core::Either;
@@ -15,7 +16,15 @@ pub struct ExecuteIter<'a> {
     /// this keeps track of the number of arguments so far
     args_used: usize,
 
+    /// the highest argument number referenced by the statements so far
+    args_referenced: usize,
+
     goto_next: bool,
+
+    /// whether the current statement returned any rows
+    returned_rows: bool,
+
+    busy_retry: Option<BusyRetry>,
 }
 
 pub(crate) fn iter<'a>(
@@ -25,9 +34,15 @@ pub(crate) fn iter<'a>(
     persistent: bool,
 ) -> Result<ExecuteIter<'a>, Error> {
     // fetch the cached statement or allocate a new one
-    let statement = conn.statements.get(query, persistent)?;
+    let statement = conn
+        .statements
+        .get(query, persistent, &conn.log_settings)?;
 
-    let logger = QueryLogger::new(query, conn.log_settings.clone());
+    let mut logger = QueryLogger::new(query, conn.log_settings.clone());
+
+    if let Some(args) = &args {
+        logger.log_parameters(&args.values);
+    }
 
     Ok(ExecuteIter {
         handle: &mut conn.handle,
@@ -35,7 +50,10 @@ pub(crate) fn iter<'a>(
         logger,
         args,
         args_used: 0,
+        args_referenced: 0,
         goto_next: true,
+        returned_rows: false,
+        busy_retry: conn.busy_retry,
     })
 }
 
@@ -43,17 +61,31 @@ fn bind(
     statement: &mut StatementHandle,
     arguments: &Option<SqliteArguments<'_>>,
     offset: usize,
-) -> Result<usize, Error> {
-    let mut n = 0;
-
-    if let Some(arguments) = arguments {
-        n = arguments.bind(statement, offset)?;
+) -> Result<(usize, usize), Error> {
+    match arguments {
+        Some(arguments) => arguments.bind(statement, offset),
+        None => Ok((0, 0)),
     }
-
-    Ok(n)
 }
 
 impl ExecuteIter<'_> {
+    /// Check that no arguments are left over once the last statement has been bound.
+    fn check_unused_args(&mut self) -> Result<(), Error> {
+        let provided = self.args.as_ref().map_or(0, |args| args.values.len());
+
+        if self.args_referenced < provided {
+            // Don't report this again.
+            self.args = None;
+
+            return Err(Error::BindCountMismatch {
+                expected: self.args_referenced,
+                provided,
+            });
+        }
+
+        Ok(())
+    }
+
     pub fn finish(&mut self) -> Result<(), Error> {
         for res in self {
             let _ = res?;
@@ -70,11 +102,12 @@ impl Iterator for ExecuteIter<'_> {
         let statement = if self.goto_next {
             let statement = match self.statement.prepare_next(self.handle) {
                 Ok(Some(statement)) => statement,
-                Ok(None) => return None,
+                Ok(None) => return self.check_unused_args().err().map(Err),
                 Err(e) => return Some(Err(e)),
             };
 
             self.goto_next = false;
+            self.returned_rows = false;
 
             // sanity check: ensure the VM is reset and the bindings are cleared
             if let Err(e) = statement.handle.reset() {
@@ -84,18 +117,59 @@ impl Iterator for ExecuteIter<'_> {
             statement.handle.clear_bindings();
 
             match bind(statement.handle, &self.args, self.args_used) {
-                Ok(args_used) => self.args_used += args_used,
+                Ok((args_used, referenced)) => {
+                    self.args_used += args_used;
+                    self.args_referenced = std::cmp::max(self.args_referenced, referenced);
+                }
                 Err(e) => return Some(Err(e)),
             }
 
-            statement
+            // If this is the last statement, we can report unused arguments before executing it.
+            if self.statement.is_last() {
+                if let Err(e) = self.check_unused_args() {
+                    self.goto_next = true;
+                    return Some(Err(e));
+                }
+            }
+
+            match self.statement.current() {
+                Some(statement) => statement,
+                None => return None,
+            }
         } else {
             self.statement.current()?
         };
 
-        match statement.handle.step() {
+        let mut attempt = 1;
+
+        let step = loop {
+            let error = match statement.handle.step() {
+                Err(error) if error.is_busy_or_locked() => error,
+                step => break step,
+            };
+
+            // Outside of a transaction, a statement has no effect until it completes, so
+            // it can be started over as long as it didn't return any rows yet.
+            let delay = self
+                .busy_retry
+                .filter(|_| !self.returned_rows && !self.handle.in_transaction())
+                .and_then(|retry| retry.delay(attempt + 1));
+
+            let Some(delay) = delay else {
+                break Err(error);
+            };
+
+            // keeps the bindings
+            statement.handle.reset().ok();
+            std::thread::sleep(delay);
+
+            attempt += 1;
+        };
+
+        match step {
             Ok(true) => {
                 self.logger.increment_rows_returned();
+                self.returned_rows = true;
 
                 Some(Ok(Either::Right(SqliteRow::current(
                     statement.handle,
@@ -6,6 +6,8 @@ use std::os::raw::{c_char, c_int, c_void};
 use std::panic::catch_unwind;
 use std::ptr;
 use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU64, AtomicUsize};
+use std::sync::Arc;
 
 use futures_core::future::BoxFuture;
 use futures_intrusive::sync::MutexGuard;
@@ -16,6 +18,7 @@ use libsqlite3_sys::{
 };
 
 pub(crate) use handle::ConnectionHandle;
+use sqlx_core::column::DuplicateColumnNames;
 use sqlx_core::common::StatementCache;
 pub(crate) use sqlx_core::connection::*;
 use sqlx_core::error::Error;
@@ -24,7 +27,7 @@ use sqlx_core::transaction::Transaction;
 
 use crate::connection::establish::EstablishParams;
 use crate::connection::worker::ConnectionWorker;
-use crate::options::OptimizeOnClose;
+use crate::options::{BusyRetry, OptimizeOnClose};
 use crate::statement::VirtualStatement;
 use crate::{Sqlite, SqliteConnectOptions};
 
@@ -105,7 +108,9 @@ pub(crate) struct ConnectionState {
 
     pub(crate) statements: Statements,
 
-    log_settings: LogSettings,
+    pub(crate) log_settings: LogSettings,
+
+    pub(crate) busy_retry: Option<BusyRetry>,
 
     /// Stores the progress handler set on the current connection. If the handler returns `false`,
     /// the query is interrupted.
@@ -162,6 +167,20 @@ pub(crate) struct Statements {
     cached: StatementCache<VirtualStatement>,
     // most recent non-persistent statement
     temp: Option<VirtualStatement>,
+    duplicate_column_names: DuplicateColumnNames,
+    // shared with `WorkerSharedState` so the counts are visible as soon as a statement is
+    // checked out, not only after the results have been fully consumed
+    pub(crate) stats: Arc<StatementStats>,
+}
+
+/// Number of statements cached, and of statements prepared, statement cache hits and
+/// statements executed over the life of the connection.
+#[derive(Default)]
+pub(crate) struct StatementStats {
+    pub(crate) cached: AtomicUsize,
+    pub(crate) prepared: AtomicU64,
+    pub(crate) hits: AtomicU64,
+    pub(crate) executed: AtomicU64,
 }
 
 impl SqliteConnection {
@@ -200,6 +219,10 @@ impl Connection for SqliteConnection {
 
     type Options = SqliteConnectOptions;
 
+    fn id(&self) -> u64 {
+        self.worker.shared.id
+    }
+
     fn close(mut self) -> BoxFuture<'static, Result<(), Error>> {
         Box::pin(async move {
             if let OptimizeOnClose::Enabled { analysis_limit } = self.optimize_on_close {
@@ -231,6 +254,36 @@ impl Connection for SqliteConnection {
         Box::pin(self.worker.ping())
     }
 
+    fn reset(&mut self) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(self.worker.reset())
+    }
+
+    fn incremental_vacuum(&mut self, pages: u32) -> BoxFuture<'_, Result<(), Error>> {
+        Box::pin(async move {
+            // the pages are only freed once the statement has been stepped until it is done,
+            // which `execute()` does
+            let sql = format!("PRAGMA incremental_vacuum({pages})");
+            self.execute(&*sql).await?;
+
+            Ok(())
+        })
+    }
+
+    #[doc(hidden)]
+    fn server_now_micros(&mut self) -> BoxFuture<'_, Result<i64, Error>> {
+        Box::pin(async move {
+            // `'now'` is the same for every call within a single step, and `%f` is `SS.SSS`
+            let row = self
+                .fetch_one(
+                    "SELECT CAST(strftime('%s', 'now') AS INTEGER) * 1000000 \
+                         + CAST(substr(strftime('%f', 'now'), 4) AS INTEGER) * 1000",
+                )
+                .await?;
+
+            sqlx_core::row::Row::try_get(&row, 0)
+        })
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -241,7 +294,32 @@ impl Connection for SqliteConnection {
     fn cached_statements_size(&self) -> usize {
         self.worker
             .shared
-            .cached_statements_size
+            .statement_stats
+            .cached
+            .load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    fn queries_executed(&self) -> u64 {
+        self.worker
+            .shared
+            .statement_stats
+            .executed
+            .load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    fn statements_prepared(&self) -> u64 {
+        self.worker
+            .shared
+            .statement_stats
+            .prepared
+            .load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    fn statement_cache_hits(&self) -> u64 {
+        self.worker
+            .shared
+            .statement_stats
+            .hits
             .load(std::sync::atomic::Ordering::Acquire)
     }
 
@@ -270,6 +348,10 @@ impl Connection for SqliteConnection {
     fn should_flush(&self) -> bool {
         false
     }
+
+    fn set_require_transaction_for_writes(&mut self, require: bool) {
+        self.worker.set_require_transaction_for_writes(require);
+    }
 }
 
 /// Implements a C binding to a progress callback. The function returns `0` if the
@@ -358,7 +440,8 @@ impl LockedSqliteHandle<'_> {
 
     /// Apply a collation to the open database.
     ///
-    /// See [`SqliteConnectOptions::collation()`] for details.
+    /// This only affects this connection; use [`SqliteConnectOptions::collation()`] to have it
+    /// on every connection of a pool. See there for details.
     pub fn create_collation(
         &mut self,
         name: &str,
@@ -506,23 +589,50 @@ impl Drop for ConnectionState {
 }
 
 impl Statements {
-    fn new(capacity: usize) -> Self {
+    fn new(capacity: usize, duplicate_column_names: DuplicateColumnNames) -> Self {
         Statements {
             cached: StatementCache::new(capacity),
             temp: None,
+            duplicate_column_names,
+            stats: Arc::default(),
         }
     }
 
-    fn get(&mut self, query: &str, persistent: bool) -> Result<&mut VirtualStatement, Error> {
+    fn get(
+        &mut self,
+        query: &str,
+        persistent: bool,
+        log_settings: &LogSettings,
+    ) -> Result<&mut VirtualStatement, Error> {
+        // see `ConnectOptions::sql_rewriter()`; statements are still cached under `query`
+        let sql = || log_settings.rewrite_sql(query);
+
         if !persistent || !self.cached.is_enabled() {
-            return Ok(self.temp.insert(VirtualStatement::new(query, false)?));
+            self.stats
+                .prepared
+                .fetch_add(1, std::sync::atomic::Ordering::Release);
+            return Ok(self.temp.insert(VirtualStatement::new(
+                &sql(),
+                false,
+                self.duplicate_column_names,
+            )?));
         }
 
         let exists = self.cached.contains_key(query);
 
-        if !exists {
-            let statement = VirtualStatement::new(query, true)?;
+        if exists {
+            self.stats
+                .hits
+                .fetch_add(1, std::sync::atomic::Ordering::Release);
+        } else {
+            self.stats
+                .prepared
+                .fetch_add(1, std::sync::atomic::Ordering::Release);
+            let statement = VirtualStatement::new(&sql(), true, self.duplicate_column_names)?;
             self.cached.insert(query, statement);
+            self.stats
+                .cached
+                .store(self.cached.len(), std::sync::atomic::Ordering::Release);
         }
 
         let statement = self.cached.get_mut(query).unwrap();
@@ -535,12 +645,11 @@ impl Statements {
         Ok(statement)
     }
 
-    fn len(&self) -> usize {
-        self.cached.len()
-    }
-
     fn clear(&mut self) {
         self.cached.clear();
         self.temp = None;
+        self.stats
+            .cached
+            .store(0, std::sync::atomic::Ordering::Release);
     }
 }
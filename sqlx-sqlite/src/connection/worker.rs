@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 use std::future::Future;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
 
@@ -11,14 +11,15 @@ use tracing::span::Span;
 use sqlx_core::describe::Describe;
 use sqlx_core::error::Error;
 use sqlx_core::transaction::{
-    begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
+    begin_ansi_transaction_sql, commit_ansi_transaction_sql, is_write_statement,
+    rollback_ansi_transaction_sql,
 };
 use sqlx_core::Either;
 
 use crate::connection::describe::describe;
 use crate::connection::establish::EstablishParams;
 use crate::connection::execute;
-use crate::connection::ConnectionState;
+use crate::connection::{ConnectionState, StatementStats};
 use crate::{Sqlite, SqliteArguments, SqliteQueryResult, SqliteRow, SqliteStatement};
 
 // Each SQLite connection has a dedicated thread.
@@ -34,7 +35,10 @@ pub(crate) struct ConnectionWorker {
 }
 
 pub(crate) struct WorkerSharedState {
-    pub(crate) cached_statements_size: AtomicUsize,
+    pub(crate) id: u64,
+    pub(crate) statement_stats: Arc<StatementStats>,
+    // see `PoolOptions::require_transaction_for_writes`
+    pub(crate) require_transaction_for_writes: AtomicBool,
     pub(crate) conn: Mutex<ConnectionState>,
 }
 
@@ -67,6 +71,9 @@ enum Command {
     ClearCache {
         tx: oneshot::Sender<()>,
     },
+    Reset {
+        tx: oneshot::Sender<Result<(), Error>>,
+    },
     Ping {
         tx: oneshot::Sender<()>,
     },
@@ -93,7 +100,9 @@ impl ConnectionWorker {
                 };
 
                 let shared = Arc::new(WorkerSharedState {
-                    cached_statements_size: AtomicUsize::new(0),
+                    id: conn.log_settings.connection_id,
+                    statement_stats: conn.statements.stats.clone(),
+                    require_transaction_for_writes: AtomicBool::new(false),
                     // note: must be fair because in `Command::UnlockDb` we unlock the mutex
                     // and then immediately try to relock it; an unfair mutex would immediately
                     // grant us the lock even if another task is waiting.
@@ -120,14 +129,7 @@ impl ConnectionWorker {
                     let _guard = span.enter();
                     match cmd {
                         Command::Prepare { query, tx } => {
-                            tx.send(prepare(&mut conn, &query).map(|prepared| {
-                                update_cached_statements_size(
-                                    &conn,
-                                    &shared.cached_statements_size,
-                                );
-                                prepared
-                            }))
-                            .ok();
+                            tx.send(prepare(&mut conn, &query)).ok();
                         }
                         Command::Describe { query, tx } => {
                             tx.send(describe(&mut conn, &query)).ok();
@@ -139,6 +141,14 @@ impl ConnectionWorker {
                             tx,
                             limit
                         } => {
+                            if shared.require_transaction_for_writes.load(Ordering::Acquire)
+                                && !conn.handle.in_transaction()
+                                && is_write_statement(&query)
+                            {
+                                tx.send(Err(Error::TransactionRequired)).ok();
+                                continue;
+                            }
+
                             let iter = match execute::iter(&mut conn, &query, arguments, persistent)
                             {
                                 Ok(iter) => iter,
@@ -177,8 +187,6 @@ impl ConnectionWorker {
                                     }
                                 },
                             }
-
-                            update_cached_statements_size(&conn, &shared.cached_statements_size);
                         }
                         Command::Begin { tx } => {
                             let depth = conn.transaction_depth;
@@ -263,9 +271,25 @@ impl ConnectionWorker {
                         }
                         Command::ClearCache { tx } => {
                             conn.statements.clear();
-                            update_cached_statements_size(&conn, &shared.cached_statements_size);
                             tx.send(()).ok();
                         }
+                        Command::Reset { tx } => {
+                            // Finalize the statements first, as one still in progress
+                            // would make the `ROLLBACK` fail.
+                            conn.statements.clear();
+
+                            let res = if conn.handle.in_transaction() {
+                                conn.handle.exec("ROLLBACK")
+                            } else {
+                                Ok(())
+                            };
+
+                            if res.is_ok() {
+                                conn.transaction_depth = 0;
+                            }
+
+                            tx.send(res).ok();
+                        }
                         Command::UnlockDb => {
                             drop(conn);
                             conn = futures_executor::block_on(shared.conn.lock());
@@ -296,6 +320,12 @@ impl ConnectionWorker {
         .await?
     }
 
+    pub(crate) fn set_require_transaction_for_writes(&mut self, require: bool) {
+        self.shared
+            .require_transaction_for_writes
+            .store(require, Ordering::Release);
+    }
+
     pub(crate) async fn describe(&mut self, query: &str) -> Result<Describe<Sqlite>, Error> {
         self.oneshot_cmd(|tx| Command::Describe {
             query: query.into(),
@@ -314,6 +344,12 @@ impl ConnectionWorker {
     ) -> Result<flume::Receiver<Result<Either<SqliteQueryResult, SqliteRow>, Error>>, Error> {
         let (tx, rx) = flume::bounded(chan_size);
 
+        // see `Connection::queries_executed()`
+        self.shared
+            .statement_stats
+            .executed
+            .fetch_add(1, std::sync::atomic::Ordering::Release);
+
         self.command_tx
             .send_async((
                 Command::Execute {
@@ -388,6 +424,10 @@ impl ConnectionWorker {
         self.oneshot_cmd(|tx| Command::ClearCache { tx }).await
     }
 
+    pub(crate) async fn reset(&mut self) -> Result<(), Error> {
+        self.oneshot_cmd(|tx| Command::Reset { tx }).await?
+    }
+
     pub(crate) async fn unlock_db(&mut self) -> Result<MutexGuard<'_, ConnectionState>, Error> {
         let (guard, res) = futures_util::future::join(
             // we need to join the wait queue for the lock before we send the message
@@ -424,7 +464,7 @@ impl ConnectionWorker {
 
 fn prepare(conn: &mut ConnectionState, query: &str) -> Result<SqliteStatement<'static>, Error> {
     // prepare statement object (or checkout from cache)
-    let statement = conn.statements.get(query, true)?;
+    let statement = conn.statements.get(query, true, &conn.log_settings)?;
 
     let mut parameters = 0;
     let mut columns = None;
@@ -448,10 +488,6 @@ fn prepare(conn: &mut ConnectionState, query: &str) -> Result<SqliteStatement<'s
     })
 }
 
-fn update_cached_statements_size(conn: &ConnectionState, size: &AtomicUsize) {
-    size.store(conn.statements.len(), Ordering::Release);
-}
-
 // A oneshot channel where send completes only after the receiver receives the value.
 mod rendezvous_oneshot {
     use super::oneshot::{self, Canceled};
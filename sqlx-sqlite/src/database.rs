@@ -1,4 +1,4 @@
-pub(crate) use sqlx_core::database::{Database, HasStatementCache};
+pub(crate) use sqlx_core::database::{Database, HasCovariantLifetimes, HasStatementCache};
 
 use crate::{
     SqliteArgumentValue, SqliteArguments, SqliteColumn, SqliteConnection, SqliteQueryResult,
@@ -37,3 +37,13 @@ impl Database for Sqlite {
 }
 
 impl HasStatementCache for Sqlite {}
+
+impl HasCovariantLifetimes for Sqlite {
+    fn shorten_arguments<'a, 'b: 'a>(arguments: Self::Arguments<'b>) -> Self::Arguments<'a> {
+        arguments
+    }
+
+    fn shorten_statement<'a, 'b: 'a>(statement: Self::Statement<'b>) -> Self::Statement<'a> {
+        statement
+    }
+}
@@ -5,9 +5,9 @@ use std::os::raw::c_int;
 use std::{borrow::Cow, str::from_utf8_unchecked};
 
 use libsqlite3_sys::{
-    sqlite3, sqlite3_errmsg, sqlite3_extended_errcode, SQLITE_CONSTRAINT_CHECK,
+    sqlite3, sqlite3_errmsg, sqlite3_extended_errcode, SQLITE_BUSY, SQLITE_CONSTRAINT_CHECK,
     SQLITE_CONSTRAINT_FOREIGNKEY, SQLITE_CONSTRAINT_NOTNULL, SQLITE_CONSTRAINT_PRIMARYKEY,
-    SQLITE_CONSTRAINT_UNIQUE,
+    SQLITE_CONSTRAINT_UNIQUE, SQLITE_LOCKED,
 };
 
 pub(crate) use sqlx_core::error::*;
@@ -46,6 +46,12 @@ impl SqliteError {
         err.message = unsafe { from_utf8_unchecked(error_msg.to_bytes()).to_owned() };
         err
     }
+
+    /// Returns `true` for `SQLITE_BUSY`, `SQLITE_LOCKED` and their extended result codes.
+    pub(crate) fn is_busy_or_locked(&self) -> bool {
+        // the primary result code is the least significant byte
+        matches!(self.code & 0xff, SQLITE_BUSY | SQLITE_LOCKED)
+    }
 }
 
 impl Display for SqliteError {
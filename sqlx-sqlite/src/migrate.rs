@@ -7,6 +7,7 @@ use crate::migrate::{AppliedMigration, Migration};
 use crate::migrate::{Migrate, MigrateDatabase};
 use crate::query::query;
 use crate::query_as::query_as;
+use crate::query_scalar::query_scalar;
 use crate::{Sqlite, SqliteConnectOptions, SqliteConnection, SqliteJournalMode};
 use futures_core::future::BoxFuture;
 use std::str::FromStr;
@@ -103,16 +104,21 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
     ) -> BoxFuture<'_, Result<Vec<AppliedMigration>, MigrateError>> {
         Box::pin(async move {
             // language=SQLite
-            let rows: Vec<(i64, Vec<u8>)> =
-                query_as("SELECT version, checksum FROM _sqlx_migrations ORDER BY version")
-                    .fetch_all(self)
-                    .await?;
+            let rows: Vec<(i64, Vec<u8>, i64)> = query_as(
+                "SELECT version, checksum, execution_time FROM _sqlx_migrations ORDER BY version",
+            )
+            .fetch_all(self)
+            .await?;
 
             let migrations = rows
                 .into_iter()
-                .map(|(version, checksum)| AppliedMigration {
+                .map(|(version, checksum, execution_time)| AppliedMigration {
                     version,
                     checksum: checksum.into(),
+                    // -1 until the time is recorded
+                    execution_time: u64::try_from(execution_time)
+                        .ok()
+                        .map(Duration::from_nanos),
                 })
                 .collect();
 
@@ -185,6 +191,66 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
         })
     }
 
+    fn skip<'e: 'm, 'm>(
+        &'e mut self,
+        migration: &'m Migration,
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async move {
+            let mut tx = self.begin().await?;
+
+            // SQLite has no `ADD COLUMN IF NOT EXISTS`
+            // language=SQLite
+            let has_column: bool = query_scalar(
+                "SELECT EXISTS( \
+                     SELECT 1 FROM pragma_table_info('_sqlx_migrations') WHERE name = 'skipped' \
+                 )",
+            )
+            .fetch_one(&mut *tx)
+            .await?;
+
+            if !has_column {
+                // language=SQLite
+                let _ = tx
+                    .execute(
+                        "ALTER TABLE _sqlx_migrations \
+                         ADD COLUMN skipped BOOLEAN NOT NULL DEFAULT FALSE",
+                    )
+                    .await?;
+            }
+
+            // language=SQLite
+            let _ = query(
+                r#"
+    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time, skipped )
+    VALUES ( ?1, ?2, TRUE, ?3, 0, TRUE )
+                "#,
+            )
+            .bind(migration.version)
+            .bind(&*migration.description)
+            .bind(&*migration.checksum)
+            .execute(&mut *tx)
+            .await?;
+
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    fn analyze<'e: 'm, 'm>(
+        &'e mut self,
+        tables: &'m [String],
+    ) -> BoxFuture<'m, Result<(), MigrateError>> {
+        Box::pin(async move {
+            for table in tables {
+                // language=SQL
+                let _ = self.execute(&*format!("ANALYZE {table}")).await?;
+            }
+
+            Ok(())
+        })
+    }
+
     fn revert<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
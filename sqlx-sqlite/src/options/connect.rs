@@ -1,7 +1,7 @@
 use crate::{SqliteConnectOptions, SqliteConnection};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
-use sqlx_core::connection::ConnectOptions;
+use sqlx_core::connection::{ConnectOptions, SlowQueryHook};
 use sqlx_core::error::Error;
 use sqlx_core::executor::Executor;
 use std::fmt::Write;
@@ -46,6 +46,10 @@ impl ConnectOptions for SqliteConnectOptions {
                 }
             }
 
+            if let Some(sql) = &self.warmup_query {
+                conn.execute(&**sql).await?;
+            }
+
             Ok(conn)
         })
     }
@@ -59,6 +63,52 @@ impl ConnectOptions for SqliteConnectOptions {
         self.log_settings.log_slow_statements(level, duration);
         self
     }
+
+    fn redact_parameters(
+        mut self,
+        f: impl Fn(usize, &dyn std::fmt::Debug) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings.redact_parameters(f);
+        self
+    }
+
+    fn redact_all_parameters(mut self) -> Self {
+        self.log_settings.redact_all_parameters();
+        self
+    }
+
+    fn on_slow_query(
+        mut self,
+        threshold: Duration,
+        f: impl Fn(&str, Duration) + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings
+            .on_slow_query(SlowQueryHook::new(threshold, f));
+        self
+    }
+
+    fn pool_name(self, pool_name: &str) -> Self {
+        tracing::warn!(pool_name, "SQLite connections can't be labeled with a pool name");
+        self
+    }
+
+    fn connection_id(mut self, f: impl Fn() -> u64 + Send + Sync + 'static) -> Self {
+        self.log_settings.connection_id(f);
+        self
+    }
+
+    fn sql_rewriter(
+        mut self,
+        f: impl for<'a> Fn(&'a str) -> std::borrow::Cow<'a, str> + Send + Sync + 'static,
+    ) -> Self {
+        self.log_settings.sql_rewriter(f);
+        self
+    }
+
+    fn dev_auto_limit(mut self, limit: Option<usize>) -> Self {
+        self.log_settings.dev_auto_limit(limit);
+        self
+    }
 }
 
 impl SqliteConnectOptions {
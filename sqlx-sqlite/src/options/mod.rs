@@ -7,6 +7,7 @@ mod locking_mode;
 mod parse;
 mod synchronous;
 
+use crate::column::DuplicateColumnNames;
 use crate::connection::LogSettings;
 pub use auto_vacuum::SqliteAutoVacuum;
 pub use journal_mode::SqliteJournalMode;
@@ -66,7 +67,9 @@ pub struct SqliteConnectOptions {
     pub(crate) create_if_missing: bool,
     pub(crate) shared_cache: bool,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) duplicate_column_names: DuplicateColumnNames,
     pub(crate) busy_timeout: Duration,
+    pub(crate) busy_retry: Option<BusyRetry>,
     pub(crate) log_settings: LogSettings,
     pub(crate) immutable: bool,
     pub(crate) vfs: Option<Cow<'static, str>>,
@@ -90,6 +93,27 @@ pub struct SqliteConnectOptions {
 
     #[cfg(feature = "regexp")]
     pub(crate) register_regexp_function: bool,
+
+    pub(crate) warmup_query: Option<String>,
+}
+
+/// The settings of [`SqliteConnectOptions::busy_retry()`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BusyRetry {
+    pub(crate) max_attempts: u32,
+    pub(crate) backoff: Duration,
+}
+
+impl BusyRetry {
+    /// The delay before attempt number `attempt`, or `None` if no attempts are left.
+    pub(crate) fn delay(&self, attempt: u32) -> Option<Duration> {
+        if attempt > self.max_attempts {
+            return None;
+        }
+
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(2));
+        Some(self.backoff.saturating_mul(factor))
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -198,7 +222,9 @@ impl SqliteConnectOptions {
             create_if_missing: false,
             shared_cache: false,
             statement_cache_capacity: 100,
+            duplicate_column_names: DuplicateColumnNames::default(),
             busy_timeout: Duration::from_secs(5),
+            busy_retry: None,
             log_settings: Default::default(),
             immutable: false,
             vfs: None,
@@ -212,6 +238,7 @@ impl SqliteConnectOptions {
             optimize_on_close: OptimizeOnClose::Disabled,
             #[cfg(feature = "regexp")]
             register_regexp_function: false,
+            warmup_query: None,
         }
     }
 
@@ -313,6 +340,16 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Sets how result columns that share a name are handled when looking them up by name,
+    /// e.g. the `id` columns of two joined tables.
+    ///
+    /// See [`DuplicateColumnNames`] for the available policies.
+    /// Defaults to [`DuplicateColumnNames::Last`].
+    pub fn duplicate_column_names(mut self, policy: DuplicateColumnNames) -> Self {
+        self.duplicate_column_names = policy;
+        self
+    }
+
     /// Sets a timeout value to wait when the database is locked, before
     /// returning a busy timeout error.
     ///
@@ -322,6 +359,28 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Retries a statement that fails with `SQLITE_BUSY` or `SQLITE_LOCKED`, making at most
+    /// `max_attempts` attempts in total and waiting `backoff` before the first retry, doubling
+    /// for each one after.
+    ///
+    /// Unlike [`busy_timeout()`][Self::busy_timeout], which waits for a lock within a single
+    /// attempt, this starts the statement over, so it also helps where SQLite gives up without
+    /// waiting on the busy timeout, e.g. to avoid a deadlock.
+    ///
+    /// A statement is only retried if no transaction is open, in which case SQLite rolled back its
+    /// effects when it failed, and if it hasn't returned any rows yet. A statement in a
+    /// transaction is never retried, as the transaction may need to be rolled back first;
+    /// see [the SQLite documentation](https://www.sqlite.org/c3ref/step.html) for details.
+    ///
+    /// Values of `0` or `1` for `max_attempts` disable retrying, which is the default.
+    pub fn busy_retry(mut self, max_attempts: u32, backoff: Duration) -> Self {
+        self.busy_retry = (max_attempts > 1).then_some(BusyRetry {
+            max_attempts,
+            backoff,
+        });
+        self
+    }
+
     /// Sets the [synchronous](https://www.sqlite.org/pragma.html#pragma_synchronous) setting for the database connection.
     ///
     /// The default synchronous settings is FULL. However, if durability is not a concern,
@@ -334,8 +393,13 @@ impl SqliteConnectOptions {
     ///
     /// The default auto_vacuum setting is NONE.
     ///
-    /// For existing databases, a change to this value does not take effect unless a
+    /// The mode can only be changed while the database has no tables yet. For existing
+    /// databases, a change to this value does not take effect unless a
     /// [`VACUUM` command](https://www.sqlite.org/lang_vacuum.html) is executed.
+    ///
+    /// [`SqliteAutoVacuum::Incremental`] does not free any pages by itself; run
+    /// `PRAGMA incremental_vacuum` for that, e.g. with
+    /// [`PoolOptions::incremental_vacuum_on_release()`][sqlx_core::pool::PoolOptions::incremental_vacuum_on_release].
     pub fn auto_vacuum(self, auto_vacuum: SqliteAutoVacuum) -> Self {
         self.pragma("auto_vacuum", auto_vacuum.as_str())
     }
@@ -361,10 +425,18 @@ impl SqliteConnectOptions {
         self
     }
 
-    /// Add a custom collation for comparing strings in SQL.
+    /// Add a custom collation for comparing strings in SQL, e.g. `ORDER BY name COLLATE mycoll`.
     ///
     /// If a collation with the same name already exists, it will be replaced.
     ///
+    /// The collation is registered on every connection opened with these options, so every
+    /// connection of a pool has it, including ones that replace a closed connection. To add a
+    /// collation to a single connection, see
+    /// [`LockedSqliteHandle::create_collation()`][crate::LockedSqliteHandle::create_collation].
+    ///
+    /// The strings are passed to `collate` as UTF-8. SQLite does not validate `TEXT` values, so
+    /// any invalid UTF-8 in them is replaced with `U+FFFD REPLACEMENT CHARACTER` first.
+    ///
     /// See [`sqlite3_create_collation()`](https://www.sqlite.org/c3ref/create_collation.html) for details.
     ///
     /// Note this excerpt:
@@ -551,6 +623,25 @@ impl SqliteConnectOptions {
         self
     }
 
+    /// Sets a query to run once on every new connection, after the `PRAGMA`s have been applied
+    /// and collations registered.
+    ///
+    /// This is meant for priming connection-local state, such as caches, that would
+    /// otherwise be populated by the first real query. The warmup query may be a `SELECT`;
+    /// any rows it returns are discarded. An error from the query fails the connection attempt.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_sqlite::SqliteConnectOptions;
+    /// let options = SqliteConnectOptions::new()
+    ///     .warmup_query("SELECT count(*) FROM sqlite_schema");
+    /// ```
+    pub fn warmup_query(mut self, sql: &str) -> Self {
+        self.warmup_query = Some(sql.to_owned());
+        self
+    }
+
     /// Register a regexp function that allows using regular expressions in queries.
     ///
     /// ```
@@ -2,7 +2,7 @@
 
 use std::sync::Arc;
 
-use sqlx_core::column::ColumnIndex;
+use sqlx_core::column::{column_index_by_name, ColumnIndex};
 use sqlx_core::error::Error;
 use sqlx_core::ext::ustr::UStr;
 use sqlx_core::row::Row;
@@ -12,6 +12,7 @@ use crate::statement::StatementHandle;
 use crate::{Sqlite, SqliteColumn, SqliteValue, SqliteValueRef};
 
 /// Implementation of [`Row`] for SQLite.
+#[derive(Clone)]
 pub struct SqliteRow {
     pub(crate) values: Box<[SqliteValue]>,
     pub(crate) columns: Arc<Vec<SqliteColumn>>,
@@ -70,10 +71,7 @@ impl Row for SqliteRow {
 
 impl ColumnIndex<SqliteRow> for &'_ str {
     fn index(&self, row: &SqliteRow) -> Result<usize, Error> {
-        row.column_names
-            .get(*self)
-            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
-            .copied()
+        column_index_by_name(&row.column_names, self)
     }
 }
 
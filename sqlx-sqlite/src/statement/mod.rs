@@ -1,4 +1,4 @@
-use crate::column::ColumnIndex;
+use crate::column::{column_index_by_name, ColumnIndex};
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::{Sqlite, SqliteArguments, SqliteColumn, SqliteTypeInfo};
@@ -53,11 +53,7 @@ impl<'q> Statement<'q> for SqliteStatement<'q> {
 
 impl ColumnIndex<SqliteStatement<'_>> for &'_ str {
     fn index(&self, statement: &SqliteStatement<'_>) -> Result<usize, Error> {
-        statement
-            .column_names
-            .get(*self)
-            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
-            .copied()
+        column_index_by_name(&statement.column_names, self)
     }
 }
 
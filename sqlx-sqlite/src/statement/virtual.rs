@@ -10,6 +10,7 @@ use libsqlite3_sys::{
 };
 
 use sqlx_core::bytes::{Buf, Bytes};
+use sqlx_core::column::DuplicateColumnNames;
 use sqlx_core::error::Error;
 use sqlx_core::ext::ustr::UStr;
 use sqlx_core::{HashMap, SmallVec};
@@ -44,6 +45,8 @@ pub struct VirtualStatement {
 
     // each set of column names
     pub(crate) column_names: SmallVec<[Arc<HashMap<UStr, usize>>; 1]>,
+
+    duplicate_column_names: DuplicateColumnNames,
 }
 
 pub struct PreparedStatement<'a> {
@@ -53,7 +56,11 @@ pub struct PreparedStatement<'a> {
 }
 
 impl VirtualStatement {
-    pub(crate) fn new(mut query: &str, persistent: bool) -> Result<Self, Error> {
+    pub(crate) fn new(
+        mut query: &str,
+        persistent: bool,
+        duplicate_column_names: DuplicateColumnNames,
+    ) -> Result<Self, Error> {
         query = query.trim();
 
         if query.len() > i32::MAX as usize {
@@ -70,6 +77,7 @@ impl VirtualStatement {
             index: None,
             columns: SmallVec::with_capacity(1),
             column_names: SmallVec::with_capacity(1),
+            duplicate_column_names,
         })
     }
 
@@ -92,7 +100,6 @@ impl VirtualStatement {
                 let num = statement.column_count();
 
                 let mut columns = Vec::with_capacity(num);
-                let mut column_names = HashMap::with_capacity(num);
 
                 for i in 0..num {
                     let name: UStr = statement.column_name(i).to_owned().into();
@@ -102,13 +109,15 @@ impl VirtualStatement {
 
                     columns.push(SqliteColumn {
                         ordinal: i,
-                        name: name.clone(),
+                        name,
                         type_info,
                     });
-
-                    column_names.insert(name, i);
                 }
 
+                let column_names = self
+                    .duplicate_column_names
+                    .column_names(columns.iter_mut().map(|column| &mut column.name));
+
                 self.handles.push(statement);
                 self.columns.push(Arc::new(columns));
                 self.column_names.push(Arc::new(column_names));
@@ -128,6 +137,12 @@ impl VirtualStatement {
             })
     }
 
+    /// Returns `true` if the current statement is the last one with any SQL left to execute.
+    pub fn is_last(&self) -> bool {
+        self.index.is_some_and(|idx| idx + 1 == self.handles.len())
+            && self.tail.iter().all(u8::is_ascii_whitespace)
+    }
+
     pub fn reset(&mut self) -> Result<(), Error> {
         self.index = None;
 
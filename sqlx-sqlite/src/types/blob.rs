@@ -0,0 +1,180 @@
+use std::borrow::Cow;
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::type_info::DataType;
+use crate::types::Type;
+use crate::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+/// Map a value to/from a SQLite `BLOB`, serialized with the codec `C`.
+///
+/// This is the binary counterpart of [`Json<T>`](sqlx_core::types::Json): the value is
+/// encoded to bytes by [`C::encode()`](SqliteBlobCodec::encode) when bound, and decoded by
+/// [`C::decode()`](SqliteBlobCodec::decode) when read. SQLx does not provide any codecs,
+/// so that it is not tied to a particular serialization crate; implementing one for
+/// `bincode`, `postcard` or similar is a few lines, see below.
+///
+/// Only `BLOB` values can be decoded; a value of any other storage class is a type mismatch.
+///
+/// ### Example
+///
+/// ```rust,ignore
+/// use serde::{de::DeserializeOwned, Deserialize, Serialize};
+/// use sqlx::error::BoxDynError;
+/// use sqlx::sqlite::types::{SqliteBlob, SqliteBlobCodec};
+///
+/// struct Bincode;
+///
+/// impl<T: Serialize + DeserializeOwned> SqliteBlobCodec<T> for Bincode {
+///     fn encode(value: &T) -> Result<Vec<u8>, BoxDynError> {
+///         Ok(bincode::serialize(value)?)
+///     }
+///
+///     fn decode(blob: &[u8]) -> Result<T, BoxDynError> {
+///         Ok(bincode::deserialize(blob)?)
+///     }
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Entry {
+///     key: String,
+///     hits: u64,
+/// }
+///
+/// // CREATE TABLE cache(id INTEGER PRIMARY KEY, entry BLOB NOT NULL);
+/// sqlx::query("INSERT INTO cache(entry) VALUES (?)")
+///     .bind(SqliteBlob::<_, Bincode>::new(Entry { key: "a".into(), hits: 1 }))
+///     .execute(&mut conn)
+///     .await?;
+///
+/// let entry: SqliteBlob<Entry, Bincode> = sqlx::query_scalar("SELECT entry FROM cache")
+///     .fetch_one(&mut conn)
+///     .await?;
+/// assert_eq!(entry.hits, 1);
+///
+/// // with the query macros, the type has to be given as an override
+/// let row = sqlx::query!(r#"SELECT entry AS "entry: SqliteBlob<Entry, Bincode>" FROM cache"#)
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+pub struct SqliteBlob<T, C> {
+    value: T,
+    codec: PhantomData<fn() -> C>,
+}
+
+/// How a [`SqliteBlob<T, C>`] serializes its value.
+pub trait SqliteBlobCodec<T> {
+    /// Serialize `value` to the bytes stored in the `BLOB`.
+    fn encode(value: &T) -> Result<Vec<u8>, BoxDynError>;
+
+    /// Deserialize a value from the bytes stored in the `BLOB`.
+    fn decode(blob: &[u8]) -> Result<T, BoxDynError>;
+}
+
+impl<T, C> SqliteBlob<T, C> {
+    /// Wrap `value` to be stored with the codec `C`.
+    pub fn new(value: T) -> Self {
+        SqliteBlob {
+            value,
+            codec: PhantomData,
+        }
+    }
+
+    /// Unwrap the value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T, C> From<T> for SqliteBlob<T, C> {
+    fn from(value: T) -> Self {
+        SqliteBlob::new(value)
+    }
+}
+
+impl<T, C> Deref for SqliteBlob<T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, C> DerefMut for SqliteBlob<T, C> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T, C> AsRef<T> for SqliteBlob<T, C> {
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, C> AsMut<T> for SqliteBlob<T, C> {
+    fn as_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+// not derived, as that would require `C` to implement these traits too
+
+impl<T: Debug, C> Debug for SqliteBlob<T, C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SqliteBlob").field(&self.value).finish()
+    }
+}
+
+impl<T: Clone, C> Clone for SqliteBlob<T, C> {
+    fn clone(&self) -> Self {
+        SqliteBlob::new(self.value.clone())
+    }
+}
+
+impl<T: PartialEq, C> PartialEq for SqliteBlob<T, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq, C> Eq for SqliteBlob<T, C> {}
+
+impl<T, C> Type<Sqlite> for SqliteBlob<T, C> {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Blob)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(ty.0, DataType::Blob)
+    }
+}
+
+impl<T, C> Encode<'_, Sqlite> for SqliteBlob<T, C>
+where
+    C: SqliteBlobCodec<T>,
+{
+    fn encode_by_ref(
+        &self,
+        args: &mut Vec<SqliteArgumentValue<'_>>,
+    ) -> Result<IsNull, BoxDynError> {
+        args.push(SqliteArgumentValue::Blob(Cow::Owned(C::encode(
+            &self.value,
+        )?)));
+
+        Ok(IsNull::No)
+    }
+}
+
+impl<'r, T, C> Decode<'r, Sqlite> for SqliteBlob<T, C>
+where
+    C: SqliteBlobCodec<T>,
+{
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        C::decode(value.blob()).map(SqliteBlob::new)
+    }
+}
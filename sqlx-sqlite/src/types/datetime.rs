@@ -0,0 +1,295 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::type_info::TypeInfo;
+use crate::value::ValueRef;
+use crate::{
+    decode::Decode,
+    encode::{Encode, IsNull},
+    error::BoxDynError,
+    type_info::DataType,
+    types::Type,
+    Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef,
+};
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+const UNIX_EPOCH_IN_JULIAN_DAYS: f64 = 2_440_587.5;
+
+/// Map a date-time to/from SQLite as the number of seconds since the Unix epoch,
+/// 1970-01-01 00:00 UTC, as returned by the `unixepoch()` function.
+///
+/// The plain date-time types infer the storage format from the storage class of each value,
+/// but always encode to `TEXT`. Use this adapter for columns that store `INTEGER` timestamps
+/// so values round-trip in that format, and so that a value stored in any other format is an
+/// error rather than being silently decoded as something else.
+///
+/// Values are encoded as `INTEGER`, truncating any fractional seconds. Values may be decoded
+/// from `INTEGER`, or from `REAL` as returned by `unixepoch('subsec')`.
+///
+/// Supported types:
+///
+/// * `chrono`: `DateTime<Utc>`, `DateTime<Local>`, `DateTime<FixedOffset>`, `NaiveDateTime`
+/// * `time`: `OffsetDateTime`, `PrimitiveDateTime`
+///
+/// Naive date-times (`NaiveDateTime`, `PrimitiveDateTime`) are assumed to be in UTC.
+///
+/// ### Example
+///
+/// ```rust,no_run
+/// # #[cfg(feature = "chrono")]
+/// # async fn example() -> sqlx_core::Result<()> {
+/// use chrono::{DateTime, Utc};
+/// use sqlx_core::connection::Connection;
+/// use sqlx_sqlite::types::UnixEpoch;
+/// use sqlx_sqlite::SqliteConnection;
+///
+/// let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+///
+/// // CREATE TABLE event(id INTEGER PRIMARY KEY, created_at INTEGER NOT NULL);
+/// sqlx_core::query::query("INSERT INTO event(created_at) VALUES (?)")
+///     .bind(UnixEpoch(Utc::now()))
+///     .execute(&mut conn)
+///     .await?;
+///
+/// let (created_at,): (UnixEpoch<DateTime<Utc>>,) =
+///     sqlx_core::query_as::query_as("SELECT created_at FROM event")
+///         .fetch_one(&mut conn)
+///         .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// With the query macros, select the format using a type override:
+/// `SELECT created_at AS "created_at: UnixEpoch<DateTime<Utc>>" FROM event`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnixEpoch<T>(pub T);
+
+/// Map a date-time to/from SQLite as the (fractional) number of days since the Julian epoch,
+/// November 24, 4714 BCE 12:00 UTC, as returned by the `julianday()` function.
+///
+/// Values are encoded as `REAL`, and may be decoded from `REAL` or `INTEGER`. Any other storage
+/// class is an error. Julian days are only precise to tens of microseconds.
+///
+/// Supports the same date-time types as [`UnixEpoch`], and is used the same way.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JulianDay<T>(pub T);
+
+macro_rules! impl_adapter {
+    ($name:ident) => {
+        impl<T> $name<T> {
+            /// Extract the inner value.
+            pub fn into_inner(self) -> T {
+                self.0
+            }
+        }
+
+        impl<T> Deref for $name<T> {
+            type Target = T;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<T> DerefMut for $name<T> {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+    };
+}
+
+impl_adapter!(UnixEpoch);
+impl_adapter!(JulianDay);
+
+impl<T: Timestamp> Type<Sqlite> for UnixEpoch<T> {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Integer)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(
+            ty.0,
+            DataType::Integer | DataType::Int4 | DataType::Float | DataType::Datetime
+        )
+    }
+}
+
+impl<T: Timestamp> Encode<'_, Sqlite> for UnixEpoch<T> {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'_>>) -> Result<IsNull, BoxDynError> {
+        let (seconds, _) = self.0.to_unix();
+
+        Encode::<Sqlite>::encode(seconds, buf)
+    }
+}
+
+impl<'r, T: Timestamp> Decode<'r, Sqlite> for UnixEpoch<T> {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        let dt = match value.type_info().0 {
+            DataType::Integer | DataType::Int4 => {
+                let seconds = value.int64();
+                T::from_unix(seconds, 0).ok_or_else(|| format!("invalid unix epoch: {seconds}"))?
+            }
+            DataType::Float => {
+                let seconds = value.double();
+                from_fractional_seconds(seconds)
+                    .ok_or_else(|| format!("invalid unix epoch: {seconds}"))?
+            }
+            _ => return Err(unexpected_storage_class("a unix epoch", &value)),
+        };
+
+        Ok(UnixEpoch(dt))
+    }
+}
+
+impl<T: Timestamp> Type<Sqlite> for JulianDay<T> {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo(DataType::Float)
+    }
+
+    fn compatible(ty: &SqliteTypeInfo) -> bool {
+        matches!(
+            ty.0,
+            DataType::Float | DataType::Integer | DataType::Int4 | DataType::Datetime
+        )
+    }
+}
+
+impl<T: Timestamp> Encode<'_, Sqlite> for JulianDay<T> {
+    fn encode_by_ref(&self, buf: &mut Vec<SqliteArgumentValue<'_>>) -> Result<IsNull, BoxDynError> {
+        let (seconds, nanos) = self.0.to_unix();
+        let seconds = seconds as f64 + f64::from(nanos) / 1E9;
+
+        Encode::<Sqlite>::encode(seconds / SECONDS_PER_DAY + UNIX_EPOCH_IN_JULIAN_DAYS, buf)
+    }
+}
+
+impl<'r, T: Timestamp> Decode<'r, Sqlite> for JulianDay<T> {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        if !matches!(
+            value.type_info().0,
+            DataType::Float | DataType::Integer | DataType::Int4
+        ) {
+            return Err(unexpected_storage_class("a julian day", &value));
+        }
+
+        let days = value.double();
+        let seconds = (days - UNIX_EPOCH_IN_JULIAN_DAYS) * SECONDS_PER_DAY;
+
+        from_fractional_seconds(seconds)
+            .map(JulianDay)
+            .ok_or_else(|| format!("invalid julian day: {days}").into())
+    }
+}
+
+fn from_fractional_seconds<T: Timestamp>(seconds: f64) -> Option<T> {
+    // Also rejects values too large for an `i64`, which would otherwise saturate below.
+    if !seconds.is_finite() || seconds.abs() >= i64::MAX as f64 {
+        return None;
+    }
+
+    // We checked above that the value is finite and in range, so these casts cannot wrap.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    {
+        let whole = seconds.floor();
+        let nanos = std::cmp::min(((seconds - whole) * 1E9).round() as u32, 999_999_999);
+
+        T::from_unix(whole as i64, nanos)
+    }
+}
+
+fn unexpected_storage_class(expected: &str, value: &SqliteValueRef<'_>) -> BoxDynError {
+    format!(
+        "expected {expected}, but the value has the storage class {}",
+        value.type_info().name()
+    )
+    .into()
+}
+
+/// A date-time type that can be converted to/from a Unix timestamp.
+///
+/// This trait is sealed and implemented for the types listed on [`UnixEpoch`].
+pub trait Timestamp: private::Sealed + Sized {
+    /// The number of whole seconds since the Unix epoch, and the nanoseconds past that second.
+    #[doc(hidden)]
+    fn to_unix(&self) -> (i64, u32);
+
+    #[doc(hidden)]
+    fn from_unix(seconds: i64, nanos: u32) -> Option<Self>;
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use chrono::{DateTime, FixedOffset, Local, NaiveDateTime, Offset, TimeZone, Utc};
+
+    use super::{private::Sealed, Timestamp};
+
+    macro_rules! impl_timestamp_for_datetime {
+        ($tz:ty, $new:expr) => {
+            impl Sealed for DateTime<$tz> {}
+
+            impl Timestamp for DateTime<$tz> {
+                fn to_unix(&self) -> (i64, u32) {
+                    (self.timestamp(), self.timestamp_subsec_nanos())
+                }
+
+                fn from_unix(seconds: i64, nanos: u32) -> Option<Self> {
+                    $new.timestamp_opt(seconds, nanos).single()
+                }
+            }
+        };
+    }
+
+    impl_timestamp_for_datetime!(Utc, Utc);
+    impl_timestamp_for_datetime!(Local, Local);
+    impl_timestamp_for_datetime!(FixedOffset, Utc.fix());
+
+    impl Sealed for NaiveDateTime {}
+
+    impl Timestamp for NaiveDateTime {
+        fn to_unix(&self) -> (i64, u32) {
+            self.and_utc().to_unix()
+        }
+
+        fn from_unix(seconds: i64, nanos: u32) -> Option<Self> {
+            DateTime::<Utc>::from_unix(seconds, nanos).map(|dt| dt.naive_utc())
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use time::{OffsetDateTime, PrimitiveDateTime};
+
+    use super::{private::Sealed, Timestamp};
+
+    impl Sealed for OffsetDateTime {}
+
+    impl Timestamp for OffsetDateTime {
+        fn to_unix(&self) -> (i64, u32) {
+            (self.unix_timestamp(), self.nanosecond())
+        }
+
+        fn from_unix(seconds: i64, nanos: u32) -> Option<Self> {
+            let nanos = i128::from(seconds) * 1_000_000_000 + i128::from(nanos);
+
+            OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+        }
+    }
+
+    impl Sealed for PrimitiveDateTime {}
+
+    impl Timestamp for PrimitiveDateTime {
+        fn to_unix(&self) -> (i64, u32) {
+            self.assume_utc().to_unix()
+        }
+
+        fn from_unix(seconds: i64, nanos: u32) -> Option<Self> {
+            OffsetDateTime::from_unix(seconds, nanos)
+                .map(|dt| PrimitiveDateTime::new(dt.date(), dt.time()))
+        }
+    }
+}
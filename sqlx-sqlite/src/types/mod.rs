@@ -17,6 +17,7 @@
 //! | `f64`                                 | REAL                                                 |
 //! | `&str`, [`String`]                    | TEXT                                                 |
 //! | `&[u8]`, `Vec<u8>`                    | BLOB                                                 |
+//! | [`SqliteBlob<T, C>`](SqliteBlob)      | BLOB, serialized with a codec of your choice         |
 //!
 //! #### Note: Unsigned Integers
 //! Decoding of unsigned integer types simply performs a checked conversion
@@ -142,6 +143,24 @@
 //! Note that non-constant offsets may still cause issues when comparing timestamps,
 //! as the comparison operators are not timezone-aware.
 //!
+//! ### Storage formats for `DATETIME`: [`UnixEpoch`] and [`JulianDay`]
+//!
+//! Requires the `chrono` or `time` Cargo feature flag.
+//!
+//! The date-time types above always encode to `TEXT`, and decode whatever they are given according
+//! to its storage class. If a column consistently uses another format, wrap the date-time type
+//! in one of these adapters to encode and decode that format explicitly:
+//!
+//! | Rust type                             | Sqlite type(s)                                       |
+//! |---------------------------------------|------------------------------------------------------|
+//! | [`UnixEpoch<T>`]                      | INTEGER (seconds since 1970-01-01 00:00 UTC)         |
+//! | [`JulianDay<T>`]                      | REAL (days since 4714-11-24 BCE 12:00 UTC)           |
+//!
+//! Values stored in any other format are an error rather than being decoded by a different rule.
+//!
+//! The query macros cannot tell which format a column uses, so choose one with a type override,
+//! e.g. `SELECT created_at AS "created_at: UnixEpoch<DateTime<Utc>>" FROM event`.
+//!
 //! ### [`uuid`](https://crates.io/crates/uuid)
 //!
 //! Requires the `uuid` Cargo feature flag.
@@ -223,10 +242,17 @@
 
 pub(crate) use sqlx_core::types::*;
 
+pub use blob::{SqliteBlob, SqliteBlobCodec};
+#[cfg(any(feature = "chrono", feature = "time"))]
+pub use datetime::{JulianDay, UnixEpoch};
+
+mod blob;
 mod bool;
 mod bytes;
 #[cfg(feature = "chrono")]
 mod chrono;
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod datetime;
 mod float;
 mod int;
 #[cfg(feature = "json")]
@@ -238,3 +264,5 @@ mod time;
 mod uint;
 #[cfg(feature = "uuid")]
 mod uuid;
+#[cfg(feature = "json")]
+mod value_to_json;
@@ -0,0 +1,22 @@
+use sqlx_core::row::{float_to_json, ValueToJson};
+
+use crate::error::BoxDynError;
+use crate::type_info::DataType;
+use crate::types::JsonValue;
+use crate::value::ValueRef;
+use crate::{Sqlite, SqliteValueRef};
+
+impl ValueToJson for Sqlite {
+    fn value_to_json(value: SqliteValueRef<'_>) -> Result<JsonValue, BoxDynError> {
+        // The type info of a value is its storage class, regardless of the declared type.
+        let json = match value.type_info().0 {
+            DataType::Null => JsonValue::Null,
+            DataType::Integer | DataType::Int4 | DataType::Bool => value.int64().into(),
+            DataType::Float => float_to_json(value.double()),
+            DataType::Blob => value.blob().into(),
+            _ => value.text()?.into(),
+        };
+
+        Ok(json)
+    }
+}
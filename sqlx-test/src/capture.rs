@@ -0,0 +1,118 @@
+// A `tracing` subscriber that records every span and event, for tests that assert on them.
+//
+// It is installed as the global default on first use, so it sees events from every thread,
+// e.g. the SQLite worker. Tests share it, so they should look for values unique to them.
+
+use std::sync::{Mutex, Once};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Debug, Clone)]
+pub struct Captured {
+    pub name: &'static str,
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl Captured {
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl Visit for Captured {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields.push((field.name(), value.to_owned()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.fields.push((field.name(), format!("{value:?}")));
+    }
+}
+
+struct Capture {
+    spans: Mutex<Vec<Captured>>,
+    events: Mutex<Vec<Captured>>,
+}
+
+static CAPTURE: Capture = Capture {
+    spans: Mutex::new(Vec::new()),
+    events: Mutex::new(Vec::new()),
+};
+
+struct Subscribed;
+
+impl Subscriber for Subscribed {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attributes: &Attributes<'_>) -> Id {
+        let mut span = Captured {
+            name: attributes.metadata().name(),
+            fields: Vec::new(),
+        };
+        attributes.record(&mut span);
+
+        let mut spans = CAPTURE.spans.lock().unwrap();
+        spans.push(span);
+
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = CAPTURE.spans.lock().unwrap();
+        let index = usize::try_from(span.into_u64() - 1).unwrap();
+        values.record(&mut spans[index]);
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut captured = Captured {
+            name: event.metadata().name(),
+            fields: Vec::new(),
+        };
+        event.record(&mut captured);
+
+        CAPTURE.events.lock().unwrap().push(captured);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+// Start capturing; a no-op after the first call.
+pub fn install() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        tracing::subscriber::set_global_default(Subscribed)
+            .expect("a global tracing subscriber is already set");
+    });
+}
+
+// The events captured so far for which `filter` returns `true`.
+pub fn events(filter: impl Fn(&Captured) -> bool) -> Vec<Captured> {
+    matching(&CAPTURE.events, filter)
+}
+
+// The spans captured so far for which `filter` returns `true`.
+pub fn spans(filter: impl Fn(&Captured) -> bool) -> Vec<Captured> {
+    matching(&CAPTURE.spans, filter)
+}
+
+fn matching(captured: &Mutex<Vec<Captured>>, filter: impl Fn(&Captured) -> bool) -> Vec<Captured> {
+    captured
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|captured| filter(captured))
+        .cloned()
+        .collect()
+}
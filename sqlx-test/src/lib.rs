@@ -2,6 +2,8 @@ use sqlx::pool::PoolOptions;
 use sqlx::{Connection, Database, Pool};
 use std::env;
 
+pub mod capture;
+
 pub fn setup_if_needed() {
     let _ = dotenvy::dotenv();
     let _ = env_logger::builder().is_test(true).try_init();
@@ -13,6 +13,8 @@ pub use sqlx_core::any::{
 #[allow(deprecated)]
 pub use sqlx_core::any::AnyKind;
 
+pub use sqlx_core::any::types;
+
 pub(crate) mod reexports {
     /// **SEE DOCUMENTATION BEFORE USE**. Type alias for `Pool<Any>`.
     #[doc = include_str!("install_drivers_note.md")]
@@ -5,9 +5,11 @@ pub use sqlx_core::acquire::Acquire;
 pub use sqlx_core::arguments::{Arguments, IntoArguments};
 pub use sqlx_core::column::Column;
 pub use sqlx_core::column::ColumnIndex;
+pub use sqlx_core::column::DuplicateColumnNames;
+pub use sqlx_core::config;
 pub use sqlx_core::connection::{ConnectOptions, Connection};
 pub use sqlx_core::database::{self, Database};
-pub use sqlx_core::describe::Describe;
+pub use sqlx_core::describe::{self, Describe};
 pub use sqlx_core::executor::{Execute, Executor};
 pub use sqlx_core::from_row::FromRow;
 pub use sqlx_core::pool::{self, Pool};
@@ -21,7 +23,12 @@ pub use sqlx_core::query_scalar::query_scalar_with_result as __query_scalar_with
 pub use sqlx_core::query_scalar::{query_scalar, query_scalar_with};
 pub use sqlx_core::raw_sql::{raw_sql, RawSql};
 pub use sqlx_core::row::Row;
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub use sqlx_core::row::{row_to_json, ValueToJson};
 pub use sqlx_core::statement::Statement;
+pub use sqlx_core::query_cache::{self, CachingExecutor, QueryCache};
+pub use sqlx_core::table_prefix::{self, PrefixedExecutor, TablePrefix};
 pub use sqlx_core::transaction::{Transaction, TransactionManager};
 pub use sqlx_core::type_info::TypeInfo;
 pub use sqlx_core::types::Type;
@@ -34,6 +41,10 @@ pub use sqlx_core::error::{self, Error, Result};
 #[cfg(feature = "migrate")]
 pub use sqlx_core::migrate;
 
+#[cfg(feature = "wire-trace")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wire-trace")))]
+pub use sqlx_core::wire_trace;
+
 #[cfg(feature = "mysql")]
 #[cfg_attr(docsrs, doc(cfg(feature = "mysql")))]
 #[doc(inline)]
@@ -139,7 +150,7 @@ pub use self::decode::Decode;
 
 /// Types and traits for the `query` family of functions and macros.
 pub mod query {
-    pub use sqlx_core::query::{Map, Query};
+    pub use sqlx_core::query::{AnyChunked, Map, Query, QueryStats, WithStats};
     pub use sqlx_core::query_as::QueryAs;
     pub use sqlx_core::query_scalar::QueryScalar;
 }
@@ -54,6 +54,12 @@
 /// * The `DATABASE_URL` environment variable must be set at build-time to point to a database
 ///   server with the schema that the query string will be checked against.
 ///   All variants of `query!()` use [dotenv]<sup>1</sup> so this can be in a `.env` file instead.
+///   If `DATABASE_URL` is set in both places, the environment variable takes precedence and
+///   queries checked against it emit a warning if the two differ; `.env` can be made to take
+///   precedence, or the difference an error, with `common.database-url-precedence` in
+///   [`sqlx.toml`][crate::config].
+///   A different variable can be named with `common.database-url-var` in
+///   [`sqlx.toml`][crate::config].
 ///
 ///     * Or, `.sqlx` must exist at the workspace root. See [Offline Mode](#offline-mode-requires-the-offline-feature)
 ///       below.
@@ -69,6 +75,8 @@
 ///     * The schema of the database URL (e.g. `postgres://` or `mysql://`) will be used to
 ///       determine the database type.
 ///
+///     * Unless the macros are set to [execute on `Any`](#executing-on-any).
+///
 /// <sup>1</sup> The `dotenv` crate itself appears abandoned as of [December 2021](https://github.com/dotenv-rs/dotenv/issues/74)
 /// so we now use the [dotenvy] crate instead. The file format is the same.
 ///
@@ -285,6 +293,20 @@
 /// | `foo!: T` | Forced not-null | Overridden |
 /// | `foo?: T` | Forced nullable | Overridden |
 ///
+/// ## Statement Caching
+/// Like [`query()`][crate::query()], the generated query is prepared once per connection and kept
+/// in that connection's statement cache. For queries where this isn't wanted, call
+/// `.persistent(false)` on the result and the prepared statement will be closed after execution
+/// instead; this works the same for [`query_as!`][`crate::query_as!`] and
+/// [`query_scalar!`][`crate::query_scalar!`]:
+///
+/// ```rust,ignore
+/// let rows = sqlx::query!("SELECT id, name FROM users WHERE id = ?", id)
+///     .persistent(false)
+///     .fetch_all(&mut conn)
+///     .await?;
+/// ```
+///
 /// ## Offline Mode
 /// The macros can be configured to not require a live database connection for compilation,
 /// but it requires a couple extra steps:
@@ -305,6 +327,35 @@
 ///
 /// See [the README for `sqlx-cli`](https://crates.io/crates/sqlx-cli) for more information.
 ///
+/// ## Executing on `Any`
+/// With `SQLX_EXECUTE_ON_ANY=true` set at build time (like `DATABASE_URL`, this can be in
+/// `.env`), the queries are still checked against the database of `DATABASE_URL` (or the
+/// `.sqlx` directory), but the generated code executes them on [`Any`][crate::Any], so the
+/// same build can run against any database with the same schema. This requires the `any`
+/// feature.
+///
+/// The check is only as good as the database it is made against, so the SQL itself has to be
+/// valid on every database it runs on, including the bind parameter syntax (MySQL only accepts
+/// `?`). The Rust types of the columns and bind parameters are those of the checking database,
+/// and must also be supported by `Any`; see [`sqlx::any::types`][crate::any::types] for the
+/// types that are portable. A type that is not, such as a `TIMESTAMPTZ` column checked against
+/// Postgres, is a compile error unless it is given a [type override](#type-overrides-output-columns)
+/// that is.
+///
+/// `SQLX_EXECUTE_ON_ANY` applies to every query macro in the crate being compiled; a value other
+/// than `true`, `false`, `1` or `0` is a compile error.
+///
+/// ```rust,ignore
+/// // SQLX_EXECUTE_ON_ANY=true
+/// // DATABASE_URL=postgres://localhost/app
+///
+/// let mut conn = sqlx::AnyConnection::connect("sqlite://app.db").await?;
+///
+/// let user = sqlx::query!("SELECT id, name FROM users WHERE id = $1", id)
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
+///
 /// ## See Also
 /// * [`query_as!`][`crate::query_as!`] if you want to use a struct you can name,
 /// * [`query_file!`][`crate::query_file!`] if you want to define the SQL query out-of-line,
@@ -621,6 +672,38 @@ macro_rules! query_file_as (
     })
 );
 
+/// Generate a public struct named `$name` for the rows of a query, with the fields that
+/// [`query!`][`crate::query!`] would infer for it, for when the record needs to be returned from
+/// a function or stored.
+///
+/// This is used in item position, and only declares the struct, with `pub` fields named, typed
+/// and made nullable like the fields of the anonymous record of [`query!`][`crate::query!`],
+/// including [type overrides](macro@crate::query#type-overrides-output-columns). The struct
+/// also implements [`FromRow`][crate::FromRow], decoding the columns by position. Bind
+/// parameters of the query are not needed, and wildcard overrides are not allowed.
+///
+/// To run the query, pass the struct to [`query_as!`][`crate::query_as!`]:
+///
+/// ```rust,ignore
+/// sqlx::query_as_named!("Account", "SELECT id, name, email FROM accounts WHERE id = $1");
+///
+/// async fn account_by_id(conn: &mut PgConnection, id: i32) -> sqlx::Result<Account> {
+///     sqlx::query_as!(Account, "SELECT id, name, email FROM accounts WHERE id = $1", id)
+///         .fetch_one(conn)
+///         .await
+/// }
+/// ```
+///
+/// The struct is checked against the database at compile time like any other query, and
+/// [`query_as!`][`crate::query_as!`] then checks that it still matches the query it is used with.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_as_named (
+    ($name:literal, $query:expr) => (
+        $crate::sqlx_macros::expand_query!(record_name = $name, source = $query);
+    )
+);
+
 /// A variant of [`query_as!`][`crate::query_as!`] which does not check the input or output types. This still does parse
 /// the query to ensure it's syntactically and semantically valid for the current database.
 #[macro_export]
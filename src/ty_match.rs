@@ -38,6 +38,10 @@ impl<T, U> WrapSameExt for WrapSame<T, Option<U>> {
     type Wrapped = Option<T>;
 }
 
+impl<T, U> WrapSameExt for WrapSame<T, &'_ Option<U>> {
+    type Wrapped = Option<T>;
+}
+
 impl<T, U> WrapSameExt for &'_ WrapSame<T, U> {
     type Wrapped = T;
 }
@@ -62,24 +66,32 @@ impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a str>, Option<String>> {
     type Matched = Option<&'a str>;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a [u8]>, Option<Vec<u8>>> {
-    type Matched = Option<&'a [u8]>;
+impl<'a, T> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, Option<Vec<T>>> {
+    type Matched = Option<&'a [T]>;
 }
 
 impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a str>, Option<&'a String>> {
     type Matched = Option<&'a str>;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a [u8]>, Option<&'a Vec<u8>>> {
-    type Matched = Option<&'a [u8]>;
+impl<'a, T> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, Option<&'a Vec<T>>> {
+    type Matched = Option<&'a [T]>;
+}
+
+impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a str>, &'a Option<String>> {
+    type Matched = Option<&'a str>;
+}
+
+impl<'a, T> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, &'a Option<Vec<T>>> {
+    type Matched = Option<&'a [T]>;
 }
 
 impl<'a> MatchBorrowExt for MatchBorrow<&'a str, String> {
     type Matched = &'a str;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<&'a [u8], Vec<u8>> {
-    type Matched = &'a [u8];
+impl<'a, T> MatchBorrowExt for MatchBorrow<&'a [T], Vec<T>> {
+    type Matched = &'a [T];
 }
 
 impl<T> MatchBorrowExt for MatchBorrow<&'_ T, T> {
@@ -143,6 +155,7 @@ fn test_wrap_same() {
         let _: i32 = WrapSame::<i32, _>::new(&0i32).wrap_same();
         let _: i32 = WrapSame::<i32, _>::new(&"hello, world!").wrap_same();
         let _: Option<i32> = WrapSame::<i32, _>::new(&Some(String::new())).wrap_same();
+        let _: Option<i32> = WrapSame::<i32, _>::new(&&Some(String::new())).wrap_same();
     }
 }
 
@@ -160,5 +173,16 @@ fn test_match_borrow() {
 
         let (_, match_borrow) = MatchBorrow::new(0i64, &0i64);
         let _: i64 = match_borrow.match_borrow();
+
+        let vec = vec![0i32];
+        let (_, match_borrow) = MatchBorrow::new(&vec[..], &vec);
+        let _: &[i32] = match_borrow.match_borrow();
+
+        let option = Some(vec);
+        let (_, match_borrow) = MatchBorrow::new(option.as_deref(), &option);
+        let _: Option<&[i32]> = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(option.as_deref(), &&option);
+        let _: Option<&[i32]> = match_borrow.match_borrow();
     }
 }
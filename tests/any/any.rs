@@ -141,3 +141,25 @@ async fn it_can_fail_and_recover_with_pool() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_decodes_portable_scalars() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+
+    let mut conn = new::<Any>().await?;
+
+    // Postgres returns `BOOLEAN` where SQLite and MySQL return integers,
+    // and `FLOAT` is `DOUBLE` on Postgres and SQLite but not on MySQL
+    let row = conn
+        .fetch_one("SELECT 1 = 1, 1 = 0, CAST(1.5 AS FLOAT), 42")
+        .await?;
+
+    assert!(row.try_get::<bool, _>(0)?);
+    assert!(!row.try_get::<bool, _>(1)?);
+    assert_eq!(row.try_get::<f32, _>(2)?, 1.5);
+    assert_eq!(row.try_get::<f64, _>(2)?, 1.5);
+    assert_eq!(row.try_get::<i16, _>(3)?, 42);
+    assert_eq!(row.try_get::<i64, _>(3)?, 42);
+
+    Ok(())
+}
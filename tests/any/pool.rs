@@ -1,10 +1,12 @@
 use sqlx::any::{AnyConnectOptions, AnyPoolOptions};
-use sqlx::Executor;
+use sqlx::config::Config;
+use sqlx::pool::{ConnectionSelection, Priority, ReplicatedPool};
+use sqlx::{AnyConnection, Connection, Executor};
 use std::sync::{
     atomic::{AtomicI32, AtomicUsize, Ordering},
     Arc, Mutex,
 };
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[sqlx_macros::test]
 async fn pool_should_invoke_after_connect() -> anyhow::Result<()> {
@@ -267,3 +269,475 @@ async fn test_connection_maintenance() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn test_idle_timeout_keeps_min_connections() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+    sqlx_test::setup_if_needed();
+    let conn_options: AnyConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+
+    let connects = Arc::new(AtomicUsize::new(0));
+    let connects_ = connects.clone();
+    let pool = AnyPoolOptions::new()
+        .idle_timeout(Duration::from_millis(100))
+        .max_lifetime(None)
+        .min_connections(2)
+        .after_connect(move |_conn, _meta| {
+            connects_.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Ok(()) })
+        })
+        .connect_lazy_with(conn_options);
+
+    // Open and release 4 connections
+    let conns = vec![
+        pool.acquire().await?,
+        pool.acquire().await?,
+        pool.acquire().await?,
+        pool.acquire().await?,
+    ];
+    for mut conn in conns {
+        conn.return_to_pool().await;
+    }
+
+    assert_eq!(pool.size(), 4);
+
+    // Sample the pool across several iterations of the maintenance task
+    for _ in 0..20 {
+        sqlx_core::rt::sleep(Duration::from_millis(50)).await;
+        assert!(pool.size() >= 2, "pool.size() = {}", pool.size());
+    }
+
+    // The connections beyond `min_connections` were closed for being idle,
+    // but the remaining ones were kept instead of being replaced.
+    assert_eq!(pool.size(), 2);
+    assert_eq!(pool.num_idle(), 2);
+    assert_eq!(connects.load(Ordering::SeqCst), 4);
+
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_close_and_replace() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+    sqlx_test::setup_if_needed();
+    let conn_options: AnyConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+
+    let connects = Arc::new(AtomicUsize::new(0));
+    let connects_ = connects.clone();
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .after_connect(move |_conn, _meta| {
+            connects_.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Ok(()) })
+        })
+        .connect_lazy_with(conn_options);
+
+    let conn = pool.acquire().await?;
+    assert_eq!(connects.load(Ordering::SeqCst), 1);
+
+    conn.close_and_replace().await?;
+
+    // Wait for the replacement to be opened in the background.
+    for _ in 0..50 {
+        if pool.num_idle() == 1 {
+            break;
+        }
+        sqlx_core::rt::sleep(Duration::from_millis(20)).await;
+    }
+
+    assert_eq!(pool.size(), 1);
+    assert_eq!(pool.num_idle(), 1);
+    assert_eq!(connects.load(Ordering::SeqCst), 2);
+
+    // The replacement is handed out without opening another connection.
+    let _conn = pool.acquire().await?;
+    assert_eq!(connects.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_connection_selection() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+    sqlx_test::setup_if_needed();
+    let conn_options: AnyConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+
+    for selection in [ConnectionSelection::Lru, ConnectionSelection::Mru] {
+        let pool = AnyPoolOptions::new()
+            .max_connections(2)
+            .connection_selection(selection)
+            .connect_lazy_with(conn_options.clone());
+
+        let mut first = pool.acquire().await?;
+        let mut second = pool.acquire().await?;
+        let (first_id, second_id) = (first.id(), second.id());
+
+        first.return_to_pool().await;
+        second.return_to_pool().await;
+        assert_eq!(pool.num_idle(), 2);
+
+        let expected = match selection {
+            ConnectionSelection::Lru => first_id,
+            ConnectionSelection::Mru => second_id,
+        };
+
+        assert_eq!(pool.acquire().await?.id(), expected, "{selection:?}");
+    }
+
+    // the reaper keeps the order of the connections it doesn't close
+    let pool = AnyPoolOptions::new()
+        .max_connections(2)
+        .min_connections(2)
+        .idle_timeout(Duration::from_millis(100))
+        .connection_selection(ConnectionSelection::Mru)
+        .connect_lazy_with(conn_options);
+
+    let mut first = pool.acquire().await?;
+    let mut second = pool.acquire().await?;
+    let second_id = second.id();
+
+    first.return_to_pool().await;
+    second.return_to_pool().await;
+
+    sqlx_core::rt::sleep(Duration::from_millis(350)).await;
+    assert_eq!(pool.size(), 2);
+    assert_eq!(pool.acquire().await?.id(), second_id);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_max_queries_per_connection() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+    sqlx_test::setup_if_needed();
+    let conn_options: AnyConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+
+    // the statements that connecting runs by itself, e.g. to set up the session
+    let connected = AnyConnection::connect_with(&conn_options)
+        .await?
+        .queries_executed();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .max_queries_per_connection(connected + 3)
+        .connect_lazy_with(conn_options);
+
+    let mut conn = pool.acquire().await?;
+    let first_id = conn.id();
+    assert_eq!(conn.queries_executed(), connected);
+
+    conn.execute("SELECT 1").await?;
+    conn.execute("SELECT 2").await?;
+    assert_eq!(conn.queries_executed(), connected + 2);
+    conn.return_to_pool().await;
+
+    // still below the limit
+    let mut conn = pool.acquire().await?;
+    assert_eq!(conn.id(), first_id);
+
+    conn.execute("SELECT 3").await?;
+    assert_eq!(conn.queries_executed(), connected + 3);
+
+    let over_limit_id = conn.id();
+    conn.return_to_pool().await;
+
+    // the connection was closed and replaced
+    assert_ne!(pool.acquire().await?.id(), over_limit_id);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_close_concurrency() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+    sqlx_test::setup_if_needed();
+    let conn_options: AnyConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(8)
+        .close_concurrency(4)
+        .connect_lazy_with(conn_options);
+
+    assert_eq!(pool.options().get_close_concurrency(), 4);
+
+    let mut conns = Vec::new();
+    for _ in 0..8 {
+        conns.push(pool.acquire().await?);
+    }
+
+    // Keep one connection checked out while the pool is closing.
+    let checked_out = conns.pop().unwrap();
+    drop(conns);
+
+    for _ in 0..50 {
+        if pool.num_idle() == 7 {
+            break;
+        }
+        sqlx_core::rt::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(pool.num_idle(), 7);
+
+    let close = sqlx_core::rt::spawn({
+        let pool = pool.clone();
+        async move { pool.close().await }
+    });
+
+    for _ in 0..50 {
+        if pool.size() == 1 {
+            break;
+        }
+        sqlx_core::rt::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(pool.size(), 1);
+
+    drop(checked_out);
+    close.await;
+
+    // The checked-out connection is closed in the background once it's returned.
+    for _ in 0..50 {
+        if pool.size() == 0 {
+            break;
+        }
+        sqlx_core::rt::sleep(Duration::from_millis(20)).await;
+    }
+
+    assert_eq!(pool.size(), 0);
+    assert!(pool.is_closed());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_acquire_with_priority() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+    sqlx_test::setup_if_needed();
+    let conn_options: AnyConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .connect_lazy_with(conn_options);
+
+    let conn = pool.acquire().await?;
+
+    let served = Arc::new(Mutex::new(Vec::new()));
+    let mut tasks = Vec::new();
+
+    for (label, priority) in [
+        ("low", Priority::Low),
+        ("normal 1", Priority::Normal),
+        ("high", Priority::High),
+        ("normal 2", Priority::Normal),
+    ] {
+        tasks.push(sqlx_core::rt::spawn({
+            let pool = pool.clone();
+            let served = served.clone();
+            async move {
+                let _conn = pool.acquire_with_priority(priority).await?;
+                served.lock().unwrap().push(label);
+                anyhow::Ok(())
+            }
+        }));
+
+        // Make sure the tasks queue up in order.
+        sqlx_core::rt::sleep(Duration::from_millis(50)).await;
+    }
+
+    assert!(served.lock().unwrap().is_empty());
+    drop(conn);
+
+    for task in tasks {
+        task.await?;
+    }
+
+    assert_eq!(
+        *served.lock().unwrap(),
+        ["high", "normal 1", "normal 2", "low"]
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_cancelled_acquire_leaves_queue() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+    sqlx_test::setup_if_needed();
+    let conn_options: AnyConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .connect_lazy_with(conn_options);
+
+    let conn = pool.acquire().await?;
+
+    // `try_acquire()` neither waits nor queues.
+    assert!(pool.try_acquire().is_none());
+
+    // A cancelled waiter at the head of the queue.
+    let cancelled = sqlx_core::rt::timeout(Duration::from_millis(50), pool.acquire()).await;
+    assert!(cancelled.is_err());
+
+    let waiting = sqlx_core::rt::spawn({
+        let pool = pool.clone();
+        async move {
+            let _conn = pool.acquire_with_priority(Priority::Low).await?;
+            anyhow::Ok(())
+        }
+    });
+
+    sqlx_core::rt::sleep(Duration::from_millis(50)).await;
+
+    // A cancelled waiter that jumped ahead of a waiting task.
+    let cancelled = sqlx_core::rt::timeout(
+        Duration::from_millis(50),
+        pool.acquire_with_priority(Priority::High),
+    )
+    .await;
+    assert!(cancelled.is_err());
+
+    drop(conn);
+
+    // Neither cancelled waiter is served, so the next one in line is.
+    sqlx_core::rt::timeout(Duration::from_secs(5), waiting).await??;
+
+    // The connection is returned to the pool in the background.
+    sqlx_core::rt::sleep(Duration::from_millis(50)).await;
+    assert!(pool.try_acquire().is_some());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_replicated_pool() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+    sqlx_test::setup_if_needed();
+    let conn_options: AnyConnectOptions = std::env::var("DATABASE_URL")?.parse()?;
+
+    let new_pool = || AnyPoolOptions::new().connect_lazy_with(conn_options.clone());
+    let broken = AnyPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(1))
+        .connect_lazy("sqlite://target/no-such-dir/replica.db")?;
+
+    let pool = ReplicatedPool::new(new_pool(), [(new_pool(), 2), (new_pool(), 1), (broken, 1)]);
+    let [heavy, light, _] = pool.replicas() else {
+        unreachable!()
+    };
+
+    // reads are spread over the replicas by weight, and the broken one is weighted out
+    let mut conns = Vec::new();
+    for _ in 0..6 {
+        conns.push(pool.acquire_read().await?);
+    }
+    assert_eq!((heavy.size(), light.size()), (4, 2));
+    assert!(!pool.is_healthy(2));
+    assert_eq!(pool.primary().size(), 0);
+    drop(conns);
+
+    // writes go to the primary
+    let write = pool.acquire_write().await?;
+    assert_eq!(pool.primary().size(), 1);
+
+    // drained replicas get no reads; without replicas, reads go to the primary
+    pool.set_weight(0, 0);
+    pool.set_weight(1, 0);
+    let read = pool.acquire_read().await?;
+    assert_eq!(pool.primary().size(), 2);
+    drop((write, read));
+
+    pool.close().await;
+    assert!(heavy.is_closed() && pool.primary().is_closed());
+
+    // a saturated replica is skipped after `replica_acquire_timeout`, rather than holding up
+    // the read for the whole `acquire_timeout` of its pool, but isn't weighted out
+    let saturated = AnyPoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_secs(30))
+        .connect_lazy_with(conn_options.clone());
+    let held = saturated.acquire().await?;
+    let broken = AnyPoolOptions::new().connect_lazy("sqlite://target/no-such-dir/replica.db")?;
+
+    let pool = ReplicatedPool::new(new_pool(), [(saturated, 1), (broken, 0)]);
+    pool.set_replica_acquire_timeout(Duration::from_millis(100));
+
+    let start = Instant::now();
+    let read = pool.acquire_read().await?;
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(pool.is_healthy(0));
+    assert_eq!(pool.primary().size(), 1);
+
+    // health checks connect past the pool, so they find the broken replica even though it
+    // is drained, and not the saturated one
+    pool.check_health().await;
+    assert!(pool.is_healthy(0));
+    assert!(!pool.is_healthy(1));
+    drop((held, read));
+
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pool_name() -> anyhow::Result<()> {
+    sqlx::any::install_default_drivers();
+    sqlx_test::setup_if_needed();
+    sqlx_test::capture::install();
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .pool_name("any-reports")
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    assert_eq!(
+        pool.connect_options().pool_name.as_deref(),
+        Some("any-reports")
+    );
+
+    let mut conn = pool.acquire().await?;
+
+    if conn.backend_name() == "PostgreSQL" {
+        let application_name: String = sqlx::query_scalar(
+            "SELECT application_name::text FROM pg_stat_activity WHERE pid = pg_backend_pid()",
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+        assert_eq!(application_name, "any-reports");
+    } else {
+        // other backends can't apply it, and say so
+        let warnings =
+            sqlx_test::capture::events(|event| event.field("pool_name") == Some("any-reports"));
+        assert_eq!(warnings.len(), 1);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_pool_options_from_config() {
+    let mut config = Config::default();
+    config.pool.max_connections = Some(50);
+    config.pool.acquire_timeout = Some(Duration::from_secs(2));
+    config.pool.max_lifetime = Some(None);
+    config.pool.test_before_acquire = Some(false);
+
+    let options = AnyPoolOptions::from_config(&config);
+    assert_eq!(options.get_max_connections(), 50);
+    assert_eq!(options.get_acquire_timeout(), Duration::from_secs(2));
+    assert_eq!(options.get_max_lifetime(), None);
+    assert!(!options.get_test_before_acquire());
+
+    // keys that aren't set keep the defaults
+    let defaults = AnyPoolOptions::new();
+    assert_eq!(options.get_min_connections(), defaults.get_min_connections());
+    assert_eq!(options.get_idle_timeout(), defaults.get_idle_timeout());
+
+    // and values set in code win over the config
+    let options = AnyPoolOptions::from_config(&config)
+        .max_connections(5)
+        .test_before_acquire(true);
+    assert_eq!(options.get_max_connections(), 5);
+    assert!(options.get_test_before_acquire());
+    assert_eq!(options.get_acquire_timeout(), Duration::from_secs(2));
+}
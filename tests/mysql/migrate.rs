@@ -1,4 +1,4 @@
-use sqlx::migrate::Migrator;
+use sqlx::migrate::{MigrateError, Migrator};
 use sqlx::mysql::{MySql, MySqlConnection};
 use sqlx::pool::PoolConnection;
 use sqlx::Executor;
@@ -27,6 +27,44 @@ async fn simple(mut conn: PoolConnection<MySql>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn skip_versions(mut conn: PoolConnection<MySql>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/mysql/migrations_simple")).await?;
+
+    // a version that does not exist is a mistake
+    migrator.skip_versions(&[20220721115250, 1]);
+    assert!(matches!(
+        migrator.run(&mut conn).await,
+        Err(MigrateError::VersionNotPresent(1))
+    ));
+
+    migrator.skip_versions(&[20220721115524]);
+    migrator.run(&mut conn).await?;
+
+    // the conversion of the payload did not run
+    let res: i64 = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, 100);
+
+    let skipped: Vec<i64> = conn
+        .fetch_all("SELECT version FROM _sqlx_migrations WHERE skipped ORDER BY version")
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(skipped, [20220721115524]);
+
+    // it counts as applied from now on
+    let migrator = Migrator::new(Path::new("tests/mysql/migrations_simple")).await?;
+    migrator.run(&mut conn).await?;
+
+    Ok(())
+}
+
 #[sqlx::test(migrations = false)]
 async fn reversible(mut conn: PoolConnection<MySql>) -> anyhow::Result<()> {
     clean_up(&mut conn).await?;
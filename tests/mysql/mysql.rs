@@ -589,6 +589,195 @@ async fn test_shrink_buffers() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_resets_the_session() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("SET @reset_me = 1").await?;
+    let _: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(conn.cached_statements_size(), 1);
+    assert_eq!(conn.prepared_statement_count(), 1);
+
+    // Left open without going through `begin()`.
+    conn.execute("START TRANSACTION").await?;
+
+    conn.reset().await?;
+
+    assert_eq!(conn.cached_statements_size(), 0);
+    assert_eq!(conn.prepared_statement_count(), 0);
+
+    let in_transaction: i64 = sqlx::query_scalar("SELECT @@in_transaction")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(in_transaction, 0);
+
+    let variable: Option<i64> = sqlx::query_scalar("SELECT @reset_me")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(variable, None);
+
+    // The session settings made on connect are restored.
+    let sql_mode: String = sqlx::query_scalar("SELECT @@sql_mode")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(sql_mode.contains("PIPES_AS_CONCAT"), "{sql_mode}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checks_the_number_of_binds() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let res = sqlx::query("SELECT ?, ?")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::BindCountMismatch {
+            expected: 2,
+            provided: 1
+        })
+    ));
+
+    // the connection is still usable
+    let value: i32 = sqlx::query_scalar("SELECT ?")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_each_result_set_of_a_procedure() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("DROP PROCEDURE IF EXISTS sqlx_result_sets")
+        .await?;
+    conn.execute(
+        r#"
+CREATE PROCEDURE sqlx_result_sets(IN n INT, OUT doubled INT)
+BEGIN
+    SELECT n AS a;
+    SELECT 1 AS b, 2 AS c FROM DUAL WHERE n < 0;
+    SET doubled = n * 2;
+END
+        "#,
+    )
+    .await?;
+
+    let results: Vec<_> = conn
+        .call_procedure("CALL sqlx_result_sets(21, @doubled)")
+        .try_collect()
+        .await?;
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].columns()[0].name(), "a");
+    assert_eq!(results[0].rows().len(), 1);
+    assert_eq!(results[0].rows()[0].try_get::<i32, _>("a")?, 21);
+    assert_eq!(results[1].columns().len(), 2);
+    assert!(results[1].rows().is_empty());
+    // the result of the `CALL` itself
+    assert!(results[2].columns().is_empty());
+    assert!(results.iter().all(|result| !result.is_out_params()));
+
+    // `OUT` parameters are only returned to prepared statements
+    let results: Vec<_> = conn
+        .call_procedure(
+            sqlx::query("CALL sqlx_result_sets(?, ?)")
+                .bind(5_i32)
+                .bind(None::<i32>),
+        )
+        .try_collect()
+        .await?;
+
+    assert_eq!(results.len(), 4);
+    assert!(results[2].is_out_params());
+    assert_eq!(results[2].rows()[0].try_get::<i32, _>(0)?, 10);
+    assert!(results[3].columns().is_empty());
+
+    // the connection is still usable
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    conn.execute("DROP PROCEDURE sqlx_result_sets").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_widens_unsigned_integers_with_lenient_integer_decode() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    async fn create_table(conn: &mut MySqlConnection) -> anyhow::Result<()> {
+        conn.execute("CREATE TEMPORARY TABLE lenient_integers (a INT UNSIGNED, b INT)")
+            .await?;
+        conn.execute("INSERT INTO lenient_integers VALUES (4294967295, -1)")
+            .await?;
+
+        Ok(())
+    }
+
+    let mut conn = new::<MySql>().await?;
+    create_table(&mut conn).await?;
+
+    let row = sqlx::query("SELECT a FROM lenient_integers")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(row.try_get::<i64, _>(0).is_err());
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .lenient_integer_decode(true);
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+    create_table(&mut conn).await?;
+
+    // text and binary formats
+    for query in [
+        sqlx::query("SELECT a, b FROM lenient_integers"),
+        sqlx::query("SELECT a, b FROM lenient_integers WHERE ?").bind(true),
+    ] {
+        let row = query.fetch_one(&mut conn).await?;
+        assert_eq!(row.try_get::<i64, _>(0)?, 4294967295);
+        // narrower types and signed columns for unsigned types are still a mismatch
+        assert!(row.try_get::<i32, _>(0).is_err());
+        assert!(row.try_get::<u64, _>(1).is_err());
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_query_stats() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE stats_rows (id INT PRIMARY KEY)").await?;
+    conn.execute("INSERT INTO stats_rows VALUES (1), (2), (3), (4), (5), (6)").await?;
+
+    let (rows, stats) = sqlx::query("SELECT id FROM stats_rows WHERE id <= ?")
+        .bind(5_i32)
+        .with_stats()
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 5);
+    assert_eq!(stats.rows_returned, 5);
+
+    // at least the returned rows were read, plus those of reading the counters themselves
+    assert!(stats.rows_examined.is_some_and(|rows| rows >= 5), "{stats:?}");
+    assert!(stats.bytes_received.is_some_and(|bytes| bytes > 0), "{stats:?}");
+
+    // only Postgres reports this
+    assert_eq!(stats.server_time, None);
+
+    Ok(())
+}
+
 async fn select_statement_count(conn: &mut MySqlConnection) -> Result<i64, sqlx::Error> {
     // Fails if performance schema does not exist
     sqlx::query_scalar(
@@ -603,3 +792,23 @@ async fn select_statement_count(conn: &mut MySqlConnection) -> Result<i64, sqlx:
     .fetch_one(conn)
     .await
 }
+
+#[test]
+fn it_rejects_unsupported_conflict_clauses() {
+    use sqlx::query_builder::{ConflictClause, ConflictClauseError, QueryBuilder};
+
+    let mut qb: QueryBuilder<'_, MySql> = QueryBuilder::new("INSERT INTO counters");
+
+    for clause in [
+        ConflictClause::any(),
+        ConflictClause::constraint("counters_pkey"),
+        ConflictClause::columns(["name"]).where_("count > 0"),
+    ] {
+        assert!(matches!(
+            qb.push_on_conflict(&clause),
+            Err(ConflictClauseError::Unsupported { database, .. }) if database == "MySQL"
+        ));
+    }
+
+    assert_eq!(qb.sql(), "INSERT INTO counters");
+}
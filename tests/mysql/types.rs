@@ -151,6 +151,49 @@ mod chrono {
 
         Ok(())
     }
+
+    #[sqlx_macros::test]
+    async fn test_type_chrono_zero_date_handling() -> anyhow::Result<()> {
+        use sqlx::mysql::{MySqlConnectOptions, MySqlConnection, ZeroDateHandling};
+        use sqlx::Connection;
+
+        for (handling, expected) in [
+            (ZeroDateHandling::Null, None),
+            (ZeroDateHandling::MinDate, Some(NaiveDate::MIN)),
+        ] {
+            let options = dotenvy::var("DATABASE_URL")?
+                .parse::<MySqlConnectOptions>()?
+                .zero_date_handling(handling);
+            let mut conn = MySqlConnection::connect_with(&options).await?;
+
+            conn.execute("SET @@sql_mode := REPLACE(@@sql_mode, 'NO_ZERO_IN_DATE', '');")
+                .await?;
+
+            conn.execute("SET @@sql_mode := REPLACE(@@sql_mode, 'NO_ZERO_DATE', '');")
+                .await?;
+
+            // the binary protocol
+            let row = sqlx::query("SELECT DATE '0000-00-00', DATE '2020-00-15'")
+                .fetch_one(&mut conn)
+                .await?;
+
+            assert_eq!(row.try_get::<Option<NaiveDate>, _>(0)?, expected);
+            assert_eq!(row.try_get::<Option<NaiveDate>, _>(1)?, expected);
+
+            // the text protocol
+            let row = conn
+                .fetch_one("SELECT DATE '0000-00-00', TIMESTAMP '0000-00-00 00:00:00'")
+                .await?;
+
+            assert_eq!(row.try_get::<Option<NaiveDate>, _>(0)?, expected);
+            assert_eq!(
+                row.try_get::<Option<NaiveDateTime>, _>(1)?,
+                expected.map(|_| NaiveDateTime::MIN)
+            );
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "time")]
@@ -342,6 +385,54 @@ CREATE TEMPORARY TABLE with_bits (
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_years() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE with_years (
+    id INT PRIMARY KEY AUTO_INCREMENT,
+    value YEAR NOT NULL
+);
+    "#,
+    )
+    .await?;
+
+    // `0` is the zero year, `0000`
+    for year in [2024_u16, 1901, 0] {
+        sqlx::query("INSERT INTO with_years (value) VALUES (?)")
+            .bind(year)
+            .execute(&mut conn)
+            .await?;
+    }
+
+    // BINARY
+    let years: Vec<u16> = sqlx::query_scalar("SELECT value FROM with_years ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(years, [2024, 1901, 0]);
+
+    let year: i32 = sqlx::query_scalar("SELECT value FROM with_years ORDER BY id")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(year, 2024);
+
+    // TEXT
+    let row = conn
+        .fetch_one("SELECT value FROM with_years ORDER BY id DESC")
+        .await?;
+    assert_eq!(row.try_get::<u16, _>(0)?, 0);
+
+    let row = conn
+        .fetch_one("SELECT value FROM with_years ORDER BY id")
+        .await?;
+    assert_eq!(row.try_get::<u16, _>(0)?, 2024);
+    assert!(row.try_get::<u8, _>(0).is_err());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_text_adapter() -> anyhow::Result<()> {
     #[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
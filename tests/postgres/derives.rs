@@ -1,9 +1,10 @@
 use futures::TryStreamExt;
 use sqlx::postgres::types::PgRange;
-use sqlx::{Connection, Executor, FromRow, Postgres};
+use sqlx::{Connection, Executor, FromRow, Postgres, Row};
 use sqlx_postgres::PgHasArrayType;
 use sqlx_test::{new, test_type};
 use std::fmt::Debug;
+use std::marker::PhantomData;
 use std::ops::Bound;
 
 // Transparent types are rust-side wrappers over DB types
@@ -18,6 +19,20 @@ struct Transparent(i32);
 #[sqlx(transparent, no_pg_array)]
 struct TransparentArray(Vec<i64>);
 
+// Transparent newtypes may carry `PhantomData` markers, e.g. to tag ids with their table
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct Id<T>(i64, PhantomData<T>);
+
+#[derive(PartialEq, Debug)]
+struct User;
+
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct TransparentNamed {
+    name: String,
+}
+
 #[sqlx_macros::test]
 async fn test_transparent_slice_to_array() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -53,6 +68,18 @@ enum Strong {
     Three,
 }
 
+// Unknown values of a strong enum may be decoded into a catch-all variant
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(type_name = "text")]
+#[sqlx(rename_all = "lowercase")]
+enum StrongOther {
+    One,
+    Two,
+
+    #[sqlx(other)]
+    Unknown(String),
+}
+
 // rename_all variants
 #[derive(PartialEq, Debug, sqlx::Type)]
 #[sqlx(type_name = "color_lower")]
@@ -152,6 +179,25 @@ test_type!(transparent_array<TransparentArray>(Postgres,
     "'{ 23523, 123456, 789 }'::int8[]" == TransparentArray(vec![23523, 123456, 789])
 ));
 
+test_type!(transparent_typed_id<Id<User>>(Postgres,
+    "0::int8" == Id::<User>(0, PhantomData),
+    "23523::int8" == Id::<User>(23523, PhantomData)
+));
+
+test_type!(transparent_typed_id_option<Option<Id<User>>>(Postgres,
+    "NULL::int8" == None::<Id<User>>,
+    "23523::int8" == Some(Id::<User>(23523, PhantomData))
+));
+
+test_type!(transparent_typed_id_vec<Vec<Id<User>>>(Postgres,
+    "'{}'::int8[]" == Vec::<Id<User>>::new(),
+    "'{ 1, 23523 }'::int8[]" == vec![Id::<User>(1, PhantomData), Id(23523, PhantomData)]
+));
+
+test_type!(transparent_named<TransparentNamed>(Postgres,
+    "'sqlx'::text" == TransparentNamed { name: "sqlx".into() }
+));
+
 test_type!(weak_enum<Weak>(Postgres,
     "0::int4" == Weak::One,
     "2::int4" == Weak::Two,
@@ -172,6 +218,16 @@ test_type!(strong_enum_array<Vec<Strong>>(Postgres,
     "ARRAY['one', 'two', 'four']" == vec![Strong::One, Strong::Two, Strong::Three],
 ));
 
+test_type!(strong_enum_other<StrongOther>(Postgres,
+    "'one'::text" == StrongOther::One,
+    "'two'::text" == StrongOther::Two,
+    "'three'::text" == StrongOther::Unknown("three".into()),
+));
+
+test_type!(strong_enum_other_array<Vec<StrongOther>>(Postgres,
+    "ARRAY['one', 'unknown']" == vec![StrongOther::One, StrongOther::Unknown("unknown".into())],
+));
+
 test_type!(floatrange<FloatRange>(Postgres,
     "'[1.234, 5.678]'::float_range" == FloatRange(PgRange::from((Bound::Included(1.234), Bound::Included(5.678)))),
 ));
@@ -395,6 +451,69 @@ SELECT $1 = ROW('fuzzy dice', 42, 199)::inventory_item, $1
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_record_type_array() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let value = vec![
+        InventoryItem {
+            name: "fuzzy dice".to_owned(),
+            supplier_id: Some(42),
+            price: Some(199),
+        },
+        InventoryItem {
+            name: "dice, fuzzy \"red\"".to_owned(),
+            supplier_id: None,
+            price: None,
+        },
+    ];
+
+    let rec: (bool, Vec<InventoryItem>) = sqlx::query_as(
+        r#"
+SELECT $1 = ARRAY[ROW('fuzzy dice', 42, 199), ROW('dice, fuzzy "red"', NULL, NULL)]::inventory_item[], $1
+        "#,
+    )
+    .bind(&value)
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(rec.0);
+    assert_eq!(rec.1, value);
+
+    // arrays of records are also decoded from the text format
+    let row = conn
+        .fetch_one(
+            r#"SELECT ARRAY[ROW('fuzzy dice', 42, 199), ROW('dice, fuzzy "red"', NULL, NULL)]::inventory_item[]"#,
+        )
+        .await?;
+    assert_eq!(row.try_get::<Vec<InventoryItem>, _>(0)?, value);
+
+    let empty: Vec<InventoryItem> = sqlx::query_scalar("SELECT '{}'::inventory_item[]")
+        .fetch_one(&mut conn)
+        .await?;
+    assert!(empty.is_empty());
+
+    let with_nulls: Vec<Option<InventoryItem>> = sqlx::query_scalar(
+        "SELECT ARRAY[NULL, ROW('fuzzy dice', 42, 199), NULL]::inventory_item[]",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    assert_eq!(
+        with_nulls,
+        vec![
+            None,
+            Some(InventoryItem {
+                name: "fuzzy dice".to_owned(),
+                supplier_id: Some(42),
+                price: Some(199),
+            }),
+            None
+        ]
+    );
+
+    Ok(())
+}
+
 #[cfg(feature = "macros")]
 #[sqlx_macros::test]
 async fn test_new_type() {
@@ -734,6 +853,104 @@ async fn test_skip() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_deny_unknown_columns() -> anyhow::Result<()> {
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(deny_unknown_columns, rename_all = "camelCase")]
+    struct Account {
+        account_id: i32,
+        #[sqlx(rename = "display_name")]
+        name: String,
+        #[sqlx(skip)]
+        extra: Option<i32>,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let account: Account = sqlx::query_as(r#"SELECT 1 AS "accountId", 'foo' AS display_name"#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(account.account_id, 1);
+    assert_eq!(account.name, "foo");
+    assert_eq!(account.extra, None);
+
+    let err = sqlx::query_as::<_, Account>(
+        r#"SELECT 1 AS "accountId", 'foo' AS display_name, 2 AS extra"#,
+    )
+    .fetch_one(&mut conn)
+    .await
+    .unwrap_err();
+
+    assert!(
+        matches!(&err, sqlx::Error::ColumnNotMapped(column) if column == "extra"),
+        "{err:?}"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_from_row_field_decode_error() -> anyhow::Result<()> {
+    #[derive(Debug, sqlx::FromRow)]
+    struct User {
+        #[sqlx(rename = "created")]
+        created_at: Option<i64>,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Pair(i32, Vec<String>);
+
+    let mut conn = new::<Postgres>().await?;
+
+    let err = sqlx::query_as::<_, User>("SELECT 'yesterday'::text AS created")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    match &err {
+        sqlx::Error::FieldDecode {
+            column,
+            field,
+            type_name,
+            source,
+        } => {
+            assert_eq!(column, "\"created\"");
+            assert_eq!(*field, "User.created_at");
+            assert_eq!(*type_name, "Option<i64>");
+            assert!(source.is::<sqlx::error::MismatchedTypes>(), "{source:?}");
+        }
+        _ => panic!("expected `FieldDecode`, got {err:?}"),
+    }
+
+    assert!(err
+        .to_string()
+        .starts_with("error occurred while decoding column \"created\" into field User.created_at (Option<i64>): "));
+
+    let err = sqlx::query_as::<_, Pair>("SELECT 1, 2")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(
+            &err,
+            sqlx::Error::FieldDecode { column, field: "Pair.1", type_name: "Vec<String>", .. } if column == "1"
+        ),
+        "{err:?}"
+    );
+
+    // a missing column is still reported as such
+    let err = sqlx::query_as::<_, User>("SELECT 1")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::ColumnNotFound(_)), "{err:?}");
+
+    Ok(())
+}
+
 #[cfg(feature = "macros")]
 #[sqlx_macros::test]
 async fn test_enum_with_schema() -> anyhow::Result<()> {
@@ -810,3 +1027,34 @@ async fn test_custom_pg_array() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn test_enum_decode_error_lists_variants() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let err = sqlx::query_scalar::<_, Strong>("SELECT 'three'::text")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(
+        err.contains(
+            r#"invalid value "three" for enum Strong, expected one of: "one", "two", "four""#
+        ),
+        "{err}"
+    );
+
+    let err = sqlx::query_scalar::<_, Weak>("SELECT 3::int4")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(
+        err.contains("invalid value 3 for enum Weak, expected one of: One = 0, Two = 2, Three = 4"),
+        "{err}"
+    );
+
+    Ok(())
+}
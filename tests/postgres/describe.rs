@@ -1,5 +1,6 @@
 use sqlx::{postgres::Postgres, Column, Executor, TypeInfo};
-use sqlx_test::new;
+use sqlx::Either;
+use sqlx_test::{new, pool};
 
 #[sqlx_macros::test]
 async fn it_describes_simple() -> anyhow::Result<()> {
@@ -25,6 +26,28 @@ async fn it_describes_simple() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_describes_with_a_pool() -> anyhow::Result<()> {
+    let pool = pool::<Postgres>().await?;
+
+    let sql = "SELECT id, text FROM tweet WHERE owner_id = $1";
+    let d = sqlx::describe::describe(&pool, sql).await?;
+
+    assert_eq!(d.columns().len(), 2);
+    assert_eq!(d.columns()[0].name(), "id");
+    assert_eq!(d.columns()[1].name(), "text");
+    assert_eq!(d.nullable(0), Some(false));
+    assert_eq!(d.nullable(1), Some(false));
+
+    let Some(Either::Left(parameters)) = d.parameters() else {
+        panic!("expected parameter types, got {:?}", d.parameters());
+    };
+    assert_eq!(parameters.len(), 1);
+    assert_eq!(parameters[0].name(), "INT8");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_describes_expression() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -21,6 +21,47 @@ async fn test_query() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_query_catalog_name_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let rows = sqlx::query!(
+        "SELECT c.relname, a.attname FROM pg_class c \
+         JOIN pg_attribute a ON a.attrelid = c.oid \
+         WHERE c.relname = $1 AND a.attnum > 0 ORDER BY a.attnum",
+        "tweet"
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    let relname: &String = &rows[0].relname;
+    assert_eq!(relname, "tweet");
+    assert_eq!(rows[0].attname, "id");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_catalog_vector_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // the primary key of `tweet` is its first column, `id`
+    let index = sqlx::query!(
+        "SELECT i.indkey, i.indclass FROM pg_index i \
+         JOIN pg_class c ON c.oid = i.indrelid \
+         WHERE c.relname = $1 AND i.indisprimary",
+        "tweet"
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    let indkey: Vec<i16> = index.indkey;
+    assert_eq!(indkey, [1]);
+    assert_eq!(index.indclass.len(), 1);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_non_null() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -83,6 +124,16 @@ async fn test_text_var_char_char_n() -> anyhow::Result<()> {
 
     assert_eq!(rec.greeting.as_deref(), Some("Hello"));
 
+    // the padding is trimmed by default, and kept with `PgBpChar`
+    let rec = sqlx::query!(
+        r#"SELECT 'Hi'::char(5) as greeting, 'Hi'::char(5) as "padded: sqlx::postgres::types::PgBpChar""#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(rec.greeting.as_deref(), Some("Hi"));
+    assert_eq!(rec.padded.as_deref(), Some("Hi   "));
+
     Ok(())
 }
 
@@ -152,6 +203,41 @@ async fn test_query_as() -> anyhow::Result<()> {
     Ok(())
 }
 
+sqlx::query_as_named!(
+    "NamedAccount",
+    r#"SELECT id "id!", name from (VALUES (1, $1::text)) accounts(id, name)"#
+);
+
+fn named_account_name(account: NamedAccount) -> Option<String> {
+    account.name
+}
+
+#[sqlx_macros::test]
+async fn test_query_as_named() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let account = sqlx::query_as!(
+        NamedAccount,
+        r#"SELECT id "id!", name from (VALUES (1, $1::text)) accounts(id, name)"#,
+        "Herp Derpinson"
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!(named_account_name(account).as_deref(), Some("Herp Derpinson"));
+
+    // The struct also works with the runtime-checked API.
+    let account: NamedAccount = sqlx::query_as(r#"SELECT 2 "id!", null::text "name""#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2, account.id);
+    assert_eq!(None, account.name);
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct RawAccount {
     r#type: i32,
@@ -314,6 +400,81 @@ async fn query_by_bigdecimal() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_infer_interval_array() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgInterval;
+    let mut conn = new::<Postgres>().await?;
+
+    let result = sqlx::query!(r#"SELECT ARRAY['1 day'::interval] as "intervals!""#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let intervals: Vec<PgInterval> = result.intervals;
+    assert_eq!(
+        intervals,
+        [PgInterval {
+            months: 0,
+            days: 1,
+            microseconds: 0
+        }]
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_infer_tid() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgTid;
+    let mut conn = new::<Postgres>().await?;
+
+    let result = sqlx::query!(r#"SELECT '(3,4)'::tid as "tid!", ARRAY['(0,1)'::tid] as "tids!""#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    let tid: PgTid = result.tid;
+    assert_eq!(tid, PgTid { block: 3, offset: 4 });
+    let tids: Vec<PgTid> = result.tids;
+    assert_eq!(tids, [PgTid { block: 0, offset: 1 }]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+#[cfg(feature = "bytes")]
+async fn query_by_bytes() -> anyhow::Result<()> {
+    use sqlx::types::Bytes;
+    let mut conn = new::<Postgres>().await?;
+
+    let bytes = Bytes::from_static(b"\xDE\xAD\xBE\xEF");
+
+    let result = sqlx::query!(r#"SELECT $1::bytea as "bytes!: Bytes""#, &bytes)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(result.bytes, bytes);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+#[cfg(feature = "json")]
+async fn test_json_map_override() -> anyhow::Result<()> {
+    use sqlx::types::{Json, JsonMap};
+
+    let mut conn = new::<Postgres>().await?;
+
+    let rec = sqlx::query!(
+        r#"SELECT '{"a": 1}'::jsonb as "map!: JsonMap", '{"b": 2}'::json as "wrapped!: Json<JsonMap>""#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(rec.map["a"], 1);
+    assert_eq!(rec.wrapped["b"], 2);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_nullable_err() -> anyhow::Result<()> {
     #[allow(dead_code)]
@@ -387,6 +548,41 @@ async fn test_array_from_slice() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_nullable_array_params() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let list: Vec<i32> = vec![1, 2];
+    let result = sqlx::query!("SELECT $1::int[] as my_array", list)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(result.my_array, Some(vec![1, 2]));
+
+    // `None` is NULL, `Some(vec![])` is an empty array
+    for list in [None, Some(vec![]), Some(vec![1_i32, 2])] {
+        let result = sqlx::query!(
+            "SELECT $1::int[] as my_array, $1::int[] IS NULL as is_null",
+            list
+        )
+        .fetch_one(&mut conn)
+        .await?;
+        assert_eq!(result.my_array, list);
+        assert_eq!(result.is_null, Some(list.is_none()));
+
+        let result = sqlx::query!("SELECT $1::int[] as my_array", &list)
+            .fetch_one(&mut conn)
+            .await?;
+        assert_eq!(result.my_array, list);
+
+        let result = sqlx::query!("SELECT $1::int[] as my_array", list.as_deref())
+            .fetch_one(&mut conn)
+            .await?;
+        assert_eq!(result.my_array, list);
+    }
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn fetch_is_usable_issue_224() -> anyhow::Result<()> {
     // ensures that the stream returned by `query::Map::fetch()` is usable with `TryStreamExt`
@@ -556,6 +752,24 @@ async fn test_column_override_exact_nullable() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_column_override_multidimensional_array() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let matrix = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+
+    let record = sqlx::query!(
+        r#"select $1::float8[] as "matrix!: Vec<Vec<f64>>""#,
+        matrix as Vec<Vec<f64>>
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(record.matrix, vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_bind_arg_override_exact() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
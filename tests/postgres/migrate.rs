@@ -1,9 +1,10 @@
-use sqlx::migrate::Migrator;
+use sqlx::migrate::{AnalyzeAfter, MigrateError, Migration, MigrationType, Migrator};
 use sqlx::pool::PoolConnection;
 use sqlx::postgres::{PgConnection, Postgres};
 use sqlx::Executor;
 use sqlx::Row;
 use std::path::Path;
+use std::time::Duration;
 
 #[sqlx::test(migrations = false)]
 async fn simple(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
@@ -27,6 +28,203 @@ async fn simple(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn analyze_after(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/postgres/migrations_simple")).await?;
+    migrator.set_analyze_after(AnalyzeAfter::Touched);
+
+    // run migration
+    migrator.run(&mut conn).await?;
+
+    // `ANALYZE` replaces the `-1` placeholder for a table that was never analyzed
+    let reltuples: f32 = conn
+        .fetch_one("SELECT reltuples FROM pg_class WHERE relname = 'migrations_simple_test'")
+        .await?
+        .get(0);
+    assert_eq!(reltuples, 1.0);
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn schema(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+    conn.execute(
+        "DROP SCHEMA IF EXISTS migrations_schema CASCADE; CREATE SCHEMA migrations_schema",
+    )
+    .await?;
+
+    let search_path: String = conn.fetch_one("SHOW search_path").await?.get(0);
+
+    let mut migrator = Migrator::new(Path::new("tests/postgres/migrations_simple")).await?;
+    migrator.set_schema("migrations_schema")?;
+
+    // run migration
+    migrator.run(&mut conn).await?;
+
+    // check outcome
+    let res: String = conn
+        .fetch_one("SELECT some_payload FROM migrations_schema.migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, "110_suffix");
+
+    let applied: i64 = conn
+        .fetch_one("SELECT count(*) FROM migrations_schema._sqlx_migrations")
+        .await?
+        .get(0);
+    assert_eq!(applied, 2);
+
+    // nothing was created in the default schema, which is restored afterwards
+    let tables: i64 = conn
+        .fetch_one("SELECT count(*) FROM pg_tables WHERE schemaname = 'public' AND tablename LIKE '%migrations%'")
+        .await?
+        .get(0);
+    assert_eq!(tables, 0);
+
+    let res: String = conn.fetch_one("SHOW search_path").await?.get(0);
+    assert_eq!(res, search_path);
+
+    // running it a 2nd time should still work
+    migrator.run(&mut conn).await?;
+
+    // the search path is also restored when a migration fails
+    let mut failing = Migrator::from_iter([Migration::new(
+        1,
+        "fails".into(),
+        MigrationType::Simple,
+        "SELECT 1 / 0".into(),
+        false,
+    )]);
+    failing.set_schema("migrations_schema")?;
+    failing.set_ignore_missing(true);
+    assert!(failing.run(&mut conn).await.is_err());
+
+    let res: String = conn.fetch_one("SHOW search_path").await?.get(0);
+    assert_eq!(res, search_path);
+
+    conn.execute("DROP SCHEMA migrations_schema CASCADE")
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn statement_timeout(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+    conn.execute("SET statement_timeout = 100").await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/postgres/migrations_slow")).await?;
+
+    // keeping the connection's statement timeout aborts the migration
+    migrator.set_statement_timeout(None);
+    assert!(migrator.run(&mut conn).await.is_err());
+
+    // by default, migrations run without a statement timeout
+    migrator.set_statement_timeout(Some(Duration::ZERO));
+    migrator.run(&mut conn).await?;
+
+    // afterwards the connection's statement timeout is restored
+    let res: String = conn.fetch_one("SHOW statement_timeout").await?.get(0);
+    assert_eq!(res, "100ms");
+
+    // also when a migration fails
+    let mut failing = Migrator::from_iter([Migration::new(
+        1,
+        "fails".into(),
+        MigrationType::Simple,
+        "SELECT 1 / 0".into(),
+        false,
+    )]);
+    failing.set_ignore_missing(true);
+    assert!(failing.run(&mut conn).await.is_err());
+
+    let res: String = conn.fetch_one("SHOW statement_timeout").await?.get(0);
+    assert_eq!(res, "100ms");
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn skip_versions(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/postgres/migrations_simple")).await?;
+
+    // a version that does not exist is a mistake
+    migrator.skip_versions(&[20220721115250, 1]);
+    assert!(matches!(
+        migrator.run(&mut conn).await,
+        Err(MigrateError::VersionNotPresent(1))
+    ));
+
+    // which is caught before touching the database
+    let exists: bool = conn
+        .fetch_one("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .await?
+        .get(0);
+    assert!(!exists);
+
+    migrator.skip_versions(&[20220721115524]);
+    migrator.run(&mut conn).await?;
+
+    // the conversion of the payload did not run
+    let res: i64 = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, 100);
+
+    let skipped: Vec<i64> = conn
+        .fetch_all("SELECT version FROM _sqlx_migrations WHERE skipped ORDER BY version")
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(skipped, [20220721115524]);
+
+    // it counts as applied from now on
+    let migrator = Migrator::new(Path::new("tests/postgres/migrations_simple")).await?;
+    migrator.run(&mut conn).await?;
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn execution_time(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
+    use sqlx::migrate::Migrate;
+    use std::sync::{Arc, Mutex};
+
+    clean_up(&mut conn).await?;
+
+    let timings = Arc::new(Mutex::new(Vec::new()));
+    let mut migrator = Migrator::new(Path::new("tests/postgres/migrations_reversible")).await?;
+    migrator.set_on_applied({
+        let timings = Arc::clone(&timings);
+        move |migration, elapsed| timings.lock().unwrap().push((migration.version, elapsed))
+    });
+
+    migrator.run(&mut conn).await?;
+
+    let applied = conn.list_applied_migrations().await?;
+    let recorded: Vec<_> = applied
+        .iter()
+        .map(|m| (m.version, m.execution_time.unwrap()))
+        .collect();
+    assert_eq!(*timings.lock().unwrap(), recorded);
+    assert_eq!(recorded.len(), 2);
+
+    // reverted migrations are reported too
+    migrator.undo(&mut conn, 20220721124650).await?;
+    let timings = timings.lock().unwrap();
+    assert_eq!(timings.len(), 3);
+    assert_eq!(timings[2].0, 20220721125033);
+
+    Ok(())
+}
+
 #[sqlx::test(migrations = false)]
 async fn reversible(mut conn: PoolConnection<Postgres>) -> anyhow::Result<()> {
     clean_up(&mut conn).await?;
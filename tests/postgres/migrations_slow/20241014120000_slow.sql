@@ -0,0 +1,2 @@
+-- longer than the statement timeout set by the test
+SELECT pg_sleep(0.5);
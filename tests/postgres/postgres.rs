@@ -2,10 +2,10 @@ use futures::{Stream, StreamExt, TryStreamExt};
 
 use sqlx::postgres::types::Oid;
 use sqlx::postgres::{
-    PgAdvisoryLock, PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgListener,
-    PgPoolOptions, PgRow, PgSeverity, Postgres,
+    PgAdvisoryLock, PgConnectOptions, PgConnection, PgCopyOnError, PgDatabaseError,
+    PgErrorPosition, PgListener, PgPlanCacheMode, PgPoolOptions, PgRow, PgSeverity, Postgres,
 };
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
+use sqlx::{Column, ConnectOptions, Connection, Executor, Row, Statement, TypeInfo};
 use sqlx_core::{bytes::Bytes, error::BoxDynError};
 use sqlx_test::{new, pool, setup_if_needed};
 use std::env;
@@ -48,6 +48,554 @@ async fn it_pings() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_reports_server_process_id() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let pid: i32 = sqlx::query_scalar("select pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(conn.server_process_id(), Some(pid as u32));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_queries_on_checked_out_connections() -> anyhow::Result<()> {
+    let pool = pool::<Postgres>().await?;
+
+    let mut conn = pool.acquire().await?;
+
+    let query = sqlx::query("select pg_sleep(30)").execute(&mut *conn);
+
+    let cancel = async {
+        sqlx_core::rt::sleep(Duration::from_millis(500)).await;
+        pool.cancel_all().await;
+    };
+
+    let (res, ()) = sqlx_core::rt::timeout(Duration::from_secs(10), async {
+        futures::join!(query, cancel)
+    })
+    .await
+    .expect("query was not cancelled");
+
+    let err = res.unwrap_err();
+    let err = err.as_database_error().expect("expected a database error");
+    assert_eq!(err.code().as_deref(), Some("57014"), "{err}");
+
+    // the connection is still usable after the cancelled query
+    let value: i32 = sqlx::query_scalar("select 1").fetch_one(&mut *conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_requires_transactions_for_writes() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .require_transaction_for_writes(true)
+        .after_connect(|conn, _meta| {
+            Box::pin(async move {
+                conn.execute("CREATE TEMPORARY TABLE strict_writes (id INT4)")
+                    .await?;
+                Ok(())
+            })
+        })
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let err = sqlx::query("INSERT INTO strict_writes (id) VALUES (1)")
+        .execute(&pool)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::TransactionRequired), "{err:?}");
+
+    let err = pool
+        .execute("SELECT 1; DELETE FROM strict_writes")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::TransactionRequired), "{err:?}");
+
+    for sql in [
+        "DO $$BEGIN DELETE FROM strict_writes; END$$",
+        "SELECT * INTO TEMPORARY strict_copy FROM strict_writes",
+    ] {
+        let err = pool.execute(sql).await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::TransactionRequired), "{sql:?}: {err:?}");
+    }
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("INSERT INTO strict_writes (id) VALUES (1)")
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    // a transaction started with a raw `BEGIN` counts too
+    let mut conn = pool.acquire().await?;
+    conn.execute("BEGIN").await?;
+    conn.execute("INSERT INTO strict_writes (id) VALUES (2)").await?;
+    conn.execute("ROLLBACK").await?;
+    drop(conn);
+
+    // reads are still allowed, including locking ones
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM strict_writes")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(count, 1);
+
+    pool.execute("WITH ids AS (SELECT id FROM strict_writes) SELECT * FROM ids FOR UPDATE")
+        .await?;
+
+    // detached connections are no longer restricted
+    let mut conn = pool.acquire().await?.detach();
+    conn.execute("DELETE FROM strict_writes").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_retries_reads_on_disconnect() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        // otherwise the dead connection would be caught when it's acquired
+        .test_before_acquire(false)
+        .retry_read_on_disconnect(true)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut admin = new::<Postgres>().await?;
+
+    async fn terminate(pid: i32, admin: &mut PgConnection) -> anyhow::Result<()> {
+        sqlx::query("SELECT pg_terminate_backend($1)")
+            .bind(pid)
+            .execute(admin)
+            .await?;
+        sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+        Ok(())
+    }
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&pool)
+        .await?;
+
+    terminate(pid, &mut admin).await?;
+
+    let (new_pid, value): (i32, i32) = sqlx::query_as("SELECT pg_backend_pid(), $1")
+        .bind(42_i32)
+        .fetch_one(&pool)
+        .await?;
+    assert_ne!(new_pid, pid);
+    assert_eq!(value, 42);
+
+    // give the connection time to be returned to the pool
+    sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+
+    terminate(new_pid, &mut admin).await?;
+
+    // writes are never retried
+    let err = pool
+        .execute("CREATE TEMPORARY TABLE retried_writes (id INT4)")
+        .await
+        .unwrap_err();
+    let code = err.as_database_error().and_then(|e| e.code());
+    assert_eq!(code.as_deref(), Some("57P01"), "{err:?}");
+
+    // neither are statements that may write, even if they don't start with a write keyword;
+    // a retry would fail with a different error as the procedure doesn't exist
+    for sql in ["CALL retried_procedure()", "DO $$BEGIN PERFORM 1; END$$"] {
+        let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+            .fetch_one(&pool)
+            .await?;
+        sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+
+        terminate(pid, &mut admin).await?;
+
+        let err = pool.execute(sql).await.unwrap_err();
+        let code = err.as_database_error().and_then(|e| e.code());
+        assert_eq!(code.as_deref(), Some("57P01"), "{sql:?}: {err:?}");
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_verifies_search_path() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .options([("search_path", "public")])
+        .verify_search_path("public");
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+    conn.ping().await?;
+
+    conn.execute("SET search_path = pg_catalog").await?;
+    let err = conn.ping().await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::Configuration(_)), "{err:?}");
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *conn)
+        .await?;
+    conn.execute("SET search_path = pg_catalog").await?;
+    drop(conn);
+
+    // the drifted connection is not handed out again
+    let mut conn = pool.acquire().await?;
+    let new_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_ne!(new_pid, pid);
+
+    let search_path: String = sqlx::query_scalar("SHOW search_path")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(search_path, "public");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_the_warmup_query_on_connect() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .warmup_query("SELECT set_config('sqlx.warm', 'yes', false)");
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+    let warm: String = sqlx::query_scalar("SELECT current_setting('sqlx.warm')")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(warm, "yes");
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[sqlx_macros::test]
+async fn it_connects_over_an_inherited_socket() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?.parse::<PgConnectOptions>()?;
+
+    // stands in for a socket passed down by a supervising process
+    let socket = std::net::TcpStream::connect((options.get_host(), options.get_port()))?;
+    let options = options.host("not-resolvable.invalid").connect_fd(socket);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+    let value: i32 = sqlx::query_scalar("SELECT $1 + 1")
+        .bind(41_i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 42);
+
+    // a cancel request would need a connection of its own
+    assert!(conn.cancel_token().is_none());
+
+    // the socket can only carry one connection
+    let err = PgConnection::connect_with(&options).await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::Configuration(_)), "{err:?}");
+
+    conn.close().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_caches_query_results() -> anyhow::Result<()> {
+    use sqlx::{CachingExecutor, QueryCache};
+
+    let mut conn = new::<Postgres>().await?;
+    let cache = QueryCache::new(10);
+
+    conn.execute("CREATE TEMPORARY TABLE cached_colors (name TEXT, warm BOOL)")
+        .await?;
+    conn.execute("INSERT INTO cached_colors VALUES ('red', true), ('blue', false)")
+        .await?;
+
+    let sql = "SELECT name FROM cached_colors WHERE warm = $1 ORDER BY name";
+
+    let warm: Vec<String> = sqlx::query_scalar(sql)
+        .bind(true)
+        .cacheable(Duration::from_millis(500))
+        .fetch_all(CachingExecutor::new(&mut conn, &cache))
+        .await?;
+    assert_eq!(warm, ["red"]);
+    assert_eq!(cache.len(), 1);
+
+    // `execute()` always runs
+    sqlx::query("INSERT INTO cached_colors VALUES ('orange', true)")
+        .cacheable(Duration::from_millis(500))
+        .execute(CachingExecutor::new(&mut conn, &cache))
+        .await?;
+
+    // the stale result is returned for the same arguments...
+    let cached: Vec<String> = sqlx::query_scalar(sql)
+        .bind(true)
+        .cacheable(Duration::from_millis(500))
+        .fetch_all(CachingExecutor::new(&mut conn, &cache))
+        .await?;
+    assert_eq!(cached, ["red"]);
+
+    let first: String = sqlx::query_scalar(sql)
+        .bind(true)
+        .cacheable(Duration::from_millis(500))
+        .fetch_one(CachingExecutor::new(&mut conn, &cache))
+        .await?;
+    assert_eq!(first, "red");
+
+    // ...but not for other arguments or queries that aren't cacheable
+    let cold: Vec<String> = sqlx::query_scalar(sql)
+        .bind(false)
+        .cacheable(Duration::from_millis(500))
+        .fetch_all(CachingExecutor::new(&mut conn, &cache))
+        .await?;
+    assert_eq!(cold, ["blue"]);
+
+    let fresh: Vec<String> = sqlx::query_scalar(sql)
+        .bind(true)
+        .fetch_all(CachingExecutor::new(&mut conn, &cache))
+        .await?;
+    assert_eq!(fresh, ["orange", "red"]);
+    assert_eq!(cache.len(), 2);
+
+    // expired results are fetched again
+    sqlx_core::rt::sleep(Duration::from_millis(600)).await;
+
+    let refreshed: Vec<String> = sqlx::query_scalar(sql)
+        .bind(true)
+        .cacheable(Duration::from_millis(500))
+        .fetch_all(CachingExecutor::new(&mut conn, &cache))
+        .await?;
+    assert_eq!(refreshed, ["orange", "red"]);
+
+    cache.clear();
+    assert!(cache.is_empty());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rewrites_sql_before_sending() -> anyhow::Result<()> {
+    use sqlx::ConnectOptions;
+    use std::borrow::Cow;
+
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .sql_rewriter(|sql| Cow::Owned(format!("{sql} /* app='sqlx-test' */")));
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    // prepared
+    let sql = "SELECT current_query()";
+    let query: String = sqlx::query_scalar(sql).fetch_one(&mut conn).await?;
+    assert_eq!(query, "SELECT current_query() /* app='sqlx-test' */");
+
+    // the statement is cached under the original SQL
+    let _: String = sqlx::query_scalar(sql).fetch_one(&mut conn).await?;
+    assert_eq!(conn.cached_statements_size(), 1);
+
+    // unprepared
+    let query: String = sqlx::raw_sql("SELECT current_query()")
+        .fetch_one(&mut conn)
+        .await?
+        .try_get(0)?;
+    assert_eq!(query, "SELECT current_query() /* app='sqlx-test' */");
+
+    // statements sent by sqlx itself still work
+    let mut tx = conn.begin().await?;
+    let query: String = sqlx::query_scalar(sql).fetch_one(&mut *tx).await?;
+    assert!(query.ends_with("/* app='sqlx-test' */"));
+    tx.commit().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_appends_dev_auto_limit() -> anyhow::Result<()> {
+    use sqlx::ConnectOptions;
+
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .dev_auto_limit(Some(3));
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let sql = "SELECT * FROM generate_series(1, 10)";
+    let rows: Vec<i32> = sqlx::query_scalar(sql).fetch_all(&mut conn).await?;
+    assert_eq!(rows, [1, 2, 3]);
+
+    // an existing limit is kept
+    let sql = "SELECT * FROM generate_series(1, 10) LIMIT 5";
+    let rows: Vec<i32> = sqlx::query_scalar(sql).fetch_all(&mut conn).await?;
+    assert_eq!(rows.len(), 5);
+
+    // as are limits in subqueries
+    let sql = "WITH s AS (SELECT * FROM generate_series(1, 10) LIMIT 5) SELECT count(*) FROM s";
+    let count: i64 = sqlx::query_scalar(sql).fetch_one(&mut conn).await?;
+    assert_eq!(count, 5);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_prefixes_table_names() -> anyhow::Result<()> {
+    use sqlx::{PrefixedExecutor, TablePrefix};
+
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    tx.execute(
+        r#"
+        CREATE SCHEMA prefixed_test;
+        CREATE TABLE prefixed_test.prefixed_items (id INT4, name TEXT);
+        INSERT INTO prefixed_test.prefixed_items VALUES (1, 'prefixed_items');
+        "#,
+    )
+    .await?;
+
+    let prefix = TablePrefix::new("prefixed_test", ["prefixed_items"]);
+
+    let name: String = sqlx::query_scalar(
+        "SELECT name FROM prefixed_items WHERE id = $1 AND name = 'prefixed_items'",
+    )
+    .bind(1_i32)
+    .fetch_one(PrefixedExecutor::new(&mut *tx, &prefix))
+    .await?;
+    assert_eq!(name, "prefixed_items");
+
+    let statement = PrefixedExecutor::new(&mut *tx, &prefix)
+        .prepare("SELECT id FROM prefixed_items")
+        .await?;
+    assert_eq!(
+        statement.sql(),
+        "SELECT id FROM prefixed_test.prefixed_items"
+    );
+
+    // without the prefix, the table doesn't exist in the search path
+    let err = sqlx::query("SELECT * FROM prefixed_items")
+        .execute(&mut *tx)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Database(_)), "{err:?}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_defers_constraints_in_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+        CREATE TEMPORARY TABLE deferred_parent (id INT4 PRIMARY KEY);
+        CREATE TEMPORARY TABLE deferred_child (
+            parent_id INT4 CONSTRAINT "Deferred_FK" REFERENCES deferred_parent (id)
+                DEFERRABLE INITIALLY IMMEDIATE
+        );
+        "#,
+    )
+    .await?;
+
+    let err = conn.defer_constraints().await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::TransactionRequired), "{err:?}");
+
+    for defer_all in [true, false] {
+        let mut tx = conn.begin().await?;
+
+        if defer_all {
+            tx.defer_constraints().await?;
+        } else {
+            tx.defer_constraint("Deferred_FK").await?;
+        }
+
+        // the child row would violate the foreign key if it were checked immediately
+        tx.execute("DELETE FROM deferred_child; DELETE FROM deferred_parent")
+            .await?;
+        tx.execute("INSERT INTO deferred_child VALUES (1)").await?;
+        tx.execute("INSERT INTO deferred_parent VALUES (1)").await?;
+        tx.commit().await?;
+    }
+
+    // the constraint is checked on commit
+    let mut tx = conn.begin().await?;
+    tx.defer_constraints().await?;
+    tx.execute("INSERT INTO deferred_child VALUES (2)").await?;
+    let err = tx.commit().await.unwrap_err();
+    let err = err.as_database_error().expect("expected a database error");
+    assert_eq!(err.code().as_deref(), Some("23503"), "{err}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_formats_intervals_as_iso8601() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgInterval;
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("SET intervalstyle = iso_8601").await?;
+
+    for value in [
+        "0",
+        "1 month",
+        "1 year 2 months 3 days 04:05:06.5",
+        "-1 day +02:00:00",
+        "-1 year -1 month -0.5 seconds",
+        "1 microsecond",
+        "-2 weeks 36:00:00",
+    ] {
+        let (interval, text): (PgInterval, String) =
+            sqlx::query_as("select $1::interval, $1::interval::text")
+                .bind(value)
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(interval.to_iso8601(), text, "{value:?}");
+        assert_eq!(
+            PgInterval::from_iso8601(&text).unwrap(),
+            interval,
+            "{value:?}"
+        );
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_jagged_multidimensional_arrays() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let res = sqlx::query("select $1::int4[]")
+        .bind(vec![vec![1_i32, 2], vec![3]])
+        .fetch_one(&mut conn)
+        .await;
+
+    let err = res.unwrap_err();
+    assert!(matches!(err, sqlx::Error::Encode(_)), "{err:?}");
+    assert!(err.to_string().contains("matching dimensions"), "{err}");
+
+    // the connection is still usable
+    conn.ping().await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_pings_after_suspended_query() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -263,1674 +811,3598 @@ async fn it_executes_with_pool() -> anyhow::Result<()> {
     Ok(())
 }
 
-// https://github.com/launchbadge/sqlx/issues/104
 #[sqlx_macros::test]
-async fn it_can_return_interleaved_nulls_issue_104() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_labels_connections_with_the_pool_name() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    let tuple = sqlx::query("SELECT NULL, 10::INT, NULL, 20::INT, NULL, 40::INT, NULL, 80::INT")
-        .map(|row: PgRow| {
-            (
-                row.get::<Option<i32>, _>(0),
-                row.get::<Option<i32>, _>(1),
-                row.get::<Option<i32>, _>(2),
-                row.get::<Option<i32>, _>(3),
-                row.get::<Option<i32>, _>(4),
-                row.get::<Option<i32>, _>(5),
-                row.get::<Option<i32>, _>(6),
-                row.get::<Option<i32>, _>(7),
-            )
-        })
-        .fetch_one(&mut conn)
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .application_name("billing");
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .pool_name("reports")
+        .connect_with(options)
         .await?;
 
-    assert_eq!(tuple.0, None);
-    assert_eq!(tuple.1, Some(10));
-    assert_eq!(tuple.2, None);
-    assert_eq!(tuple.3, Some(20));
-    assert_eq!(tuple.4, None);
-    assert_eq!(tuple.5, Some(40));
-    assert_eq!(tuple.6, None);
-    assert_eq!(tuple.7, Some(80));
+    assert_eq!(pool.options().get_pool_name(), Some("reports"));
+    assert_eq!(
+        pool.connect_options().get_application_name(),
+        Some("billing[reports]")
+    );
+
+    let application_name: String = sqlx::query_scalar(
+        "SELECT application_name::text FROM pg_stat_activity WHERE pid = pg_backend_pid()",
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(application_name, "billing[reports]");
+
+    // the name is not applied twice when the options are set again
+    pool.set_connect_options((*pool.connect_options()).clone());
+    assert_eq!(
+        pool.connect_options().get_application_name(),
+        Some("billing[reports]")
+    );
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_fail_and_recover() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_returns_the_connection_after_a_transaction() -> anyhow::Result<()> {
+    let pool = pool::<Postgres>().await?;
 
-    for i in 0..10 {
-        // make a query that will fail
-        let res = conn
-            .execute("INSERT INTO not_found (column) VALUES (10)")
-            .await;
+    let mut tx = pool.begin().await?;
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *tx)
+        .await?;
+    let mut conn = tx.commit_returning_connection().await?;
 
-        assert!(res.is_err());
+    // `VACUUM` cannot run inside a transaction block
+    conn.execute("VACUUM pg_catalog.pg_class").await?;
+    let same_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert_eq!(same_pid, pid);
 
-        // now try and use the connection
-        let val: i32 = conn.fetch_one(&*format!("SELECT {i}::int4")).await?.get(0);
+    drop(conn);
 
-        assert_eq!(val, i);
-    }
+    let mut tx = pool.begin().await?;
+    tx.execute("CREATE TEMPORARY TABLE returned_after_rollback (id INT4)")
+        .await?;
+    let mut conn = tx.rollback_returning_connection().await?;
+
+    let exists: bool =
+        sqlx::query_scalar("SELECT to_regclass('returned_after_rollback') IS NOT NULL")
+            .fetch_one(&mut *conn)
+            .await?;
+    assert!(!exists);
+
+    // a transaction on a borrowed connection has no connection to return, and is rolled back
+    let mut tx = conn.begin().await?;
+    tx.execute("CREATE TEMPORARY TABLE not_returned (id INT4)")
+        .await?;
+    assert!(matches!(
+        tx.commit_returning_connection().await,
+        Err(sqlx::Error::Configuration(_))
+    ));
+
+    let exists: bool = sqlx::query_scalar("SELECT to_regclass('not_returned') IS NOT NULL")
+        .fetch_one(&mut *conn)
+        .await?;
+    assert!(!exists);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_fail_and_recover_with_pool() -> anyhow::Result<()> {
-    let pool = sqlx_test::pool::<Postgres>().await?;
+async fn it_widens_integers_with_lenient_integer_decode() -> anyhow::Result<()> {
+    use sqlx::ConnectOptions;
 
-    for i in 0..10 {
-        // make a query that will fail
-        let res = pool
-            .execute("INSERT INTO not_found (column) VALUES (10)")
-            .await;
+    setup_if_needed();
 
-        assert!(res.is_err());
+    let sql = "SELECT 1::int2, 2::int4, 3::int8";
+    let mut conn = new::<Postgres>().await?;
 
-        // now try and use the connection
-        let val: i32 = pool.fetch_one(&*format!("SELECT {i}::int4")).await?.get(0);
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+    assert!(row.try_get::<i64, _>(1).is_err());
 
-        assert_eq!(val, i);
-    }
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .lenient_integer_decode(true);
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let (a, b, c): (i64, i64, i64) = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+    assert_eq!((a, b, c), (1, 2, 3));
+
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get::<i32, _>(0)?, 1);
+    assert_eq!(row.try_get::<Option<i64>, _>(0)?, Some(1));
+    // narrowing is still a mismatch, even if the value would fit
+    assert!(row.try_get::<i16, _>(1).is_err());
+    assert!(row.try_get::<i32, _>(2).is_err());
+
+    // the same goes for the text format
+    let row = sqlx::raw_sql(sql).fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get::<i64, _>(0)?, 1);
+    assert!(row.try_get::<i32, _>(2).is_err());
 
     Ok(())
 }
 
+#[cfg(feature = "chrono")]
 #[sqlx_macros::test]
-async fn it_can_query_scalar() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_rejects_naive_timestamps_with_strict_timezone() -> anyhow::Result<()> {
+    use sqlx::types::chrono::{DateTime, NaiveDateTime, Utc};
 
-    let scalar: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
-    assert_eq!(scalar, 42);
+    setup_if_needed();
 
-    let scalar: Option<i32> = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
-    assert_eq!(scalar, Some(42));
+    let sql = "SELECT '2024-01-01 12:00:00+00'::timestamptz AS at, '2024-01-01 12:00:00'::timestamp";
+    let naive = DateTime::from_timestamp(0, 0).unwrap().naive_utc();
 
-    let scalar: Option<i32> = sqlx::query_scalar("SELECT NULL")
-        .fetch_one(&mut conn)
-        .await?;
-    assert_eq!(scalar, None);
+    let mut conn = new::<Postgres>().await?;
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+    row.try_get_unchecked::<NaiveDateTime, _>("at")?;
+    sqlx::query("SELECT $1").bind(naive).execute(&mut conn).await?;
 
-    let scalar: Option<i64> = sqlx::query_scalar("SELECT 42::bigint")
-        .fetch_optional(&mut conn)
-        .await?;
-    assert_eq!(scalar, Some(42));
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .strict_timezone(true);
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-    let scalar: Option<i16> = sqlx::query_scalar("").fetch_optional(&mut conn).await?;
-    assert_eq!(scalar, None);
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+    let err = row.try_get_unchecked::<NaiveDateTime, _>("at").unwrap_err();
+    assert!(err.to_string().contains("\"at\""), "{err}");
+    assert!(err.to_string().contains("strict_timezone"), "{err}");
+    assert!(row.try_get::<DateTime<Utc>, _>(0).is_ok());
+    // a `TIMESTAMP` column has no time zone to lose
+    assert!(row.try_get::<NaiveDateTime, _>(1).is_ok());
+
+    // the same goes for arrays and the text format
+    let row = sqlx::query("SELECT ARRAY[now()]").fetch_one(&mut conn).await?;
+    assert!(row.try_get_unchecked::<Vec<NaiveDateTime>, _>(0).is_err());
+    assert!(row.try_get::<Vec<DateTime<Utc>>, _>(0).is_ok());
+
+    let row = sqlx::raw_sql(sql).fetch_one(&mut conn).await?;
+    assert!(row.try_get_unchecked::<NaiveDateTime, _>(0).is_err());
+    assert!(row.try_get::<DateTime<Utc>, _>(0).is_ok());
+
+    let res = sqlx::query("SELECT $1::int4, $2")
+        .bind(1_i32)
+        .bind(naive)
+        .execute(&mut conn)
+        .await;
+    assert!(
+        matches!(&res, Err(sqlx::Error::Encode(e)) if e.to_string().contains("$2")),
+        "{res:?}"
+    );
+
+    sqlx::query("SELECT $1")
+        .bind(naive.and_utc())
+        .execute(&mut conn)
+        .await?;
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-/// This is separate from `it_can_query_scalar` because while implementing it I ran into a
-/// bug which that prevented `Vec<i32>` from compiling but allowed Vec<Option<i32>>.
-async fn it_can_query_all_scalar() -> anyhow::Result<()> {
+async fn it_decodes_types_as_text_with_prefer_text_for() -> anyhow::Result<()> {
+    use sqlx::postgres::PgValueFormat;
+    use sqlx::ConnectOptions;
+
+    setup_if_needed();
+
+    let sql = "SELECT 'fat cat'::tsvector, $1::int4, ARRAY['a:1'::tsvector]";
     let mut conn = new::<Postgres>().await?;
 
-    let scalar: Vec<i32> = sqlx::query_scalar("SELECT $1")
-        .bind(42)
-        .fetch_all(&mut conn)
-        .await?;
-    assert_eq!(scalar, vec![42]);
+    let row = sqlx::query(sql).bind(7_i32).fetch_one(&mut conn).await?;
+    assert!(row.try_get::<String, _>(0).is_err());
 
-    let scalar: Vec<Option<i32>> = sqlx::query_scalar("SELECT $1 UNION ALL SELECT NULL")
-        .bind(42)
-        .fetch_all(&mut conn)
-        .await?;
-    assert_eq!(scalar, vec![Some(42), None]);
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .prefer_text_for(&["TSVECTOR", "int4", "tsvector[]"]);
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let row = sqlx::query(sql).bind(7_i32).fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get_raw(0)?.format(), PgValueFormat::Text);
+    assert_eq!(row.try_get::<String, _>(0)?, "'cat' 'fat'");
+    assert_eq!(row.try_get::<&str, _>(2)?, "{'a':1}");
+
+    // types that SQLx supports still decode from the text format
+    assert_eq!(row.try_get::<i32, _>(1)?, 7);
+    assert_eq!(row.try_get::<String, _>(1)?, "7");
 
     Ok(())
 }
 
-#[ignore]
 #[sqlx_macros::test]
-async fn copy_can_work_with_failed_transactions() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_gets_raw_values() -> anyhow::Result<()> {
+    use sqlx::postgres::PgValueFormat;
+    use sqlx::ValueRef;
 
-    // We're using a (local) statement_timeout to simulate a runtime failure, as opposed to
-    // a parse/plan failure.
-    let mut tx = conn.begin().await?;
-    let _ = sqlx::query("SELECT pg_catalog.set_config($1, $2, true)")
-        .bind("statement_timeout")
-        .bind("1ms")
-        .execute(tx.as_mut())
-        .await?;
+    let mut conn = new::<Postgres>().await?;
 
-    let mut copy_out: Pin<
-        Box<dyn Stream<Item = Result<Bytes, sqlx::Error>> + Send>,
-    > = (&mut tx)
-        .copy_out_raw("COPY (SELECT nspname FROM pg_catalog.pg_namespace WHERE pg_sleep(0.001) IS NULL) TO STDOUT")
+    let row = sqlx::query("SELECT 258::int4, NULL::int4")
+        .fetch_one(&mut conn)
         .await?;
-
-    while copy_out.try_next().await.is_ok() {}
-    drop(copy_out);
-
-    tx.rollback().await?;
-
-    // conn should be usable again, as we explictly rolled back the transaction
-    let got: i32 = sqlx::query_scalar("SELECT 1")
-        .fetch_one(conn.as_mut())
+    let value = row.try_get_raw(0)?;
+    assert_eq!(value.format(), PgValueFormat::Binary);
+    assert_eq!(value.type_info().name(), "INT4");
+    assert_eq!(value.as_bytes().unwrap(), [0, 0, 1, 2]);
+    assert!(row.try_get_raw(1)?.is_null());
+    assert!(row.try_get_raw(1)?.as_bytes().is_err());
+
+    // the simple query protocol returns every value as text
+    let row = sqlx::raw_sql("SELECT 258::int4")
+        .fetch_one(&mut conn)
         .await?;
-    assert_eq!(1, got);
+    let value = row.try_get_raw(0)?;
+    assert_eq!(value.format(), PgValueFormat::Text);
+    assert_eq!(value.as_str().unwrap(), "258");
 
     Ok(())
 }
 
+#[cfg(feature = "blocking")]
 #[sqlx_macros::test]
-async fn it_can_work_with_failed_transactions() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_runs_a_blocking_query_on_drop() -> anyhow::Result<()> {
+    struct DropTable(sqlx::PgPool);
+
+    impl Drop for DropTable {
+        fn drop(&mut self) {
+            let mut conn = self.0.acquire_blocking().unwrap();
+            conn.execute_blocking("DROP TABLE dropped_blocking")
+                .unwrap();
+        }
+    }
 
-    // We're using a (local) statement_timeout to simulate a runtime failure, as opposed to
-    // a parse/plan failure.
-    let mut tx = conn.begin().await?;
-    let _ = sqlx::query("SELECT pg_catalog.set_config($1, $2, true)")
-        .bind("statement_timeout")
-        .bind("1ms")
-        .execute(tx.as_mut())
+    let pool = pool::<Postgres>().await?;
+    pool.execute("DROP TABLE IF EXISTS dropped_blocking; CREATE TABLE dropped_blocking (id INT4)")
         .await?;
 
-    assert!(sqlx::query("SELECT 1 WHERE pg_sleep(0.30) IS NULL")
-        .fetch_one(tx.as_mut())
-        .await
-        .is_err());
-    tx.rollback().await?;
+    let guard = DropTable(pool.clone());
+    tokio::task::spawn_blocking(move || drop(guard)).await?;
 
-    // conn should be usable again, as we explictly rolled back the transaction
-    let got: i32 = sqlx::query_scalar("SELECT 1")
-        .fetch_one(conn.as_mut())
+    let exists: bool = sqlx::query_scalar("SELECT to_regclass('dropped_blocking') IS NOT NULL")
+        .fetch_one(&pool)
         .await?;
-    assert_eq!(1, got);
+    assert!(!exists);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_work_with_transactions() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
-
-    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_1922 (id INTEGER PRIMARY KEY)")
-        .await?;
+async fn it_reads_bytea_in_chunks() -> anyhow::Result<()> {
+    struct Chunks(Vec<Vec<u8>>);
 
-    conn.execute("TRUNCATE _sqlx_users_1922").await?;
+    impl std::io::Write for Chunks {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.push(buf.to_vec());
+            Ok(buf.len())
+        }
 
-    // begin .. rollback
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
 
-    let mut tx = conn.begin().await?;
+    let mut conn = new::<Postgres>().await?;
 
-    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES ($1)")
-        .bind(10_i32)
-        .execute(&mut *tx)
+    let value: Vec<u8> = (0..=255).cycle().take(1000).collect();
+    let mut chunks = Chunks(Vec::new());
+    let len = conn
+        .read_bytea_into(
+            sqlx::query("SELECT $1::bytea").bind(&value),
+            300,
+            &mut chunks,
+        )
         .await?;
 
-    tx.rollback().await?;
+    assert_eq!(len, Some(1000));
+    assert_eq!(
+        chunks.0.iter().map(Vec::len).collect::<Vec<_>>(),
+        [300, 300, 300, 100]
+    );
+    assert_eq!(chunks.0.concat(), value);
 
-    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
-        .fetch_one(&mut conn)
+    let mut out = Vec::new();
+    let len = conn
+        .read_bytea_into(sqlx::query("SELECT ''::bytea"), 300, &mut out)
         .await?;
+    assert_eq!(len, Some(0));
 
-    assert_eq!(count, 0);
-
-    // begin .. commit
+    let len = conn
+        .read_bytea_into(sqlx::query("SELECT NULL::bytea"), 300, &mut out)
+        .await?;
+    assert_eq!(len, None);
+    assert!(out.is_empty());
 
-    let mut tx = conn.begin().await?;
+    let err = conn
+        .read_bytea_into(sqlx::query("SELECT ''::bytea WHERE false"), 300, &mut out)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::RowNotFound), "{err:?}");
 
-    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES ($1)")
-        .bind(10_i32)
-        .execute(&mut *tx)
+    // the query is run as it is
+    conn.execute("CREATE TEMPORARY TABLE blobs (id INT4, data BYTEA)")
         .await?;
 
-    tx.commit().await?;
+    let mut out = Vec::new();
+    let len = conn
+        .read_bytea_into(
+            sqlx::query("INSERT INTO blobs VALUES (1, $1), (2, '') RETURNING data;").bind(&value),
+            300,
+            &mut out,
+        )
+        .await?;
+    assert_eq!(len, Some(1000));
+    assert_eq!(out, value);
 
-    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
+    let inserted: i64 = sqlx::query_scalar("SELECT count(*) FROM blobs")
         .fetch_one(&mut conn)
         .await?;
+    assert_eq!(inserted, 2);
 
-    assert_eq!(count, 1);
+    let err = conn
+        .read_bytea_into(sqlx::query("SELECT 1"), 300, &mut out)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{err:?}");
 
-    // begin .. (drop)
+    // the rest of the value is skipped if writing fails part way through
+    struct FailAfter(usize);
 
-    {
-        let mut tx = conn.begin().await?;
+    impl std::io::Write for FailAfter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 = self.0.checked_sub(1).ok_or(std::io::ErrorKind::BrokenPipe)?;
+            Ok(buf.len())
+        }
 
-        sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES ($1)")
-            .bind(20_i32)
-            .execute(&mut *tx)
-            .await?;
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
     }
 
-    conn = new::<Postgres>().await?;
+    let err = conn
+        .read_bytea_into(
+            sqlx::query("SELECT data, id FROM blobs WHERE id = 1"),
+            300,
+            &mut FailAfter(1),
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Io(_)), "{err:?}");
 
-    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
+    let id: i32 = sqlx::query_scalar("SELECT id FROM blobs WHERE id = 1")
         .fetch_one(&mut conn)
         .await?;
-
-    assert_eq!(count, 1);
+    assert_eq!(id, 1);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_prepares_again_after_the_cached_statement_is_gone() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_2523 (id INTEGER PRIMARY KEY)")
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .recover_stale_statements(false);
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let select = "SELECT $1::int4";
+    let value: i32 = sqlx::query_scalar(select)
+        .bind(1)
+        .fetch_one(&mut conn)
         .await?;
+    assert_eq!(value, 1);
 
-    conn.execute("TRUNCATE _sqlx_users_2523").await?;
+    // drops the statement on the server, but not from the cache
+    conn.execute("DEALLOCATE ALL").await?;
 
-    // begin
-    let mut tx = conn.begin().await?; // transaction
+    let err = sqlx::query_scalar::<_, i32>(select)
+        .bind(2)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("26000"),
+        "{err:?}"
+    );
 
-    // insert a user
-    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES ($1)")
-        .bind(50_i32)
-        .execute(&mut *tx)
+    let value: i32 = sqlx::query_scalar(select)
+        .bind(3)
+        .fetch_one(&mut conn)
         .await?;
+    assert_eq!(value, 3);
 
-    // begin once more
-    let mut tx2 = tx.begin().await?; // savepoint
+    // the result type of a cached statement changes
+    conn.execute(
+        "CREATE TEMPORARY TABLE stale_statement (x INT4); INSERT INTO stale_statement VALUES (4)",
+    )
+    .await?;
 
-    // insert another user
-    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES ($1)")
-        .bind(10_i32)
-        .execute(&mut *tx2)
+    let select = "SELECT * FROM stale_statement WHERE $1";
+    let value: i32 = sqlx::query_scalar(select)
+        .bind(true)
+        .fetch_one(&mut conn)
         .await?;
+    assert_eq!(value, 4);
 
-    // never mind, rollback
-    tx2.rollback().await?; // roll that one back
-
-    // did we really?
-    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
-        .fetch_one(&mut *tx)
+    conn.execute("ALTER TABLE stale_statement ALTER COLUMN x TYPE INT8")
         .await?;
 
-    assert_eq!(count, 1);
-
-    // actually, commit
-    tx.commit().await?;
+    let err = sqlx::query_scalar::<_, i64>(select)
+        .bind(true)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("0A000"),
+        "{err:?}"
+    );
 
-    // did we really?
-    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
+    let value: i64 = sqlx::query_scalar(select)
+        .bind(true)
         .fetch_one(&mut conn)
         .await?;
-
-    assert_eq!(count, 1);
+    assert_eq!(value, 4);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
+async fn it_recovers_from_stale_cached_statements() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_3952 (id INTEGER PRIMARY KEY)")
+    conn.execute("CREATE TEMPORARY TABLE migrated (x INT4); INSERT INTO migrated VALUES (4)")
         .await?;
 
-    conn.execute("TRUNCATE _sqlx_users_3952").await?;
+    let select = "SELECT * FROM migrated WHERE $1";
+    let count = "SELECT COUNT(*) FROM migrated WHERE $1";
 
-    // begin .. (drop)
+    let value: i32 = sqlx::query_scalar(select)
+        .bind(true)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 4);
+    let _: i64 = sqlx::query_scalar(count)
+        .bind(true)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(conn.cached_statements_size(), 2);
 
-    // run 2 times to see what happens if we drop transactions repeatedly
-    for _ in 0..2 {
-        {
-            let mut tx = conn.begin().await?;
+    conn.execute("ALTER TABLE migrated ALTER COLUMN x TYPE INT8")
+        .await?;
 
-            // do actually something before dropping
-            let _user = sqlx::query("INSERT INTO _sqlx_users_3952 (id) VALUES ($1) RETURNING id")
-                .bind(20_i32)
-                .fetch_one(&mut *tx)
-                .await?;
-        }
+    // the whole cache is cleared and the query runs again
+    let value: i64 = sqlx::query_scalar(select)
+        .bind(true)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 4);
+    assert_eq!(conn.cached_statements_size(), 1);
 
-        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_3952")
-            .fetch_one(&mut conn)
-            .await?;
+    // in a transaction, the error aborts it and is returned
+    conn.execute("ALTER TABLE migrated ALTER COLUMN x TYPE INT4")
+        .await?;
 
-        assert_eq!(count, 0);
-    }
+    let mut tx = conn.begin().await?;
+    let err = sqlx::query_scalar::<_, i32>(select)
+        .bind(true)
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("0A000"),
+        "{err:?}"
+    );
+    tx.rollback().await?;
+
+    let value: i32 = sqlx::query_scalar(select)
+        .bind(true)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 4);
 
     Ok(())
 }
 
-// run with `cargo test --features postgres -- --ignored --nocapture pool_smoke_test`
-#[ignore]
 #[sqlx_macros::test]
-async fn pool_smoke_test() -> anyhow::Result<()> {
-    use futures::{future, task::Poll, Future};
+async fn it_keeps_connect_option_callbacks_in_a_pool() -> anyhow::Result<()> {
+    use sqlx::ConnectOptions;
 
-    eprintln!("starting pool");
+    setup_if_needed();
+
+    let statements = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let statements_ = statements.clone();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .on_slow_query(Duration::ZERO, move |sql, _| {
+            statements_.lock().unwrap().push(sql.to_owned())
+        });
 
     let pool = PgPoolOptions::new()
-        .acquire_timeout(Duration::from_secs(5))
-        .min_connections(1)
         .max_connections(1)
-        .connect(&dotenvy::var("DATABASE_URL")?)
+        .connect_with(options)
         .await?;
 
-    // spin up more tasks than connections available, and ensure we don't deadlock
-    for i in 0..200 {
-        let pool = pool.clone();
-        sqlx_core::rt::spawn(async move {
-            for j in 0.. {
-                if let Err(e) = sqlx::query("select 1 + 1").execute(&pool).await {
-                    // normal error at termination of the test
-                    if matches!(e, sqlx::Error::PoolClosed) {
-                        eprintln!("pool task {i} exiting normally after {j} iterations");
-                    } else {
-                        eprintln!("pool task {i} dying due to {e} after {j} iterations");
-                    }
-                    break;
-                }
-
-                // shouldn't be necessary if the pool is fair
-                // sqlx_core::rt::yield_now().await;
-            }
-        });
-    }
-
-    // spawn a bunch of tasks that attempt to acquire but give up to ensure correct handling
-    // of cancellations
-    for _ in 0..50 {
-        let pool = pool.clone();
-        sqlx_core::rt::spawn(async move {
-            while !pool.is_closed() {
-                let acquire = pool.acquire();
-                futures::pin_mut!(acquire);
+    pool.execute("SELECT 'from the pool'").await?;
+    assert!(statements
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|sql| sql == "SELECT 'from the pool'"));
 
-                // poll the acquire future once to put the waiter in the queue
-                future::poll_fn(move |cx| {
-                    let _ = acquire.as_mut().poll(cx);
-                    Poll::Ready(())
-                })
-                .await;
+    Ok(())
+}
 
-                // this one is necessary since this is a hot loop,
-                // otherwise this task will never be descheduled
-                sqlx_core::rt::yield_now().await;
-            }
-        });
-    }
+#[sqlx_macros::test]
+async fn it_sizes_the_pool_from_the_server() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    eprintln!("sleeping for 30 seconds");
+    let server_max: String = sqlx::query_scalar("SHOW max_connections")
+        .fetch_one(&mut conn)
+        .await?;
+    let server_max: u32 = server_max.parse()?;
 
-    sqlx_core::rt::sleep(Duration::from_secs(30)).await;
+    assert_eq!(conn.server_max_connections().await?, Some(server_max));
 
-    // assert_eq!(pool.size(), 10);
+    let pool = PgPoolOptions::new()
+        .max_connections_from_server(5)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
 
-    eprintln!("closing pool");
+    assert_eq!(pool.options().get_max_connections(), server_max - 5);
 
-    sqlx_core::rt::timeout(Duration::from_secs(30), pool.close()).await?;
+    // the size never drops below `min_connections`
+    let pool = PgPoolOptions::new()
+        .min_connections(2)
+        .max_connections_from_server(server_max)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
 
-    eprintln!("pool closed successfully");
+    assert_eq!(pool.options().get_max_connections(), 2);
 
     Ok(())
 }
 
+#[cfg(feature = "time")]
 #[sqlx_macros::test]
-async fn test_invalid_query() -> anyhow::Result<()> {
+async fn it_gets_the_server_time() -> anyhow::Result<()> {
+    use sqlx::types::time::OffsetDateTime;
+
     let mut conn = new::<Postgres>().await?;
 
-    conn.execute("definitely not a correct query")
-        .await
-        .unwrap_err();
+    let now = conn.server_now().await?;
+    assert!((now - OffsetDateTime::now_utc()).abs().whole_seconds() < 60);
 
-    let mut s = conn.fetch("select 1");
-    let row = s.try_next().await?.unwrap();
+    // `now()` is the start time of the transaction
+    let mut tx = conn.begin().await?;
+    let first = tx.server_now().await?;
+    sqlx::query("SELECT pg_sleep(0.01)")
+        .execute(&mut *tx)
+        .await?;
+    assert_eq!(tx.server_now().await?, first);
+    tx.rollback().await?;
 
-    assert_eq!(row.get::<i32, _>(0), 1i32);
+    assert!(conn.server_now().await? > first);
 
     Ok(())
 }
 
-/// Tests the edge case of executing a completely empty query string.
-///
-/// This gets flagged as an `EmptyQueryResponse` in Postgres. We
-/// catch this and just return no rows.
+// https://github.com/launchbadge/sqlx/issues/104
 #[sqlx_macros::test]
-async fn test_empty_query() -> anyhow::Result<()> {
+async fn it_can_return_interleaved_nulls_issue_104() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
-    let done = conn.execute("").await?;
 
-    assert_eq!(done.rows_affected(), 0);
+    let tuple = sqlx::query("SELECT NULL, 10::INT, NULL, 20::INT, NULL, 40::INT, NULL, 80::INT")
+        .map(|row: PgRow| {
+            (
+                row.get::<Option<i32>, _>(0),
+                row.get::<Option<i32>, _>(1),
+                row.get::<Option<i32>, _>(2),
+                row.get::<Option<i32>, _>(3),
+                row.get::<Option<i32>, _>(4),
+                row.get::<Option<i32>, _>(5),
+                row.get::<Option<i32>, _>(6),
+                row.get::<Option<i32>, _>(7),
+            )
+        })
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(tuple.0, None);
+    assert_eq!(tuple.1, Some(10));
+    assert_eq!(tuple.2, None);
+    assert_eq!(tuple.3, Some(20));
+    assert_eq!(tuple.4, None);
+    assert_eq!(tuple.5, Some(40));
+    assert_eq!(tuple.6, None);
+    assert_eq!(tuple.7, Some(80));
 
     Ok(())
 }
 
-/// Test a simple select expression. This should return the row.
 #[sqlx_macros::test]
-async fn test_select_expression() -> anyhow::Result<()> {
+async fn it_can_fail_and_recover() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    let mut s = conn.fetch("SELECT 5");
-    let row = s.try_next().await?.unwrap();
+    for i in 0..10 {
+        // make a query that will fail
+        let res = conn
+            .execute("INSERT INTO not_found (column) VALUES (10)")
+            .await;
 
-    assert!(5i32 == row.try_get::<i32, _>(0)?);
+        assert!(res.is_err());
+
+        // now try and use the connection
+        let val: i32 = conn.fetch_one(&*format!("SELECT {i}::int4")).await?.get(0);
+
+        assert_eq!(val, i);
+    }
 
     Ok(())
 }
 
-/// Test that we can interleave reads and writes to the database
-/// in one simple query. Using the `Cursor` API we should be
-/// able to fetch from both queries in sequence.
 #[sqlx_macros::test]
-async fn test_multi_read_write() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_can_fail_and_recover_with_pool() -> anyhow::Result<()> {
+    let pool = sqlx_test::pool::<Postgres>().await?;
 
-    let mut s = conn.fetch(
-        "
-CREATE TABLE IF NOT EXISTS _sqlx_test_postgres_5112 (
-    id BIGSERIAL PRIMARY KEY,
-    text TEXT NOT NULL
-);
+    for i in 0..10 {
+        // make a query that will fail
+        let res = pool
+            .execute("INSERT INTO not_found (column) VALUES (10)")
+            .await;
 
-SELECT 'Hello World' as _1;
+        assert!(res.is_err());
 
-INSERT INTO _sqlx_test_postgres_5112 (text) VALUES ('this is a test');
+        // now try and use the connection
+        let val: i32 = pool.fetch_one(&*format!("SELECT {i}::int4")).await?.get(0);
 
-SELECT id, text FROM _sqlx_test_postgres_5112;
-    ",
-    );
+        assert_eq!(val, i);
+    }
 
-    let row = s.try_next().await?.unwrap();
+    Ok(())
+}
 
-    assert!("Hello World" == row.try_get::<&str, _>("_1")?);
+#[sqlx_macros::test]
+async fn it_can_query_scalar() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    let row = s.try_next().await?.unwrap();
+    let scalar: i32 = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(scalar, 42);
 
-    let id: i64 = row.try_get("id")?;
-    let text: &str = row.try_get("text")?;
+    let scalar: Option<i32> = sqlx::query_scalar("SELECT 42").fetch_one(&mut conn).await?;
+    assert_eq!(scalar, Some(42));
 
-    assert_eq!(1_i64, id);
-    assert_eq!("this is a test", text);
+    let scalar: Option<i32> = sqlx::query_scalar("SELECT NULL")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(scalar, None);
+
+    let scalar: Option<i64> = sqlx::query_scalar("SELECT 42::bigint")
+        .fetch_optional(&mut conn)
+        .await?;
+    assert_eq!(scalar, Some(42));
+
+    let scalar: Option<i16> = sqlx::query_scalar("").fetch_optional(&mut conn).await?;
+    assert_eq!(scalar, None);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_caches_statements() -> anyhow::Result<()> {
+/// This is separate from `it_can_query_scalar` because while implementing it I ran into a
+/// bug which that prevented `Vec<i32>` from compiling but allowed Vec<Option<i32>>.
+async fn it_can_query_all_scalar() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    for i in 0..2 {
-        let row = sqlx::query("SELECT $1 AS val")
-            .bind(Oid(i))
-            .persistent(true)
-            .fetch_one(&mut conn)
-            .await?;
+    let scalar: Vec<i32> = sqlx::query_scalar("SELECT $1")
+        .bind(42)
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(scalar, vec![42]);
 
-        let val: Oid = row.get("val");
+    let scalar: Vec<Option<i32>> = sqlx::query_scalar("SELECT $1 UNION ALL SELECT NULL")
+        .bind(42)
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(scalar, vec![Some(42), None]);
 
-        assert_eq!(Oid(i), val);
-    }
+    Ok(())
+}
 
-    assert_eq!(1, conn.cached_statements_size());
-    conn.clear_cached_statements().await?;
-    assert_eq!(0, conn.cached_statements_size());
+#[ignore]
+#[sqlx_macros::test]
+async fn copy_can_work_with_failed_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    for i in 0..2 {
-        let row = sqlx::query("SELECT $1 AS val")
-            .bind(Oid(i))
-            .persistent(false)
-            .fetch_one(&mut conn)
-            .await?;
+    // We're using a (local) statement_timeout to simulate a runtime failure, as opposed to
+    // a parse/plan failure.
+    let mut tx = conn.begin().await?;
+    let _ = sqlx::query("SELECT pg_catalog.set_config($1, $2, true)")
+        .bind("statement_timeout")
+        .bind("1ms")
+        .execute(tx.as_mut())
+        .await?;
 
-        let val: Oid = row.get("val");
+    let mut copy_out: Pin<
+        Box<dyn Stream<Item = Result<Bytes, sqlx::Error>> + Send>,
+    > = (&mut tx)
+        .copy_out_raw("COPY (SELECT nspname FROM pg_catalog.pg_namespace WHERE pg_sleep(0.001) IS NULL) TO STDOUT")
+        .await?;
 
-        assert_eq!(Oid(i), val);
-    }
+    while copy_out.try_next().await.is_ok() {}
+    drop(copy_out);
 
-    assert_eq!(0, conn.cached_statements_size());
+    tx.rollback().await?;
+
+    // conn should be usable again, as we explictly rolled back the transaction
+    let got: i32 = sqlx::query_scalar("SELECT 1")
+        .fetch_one(conn.as_mut())
+        .await?;
+    assert_eq!(1, got);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
-    sqlx_test::setup_if_needed();
+async fn it_can_work_with_failed_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    // We're using a (local) statement_timeout to simulate a runtime failure, as opposed to
+    // a parse/plan failure.
+    let mut tx = conn.begin().await?;
+    let _ = sqlx::query("SELECT pg_catalog.set_config($1, $2, true)")
+        .bind("statement_timeout")
+        .bind("1ms")
+        .execute(tx.as_mut())
+        .await?;
 
-    // a capacity of 1 means that before each statement (after the first)
-    // we will close the previous statement
-    options = options.statement_cache_capacity(1);
+    assert!(sqlx::query("SELECT 1 WHERE pg_sleep(0.30) IS NULL")
+        .fetch_one(tx.as_mut())
+        .await
+        .is_err());
+    tx.rollback().await?;
 
-    let mut conn = PgConnection::connect_with(&options).await?;
+    // conn should be usable again, as we explictly rolled back the transaction
+    let got: i32 = sqlx::query_scalar("SELECT 1")
+        .fetch_one(conn.as_mut())
+        .await?;
+    assert_eq!(1, got);
 
-    for i in 0..5 {
-        let row = sqlx::query(&*format!("SELECT {i}::int4 AS val"))
-            .fetch_one(&mut conn)
-            .await?;
+    Ok(())
+}
 
-        let val: i32 = row.get("val");
+#[sqlx_macros::test]
+async fn it_can_work_with_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-        assert_eq!(i, val);
-    }
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_1922 (id INTEGER PRIMARY KEY)")
+        .await?;
 
-    assert_eq!(1, conn.cached_statements_size());
+    conn.execute("TRUNCATE _sqlx_users_1922").await?;
 
-    Ok(())
-}
+    // begin .. rollback
 
-#[sqlx_macros::test]
-async fn it_sets_application_name() -> anyhow::Result<()> {
-    sqlx_test::setup_if_needed();
+    let mut tx = conn.begin().await?;
 
-    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
-    options = options.application_name("some-name");
+    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES ($1)")
+        .bind(10_i32)
+        .execute(&mut *tx)
+        .await?;
 
-    let mut conn = PgConnection::connect_with(&options).await?;
+    tx.rollback().await?;
 
-    let row = sqlx::query("select current_setting('application_name') as app_name")
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
         .fetch_one(&mut conn)
         .await?;
 
-    let val: String = row.get("app_name");
+    assert_eq!(count, 0);
 
-    assert_eq!("some-name", &val);
+    // begin .. commit
 
-    Ok(())
-}
+    let mut tx = conn.begin().await?;
 
-#[sqlx_macros::test]
-async fn it_can_handle_parameter_status_message_issue_484() -> anyhow::Result<()> {
-    new::<Postgres>().await?.execute("SET NAMES 'UTF8'").await?;
-    Ok(())
-}
+    sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES ($1)")
+        .bind(10_i32)
+        .execute(&mut *tx)
+        .await?;
 
-#[sqlx_macros::test]
-async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
-    let mut tx = conn.begin().await?;
+    tx.commit().await?;
 
-    let tweet_id: i64 =
-        sqlx::query_scalar("INSERT INTO tweet ( text ) VALUES ( 'Hello, World' ) RETURNING id")
-            .fetch_one(&mut *tx)
-            .await?;
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
+        .fetch_one(&mut conn)
+        .await?;
 
-    let statement = tx.prepare("SELECT * FROM tweet WHERE id = $1").await?;
+    assert_eq!(count, 1);
 
-    assert_eq!(statement.column(0).name(), "id");
-    assert_eq!(statement.column(1).name(), "created_at");
-    assert_eq!(statement.column(2).name(), "text");
-    assert_eq!(statement.column(3).name(), "owner_id");
+    // begin .. (drop)
 
-    assert_eq!(statement.column(0).type_info().name(), "INT8");
-    assert_eq!(statement.column(1).type_info().name(), "TIMESTAMPTZ");
-    assert_eq!(statement.column(2).type_info().name(), "TEXT");
-    assert_eq!(statement.column(3).type_info().name(), "INT8");
+    {
+        let mut tx = conn.begin().await?;
 
-    let row = statement.query().bind(tweet_id).fetch_one(&mut *tx).await?;
-    let tweet_text: &str = row.try_get("text")?;
+        sqlx::query("INSERT INTO _sqlx_users_1922 (id) VALUES ($1)")
+            .bind(20_i32)
+            .execute(&mut *tx)
+            .await?;
+    }
 
-    assert_eq!(tweet_text, "Hello, World");
+    conn = new::<Postgres>().await?;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_1922")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
 
     Ok(())
 }
 
-// repro is more reliable with the basic scheduler used by `#[tokio::test]`
-#[cfg(feature = "_rt-tokio")]
-#[tokio::test]
-async fn test_issue_622() -> anyhow::Result<()> {
-    use std::time::Instant;
-
-    setup_if_needed();
+#[sqlx_macros::test]
+async fn it_can_work_with_nested_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    let pool = PgPoolOptions::new()
-        .max_connections(1) // also fails with higher counts, e.g. 5
-        .connect(&std::env::var("DATABASE_URL").unwrap())
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_2523 (id INTEGER PRIMARY KEY)")
         .await?;
 
-    println!("pool state: {pool:?}");
+    conn.execute("TRUNCATE _sqlx_users_2523").await?;
 
-    let mut handles = vec![];
+    // begin
+    let mut tx = conn.begin().await?; // transaction
 
-    // given repro spawned 100 tasks but I found it reliably reproduced with 3
-    for i in 0..3 {
-        let pool = pool.clone();
+    // insert a user
+    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES ($1)")
+        .bind(50_i32)
+        .execute(&mut *tx)
+        .await?;
 
-        handles.push(sqlx_core::rt::spawn(async move {
-            {
-                let mut conn = pool.acquire().await.unwrap();
+    // begin once more
+    let mut tx2 = tx.begin().await?; // savepoint
 
-                let _ = sqlx::query("SELECT 1").fetch_one(&mut *conn).await.unwrap();
+    // insert another user
+    sqlx::query("INSERT INTO _sqlx_users_2523 (id) VALUES ($1)")
+        .bind(10_i32)
+        .execute(&mut *tx2)
+        .await?;
 
-                // conn gets dropped here and should be returned to the pool
-            }
+    // never mind, rollback
+    tx2.rollback().await?; // roll that one back
 
-            // (do some other work here without holding on to a connection)
-            // this actually fixes the issue, depending on the timeout used
-            // sqlx_core::rt::sleep(Duration::from_millis(500)).await;
+    // did we really?
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
+        .fetch_one(&mut *tx)
+        .await?;
 
-            {
-                let start = Instant::now();
-                match pool.acquire().await {
-                    Ok(conn) => {
-                        println!("{} acquire took {:?}", i, start.elapsed());
-                        drop(conn);
-                    }
-                    Err(e) => panic!("{i} acquire returned error: {e} pool state: {pool:?}"),
-                }
-            }
+    assert_eq!(count, 1);
 
-            Result::<(), anyhow::Error>::Ok(())
-        }));
-    }
+    // actually, commit
+    tx.commit().await?;
 
-    futures::future::try_join_all(handles).await?;
+    // did we really?
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_2523")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_describe_outer_join_nullable() -> anyhow::Result<()> {
+async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    // test nullability inference for various joins
-
-    // inner join, nullability should not be overridden
-    // language=PostgreSQL
-    let describe = conn
-        .describe(
-            "select tweet.id
-    from tweet
-    inner join products on products.name = tweet.text",
-        )
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_users_3952 (id INTEGER PRIMARY KEY)")
         .await?;
 
-    assert_eq!(describe.nullable(0), Some(false));
-
-    // language=PostgreSQL
-    let describe = conn
-        .describe(
-            "select tweet.id
-from (values (null)) vals(val)
-         left join tweet on false",
-        )
-        .await?;
-
-    // tweet.id is marked NOT NULL but it's brought in from a left-join here
-    // which should make it nullable
-    assert_eq!(describe.nullable(0), Some(true));
-
-    // make sure we don't mis-infer for the outer half of the join
-    // language=PostgreSQL
-    let describe = conn
-        .describe(
-            "select tweet1.id, tweet2.id
-    from tweet tweet1
-    left join tweet tweet2 on false",
-        )
-        .await?;
+    conn.execute("TRUNCATE _sqlx_users_3952").await?;
 
-    assert_eq!(describe.nullable(0), Some(false));
-    assert_eq!(describe.nullable(1), Some(true));
+    // begin .. (drop)
 
-    // right join, nullability should be inverted
-    // language=PostgreSQL
-    let describe = conn
-        .describe(
-            "select tweet1.id, tweet2.id
-    from tweet tweet1
-    right join tweet tweet2 on false",
-        )
-        .await?;
+    // run 2 times to see what happens if we drop transactions repeatedly
+    for _ in 0..2 {
+        {
+            let mut tx = conn.begin().await?;
 
-    assert_eq!(describe.nullable(0), Some(true));
-    assert_eq!(describe.nullable(1), Some(false));
+            // do actually something before dropping
+            let _user = sqlx::query("INSERT INTO _sqlx_users_3952 (id) VALUES ($1) RETURNING id")
+                .bind(20_i32)
+                .fetch_one(&mut *tx)
+                .await?;
+        }
 
-    // full outer join, both tables are nullable
-    // language=PostgreSQL
-    let describe = conn
-        .describe(
-            "select tweet1.id, tweet2.id
-    from tweet tweet1
-    full join tweet tweet2 on false",
-        )
-        .await?;
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM _sqlx_users_3952")
+            .fetch_one(&mut conn)
+            .await?;
 
-    assert_eq!(describe.nullable(0), Some(true));
-    assert_eq!(describe.nullable(1), Some(true));
+        assert_eq!(count, 0);
+    }
 
     Ok(())
 }
 
+// run with `cargo test --features postgres -- --ignored --nocapture pool_smoke_test`
+#[ignore]
 #[sqlx_macros::test]
-async fn test_listener_cleanup() -> anyhow::Result<()> {
-    use sqlx_core::rt::timeout;
-
-    use sqlx::pool::PoolOptions;
-    use sqlx::postgres::PgListener;
+async fn pool_smoke_test() -> anyhow::Result<()> {
+    use futures::{future, task::Poll, Future};
 
-    // Create a connection on which to send notifications
-    let mut notify_conn = new::<Postgres>().await?;
+    eprintln!("starting pool");
 
-    // Create a pool with exactly one connection so we can
-    // deterministically test the cleanup.
-    let pool = PoolOptions::<Postgres>::new()
+    let pool = PgPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(5))
         .min_connections(1)
         .max_connections(1)
-        .test_before_acquire(true)
-        .connect(&env::var("DATABASE_URL")?)
+        .connect(&dotenvy::var("DATABASE_URL")?)
         .await?;
 
-    let mut listener = PgListener::connect_with(&pool).await?;
-    listener.listen("test_channel").await?;
+    // spin up more tasks than connections available, and ensure we don't deadlock
+    for i in 0..200 {
+        let pool = pool.clone();
+        sqlx_core::rt::spawn(async move {
+            for j in 0.. {
+                if let Err(e) = sqlx::query("select 1 + 1").execute(&pool).await {
+                    // normal error at termination of the test
+                    if matches!(e, sqlx::Error::PoolClosed) {
+                        eprintln!("pool task {i} exiting normally after {j} iterations");
+                    } else {
+                        eprintln!("pool task {i} dying due to {e} after {j} iterations");
+                    }
+                    break;
+                }
 
-    // Checks for a notification on the test channel
-    async fn try_recv(listener: &mut PgListener) -> anyhow::Result<bool> {
-        match timeout(Duration::from_millis(100), listener.recv()).await {
-            Ok(res) => {
-                res?;
-                Ok(true)
+                // shouldn't be necessary if the pool is fair
+                // sqlx_core::rt::yield_now().await;
             }
-            Err(_) => Ok(false),
-        }
+        });
     }
 
-    // Check no notification is received before one is sent
-    assert!(!try_recv(&mut listener).await?, "Notification not sent");
+    // spawn a bunch of tasks that attempt to acquire but give up to ensure correct handling
+    // of cancellations
+    for _ in 0..50 {
+        let pool = pool.clone();
+        sqlx_core::rt::spawn(async move {
+            while !pool.is_closed() {
+                let acquire = pool.acquire();
+                futures::pin_mut!(acquire);
 
-    // Check notification is sent and received
-    notify_conn.execute("NOTIFY test_channel").await?;
-    assert!(
-        try_recv(&mut listener).await?,
-        "Notification sent and received"
-    );
-    assert!(
-        !try_recv(&mut listener).await?,
-        "Notification is not duplicated"
-    );
+                // poll the acquire future once to put the waiter in the queue
+                future::poll_fn(move |cx| {
+                    let _ = acquire.as_mut().poll(cx);
+                    Poll::Ready(())
+                })
+                .await;
 
-    // Test that cleanup stops listening on the channel
-    drop(listener);
-    let mut listener = PgListener::connect_with(&pool).await?;
+                // this one is necessary since this is a hot loop,
+                // otherwise this task will never be descheduled
+                sqlx_core::rt::yield_now().await;
+            }
+        });
+    }
 
-    // Check notification is sent but not received
-    notify_conn.execute("NOTIFY test_channel").await?;
-    assert!(
-        !try_recv(&mut listener).await?,
-        "Notification is not received on fresh listener"
-    );
+    eprintln!("sleeping for 30 seconds");
+
+    sqlx_core::rt::sleep(Duration::from_secs(30)).await;
+
+    // assert_eq!(pool.size(), 10);
+
+    eprintln!("closing pool");
+
+    sqlx_core::rt::timeout(Duration::from_secs(30), pool.close()).await?;
+
+    eprintln!("pool closed successfully");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_pg_listener_allows_pool_to_close() -> anyhow::Result<()> {
-    let pool = pool::<Postgres>().await?;
+async fn test_invalid_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    // acquires and holds a connection which would normally prevent the pool from closing
-    let mut listener = PgListener::connect_with(&pool).await?;
+    conn.execute("definitely not a correct query")
+        .await
+        .unwrap_err();
 
-    sqlx_core::rt::spawn(async move {
-        listener.recv().await.unwrap();
-    });
+    let mut s = conn.fetch("select 1");
+    let row = s.try_next().await?.unwrap();
 
-    // would previously hang forever since `PgListener` had no way to know the pool wanted to close
-    pool.close().await;
+    assert_eq!(row.get::<i32, _>(0), 1i32);
 
     Ok(())
 }
 
+/// Tests the edge case of executing a completely empty query string.
+///
+/// This gets flagged as an `EmptyQueryResponse` in Postgres. We
+/// catch this and just return no rows.
 #[sqlx_macros::test]
-async fn it_supports_domain_types_in_composite_domain_types() -> anyhow::Result<()> {
-    // Only supported in Postgres 11+
+async fn test_empty_query() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
-    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
-        return Ok(());
-    }
+    let done = conn.execute("").await?;
 
-    conn.execute(
-        r#"
-DROP TABLE IF EXISTS heating_bills;
-DROP DOMAIN IF EXISTS winter_year_month;
-DROP TYPE IF EXISTS year_month;
-DROP DOMAIN IF EXISTS month_id;
+    assert_eq!(done.rows_affected(), 0);
 
-CREATE DOMAIN month_id AS INT2 CHECK (1 <= value AND value <= 12);
-CREATE TYPE year_month AS (year INT4, month month_id);
-CREATE DOMAIN winter_year_month AS year_month CHECK ((value).month <= 3);
-CREATE TABLE heating_bills (
-  month winter_year_month NOT NULL PRIMARY KEY,
-  cost INT4 NOT NULL
-);
-    "#,
-    )
-    .await?;
+    Ok(())
+}
 
-    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct MonthId(i16);
+/// Test a simple select expression. This should return the row.
+#[sqlx_macros::test]
+async fn test_select_expression() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    impl sqlx::Type<Postgres> for MonthId {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            sqlx::postgres::PgTypeInfo::with_name("month_id")
-        }
+    let mut s = conn.fetch("SELECT 5");
+    let row = s.try_next().await?.unwrap();
 
-        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
-            *ty == Self::type_info()
-        }
-    }
+    assert!(5i32 == row.try_get::<i32, _>(0)?);
 
-    impl<'r> sqlx::Decode<'r, Postgres> for MonthId {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            Ok(Self(<i16 as sqlx::Decode<Postgres>>::decode(value)?))
-        }
-    }
+    Ok(())
+}
 
-    impl<'q> sqlx::Encode<'q, Postgres> for MonthId {
-        fn encode_by_ref(
-            &self,
-            buf: &mut sqlx::postgres::PgArgumentBuffer,
-        ) -> Result<sqlx::encode::IsNull, BoxDynError> {
-            <i16 as sqlx::Encode<Postgres>>::encode(self.0, buf)
-        }
-    }
+/// Test that we can interleave reads and writes to the database
+/// in one simple query. Using the `Cursor` API we should be
+/// able to fetch from both queries in sequence.
+#[sqlx_macros::test]
+async fn test_multi_read_write() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct WinterYearMonth {
-        year: i32,
-        month: MonthId,
-    }
+    let mut s = conn.fetch(
+        "
+CREATE TABLE IF NOT EXISTS _sqlx_test_postgres_5112 (
+    id BIGSERIAL PRIMARY KEY,
+    text TEXT NOT NULL
+);
 
-    impl sqlx::Type<Postgres> for WinterYearMonth {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            sqlx::postgres::PgTypeInfo::with_name("winter_year_month")
-        }
+SELECT 'Hello World' as _1;
 
-        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
-            *ty == Self::type_info()
-        }
-    }
+INSERT INTO _sqlx_test_postgres_5112 (text) VALUES ('this is a test');
 
-    impl<'r> sqlx::Decode<'r, Postgres> for WinterYearMonth {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
+SELECT id, text FROM _sqlx_test_postgres_5112;
+    ",
+    );
 
-            let year = decoder.try_decode::<i32>()?;
-            let month = decoder.try_decode::<MonthId>()?;
+    let row = s.try_next().await?.unwrap();
 
-            Ok(Self { year, month })
-        }
-    }
+    assert!("Hello World" == row.try_get::<&str, _>("_1")?);
 
-    impl<'q> sqlx::Encode<'q, Postgres> for WinterYearMonth {
-        fn encode_by_ref(
-            &self,
-            buf: &mut sqlx::postgres::PgArgumentBuffer,
-        ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
-            let mut encoder = sqlx::postgres::types::PgRecordEncoder::new(buf);
-            encoder.encode(self.year)?;
-            encoder.encode(self.month)?;
-            encoder.finish();
-            Ok(sqlx::encode::IsNull::No)
-        }
-    }
+    let row = s.try_next().await?.unwrap();
+
+    let id: i64 = row.try_get("id")?;
+    let text: &str = row.try_get("text")?;
+
+    assert_eq!(1_i64, id);
+    assert_eq!("this is a test", text);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_caches_statements() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    let result = sqlx::query("DELETE FROM heating_bills;")
-        .execute(&mut conn)
-        .await;
+    for i in 0..2 {
+        let row = sqlx::query("SELECT $1 AS val")
+            .bind(Oid(i))
+            .persistent(true)
+            .fetch_one(&mut conn)
+            .await?;
 
-    let result = result.unwrap();
-    assert_eq!(result.rows_affected(), 0);
+        let val: Oid = row.get("val");
 
-    let result =
-        sqlx::query("INSERT INTO heating_bills(month, cost) VALUES($1::winter_year_month, 100);")
-            .bind(WinterYearMonth {
-                year: 2021,
-                month: MonthId(1),
-            })
-            .execute(&mut conn)
-            .await;
+        assert_eq!(Oid(i), val);
+    }
 
-    let result = result.unwrap();
-    assert_eq!(result.rows_affected(), 1);
+    assert_eq!(1, conn.cached_statements_size());
+    conn.clear_cached_statements().await?;
+    assert_eq!(0, conn.cached_statements_size());
 
-    let result = sqlx::query("DELETE FROM heating_bills;")
-        .execute(&mut conn)
-        .await;
+    for i in 0..2 {
+        let row = sqlx::query("SELECT $1 AS val")
+            .bind(Oid(i))
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
 
-    let result = result.unwrap();
-    assert_eq!(result.rows_affected(), 1);
+        let val: Oid = row.get("val");
+
+        assert_eq!(Oid(i), val);
+    }
+
+    assert_eq!(0, conn.cached_statements_size());
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_resolves_custom_type_in_array() -> anyhow::Result<()> {
-    // Only supported in Postgres 11+
+async fn it_counts_prepared_statements_and_cache_hits() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
-    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
-        return Ok(());
-    }
-
-    // language=PostgreSQL
-    conn.execute(
-        r#"
-DROP TABLE IF EXISTS pets;
-DROP TYPE IF EXISTS pet_name_and_race;
 
-CREATE TYPE pet_name_and_race AS (
-  name TEXT,
-  race TEXT
-);
-CREATE TABLE pets (
-  owner TEXT NOT NULL,
-  name TEXT NOT NULL,
-  race TEXT NOT NULL,
-  PRIMARY KEY (owner, name)
-);
-INSERT INTO pets(owner, name, race)
-VALUES
-  ('Alice', 'Foo', 'cat');
-INSERT INTO pets(owner, name, race)
-VALUES
-  ('Alice', 'Bar', 'dog');
-    "#,
-    )
-    .await?;
+    let prepared = conn.statements_prepared();
+    let hits = conn.statement_cache_hits();
 
-    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct PetNameAndRace {
-        name: String,
-        race: String,
+    for i in 0..3 {
+        sqlx::query("SELECT $1::int4 AS val")
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
     }
 
-    impl sqlx::Type<Postgres> for PetNameAndRace {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            sqlx::postgres::PgTypeInfo::with_name("pet_name_and_race")
-        }
-    }
+    assert_eq!(prepared + 1, conn.statements_prepared());
+    assert_eq!(hits + 2, conn.statement_cache_hits());
 
-    impl<'r> sqlx::Decode<'r, Postgres> for PetNameAndRace {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
-            let name = decoder.try_decode::<String>()?;
-            let race = decoder.try_decode::<String>()?;
-            Ok(Self { name, race })
-        }
+    // non-persistent queries are prepared every time
+    for i in 0..2 {
+        sqlx::query("SELECT $1::int8 AS val")
+            .bind(i64::from(i))
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
     }
 
-    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct PetNameAndRaceArray(Vec<PetNameAndRace>);
+    assert_eq!(prepared + 3, conn.statements_prepared());
+    assert_eq!(hits + 2, conn.statement_cache_hits());
 
-    impl sqlx::Type<Postgres> for PetNameAndRaceArray {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            // Array type name is the name of the element type prefixed with `_`
-            sqlx::postgres::PgTypeInfo::with_name("_pet_name_and_race")
-        }
-    }
+    Ok(())
+}
 
-    impl<'r> sqlx::Decode<'r, Postgres> for PetNameAndRaceArray {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            Ok(Self(Vec::<PetNameAndRace>::decode(value)?))
-        }
+#[sqlx_macros::test]
+async fn it_counts_open_prepared_statements() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let mut conn = PgConnection::connect_with(&options.statement_cache_capacity(2)).await?;
+
+    let open = conn.prepared_statement_count();
+
+    for sql in ["SELECT $1::int4", "SELECT $1::int4 + 1", "SELECT $1::int4"] {
+        sqlx::query(sql).bind(1_i32).execute(&mut conn).await?;
     }
 
-    let mut conn = new::<Postgres>().await?;
+    assert_eq!(open + 2, conn.prepared_statement_count());
 
-    let row = sqlx::query("select owner, array_agg(row(name, race)::pet_name_and_race) as pets from pets group by owner")
-        .fetch_one(&mut conn)
+    // a statement evicted from the cache is closed
+    sqlx::query("SELECT $1::int8")
+        .bind(1_i64)
+        .execute(&mut conn)
         .await?;
 
-    let pets: PetNameAndRaceArray = row.get("pets");
+    assert_eq!(open + 2, conn.prepared_statement_count());
+    assert_eq!(2, conn.cached_statements_size());
+
+    conn.clear_cached_statements().await?;
+    assert_eq!(open, conn.prepared_statement_count());
 
-    assert_eq!(pets.0.len(), 2);
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_resolves_custom_types_in_anonymous_records() -> anyhow::Result<()> {
-    use sqlx_core::error::Error;
-    // This request involves nested records and array types.
+async fn it_casts_parameters_to_inferred_types() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let sql = "SELECT $1 + 1 AS next, $2 AS text, '$1' AS quoted";
 
-    // Only supported in Postgres 11+
     let mut conn = new::<Postgres>().await?;
-    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
-        return Ok(());
-    }
+    let err = sqlx::query(sql)
+        .bind("41")
+        .bind("a")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(err.as_database_error().is_some(), "{err:?}");
 
-    // language=PostgreSQL
-    conn.execute(
-        r#"
-DROP TABLE IF EXISTS repo_users;
-DROP TABLE IF EXISTS repositories;
-DROP TABLE IF EXISTS repo_memberships;
-DROP TYPE IF EXISTS repo_member;
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .cast_parameters(true);
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-CREATE TABLE repo_users (
-  user_id INT4 NOT NULL,
-  username TEXT NOT NULL,
-  PRIMARY KEY (user_id)
-);
-CREATE TABLE repositories (
-  repo_id INT4 NOT NULL,
-  repo_name TEXT NOT NULL,
-  PRIMARY KEY (repo_id)
-);
-CREATE TABLE repo_memberships (
-  repo_id INT4 NOT NULL,
-  user_id INT4 NOT NULL,
-  permission TEXT NOT NULL,
-  PRIMARY KEY (repo_id, user_id)
-);
-CREATE TYPE repo_member AS (
-  user_id INT4,
-  permission TEXT
-);
-INSERT INTO repo_users(user_id, username)
-VALUES
-  (101, 'alice'),
-  (102, 'bob'),
-  (103, 'charlie');
-INSERT INTO repositories(repo_id, repo_name)
-VALUES
-  (201, 'rust'),
-  (202, 'sqlx'),
-  (203, 'hello-world');
-INSERT INTO repo_memberships(repo_id, user_id, permission)
-VALUES
-  (201, 101, 'admin'),
-  (201, 102, 'write'),
-  (201, 103, 'read'),
-  (202, 102, 'admin');
-"#,
-    )
-    .await?;
+    for persistent in [true, true, false] {
+        let row = sqlx::query(sql)
+            .bind("41")
+            .bind("a")
+            .persistent(persistent)
+            .fetch_one(&mut conn)
+            .await?;
 
-    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct RepoMember {
-        user_id: i32,
-        permission: String,
+        assert_eq!(42, row.try_get::<i32, _>("next")?);
+        assert_eq!("a", row.try_get::<&str, _>("text")?);
+        assert_eq!("$1", row.try_get::<&str, _>("quoted")?);
     }
 
-    impl sqlx::Type<Postgres> for RepoMember {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            sqlx::postgres::PgTypeInfo::with_name("repo_member")
-        }
-    }
+    // statements that fit their arguments are left alone
+    let value: i64 = sqlx::query_scalar("SELECT $1")
+        .bind(7_i64)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 7);
+
+    // only the rewritten statement is cached, not the one that failed
+    assert_eq!(2, conn.cached_statements_size());
+
+    // inside a transaction, the failed attempt aborts it, so nothing is rewritten
+    let mut tx = conn.begin().await?;
+
+    let err = sqlx::query("SELECT $1 * 2 AS doubled")
+        .bind("21")
+        .fetch_one(&mut *tx)
+        .await
+        .unwrap_err();
+    assert_eq!(err.as_database_error().unwrap().code().unwrap(), "42883");
+
+    let err = tx.execute("SELECT 1").await.unwrap_err();
+    assert_eq!(err.as_database_error().unwrap().code().unwrap(), "25P02");
+
+    tx.rollback().await?;
+
+    // but statements rewritten before are used as rewritten
+    let mut tx = conn.begin().await?;
+
+    let row = sqlx::query(sql)
+        .bind("41")
+        .bind("a")
+        .fetch_one(&mut *tx)
+        .await?;
+    assert_eq!(42, row.try_get::<i32, _>("next")?);
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_inlines_arguments_without_prepared_statements() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .prepared_statements(false);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let text = r"it's a \ test with $1 and ?";
+    let texts = vec![
+        r#"{"a", b}"#.to_owned(),
+        "'; DROP TABLE tweet; --".to_owned(),
+    ];
+
+    let row = sqlx::query(
+        "SELECT $1 AS text, $2 AS int, $3 AS float, $4 AS bool, $5 AS null, $6 AS bytes, \
+            $7 AS texts, $8 AS ints, '$1' AS quoted -- $2",
+    )
+    .bind(text)
+    .bind(-42_i64)
+    .bind(1.5_f64)
+    .bind(true)
+    .bind(None::<i32>)
+    .bind(&b"\x00\xffbytes"[..])
+    .bind(&texts)
+    .bind(vec![Some(1_i32), None, Some(3)])
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(text, row.try_get::<String, _>("text")?);
+    assert_eq!(-42, row.try_get::<i64, _>("int")?);
+    assert_eq!(1.5, row.try_get::<f64, _>("float")?);
+    assert!(row.try_get::<bool, _>("bool")?);
+    assert_eq!(None, row.try_get::<Option<i32>, _>("null")?);
+    assert_eq!(b"\x00\xffbytes", &*row.try_get::<Vec<u8>, _>("bytes")?);
+    assert_eq!(texts, row.try_get::<Vec<String>, _>("texts")?);
+    assert_eq!(
+        vec![Some(1), None, Some(3)],
+        row.try_get::<Vec<Option<i32>>, _>("ints")?
+    );
+    assert_eq!("$1", row.try_get::<String, _>("quoted")?);
+
+    // the arguments keep their types
+    let ty: String = sqlx::query_scalar("SELECT pg_typeof($1)::text")
+        .bind(1_i16)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!("smallint", ty);
+
+    let prepared: i64 = sqlx::query_scalar("SELECT count(*) FROM pg_prepared_statements")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(0, prepared);
+    assert_eq!(0, conn.statements_prepared());
+
+    // values with NUL bytes cannot be inlined
+    let res = sqlx::query("SELECT $1::text")
+        .bind("nul\0")
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Encode(_))));
+
+    Ok(())
+}
+
+#[cfg(feature = "offline-describe-cache")]
+#[sqlx_macros::test]
+async fn it_seeds_statements_from_offline_describe_cache() -> anyhow::Result<()> {
+    let dir = tempfile::tempdir()?;
+
+    // the column is named differently than the server would name it,
+    // to tell whether the saved metadata was used
+    std::fs::write(
+        dir.path().join("query-test.json"),
+        r#"{
+            "db_name": "PostgreSQL",
+            "query": "SELECT $1::int4 + 1 AS answer",
+            "describe": {
+                "columns": [{ "ordinal": 0, "name": "cached_answer", "type_info": "Int4" }],
+                "parameters": { "Left": ["Int4"] },
+                "nullable": [null]
+            },
+            "hash": "test"
+        }"#,
+    )?;
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .offline_describe_cache(dir.path());
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let row = sqlx::query("SELECT $1::int4 + 1 AS answer")
+        .bind(41_i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(row.try_get::<i32, _>("cached_answer")?, 42);
+
+    // other queries are described by the server
+    let row = sqlx::query("SELECT $1::int4 + 2 AS answer")
+        .bind(40_i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(row.try_get::<i32, _>("answer")?, 42);
+
+    let missing = options.offline_describe_cache(dir.path().join("missing"));
+    assert!(PgConnection::connect_with(&missing).await.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_column_errors_distinctly() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = conn.fetch_one("SELECT 'foo'::text AS name").await?;
+
+    let err = row.try_get::<String, _>("missing").unwrap_err();
+    assert!(matches!(err, sqlx::Error::ColumnNotFound(_)), "{err:?}");
+
+    let err = row.try_get::<String, _>(1).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            sqlx::Error::ColumnIndexOutOfBounds { index: 1, len: 1 }
+        ),
+        "{err:?}"
+    );
+
+    let err = row.try_get::<i32, _>("name").unwrap_err();
+    match &err {
+        sqlx::Error::ColumnDecode { index, source } => {
+            assert_eq!(index, "\"name\"");
+
+            let mismatch = source
+                .downcast_ref::<sqlx::error::MismatchedTypes>()
+                .expect("expected `MismatchedTypes`");
+            assert_eq!(mismatch.rust_type(), "i32");
+            assert_eq!(mismatch.sql_type(), "TEXT");
+        }
+        _ => panic!("expected `ColumnDecode`, got {err:?}"),
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resolves_duplicate_column_names() -> anyhow::Result<()> {
+    use sqlx::DuplicateColumnNames;
+
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    let query = "SELECT 1::int4 AS id, 2::int4 AS id, 3::int4 AS name";
+
+    // by default, the last column with the name wins
+    let mut conn = PgConnection::connect_with(&options).await?;
+    let row = sqlx::query(query).fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get::<i32, _>("id")?, 2);
+
+    let mut conn = PgConnection::connect_with(
+        &options
+            .clone()
+            .duplicate_column_names(DuplicateColumnNames::Error),
+    )
+    .await?;
+    let row = sqlx::query(query).fetch_one(&mut conn).await?;
+    let err = row.try_get::<i32, _>("id").unwrap_err();
+    assert!(
+        matches!(&err, sqlx::Error::ColumnNameAmbiguous(name) if name == "id"),
+        "{err:?}"
+    );
+    assert_eq!(row.try_get::<i32, _>(1)?, 2);
+    assert_eq!(row.try_get::<i32, _>("name")?, 3);
+
+    let mut conn =
+        PgConnection::connect_with(&options.duplicate_column_names(DuplicateColumnNames::Suffix))
+            .await?;
+    let row = sqlx::query(query).fetch_one(&mut conn).await?;
+    assert_eq!(row.column(1).name(), "id_1");
+    assert_eq!(row.try_get::<i32, _>("id")?, 1);
+    assert_eq!(row.try_get::<i32, _>("id_1")?, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+
+    // a capacity of 1 means that before each statement (after the first)
+    // we will close the previous statement
+    options = options.statement_cache_capacity(1);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    for i in 0..5 {
+        let row = sqlx::query(&*format!("SELECT {i}::int4 AS val"))
+            .fetch_one(&mut conn)
+            .await?;
+
+        let val: i32 = row.get("val");
+
+        assert_eq!(i, val);
+    }
+
+    assert_eq!(1, conn.cached_statements_size());
+
+    Ok(())
+}
+
+#[cfg(feature = "wire-trace")]
+#[sqlx_macros::test]
+async fn it_traces_wire_protocol_messages() -> anyhow::Result<()> {
+    use sqlx::wire_trace::Direction;
+    use sqlx::ConnectOptions;
+    use std::sync::Mutex;
+
+    sqlx_test::setup_if_needed();
+
+    let messages = Arc::new(Mutex::new(Vec::new()));
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+    let options = options.wire_trace({
+        let messages = messages.clone();
+        move |direction, bytes| messages.lock().unwrap().push((direction, bytes.to_vec()))
+    });
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+    messages.lock().unwrap().clear();
+
+    conn.execute("SELECT 1").await?;
+
+    let messages = messages.lock().unwrap();
+
+    // Query
+    let (direction, query) = &messages[0];
+    assert_eq!(*direction, Direction::Sent);
+    assert_eq!(query[0], b'Q');
+    assert_eq!(&query[5..], b"SELECT 1\0");
+
+    // RowDescription, DataRow, CommandComplete, ReadyForQuery
+    let received: Vec<u8> = messages[1..]
+        .iter()
+        .map(|(direction, bytes)| {
+            assert_eq!(*direction, Direction::Received);
+            bytes[0]
+        })
+        .collect();
+    assert_eq!(received, b"TDCZ");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_sets_application_name() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let mut options: PgConnectOptions = env::var("DATABASE_URL")?.parse().unwrap();
+    options = options.application_name("some-name");
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    let row = sqlx::query("select current_setting('application_name') as app_name")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let val: String = row.get("app_name");
+
+    assert_eq!("some-name", &val);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_handle_parameter_status_message_issue_484() -> anyhow::Result<()> {
+    new::<Postgres>().await?.execute("SET NAMES 'UTF8'").await?;
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    let tweet_id: i64 =
+        sqlx::query_scalar("INSERT INTO tweet ( text ) VALUES ( 'Hello, World' ) RETURNING id")
+            .fetch_one(&mut *tx)
+            .await?;
+
+    let statement = tx.prepare("SELECT * FROM tweet WHERE id = $1").await?;
+
+    assert_eq!(statement.column(0).name(), "id");
+    assert_eq!(statement.column(1).name(), "created_at");
+    assert_eq!(statement.column(2).name(), "text");
+    assert_eq!(statement.column(3).name(), "owner_id");
+
+    assert_eq!(statement.column(0).type_info().name(), "INT8");
+    assert_eq!(statement.column(1).type_info().name(), "TIMESTAMPTZ");
+    assert_eq!(statement.column(2).type_info().name(), "TEXT");
+    assert_eq!(statement.column(3).type_info().name(), "INT8");
+
+    let row = statement.query().bind(tweet_id).fetch_one(&mut *tx).await?;
+    let tweet_text: &str = row.try_get("text")?;
+
+    assert_eq!(tweet_text, "Hello, World");
+
+    Ok(())
+}
+
+// repro is more reliable with the basic scheduler used by `#[tokio::test]`
+#[cfg(feature = "_rt-tokio")]
+#[tokio::test]
+async fn test_issue_622() -> anyhow::Result<()> {
+    use std::time::Instant;
+
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1) // also fails with higher counts, e.g. 5
+        .connect(&std::env::var("DATABASE_URL").unwrap())
+        .await?;
+
+    println!("pool state: {pool:?}");
+
+    let mut handles = vec![];
+
+    // given repro spawned 100 tasks but I found it reliably reproduced with 3
+    for i in 0..3 {
+        let pool = pool.clone();
+
+        handles.push(sqlx_core::rt::spawn(async move {
+            {
+                let mut conn = pool.acquire().await.unwrap();
+
+                let _ = sqlx::query("SELECT 1").fetch_one(&mut *conn).await.unwrap();
+
+                // conn gets dropped here and should be returned to the pool
+            }
+
+            // (do some other work here without holding on to a connection)
+            // this actually fixes the issue, depending on the timeout used
+            // sqlx_core::rt::sleep(Duration::from_millis(500)).await;
+
+            {
+                let start = Instant::now();
+                match pool.acquire().await {
+                    Ok(conn) => {
+                        println!("{} acquire took {:?}", i, start.elapsed());
+                        drop(conn);
+                    }
+                    Err(e) => panic!("{i} acquire returned error: {e} pool state: {pool:?}"),
+                }
+            }
+
+            Result::<(), anyhow::Error>::Ok(())
+        }));
+    }
+
+    futures::future::try_join_all(handles).await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_describe_outer_join_nullable() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // test nullability inference for various joins
+
+    // inner join, nullability should not be overridden
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "select tweet.id
+    from tweet
+    inner join products on products.name = tweet.text",
+        )
+        .await?;
+
+    assert_eq!(describe.nullable(0), Some(false));
+
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "select tweet.id
+from (values (null)) vals(val)
+         left join tweet on false",
+        )
+        .await?;
+
+    // tweet.id is marked NOT NULL but it's brought in from a left-join here
+    // which should make it nullable
+    assert_eq!(describe.nullable(0), Some(true));
+
+    // make sure we don't mis-infer for the outer half of the join
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "select tweet1.id, tweet2.id
+    from tweet tweet1
+    left join tweet tweet2 on false",
+        )
+        .await?;
+
+    assert_eq!(describe.nullable(0), Some(false));
+    assert_eq!(describe.nullable(1), Some(true));
+
+    // right join, nullability should be inverted
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "select tweet1.id, tweet2.id
+    from tweet tweet1
+    right join tweet tweet2 on false",
+        )
+        .await?;
+
+    assert_eq!(describe.nullable(0), Some(true));
+    assert_eq!(describe.nullable(1), Some(false));
+
+    // full outer join, both tables are nullable
+    // language=PostgreSQL
+    let describe = conn
+        .describe(
+            "select tweet1.id, tweet2.id
+    from tweet tweet1
+    full join tweet tweet2 on false",
+        )
+        .await?;
+
+    assert_eq!(describe.nullable(0), Some(true));
+    assert_eq!(describe.nullable(1), Some(true));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_listener_cleanup() -> anyhow::Result<()> {
+    use sqlx_core::rt::timeout;
+
+    use sqlx::pool::PoolOptions;
+    use sqlx::postgres::PgListener;
+
+    // Create a connection on which to send notifications
+    let mut notify_conn = new::<Postgres>().await?;
+
+    // Create a pool with exactly one connection so we can
+    // deterministically test the cleanup.
+    let pool = PoolOptions::<Postgres>::new()
+        .min_connections(1)
+        .max_connections(1)
+        .test_before_acquire(true)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut listener = PgListener::connect_with(&pool).await?;
+    listener.listen("test_channel").await?;
+
+    // Checks for a notification on the test channel
+    async fn try_recv(listener: &mut PgListener) -> anyhow::Result<bool> {
+        match timeout(Duration::from_millis(100), listener.recv()).await {
+            Ok(res) => {
+                res?;
+                Ok(true)
+            }
+            Err(_) => Ok(false),
+        }
+    }
+
+    // Check no notification is received before one is sent
+    assert!(!try_recv(&mut listener).await?, "Notification not sent");
+
+    // Check notification is sent and received
+    notify_conn.execute("NOTIFY test_channel").await?;
+    assert!(
+        try_recv(&mut listener).await?,
+        "Notification sent and received"
+    );
+    assert!(
+        !try_recv(&mut listener).await?,
+        "Notification is not duplicated"
+    );
+
+    // Test that cleanup stops listening on the channel
+    drop(listener);
+    let mut listener = PgListener::connect_with(&pool).await?;
+
+    // Check notification is sent but not received
+    notify_conn.execute("NOTIFY test_channel").await?;
+    assert!(
+        !try_recv(&mut listener).await?,
+        "Notification is not received on fresh listener"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_listener_reconnect_policy() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    use sqlx::pool::PoolOptions;
+    use sqlx::postgres::{PgListener, PgReconnectEvent, PgReconnectPolicy};
+
+    let mut conn = new::<Postgres>().await?;
+
+    // While set, new connections fail, so the listener can't reconnect.
+    let fail = Arc::new(AtomicBool::new(false));
+
+    let pool = PoolOptions::<Postgres>::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(200))
+        .after_connect({
+            let fail = fail.clone();
+            move |_, _| {
+                let fail = fail.load(Ordering::SeqCst);
+                Box::pin(async move {
+                    if fail {
+                        Err(sqlx::Error::Protocol("refusing to connect".into()))
+                    } else {
+                        Ok(())
+                    }
+                })
+            }
+        })
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let mut listener = PgListener::connect_with(&pool).await?;
+    listener.reconnect_policy(
+        PgReconnectPolicy::new()
+            .initial_delay(Duration::from_millis(10))
+            .max_attempts(3),
+    );
+    listener.on_reconnect({
+        let events = events.clone();
+        let fail = fail.clone();
+        move |event| {
+            // Let the second attempt succeed.
+            if let PgReconnectEvent::Failed { .. } = event {
+                fail.store(false, Ordering::SeqCst);
+            }
+            events.lock().unwrap().push(format!("{event:?}"));
+        }
+    });
+    listener.listen("test_reconnect").await?;
+
+    async fn terminate(conn: &mut PgConnection, listener: &mut PgListener) -> anyhow::Result<()> {
+        let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+            .fetch_one(&mut *listener)
+            .await?;
+        sqlx::query("SELECT pg_terminate_backend($1)")
+            .bind(pid)
+            .execute(conn)
+            .await?;
+        Ok(())
+    }
+
+    terminate(&mut conn, &mut listener).await?;
+    fail.store(true, Ordering::SeqCst);
+
+    // The lost connection is reported once, then the listener reconnects on next use.
+    assert!(listener.try_recv().await?.is_none());
+    listener.execute("SELECT 1").await?;
+
+    conn.execute("NOTIFY test_reconnect, 'hello'").await?;
+    let notification = listener.recv().await?;
+    assert_eq!(notification.payload(), "hello");
+
+    {
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 4, "{events:?}");
+        assert_eq!(events[0], "Attempting { attempt: 1 }");
+        assert!(events[1].starts_with("Failed { attempt: 1,"), "{events:?}");
+        assert_eq!(events[2], "Attempting { attempt: 2 }");
+        assert_eq!(events[3], "Reconnected { attempts: 2 }");
+    }
+
+    // Now keep failing until the listener gives up.
+    listener.on_reconnect({
+        let events = events.clone();
+        move |event| events.lock().unwrap().push(format!("{event:?}"))
+    });
+    events.lock().unwrap().clear();
+
+    terminate(&mut conn, &mut listener).await?;
+    fail.store(true, Ordering::SeqCst);
+
+    assert!(listener.try_recv().await?.is_none());
+    assert!(matches!(
+        listener.try_recv().await,
+        Err(sqlx::Error::PoolTimedOut)
+    ));
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 6, "{events:?}");
+    assert!(events[5].starts_with("GaveUp { attempts: 3,"), "{events:?}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pg_listener_allows_pool_to_close() -> anyhow::Result<()> {
+    let pool = pool::<Postgres>().await?;
+
+    // acquires and holds a connection which would normally prevent the pool from closing
+    let mut listener = PgListener::connect_with(&pool).await?;
+
+    sqlx_core::rt::spawn(async move {
+        listener.recv().await.unwrap();
+    });
+
+    // would previously hang forever since `PgListener` had no way to know the pool wanted to close
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_supports_domain_types_in_composite_domain_types() -> anyhow::Result<()> {
+    // Only supported in Postgres 11+
+    let mut conn = new::<Postgres>().await?;
+    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
+        return Ok(());
+    }
+
+    conn.execute(
+        r#"
+DROP TABLE IF EXISTS heating_bills;
+DROP DOMAIN IF EXISTS winter_year_month;
+DROP TYPE IF EXISTS year_month;
+DROP DOMAIN IF EXISTS month_id;
+
+CREATE DOMAIN month_id AS INT2 CHECK (1 <= value AND value <= 12);
+CREATE TYPE year_month AS (year INT4, month month_id);
+CREATE DOMAIN winter_year_month AS year_month CHECK ((value).month <= 3);
+CREATE TABLE heating_bills (
+  month winter_year_month NOT NULL PRIMARY KEY,
+  cost INT4 NOT NULL
+);
+    "#,
+    )
+    .await?;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct MonthId(i16);
+
+    impl sqlx::Type<Postgres> for MonthId {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("month_id")
+        }
+
+        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+            *ty == Self::type_info()
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for MonthId {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            Ok(Self(<i16 as sqlx::Decode<Postgres>>::decode(value)?))
+        }
+    }
+
+    impl<'q> sqlx::Encode<'q, Postgres> for MonthId {
+        fn encode_by_ref(
+            &self,
+            buf: &mut sqlx::postgres::PgArgumentBuffer,
+        ) -> Result<sqlx::encode::IsNull, BoxDynError> {
+            <i16 as sqlx::Encode<Postgres>>::encode(self.0, buf)
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct WinterYearMonth {
+        year: i32,
+        month: MonthId,
+    }
+
+    impl sqlx::Type<Postgres> for WinterYearMonth {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("winter_year_month")
+        }
+
+        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+            *ty == Self::type_info()
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for WinterYearMonth {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
+
+            let year = decoder.try_decode::<i32>()?;
+            let month = decoder.try_decode::<MonthId>()?;
+
+            Ok(Self { year, month })
+        }
+    }
+
+    impl<'q> sqlx::Encode<'q, Postgres> for WinterYearMonth {
+        fn encode_by_ref(
+            &self,
+            buf: &mut sqlx::postgres::PgArgumentBuffer,
+        ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+            let mut encoder = sqlx::postgres::types::PgRecordEncoder::new(buf);
+            encoder.encode(self.year)?;
+            encoder.encode(self.month)?;
+            encoder.finish();
+            Ok(sqlx::encode::IsNull::No)
+        }
+    }
+    let mut conn = new::<Postgres>().await?;
+
+    let result = sqlx::query("DELETE FROM heating_bills;")
+        .execute(&mut conn)
+        .await;
+
+    let result = result.unwrap();
+    assert_eq!(result.rows_affected(), 0);
+
+    let result =
+        sqlx::query("INSERT INTO heating_bills(month, cost) VALUES($1::winter_year_month, 100);")
+            .bind(WinterYearMonth {
+                year: 2021,
+                month: MonthId(1),
+            })
+            .execute(&mut conn)
+            .await;
+
+    let result = result.unwrap();
+    assert_eq!(result.rows_affected(), 1);
+
+    let result = sqlx::query("DELETE FROM heating_bills;")
+        .execute(&mut conn)
+        .await;
+
+    let result = result.unwrap();
+    assert_eq!(result.rows_affected(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resolves_custom_type_in_array() -> anyhow::Result<()> {
+    // Only supported in Postgres 11+
+    let mut conn = new::<Postgres>().await?;
+    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
+        return Ok(());
+    }
+
+    // language=PostgreSQL
+    conn.execute(
+        r#"
+DROP TABLE IF EXISTS pets;
+DROP TYPE IF EXISTS pet_name_and_race;
+
+CREATE TYPE pet_name_and_race AS (
+  name TEXT,
+  race TEXT
+);
+CREATE TABLE pets (
+  owner TEXT NOT NULL,
+  name TEXT NOT NULL,
+  race TEXT NOT NULL,
+  PRIMARY KEY (owner, name)
+);
+INSERT INTO pets(owner, name, race)
+VALUES
+  ('Alice', 'Foo', 'cat');
+INSERT INTO pets(owner, name, race)
+VALUES
+  ('Alice', 'Bar', 'dog');
+    "#,
+    )
+    .await?;
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct PetNameAndRace {
+        name: String,
+        race: String,
+    }
+
+    impl sqlx::Type<Postgres> for PetNameAndRace {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("pet_name_and_race")
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for PetNameAndRace {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
+            let name = decoder.try_decode::<String>()?;
+            let race = decoder.try_decode::<String>()?;
+            Ok(Self { name, race })
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct PetNameAndRaceArray(Vec<PetNameAndRace>);
+
+    impl sqlx::Type<Postgres> for PetNameAndRaceArray {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            // Array type name is the name of the element type prefixed with `_`
+            sqlx::postgres::PgTypeInfo::with_name("_pet_name_and_race")
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for PetNameAndRaceArray {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            Ok(Self(Vec::<PetNameAndRace>::decode(value)?))
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query("select owner, array_agg(row(name, race)::pet_name_and_race) as pets from pets group by owner")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let pets: PetNameAndRaceArray = row.get("pets");
+
+    assert_eq!(pets.0.len(), 2);
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resolves_custom_types_in_anonymous_records() -> anyhow::Result<()> {
+    use sqlx_core::error::Error;
+    // This request involves nested records and array types.
+
+    // Only supported in Postgres 11+
+    let mut conn = new::<Postgres>().await?;
+    if matches!(conn.server_version_num(), Some(version) if version < 110000) {
+        return Ok(());
+    }
+
+    // language=PostgreSQL
+    conn.execute(
+        r#"
+DROP TABLE IF EXISTS repo_users;
+DROP TABLE IF EXISTS repositories;
+DROP TABLE IF EXISTS repo_memberships;
+DROP TYPE IF EXISTS repo_member;
+
+CREATE TABLE repo_users (
+  user_id INT4 NOT NULL,
+  username TEXT NOT NULL,
+  PRIMARY KEY (user_id)
+);
+CREATE TABLE repositories (
+  repo_id INT4 NOT NULL,
+  repo_name TEXT NOT NULL,
+  PRIMARY KEY (repo_id)
+);
+CREATE TABLE repo_memberships (
+  repo_id INT4 NOT NULL,
+  user_id INT4 NOT NULL,
+  permission TEXT NOT NULL,
+  PRIMARY KEY (repo_id, user_id)
+);
+CREATE TYPE repo_member AS (
+  user_id INT4,
+  permission TEXT
+);
+INSERT INTO repo_users(user_id, username)
+VALUES
+  (101, 'alice'),
+  (102, 'bob'),
+  (103, 'charlie');
+INSERT INTO repositories(repo_id, repo_name)
+VALUES
+  (201, 'rust'),
+  (202, 'sqlx'),
+  (203, 'hello-world');
+INSERT INTO repo_memberships(repo_id, user_id, permission)
+VALUES
+  (201, 101, 'admin'),
+  (201, 102, 'write'),
+  (201, 103, 'read'),
+  (202, 102, 'admin');
+"#,
+    )
+    .await?;
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct RepoMember {
+        user_id: i32,
+        permission: String,
+    }
+
+    impl sqlx::Type<Postgres> for RepoMember {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("repo_member")
+        }
+    }
 
     impl<'r> sqlx::Decode<'r, Postgres> for RepoMember {
         fn decode(
             value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
-            let user_id = decoder.try_decode::<i32>()?;
-            let permission = decoder.try_decode::<String>()?;
-            Ok(Self {
-                user_id,
-                permission,
-            })
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            let mut decoder = sqlx::postgres::types::PgRecordDecoder::new(value)?;
+            let user_id = decoder.try_decode::<i32>()?;
+            let permission = decoder.try_decode::<String>()?;
+            Ok(Self {
+                user_id,
+                permission,
+            })
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct RepoMemberArray(Vec<RepoMember>);
+
+    impl sqlx::Type<Postgres> for RepoMemberArray {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            // Array type name is the name of the element type prefixed with `_`
+            sqlx::postgres::PgTypeInfo::with_name("_repo_member")
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for RepoMemberArray {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            Ok(Self(Vec::<RepoMember>::decode(value)?))
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    #[derive(Debug, sqlx::FromRow)]
+    #[allow(dead_code)] // We don't actually read these fields.
+    struct Row {
+        count: i64,
+        items: Vec<(i32, String, RepoMemberArray)>,
+    }
+    // language=PostgreSQL
+    let row: Result<Row, Error> = sqlx::query_as::<_, Row>(
+        r"
+        WITH
+          members_by_repo AS (
+            SELECT repo_id,
+              ARRAY_AGG(ROW (user_id, permission)::repo_member) AS members
+            FROM repo_memberships
+            GROUP BY repo_id
+          ),
+          repos AS (
+            SELECT repo_id, repo_name, COALESCE(members, '{}') AS members
+            FROM repositories
+              LEFT OUTER JOIN members_by_repo USING (repo_id)
+            ORDER BY repo_id
+          ),
+          repo_array AS (
+            SELECT COALESCE(ARRAY_AGG(repos.*), '{}') AS items
+            FROM repos
+          ),
+          repo_count AS (
+            SELECT COUNT(*) AS count
+            FROM repos
+          )
+        SELECT count, items
+        FROM repo_count, repo_array
+        ;
+    ",
+    )
+    .fetch_one(&mut conn)
+    .await;
+
+    // This test currently tests mitigations for `#1672` (use regular errors
+    // instead of panics). Once we fully support custom types, it should be
+    // updated accordingly.
+    match row {
+        Ok(_) => panic!("full support for custom types is not implemented yet"),
+        Err(e) => assert!(e
+            .to_string()
+            .contains("custom types in records are not fully supported yet")),
+    }
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn custom_type_resolution_respects_search_path() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+DROP TYPE IF EXISTS some_enum_type;
+DROP SCHEMA IF EXISTS another CASCADE;
+
+CREATE SCHEMA another;
+CREATE TYPE some_enum_type AS ENUM ('a', 'b', 'c');
+CREATE TYPE another.some_enum_type AS ENUM ('d', 'e', 'f');
+    "#,
+    )
+    .await?;
+
+    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct SomeEnumType(String);
+
+    impl sqlx::Type<Postgres> for SomeEnumType {
+        fn type_info() -> sqlx::postgres::PgTypeInfo {
+            sqlx::postgres::PgTypeInfo::with_name("some_enum_type")
+        }
+
+        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+            *ty == Self::type_info()
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for SomeEnumType {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            Ok(Self(<String as sqlx::Decode<Postgres>>::decode(value)?))
+        }
+    }
+
+    impl<'q> sqlx::Encode<'q, Postgres> for SomeEnumType {
+        fn encode_by_ref(
+            &self,
+            buf: &mut sqlx::postgres::PgArgumentBuffer,
+        ) -> Result<sqlx::encode::IsNull, BoxDynError> {
+            <String as sqlx::Encode<Postgres>>::encode_by_ref(&self.0, buf)
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    sqlx::query("set search_path = 'another'")
+        .execute(&mut conn)
+        .await?;
+
+    let result = sqlx::query("SELECT 1 WHERE $1::some_enum_type = 'd'::some_enum_type;")
+        .bind(SomeEnumType("d".into()))
+        .fetch_all(&mut conn)
+        .await;
+
+    let result = result.unwrap();
+    assert_eq!(result.len(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_pg_server_num() -> anyhow::Result<()> {
+    let conn = new::<Postgres>().await?;
+
+    assert!(conn.server_version_num().is_some());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_in() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    conn.execute(
+        r#"
+        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
+    "#,
+    )
+    .await?;
+
+    let mut copy = conn
+        .copy_in_raw(
+            r#"
+        COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);
+    "#,
+        )
+        .await?;
+
+    copy.send("id\n1\n2\n".as_bytes()).await?;
+    let rows = copy.finish().await?;
+    assert_eq!(rows, 2);
+
+    // conn is safe for reuse
+    let value = sqlx::query("select 1 + 1")
+        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2i32, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_in_ignoring_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    conn.execute(
+        r#"
+        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
+    "#,
+    )
+    .await?;
+
+    let statement = "COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);";
+
+    if conn.server_version_num().unwrap_or(0) < 170000 {
+        let err = conn
+            .copy_in_raw_on_error(statement, PgCopyOnError::Ignore)
+            .await
+            .err();
+        assert!(matches!(err, Some(sqlx::Error::Configuration(_))));
+
+        // the statement was never sent, so the connection is still usable
+        let mut copy = conn
+            .copy_in_raw_on_error(statement, PgCopyOnError::Stop)
+            .await?;
+        copy.send("id\n1\n2\n".as_bytes()).await?;
+        let res = copy.finish_with_skipped().await?;
+        assert_eq!(res.rows_copied, 2);
+        assert_eq!(res.rows_skipped, 0);
+
+        return Ok(());
+    }
+
+    let mut copy = conn
+        .copy_in_raw_on_error(statement, PgCopyOnError::Ignore)
+        .await?;
+
+    copy.send("id\n1\nfoo\n2\nbar\n3\n".as_bytes()).await?;
+    let res = copy.finish_with_skipped().await?;
+    assert_eq!(res.rows_copied, 3);
+    assert_eq!(res.rows_skipped, 2);
+
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_abort_copy_in() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    conn.execute(
+        r#"
+        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
+    "#,
+    )
+    .await?;
+
+    let copy = conn
+        .copy_in_raw(
+            r#"
+        COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);
+    "#,
+        )
+        .await?;
+
+    copy.abort("this is only a test").await?;
+
+    // conn is safe for reuse
+    let value = sqlx::query("select 1 + 1")
+        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2i32, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_copy_out() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    {
+        let mut copy = conn
+            .copy_out_raw(
+                "
+            COPY (SELECT generate_series(1, 2) AS id) TO STDOUT WITH (FORMAT CSV, HEADER);
+        ",
+            )
+            .await?;
+
+        assert_eq!(copy.next().await.unwrap().unwrap(), "id\n");
+        assert_eq!(copy.next().await.unwrap().unwrap(), "1\n");
+        assert_eq!(copy.next().await.unwrap().unwrap(), "2\n");
+        if copy.next().await.is_some() {
+            anyhow::bail!("Unexpected data from COPY");
+        }
+    }
+
+    // conn is safe for reuse
+    let value = sqlx::query("select 1 + 1")
+        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2i32, value);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_encodes_custom_array_issue_1504() -> anyhow::Result<()> {
+    use sqlx::encode::IsNull;
+    use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
+    use sqlx::{Decode, Encode, Type, ValueRef};
+
+    #[derive(Debug, PartialEq)]
+    enum Value {
+        String(String),
+        Number(i32),
+        Array(Vec<Value>),
+    }
+
+    impl<'r> Decode<'r, Postgres> for Value {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> std::result::Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            let typ = value.type_info().into_owned();
+
+            if typ == PgTypeInfo::with_name("text") {
+                let s = <String as Decode<'_, Postgres>>::decode(value)?;
+
+                Ok(Self::String(s))
+            } else if typ == PgTypeInfo::with_name("int4") {
+                let n = <i32 as Decode<'_, Postgres>>::decode(value)?;
+
+                Ok(Self::Number(n))
+            } else if typ == PgTypeInfo::with_name("_text") {
+                let arr = Vec::<String>::decode(value)?;
+                let v = arr.into_iter().map(|s| Value::String(s)).collect();
+
+                Ok(Self::Array(v))
+            } else if typ == PgTypeInfo::with_name("_int4") {
+                let arr = Vec::<i32>::decode(value)?;
+                let v = arr.into_iter().map(|n| Value::Number(n)).collect();
+
+                Ok(Self::Array(v))
+            } else {
+                Err("unknown type".into())
+            }
+        }
+    }
+
+    impl Encode<'_, Postgres> for Value {
+        fn produces(&self) -> Option<PgTypeInfo> {
+            match self {
+                Self::Array(a) => {
+                    if a.len() < 1 {
+                        return Some(PgTypeInfo::with_name("_text"));
+                    }
+
+                    match a[0] {
+                        Self::String(_) => Some(PgTypeInfo::with_name("_text")),
+                        Self::Number(_) => Some(PgTypeInfo::with_name("_int4")),
+                        Self::Array(_) => None,
+                    }
+                }
+                Self::String(_) => Some(PgTypeInfo::with_name("text")),
+                Self::Number(_) => Some(PgTypeInfo::with_name("int4")),
+            }
+        }
+
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
+            match self {
+                Value::String(s) => <String as Encode<'_, Postgres>>::encode_by_ref(s, buf),
+                Value::Number(n) => <i32 as Encode<'_, Postgres>>::encode_by_ref(n, buf),
+                Value::Array(arr) => arr.encode(buf),
+            }
+        }
+    }
+
+    impl Type<Postgres> for Value {
+        fn type_info() -> PgTypeInfo {
+            PgTypeInfo::with_name("unknown")
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            [
+                PgTypeInfo::with_name("text"),
+                PgTypeInfo::with_name("_text"),
+                PgTypeInfo::with_name("int4"),
+                PgTypeInfo::with_name("_int4"),
+            ]
+            .contains(ty)
+        }
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let (row,): (Value,) = sqlx::query_as("SELECT $1::text[] as Dummy")
+        .bind(Value::Array(vec![
+            Value::String("Test 0".to_string()),
+            Value::String("Test 1".to_string()),
+        ]))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(
+        row,
+        Value::Array(vec![
+            Value::String("Test 0".to_string()),
+            Value::String("Test 1".to_string()),
+        ])
+    );
+
+    let (row,): (Value,) = sqlx::query_as("SELECT $1::int4[] as Dummy")
+        .bind(Value::Array(vec![
+            Value::Number(3),
+            Value::Number(2),
+            Value::Number(1),
+        ]))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(
+        row,
+        Value::Array(vec![Value::Number(3), Value::Number(2), Value::Number(1)])
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_issue_1254() -> anyhow::Result<()> {
+    #[derive(sqlx::Type)]
+    #[sqlx(type_name = "pair")]
+    struct Pair {
+        one: i32,
+        two: i32,
+    }
+
+    // array for custom type is not supported, use wrapper
+    #[derive(sqlx::Type)]
+    #[sqlx(type_name = "_pair")]
+    struct Pairs(Vec<Pair>);
+
+    let mut conn = new::<Postgres>().await?;
+    conn.execute(
+        "
+DROP TABLE IF EXISTS issue_1254;
+DROP TYPE IF EXISTS pair;
+
+CREATE TYPE pair AS (one INT4, two INT4);
+CREATE TABLE issue_1254 (id INT4 PRIMARY KEY, pairs PAIR[]);
+",
+    )
+    .await?;
+
+    let result = sqlx::query("INSERT INTO issue_1254 VALUES($1, $2)")
+        .bind(0)
+        .bind(Pairs(vec![Pair { one: 94, two: 87 }]))
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(result.rows_affected(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_advisory_locks() -> anyhow::Result<()> {
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let lock1 = Arc::new(PgAdvisoryLock::new("sqlx-postgres-tests-1"));
+    let lock2 = Arc::new(PgAdvisoryLock::new("sqlx-postgres-tests-2"));
+
+    let conn1 = pool.acquire().await?;
+    let mut conn1_lock1 = lock1.acquire(conn1).await?;
+
+    // try acquiring a recursive lock through a mutable reference then dropping
+    drop(lock1.acquire(&mut conn1_lock1).await?);
+
+    let conn2 = pool.acquire().await?;
+    // leak so we can take it across the task boundary
+    let conn2_lock2 = lock2.acquire(conn2).await?.leak();
+
+    sqlx_core::rt::spawn({
+        let lock1 = lock1.clone();
+        let lock2 = lock2.clone();
+
+        async move {
+            let conn2_lock2 = lock1.try_acquire(conn2_lock2).await?.right_or_else(|_| {
+                panic!(
+                    "acquired lock but wasn't supposed to! Key: {:?}",
+                    lock1.key()
+                )
+            });
+
+            let (conn2, released) = lock2.force_release(conn2_lock2).await?;
+            assert!(released);
+
+            // acquire both locks but let the pool release them
+            let conn2_lock1 = lock1.acquire(conn2).await?;
+            let _conn2_lock1and2 = lock2.acquire(conn2_lock1).await?;
+
+            anyhow::Ok(())
         }
-    }
+    });
 
-    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct RepoMemberArray(Vec<RepoMember>);
+    // acquire lock2 on conn1, we leak the lock1 guard so we can manually release it before lock2
+    let conn1_lock1and2 = lock2.acquire(conn1_lock1.leak()).await?;
 
-    impl sqlx::Type<Postgres> for RepoMemberArray {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            // Array type name is the name of the element type prefixed with `_`
-            sqlx::postgres::PgTypeInfo::with_name("_repo_member")
-        }
+    // release lock1 while holding lock2
+    let (conn1_lock2, released) = lock1.force_release(conn1_lock1and2).await?;
+    assert!(released);
+
+    let conn1 = conn1_lock2.release_now().await?;
+
+    // acquire both locks to be sure they were released
+    {
+        let conn1_lock1 = lock1.acquire(conn1).await?;
+        let _conn1_lock1and2 = lock2.acquire(conn1_lock1).await?;
     }
 
-    impl<'r> sqlx::Decode<'r, Postgres> for RepoMemberArray {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            Ok(Self(Vec::<RepoMember>::decode(value)?))
-        }
+    pool.close().await;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_postgres_bytea_hex_deserialization_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    conn.execute("SET bytea_output = 'escape';").await?;
+    for value in ["", "DEADBEEF"] {
+        let query = format!("SELECT '\\x{value}'::bytea");
+        let res: sqlx::Result<Vec<u8>> = conn.fetch_one(query.as_str()).await?.try_get(0usize);
+        // Deserialization only supports hex format so this should error and definitely not panic.
+        res.unwrap_err();
     }
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_shrink_buffers() -> anyhow::Result<()> {
+    // We don't really have a good way to test that `.shrink_buffers()` functions as expected
+    // without exposing a lot of internals, but we can at least be sure it doesn't
+    // materially affect the operation of the connection.
 
     let mut conn = new::<Postgres>().await?;
 
-    #[derive(Debug, sqlx::FromRow)]
-    #[allow(dead_code)] // We don't actually read these fields.
-    struct Row {
-        count: i64,
-        items: Vec<(i32, String, RepoMemberArray)>,
-    }
-    // language=PostgreSQL
-    let row: Result<Row, Error> = sqlx::query_as::<_, Row>(
-        r"
-        WITH
-          members_by_repo AS (
-            SELECT repo_id,
-              ARRAY_AGG(ROW (user_id, permission)::repo_member) AS members
-            FROM repo_memberships
-            GROUP BY repo_id
-          ),
-          repos AS (
-            SELECT repo_id, repo_name, COALESCE(members, '{}') AS members
-            FROM repositories
-              LEFT OUTER JOIN members_by_repo USING (repo_id)
-            ORDER BY repo_id
-          ),
-          repo_array AS (
-            SELECT COALESCE(ARRAY_AGG(repos.*), '{}') AS items
-            FROM repos
-          ),
-          repo_count AS (
-            SELECT COUNT(*) AS count
-            FROM repos
-          )
-        SELECT count, items
-        FROM repo_count, repo_array
-        ;
-    ",
+    // The connection buffer is only 8 KiB by default so this should definitely force it to grow.
+    let data = vec![0u8; 32 * 1024];
+
+    let ret: Vec<u8> = sqlx::query_scalar("SELECT $1::bytea")
+        .bind(&data)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(ret, data);
+
+    conn.shrink_buffers();
+
+    let ret: i64 = sqlx::query_scalar("SELECT $1::int8")
+        .bind(&12345678i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(ret, 12345678i64);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_error_handling_with_deferred_constraints() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    sqlx::query("CREATE TABLE IF NOT EXISTS deferred_constraint ( id INTEGER PRIMARY KEY )")
+        .execute(&mut conn)
+        .await?;
+
+    sqlx::query("CREATE TABLE IF NOT EXISTS deferred_constraint_fk ( fk INTEGER CONSTRAINT deferred_fk REFERENCES deferred_constraint(id) DEFERRABLE INITIALLY DEFERRED )")
+            .execute(&mut conn)
+            .await?;
+
+    let result: sqlx::Result<i32> =
+        sqlx::query_scalar("INSERT INTO deferred_constraint_fk VALUES (1) RETURNING fk")
+            .fetch_one(&mut conn)
+            .await;
+
+    let err = result.unwrap_err();
+    let db_err = err.as_database_error().unwrap();
+    assert_eq!(db_err.constraint(), Some("deferred_fk"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+#[cfg(feature = "bigdecimal")]
+async fn test_issue_3052() {
+    use sqlx::types::BigDecimal;
+
+    // https://github.com/launchbadge/sqlx/issues/3052
+    // Previously, attempting to bind a `BigDecimal` would panic if the value was out of range.
+    // Now, we rewrite it to a sentinel value so that Postgres will return a range error.
+    let too_small: BigDecimal = "1E-65536".parse().unwrap();
+    let too_large: BigDecimal = "1E262144".parse().unwrap();
+
+    let mut conn = new::<Postgres>().await.unwrap();
+
+    let too_small_error = sqlx::query_scalar::<_, BigDecimal>("SELECT $1::numeric")
+        .bind(&too_small)
+        .fetch_one(&mut conn)
+        .await
+        .expect_err("Too small number should have failed");
+    assert!(
+        matches!(&too_small_error, sqlx::Error::Encode(_)),
+        "expected encode error, got {too_small_error:?}"
+    );
+
+    let too_large_error = sqlx::query_scalar::<_, BigDecimal>("SELECT $1::numeric")
+        .bind(&too_large)
+        .fetch_one(&mut conn)
+        .await
+        .expect_err("Too large number should have failed");
+
+    assert!(
+        matches!(&too_large_error, sqlx::Error::Encode(_)),
+        "expected encode error, got {too_large_error:?}",
+    );
+}
+
+#[sqlx_macros::test]
+async fn it_converts_rows_to_json() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let sql = r#"
+        SELECT
+            1::int2 AS small, 2::int4 AS int, 3::int8 AS big, NULL::int4 AS null,
+            true AS bool, 1.5::float8 AS float, 'NaN'::float4 AS nan,
+            12345.6789::numeric AS numeric, -0.00012::numeric AS small_numeric,
+            10000::numeric(10, 2) AS numeric_scale,
+            'text' AS text, '\x0102'::bytea AS bytes, '{"a": [1, null]}'::jsonb AS json,
+            ARRAY[1, NULL, 3] AS array, ARRAY[['a', 'b'], ['c', 'd']] AS matrix,
+            ARRAY['a,"b\', NULL, 'NULL'] AS strings,
+            '{}'::numeric[] AS empty, '2024-01-02'::date AS date
+    "#;
+
+    let expected = serde_json::json!({
+        "small": 1, "int": 2, "big": 3, "null": null,
+        "bool": true, "float": 1.5, "nan": "NaN",
+        "numeric": "12345.6789", "small_numeric": "-0.00012",
+        "numeric_scale": "10000.00",
+        "text": "text", "bytes": [1, 2], "json": { "a": [1, null] },
+        "array": [1, null, 3], "matrix": [["a", "b"], ["c", "d"]],
+        "strings": ["a,\"b\\", null, "NULL"],
+        "empty": [], "date": "2024-01-02",
+    });
+
+    // The text format, as used by queries without arguments.
+    let row = conn.fetch_one(sql).await?;
+    assert_eq!(sqlx::row_to_json(&row)?, expected);
+
+    // The binary format, except for `DATE` which has no mapping.
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+    let err = sqlx::row_to_json(&row).unwrap_err();
+    assert!(matches!(err, sqlx::Error::ColumnDecode { ref index, .. } if index == "\"date\""));
+
+    let sql = sql.replace("'2024-01-02'::date", "'2024-01-02'::date::text");
+    let row = sqlx::query(&sql).fetch_one(&mut conn).await?;
+    assert_eq!(sqlx::row_to_json(&row)?, expected);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_prepares_statements_on_new_connections() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let statements = ["SELECT 1", "SELECT 2"];
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .prepare_statements(statements)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    assert_eq!(pool.options().get_prepare_statements(), statements);
+
+    // The statements are prepared before the connection is handed out.
+    let conn = pool.acquire().await?;
+    assert_eq!(conn.cached_statements_size(), 2);
+    drop(conn);
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .prepare_statements_in_background(statements)
+        .connect_lazy(&env::var("DATABASE_URL")?)?;
+
+    // The statements are prepared once the connection is first returned.
+    let conn = pool.acquire().await?;
+    assert_eq!(conn.cached_statements_size(), 0);
+    drop(conn);
+
+    let conn = pool.acquire().await?;
+    assert_eq!(conn.cached_statements_size(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_limits_the_number_of_rows() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let sql = "SELECT generate_series(1, 10)::int4";
+
+    let rows = sqlx::query(sql).max_rows(10).fetch_all(&mut conn).await?;
+    assert_eq!(rows.len(), 10);
+
+    let res = sqlx::query(sql).max_rows(9).fetch_all(&mut conn).await;
+    assert!(
+        matches!(res, Err(sqlx::Error::RowLimitExceeded { max_rows: 9 })),
+        "{res:?}"
+    );
+
+    // The rows up to the limit are still streamed.
+    let mut stream = sqlx::query_scalar::<_, i32>(sql)
+        .max_rows(2)
+        .fetch(&mut conn);
+    assert_eq!(stream.try_next().await?, Some(1));
+    assert_eq!(stream.try_next().await?, Some(2));
+    assert!(matches!(
+        stream.try_next().await,
+        Err(sqlx::Error::RowLimitExceeded { max_rows: 2 })
+    ));
+    drop(stream);
+
+    let res = sqlx::query_as::<_, (i32,)>(sql)
+        .max_rows(0)
+        .fetch_optional(&mut conn)
+        .await;
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::RowLimitExceeded { max_rows: 0 })
+    ));
+
+    // The connection is still usable afterwards.
+    let value: i32 = sqlx::query_scalar("SELECT 1")
+        .max_rows(1)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checks_the_number_of_binds() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let res = sqlx::query("SELECT $1::int4, $2::int4")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::BindCountMismatch {
+            expected: 2,
+            provided: 1
+        })
+    ));
+
+    let res = sqlx::query("SELECT $1::int4")
+        .bind(1_i32)
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::BindCountMismatch {
+            expected: 1,
+            provided: 2
+        })
+    ));
+
+    // placeholders in strings and comments don't count
+    let value: i32 = sqlx::query_scalar("SELECT $1::int4 + length('$2') -- $3")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 3);
+
+    // without binds, parameters may belong to the statement
+    conn.execute("PREPARE bind_count(int4) AS SELECT $1").await?;
+    let value: i32 = sqlx::query_scalar("EXECUTE bind_count(7)")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 7);
+    conn.execute("DEALLOCATE bind_count").await?;
+
+    sqlx::query("CREATE FUNCTION pg_temp.bind_count(int4) RETURNS int4 RETURN $1")
+        .execute(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_times_out_the_whole_query() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let timeout = Duration::from_millis(500);
+
+    // a slow query is cancelled on the server, so this doesn't wait for it to finish
+    let res = sqlx_core::rt::timeout(
+        Duration::from_secs(10),
+        sqlx::query("SELECT pg_sleep(30)")
+            .total_timeout(timeout)
+            .execute(&mut conn),
     )
-    .fetch_one(&mut conn)
-    .await;
+    .await
+    .expect("query was not timed out");
+
+    assert!(matches!(res, Err(sqlx::Error::QueryTimedOut { timeout: t }) if t == timeout));
+
+    // a slow consumer of a fast query times out too
+    let mut stream = sqlx::query_scalar::<_, i32>("SELECT generate_series(1, 100)")
+        .total_timeout(timeout)
+        .fetch(&mut conn);
+
+    let mut received = 0;
+
+    let err = loop {
+        match stream.try_next().await {
+            Ok(Some(_)) => received += 1,
+            Ok(None) => panic!("query completed within the timeout"),
+            Err(e) => break e,
+        }
+
+        sqlx_core::rt::sleep(Duration::from_millis(100)).await;
+    };
+
+    drop(stream);
+
+    assert!(matches!(err, sqlx::Error::QueryTimedOut { .. }), "{err}");
+    assert!(received < 100);
+
+    // the connection is still usable
+    let value: i32 = sqlx::query_scalar("SELECT 1")
+        .total_timeout(timeout)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 1);
 
-    // This test currently tests mitigations for `#1672` (use regular errors
-    // instead of panics). Once we fully support custom types, it should be
-    // updated accordingly.
-    match row {
-        Ok(_) => panic!("full support for custom types is not implemented yet"),
-        Err(e) => assert!(e
-            .to_string()
-            .contains("custom types in records are not fully supported yet")),
-    }
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn custom_type_resolution_respects_search_path() -> anyhow::Result<()> {
+async fn it_runs_queries_with_span_attributes() -> anyhow::Result<()> {
+    sqlx_test::capture::install();
+
     let mut conn = new::<Postgres>().await?;
 
-    conn.execute(
-        r#"
-DROP TYPE IF EXISTS some_enum_type;
-DROP SCHEMA IF EXISTS another CASCADE;
+    let tenant_id = 4242_i64;
 
-CREATE SCHEMA another;
-CREATE TYPE some_enum_type AS ENUM ('a', 'b', 'c');
-CREATE TYPE another.some_enum_type AS ENUM ('d', 'e', 'f');
-    "#,
-    )
-    .await?;
+    let value: i64 = sqlx::query_scalar("SELECT $1")
+        .bind(tenant_id)
+        .with_span_attributes([("tenant_id", tenant_id.to_string())])
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 4242);
 
-    #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-    struct SomeEnumType(String);
+    let rows: Vec<(i32,)> = sqlx::query_as::<_, (i32,)>("SELECT generate_series(1, 3)")
+        .with_span_attributes([("tenant_id", "4343"), ("flag", "span-attributes-beta")])
+        .fetch(&mut conn)
+        .try_collect()
+        .await?;
+    assert_eq!(rows, [(1,), (2,), (3,)]);
 
-    impl sqlx::Type<Postgres> for SomeEnumType {
-        fn type_info() -> sqlx::postgres::PgTypeInfo {
-            sqlx::postgres::PgTypeInfo::with_name("some_enum_type")
-        }
+    let done = sqlx::query("SELECT 1")
+        .with_span_attributes([("flag", "span-attributes-gamma")])
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(done.rows_affected(), 1);
 
-        fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
-            *ty == Self::type_info()
-        }
-    }
+    // each pair is a field of its own
+    let spans = |field: &str, value: &str| {
+        sqlx_test::capture::spans(|span| span.name == "db.query" && span.field(field) == Some(value))
+    };
 
-    impl<'r> sqlx::Decode<'r, Postgres> for SomeEnumType {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            Ok(Self(<String as sqlx::Decode<Postgres>>::decode(value)?))
-        }
-    }
+    let [span] = &spans("tenant_id", "4242")[..] else {
+        panic!("expected one span");
+    };
+    assert_eq!(span.field("summary"), Some("SELECT $1"));
+    assert_eq!(span.field("flag"), None);
 
-    impl<'q> sqlx::Encode<'q, Postgres> for SomeEnumType {
-        fn encode_by_ref(
-            &self,
-            buf: &mut sqlx::postgres::PgArgumentBuffer,
-        ) -> Result<sqlx::encode::IsNull, BoxDynError> {
-            <String as sqlx::Encode<Postgres>>::encode_by_ref(&self.0, buf)
-        }
-    }
+    let [span] = &spans("flag", "span-attributes-beta")[..] else {
+        panic!("expected one span");
+    };
+    assert_eq!(span.field("tenant_id"), Some("4343"));
 
-    let mut conn = new::<Postgres>().await?;
+    assert_eq!(spans("flag", "span-attributes-gamma").len(), 1);
 
-    sqlx::query("set search_path = 'another'")
+    // queries without attributes get no span
+    sqlx::query("SELECT 'span-attributes-none'")
         .execute(&mut conn)
         .await?;
 
-    let result = sqlx::query("SELECT 1 WHERE $1::some_enum_type = 'd'::some_enum_type;")
-        .bind(SomeEnumType("d".into()))
-        .fetch_all(&mut conn)
-        .await;
-
-    let result = result.unwrap();
-    assert_eq!(result.len(), 1);
+    let untraced = sqlx_test::capture::spans(|span| {
+        span.field("summary")
+            .is_some_and(|summary| summary.contains("span-attributes-none"))
+    });
+    assert!(untraced.is_empty());
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_pg_server_num() -> anyhow::Result<()> {
-    let conn = new::<Postgres>().await?;
+async fn it_resets_the_session() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    assert!(conn.server_version_num().is_some());
+    conn.execute("CREATE TEMPORARY TABLE reset_me (id INT)")
+        .await?;
+    conn.execute("SET application_name = 'reset_me'").await?;
+    let _: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(conn.cached_statements_size(), 1);
+    assert_eq!(conn.prepared_statement_count(), 1);
+
+    // Left open without going through `begin()`.
+    conn.execute("BEGIN").await?;
+
+    conn.reset().await?;
+
+    assert_eq!(conn.cached_statements_size(), 0);
+    assert_eq!(conn.prepared_statement_count(), 0);
+
+    // `SAVEPOINT` can only be used in a transaction block.
+    assert!(conn.execute("SAVEPOINT reset_me").await.is_err());
+
+    let temp_tables: i64 =
+        sqlx::query_scalar("SELECT count(*) FROM pg_class WHERE relname = 'reset_me'")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(temp_tables, 0);
+
+    let application_name: String = sqlx::query_scalar("SHOW application_name")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_ne!(application_name, "reset_me");
+
+    // Statements are prepared again as needed.
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_copy_in() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
-    conn.execute(
-        r#"
-        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
-    "#,
-    )
-    .await?;
+async fn it_resets_connections_on_return() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    let mut copy = conn
-        .copy_in_raw(
-            r#"
-        COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);
-    "#,
-        )
-        .await?;
+    let temp_tables = "SELECT count(*) FROM pg_class WHERE relname = 'returned'";
 
-    copy.send("id\n1\n2\n".as_bytes()).await?;
-    let rows = copy.finish().await?;
-    assert_eq!(rows, 2);
+    for (reset, keeps_temp_table) in [("rollback", true), ("discard-all", false)] {
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .reset_on_return(reset.parse()?)
+            .connect(&env::var("DATABASE_URL")?)
+            .await?;
 
-    // conn is safe for reuse
-    let value = sqlx::query("select 1 + 1")
-        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
-        .fetch_one(&mut conn)
-        .await?;
+        let mut conn = pool.acquire().await?;
+        conn.execute("CREATE TEMPORARY TABLE returned (id INT)")
+            .await?;
+        // Left open without going through `begin()`.
+        conn.execute("BEGIN").await?;
+        drop(conn);
 
-    assert_eq!(2i32, value);
+        let mut conn = pool.acquire().await?;
+
+        // `SAVEPOINT` can only be used in a transaction block.
+        assert!(conn.execute("SAVEPOINT returned").await.is_err(), "{reset}");
+
+        let count: i64 = sqlx::query_scalar(temp_tables).fetch_one(&mut *conn).await?;
+        assert_eq!(count == 1, keeps_temp_table, "{reset}");
+    }
+
+    let err = "discard".parse::<sqlx::pool::ResetOnReturn>().unwrap_err();
+    assert!(matches!(err, sqlx::Error::Configuration(_)), "{err}");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_abort_copy_in() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
-    conn.execute(
-        r#"
-        CREATE TEMPORARY TABLE users (id INTEGER NOT NULL);
-    "#,
-    )
-    .await?;
+async fn it_sets_the_plan_cache_mode() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    let copy = conn
-        .copy_in_raw(
-            r#"
-        COPY users (id) FROM STDIN WITH (FORMAT CSV, HEADER);
-    "#,
-        )
-        .await?;
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .plan_cache_mode(PgPlanCacheMode::ForceCustomPlan);
 
-    copy.abort("this is only a test").await?;
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-    // conn is safe for reuse
-    let value = sqlx::query("select 1 + 1")
-        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
+    let mode: String = sqlx::query_scalar("SHOW plan_cache_mode")
         .fetch_one(&mut conn)
         .await?;
+    assert_eq!(mode, "force_custom_plan");
 
-    assert_eq!(2i32, value);
+    // `DISCARD ALL` resets the parameter, so it is applied again
+    conn.execute("SET plan_cache_mode = auto").await?;
+    conn.reset().await?;
+
+    let mode: String = sqlx::query_scalar("SHOW plan_cache_mode")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(mode, "force_custom_plan");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_can_copy_out() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
+async fn it_times_out_waiting_for_locks() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    {
-        let mut copy = conn
-            .copy_out_raw(
-                "
-            COPY (SELECT generate_series(1, 2) AS id) TO STDOUT WITH (FORMAT CSV, HEADER);
-        ",
-            )
-            .await?;
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .lock_timeout(Duration::from_millis(100));
 
-        assert_eq!(copy.next().await.unwrap().unwrap(), "id\n");
-        assert_eq!(copy.next().await.unwrap().unwrap(), "1\n");
-        assert_eq!(copy.next().await.unwrap().unwrap(), "2\n");
-        if copy.next().await.is_some() {
-            anyhow::bail!("Unexpected data from COPY");
-        }
-    }
+    let mut holder = new::<Postgres>().await?;
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-    // conn is safe for reuse
-    let value = sqlx::query("select 1 + 1")
-        .try_map(|row: PgRow| row.try_get::<i32, _>(0))
-        .fetch_one(&mut conn)
+    holder
+        .execute("CREATE TABLE IF NOT EXISTS lock_timeout_test (id INT)")
         .await?;
 
-    assert_eq!(2i32, value);
+    let mut tx = holder.begin().await?;
+    tx.execute("LOCK TABLE lock_timeout_test IN ACCESS EXCLUSIVE MODE")
+        .await?;
+
+    let err = conn
+        .execute("SELECT * FROM lock_timeout_test")
+        .await
+        .unwrap_err();
+
+    let err = err.as_database_error().expect("a database error");
+    assert!(err.is_lock_timeout(), "{err:?}");
+    assert_eq!(err.code().unwrap(), "55P03");
+
+    tx.rollback().await?;
+
+    // `DISCARD ALL` resets the parameter, so it is applied again
+    conn.execute("SET lock_timeout = 0").await?;
+    conn.reset().await?;
+
+    let timeout: String = sqlx::query_scalar("SHOW lock_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(timeout, "100ms");
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn it_encodes_custom_array_issue_1504() -> anyhow::Result<()> {
-    use sqlx::encode::IsNull;
-    use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
-    use sqlx::{Decode, Encode, Type, ValueRef};
+async fn it_fails_queries_on_matching_notices() -> anyhow::Result<()> {
+    setup_if_needed();
 
-    #[derive(Debug, PartialEq)]
-    enum Value {
-        String(String),
-        Number(i32),
-        Array(Vec<Value>),
-    }
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .notices_as_errors(|notice| notice.severity() == PgSeverity::Warning);
 
-    impl<'r> Decode<'r, Postgres> for Value {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> std::result::Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            let typ = value.type_info().into_owned();
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-            if typ == PgTypeInfo::with_name("text") {
-                let s = <String as Decode<'_, Postgres>>::decode(value)?;
+    let err = conn
+        .execute("DO $$ BEGIN RAISE WARNING 'trigger should not fire'; END $$")
+        .await
+        .unwrap_err();
 
-                Ok(Self::String(s))
-            } else if typ == PgTypeInfo::with_name("int4") {
-                let n = <i32 as Decode<'_, Postgres>>::decode(value)?;
+    let err = err.into_database_error().unwrap();
+    let err = err.downcast_ref::<PgDatabaseError>();
+    assert_eq!(err.severity(), PgSeverity::Warning);
+    assert_eq!(err.message(), "trigger should not fire");
 
-                Ok(Self::Number(n))
-            } else if typ == PgTypeInfo::with_name("_text") {
-                let arr = Vec::<String>::decode(value)?;
-                let v = arr.into_iter().map(|s| Value::String(s)).collect();
+    // other notices are only logged
+    conn.execute("DO $$ BEGIN RAISE NOTICE 'just saying'; END $$")
+        .await?;
 
-                Ok(Self::Array(v))
-            } else if typ == PgTypeInfo::with_name("_int4") {
-                let arr = Vec::<i32>::decode(value)?;
-                let v = arr.into_iter().map(|n| Value::Number(n)).collect();
+    // the rest of the failed query is drained before the next one
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
 
-                Ok(Self::Array(v))
-            } else {
-                Err("unknown type".into())
-            }
-        }
-    }
+    Ok(())
+}
 
-    impl Encode<'_, Postgres> for Value {
-        fn produces(&self) -> Option<PgTypeInfo> {
-            match self {
-                Self::Array(a) => {
-                    if a.len() < 1 {
-                        return Some(PgTypeInfo::with_name("_text"));
-                    }
+#[sqlx_macros::test]
+async fn it_decodes_registered_enum_arrays() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgEnumValue;
 
-                    match a[0] {
-                        Self::String(_) => Some(PgTypeInfo::with_name("_text")),
-                        Self::Number(_) => Some(PgTypeInfo::with_name("_int4")),
-                        Self::Array(_) => None,
-                    }
-                }
-                Self::String(_) => Some(PgTypeInfo::with_name("text")),
-                Self::Number(_) => Some(PgTypeInfo::with_name("int4")),
-            }
-        }
+    let mut conn = new::<Postgres>().await?;
 
-        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> Result<IsNull, BoxDynError> {
-            match self {
-                Value::String(s) => <String as Encode<'_, Postgres>>::encode_by_ref(s, buf),
-                Value::Number(n) => <i32 as Encode<'_, Postgres>>::encode_by_ref(n, buf),
-                Value::Array(arr) => arr.encode(buf),
-            }
-        }
-    }
+    // the text format can't look up the enum in the middle of a query
+    let row = sqlx::raw_sql("SELECT '{new,open}'::status[]")
+        .fetch_one(&mut conn)
+        .await?;
 
-    impl Type<Postgres> for Value {
-        fn type_info() -> PgTypeInfo {
-            PgTypeInfo::with_name("unknown")
-        }
+    assert!(row.try_get::<Vec<PgEnumValue>, _>(0).is_err());
 
-        fn compatible(ty: &PgTypeInfo) -> bool {
-            [
-                PgTypeInfo::with_name("text"),
-                PgTypeInfo::with_name("_text"),
-                PgTypeInfo::with_name("int4"),
-                PgTypeInfo::with_name("_int4"),
-            ]
-            .contains(ty)
-        }
-    }
+    // but prepared queries can
+    let statuses: Vec<PgEnumValue> = sqlx::query_scalar("SELECT '{new,closed}'::status[]")
+        .fetch_one(&mut conn)
+        .await?;
 
-    let mut conn = new::<Postgres>().await?;
+    assert_eq!(statuses, [PgEnumValue("new".into()), PgEnumValue("closed".into())]);
 
-    let (row,): (Value,) = sqlx::query_as("SELECT $1::text[] as Dummy")
-        .bind(Value::Array(vec![
-            Value::String("Test 0".to_string()),
-            Value::String("Test 1".to_string()),
-        ]))
+    conn.register_enum("status", &["new", "open"]).await?;
+
+    let row = sqlx::raw_sql("SELECT '{new,open}'::status[], 'open'::status")
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(
-        row,
-        Value::Array(vec![
-            Value::String("Test 0".to_string()),
-            Value::String("Test 1".to_string()),
-        ])
-    );
+    let statuses: Vec<PgEnumValue> = row.try_get(0)?;
+    assert_eq!(statuses, [PgEnumValue("new".into()), PgEnumValue("open".into())]);
+    assert_eq!(row.try_get::<PgEnumValue, _>(1)?.as_str(), "open");
 
-    let (row,): (Value,) = sqlx::query_as("SELECT $1::int4[] as Dummy")
-        .bind(Value::Array(vec![
-            Value::Number(3),
-            Value::Number(2),
-            Value::Number(1),
-        ]))
+    // a label that wasn't registered doesn't decode
+    let row = sqlx::query("SELECT '{open,closed}'::status[]")
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(
-        row,
-        Value::Array(vec![Value::Number(3), Value::Number(2), Value::Number(1)])
-    );
+    assert!(row.try_get::<Vec<PgEnumValue>, _>(0).is_err());
+
+    assert!(conn.register_enum("status", &["pending"]).await.is_err());
+    assert!(conn.register_enum("int4", &[]).await.is_err());
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_issue_1254() -> anyhow::Result<()> {
-    #[derive(sqlx::Type)]
-    #[sqlx(type_name = "pair")]
-    struct Pair {
-        one: i32,
-        two: i32,
-    }
+async fn it_tags_the_connection() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    // array for custom type is not supported, use wrapper
-    #[derive(sqlx::Type)]
-    #[sqlx(type_name = "_pair")]
-    struct Pairs(Vec<Pair>);
+    let untagged: String = conn.fetch_one("SHOW application_name").await?.get(0);
+
+    let tagged: String = conn
+        .with_tag("request-1234", |conn| {
+            Box::pin(async move {
+                sqlx::query_scalar(
+                    "SELECT application_name FROM pg_stat_activity WHERE pid = pg_backend_pid()",
+                )
+                .fetch_one(conn)
+                .await
+            })
+        })
+        .await?;
+
+    assert_eq!(tagged, "request-1234");
+
+    let after: String = conn.fetch_one("SHOW application_name").await?.get(0);
+    assert_eq!(after, untagged);
+
+    // the tag is removed even if the function fails
+    let res: Result<(), sqlx::Error> = conn
+        .with_tag("request-5678", |conn| {
+            Box::pin(async move {
+                conn.execute("SELECT 1/0").await?;
+                Ok(())
+            })
+        })
+        .await;
 
+    assert!(res.is_err());
+
+    let after: String = conn.fetch_one("SHOW application_name").await?.get(0);
+    assert_eq!(after, untagged);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_chunks_any_parameters() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
-    conn.execute(
-        "
-DROP TABLE IF EXISTS issue_1254;
-DROP TYPE IF EXISTS pair;
 
-CREATE TYPE pair AS (one INT4, two INT4);
-CREATE TABLE issue_1254 (id INT4 PRIMARY KEY, pairs PAIR[]);
-",
-    )
-    .await?;
+    conn.execute("CREATE TEMPORARY TABLE any_chunked (id INT4 PRIMARY KEY, tenant_id INT4)")
+        .await?;
+    conn.execute("INSERT INTO any_chunked SELECT i, i % 2 FROM generate_series(1, 100) i")
+        .await?;
 
-    let result = sqlx::query("INSERT INTO issue_1254 VALUES($1, $2)")
-        .bind(0)
-        .bind(Pairs(vec![Pair { one: 94, two: 87 }]))
+    let ids: Vec<i32> = (1..=50).collect();
+
+    // the arguments bound before are used for each chunk
+    let mut found: Vec<i32> = sqlx::query("SELECT id FROM any_chunked WHERE tenant_id = $1 AND")
+        .bind(0_i32)
+        .any_chunked("id", ids.clone(), 7)
+        .fetch_all(&mut conn)
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+
+    found.sort_unstable();
+    assert_eq!(found, (2..=50).step_by(2).collect::<Vec<_>>());
+
+    // below the threshold, the query runs once
+    let rows = sqlx::query("SELECT id FROM any_chunked WHERE")
+        .any_chunked("id", ids.clone(), 1000)
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(rows.len(), 50);
+
+    // the row limit applies to all chunks together
+    let res = sqlx::query("SELECT id FROM any_chunked WHERE")
+        .max_rows(20)
+        .any_chunked("id", ids.clone(), 15)
+        .fetch_all(&mut conn)
+        .await;
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::RowLimitExceeded { max_rows: 20 })
+    ));
+
+    let res = sqlx::query("DELETE FROM any_chunked WHERE")
+        .any_chunked("id", ids, 9)
         .execute(&mut conn)
         .await?;
-    assert_eq!(result.rows_affected(), 1);
+    assert_eq!(res.rows_affected(), 50);
+
+    // nothing is executed without values
+    let rows = sqlx::query("SELECT syntax error WHERE")
+        .any_chunked("id", Vec::<i32>::new(), 10)
+        .fetch_all(&mut conn)
+        .await?;
+    assert!(rows.is_empty());
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_advisory_locks() -> anyhow::Result<()> {
-    let pool = PgPoolOptions::new()
-        .max_connections(2)
-        .connect(&dotenvy::var("DATABASE_URL")?)
-        .await?;
+#[cfg(feature = "bigdecimal")]
+async fn it_rejects_decimals_that_would_be_rounded() -> anyhow::Result<()> {
+    use sqlx::postgres::PgDecimalScaleMode;
+    use sqlx::types::BigDecimal;
 
-    let lock1 = Arc::new(PgAdvisoryLock::new("sqlx-postgres-tests-1"));
-    let lock2 = Arc::new(PgAdvisoryLock::new("sqlx-postgres-tests-2"));
+    setup_if_needed();
 
-    let conn1 = pool.acquire().await?;
-    let mut conn1_lock1 = lock1.acquire(conn1).await?;
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .decimal_scale_mode(PgDecimalScaleMode::Error);
 
-    // try acquiring a recursive lock through a mutable reference then dropping
-    drop(lock1.acquire(&mut conn1_lock1).await?);
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-    let conn2 = pool.acquire().await?;
-    // leak so we can take it across the task boundary
-    let conn2_lock2 = lock2.acquire(conn2).await?.leak();
+    conn.execute("CREATE TEMPORARY TABLE dec_scale (id int4, price numeric(10, 2), qty numeric)")
+        .await?;
 
-    sqlx_core::rt::spawn({
-        let lock1 = lock1.clone();
-        let lock2 = lock2.clone();
+    let decimal = |s: &str| s.parse::<BigDecimal>().unwrap();
 
-        async move {
-            let conn2_lock2 = lock1.try_acquire(conn2_lock2).await?.right_or_else(|_| {
-                panic!(
-                    "acquired lock but wasn't supposed to! Key: {:?}",
-                    lock1.key()
-                )
-            });
+    // trailing zeros don't count and unconstrained columns take any scale
+    sqlx::query("INSERT INTO dec_scale (id, price, qty) VALUES ($1, $2, $3), ($4, $5, $6)")
+        .bind(1)
+        .bind(decimal("19.90"))
+        .bind(decimal("0.333333"))
+        .bind(2)
+        .bind(decimal("5.5000"))
+        .bind(decimal("1"))
+        .execute(&mut conn)
+        .await?;
 
-            let (conn2, released) = lock2.force_release(conn2_lock2).await?;
-            assert!(released);
+    let err = sqlx::query("INSERT INTO dec_scale (id, price) VALUES ($1, $2)")
+        .bind(3)
+        .bind(decimal("19.999"))
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
 
-            // acquire both locks but let the pool release them
-            let conn2_lock1 = lock1.acquire(conn2).await?;
-            let _conn2_lock1and2 = lock2.acquire(conn2_lock1).await?;
+    assert!(
+        matches!(
+            &err,
+            sqlx::Error::DecimalScaleOverflow { index: 2, column, scale: 3, max_scale: 2 }
+                if column == "price"
+        ),
+        "unexpected error: {err:?}"
+    );
 
-            anyhow::Ok(())
-        }
-    });
+    let err = sqlx::query("UPDATE dec_scale SET price = $1 WHERE id = $2")
+        .bind(decimal("0.001"))
+        .bind(1)
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
 
-    // acquire lock2 on conn1, we leak the lock1 guard so we can manually release it before lock2
-    let conn1_lock1and2 = lock2.acquire(conn1_lock1.leak()).await?;
+    assert!(matches!(err, sqlx::Error::DecimalScaleOverflow { index: 1, .. }));
 
-    // release lock1 while holding lock2
-    let (conn1_lock2, released) = lock1.force_release(conn1_lock1and2).await?;
-    assert!(released);
+    // expressions are left to Postgres
+    sqlx::query("UPDATE dec_scale SET price = round($1, 2) WHERE id = $2")
+        .bind(decimal("0.001"))
+        .bind(1)
+        .execute(&mut conn)
+        .await?;
 
-    let conn1 = conn1_lock2.release_now().await?;
+    let total: BigDecimal = sqlx::query_scalar("SELECT sum(price) FROM dec_scale")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(total, decimal("5.50"));
 
-    // acquire both locks to be sure they were released
-    {
-        let conn1_lock1 = lock1.acquire(conn1).await?;
-        let _conn1_lock1and2 = lock2.acquire(conn1_lock1).await?;
+    Ok(())
+}
+
+/// Whether the server authenticates `conn` with SCRAM over TLS, which channel binding needs.
+///
+/// `None` if that can't be told: servers before Postgres 16 don't report the auth method.
+async fn uses_scram_over_tls(conn: &mut PgConnection) -> anyhow::Result<Option<bool>> {
+    let tls: bool = sqlx::query_scalar("SELECT ssl FROM pg_stat_ssl WHERE pid = pg_backend_pid()")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    if !tls {
+        return Ok(Some(false));
     }
 
-    pool.close().await;
+    if conn.server_version_num().unwrap_or(0) < 160000 {
+        return Ok(None);
+    }
 
-    Ok(())
+    // `NULL` for `trust`, otherwise prefixed with the auth method
+    let scram = sqlx::query_scalar("SELECT coalesce(system_user, '') LIKE 'scram-sha-256:%'")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    Ok(Some(scram))
 }
 
 #[sqlx_macros::test]
-async fn test_postgres_bytea_hex_deserialization_errors() -> anyhow::Result<()> {
-    let mut conn = new::<Postgres>().await?;
-    conn.execute("SET bytea_output = 'escape';").await?;
-    for value in ["", "DEADBEEF"] {
-        let query = format!("SELECT '\\x{value}'::bytea");
-        let res: sqlx::Result<Vec<u8>> = conn.fetch_one(query.as_str()).await?.try_get(0usize);
-        // Deserialization only supports hex format so this should error and definitely not panic.
-        res.unwrap_err();
+async fn it_requires_channel_binding() -> anyhow::Result<()> {
+    use sqlx::postgres::PgChannelBinding;
+
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?.parse::<PgConnectOptions>()?;
+
+    let mut conn =
+        PgConnection::connect_with(&options.clone().channel_binding(PgChannelBinding::Disable))
+            .await?;
+
+    if uses_scram_over_tls(&mut conn).await? != Some(false) {
+        return Ok(());
     }
+
+    // the server authenticates us without SCRAM-SHA-256-PLUS, e.g. with `trust` or without TLS
+    let required = options.channel_binding(PgChannelBinding::Require);
+    let err = PgConnection::connect_with(&required).await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::Tls(_)), "unexpected error: {err:?}");
+
+    conn.ping().await?;
+
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_shrink_buffers() -> anyhow::Result<()> {
-    // We don't really have a good way to test that `.shrink_buffers()` functions as expected
-    // without exposing a lot of internals, but we can at least be sure it doesn't
-    // materially affect the operation of the connection.
-
-    let mut conn = new::<Postgres>().await?;
-
-    // The connection buffer is only 8 KiB by default so this should definitely force it to grow.
-    let data = vec![0u8; 32 * 1024];
+async fn it_binds_the_channel_with_scram_over_tls() -> anyhow::Result<()> {
+    use sqlx::postgres::PgChannelBinding;
 
-    let ret: Vec<u8> = sqlx::query_scalar("SELECT $1::bytea")
-        .bind(&data)
-        .fetch_one(&mut conn)
-        .await?;
+    setup_if_needed();
 
-    assert_eq!(ret, data);
+    let options = env::var("DATABASE_URL")?.parse::<PgConnectOptions>()?;
 
-    conn.shrink_buffers();
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-    let ret: i64 = sqlx::query_scalar("SELECT $1::int8")
-        .bind(&12345678i64)
-        .fetch_one(&mut conn)
-        .await?;
+    if uses_scram_over_tls(&mut conn).await? != Some(true) {
+        return Ok(());
+    }
 
-    assert_eq!(ret, 12345678i64);
+    let required = options.channel_binding(PgChannelBinding::Require);
+    let mut conn = PgConnection::connect_with(&required).await?;
+    conn.ping().await?;
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-async fn test_error_handling_with_deferred_constraints() -> anyhow::Result<()> {
+async fn it_returns_query_stats() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
-    sqlx::query("CREATE TABLE IF NOT EXISTS deferred_constraint ( id INTEGER PRIMARY KEY )")
-        .execute(&mut conn)
+    let (rows, stats) = sqlx::query("SELECT i FROM generate_series(1, $1) AS i")
+        .bind(5_i32)
+        .with_stats()
+        .fetch_all(&mut conn)
         .await?;
 
-    sqlx::query("CREATE TABLE IF NOT EXISTS deferred_constraint_fk ( fk INTEGER CONSTRAINT deferred_fk REFERENCES deferred_constraint(id) DEFERRABLE INITIALLY DEFERRED )")
-            .execute(&mut conn)
-            .await?;
+    assert_eq!(rows.len(), 5);
+    assert_eq!(stats.rows_returned, 5);
 
-    let result: sqlx::Result<i32> =
-        sqlx::query_scalar("INSERT INTO deferred_constraint_fk VALUES (1) RETURNING fk")
-            .fetch_one(&mut conn)
-            .await;
+    // only MySQL reports these
+    assert_eq!(stats.rows_examined, None);
+    assert_eq!(stats.bytes_received, None);
 
-    let err = result.unwrap_err();
-    let db_err = err.as_database_error().unwrap();
-    assert_eq!(db_err.constraint(), Some("deferred_fk"));
+    // the counters are read without disturbing a transaction
+    let mut tx = conn.begin().await?;
+
+    let (_, stats) = sqlx::query("SELECT 1 WHERE false")
+        .with_stats()
+        .execute(&mut *tx)
+        .await?;
+    assert_eq!(stats.rows_returned, 0);
+
+    tx.rollback().await?;
 
     Ok(())
 }
 
 #[sqlx_macros::test]
-#[cfg(feature = "bigdecimal")]
-async fn test_issue_3052() {
-    use sqlx::types::BigDecimal;
+async fn it_logs_parameters_formatted_by_redact_parameters() -> anyhow::Result<()> {
+    setup_if_needed();
+    sqlx_test::capture::install();
 
-    // https://github.com/launchbadge/sqlx/issues/3052
-    // Previously, attempting to bind a `BigDecimal` would panic if the value was out of range.
-    // Now, we rewrite it to a sentinel value so that Postgres will return a range error.
-    let too_small: BigDecimal = "1E-65536".parse().unwrap();
-    let too_large: BigDecimal = "1E262144".parse().unwrap();
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .redact_parameters(|i, value| match i {
+            1 => format!("{value:?}"),
+            _ => "<redacted>".into(),
+        });
+    let mut conn = PgConnection::connect_with(&options).await?;
 
-    let mut conn = new::<Postgres>().await.unwrap();
+    sqlx::query("SELECT $1::int4 AS redacted_log_1, $2::text")
+        .bind(42_i32)
+        .bind("hunter2")
+        .execute(&mut conn)
+        .await?;
 
-    let too_small_error = sqlx::query_scalar::<_, BigDecimal>("SELECT $1::numeric")
-        .bind(&too_small)
-        .fetch_one(&mut conn)
-        .await
-        .expect_err("Too small number should have failed");
-    assert!(
-        matches!(&too_small_error, sqlx::Error::Encode(_)),
-        "expected encode error, got {too_small_error:?}"
-    );
+    // parameters are redacted by default
+    let mut conn = PgConnection::connect(&env::var("DATABASE_URL")?).await?;
 
-    let too_large_error = sqlx::query_scalar::<_, BigDecimal>("SELECT $1::numeric")
-        .bind(&too_large)
-        .fetch_one(&mut conn)
-        .await
-        .expect_err("Too large number should have failed");
+    sqlx::query("SELECT $1::text AS redacted_log_2")
+        .bind("hunter2")
+        .execute(&mut conn)
+        .await?;
 
-    assert!(
-        matches!(&too_large_error, sqlx::Error::Encode(_)),
-        "expected encode error, got {too_large_error:?}",
-    );
+    let parameters = |marker: &str| {
+        sqlx_test::capture::events(|event| event.field("summary").unwrap_or("").contains(marker))
+            .iter()
+            .map(|event| event.field("db.parameters").unwrap_or("").to_owned())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(parameters("redacted_log_1"), ["INT4 0x0000002a, <redacted>"]);
+    assert_eq!(parameters("redacted_log_2"), [""]);
+
+    Ok(())
 }
@@ -1,5 +1,5 @@
 use sqlx::postgres::Postgres;
-use sqlx::query_builder::QueryBuilder;
+use sqlx::query_builder::{ConflictClause, ConflictClauseError, QueryBuilder};
 use sqlx::Executor;
 use sqlx::Type;
 use sqlx::{Either, Execute};
@@ -157,3 +157,330 @@ async fn test_max_number_of_binds() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx::test]
+async fn test_build_query_as_returning_star() -> anyhow::Result<()> {
+    #[derive(Debug, PartialEq, sqlx::FromRow)]
+    struct Account {
+        // Declared in a different order than the table to check columns are matched by name.
+        name: String,
+        nickname: Option<String>,
+        id: i32,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE accounts (id INT4 NOT NULL, nickname TEXT, name TEXT NOT NULL)",
+    )
+    .await?;
+
+    let mut qb: QueryBuilder<'_, Postgres> =
+        QueryBuilder::new("INSERT INTO accounts (id, name, nickname) ");
+
+    qb.push_values(
+        [(1, "alice", Some("ally")), (2, "bob", None)],
+        |mut b, (id, name, nickname)| {
+            b.push_bind(id).push_bind(name).push_bind(nickname);
+        },
+    );
+
+    qb.push(" RETURNING *");
+
+    let accounts: Vec<Account> = qb.build_query_as().fetch_all(&mut conn).await?;
+
+    assert_eq!(
+        accounts,
+        [
+            Account {
+                id: 1,
+                name: "alice".into(),
+                nickname: Some("ally".into()),
+            },
+            Account {
+                id: 2,
+                name: "bob".into(),
+                nickname: None,
+            },
+        ]
+    );
+
+    Ok(())
+}
+
+#[sqlx::test]
+async fn test_push_unnest() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE pets (id INT4 NOT NULL, name TEXT NOT NULL, age INT2)")
+        .await?;
+
+    let pets = [
+        (1, "alice", Some(3_i16)),
+        (2, "bob", None),
+        (3, "carol", Some(7)),
+    ];
+
+    let mut qb: QueryBuilder<'_, Postgres> =
+        QueryBuilder::new("INSERT INTO pets (id, name, age) SELECT * FROM ");
+
+    qb.push_unnest(pets);
+    qb.push(" RETURNING id, name, age");
+
+    assert_eq!(
+        qb.sql(),
+        "INSERT INTO pets (id, name, age) SELECT * FROM unnest($1::INT4[], $2::TEXT[], $3::INT2[]) \
+         RETURNING id, name, age"
+    );
+
+    let inserted: Vec<(i32, String, Option<i16>)> =
+        qb.build_query_as().fetch_all(&mut conn).await?;
+
+    assert_eq!(
+        inserted,
+        pets.map(|(id, name, age)| (id, name.to_owned(), age))
+    );
+
+    // no rows
+    let mut qb: QueryBuilder<'_, Postgres> =
+        QueryBuilder::new("INSERT INTO pets (id, name, age) SELECT * FROM ");
+
+    qb.push_unnest(Vec::<(i32, String, Option<i16>)>::new());
+
+    let result = qb.build().execute(&mut conn).await?;
+    assert_eq!(result.rows_affected(), 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_push_delete_in() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE doomed (id INT8 NOT NULL); \
+         INSERT INTO doomed SELECT generate_series(1, 10)",
+    )
+    .await?;
+
+    // 7 ids in chunks of 3 take 3 statements
+    let result =
+        QueryBuilder::<Postgres>::push_delete_in(&mut conn, "doomed", "id", 1..=7_i64, 3).await?;
+    assert_eq!(result.rows_affected(), 7);
+
+    let result =
+        QueryBuilder::<Postgres>::push_delete_in(&mut conn, "doomed", "id", Vec::<i64>::new(), 3)
+            .await?;
+    assert_eq!(result.rows_affected(), 0);
+
+    let remaining: Vec<i64> = sqlx::query_scalar("SELECT id FROM doomed ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(remaining, [8, 9, 10]);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_push_on_conflict() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE counters (name TEXT PRIMARY KEY, count INT4 NOT NULL, note TEXT); \
+         INSERT INTO counters VALUES ('hits', 1, 'old'), ('misses', 1, 'old')",
+    )
+    .await?;
+
+    let upsert = |clause: ConflictClause| -> anyhow::Result<_> {
+        let mut qb: QueryBuilder<'_, Postgres> =
+            QueryBuilder::new("INSERT INTO counters (name, count, note) ");
+        qb.push_values([("hits", 5, "new"), ("misses", 5, "new")], |mut b, row| {
+            b.push_bind(row.0).push_bind(row.1).push_bind(row.2);
+        });
+        qb.push_on_conflict(&clause)?;
+        Ok(qb)
+    };
+
+    let mut qb = upsert(ConflictClause::columns(["name"]))?;
+    assert!(qb.sql().ends_with(" ON CONFLICT (name) DO NOTHING"));
+    assert_eq!(qb.build().execute(&mut conn).await?.rows_affected(), 0);
+
+    let mut qb = upsert(
+        ConflictClause::constraint("counters_pkey")
+            .do_update(["note"])
+            .set("count", "counters.count + EXCLUDED.count")
+            .where_("counters.name = 'hits'"),
+    )?;
+    assert!(qb.sql().ends_with(
+        " ON CONFLICT ON CONSTRAINT counters_pkey DO UPDATE SET note = EXCLUDED.note, \
+         count = counters.count + EXCLUDED.count WHERE counters.name = 'hits'"
+    ));
+    assert_eq!(qb.build().execute(&mut conn).await?.rows_affected(), 1);
+
+    let rows: Vec<(String, i32, String)> =
+        sqlx::query_as("SELECT name, count, note FROM counters ORDER BY name")
+            .fetch_all(&mut conn)
+            .await?;
+    assert_eq!(
+        rows,
+        [
+            ("hits".to_owned(), 6, "new".to_owned()),
+            ("misses".to_owned(), 1, "old".to_owned()),
+        ]
+    );
+
+    let qb = upsert(ConflictClause::any())?;
+    assert!(qb.sql().ends_with(" ON CONFLICT DO NOTHING"));
+
+    // nothing is pushed for a clause that can't be expressed
+    let mut qb: QueryBuilder<'_, Postgres> = QueryBuilder::new("INSERT INTO counters");
+    let mut err = |clause| qb.push_on_conflict(&clause).map(|_| ()).unwrap_err();
+
+    assert_eq!(
+        err(ConflictClause::columns(Vec::<String>::new())),
+        ConflictClauseError::NoColumns
+    );
+    assert_eq!(
+        err(ConflictClause::any().do_update(["note"])),
+        ConflictClauseError::UpdateWithoutTarget
+    );
+    assert_eq!(qb.sql(), "INSERT INTO counters");
+
+    // `Any` could be any database, so the syntax must be chosen explicitly
+    #[cfg(feature = "any")]
+    {
+        let mut qb: QueryBuilder<'_, sqlx::Any> = QueryBuilder::new("INSERT INTO counters");
+        let clause = ConflictClause::columns(["name"]).do_update(["count"]);
+        assert!(matches!(
+            qb.push_on_conflict(&clause),
+            Err(ConflictClauseError::UnknownBackend(_))
+        ));
+        qb.push_on_conflict_for(&clause, "MySQL")?;
+        assert_eq!(
+            qb.sql(),
+            "INSERT INTO counters ON DUPLICATE KEY UPDATE count = VALUES(count)"
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "json")]
+#[sqlx_macros::test]
+async fn test_push_bind_cast_jsonb_operators() -> anyhow::Result<()> {
+    use serde_json::{json, Value as JsonValue};
+    use sqlx::postgres::PgConnection;
+    use sqlx::types::Json;
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE events (id INT4 PRIMARY KEY, data JSONB NOT NULL);
+INSERT INTO events (id, data) VALUES
+    (1, '{"kind": "login", "user": "alice"}'),
+    (2, '{"kind": "logout", "user": "bob"}'),
+    (3, '{"kind": "login", "session": "x"}');
+"#,
+    )
+    .await?;
+
+    async fn ids(qb: &mut QueryBuilder<'_, Postgres>, conn: &mut PgConnection) -> Vec<i32> {
+        qb.push(" ORDER BY id");
+        qb.build_query_scalar().fetch_all(conn).await.unwrap()
+    }
+
+    // @>
+    let mut qb = QueryBuilder::new("SELECT id FROM events WHERE data @> ");
+    qb.push_bind_cast(Json(json!({ "kind": "login" })))?;
+    assert_eq!(qb.sql(), r#"SELECT id FROM events WHERE data @> $1::"jsonb""#);
+    assert_eq!(ids(&mut qb, &mut conn).await, [1, 3]);
+
+    // <@
+    let mut qb = QueryBuilder::new("SELECT id FROM events WHERE data <@ ");
+    qb.push_bind_cast(json!({ "kind": "logout", "user": "bob", "extra": true }))?;
+    assert_eq!(ids(&mut qb, &mut conn).await, [2]);
+
+    // ?
+    let mut qb = QueryBuilder::new("SELECT id FROM events WHERE data ? ");
+    qb.push_bind_cast("session")?;
+    assert_eq!(qb.sql(), r#"SELECT id FROM events WHERE data ? $1::"text""#);
+    assert_eq!(ids(&mut qb, &mut conn).await, [3]);
+
+    // ?|
+    let mut qb = QueryBuilder::new("SELECT id FROM events WHERE data ?| ");
+    qb.push_bind_cast(vec!["session".to_owned(), "user".to_owned()])?;
+    assert_eq!(qb.sql(), r#"SELECT id FROM events WHERE data ?| $1::"_text""#);
+    assert_eq!(ids(&mut qb, &mut conn).await, [1, 2, 3]);
+
+    // the bound document decodes back as it was sent
+    let doc: Json<JsonValue> =
+        sqlx::query_scalar("SELECT data FROM events WHERE data @> $1::jsonb")
+            .bind(Json(json!({ "session": "x" })))
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(doc.0, json!({ "kind": "login", "session": "x" }));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_push_bind_cast_type_names() -> anyhow::Result<()> {
+    use sqlx::postgres::PgTypeInfo;
+    use sqlx::query_builder::BindCastError;
+    use sqlx::Encode;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // `"char"` is the one-byte type, `char` would be `bpchar`
+    let mut qb: QueryBuilder<'_, Postgres> = QueryBuilder::new("SELECT ");
+    qb.push_bind_cast(7_i8)?;
+    assert_eq!(qb.sql(), r#"SELECT $1::"char""#);
+    let value: i8 = qb.build_query_scalar().fetch_one(&mut conn).await?;
+    assert_eq!(value, 7);
+
+    // a type declared by name is cast to that name
+    #[derive(sqlx::Type)]
+    #[sqlx(type_name = "text")]
+    struct Label(String);
+
+    let mut qb: QueryBuilder<'_, Postgres> = QueryBuilder::new("SELECT ");
+    qb.push_bind_cast(Label("x".to_owned()))?;
+    assert_eq!(qb.sql(), "SELECT $1::text");
+    let value: String = qb.build_query_scalar().fetch_one(&mut conn).await?;
+    assert_eq!(value, "x");
+
+    // a type only known by its OID has no name to cast to, and nothing is pushed
+    struct ByOid(i32);
+
+    impl Type<Postgres> for ByOid {
+        fn type_info() -> PgTypeInfo {
+            PgTypeInfo::with_oid(sqlx::postgres::types::Oid(23))
+        }
+    }
+
+    impl Encode<'_, Postgres> for ByOid {
+        fn encode_by_ref(
+            &self,
+            buf: &mut sqlx::postgres::PgArgumentBuffer,
+        ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+            <i32 as Encode<Postgres>>::encode_by_ref(&self.0, buf)
+        }
+    }
+
+    let mut qb: QueryBuilder<'_, Postgres> = QueryBuilder::new("SELECT ");
+    let err = qb.push_bind_cast(ByOid(1)).map(|_| ()).unwrap_err();
+    assert!(matches!(err, BindCastError::UnnamedType(_)), "{err:?}");
+    assert_eq!(qb.sql(), "SELECT ");
+
+    #[cfg(feature = "any")]
+    {
+        let mut qb: QueryBuilder<'_, sqlx::Any> = QueryBuilder::new("SELECT ");
+        assert_eq!(
+            qb.push_bind_cast(1_i32).map(|_| ()),
+            Err(BindCastError::Unsupported("Any".to_owned()))
+        );
+    }
+
+    Ok(())
+}
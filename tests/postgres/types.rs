@@ -3,7 +3,10 @@ extern crate time_ as time;
 use std::net::SocketAddr;
 use std::ops::Bound;
 
-use sqlx::postgres::types::{Oid, PgCiText, PgInterval, PgMoney, PgRange};
+use sqlx::postgres::types::{
+    Oid, PgAclItem, PgAclPrivilegeKind, PgCiText, PgInterval, PgLsn, PgMoney, PgNumericInt,
+    PgRange, PgRegConfig, PgTid,
+};
 use sqlx::postgres::Postgres;
 use sqlx_test::{new, test_decode_type, test_prepared_type, test_type};
 
@@ -23,6 +26,43 @@ test_type!(null_array<[Option<i16>; 3]>(Postgres,
     "array[10,NULL,50]::int2[]" == vec![Some(10_i16), None, Some(50)],
 ));
 
+#[sqlx_macros::test]
+async fn test_nullable_array_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `None` is NULL and `Some(vec![])` is an empty array, both typed as `int4[]`
+    for (value, is_null, len) in [
+        (None, true, None),
+        (Some(vec![]), false, Some(0)),
+        (Some(vec![1_i32, 2]), false, Some(2)),
+    ] {
+        let (null, ty, cardinality, decoded): (bool, String, Option<i32>, Option<Vec<i32>>) =
+            sqlx::query_as("SELECT $1 IS NULL, pg_typeof($1)::text, cardinality($1), $1")
+                .bind(&value)
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(null, is_null);
+        assert_eq!(ty, "integer[]");
+        assert_eq!(cardinality, len);
+        assert_eq!(decoded, value);
+    }
+
+    // the same goes for arrays of types that are looked up by name
+    for value in [None, Some(Vec::<PgLsn>::new())] {
+        let (ty, decoded): (String, Option<Vec<PgLsn>>) =
+            sqlx::query_as("SELECT pg_typeof($1)::text, $1")
+                .bind(&value)
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(ty, "pg_lsn[]");
+        assert_eq!(decoded, value);
+    }
+
+    Ok(())
+}
+
 test_type!(bool<bool>(Postgres,
     "false::boolean" == false,
     "true::boolean" == true
@@ -72,6 +112,23 @@ test_type!(str<&str>(Postgres,
 
 test_type!(string<String>(Postgres,
     "'this is foo'" == format!("this is foo"),
+    "'ab'::char(4)" == format!("ab"),
+));
+
+test_type!(bpchar<sqlx::postgres::types::PgBpChar>(Postgres,
+    "'ab'::char(4)" == sqlx::postgres::types::PgBpChar("ab  ".into()),
+    "'ab  '::char(4)" == sqlx::postgres::types::PgBpChar("ab  ".into()),
+));
+
+test_decode_type!(bpchar_vec<Vec<String>>(Postgres,
+    "array['a', 'bc']::char(3)[]" == vec!["a".to_owned(), "bc".to_owned()],
+));
+
+test_type!(bpchar_padded_vec<Vec<sqlx::postgres::types::PgBpChar>>(Postgres,
+    "array['a', 'bc']::char(3)[]" == vec![
+        sqlx::postgres::types::PgBpChar("a  ".into()),
+        sqlx::postgres::types::PgBpChar("bc ".into()),
+    ],
 ));
 
 test_type!(string_vec<Vec<String>>(Postgres,
@@ -85,6 +142,12 @@ test_type!(string_vec<Vec<String>>(Postgres,
         == vec!["Hello, World", "", "Goodbye"],
 ));
 
+// `text[] = name[]` has no operator, so this can't go through `test_prepared_type!`
+test_decode_type!(name_vec<Vec<String>>(Postgres,
+    "array['pg_class', 'pg_attribute']::name[]"
+        == vec!["pg_class", "pg_attribute"],
+));
+
 test_type!(string_array<[String; 3]>(Postgres,
     "array['one','two','three']::text[]" == ["one","two","three"],
 ));
@@ -97,6 +160,17 @@ test_type!(i8(
 
 test_type!(Oid(Postgres, "325235::oid" == Oid(325235),));
 
+// Postgres can't cast arrays to the vector types, so these can't be tested with binds
+test_decode_type!(int2vector<Vec<i16>>(Postgres,
+    "'1 -3 12'::int2vector" == vec![1_i16, -3, 12],
+    "''::int2vector" == Vec::<i16>::new(),
+));
+
+test_decode_type!(oidvector<Vec<Oid>>(Postgres,
+    "'23 4294967295'::oidvector" == vec![Oid(23), Oid(u32::MAX)],
+    "''::oidvector" == Vec::<Oid>::new(),
+));
+
 test_type!(i16(
     Postgres,
     "-2144::smallint" == -2144_i16,
@@ -137,6 +211,24 @@ test_type!(f64_vec<Vec<f64>>(Postgres,
     "'{939399419.1225182,-12.0}'::float8[]" == vec![939399419.1225182_f64, -12.0]
 ));
 
+test_type!(f64_vec_vec<Vec<Vec<f64>>>(Postgres,
+    "'{{1.5,-2.0},{3.25,4.0},{0.0,1e10}}'::float8[][]" == vec![vec![1.5_f64, -2.0], vec![3.25, 4.0], vec![0.0, 1e10]],
+    "'{}'::float8[][]" == Vec::<Vec<f64>>::new(),
+));
+
+test_type!(i32_vec_vec_vec<Vec<Vec<Vec<i32>>>>(Postgres,
+    "'{{{1,2},{3,4}},{{5,6},{7,8}}}'::int4[]" == vec![vec![vec![1, 2], vec![3, 4]], vec![vec![5, 6], vec![7, 8]]],
+));
+
+test_type!(null_vec_vec<Vec<Vec<Option<i64>>>>(Postgres,
+    "'{{1,NULL},{NULL,4}}'::int8[]" == vec![vec![Some(1_i64), None], vec![None, Some(4)]],
+));
+
+test_type!(string_vec_vec<Vec<Vec<String>>>(Postgres,
+    r#"array[array['a', 'b,c'], array['{d}', '"e"']]"#
+        == vec![vec!["a".to_owned(), "b,c".to_owned()], vec!["{d}".to_owned(), "\"e\"".to_owned()]],
+));
+
 test_decode_type!(bool_tuple<(bool,)>(Postgres, "row(true)" == (true,)));
 
 test_decode_type!(num_tuple<(i32, i64, f64,)>(Postgres, "row(10,515::int8,3.124::float8)" == (10,515,3.124)));
@@ -171,6 +263,68 @@ test_type!(uuid_vec<Vec<sqlx::types::Uuid>>(Postgres,
         ]
 ));
 
+test_type!(uuid_u128<u128>(Postgres,
+    "'b731678f-636f-4135-bc6f-19440c13bd19'::uuid" == 0xb731678f_636f_4135_bc6f_19440c13bd19_u128,
+    "'00000000-0000-0000-0000-000000000001'::uuid" == 1_u128,
+    "'ffffffff-ffff-ffff-ffff-ffffffffffff'::uuid" == u128::MAX
+));
+
+test_type!(uuid_u128_vec<Vec<u128>>(Postgres,
+    "'{b731678f-636f-4135-bc6f-19440c13bd19,00000000-0000-0000-0000-000000000000}'::uuid[]"
+        == vec![0xb731678f_636f_4135_bc6f_19440c13bd19_u128, 0]
+));
+
+test_type!(numeric_i128<PgNumericInt<i128>>(Postgres,
+    "0::numeric" == PgNumericInt(0_i128),
+    "10000::numeric" == PgNumericInt(10_000_i128),
+    "-123456789012345678901234567890::numeric"
+        == PgNumericInt(-123456789012345678901234567890_i128),
+    "170141183460469231731687303715884105727::numeric" == PgNumericInt(i128::MAX),
+    "-170141183460469231731687303715884105728::numeric" == PgNumericInt(i128::MIN)
+));
+
+test_type!(numeric_u128<PgNumericInt<u128>>(Postgres,
+    "1::numeric" == PgNumericInt(1_u128),
+    "340282366920938463463374607431768211455::numeric" == PgNumericInt(u128::MAX)
+));
+
+test_type!(numeric_u128_vec<Vec<PgNumericInt<u128>>>(Postgres,
+    "'{0,100000000}'::numeric[]" == vec![PgNumericInt(0_u128), PgNumericInt(100_000_000)]
+));
+
+test_decode_type!(numeric_i128_trailing_zeroes<PgNumericInt<i128>>(Postgres,
+    "-42.000::numeric" == PgNumericInt(-42_i128)
+));
+
+#[sqlx_macros::test]
+async fn it_rejects_non_integral_numeric_as_i128() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    for value in [
+        "12.5",
+        "'NaN'",
+        "170141183460469231731687303715884105728",
+        "1e40",
+    ] {
+        let res = sqlx::query_scalar::<_, PgNumericInt<i128>>(&format!("SELECT {value}::numeric"))
+            .fetch_one(&mut conn)
+            .await;
+
+        assert!(
+            matches!(res, Err(sqlx::Error::ColumnDecode { .. })),
+            "{value}: {res:?}"
+        );
+    }
+
+    let res = sqlx::query_scalar::<_, PgNumericInt<u128>>("SELECT (-1)::numeric")
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::ColumnDecode { .. })), "{res:?}");
+
+    Ok(())
+}
+
 #[cfg(feature = "ipnetwork")]
 test_type!(ipnetwork<sqlx::types::ipnetwork::IpNetwork>(Postgres,
     "'127.0.0.1'::inet"
@@ -232,6 +386,25 @@ test_type!(bitvec<sqlx::types::BitVec>(
     },
 ));
 
+#[cfg(feature = "bytes")]
+test_type!(bytes<sqlx::types::Bytes>(Postgres,
+    "E'\\\\xDEADBEEF'::bytea"
+        == sqlx::types::Bytes::from_static(&[0xDE, 0xAD, 0xBE, 0xEF]),
+    "E'\\\\x'::bytea" == sqlx::types::Bytes::new(),
+));
+
+#[cfg(feature = "bytes")]
+test_type!(bytes_mut<sqlx::types::BytesMut>(Postgres,
+    "E'\\\\xDEADBEEF'::bytea"
+        == sqlx::types::BytesMut::from(&[0xDE_u8, 0xAD, 0xBE, 0xEF][..]),
+));
+
+#[cfg(feature = "bytes")]
+test_type!(bytes_vec<Vec<sqlx::types::Bytes>>(Postgres,
+    "array[E'\\\\x01'::bytea, E'\\\\x'::bytea]"
+        == vec![sqlx::types::Bytes::from_static(&[1]), sqlx::types::Bytes::new()],
+));
+
 #[cfg(feature = "ipnetwork")]
 test_type!(ipnetwork_vec<Vec<sqlx::types::ipnetwork::IpNetwork>>(Postgres,
     "'{127.0.0.1,8.8.8.8/24}'::inet[]"
@@ -257,6 +430,8 @@ mod chrono {
         DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
     };
 
+    use sqlx::postgres::types::chrono::{PgDateRange, PgTsRange, PgTsTzRange};
+
     type PgTimeTz = sqlx::postgres::types::PgTimeTz<NaiveTime, FixedOffset>;
 
     test_type!(chrono_date<NaiveDate>(Postgres,
@@ -302,15 +477,40 @@ mod chrono {
         "TIMETZ '05:10:20.115100+00'" == PgTimeTz { time: NaiveTime::from_hms_micro_opt(5, 10, 20, 115100).unwrap(), offset: FixedOffset::east_opt(0).unwrap() },
         "TIMETZ '05:10:20.115100+06:30'" == PgTimeTz { time: NaiveTime::from_hms_micro_opt(5, 10, 20, 115100).unwrap(), offset: FixedOffset::east_opt(60 * 60 * 6 + 1800).unwrap() },
         "TIMETZ '05:10:20.115100-05'" == PgTimeTz { time: NaiveTime::from_hms_micro_opt(5, 10, 20, 115100).unwrap(), offset: FixedOffset::west_opt(60 * 60 * 5).unwrap() },
+        "TIMETZ '23:59:59.999999-03:30'" == PgTimeTz { time: NaiveTime::from_hms_micro_opt(23, 59, 59, 999999).unwrap(), offset: FixedOffset::west_opt(60 * 60 * 3 + 1800).unwrap() },
         "TIMETZ '05:10:20+02'" == PgTimeTz { time: NaiveTime::from_hms_opt(5, 10, 20).unwrap(), offset: FixedOffset::east_opt(60 * 60 * 2 ).unwrap() }
     ));
+
+    test_type!(chrono_tstzrange<PgTsTzRange>(Postgres,
+        "'[2019-01-02 05:10:20+00,2019-01-03 05:10:20+00)'::tstzrange"
+            == PgRange::from(
+                Utc.with_ymd_and_hms(2019, 1, 2, 5, 10, 20).unwrap()
+                    ..Utc.with_ymd_and_hms(2019, 1, 3, 5, 10, 20).unwrap()
+            ),
+        "'[2019-01-02 05:10:20+06:30,)'::tstzrange"
+            == PgRange::from(Utc.with_ymd_and_hms(2019, 1, 1, 22, 40, 20).unwrap()..)
+    ));
+
+    test_type!(chrono_tsrange<PgTsRange>(Postgres,
+        "'(2019-01-02 05:10:20,2019-01-03 05:10:20]'::tsrange"
+            == PgRange::from((
+                Bound::Excluded(NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_opt(5, 10, 20).unwrap()),
+                Bound::Included(NaiveDate::from_ymd_opt(2019, 1, 3).unwrap().and_hms_opt(5, 10, 20).unwrap()),
+            ))
+    ));
+
+    test_type!(chrono_daterange<PgDateRange>(Postgres,
+        "'[2001-01-05,2001-01-10]'::daterange"
+            == PgRange::from(NaiveDate::from_ymd_opt(2001, 1, 5).unwrap()..NaiveDate::from_ymd_opt(2001, 1, 11).unwrap())
+    ));
 }
 
 #[cfg(feature = "time")]
 mod time_tests {
     use super::*;
+    use sqlx::postgres::types::time::{PgDateRange, PgTsRange, PgTsTzRange};
     use sqlx::types::time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
-    use time::macros::{date, time};
+    use time::macros::{date, datetime, time};
 
     type PgTimeTz = sqlx::postgres::types::PgTimeTz<Time, UtcOffset>;
 
@@ -340,13 +540,32 @@ mod time_tests {
                 .assume_utc()
     ));
 
-    test_prepared_type!(time_time_tz<PgTimeTz>(Postgres,
+    test_type!(time_time_tz<PgTimeTz>(Postgres,
         "TIMETZ '05:10:20.115100+00'" == PgTimeTz { time: time!(5:10:20.115100), offset: UtcOffset::from_whole_seconds(0).unwrap() },
         "TIMETZ '05:10:20.115100+00'" == PgTimeTz { time: time!(5:10:20.115100), offset: UtcOffset::from_whole_seconds(0).unwrap() },
         "TIMETZ '05:10:20.115100+06:30'" == PgTimeTz { time: time!(5:10:20.115100), offset: UtcOffset::from_whole_seconds(60 * 60 * 6 + 1800).unwrap() },
         "TIMETZ '05:10:20.115100-05'" == PgTimeTz { time: time!(5:10:20.115100), offset: UtcOffset::from_whole_seconds(-(60 * 60 * 5)).unwrap() },
+        "TIMETZ '23:59:59.999999-03:30'" == PgTimeTz { time: time!(23:59:59.999999), offset: UtcOffset::from_whole_seconds(-(60 * 60 * 3 + 1800)).unwrap() },
         "TIMETZ '05:10:20+02'" == PgTimeTz { time: time!(5:10:20), offset: UtcOffset::from_whole_seconds(60 * 60 * 2 ).unwrap() }
     ));
+
+    test_type!(time_tstzrange<PgTsTzRange>(Postgres,
+        "'[2019-01-02 05:10:20+00,2019-01-03 05:10:20+00)'::tstzrange"
+            == PgRange::from(
+                datetime!(2019-01-02 05:10:20 UTC)..datetime!(2019-01-03 05:10:20 UTC)
+            ),
+        "'(,2019-01-02 05:10:20+06:30]'::tstzrange"
+            == PgRange::from(..=datetime!(2019-01-01 22:40:20 UTC))
+    ));
+
+    test_type!(time_tsrange<PgTsRange>(Postgres,
+        "'[2019-01-02 05:10:20,2019-01-03 05:10:20)'::tsrange"
+            == PgRange::from(datetime!(2019-01-02 05:10:20)..datetime!(2019-01-03 05:10:20))
+    ));
+
+    test_type!(time_daterange<PgDateRange>(Postgres,
+        "'[2001-01-05,2001-01-10)'::daterange" == PgRange::from(date!(2001 - 1 - 5)..date!(2001 - 1 - 10))
+    ));
 }
 
 #[cfg(feature = "json")]
@@ -354,8 +573,10 @@ mod json {
     use super::*;
     use serde_json::value::RawValue as JsonRawValue;
     use serde_json::{json, Value as JsonValue};
+    use sqlx::error::BoxDynError;
+    use sqlx::postgres::types::{PgJsonScalar, PgJsonb, PgJsonbArray};
     use sqlx::postgres::PgRow;
-    use sqlx::types::Json;
+    use sqlx::types::{Json, JsonMap, JsonVersions, VersionedJson};
     use sqlx::{Executor, Row};
     use sqlx_test::new;
 
@@ -414,6 +635,111 @@ mod json {
             ]
     ));
 
+    test_type!(json_map<JsonMap>(Postgres,
+        "'{\"name\":\"Joe\",\"age\":33}'::jsonb"
+            == json!({ "name": "Joe", "age": 33 }).as_object().unwrap().clone(),
+        "'{}'::jsonb" == JsonMap::new()
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_json_map_not_an_object() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        let row: PgRow = conn
+            .fetch_one(sqlx::query("SELECT '[1, 2]'::jsonb, 'null'::json"))
+            .await?;
+
+        let err = row.try_get::<JsonMap, _>(0).unwrap_err();
+        assert!(
+            err.to_string().contains("expected a JSON object, got an array"),
+            "{err}"
+        );
+
+        let err = row.try_get::<JsonMap, _>(1).unwrap_err();
+        assert!(err.to_string().contains("got null"), "{err}");
+
+        // with `Json<_>`, the error is the one from `serde_json`
+        assert!(row.try_get::<Json<JsonMap>, _>(0).is_err());
+
+        Ok(())
+    }
+
+    test_decode_type!(json_scalar<PgJsonScalar>(Postgres,
+        "'{\"a\": \"Hello, World\"}'::jsonb->'a'"
+            == PgJsonScalar::String("Hello, World".into()),
+        "'{\"a\": \"say \\\"hi\\\"\"}'::jsonb->'a'" == PgJsonScalar::String("say \"hi\"".into()),
+        "'{\"a\": 33}'::jsonb->'a'" == PgJsonScalar::Number(33.into()),
+        "'{\"a\": true}'::json->'a'" == PgJsonScalar::Bool(true),
+        "'{\"a\": null}'::jsonb->'a'" == PgJsonScalar::Null,
+        "'{\"a\": [1]}'::jsonb->'a'" == PgJsonScalar::Complex(json!([1])),
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_jsonb_array() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        let sql = "SELECT '[1, \"two\", {\"three\": [3]}]'::jsonb, '[]'::json";
+
+        // unprepared, text API
+        let row: PgRow = conn.fetch_one(sql).await?;
+        let array: PgJsonbArray = row.try_get(0)?;
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.get::<String>(1).unwrap()?, "two");
+        assert_eq!(array.get_raw(2), Some("{\"three\": [3]}"));
+        assert!(row.try_get::<PgJsonbArray, _>(1)?.is_empty());
+
+        // prepared, binary API
+        let row: PgRow = conn.fetch_one(sqlx::query(sql)).await?;
+        let array: PgJsonbArray = row.try_get(0)?;
+        assert_eq!(array.get::<i32>(0).unwrap()?, 1);
+        assert_eq!(array.get::<JsonValue>(2).unwrap()?, json!({ "three": [3] }));
+        assert!(array.get::<JsonValue>(3).is_none());
+        assert!(row.try_get::<PgJsonbArray, _>(1)?.is_empty());
+
+        let err = conn
+            .fetch_one(sqlx::query("SELECT '{}'::jsonb"))
+            .await?
+            .try_get::<PgJsonbArray, _>(0)
+            .unwrap_err();
+        assert!(matches!(err, sqlx::Error::ColumnDecode { .. }), "{err:?}");
+
+        Ok(())
+    }
+
+    #[sqlx_macros::test]
+    async fn test_jsonb_patch() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        let (old, new): (Json<JsonValue>, Json<JsonValue>) = sqlx::query_as(
+            r#"SELECT '{"status": "draft", "tags": ["a", "b"], "v": 1}'::jsonb,
+                      '{"status": "published", "tags": ["a"], "author": "x", "v": 1}'::jsonb"#,
+        )
+        .fetch_one(&mut conn)
+        .await?;
+
+        let patch = PgJsonb::diff(&old, &new);
+        assert_eq!(
+            patch,
+            json!([
+                { "op": "replace", "path": "/status", "value": "published" },
+                { "op": "remove", "path": "/tags/1" },
+                { "op": "add", "path": "/author", "value": "x" },
+            ])
+        );
+
+        // the patch survives a round-trip through the database
+        let Json(patch): Json<JsonValue> = sqlx::query_scalar("SELECT $1::jsonb")
+            .bind(Json(&patch))
+            .fetch_one(&mut conn)
+            .await?;
+
+        let mut replayed = old.0;
+        PgJsonb::apply_patch(&mut replayed, &patch)?;
+        assert_eq!(replayed, new.0);
+
+        Ok(())
+    }
+
     #[sqlx_macros::test]
     async fn test_json_raw_value() -> anyhow::Result<()> {
         let mut conn = new::<Postgres>().await?;
@@ -438,6 +764,101 @@ mod json {
 
         Ok(())
     }
+
+    #[sqlx_macros::test]
+    async fn test_json_borrowed() -> anyhow::Result<()> {
+        use std::borrow::Cow;
+
+        #[derive(serde::Deserialize)]
+        struct Event<'r> {
+            #[serde(borrow)]
+            kind: Cow<'r, str>,
+            #[serde(borrow)]
+            note: Cow<'r, str>,
+        }
+
+        let mut conn = new::<Postgres>().await?;
+
+        let sql = r#"SELECT '{"kind": "click", "note": "say \"hi\""}'::jsonb, '{"kind": "view", "note": ""}'::json"#;
+
+        // unprepared, text API, and prepared, binary API
+        for row in [
+            conn.fetch_one(sql).await?,
+            conn.fetch_one(sqlx::query(sql)).await?,
+        ] {
+            for (i, kind) in [(0, "click"), (1, "view")] {
+                let event: Json<Event<'_>> = row.try_get(i)?;
+
+                assert!(matches!(event.kind, Cow::Borrowed(k) if k == kind));
+            }
+
+            // a string with escapes can't be borrowed
+            let event: Json<Event<'_>> = row.try_get(0)?;
+            assert!(matches!(event.note, Cow::Owned(ref note) if note == "say \"hi\""));
+        }
+
+        Ok(())
+    }
+
+    #[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+    #[serde(untagged)]
+    enum Profile {
+        V1 {
+            name: String,
+        },
+        V2 {
+            version: u32,
+            first_name: String,
+            last_name: String,
+        },
+    }
+
+    impl JsonVersions for Profile {
+        fn from_version(version: JsonValue, document: JsonValue) -> Result<Self, BoxDynError> {
+            match version.as_u64() {
+                None => Ok(serde_json::from_value(document)?),
+                Some(2) => Ok(serde_json::from_value(document)?),
+                Some(other) => Err(format!("unknown profile version {other}").into()),
+            }
+        }
+    }
+
+    #[sqlx_macros::test]
+    async fn test_versioned_json() -> anyhow::Result<()> {
+        let mut conn = new::<Postgres>().await?;
+
+        let profile: VersionedJson<Profile> =
+            sqlx::query_scalar(r#"SELECT '{"name": "Joe"}'::jsonb"#)
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(profile.0, Profile::V1 { name: "Joe".into() });
+
+        let v2 = Profile::V2 {
+            version: 2,
+            first_name: "Joe".into(),
+            last_name: "Bloggs".into(),
+        };
+
+        let profile: VersionedJson<Profile> = sqlx::query_scalar("SELECT $1")
+            .bind(VersionedJson(&v2))
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(profile.0, v2);
+
+        let res: Result<VersionedJson<Profile>, _> =
+            sqlx::query_scalar(r#"SELECT '{"version": 3}'::jsonb"#)
+                .fetch_one(&mut conn)
+                .await;
+
+        assert!(
+            matches!(res, Err(sqlx::Error::ColumnDecode { ref source, .. }) if source.to_string() == "unknown profile version 3"),
+            "{res:?}"
+        );
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "bigdecimal")]
@@ -472,7 +893,22 @@ test_type!(bigdecimal<sqlx::types::BigDecimal>(Postgres,
 test_type!(numrange_bigdecimal<PgRange<sqlx::types::BigDecimal>>(Postgres,
     "'(1.3,2.4)'::numrange" == PgRange::from(
         (Bound::Excluded("1.3".parse::<sqlx::types::BigDecimal>().unwrap()),
-         Bound::Excluded("2.4".parse::<sqlx::types::BigDecimal>().unwrap())))
+         Bound::Excluded("2.4".parse::<sqlx::types::BigDecimal>().unwrap()))),
+    "'[-0.5,)'::numrange" == PgRange::from(
+        (Bound::Included("-0.5".parse::<sqlx::types::BigDecimal>().unwrap()),
+         Bound::Unbounded)),
+));
+
+#[cfg(feature = "bigdecimal")]
+test_type!(numrange_bigdecimal_vec<Vec<PgRange<sqlx::types::BigDecimal>>>(Postgres,
+    "array['[1,2]'::numrange, '(,10.25)'::numrange]" == vec![
+        PgRange::from(
+            (Bound::Included("1".parse::<sqlx::types::BigDecimal>().unwrap()),
+             Bound::Included("2".parse::<sqlx::types::BigDecimal>().unwrap()))),
+        PgRange::from(
+            (Bound::Unbounded,
+             Bound::Excluded("10.25".parse::<sqlx::types::BigDecimal>().unwrap()))),
+    ]
 ));
 
 #[cfg(any(postgres_14, postgres_15))]
@@ -511,8 +947,41 @@ test_type!(numrange_decimal<PgRange<sqlx::types::Decimal>>(Postgres,
     "'(1.3,2.4)'::numrange" == PgRange::from(
         (Bound::Excluded(sqlx::types::Decimal::from_str("1.3").unwrap()),
          Bound::Excluded(sqlx::types::Decimal::from_str("2.4").unwrap()))),
+    "'(,99.99]'::numrange" == PgRange::from(
+        (Bound::Unbounded,
+         Bound::Included(sqlx::types::Decimal::from_str("99.99").unwrap()))),
 ));
 
+#[cfg(feature = "rust_decimal")]
+test_type!(numrange_decimal_vec<Vec<PgRange<sqlx::types::Decimal>>>(Postgres,
+    "array['[0,0.5)'::numrange, '[0.5,)'::numrange]" == vec![
+        PgRange::from(sqlx::types::Decimal::from_str("0").unwrap()
+            ..sqlx::types::Decimal::from_str("0.5").unwrap()),
+        PgRange::from(sqlx::types::Decimal::from_str("0.5").unwrap()..),
+    ]
+));
+
+#[cfg(feature = "rust_decimal")]
+#[sqlx_macros::test]
+async fn test_numrange_decimal_contains_and_overlaps() -> anyhow::Result<()> {
+    use sqlx::types::Decimal;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let (tier, price, next): (PgRange<Decimal>, Decimal, PgRange<Decimal>) =
+        sqlx::query_as("SELECT '[10,100)'::numrange, 99.99::numeric, '[100,)'::numrange")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert!(tier.contains(&price));
+    assert!(!tier.contains(&Decimal::from(100)));
+    assert!(next.contains(&Decimal::from(100)));
+    assert!(!tier.overlaps(&next));
+    assert!(tier.overlaps(&PgRange::from(Decimal::from_str("99.5")?..)));
+
+    Ok(())
+}
+
 const EXC2: Bound<i32> = Bound::Excluded(2);
 const EXC3: Bound<i32> = Bound::Excluded(3);
 const INC1: Bound<i32> = Bound::Included(1);
@@ -565,12 +1034,60 @@ test_prepared_type!(interval<PgInterval>(
         },
 ));
 
+test_prepared_type!(interval_vec<Vec<PgInterval>>(Postgres,
+    "array[INTERVAL '1 day', INTERVAL '-30 minutes', INTERVAL '1 year']"
+        == vec![
+            PgInterval { months: 0, days: 1, microseconds: 0 },
+            PgInterval { months: 0, days: 0, microseconds: -30 * 60 * 1_000_000 },
+            PgInterval { months: 12, days: 0, microseconds: 0 },
+        ],
+    "'{}'::interval[]" == Vec::<PgInterval>::new(),
+));
+
+test_prepared_type!(interval_vec_null<Vec<Option<PgInterval>>>(Postgres,
+    "array[INTERVAL '1 week', NULL]"
+        == vec![Some(PgInterval { months: 0, days: 7, microseconds: 0 }), None],
+));
+
 test_prepared_type!(money<PgMoney>(Postgres, "123.45::money" == PgMoney(12345)));
 
 test_prepared_type!(money_vec<Vec<PgMoney>>(Postgres,
     "array[123.45,420.00,666.66]::money[]" == vec![PgMoney(12345), PgMoney(42000), PgMoney(66666)],
 ));
 
+test_type!(pg_lsn<PgLsn>(Postgres,
+    "'0/0'::pg_lsn" == PgLsn(0),
+    "'16/B374D848'::pg_lsn" == PgLsn(0x16_B374_D848),
+    "'FFFFFFFF/FFFFFFFF'::pg_lsn" == PgLsn(u64::MAX),
+));
+
+test_type!(pg_lsn_vec<Vec<PgLsn>>(Postgres,
+    "array['0/1','1/0']::pg_lsn[]" == vec![PgLsn(1), PgLsn(1 << 32)],
+));
+
+test_type!(tid<PgTid>(Postgres,
+    "'(0,1)'::tid" == PgTid { block: 0, offset: 1 },
+    "'(4294967295,65535)'::tid" == PgTid { block: u32::MAX, offset: u16::MAX },
+));
+
+test_type!(tid_vec<Vec<PgTid>>(Postgres,
+    "array['(0,1)','(7,42)']::tid[]"
+        == vec![PgTid { block: 0, offset: 1 }, PgTid { block: 7, offset: 42 }],
+));
+
+test_decode_type!(aclitem<PgAclItem>(Postgres,
+    "'=r/postgres'::aclitem" == PgAclItem::from_str("=r/postgres").unwrap(),
+    "'postgres=a*r/postgres'::text" == PgAclItem::from_str("postgres=a*r/postgres").unwrap(),
+));
+
+test_decode_type!(aclitem_vec<Vec<PgAclItem>>(Postgres,
+    "array['=r/postgres','postgres=w*/postgres']::aclitem[]" == vec![
+        PgAclItem::from_str("=r/postgres").unwrap(),
+        PgAclItem::from_str("postgres=w*/postgres").unwrap(),
+    ],
+    "array['=U/postgres']::text[]" == vec![PgAclItem::from_str("=U/postgres").unwrap()],
+));
+
 test_prepared_type!(citext_array<Vec<PgCiText>>(Postgres,
     "array['one','two','three']::citext[]" == vec![
         PgCiText("one".to_string()),
@@ -598,6 +1115,77 @@ test_type!(ltree_vec<Vec<sqlx::postgres::types::PgLTree>>(Postgres,
         ]
 ));
 
+// `lquery` has no equality operator, so this can only test decoding
+#[cfg(any(postgres_14, postgres_15))]
+test_decode_type!(lquery_vec<Vec<sqlx::postgres::types::PgLQuery>>(Postgres,
+    "array['Foo.*', '*.Bar']::lquery[]" ==
+        vec![
+            sqlx::postgres::types::PgLQuery::from_str("Foo.*").unwrap(),
+            sqlx::postgres::types::PgLQuery::from_str("*.Bar").unwrap()
+        ]
+));
+
+// the `simple` configuration has a fixed OID; others are assigned at `initdb`
+test_prepared_type!(regconfig<PgRegConfig>(Postgres,
+    "'simple'::regconfig" == PgRegConfig::Oid(Oid(3748)),
+));
+
+test_prepared_type!(regconfig_vec<Vec<PgRegConfig>>(Postgres,
+    "array['simple']::regconfig[]" == vec![PgRegConfig::Oid(Oid(3748))],
+));
+
+#[sqlx_macros::test]
+async fn test_regconfig() -> anyhow::Result<()> {
+    use sqlx::Row;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // the text format is the name
+    let row = sqlx::raw_sql("SELECT 'english'::regconfig")
+        .fetch_one(&mut conn)
+        .await?;
+    let config: PgRegConfig = row.try_get(0)?;
+    assert_eq!(config, PgRegConfig::Name("english".into()));
+
+    // the binary format is the OID, which can be bound as a parameter
+    let config: PgRegConfig = sqlx::query_scalar("SELECT 'english'::regconfig")
+        .fetch_one(&mut conn)
+        .await?;
+    let name: String = sqlx::query_scalar("SELECT $1::text")
+        .bind(&config)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(name, "english");
+
+    let err = sqlx::query("SELECT to_tsvector($1, 'x')")
+        .bind(PgRegConfig::Name("english".into()))
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("by name"), "{err}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_aclitem_with_binary_columns() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // only the `aclitem[]` column is requested as text
+    let (oid, acl): (Oid, Vec<PgAclItem>) =
+        sqlx::query_as("SELECT oid, relacl FROM pg_class WHERE relname = $1")
+            .bind("pg_class")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(oid, Oid(1259));
+    assert!(acl
+        .iter()
+        .any(|item| item.grantee.is_none() && item.has(PgAclPrivilegeKind::Select)));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_text_adapter() -> anyhow::Result<()> {
     #[derive(sqlx::FromRow, Debug, PartialEq, Eq)]
@@ -33,3 +33,27 @@ async fn issue_3179() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_prefixes_tables_for_the_backend_of_any() -> anyhow::Result<()> {
+    use sqlx::{PrefixedExecutor, TablePrefix};
+
+    sqlx::any::install_default_drivers();
+
+    let mut conn = new::<Any>().await?;
+    let prefix = TablePrefix::new("main", ["accounts"]);
+
+    // the rules for string literals depend on the backend, which `Any` doesn't know up front
+    let res = sqlx::query("SELECT COUNT(*) FROM accounts")
+        .fetch_one(PrefixedExecutor::new(&mut conn, &prefix))
+        .await;
+    assert!(matches!(res, Err(sqlx::Error::Configuration(_))));
+
+    let backend_name = conn.backend_name().to_owned();
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM accounts WHERE name <> 'accounts'")
+        .fetch_one(PrefixedExecutor::new(&mut conn, &prefix).for_backend(&backend_name))
+        .await?;
+    assert!(count >= 1);
+
+    Ok(())
+}
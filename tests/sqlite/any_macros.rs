@@ -0,0 +1,61 @@
+//! Run both with and without `SQLX_EXECUTE_ON_ANY=true`; the same queries must compile and
+//! return the same values either way.
+
+use sqlx::query::Map;
+use sqlx::{Database, Executor, IntoArguments};
+use sqlx_test::new;
+
+// `Any` when the macros were compiled with `SQLX_EXECUTE_ON_ANY=true`, `Sqlite` otherwise.
+fn expected_database() -> &'static str {
+    match option_env!("SQLX_EXECUTE_ON_ANY") {
+        Some("true" | "1") => "Any",
+        _ => "SQLite",
+    }
+}
+
+fn database_of<DB: Database, F, A>(_: &Map<'_, DB, F, A>) -> &'static str {
+    DB::NAME
+}
+
+async fn fetch_all<'q, DB, F, O, A>(query: Map<'q, DB, F, A>) -> anyhow::Result<Vec<O>>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    F: FnMut(DB::Row) -> Result<O, sqlx::Error> + Send,
+    O: Send + Unpin,
+    A: 'q + Send + IntoArguments<'q, DB>,
+{
+    sqlx::any::install_default_drivers();
+
+    let mut conn = new::<DB>().await?;
+
+    Ok(query.fetch_all(&mut conn).await?)
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_rows() -> anyhow::Result<()> {
+    let query = sqlx::query!("SELECT id, name, is_active FROM accounts ORDER BY id LIMIT 1");
+    assert_eq!(database_of(&query), expected_database());
+
+    let rows = fetch_all(query).await?;
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].id, 1);
+    assert_eq!(rows[0].name, "Herp Derpinson");
+    assert_eq!(rows[0].is_active, Some(true));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_parameters() -> anyhow::Result<()> {
+    let query = sqlx::query!("SELECT name FROM accounts WHERE id = ?", 1i64);
+    assert_eq!(database_of(&query), expected_database());
+
+    let rows = fetch_all(query).await?;
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].name, "Herp Derpinson");
+
+    Ok(())
+}
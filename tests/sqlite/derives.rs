@@ -1,5 +1,6 @@
 use sqlx::Sqlite;
 use sqlx_test::test_type;
+use std::marker::PhantomData;
 
 #[derive(Debug, PartialEq, sqlx::Type)]
 #[repr(u32)]
@@ -12,3 +13,20 @@ test_type!(origin_enum<Origin>(Sqlite,
     "1" == Origin::Foo,
     "2" == Origin::Bar,
 ));
+
+#[derive(Debug, PartialEq, sqlx::Type)]
+#[sqlx(transparent)]
+struct Id<T>(i64, PhantomData<T>);
+
+#[derive(Debug, PartialEq)]
+struct User;
+
+test_type!(transparent_typed_id<Id<User>>(Sqlite,
+    "1" == Id::<User>(1, PhantomData),
+    "9223372036854775807" == Id::<User>(i64::MAX, PhantomData),
+));
+
+test_type!(transparent_typed_id_option<Option<Id<User>>>(Sqlite,
+    "NULL" == None::<Id<User>>,
+    "1" == Some(Id::<User>(1, PhantomData)),
+));
@@ -1,4 +1,4 @@
-use sqlx::Sqlite;
+use sqlx::{Connection, Sqlite};
 use sqlx_test::new;
 
 #[sqlx_macros::test]
@@ -16,6 +16,35 @@ async fn macro_select() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn macro_select_not_persistent() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let account = sqlx::query!("select id, name from accounts where id = ?", 1)
+        .persistent(false)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!(0, conn.cached_statements_size());
+
+    let id = sqlx::query_scalar!("select id from accounts where id = ?", 1)
+        .persistent(false)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(1, id);
+    assert_eq!(0, conn.cached_statements_size());
+
+    let _ = sqlx::query!("select id, name from accounts where id = ?", 1)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(1, conn.cached_statements_size());
+
+    Ok(())
+}
+
 macro_rules! gen_macro_select_concats {
     ($param:literal) => {
         #[sqlx_macros::test]
@@ -175,6 +204,28 @@ async fn test_query_scalar() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg(feature = "chrono")]
+#[sqlx_macros::test]
+async fn test_column_override_unix_epoch() -> anyhow::Result<()> {
+    use sqlx::sqlite::types::UnixEpoch;
+    use sqlx::types::chrono::{DateTime, TimeZone, Utc};
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let created_at = sqlx::query_scalar!(
+        r#"select unixepoch('2016-11-22 18:00:00') as "created_at!: UnixEpoch<DateTime<Utc>>""#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(
+        *created_at,
+        Utc.with_ymd_and_hms(2016, 11, 22, 18, 0, 0).unwrap()
+    );
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn macro_select_from_view() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
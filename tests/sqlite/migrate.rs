@@ -1,9 +1,10 @@
-use sqlx::migrate::Migrator;
+use sqlx::migrate::{MigrateError, Migration, MigrationStage, MigrationType, Migrator};
 use sqlx::pool::PoolConnection;
 use sqlx::sqlite::{Sqlite, SqliteConnection};
 use sqlx::Executor;
 use sqlx::Row;
 use std::path::Path;
+use std::sync::Mutex;
 
 #[sqlx::test(migrations = false)]
 async fn simple(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
@@ -27,6 +28,44 @@ async fn simple(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn skip_versions(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let mut migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+
+    // a version that does not exist is a mistake
+    migrator.skip_versions(&[20220721115250, 1]);
+    assert!(matches!(
+        migrator.run(&mut conn).await,
+        Err(MigrateError::VersionNotPresent(1))
+    ));
+
+    migrator.skip_versions(&[20220721115524]);
+    migrator.run(&mut conn).await?;
+
+    // the conversion of the payload did not run
+    let res: i64 = conn
+        .fetch_one("SELECT some_payload FROM migrations_simple_test")
+        .await?
+        .get(0);
+    assert_eq!(res, 100);
+
+    let skipped: Vec<i64> = conn
+        .fetch_all("SELECT version FROM _sqlx_migrations WHERE skipped ORDER BY version")
+        .await?
+        .iter()
+        .map(|row| row.get(0))
+        .collect();
+    assert_eq!(skipped, [20220721115524]);
+
+    // it counts as applied from now on
+    let migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+    migrator.run(&mut conn).await?;
+
+    Ok(())
+}
+
 #[sqlx::test(migrations = false)]
 async fn reversible(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
     clean_up(&mut conn).await?;
@@ -66,6 +105,110 @@ async fn reversible(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx::test(migrations = false)]
+async fn progress(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let migrator = Migrator::new(Path::new("tests/sqlite/migrations_simple")).await?;
+
+    let reports = Mutex::new(Vec::new());
+    migrator
+        .run_with_progress(&mut conn, |progress| {
+            reports.lock().unwrap().push((
+                progress.migration.version,
+                progress.index,
+                progress.total,
+                progress.stage,
+            ))
+        })
+        .await?;
+
+    assert_eq!(
+        *reports.lock().unwrap(),
+        [
+            (20220721115250, 0, 2, MigrationStage::Starting),
+            (20220721115250, 0, 2, MigrationStage::Finished),
+            (20220721115524, 1, 2, MigrationStage::Starting),
+            (20220721115524, 1, 2, MigrationStage::Finished),
+        ]
+    );
+
+    // nothing is reported for migrations that were applied already
+    let called = Mutex::new(false);
+    migrator
+        .run_with_progress(&mut conn, |_| *called.lock().unwrap() = true)
+        .await?;
+    assert!(!*called.lock().unwrap());
+
+    Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn from_iter(mut conn: PoolConnection<Sqlite>) -> anyhow::Result<()> {
+    clean_up(&mut conn).await?;
+
+    let migration = |version, migration_type, sql: &str| {
+        Migration::new(
+            version,
+            "generated".into(),
+            migration_type,
+            sql.to_owned().into(),
+            false,
+        )
+    };
+
+    // out of order, as a generator might produce them
+    let migrator = Migrator::from_iter([
+        migration(
+            2,
+            MigrationType::ReversibleUp,
+            "INSERT INTO migrations_reversible_test VALUES (2)",
+        ),
+        migration(
+            2,
+            MigrationType::ReversibleDown,
+            "DELETE FROM migrations_reversible_test WHERE some_id = 2",
+        ),
+        migration(
+            1,
+            MigrationType::ReversibleUp,
+            "CREATE TABLE migrations_reversible_test (some_id INTEGER)",
+        ),
+        migration(
+            1,
+            MigrationType::ReversibleDown,
+            "DROP TABLE migrations_reversible_test",
+        ),
+    ]);
+
+    assert_eq!(
+        migrator.iter().map(|m| m.version).collect::<Vec<_>>(),
+        [1, 1, 2, 2]
+    );
+    migrator.validate_offline()?;
+
+    migrator.run(&mut conn).await?;
+
+    let res: i64 = conn
+        .fetch_one("SELECT some_id FROM migrations_reversible_test")
+        .await?
+        .get(0);
+    assert_eq!(res, 2);
+
+    // the checksums are computed from the SQL, so the same migrations are already applied
+    migrator.run(&mut conn).await?;
+
+    migrator.undo(&mut conn, 1).await?;
+
+    let res: i64 = conn
+        .fetch_one("SELECT COUNT(*) FROM migrations_reversible_test")
+        .await?
+        .get(0);
+    assert_eq!(res, 0);
+
+    Ok(())
+}
+
 /// Ensure that we have a clean initial state.
 async fn clean_up(conn: &mut SqliteConnection) -> anyhow::Result<()> {
     conn.execute("DROP TABLE migrations_simple_test").await.ok();
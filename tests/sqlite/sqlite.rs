@@ -175,6 +175,82 @@ async fn it_binds_positional_parameters_issue_467() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_fetches_with_map() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let names: Vec<String> = sqlx::query("SELECT name FROM accounts ORDER BY id")
+        .fetch_map(&mut conn, |row| {
+            row.try_get::<String, _>(0).map(|s| s.to_uppercase())
+        })
+        .try_collect()
+        .await?;
+
+    assert_eq!(names[0], "HERP DERPINSON");
+
+    let err = sqlx::query("SELECT name FROM accounts")
+        .fetch_map(&mut conn, |row| row.try_get::<String, _>("missing"))
+        .try_next()
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, sqlx::Error::ColumnNotFound(_)), "{err:?}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_into_a_map() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let names = sqlx::query("SELECT id, name FROM accounts")
+        .fetch_map_by(&mut conn, |row| {
+            Ok((row.try_get::<i64, _>(0)?, row.try_get::<String, _>(1)?))
+        })
+        .await?;
+
+    assert_eq!(names.len(), 1);
+    assert_eq!(names[&1], "Herp Derpinson");
+
+    // the last row wins for duplicate keys
+    let names = sqlx::query("SELECT 1, 'first' UNION ALL SELECT 1, 'last'")
+        .fetch_map_by(&mut conn, |row| {
+            Ok((row.try_get::<i64, _>(0)?, row.try_get::<String, _>(1)?))
+        })
+        .await?;
+
+    assert_eq!(names.len(), 1);
+    assert_eq!(names[&1], "last");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_converts_rows_to_json() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let row = sqlx::query(
+        "SELECT 1 AS int, 1.5 AS float, 'text' AS text, x'0102' AS blob, NULL AS missing, \
+         name FROM accounts",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(
+        sqlx::row_to_json(&row)?,
+        serde_json::json!({
+            "int": 1,
+            "float": 1.5,
+            "text": "text",
+            "blob": [1, 2],
+            "missing": null,
+            "name": "Herp Derpinson",
+        })
+    );
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_fetches_in_loop() -> anyhow::Result<()> {
     // this is trying to check for any data races
@@ -205,6 +281,250 @@ async fn it_executes_with_pool() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_keeps_max_connections_without_a_server_limit() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(3)
+        .max_connections_from_server(1)
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    assert_eq!(pool.options().get_max_connections(), 3);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_calls_on_slow_query() -> anyhow::Result<()> {
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    let seen = Arc::new(Mutex::new(Vec::<String>::new()));
+    let seen_ = seen.clone();
+
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .on_slow_query(Duration::ZERO, move |sql, _elapsed| {
+            seen_.lock().unwrap().push(sql.to_owned());
+        })
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let _: i64 = sqlx::query_scalar("SELECT 1 + ?")
+        .bind(1_i64)
+        .fetch_one(&pool)
+        .await?;
+    assert!(seen.lock().unwrap().iter().any(|sql| sql == "SELECT 1 + ?"));
+
+    let seen = Arc::new(Mutex::new(Vec::<String>::new()));
+    let seen_ = seen.clone();
+
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .on_slow_query(Duration::from_secs(3600), move |sql, _elapsed| {
+            seen_.lock().unwrap().push(sql.to_owned());
+        })
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    pool.execute("SELECT 1").await?;
+    assert!(seen.lock().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_assigns_connection_ids() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Mutex;
+
+    let url = dotenvy::var("DATABASE_URL")?;
+
+    // by default, connections are numbered
+    let a = SqliteConnection::connect(&url).await?;
+    let b = SqliteConnection::connect(&url).await?;
+    assert_ne!(a.id(), b.id());
+
+    let next = Arc::new(AtomicU64::new(100));
+    let next_ = next.clone();
+    let options = url
+        .parse::<SqliteConnectOptions>()?
+        .connection_id(move || next_.fetch_add(1, Ordering::SeqCst));
+
+    let conn = SqliteConnection::connect_with(&options).await?;
+    assert_eq!(conn.id(), 100);
+
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_ = seen.clone();
+
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .after_connect(move |conn, meta| {
+            seen_.lock().unwrap().push((conn.id(), meta.id));
+            Box::pin(async { Ok(()) })
+        })
+        .connect_with(options)
+        .await?;
+
+    let conn = pool.acquire().await?;
+    assert_eq!(conn.id(), 101);
+    assert_eq!(*seen.lock().unwrap(), [(101, 101)]);
+
+    Ok(())
+}
+
+#[cfg(feature = "time")]
+#[sqlx_macros::test]
+async fn it_gets_the_server_time() -> anyhow::Result<()> {
+    use sqlx::types::time::OffsetDateTime;
+
+    let mut conn = new::<Sqlite>().await?;
+
+    let now = conn.server_now().await?;
+    assert!((now - OffsetDateTime::now_utc()).abs().whole_seconds() < 60);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_requires_transactions_for_writes() -> anyhow::Result<()> {
+    let pool: SqlitePool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .require_transaction_for_writes(true)
+        .after_connect(|conn, _meta| {
+            Box::pin(async move {
+                conn.execute("CREATE TEMPORARY TABLE strict_writes (id INTEGER)")
+                    .await?;
+                Ok(())
+            })
+        })
+        .connect(&dotenvy::var("DATABASE_URL")?)
+        .await?;
+
+    let err = sqlx::query("INSERT INTO strict_writes (id) VALUES (1)")
+        .execute(&pool)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::TransactionRequired), "{err:?}");
+
+    let mut tx = pool.begin().await?;
+    sqlx::query("INSERT INTO strict_writes (id) VALUES (1)")
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    // a transaction started with a raw `BEGIN` counts too
+    let mut conn = pool.acquire().await?;
+    conn.execute("BEGIN").await?;
+    conn.execute("INSERT INTO strict_writes (id) VALUES (2)").await?;
+    conn.execute("ROLLBACK").await?;
+    drop(conn);
+
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM strict_writes")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_resolves_duplicate_column_names() -> anyhow::Result<()> {
+    use sqlx::DuplicateColumnNames;
+    use std::str::FromStr;
+
+    let options = SqliteConnectOptions::from_str(":memory:")?;
+    let query = "SELECT 1 AS id, 2 AS id, 3 AS id_1";
+
+    let mut conn = options
+        .clone()
+        .duplicate_column_names(DuplicateColumnNames::Error)
+        .connect()
+        .await?;
+    let row = sqlx::query(query).fetch_one(&mut conn).await?;
+    let err = row.try_get::<i32, _>("id").unwrap_err();
+    assert!(
+        matches!(err, sqlx::Error::ColumnNameAmbiguous(_)),
+        "{err:?}"
+    );
+    assert_eq!(row.try_get::<i32, _>("id_1")?, 3);
+
+    let mut conn = options
+        .duplicate_column_names(DuplicateColumnNames::Suffix)
+        .connect()
+        .await?;
+    let row = sqlx::query(query).fetch_one(&mut conn).await?;
+
+    // `id_1` is already taken so the duplicate becomes `id_2`
+    let names: Vec<_> = row.columns().iter().map(|column| column.name()).collect();
+    assert_eq!(names, ["id", "id_2", "id_1"]);
+    assert_eq!(row.try_get::<i32, _>("id_2")?, 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rewrites_sql_before_preparing() -> anyhow::Result<()> {
+    use std::borrow::Cow;
+    use std::str::FromStr;
+
+    // SQLite can't show the statement it was given, so the rewrite is made visible in its result
+    let mut conn = SqliteConnectOptions::from_str(":memory:")?
+        .sql_rewriter(|sql| {
+            let sql = sql.replace("'original'", "'rewritten'");
+            Cow::Owned(format!("{sql} /* app='sqlx-test' */"))
+        })
+        .connect()
+        .await?;
+
+    // prepared
+    let sql = "SELECT 'original'";
+    let value: String = sqlx::query_scalar(sql).fetch_one(&mut conn).await?;
+    assert_eq!(value, "rewritten");
+
+    // the statement is cached under the original SQL
+    let value: String = sqlx::query_scalar(sql).fetch_one(&mut conn).await?;
+    assert_eq!(value, "rewritten");
+    assert_eq!(conn.cached_statements_size(), 1);
+
+    // unprepared, with several statements
+    let value: String = sqlx::raw_sql("SELECT 1; SELECT 'original'")
+        .fetch_all(&mut conn)
+        .await?[1]
+        .try_get(0)?;
+    assert_eq!(value, "rewritten");
+
+    // statements sent by sqlx itself still work
+    let mut tx = conn.begin().await?;
+    let value: String = sqlx::query_scalar(sql).fetch_one(&mut *tx).await?;
+    assert_eq!(value, "rewritten");
+    tx.commit().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_appends_dev_auto_limit() -> anyhow::Result<()> {
+    use std::str::FromStr;
+
+    let mut conn = SqliteConnectOptions::from_str(":memory:")?
+        .dev_auto_limit(Some(3))
+        .connect()
+        .await?;
+
+    let sql = "WITH RECURSIVE s(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM s WHERE n < 10) \
+               SELECT n FROM s";
+    let rows: Vec<i32> = sqlx::query_scalar(sql).fetch_all(&mut conn).await?;
+    assert_eq!(rows, [1, 2, 3]);
+
+    // an existing limit is kept
+    let sql = "SELECT value FROM json_each('[1,2,3,4,5,6]') LIMIT 5";
+    let rows: Vec<i32> = sqlx::query_scalar(sql).fetch_all(&mut conn).await?;
+    assert_eq!(rows.len(), 5);
+
+    Ok(())
+}
+
 #[cfg(sqlite_ipaddr)]
 #[sqlx_macros::test]
 async fn it_opens_with_extension() -> anyhow::Result<()> {
@@ -439,6 +759,37 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL COLLATE
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_passes_invalid_utf8_to_collations_lossily() -> anyhow::Result<()> {
+    use std::str::FromStr;
+
+    let options = SqliteConnectOptions::from_str(":memory:")?
+        .collation("by_len", |l, r| l.chars().count().cmp(&r.chars().count()));
+
+    // every connection of the pool gets the collation
+    let pool = SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect_with(options)
+        .await?;
+
+    let mut a = pool.acquire().await?;
+    let mut b = pool.acquire().await?;
+
+    for conn in [&mut a, &mut b] {
+        // `x'ffff'` is not UTF-8, and each byte becomes one `U+FFFD`
+        let values: Vec<String> = sqlx::query_scalar(
+            "SELECT hex(v) FROM (SELECT 'abc' AS v UNION ALL SELECT CAST(x'ffff' AS TEXT)) \
+             ORDER BY v COLLATE by_len",
+        )
+        .fetch_all(&mut **conn)
+        .await?;
+
+        assert_eq!(values, ["FFFF", "616263"]);
+    }
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_caches_statements() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -490,6 +841,38 @@ async fn it_caches_statements() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_counts_prepared_statements_and_cache_hits() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let prepared = conn.statements_prepared();
+    let hits = conn.statement_cache_hits();
+
+    for i in 0..3 {
+        sqlx::query("SELECT ? AS val")
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+    }
+
+    assert_eq!(prepared + 1, conn.statements_prepared());
+    assert_eq!(hits + 2, conn.statement_cache_hits());
+
+    // non-persistent queries are prepared every time
+    for i in 0..2 {
+        sqlx::query("SELECT ? AS val")
+            .bind(i)
+            .persistent(false)
+            .fetch_one(&mut conn)
+            .await?;
+    }
+
+    assert_eq!(prepared + 3, conn.statements_prepared());
+    assert_eq!(hits + 2, conn.statement_cache_hits());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     let mut conn = new::<Sqlite>().await?;
@@ -560,6 +943,67 @@ async fn it_resets_prepared_statement_after_fetch_many() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_vacuums_incrementally_on_release() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteAutoVacuum;
+    use std::str::FromStr;
+
+    let options =
+        SqliteConnectOptions::from_str(":memory:")?.auto_vacuum(SqliteAutoVacuum::Incremental);
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .incremental_vacuum_on_release(2, 0)
+        .connect_with(options)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    conn.execute(
+        "CREATE TABLE vacuumed (data BLOB); \
+         INSERT INTO vacuumed SELECT zeroblob(100000) FROM (SELECT 1 UNION SELECT 2); \
+         DELETE FROM vacuumed;",
+    )
+    .await?;
+
+    let freelist_count = "PRAGMA freelist_count";
+    let free: i64 = sqlx::query_scalar(freelist_count)
+        .fetch_one(&mut *conn)
+        .await?;
+    assert!(free > 0);
+    drop(conn);
+
+    // the second release runs the vacuum
+    pool.acquire().await?.ping().await?;
+    let free: i64 = sqlx::query_scalar(freelist_count).fetch_one(&pool).await?;
+    assert_eq!(free, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_the_warmup_query_on_connect() -> anyhow::Result<()> {
+    use std::str::FromStr;
+
+    let mut conn = SqliteConnectOptions::from_str(":memory:")?
+        .warmup_query("CREATE TEMP TABLE warm (x INTEGER); SELECT 1")
+        .connect()
+        .await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM warm")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    let err = SqliteConnectOptions::from_str(":memory:")?
+        .warmup_query("SELECT * FROM not_a_table")
+        .connect()
+        .await
+        .unwrap_err();
+    assert!(matches!(err, sqlx::Error::Database(_)), "{err:?}");
+
+    Ok(())
+}
+
 // https://github.com/launchbadge/sqlx/issues/1300
 #[sqlx_macros::test]
 async fn concurrent_resets_dont_segfault() {
@@ -699,7 +1143,7 @@ async fn concurrent_read_and_write() {
 
         async move {
             for i in 0u32..n {
-                sqlx::query("SELECT v FROM kv")
+                sqlx::query("SELECT v FROM kv WHERE k = ?")
                     .bind(i)
                     .fetch_all(&mut *conn)
                     .await
@@ -960,3 +1404,232 @@ async fn test_multiple_set_rollback_hook_calls_drop_old_handler() -> anyhow::Res
     assert_eq!(1, Arc::strong_count(&ref_counted_object));
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_resets_the_session() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    // Left open without going through `begin()`.
+    conn.execute("BEGIN").await?;
+    conn.execute("CREATE TEMPORARY TABLE reset_me (id INT)")
+        .await?;
+    let _: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(conn.cached_statements_size(), 1);
+
+    conn.reset().await?;
+
+    assert_eq!(conn.cached_statements_size(), 0);
+
+    // The temporary table was rolled back with the transaction.
+    let temp_tables: i32 =
+        sqlx::query_scalar("SELECT count(*) FROM temp.sqlite_master WHERE name = 'reset_me'")
+            .fetch_one(&mut conn)
+            .await?;
+    assert_eq!(temp_tables, 0);
+
+    // A new transaction can be started, so the old one is gone.
+    conn.begin().await?.rollback().await?;
+
+    // Resetting outside of a transaction is fine too.
+    conn.reset().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rolls_back_connections_on_return() -> anyhow::Result<()> {
+    use sqlx::pool::ResetOnReturn;
+
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .reset_on_return(ResetOnReturn::Rollback)
+        .connect(":memory:")
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    // Left open without going through `begin()`.
+    conn.execute("BEGIN").await?;
+    conn.execute("CREATE TABLE rolled_back (id INT)").await?;
+    drop(conn);
+
+    // A new transaction can be started, so the old one is gone.
+    let mut conn = pool.acquire().await?;
+    conn.begin().await?.rollback().await?;
+
+    let tables: i32 =
+        sqlx::query_scalar("SELECT count(*) FROM sqlite_master WHERE name = 'rolled_back'")
+            .fetch_one(&mut *conn)
+            .await?;
+    assert_eq!(tables, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checks_the_number_of_binds() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let res = sqlx::query("SELECT ?, ?")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::BindCountMismatch {
+            expected: 2,
+            provided: 1
+        })
+    ));
+
+    let res = sqlx::query("SELECT ?2")
+        .bind(1_i32)
+        .bind(2_i32)
+        .bind(3_i32)
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(
+        res,
+        Err(sqlx::Error::BindCountMismatch {
+            expected: 2,
+            provided: 3
+        })
+    ));
+
+    // arguments are shared by all of the statements
+    let value: i32 = sqlx::query_scalar("SELECT ?1; SELECT ?2 + ?1")
+        .bind(1_i32)
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_retries_busy_statements() -> anyhow::Result<()> {
+    use sqlx::sqlite::SqliteJournalMode;
+    use std::time::Duration;
+
+    let dir = tempfile::TempDir::new()?;
+
+    // readers are only blocked by writers with a rollback journal
+    let options = SqliteConnectOptions::new()
+        .filename(dir.path().join("busy.db"))
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Delete)
+        .busy_timeout(Duration::ZERO);
+
+    let mut writer = options.connect().await?;
+    writer.execute("CREATE TABLE t (x INTEGER)").await?;
+
+    let mut reader = options.connect().await?;
+    let mut retrying_reader = options
+        .busy_retry(20, Duration::from_millis(10))
+        .connect()
+        .await?;
+
+    writer.execute("BEGIN EXCLUSIVE").await?;
+
+    let err = reader.execute("SELECT * FROM t").await.unwrap_err();
+    assert!(err.to_string().contains("database is locked"), "{err}");
+
+    let read = sqlx::query("SELECT * FROM t").fetch_all(&mut retrying_reader);
+    let commit = async {
+        sqlx_core::rt::sleep(Duration::from_millis(50)).await;
+        writer.execute("COMMIT").await
+    };
+
+    let (rows, commit) = futures::join!(read, commit);
+    commit?;
+    assert!(rows?.is_empty());
+
+    // statements in a transaction are never retried
+    writer.execute("BEGIN EXCLUSIVE").await?;
+    retrying_reader.execute("BEGIN").await?;
+
+    let err = retrying_reader.execute("SELECT * FROM t").await.unwrap_err();
+    assert!(err.to_string().contains("database is locked"), "{err}");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_pushes_on_conflict() -> anyhow::Result<()> {
+    use sqlx::query_builder::{ConflictClause, ConflictClauseError, QueryBuilder};
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE counters (name TEXT PRIMARY KEY, count INTEGER NOT NULL); \
+         INSERT INTO counters VALUES ('hits', 1)",
+    )
+    .await?;
+
+    let mut qb: QueryBuilder<'_, Sqlite> =
+        QueryBuilder::new("INSERT INTO counters (name, count) VALUES ('hits', 5)");
+    qb.push_on_conflict(
+        &ConflictClause::columns(["name"]).set("count", "counters.count + EXCLUDED.count"),
+    )?;
+    qb.build().execute(&mut conn).await?;
+
+    let count: i32 = sqlx::query_scalar("SELECT count FROM counters")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 6);
+
+    let mut qb: QueryBuilder<'_, Sqlite> = QueryBuilder::new("INSERT INTO counters");
+    assert!(matches!(
+        qb.push_on_conflict(&ConflictClause::constraint("counters_pkey")),
+        Err(ConflictClauseError::Unsupported { database, .. }) if database == "SQLite"
+    ));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_logs_parameters_formatted_by_redact_parameters() -> anyhow::Result<()> {
+    sqlx_test::capture::install();
+
+    let options = std::env::var("DATABASE_URL")?
+        .parse::<SqliteConnectOptions>()?
+        .redact_parameters(|i, value| match i {
+            1 => format!("{value:?}"),
+            _ => "<redacted>".into(),
+        });
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+
+    sqlx::query("SELECT ?1 AS redacted_log_1, ?2")
+        .bind(42_i32)
+        .bind("hunter2")
+        .execute(&mut conn)
+        .await?;
+
+    // parameters are redacted by default
+    let mut conn = new::<Sqlite>().await?;
+
+    sqlx::query("SELECT ?1 AS redacted_log_2")
+        .bind("hunter2")
+        .execute(&mut conn)
+        .await?;
+
+    let parameters = |marker: &str| {
+        sqlx_test::capture::events(|event| event.field("summary").unwrap_or("").contains(marker))
+            .iter()
+            .map(|event| event.field("db.parameters").unwrap_or("").to_owned())
+            .collect::<Vec<_>>()
+    };
+
+    let logged = parameters("redacted_log_1");
+    assert_eq!(logged.len(), 1);
+    assert!(logged[0].contains("42"), "{logged:?}");
+    assert!(logged[0].ends_with(", <redacted>"), "{logged:?}");
+    assert!(!logged[0].contains("hunter2"), "{logged:?}");
+
+    assert_eq!(parameters("redacted_log_2"), [""]);
+
+    Ok(())
+}
@@ -97,6 +97,7 @@ mod json_tests {
 #[cfg(feature = "chrono")]
 mod chrono {
     use super::*;
+    use sqlx::sqlite::types::{JulianDay, UnixEpoch};
     use sqlx::types::chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
 
     test_type!(chrono_naive_date_time<NaiveDateTime>(Sqlite, "SELECT datetime({0}) is datetime(?), {0}, ?",
@@ -110,11 +111,57 @@ mod chrono {
     test_type!(chrono_date_time_fixed_offset<DateTime::<FixedOffset>>(Sqlite, "SELECT datetime({0}) is datetime(?), {0}, ?",
         "'2016-11-08T03:50:23-05:00'" == DateTime::<Utc>::from(FixedOffset::west_opt(5 * 3600).unwrap().with_ymd_and_hms(2016, 11, 08, 3, 50, 23).unwrap())
     ));
+
+    test_type!(chrono_unix_epoch<UnixEpoch<DateTime::<Utc>>>(Sqlite, "SELECT {0} is ?, {0}, ?",
+        "1479837600" == UnixEpoch(Utc.with_ymd_and_hms(2016, 11, 22, 18, 0, 0).unwrap()),
+        "unixepoch('1996-12-20T00:39:57')" == UnixEpoch(Utc.with_ymd_and_hms(1996, 12, 20, 0, 39, 57).unwrap()),
+        "-1" == UnixEpoch(Utc.with_ymd_and_hms(1969, 12, 31, 23, 59, 59).unwrap())
+    ));
+
+    test_type!(chrono_unix_epoch_naive<UnixEpoch<NaiveDateTime>>(Sqlite, "SELECT {0} is ?, {0}, ?",
+        "1546405820" == UnixEpoch(NaiveDate::from_ymd_opt(2019, 1, 2).unwrap().and_hms_opt(5, 10, 20).unwrap())
+    ));
+
+    #[sqlx_macros::test]
+    async fn test_unix_epoch_rejects_other_storage_classes() -> anyhow::Result<()> {
+        let mut conn = new::<Sqlite>().await?;
+
+        let res: Result<UnixEpoch<DateTime<Utc>>, _> =
+            sqlx::query_scalar("SELECT '2016-11-22 18:00:00'")
+                .fetch_one(&mut conn)
+                .await;
+
+        assert!(
+            matches!(res, Err(sqlx::Error::ColumnDecode { .. })),
+            "{res:?}"
+        );
+
+        Ok(())
+    }
+
+    #[sqlx_macros::test]
+    async fn test_julian_day() -> anyhow::Result<()> {
+        let mut conn = new::<Sqlite>().await?;
+
+        let dt = Utc.with_ymd_and_hms(2016, 11, 22, 18, 0, 0).unwrap();
+
+        let (is_same, decoded): (bool, JulianDay<DateTime<Utc>>) =
+            sqlx::query_as("SELECT abs(julianday('2016-11-22 18:00:00') - ?) < 1e-8, julianday('2016-11-22 18:00:00')")
+                .bind(JulianDay(dt))
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert!(is_same);
+        assert!((*decoded - dt).num_milliseconds().abs() < 1, "{decoded:?}");
+
+        Ok(())
+    }
 }
 
 #[cfg(feature = "time")]
 mod time_tests {
     use super::*;
+    use sqlx::sqlite::types::UnixEpoch;
     use sqlx::types::time::{Date, OffsetDateTime, PrimitiveDateTime, Time};
     use time::macros::{date, datetime, time};
 
@@ -145,6 +192,13 @@ mod time_tests {
         "'2008-02-21T18:59Z'" == datetime!(2008 - 2 - 21 18:59:00),
     ));
 
+    test_type!(time_unix_epoch<UnixEpoch<OffsetDateTime>>(
+        Sqlite,
+        "SELECT {0} is ?, {0}, ?",
+        "1447891299" == UnixEpoch(datetime!(2015 - 11 - 19 1:01:39 +1)),
+        "unixepoch('2014-10-18 00:00:38')" == UnixEpoch(datetime!(2014 - 10 - 18 00:00:38 +0)),
+    ));
+
     test_type!(time_date<Date>(
         Sqlite,
         "SELECT date({0}) is date(?), {0}, ?",
@@ -160,6 +214,50 @@ mod time_tests {
     ));
 }
 
+mod blob {
+    use super::*;
+    use sqlx::sqlite::types::{SqliteBlob, SqliteBlobCodec};
+    use sqlx_core::error::BoxDynError;
+
+    // a codec for a pair of integers, stored as two little-endian `u32`s
+    struct LePair;
+
+    type Pair = SqliteBlob<(u32, u32), LePair>;
+
+    impl SqliteBlobCodec<(u32, u32)> for LePair {
+        fn encode(value: &(u32, u32)) -> Result<Vec<u8>, BoxDynError> {
+            Ok([value.0.to_le_bytes(), value.1.to_le_bytes()].concat())
+        }
+
+        fn decode(blob: &[u8]) -> Result<(u32, u32), BoxDynError> {
+            let bytes: [u8; 8] = blob.try_into()?;
+            let (a, b) = bytes.split_at(4);
+
+            Ok((
+                u32::from_le_bytes(a.try_into()?),
+                u32::from_le_bytes(b.try_into()?),
+            ))
+        }
+    }
+
+    test_type!(blob_codec<Pair>(Sqlite,
+        "X'0100000002000000'" == Pair::new((1, 2)),
+        "X'FFFFFFFF00000000'" == Pair::new((u32::MAX, 0))
+    ));
+
+    #[sqlx_macros::test]
+    async fn it_rejects_invalid_blobs() -> anyhow::Result<()> {
+        let mut conn = new::<Sqlite>().await?;
+
+        for sql in ["SELECT X'01'", "SELECT 'not a blob'"] {
+            let res: Result<Pair, _> = sqlx::query_scalar(sql).fetch_one(&mut conn).await;
+            assert!(res.is_err(), "{sql}");
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(feature = "bstr")]
 mod bstr {
     use super::*;